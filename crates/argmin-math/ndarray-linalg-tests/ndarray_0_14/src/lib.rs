@@ -1,6 +1,12 @@
 mod add {
     include!(concat!(env!("CARGO_MANIFEST_DIR"), "/../../ndarray-tests-src/add.rs"));
 }
+mod cholesky {
+    include!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../ndarray-tests-src/cholesky.rs"
+    ));
+}
 mod conj {
     include!(concat!(env!("CARGO_MANIFEST_DIR"), "/../../ndarray-tests-src/conj.rs"));
 }
@@ -40,6 +46,12 @@ mod scaledsub {
 mod signum {
     include!(concat!(env!("CARGO_MANIFEST_DIR"), "/../../ndarray-tests-src/signum.rs"));
 }
+mod sqrt {
+    include!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../ndarray-tests-src/sqrt.rs"
+    ));
+}
 mod sub {
     include!(concat!(env!("CARGO_MANIFEST_DIR"), "/../../ndarray-tests-src/sub.rs"));
 }
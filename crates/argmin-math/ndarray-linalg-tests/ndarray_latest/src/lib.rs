@@ -4,6 +4,12 @@ mod add {
         "/../../ndarray-tests-src/add.rs"
     ));
 }
+mod cholesky {
+    include!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../ndarray-tests-src/cholesky.rs"
+    ));
+}
 mod conj {
     include!(concat!(
         env!("CARGO_MANIFEST_DIR"),
@@ -82,6 +88,12 @@ mod signum {
         "/../../ndarray-tests-src/signum.rs"
     ));
 }
+mod sqrt {
+    include!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../ndarray-tests-src/sqrt.rs"
+    ));
+}
 mod sub {
     include!(concat!(
         env!("CARGO_MANIFEST_DIR"),
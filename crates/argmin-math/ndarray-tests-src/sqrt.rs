@@ -0,0 +1,56 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use approx::assert_relative_eq;
+    use argmin_math::ArgminSqrt;
+    use ndarray::array;
+    use ndarray::{Array1, Array2};
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_sqrt_array1_ $t>]() {
+                    let x = Array1::from(vec![4 as $t, 9 as $t, 2 as $t]);
+                    let y = Array1::from(vec![2 as $t, 3 as $t, std::$t::consts::SQRT_2]);
+                    let res = <Array1<$t> as ArgminSqrt>::sqrt(x);
+                    for i in 0..3 {
+                        assert_relative_eq!(y[i], res[i], epsilon = std::$t::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_sqrt_array2_ $t>]() {
+                    let x = array![
+                        [4 as $t, 9 as $t, 16 as $t],
+                        [1 as $t, 0 as $t, 25 as $t]
+                    ];
+                    let y = array![
+                        [2 as $t, 3 as $t, 4 as $t],
+                        [1 as $t, 0 as $t, 5 as $t]
+                    ];
+                    let res = <Array2<$t> as ArgminSqrt>::sqrt(x);
+                    for j in 0..2 {
+                        for i in 0..3 {
+                            assert_relative_eq!(y[(j, i)], res[(j, i)], epsilon = std::$t::EPSILON);
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -322,6 +322,18 @@ pub trait ArgminInv<T> {
     fn inv(&self) -> Result<T, Error>;
 }
 
+/// Compute the Cholesky factorization (`T`) of a symmetric positive definite matrix `self`
+pub trait ArgminCholesky<T> {
+    /// Compute the lower triangular factor `L` with `self = L * L^T`
+    fn cholesky(&self) -> Result<T, Error>;
+}
+
+/// Solve a linear system with a Cholesky factor `self`
+pub trait ArgminCholeskySolve<T, U> {
+    /// Solve `L * L^T * x = b` for `x` (`U`), where `self` is the lower triangular factor `L`
+    fn cholesky_solve(&self, b: &T) -> Result<U, Error>;
+}
+
 /// Create a random number
 pub trait ArgminRandom {
     /// Get a random element between min and max,
@@ -341,3 +353,27 @@ pub trait ArgminSignum {
     /// Returns a number that represents the sign of `self`.
     fn signum(self) -> Self;
 }
+
+/// Elementwise square root of `self`
+pub trait ArgminSqrt {
+    /// Returns the elementwise square root of `self`
+    fn sqrt(self) -> Self;
+}
+
+/// Read access to a single element of `self` at index `I`
+pub trait ArgminGet<I, U> {
+    /// Returns the element at index `idx`
+    fn get(&self, idx: I) -> U;
+}
+
+/// Write access to a single element of `self` at index `I`
+pub trait ArgminSet<I, U> {
+    /// Sets the element at index `idx` to `value`
+    fn set(&mut self, idx: I, value: U);
+}
+
+/// Returns the size (`U`) of `self`
+pub trait ArgminSize<U> {
+    /// Returns the size (`U`) of `self`
+    fn shape(&self) -> U;
+}
@@ -0,0 +1,122 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::{ArgminCholesky, ArgminCholeskySolve, Error};
+use nalgebra::{
+    base::{allocator::Allocator, dimension::Dim, storage::Storage},
+    Cholesky, DefaultAllocator, OMatrix, OVector, RealField, SquareMatrix,
+};
+use std::fmt;
+
+#[derive(Debug, thiserror::Error, PartialEq)]
+struct CholeskyError;
+
+impl fmt::Display for CholeskyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Matrix is not positive definite")
+    }
+}
+
+impl<N, D, S> ArgminCholesky<OMatrix<N, D, D>> for SquareMatrix<N, D, S>
+where
+    N: RealField,
+    D: Dim,
+    S: Storage<N, D, D>,
+    DefaultAllocator: Allocator<N, D, D>,
+{
+    #[inline]
+    fn cholesky(&self) -> Result<OMatrix<N, D, D>, Error> {
+        match Cholesky::new(self.clone_owned()) {
+            Some(c) => Ok(c.unpack()),
+            None => Err(CholeskyError {}.into()),
+        }
+    }
+}
+
+impl<N, D, S> ArgminCholeskySolve<OVector<N, D>, OVector<N, D>> for SquareMatrix<N, D, S>
+where
+    N: RealField,
+    D: Dim,
+    S: Storage<N, D, D>,
+    DefaultAllocator: Allocator<N, D, D> + Allocator<N, D>,
+{
+    #[inline]
+    fn cholesky_solve(&self, b: &OVector<N, D>) -> Result<OVector<N, D>, Error> {
+        self.solve_lower_triangular(b)
+            .and_then(|y| self.tr_solve_lower_triangular(&y))
+            .ok_or_else(|| CholeskyError {}.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use nalgebra::{Matrix2, Matrix3, Vector3};
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_cholesky_ $t>]() {
+                    let a = Matrix3::new(
+                        4 as $t, 2 as $t, 2 as $t,
+                        2 as $t, 5 as $t, 3 as $t,
+                        2 as $t, 3 as $t, 6 as $t,
+                    );
+                    let target = Matrix3::new(
+                        2 as $t, 0 as $t, 0 as $t,
+                        1 as $t, 2 as $t, 0 as $t,
+                        1 as $t, 1 as $t, 2 as $t,
+                    );
+                    let res = <Matrix3<$t> as ArgminCholesky<Matrix3<$t>>>::cholesky(&a).unwrap();
+                    for i in 0..3 {
+                        for j in 0..3 {
+                            assert_relative_eq!(res[(i, j)], target[(i, j)], epsilon = std::$t::EPSILON);
+                        }
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_cholesky_solve_ $t>]() {
+                    let l = Matrix3::new(
+                        2 as $t, 0 as $t, 0 as $t,
+                        1 as $t, 2 as $t, 0 as $t,
+                        1 as $t, 1 as $t, 2 as $t,
+                    );
+                    // L * L^T * (1, -1, 2) = (6, 3, 11)
+                    let b = Vector3::new(6 as $t, 3 as $t, 11 as $t);
+                    let target = Vector3::new(1 as $t, -1 as $t, 2 as $t);
+                    let res = <Matrix3<$t> as ArgminCholeskySolve<Vector3<$t>, Vector3<$t>>>::cholesky_solve(&l, &b).unwrap();
+                    for i in 0..3 {
+                        assert_relative_eq!(res[i], target[i], epsilon = std::$t::EPSILON.sqrt());
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_cholesky_error_ $t>]() {
+                    let a = Matrix2::new(
+                        1 as $t, 2 as $t,
+                        2 as $t, 1 as $t,
+                    );
+                    let err = <Matrix2<$t> as ArgminCholesky<Matrix2<$t>>>::cholesky(&a).unwrap_err().downcast::<CholeskyError>().unwrap();
+                    assert_eq!(err, CholeskyError {});
+                    assert_eq!(format!("{}", err), "Matrix is not positive definite");
+                    assert_eq!(format!("{:?}", err), "CholeskyError");
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
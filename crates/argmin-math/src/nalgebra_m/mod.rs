@@ -8,10 +8,12 @@
 #![allow(unused_imports)]
 
 mod add;
+mod cholesky;
 mod conj;
 mod div;
 mod dot;
 mod eye;
+mod get;
 mod inv;
 mod l1norm;
 mod l2norm;
@@ -20,16 +22,21 @@ mod mul;
 mod random;
 mod scaledadd;
 mod scaledsub;
+mod set;
 mod signum;
+mod size;
+mod sqrt;
 mod sub;
 mod transpose;
 mod zero;
 
 pub use add::*;
+pub use cholesky::*;
 pub use conj::*;
 pub use div::*;
 pub use dot::*;
 pub use eye::*;
+pub use get::*;
 pub use inv::*;
 pub use l1norm::*;
 pub use l2norm::*;
@@ -38,7 +45,10 @@ pub use mul::*;
 pub use random::*;
 pub use scaledadd::*;
 pub use scaledsub::*;
+pub use set::*;
 pub use signum::*;
+pub use size::*;
+pub use sqrt::*;
 pub use sub::*;
 pub use transpose::*;
 pub use zero::*;
@@ -0,0 +1,81 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminSize;
+
+use nalgebra::{
+    base::{allocator::Allocator, dimension::Dim, Scalar},
+    DefaultAllocator, OMatrix, U1,
+};
+
+impl<N, R> ArgminSize<usize> for OMatrix<N, R, U1>
+where
+    N: Scalar,
+    R: Dim,
+    DefaultAllocator: Allocator<N, R, U1>,
+{
+    #[inline]
+    fn shape(&self) -> usize {
+        self.nrows()
+    }
+}
+
+impl<N, R, C> ArgminSize<(usize, usize)> for OMatrix<N, R, C>
+where
+    N: Scalar,
+    R: Dim,
+    C: Dim,
+    DefaultAllocator: Allocator<N, R, C>,
+{
+    #[inline]
+    fn shape(&self) -> (usize, usize) {
+        self.shape()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::{DVector, Matrix2x3, Vector3};
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_size_ $t>]() {
+                    let a = Vector3::new(1 as $t, 4 as $t, 8 as $t);
+                    assert_eq!(<Vector3<$t> as ArgminSize<usize>>::shape(&a), 3);
+                    let b = DVector::from_vec(vec![1 as $t, 4 as $t]);
+                    assert_eq!(<DVector<$t> as ArgminSize<usize>>::shape(&b), 2);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_size_mat_ $t>]() {
+                    let a = Matrix2x3::new(
+                        1 as $t, 4 as $t, 8 as $t,
+                        2 as $t, 5 as $t, 9 as $t
+                    );
+                    assert_eq!(<Matrix2x3<$t> as ArgminSize<(usize, usize)>>::shape(&a), (2, 3));
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
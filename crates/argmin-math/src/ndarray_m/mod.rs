@@ -8,10 +8,17 @@
 #![allow(unused_imports)]
 
 mod add;
+#[cfg(any(
+    feature = "ndarray-linalg_0_12",
+    feature = "ndarray-linalg_0_13",
+    feature = "ndarray-linalg_0_16"
+))]
+mod cholesky;
 mod conj;
 mod div;
 mod dot;
 mod eye;
+mod get;
 #[cfg(any(
     feature = "ndarray-linalg_0_12",
     feature = "ndarray-linalg_0_13",
@@ -25,16 +32,26 @@ mod mul;
 mod random;
 mod scaledadd;
 mod scaledsub;
+mod set;
 mod signum;
+mod size;
+mod sqrt;
 mod sub;
 mod transpose;
 mod zero;
 
 pub use add::*;
+#[cfg(any(
+    feature = "ndarray-linalg_0_12",
+    feature = "ndarray-linalg_0_13",
+    feature = "ndarray-linalg_0_16"
+))]
+pub use cholesky::*;
 pub use conj::*;
 pub use div::*;
 pub use dot::*;
 pub use eye::*;
+pub use get::*;
 #[cfg(any(
     feature = "ndarray-linalg_0_12",
     feature = "ndarray-linalg_0_13",
@@ -47,7 +64,10 @@ pub use minmax::*;
 pub use mul::*;
 pub use scaledadd::*;
 pub use scaledsub::*;
+pub use set::*;
 pub use signum::*;
+pub use size::*;
+pub use sqrt::*;
 pub use sub::*;
 pub use transpose::*;
 pub use zero::*;
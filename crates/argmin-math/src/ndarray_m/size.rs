@@ -0,0 +1,64 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminSize;
+use ndarray::{Array1, Array2};
+
+impl<T> ArgminSize<usize> for Array1<T> {
+    #[inline]
+    fn shape(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> ArgminSize<(usize, usize)> for Array2<T> {
+    #[inline]
+    fn shape(&self) -> (usize, usize) {
+        self.dim()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_size_ $t>]() {
+                    let a = array![1 as $t, 4 as $t, 8 as $t];
+                    assert_eq!(<Array1<$t> as ArgminSize<usize>>::shape(&a), 3);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_size_2d_ $t>]() {
+                    let a = array![
+                        [1 as $t, 4 as $t, 8 as $t],
+                        [2 as $t, 5 as $t, 9 as $t]
+                    ];
+                    assert_eq!(<Array2<$t> as ArgminSize<(usize, usize)>>::shape(&a), (2, 3));
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -0,0 +1,40 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminSqrt;
+use ndarray::{Array1, Array2};
+
+macro_rules! make_sqrt {
+    ($t:ty) => {
+        impl ArgminSqrt for Array1<$t> {
+            #[inline]
+            fn sqrt(self) -> Array1<$t> {
+                self.mapv_into(|a| a.sqrt())
+            }
+        }
+
+        impl ArgminSqrt for Array2<$t> {
+            #[inline]
+            fn sqrt(self) -> Array2<$t> {
+                self.mapv_into(|a| a.sqrt())
+            }
+        }
+    };
+}
+
+make_sqrt!(f32);
+make_sqrt!(f64);
+
+// All code that does not depend on a linked ndarray-linalg backend can still be tested as normal.
+// To avoid dublicating tests and to allow convenient testing of functionality that does not need ndarray-linalg the tests are still included here.
+// The tests expect the name for the crate containing the tested functions to be argmin_math
+#[cfg(test)]
+use crate as argmin_math;
+include!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/ndarray-tests-src/sqrt.rs"
+));
@@ -0,0 +1,142 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::{ArgminCholesky, ArgminCholeskySolve, Error};
+use std::fmt;
+
+#[derive(Debug, thiserror::Error, PartialEq)]
+struct CholeskyError;
+
+impl fmt::Display for CholeskyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Matrix is not positive definite")
+    }
+}
+
+macro_rules! make_cholesky {
+    ($t:ty) => {
+        impl ArgminCholesky<Vec<Vec<$t>>> for Vec<Vec<$t>> {
+            #[inline]
+            fn cholesky(&self) -> Result<Vec<Vec<$t>>, Error> {
+                let n = self.len();
+                let mut l = vec![vec![0 as $t; n]; n];
+                for j in 0..n {
+                    let d = self[j][j] - l[j][..j].iter().map(|v| v * v).sum::<$t>();
+                    if d <= 0 as $t || d.is_nan() {
+                        return Err(CholeskyError {}.into());
+                    }
+                    let d = d.sqrt();
+                    l[j][j] = d;
+                    for i in (j + 1)..n {
+                        let s = l[i][..j]
+                            .iter()
+                            .zip(l[j][..j].iter())
+                            .map(|(a, b)| a * b)
+                            .sum::<$t>();
+                        l[i][j] = (self[i][j] - s) / d;
+                    }
+                }
+                Ok(l)
+            }
+        }
+
+        impl ArgminCholeskySolve<Vec<$t>, Vec<$t>> for Vec<Vec<$t>> {
+            #[inline]
+            fn cholesky_solve(&self, b: &Vec<$t>) -> Result<Vec<$t>, Error> {
+                let n = self.len();
+                assert_eq!(n, b.len());
+                let mut x = b.clone();
+                // Forward substitution with `L`
+                for i in 0..n {
+                    let s = self[i][..i]
+                        .iter()
+                        .zip(x[..i].iter())
+                        .map(|(a, b)| a * b)
+                        .sum::<$t>();
+                    x[i] = (x[i] - s) / self[i][i];
+                }
+                // Backward substitution with `L^T`
+                for i in (0..n).rev() {
+                    let s = ((i + 1)..n).map(|k| self[k][i] * x[k]).sum::<$t>();
+                    x[i] = (x[i] - s) / self[i][i];
+                }
+                Ok(x)
+            }
+        }
+    };
+}
+
+make_cholesky!(f32);
+make_cholesky!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_cholesky_ $t>]() {
+                    let a = vec![
+                        vec![4 as $t, 2 as $t, 2 as $t],
+                        vec![2 as $t, 5 as $t, 3 as $t],
+                        vec![2 as $t, 3 as $t, 6 as $t],
+                    ];
+                    let target = vec![
+                        vec![2 as $t, 0 as $t, 0 as $t],
+                        vec![1 as $t, 2 as $t, 0 as $t],
+                        vec![1 as $t, 1 as $t, 2 as $t],
+                    ];
+                    let res = <Vec<Vec<$t>> as ArgminCholesky<Vec<Vec<$t>>>>::cholesky(&a).unwrap();
+                    for i in 0..3 {
+                        for j in 0..3 {
+                            assert_relative_eq!(res[i][j], target[i][j], epsilon = std::$t::EPSILON);
+                        }
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_cholesky_solve_ $t>]() {
+                    let l = vec![
+                        vec![2 as $t, 0 as $t, 0 as $t],
+                        vec![1 as $t, 2 as $t, 0 as $t],
+                        vec![1 as $t, 1 as $t, 2 as $t],
+                    ];
+                    // L * L^T * (1, -1, 2) = (6, 3, 11)
+                    let b = vec![6 as $t, 3 as $t, 11 as $t];
+                    let target = vec![1 as $t, -1 as $t, 2 as $t];
+                    let res = <Vec<Vec<$t>> as ArgminCholeskySolve<Vec<$t>, Vec<$t>>>::cholesky_solve(&l, &b).unwrap();
+                    for i in 0..3 {
+                        assert_relative_eq!(res[i], target[i], epsilon = std::$t::EPSILON.sqrt());
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_cholesky_error_ $t>]() {
+                    let a = vec![
+                        vec![1 as $t, 2 as $t],
+                        vec![2 as $t, 1 as $t],
+                    ];
+                    let err = <Vec<Vec<$t>> as ArgminCholesky<Vec<Vec<$t>>>>::cholesky(&a).unwrap_err().downcast::<CholeskyError>().unwrap();
+                    assert_eq!(err, CholeskyError {});
+                    assert_eq!(format!("{}", err), "Matrix is not positive definite");
+                    assert_eq!(format!("{:?}", err), "CholeskyError");
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
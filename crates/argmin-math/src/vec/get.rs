@@ -0,0 +1,91 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminGet;
+
+impl<T> ArgminGet<usize, T> for Vec<T>
+where
+    T: Clone,
+{
+    #[inline]
+    fn get(&self, idx: usize) -> T {
+        self[idx].clone()
+    }
+}
+
+impl<T> ArgminGet<(usize, usize), T> for Vec<Vec<T>>
+where
+    T: Clone,
+{
+    #[inline]
+    fn get(&self, (row, col): (usize, usize)) -> T {
+        self[row][col].clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_get_vec_ $t>]() {
+                    let a = vec![1 as $t, 4 as $t, 8 as $t];
+                    assert_relative_eq!(
+                        <Vec<$t> as ArgminGet<usize, $t>>::get(&a, 0) as f64,
+                        1 as f64,
+                        epsilon = f64::EPSILON
+                    );
+                    assert_relative_eq!(
+                        <Vec<$t> as ArgminGet<usize, $t>>::get(&a, 1) as f64,
+                        4 as f64,
+                        epsilon = f64::EPSILON
+                    );
+                    assert_relative_eq!(
+                        <Vec<$t> as ArgminGet<usize, $t>>::get(&a, 2) as f64,
+                        8 as f64,
+                        epsilon = f64::EPSILON
+                    );
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_get_vec_vec_ $t>]() {
+                    let a = vec![
+                        vec![1 as $t, 4 as $t, 8 as $t],
+                        vec![2 as $t, 5 as $t, 9 as $t]
+                    ];
+                    for i in 0..2 {
+                        for j in 0..3 {
+                            assert_relative_eq!(
+                                <Vec<Vec<$t>> as ArgminGet<(usize, usize), $t>>::get(&a, (i, j)) as f64,
+                                a[i][j] as f64,
+                                epsilon = f64::EPSILON
+                            );
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
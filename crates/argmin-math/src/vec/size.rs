@@ -0,0 +1,67 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminSize;
+
+impl<T> ArgminSize<usize> for Vec<T> {
+    #[inline]
+    fn shape(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> ArgminSize<(usize, usize)> for Vec<Vec<T>> {
+    #[inline]
+    fn shape(&self) -> (usize, usize) {
+        let ncols = self.first().map(|row| row.len()).unwrap_or(0);
+        (self.len(), ncols)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_size_vec_ $t>]() {
+                    let a = vec![1 as $t, 4 as $t, 8 as $t];
+                    assert_eq!(<Vec<$t> as ArgminSize<usize>>::shape(&a), 3);
+                    let b: Vec<$t> = vec![];
+                    assert_eq!(<Vec<$t> as ArgminSize<usize>>::shape(&b), 0);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_size_vec_vec_ $t>]() {
+                    let a = vec![
+                        vec![1 as $t, 4 as $t, 8 as $t],
+                        vec![2 as $t, 5 as $t, 9 as $t]
+                    ];
+                    assert_eq!(<Vec<Vec<$t>> as ArgminSize<(usize, usize)>>::shape(&a), (2, 3));
+                    let b: Vec<Vec<$t>> = vec![];
+                    assert_eq!(<Vec<Vec<$t>> as ArgminSize<(usize, usize)>>::shape(&b), (0, 0));
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
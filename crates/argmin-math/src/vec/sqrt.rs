@@ -0,0 +1,81 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminSqrt;
+
+macro_rules! make_sqrt {
+    ($t:ty) => {
+        impl ArgminSqrt for Vec<$t> {
+            #[inline]
+            fn sqrt(mut self) -> Self {
+                for x in &mut self {
+                    *x = x.sqrt();
+                }
+                self
+            }
+        }
+
+        impl ArgminSqrt for Vec<Vec<$t>> {
+            #[inline]
+            fn sqrt(mut self) -> Self {
+                for x in self.iter_mut().flat_map(|row| row.iter_mut()) {
+                    *x = x.sqrt();
+                }
+                self
+            }
+        }
+    };
+}
+
+make_sqrt!(f32);
+make_sqrt!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_sqrt_vec_ $t>]() {
+                    let x = vec![4 as $t, 9 as $t, 2 as $t];
+                    let y = vec![2 as $t, 3 as $t, std::$t::consts::SQRT_2];
+                    let res = <Vec<$t> as ArgminSqrt>::sqrt(x);
+                    for i in 0..3 {
+                        assert_relative_eq!(y[i], res[i], epsilon = std::$t::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_sqrt_vec_vec_ $t>]() {
+                    let x = vec![
+                        vec![4 as $t, 9 as $t],
+                        vec![16 as $t, 0 as $t],
+                    ];
+                    let y = vec![
+                        vec![2 as $t, 3 as $t],
+                        vec![4 as $t, 0 as $t],
+                    ];
+                    let res = <Vec<Vec<$t>> as ArgminSqrt>::sqrt(x);
+                    for i in 0..2 {
+                        for j in 0..2 {
+                            assert_relative_eq!(y[i][j], res[i][j], epsilon = std::$t::EPSILON);
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
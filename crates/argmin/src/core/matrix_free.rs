@@ -0,0 +1,249 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, CostFunction, Error, Gradient, HessianVectorProduct};
+use argmin_math::{ArgminL2Norm, ArgminMul, ArgminScaledAdd, ArgminSub};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Marker type which configures a solver to compute products with the Hessian via
+/// [`HessianVectorProduct`] instead of evaluating the full [`Hessian`](`crate::core::Hessian`).
+///
+/// Solvers supporting this are switched over with their `matrix_free` method, for instance
+/// [`NewtonCG::matrix_free`](`crate::solver::newton::NewtonCG::matrix_free`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct MatrixFree;
+
+/// Approximates Hessian-vector products by finite differences of the gradient.
+///
+/// Wraps a problem implementing [`Gradient`] and implements [`HessianVectorProduct`] via central
+/// differences
+///
+/// ```text
+/// H(x) v ≈ (∇f(x + h v) - ∇f(x - h v)) / (2 h)
+/// ```
+///
+/// where `h = epsilon * (1 + ||x||) / ||v||`. Each product therefore costs two evaluations of the
+/// gradient. [`CostFunction`] and [`Gradient`] are forwarded to the wrapped problem.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{Error, FiniteDiffHessianVec, Gradient, HessianVectorProduct};
+/// struct Quartic {}
+///
+/// impl Gradient for Quartic {
+///     type Param = Vec<f64>;
+///     type Gradient = Vec<f64>;
+///
+///     fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+///         Ok(param.iter().map(|x| 4.0 * x.powi(3)).collect())
+///     }
+/// }
+///
+/// # fn main() -> Result<(), Error> {
+/// let problem: FiniteDiffHessianVec<_, f64> = FiniteDiffHessianVec::new(Quartic {});
+/// let hv = problem.hessian_vec(&vec![1.0, 2.0], &vec![1.0, 1.0])?;
+/// # assert!((hv[0] - 12.0).abs() < 1e-6);
+/// # assert!((hv[1] - 48.0).abs() < 1e-6);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct FiniteDiffHessianVec<O, F> {
+    /// Wrapped problem
+    problem: O,
+    /// Relative step size
+    epsilon: F,
+}
+
+impl<O, F> FiniteDiffHessianVec<O, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`FiniteDiffHessianVec`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::FiniteDiffHessianVec;
+    /// # let problem = ();
+    /// let fd: FiniteDiffHessianVec<_, f64> = FiniteDiffHessianVec::new(problem);
+    /// ```
+    pub fn new(problem: O) -> Self {
+        FiniteDiffHessianVec {
+            problem,
+            epsilon: F::epsilon().cbrt(),
+        }
+    }
+
+    /// Set the relative step size
+    ///
+    /// Must be larger than 0 and defaults to the cube root of machine epsilon, which balances
+    /// truncation and rounding errors of central differences.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, FiniteDiffHessianVec};
+    /// # fn main() -> Result<(), Error> {
+    /// # let problem = ();
+    /// let fd: FiniteDiffHessianVec<_, f64> = FiniteDiffHessianVec::new(problem).with_epsilon(1e-4)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_epsilon(mut self, epsilon: F) -> Result<Self, Error> {
+        if epsilon <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`FiniteDiffHessianVec`: epsilon must be > 0."
+            ));
+        }
+        self.epsilon = epsilon;
+        Ok(self)
+    }
+
+    /// Return the wrapped problem
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::FiniteDiffHessianVec;
+    /// # let problem = ();
+    /// let fd: FiniteDiffHessianVec<_, f64> = FiniteDiffHessianVec::new(problem);
+    /// let problem = fd.into_inner();
+    /// ```
+    pub fn into_inner(self) -> O {
+        self.problem
+    }
+}
+
+impl<O, F> CostFunction for FiniteDiffHessianVec<O, F>
+where
+    O: CostFunction,
+{
+    type Param = O::Param;
+    type Output = O::Output;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        self.problem.cost(param)
+    }
+}
+
+impl<O, F> Gradient for FiniteDiffHessianVec<O, F>
+where
+    O: Gradient,
+{
+    type Param = O::Param;
+    type Gradient = O::Gradient;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        self.problem.gradient(param)
+    }
+}
+
+impl<O, P, G, F> HessianVectorProduct for FiniteDiffHessianVec<O, F>
+where
+    O: Gradient<Param = P, Gradient = G>,
+    P: ArgminScaledAdd<P, F, P> + ArgminL2Norm<F>,
+    G: ArgminSub<G, G> + ArgminMul<F, G>,
+    F: ArgminFloat,
+{
+    type Param = P;
+    type Output = G;
+
+    fn hessian_vec(&self, param: &P, vec: &P) -> Result<G, Error> {
+        let vec_norm = vec.l2_norm();
+        let mut h = self.epsilon * (float!(1.0) + param.l2_norm());
+        if vec_norm > float!(0.0) {
+            h = h / vec_norm;
+        }
+        let grad_fwd = self.problem.gradient(&param.scaled_add(&h, vec))?;
+        let grad_bwd = self.problem.gradient(&param.scaled_add(&(-h), vec))?;
+        Ok(grad_fwd.sub(&grad_bwd).mul(&(float!(0.5) / h)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Problem};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+    use argmin_testfunctions::{rosenbrock_derivative, rosenbrock_hessian};
+
+    test_trait_impl!(matrix_free, MatrixFree);
+
+    struct Rosenbrock {}
+
+    impl Gradient for Rosenbrock {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(rosenbrock_derivative(p, 1.0, 100.0))
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let fd: FiniteDiffHessianVec<_, f64> = FiniteDiffHessianVec::new(());
+        let FiniteDiffHessianVec {
+            problem: _,
+            epsilon,
+        } = fd;
+        assert_eq!(epsilon.to_ne_bytes(), f64::EPSILON.cbrt().to_ne_bytes());
+    }
+
+    #[test]
+    fn test_with_epsilon() {
+        for eps in [f64::EPSILON, 1e-6, 1e-3, 1.0] {
+            let fd: FiniteDiffHessianVec<_, f64> =
+                FiniteDiffHessianVec::new(()).with_epsilon(eps).unwrap();
+            assert_eq!(fd.epsilon.to_ne_bytes(), eps.to_ne_bytes());
+        }
+
+        for eps in [0.0, -f64::EPSILON, -1.0] {
+            let res: Result<FiniteDiffHessianVec<_, f64>, _> =
+                FiniteDiffHessianVec::new(()).with_epsilon(eps);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`FiniteDiffHessianVec`: epsilon must be > 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_hessian_vec() {
+        let fd: FiniteDiffHessianVec<_, f64> = FiniteDiffHessianVec::new(Rosenbrock {});
+        let mut problem = Problem::new(fd);
+
+        for (x, v) in [
+            (vec![1.0, 1.0, 1.0], vec![1.0, 0.0, 0.0]),
+            (vec![-1.2, 1.0, 0.5], vec![0.3, -2.0, 1.0]),
+            (vec![0.0, 0.0, 0.0], vec![1e-3, 1e3, 1.0]),
+        ] {
+            let hv = problem.hessian_vec(&x, &v).unwrap();
+            let h = rosenbrock_hessian(&x, 1.0, 100.0);
+            for (hv_i, h_i) in hv.iter().zip(h.iter()) {
+                let expected: f64 = h_i.iter().zip(v.iter()).map(|(a, b)| a * b).sum();
+                assert_relative_eq!(*hv_i, expected, epsilon = 1e-5, max_relative = 1e-6);
+            }
+        }
+
+        assert_eq!(problem.counts["hessian_vec_count"], 3);
+    }
+
+    #[test]
+    fn test_hessian_vec_zero() {
+        let fd: FiniteDiffHessianVec<_, f64> = FiniteDiffHessianVec::new(Rosenbrock {});
+        let hv = fd.hessian_vec(&vec![1.0, 2.0], &vec![0.0, 0.0]).unwrap();
+        assert_eq!(hv, vec![0.0, 0.0]);
+    }
+}
@@ -22,6 +22,8 @@ mod executor;
 mod float;
 /// Key value data structure
 mod kv;
+/// Support for matrix-free solvers
+mod matrix_free;
 pub mod observers;
 /// Trait alias for `Send` and `Sync`
 mod parallelization;
@@ -46,9 +48,14 @@ pub use errors::ArgminError;
 pub use executor::Executor;
 pub use float::ArgminFloat;
 pub use kv::{KvValue, KV};
+pub use matrix_free::{FiniteDiffHessianVec, MatrixFree};
 pub use parallelization::{SendAlias, SyncAlias};
-pub use problem::{CostFunction, Gradient, Hessian, Jacobian, LinearProgram, Operator, Problem};
+pub use problem::{
+    ConstraintJacobian, CostFunction, EqualityConstraints, Gradient, Hessian, HessianVectorProduct,
+    InequalityConstraints, Jacobian, LinearMinimizationOracle, LinearProgram, MinibatchGradient,
+    MultiObjective, Operator, PartialGradient, Problem, ProximalOperator, QuadraticProgram,
+};
 pub use result::OptimizationResult;
 pub use solver::Solver;
-pub use state::{IterState, LinearProgramState, PopulationState, State};
+pub use state::{IterState, LinearProgramState, ParetoState, PopulationState, State};
 pub use termination::{TerminationReason, TerminationStatus};
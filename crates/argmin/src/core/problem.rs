@@ -444,6 +444,44 @@ pub trait Hessian {
     bulk!(hessian, Self::Param, Self::Hessian);
 }
 
+/// Defines the computation of the product of the Hessian with a vector.
+///
+/// This allows solvers such as [`NewtonCG`](`crate::solver::newton::NewtonCG`) or
+/// [`Steihaug`](`crate::solver::trustregion::Steihaug`) to work without ever forming the Hessian,
+/// which is infeasible for problems with many parameters. Solvers use this trait when configured
+/// with [`MatrixFree`](`crate::core::MatrixFree`).
+///
+/// If only the gradient is available, [`FiniteDiffHessianVec`](`crate::core::FiniteDiffHessianVec`)
+/// approximates the product via finite differences of the gradient.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{HessianVectorProduct, Error};
+///
+/// /// f(x) = sum_i x_i^4
+/// struct Quartic {}
+///
+/// impl HessianVectorProduct for Quartic {
+///     type Param = Vec<f64>;
+///     type Output = Vec<f64>;
+///
+///     /// The Hessian is diagonal with entries 12 x_i^2
+///     fn hessian_vec(&self, param: &Self::Param, vec: &Self::Param) -> Result<Self::Output, Error> {
+///         Ok(param.iter().zip(vec).map(|(x, v)| 12.0 * x.powi(2) * v).collect())
+///     }
+/// }
+/// ```
+pub trait HessianVectorProduct {
+    /// Type of the parameter vector
+    type Param;
+    /// Type of the product of the Hessian with a vector
+    type Output;
+
+    /// Compute the product of the Hessian at `param` with `vec`
+    fn hessian_vec(&self, param: &Self::Param, vec: &Self::Param) -> Result<Self::Output, Error>;
+}
+
 /// Defines the computation of the Jacobian.
 ///
 /// # Example
@@ -478,7 +516,29 @@ pub trait Jacobian {
     bulk!(jacobian, Self::Param, Self::Jacobian);
 }
 
-/// Defines a linear Program
+/// Defines a linear program in standard form
+///
+/// ```text
+/// min_x  c^T x
+/// s.t.   A x = b
+///        x >= 0
+/// ```
+///
+/// where `c` and `x` are of length `n`, `b` is of length `m` and `A` is an `m` times `n` matrix
+/// given as a vector of rows. Inequality constraints can be brought into this form by adding
+/// slack variables.
+///
+/// Optionally, a quadratic term can be added to the objective function by implementing
+/// [`Q`](`LinearProgram::Q`), which turns the problem into the (convex) quadratic program
+///
+/// ```text
+/// min_x  1/2 x^T Q x + c^T x
+/// s.t.   A x = b
+///        x >= 0
+/// ```
+///
+/// where `Q` is a symmetric positive semidefinite `n` times `n` matrix. Not all solvers support
+/// the quadratic term.
 ///
 /// # Example
 ///
@@ -510,8 +570,7 @@ pub trait LinearProgram {
     /// Precision of floats
     type Float: ArgminFloat;
 
-    /// TODO c for linear programs
-    /// Those three could maybe be merged into a single function; name unclear
+    /// Coefficients `c` of the linear objective function
     fn c(&self) -> Result<Vec<Self::Float>, Error> {
         Err(argmin_error!(
             NotImplemented,
@@ -519,7 +578,7 @@ pub trait LinearProgram {
         ))
     }
 
-    /// TODO b for linear programs
+    /// Right-hand side `b` of the equality constraints
     fn b(&self) -> Result<Vec<Self::Float>, Error> {
         Err(argmin_error!(
             NotImplemented,
@@ -527,7 +586,7 @@ pub trait LinearProgram {
         ))
     }
 
-    /// TODO A for linear programs
+    /// Matrix `A` of the equality constraints, given as a vector of rows
     #[allow(non_snake_case)]
     fn A(&self) -> Result<Vec<Vec<Self::Float>>, Error> {
         Err(argmin_error!(
@@ -535,522 +594,1903 @@ pub trait LinearProgram {
             "Method `A` of LinearProgram trait not implemented!"
         ))
     }
-}
 
-/// Wraps a call to `apply` defined in the `Operator` trait and as such allows to call `apply` on
-/// an instance of `Problem`. Internally, the number of evaluations of `apply` is counted.
-impl<O: Operator> Problem<O> {
-    /// Calls `apply` defined in the `Operator` trait and keeps track of the number of evaluations.
+    /// Optional quadratic term `Q` of the objective function, given as a vector of rows
     ///
-    /// # Example
-    ///
-    /// ```
-    /// # use argmin::core::{Problem, Operator, Error};
-    /// #
-    /// # #[derive(Eq, PartialEq, Debug, Clone)]
-    /// # struct UserDefinedProblem {};
-    /// #
-    /// # impl Operator for UserDefinedProblem {
-    /// #     type Param = Vec<f64>;
-    /// #     type Output = Vec<f64>;
-    /// #
-    /// #     fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
-    /// #         Ok(vec![1.0f64, 1.0f64])
-    /// #     }
-    /// # }
-    /// // `UserDefinedProblem` implements `Operator`.
-    /// let mut problem1 = Problem::new(UserDefinedProblem {});
-    ///
-    /// let param = vec![2.0f64, 1.0f64];
-    ///
-    /// let res = problem1.apply(&param);
-    ///
-    /// assert_eq!(problem1.counts["operator_count"], 1);
-    /// # assert_eq!(res.unwrap(), vec![1.0f64, 1.0f64]);
-    /// ```
-    pub fn apply(&mut self, param: &O::Param) -> Result<O::Output, Error> {
-        self.problem("operator_count", |problem| problem.apply(param))
+    /// Defaults to `None`, which corresponds to a linear objective function.
+    #[allow(non_snake_case)]
+    fn Q(&self) -> Result<Option<Vec<Vec<Self::Float>>>, Error> {
+        Ok(None)
     }
+}
 
-    /// Calls `bulk_apply` defined in the `Operator` trait and keeps track of the number of
-    /// evaluations.
-    ///
-    /// # Example
+/// Defines a convex quadratic program with linear equality and inequality constraints
+///
+/// ```text
+/// min_x  1/2 x^T H x + g^T x
+/// s.t.   A_eq x = b_eq
+///        A_ineq x <= b_ineq
+/// ```
+///
+/// where `H` is a symmetric positive (semi)definite `n` times `n` matrix, `A_eq` is an `m_eq`
+/// times `n` matrix and `A_ineq` is an `m_ineq` times `n` matrix. Vectors and matrices are given
+/// in the types `Param` and `Matrix`, which allows the use of the linear algebra backends of
+/// `argmin-math` (for instance `ndarray::Array1<f64>` and `ndarray::Array2<f64>` or
+/// `nalgebra::DVector<f64>` and `nalgebra::DMatrix<f64>`).
+///
+/// Both kinds of constraints are optional: the default implementations return `None`, which
+/// corresponds to no constraints of this kind.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{QuadraticProgram, Error};
+/// # #[cfg(feature = "_nalgebral")]
+/// use nalgebra::{DMatrix, DVector};
+///
+/// struct Problem {}
+///
+/// # #[cfg(feature = "_nalgebral")]
+/// impl QuadraticProgram for Problem {
+///     type Param = DVector<f64>;
+///     type Matrix = DMatrix<f64>;
+///     type Float = f64;
+///
+///     fn H(&self) -> Result<Self::Matrix, Error> {
+///         Ok(DMatrix::from_row_slice(2, 2, &[2.0, 0.0, 0.0, 2.0]))
+///     }
+///
+///     fn g(&self) -> Result<Self::Param, Error> {
+///         Ok(DVector::from_vec(vec![-2.0, -5.0]))
+///     }
+///
+///     /// x_0 + x_1 <= 1
+///     fn A_ineq(&self) -> Result<Option<Self::Matrix>, Error> {
+///         Ok(Some(DMatrix::from_row_slice(1, 2, &[1.0, 1.0])))
+///     }
+///
+///     fn b_ineq(&self) -> Result<Option<Self::Param>, Error> {
+///         Ok(Some(DVector::from_vec(vec![1.0])))
+///     }
+/// }
+/// ```
+pub trait QuadraticProgram {
+    /// Type of the parameter vector
+    type Param;
+    /// Type of the matrices
+    type Matrix;
+    /// Precision of floats
+    type Float: ArgminFloat;
+
+    /// Matrix `H` of the quadratic term of the objective function
+    #[allow(non_snake_case)]
+    fn H(&self) -> Result<Self::Matrix, Error> {
+        Err(argmin_error!(
+            NotImplemented,
+            "Method `H` of QuadraticProgram trait not implemented!"
+        ))
+    }
+
+    /// Coefficients `g` of the linear term of the objective function
+    fn g(&self) -> Result<Self::Param, Error> {
+        Err(argmin_error!(
+            NotImplemented,
+            "Method `g` of QuadraticProgram trait not implemented!"
+        ))
+    }
+
+    /// Optional matrix `A_eq` of the equality constraints
     ///
-    /// ```
-    /// # use argmin::core::{Problem, Operator, Error};
-    /// #
-    /// # #[derive(Eq, PartialEq, Debug, Clone)]
-    /// # struct UserDefinedProblem {};
-    /// #
-    /// # impl Operator for UserDefinedProblem {
-    /// #     type Param = Vec<f64>;
-    /// #     type Output = Vec<f64>;
-    /// #
-    /// #     fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
-    /// #         Ok(vec![1.0f64, 1.0f64])
-    /// #     }
-    /// # }
-    /// // `UserDefinedProblem` implements `Operator`.
-    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    /// Defaults to `None`, which corresponds to no equality constraints.
+    #[allow(non_snake_case)]
+    fn A_eq(&self) -> Result<Option<Self::Matrix>, Error> {
+        Ok(None)
+    }
+
+    /// Optional right-hand side `b_eq` of the equality constraints
     ///
-    /// let param1 = vec![2.0f64, 1.0f64];
-    /// let param2 = vec![3.0f64, 5.0f64];
-    /// let params = vec![&param1, &param2];
+    /// Defaults to `None`, which corresponds to no equality constraints.
+    fn b_eq(&self) -> Result<Option<Self::Param>, Error> {
+        Ok(None)
+    }
+
+    /// Optional matrix `A_ineq` of the inequality constraints
     ///
-    /// let res = problem1.bulk_apply(&params);
+    /// Defaults to `None`, which corresponds to no inequality constraints.
+    #[allow(non_snake_case)]
+    fn A_ineq(&self) -> Result<Option<Self::Matrix>, Error> {
+        Ok(None)
+    }
+
+    /// Optional right-hand side `b_ineq` of the inequality constraints
     ///
-    /// assert_eq!(problem1.counts["operator_count"], 2);
-    /// # let res = res.unwrap();
-    /// # assert_eq!(res[0], vec![1.0f64, 1.0f64]);
-    /// # assert_eq!(res[1], vec![1.0f64, 1.0f64]);
-    /// ```
-    pub fn bulk_apply<P>(&mut self, params: &[P]) -> Result<Vec<O::Output>, Error>
-    where
-        P: std::borrow::Borrow<O::Param> + SyncAlias,
-        O::Output: SendAlias,
-        O: SyncAlias,
-    {
-        self.bulk_problem("operator_count", params.len(), |problem| {
-            problem.bulk_apply(params)
-        })
+    /// Defaults to `None`, which corresponds to no inequality constraints.
+    fn b_ineq(&self) -> Result<Option<Self::Param>, Error> {
+        Ok(None)
     }
 }
 
-/// Wraps a call to `cost` defined in the `CostFunction` trait and as such allows to call `cost` on
-/// an instance of `Problem`. Internally, the number of evaluations of `cost` is counted.
-impl<O: CostFunction> Problem<O> {
-    /// Calls `cost` defined in the `CostFunction` trait and keeps track of the number of
-    /// evaluations.
-    ///
-    /// # Example
-    ///
-    /// ```
-    /// # use argmin::core::{Problem, CostFunction, Error};
-    /// #
-    /// # #[derive(Eq, PartialEq, Debug, Clone)]
-    /// # struct UserDefinedProblem {};
-    /// #
-    /// # impl CostFunction for UserDefinedProblem {
-    /// #     type Param = Vec<f64>;
-    /// #     type Output = f64;
-    /// #
-    /// #     fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
-    /// #         Ok(4.0f64)
-    /// #     }
-    /// # }
-    /// // `UserDefinedProblem` implements `CostFunction`.
-    /// let mut problem1 = Problem::new(UserDefinedProblem {});
-    ///
-    /// let param = vec![2.0f64, 1.0f64];
+/// Defines equality constraints `c(x) = 0`
+///
+/// The values of all constraints are returned as a vector; a parameter vector is feasible if all
+/// of them are zero. Solvers which use derivatives additionally require the gradients of the
+/// constraints, which are provided by the optional method
+/// [`equality_jacobian`](`EqualityConstraints::equality_jacobian`).
+///
+/// Problems without equality constraints can rely on the default implementation of
+/// [`equality_constraints`](`EqualityConstraints::equality_constraints`), which returns no
+/// constraints.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{EqualityConstraints, Error};
+///
+/// struct Problem {}
+///
+/// impl EqualityConstraints for Problem {
+///     type Param = Vec<f64>;
+///     type Float = f64;
+///
+///     /// x_0 + x_1 = 1
+///     fn equality_constraints(&self, p: &Self::Param) -> Result<Vec<Self::Float>, Error> {
+///         Ok(vec![p[0] + p[1] - 1.0])
+///     }
+///
+///     fn equality_jacobian(&self, _p: &Self::Param) -> Result<Vec<Self::Param>, Error> {
+///         Ok(vec![vec![1.0, 1.0]])
+///     }
+/// }
+/// ```
+pub trait EqualityConstraints {
+    /// Type of the parameter vector
+    type Param;
+    /// Precision of floats
+    type Float: ArgminFloat;
+
+    /// Compute values `c(x)` of the equality constraints
     ///
-    /// let res = problem1.cost(&param);
+    /// Defaults to no constraints.
+    fn equality_constraints(&self, _param: &Self::Param) -> Result<Vec<Self::Float>, Error> {
+        Ok(vec![])
+    }
+
+    /// Compute the Jacobian of the equality constraints, given as the gradients of the individual
+    /// constraints
+    fn equality_jacobian(&self, _param: &Self::Param) -> Result<Vec<Self::Param>, Error> {
+        Err(argmin_error!(
+            NotImplemented,
+            "Method `equality_jacobian` of EqualityConstraints trait not implemented!"
+        ))
+    }
+}
+
+/// Defines inequality constraints `g(x) <= 0`
+///
+/// The values of all constraints are returned as a vector; a parameter vector is feasible if none
+/// of them is positive. Solvers which use derivatives additionally require the gradients of the
+/// constraints, which are provided by the optional method
+/// [`inequality_jacobian`](`InequalityConstraints::inequality_jacobian`).
+///
+/// Problems without inequality constraints can rely on the default implementation of
+/// [`inequality_constraints`](`InequalityConstraints::inequality_constraints`), which returns no
+/// constraints.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{InequalityConstraints, Error};
+///
+/// struct Problem {}
+///
+/// impl InequalityConstraints for Problem {
+///     type Param = Vec<f64>;
+///     type Float = f64;
+///
+///     /// x_0^2 + x_1^2 <= 1
+///     fn inequality_constraints(&self, p: &Self::Param) -> Result<Vec<Self::Float>, Error> {
+///         Ok(vec![p[0].powi(2) + p[1].powi(2) - 1.0])
+///     }
+///
+///     fn inequality_jacobian(&self, p: &Self::Param) -> Result<Vec<Self::Param>, Error> {
+///         Ok(vec![vec![2.0 * p[0], 2.0 * p[1]]])
+///     }
+/// }
+/// ```
+pub trait InequalityConstraints {
+    /// Type of the parameter vector
+    type Param;
+    /// Precision of floats
+    type Float: ArgminFloat;
+
+    /// Compute values `g(x)` of the inequality constraints
     ///
-    /// assert_eq!(problem1.counts["cost_count"], 1);
-    /// # assert_eq!(res.unwrap(), 4.0f64);
+    /// Defaults to no constraints.
+    fn inequality_constraints(&self, _param: &Self::Param) -> Result<Vec<Self::Float>, Error> {
+        Ok(vec![])
+    }
+
+    /// Compute the Jacobian of the inequality constraints, given as the gradients of the
+    /// individual constraints
+    fn inequality_jacobian(&self, _param: &Self::Param) -> Result<Vec<Self::Param>, Error> {
+        Err(argmin_error!(
+            NotImplemented,
+            "Method `inequality_jacobian` of InequalityConstraints trait not implemented!"
+        ))
+    }
+}
+
+/// Defines the Jacobians of the equality constraints `c(x) = 0` and the inequality constraints
+/// `g(x) <= 0`
+///
+/// [`constraint_jacobian`](`ConstraintJacobian::constraint_jacobian`) returns the Jacobian of the
+/// equality constraints (see [`EqualityConstraints`]) and the Jacobian of the inequality
+/// constraints (see [`InequalityConstraints`]), each given as the gradients of the individual
+/// constraints in the order in which the constraints are returned. The Jacobian of absent
+/// constraints is empty.
+///
+/// While the Jacobian methods of the constraint traits are optional and only evaluated on demand,
+/// solvers which cannot work without the Jacobians (such as
+/// [`SQP`](`crate::solver::sqp::SQP`)) require this trait instead.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{ConstraintJacobian, Error};
+///
+/// struct Problem {}
+///
+/// impl ConstraintJacobian for Problem {
+///     type Param = Vec<f64>;
+///
+///     /// Equality constraint x_0 + x_1 = 1, inequality constraint x_0^2 + x_1^2 <= 1
+///     fn constraint_jacobian(
+///         &self,
+///         p: &Self::Param,
+///     ) -> Result<(Vec<Self::Param>, Vec<Self::Param>), Error> {
+///         Ok((vec![vec![1.0, 1.0]], vec![vec![2.0 * p[0], 2.0 * p[1]]]))
+///     }
+/// }
+/// ```
+pub trait ConstraintJacobian {
+    /// Type of the parameter vector
+    type Param;
+
+    /// Compute the Jacobians of the equality and of the inequality constraints
+    fn constraint_jacobian(
+        &self,
+        param: &Self::Param,
+    ) -> Result<(Vec<Self::Param>, Vec<Self::Param>), Error>;
+}
+
+/// Defines the nonsmooth part `g(x)` of a composite cost function `f(x) + g(x)` via its proximal
+/// operator
+///
+/// The proximal operator with step length `t > 0` is
+///
+/// ```text
+/// prox_{t g}(x) = argmin_z  g(z) + 1/(2 t) ||z - x||^2
+/// ```
+///
+/// The function `g` may be nonsmooth (for instance an L1 norm, a group lasso or nuclear norm
+/// penalty or a total variation term) or an indicator function of a convex set, in which case the
+/// proximal operator is the projection onto the set and [`nonsmooth_cost`] is zero for all
+/// parameter vectors returned by [`prox`].
+///
+/// [`prox`]: `ProximalOperator::prox`
+/// [`nonsmooth_cost`]: `ProximalOperator::nonsmooth_cost`
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{ProximalOperator, Error};
+///
+/// /// g(x) = lambda ||x||_1
+/// struct L1Norm {
+///     lambda: f64,
+/// }
+///
+/// impl ProximalOperator for L1Norm {
+///     type Param = Vec<f64>;
+///     type Float = f64;
+///
+///     /// Soft thresholding
+///     fn prox(&self, p: &Self::Param, step: Self::Float) -> Result<Self::Param, Error> {
+///         let threshold = self.lambda * step;
+///         Ok(p.iter()
+///             .map(|x| x.signum() * (x.abs() - threshold).max(0.0))
+///             .collect())
+///     }
+///
+///     fn nonsmooth_cost(&self, p: &Self::Param) -> Result<Self::Float, Error> {
+///         Ok(self.lambda * p.iter().map(|x| x.abs()).sum::<f64>())
+///     }
+/// }
+/// ```
+pub trait ProximalOperator {
+    /// Type of the parameter vector
+    type Param;
+    /// Precision of floats
+    type Float: ArgminFloat;
+
+    /// Compute the proximal operator of `g` with step length `step` at `param`
+    fn prox(&self, param: &Self::Param, step: Self::Float) -> Result<Self::Param, Error>;
+
+    /// Compute the value `g(x)` of the nonsmooth part of the cost function
+    fn nonsmooth_cost(&self, param: &Self::Param) -> Result<Self::Float, Error>;
+}
+
+/// Defines the computation of the gradient of a cost function which is a sum over samples, based on
+/// a subset (a minibatch) of these samples.
+///
+/// Cost functions of the form
+///
+/// ```text
+/// f(x) = 1/N sum_{i=0}^{N-1} f_i(x)
+/// ```
+///
+/// are common in statistics and machine learning. Stochastic solvers such as
+/// [`SGD`](`crate::solver::stochastic::SGD`) or [`Adam`](`crate::solver::stochastic::Adam`)
+/// approximate the gradient of `f` by averaging the gradients of the `f_i` over a small randomly
+/// drawn set of `indices` in each iteration.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{MinibatchGradient, Error};
+///
+/// /// Mean of squared distances to the data points
+/// struct Mean {
+///     data: Vec<f64>,
+/// }
+///
+/// impl MinibatchGradient for Mean {
+///     type Param = Vec<f64>;
+///     type Gradient = Vec<f64>;
+///
+///     fn num_samples(&self) -> usize {
+///         self.data.len()
+///     }
+///
+///     fn minibatch_gradient(
+///         &self,
+///         param: &Self::Param,
+///         indices: &[usize],
+///     ) -> Result<Self::Gradient, Error> {
+///         let sum: f64 = indices.iter().map(|&i| param[0] - self.data[i]).sum();
+///         Ok(vec![sum / indices.len() as f64])
+///     }
+/// }
+/// ```
+pub trait MinibatchGradient {
+    /// Type of the parameter vector
+    type Param;
+    /// Type of the gradient
+    type Gradient;
+
+    /// Total number of samples `N`
+    fn num_samples(&self) -> usize;
+
+    /// Compute the gradient averaged over the samples given by `indices`
+    fn minibatch_gradient(
+        &self,
+        param: &Self::Param,
+        indices: &[usize],
+    ) -> Result<Self::Gradient, Error>;
+}
+
+/// Defines the computation of single partial derivatives of a cost function.
+///
+/// Coordinate descent methods such as
+/// [`CoordinateDescent`](`crate::solver::coordinatedescent::CoordinateDescent`) update one
+/// parameter at a time and therefore only require the partial derivative with respect to this
+/// parameter. The step length along coordinate `i` is the inverse of `coordinate_lipschitz(i)`, an
+/// upper bound on the second partial derivative with respect to parameter `i` (the Lipschitz
+/// constant of the partial derivative). For quadratic cost functions, this is the `i`th diagonal
+/// element of the Hessian.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{PartialGradient, Error};
+///
+/// /// Least squares `1/2 ||X x - y||^2`
+/// struct LeastSquares {
+///     /// Columns of `X`
+///     columns: Vec<Vec<f64>>,
+///     y: Vec<f64>,
+/// }
+///
+/// impl PartialGradient for LeastSquares {
+///     type Param = Vec<f64>;
+///     type Float = f64;
+///
+///     fn partial_gradient(&self, p: &Self::Param, idx: usize) -> Result<f64, Error> {
+///         Ok((0..self.y.len())
+///             .map(|k| {
+///                 let residual: f64 = self.columns.iter().zip(p.iter()).map(|(c, x)| c[k] * x).sum();
+///                 (residual - self.y[k]) * self.columns[idx][k]
+///             })
+///             .sum())
+///     }
+///
+///     fn coordinate_lipschitz(&self, idx: usize) -> Result<f64, Error> {
+///         Ok(self.columns[idx].iter().map(|c| c * c).sum())
+///     }
+/// }
+/// ```
+pub trait PartialGradient {
+    /// Type of the parameter vector
+    type Param;
+    /// Precision of floats
+    type Float: ArgminFloat;
+
+    /// Compute the partial derivative with respect to the parameter at index `idx`
+    fn partial_gradient(&self, param: &Self::Param, idx: usize) -> Result<Self::Float, Error>;
+
+    /// Upper bound on the second partial derivative with respect to the parameter at index `idx`
+    fn coordinate_lipschitz(&self, idx: usize) -> Result<Self::Float, Error>;
+}
+
+/// Defines a linear minimization oracle (LMO) over a compact convex set.
+///
+/// Given a gradient `g`, the oracle returns a point `s` of the feasible set `C` which minimizes
+/// the linear function `<g, s>` over `C`. Projection-free solvers such as
+/// [`FrankWolfe`](`crate::solver::frankwolfe::FrankWolfe`) only access `C` via this oracle, which
+/// is much cheaper than a projection for many sets (for instance the vertex `e_i` with the
+/// smallest `g_i` for the probability simplex, or the leading singular vector pair for a
+/// nuclear-norm ball). The returned point should be a vertex (extreme point) of `C`.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{LinearMinimizationOracle, Error};
+///
+/// /// Probability simplex `{x : x_i >= 0, sum_i x_i = 1}`
+/// struct ProbabilitySimplex {}
+///
+/// impl LinearMinimizationOracle for ProbabilitySimplex {
+///     type Param = Vec<f64>;
+///     type Gradient = Vec<f64>;
+///
+///     fn lmo(&self, gradient: &Self::Gradient) -> Result<Self::Param, Error> {
+///         let i = (0..gradient.len())
+///             .min_by(|&a, &b| gradient[a].total_cmp(&gradient[b]))
+///             .unwrap();
+///         let mut vertex = vec![0.0; gradient.len()];
+///         vertex[i] = 1.0;
+///         Ok(vertex)
+///     }
+/// }
+/// ```
+pub trait LinearMinimizationOracle {
+    /// Type of the parameter vector
+    type Param;
+    /// Type of the gradient
+    type Gradient;
+
+    /// Compute a minimizer of `<gradient, s>` over the feasible set
+    fn lmo(&self, gradient: &Self::Gradient) -> Result<Self::Param, Error>;
+}
+
+/// Defines a vector-valued objective function for multi-objective optimization
+///
+/// All objectives are to be minimized. A parameter vector dominates another one if none of its
+/// objective values is larger and at least one of them is smaller. Multi-objective solvers such as
+/// [`NSGAII`](`crate::solver::nsga::NSGAII`) approximate the set of parameter vectors which are
+/// not dominated by any other parameter vector (the Pareto front).
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{MultiObjective, Error};
+///
+/// /// Schaffer's function N. 1
+/// struct Schaffer {}
+///
+/// impl MultiObjective for Schaffer {
+///     type Param = Vec<f64>;
+///     type Float = f64;
+///
+///     fn objectives(&self, p: &Self::Param) -> Result<Vec<Self::Float>, Error> {
+///         Ok(vec![p[0].powi(2), (p[0] - 2.0).powi(2)])
+///     }
+/// }
+/// ```
+pub trait MultiObjective {
+    /// Type of the parameter vector
+    type Param;
+    /// Precision of floats
+    type Float: ArgminFloat;
+
+    /// Compute the values of all objectives
+    fn objectives(&self, param: &Self::Param) -> Result<Vec<Self::Float>, Error>;
+
+    bulk!(objectives, Self::Param, Vec<Self::Float>);
+}
+
+/// Wraps a call to `apply` defined in the `Operator` trait and as such allows to call `apply` on
+/// an instance of `Problem`. Internally, the number of evaluations of `apply` is counted.
+impl<O: Operator> Problem<O> {
+    /// Calls `apply` defined in the `Operator` trait and keeps track of the number of evaluations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, Operator, Error};
+    /// #
+    /// # #[derive(Eq, PartialEq, Debug, Clone)]
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl Operator for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Output = Vec<f64>;
+    /// #
+    /// #     fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+    /// #         Ok(vec![1.0f64, 1.0f64])
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `Operator`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let param = vec![2.0f64, 1.0f64];
+    ///
+    /// let res = problem1.apply(&param);
+    ///
+    /// assert_eq!(problem1.counts["operator_count"], 1);
+    /// # assert_eq!(res.unwrap(), vec![1.0f64, 1.0f64]);
+    /// ```
+    pub fn apply(&mut self, param: &O::Param) -> Result<O::Output, Error> {
+        self.problem("operator_count", |problem| problem.apply(param))
+    }
+
+    /// Calls `bulk_apply` defined in the `Operator` trait and keeps track of the number of
+    /// evaluations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, Operator, Error};
+    /// #
+    /// # #[derive(Eq, PartialEq, Debug, Clone)]
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl Operator for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Output = Vec<f64>;
+    /// #
+    /// #     fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+    /// #         Ok(vec![1.0f64, 1.0f64])
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `Operator`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let param1 = vec![2.0f64, 1.0f64];
+    /// let param2 = vec![3.0f64, 5.0f64];
+    /// let params = vec![&param1, &param2];
+    ///
+    /// let res = problem1.bulk_apply(&params);
+    ///
+    /// assert_eq!(problem1.counts["operator_count"], 2);
+    /// # let res = res.unwrap();
+    /// # assert_eq!(res[0], vec![1.0f64, 1.0f64]);
+    /// # assert_eq!(res[1], vec![1.0f64, 1.0f64]);
+    /// ```
+    pub fn bulk_apply<P>(&mut self, params: &[P]) -> Result<Vec<O::Output>, Error>
+    where
+        P: std::borrow::Borrow<O::Param> + SyncAlias,
+        O::Output: SendAlias,
+        O: SyncAlias,
+    {
+        self.bulk_problem("operator_count", params.len(), |problem| {
+            problem.bulk_apply(params)
+        })
+    }
+}
+
+/// Wraps a call to `cost` defined in the `CostFunction` trait and as such allows to call `cost` on
+/// an instance of `Problem`. Internally, the number of evaluations of `cost` is counted.
+impl<O: CostFunction> Problem<O> {
+    /// Calls `cost` defined in the `CostFunction` trait and keeps track of the number of
+    /// evaluations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, CostFunction, Error};
+    /// #
+    /// # #[derive(Eq, PartialEq, Debug, Clone)]
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl CostFunction for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Output = f64;
+    /// #
+    /// #     fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+    /// #         Ok(4.0f64)
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `CostFunction`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let param = vec![2.0f64, 1.0f64];
+    ///
+    /// let res = problem1.cost(&param);
+    ///
+    /// assert_eq!(problem1.counts["cost_count"], 1);
+    /// # assert_eq!(res.unwrap(), 4.0f64);
+    /// ```
+    pub fn cost(&mut self, param: &O::Param) -> Result<O::Output, Error> {
+        self.problem("cost_count", |problem| problem.cost(param))
+    }
+
+    /// Calls `bulk_cost` defined in the `CostFunction` trait and keeps track of the number of
+    /// evaluations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, CostFunction, Error};
+    /// #
+    /// # #[derive(Eq, PartialEq, Debug, Clone)]
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl CostFunction for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Output = f64;
+    /// #
+    /// #     fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+    /// #         Ok(4.0f64)
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `CostFunction`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let param1 = vec![2.0f64, 1.0f64];
+    /// let param2 = vec![3.0f64, 5.0f64];
+    /// let params = vec![&param1, &param2];
+    ///
+    /// let res = problem1.bulk_cost(&params);
+    ///
+    /// assert_eq!(problem1.counts["cost_count"], 2);
+    /// # let res = res.unwrap();
+    /// # assert_eq!(res[0], 4.0f64);
+    /// # assert_eq!(res[1], 4.0f64);
+    /// ```
+    pub fn bulk_cost<P>(&mut self, params: &[P]) -> Result<Vec<O::Output>, Error>
+    where
+        P: std::borrow::Borrow<O::Param> + SyncAlias,
+        O::Output: SendAlias,
+        O: SyncAlias,
+    {
+        self.bulk_problem("cost_count", params.len(), |problem| {
+            problem.bulk_cost(params)
+        })
+    }
+}
+
+/// Wraps a call to `gradient` defined in the `Gradient` trait and as such allows to call `gradient` on
+/// an instance of `Problem`. Internally, the number of evaluations of `gradient` is counted.
+impl<O: Gradient> Problem<O> {
+    /// Calls `gradient` defined in the `Gradient` trait and keeps track of the number of
+    /// evaluations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, Gradient, Error};
+    /// #
+    /// # #[derive(Eq, PartialEq, Debug, Clone)]
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl Gradient for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Gradient = Vec<f64>;
+    /// #
+    /// #     fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+    /// #         Ok(vec![1.0f64, 1.0f64])
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `Gradient`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let param = vec![2.0f64, 1.0f64];
+    ///
+    /// let res = problem1.gradient(&param);
+    ///
+    /// assert_eq!(problem1.counts["gradient_count"], 1);
+    /// # assert_eq!(res.unwrap(), vec![1.0f64, 1.0f64]);
+    /// ```
+    pub fn gradient(&mut self, param: &O::Param) -> Result<O::Gradient, Error> {
+        self.problem("gradient_count", |problem| problem.gradient(param))
+    }
+
+    /// Calls `bulk_gradient` defined in the `Gradient` trait and keeps track of the number of
+    /// evaluations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, Gradient, Error};
+    /// #
+    /// # #[derive(Eq, PartialEq, Debug, Clone)]
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl Gradient for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Gradient = Vec<f64>;
+    /// #
+    /// #     fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+    /// #         Ok(vec![1.0f64, 1.0f64])
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `Gradient`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let param1 = vec![2.0f64, 1.0f64];
+    /// let param2 = vec![3.0f64, 5.0f64];
+    /// let params = vec![&param1, &param2];
+    ///
+    /// let res = problem1.bulk_gradient(&params);
+    ///
+    /// assert_eq!(problem1.counts["gradient_count"], 2);
+    /// # let res = res.unwrap();
+    /// # assert_eq!(res[0], vec![1.0f64, 1.0f64]);
+    /// # assert_eq!(res[1], vec![1.0f64, 1.0f64]);
+    /// ```
+    pub fn bulk_gradient<P>(&mut self, params: &[P]) -> Result<Vec<O::Gradient>, Error>
+    where
+        P: std::borrow::Borrow<O::Param> + SyncAlias,
+        O::Gradient: SendAlias,
+        O: SyncAlias,
+    {
+        self.bulk_problem("gradient_count", params.len(), |problem| {
+            problem.bulk_gradient(params)
+        })
+    }
+}
+
+/// Wraps a call to `hessian` defined in the `Hessian` trait and as such allows to call `hessian` on
+/// an instance of `Problem`. Internally, the number of evaluations of `hessian` is counted.
+impl<O: Hessian> Problem<O> {
+    /// Calls `hessian` defined in the `Hessian` trait and keeps track of the number of evaluations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, Hessian, Error};
+    /// #
+    /// # #[derive(Eq, PartialEq, Debug, Clone)]
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl Hessian for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Hessian = Vec<Vec<f64>>;
+    /// #
+    /// #     fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+    /// #         Ok(vec![vec![1.0f64, 0.0f64], vec![0.0f64, 1.0f64]])
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `Hessian`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let param = vec![2.0f64, 1.0f64];
+    ///
+    /// let res = problem1.hessian(&param);
+    ///
+    /// assert_eq!(problem1.counts["hessian_count"], 1);
+    /// # assert_eq!(res.unwrap(), vec![vec![1.0f64, 0.0f64], vec![0.0f64, 1.0f64]]);
+    /// ```
+    pub fn hessian(&mut self, param: &O::Param) -> Result<O::Hessian, Error> {
+        self.problem("hessian_count", |problem| problem.hessian(param))
+    }
+
+    /// Calls `bulk_hessian` defined in the `Hessian` trait and keeps track of the number of
+    /// evaluations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, Hessian, Error};
+    /// #
+    /// # #[derive(Eq, PartialEq, Debug, Clone)]
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl Hessian for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Hessian = Vec<Vec<f64>>;
+    /// #
+    /// #     fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+    /// #         Ok(vec![vec![1.0f64, 0.0f64], vec![0.0f64, 1.0f64]])
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `Hessian`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let param1 = vec![2.0f64, 1.0f64];
+    /// let param2 = vec![3.0f64, 5.0f64];
+    /// let params = vec![&param1, &param2];
+    ///
+    /// let res = problem1.bulk_hessian(&params);
+    ///
+    /// assert_eq!(problem1.counts["hessian_count"], 2);
+    /// # let res = res.unwrap();
+    /// # assert_eq!(res[0], vec![vec![1.0f64, 0.0f64], vec![0.0f64, 1.0f64]]);
+    /// # assert_eq!(res[1], vec![vec![1.0f64, 0.0f64], vec![0.0f64, 1.0f64]]);
+    /// ```
+    pub fn bulk_hessian<P>(&mut self, params: &[P]) -> Result<Vec<O::Hessian>, Error>
+    where
+        P: std::borrow::Borrow<O::Param> + SyncAlias,
+        O::Hessian: SendAlias,
+        O: SyncAlias,
+    {
+        self.bulk_problem("hessian_count", params.len(), |problem| {
+            problem.bulk_hessian(params)
+        })
+    }
+}
+
+/// Wraps a call to `hessian_vec` defined in the `HessianVectorProduct` trait and as such allows
+/// to call `hessian_vec` on an instance of `Problem`. Internally, the number of evaluations of
+/// `hessian_vec` is counted.
+impl<O: HessianVectorProduct> Problem<O> {
+    /// Calls `hessian_vec` defined in the `HessianVectorProduct` trait and keeps track of the
+    /// number of evaluations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, HessianVectorProduct, Error};
+    /// #
+    /// # #[derive(Eq, PartialEq, Debug, Clone)]
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl HessianVectorProduct for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Output = Vec<f64>;
+    /// #
+    /// #     fn hessian_vec(&self, _param: &Self::Param, vec: &Self::Param) -> Result<Self::Output, Error> {
+    /// #         Ok(vec.clone())
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `HessianVectorProduct`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let param = vec![2.0f64, 1.0f64];
+    /// let vec = vec![1.0f64, 0.0f64];
+    ///
+    /// let res = problem1.hessian_vec(&param, &vec);
+    ///
+    /// assert_eq!(problem1.counts["hessian_vec_count"], 1);
+    /// # assert_eq!(res.unwrap(), vec![1.0f64, 0.0f64]);
+    /// ```
+    pub fn hessian_vec(&mut self, param: &O::Param, vec: &O::Param) -> Result<O::Output, Error> {
+        self.problem("hessian_vec_count", |problem| {
+            problem.hessian_vec(param, vec)
+        })
+    }
+}
+
+/// Wraps a call to `jacobian` defined in the `Jacobian` trait and as such allows to call `jacobian`
+/// on an instance of `Problem`. Internally, the number of evaluations of `jacobian` is counted.
+impl<O: Jacobian> Problem<O> {
+    /// Calls `jacobian` defined in the `Jacobian` trait and keeps track of the number of
+    /// evaluations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, Jacobian, Error};
+    /// #
+    /// # #[derive(Eq, PartialEq, Debug, Clone)]
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl Jacobian for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Jacobian = Vec<Vec<f64>>;
+    /// #
+    /// #     fn jacobian(&self, param: &Self::Param) -> Result<Self::Jacobian, Error> {
+    /// #         Ok(vec![vec![1.0f64, 0.0f64], vec![0.0f64, 1.0f64]])
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `Jacobian`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let param = vec![2.0f64, 1.0f64];
+    ///
+    /// let res = problem1.jacobian(&param);
+    ///
+    /// assert_eq!(problem1.counts["jacobian_count"], 1);
+    /// # assert_eq!(res.unwrap(), vec![vec![1.0f64, 0.0f64], vec![0.0f64, 1.0f64]]);
+    /// ```
+    pub fn jacobian(&mut self, param: &O::Param) -> Result<O::Jacobian, Error> {
+        self.problem("jacobian_count", |problem| problem.jacobian(param))
+    }
+
+    /// Calls `bulk_jacobian` defined in the `Jacobian` trait and keeps track of the number of
+    /// evaluations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, Jacobian, Error};
+    /// #
+    /// # #[derive(Eq, PartialEq, Debug, Clone)]
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl Jacobian for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Jacobian = Vec<Vec<f64>>;
+    /// #
+    /// #     fn jacobian(&self, param: &Self::Param) -> Result<Self::Jacobian, Error> {
+    /// #         Ok(vec![vec![1.0f64, 0.0f64], vec![0.0f64, 1.0f64]])
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `Jacobian`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let params = vec![vec![2.0f64, 1.0f64], vec![3.0f64, 5.0f64]];
+    ///
+    /// let res = problem1.bulk_jacobian(&params);
+    ///
+    /// assert_eq!(problem1.counts["jacobian_count"], 2);
+    /// # let res = res.unwrap();
+    /// # assert_eq!(res[0], vec![vec![1.0f64, 0.0f64], vec![0.0f64, 1.0f64]]);
+    /// # assert_eq!(res[1], vec![vec![1.0f64, 0.0f64], vec![0.0f64, 1.0f64]]);
+    /// ```
+    pub fn bulk_jacobian<P>(&mut self, params: &[P]) -> Result<Vec<O::Jacobian>, Error>
+    where
+        P: std::borrow::Borrow<O::Param> + SyncAlias,
+        O::Jacobian: SendAlias,
+        O: SyncAlias,
+    {
+        self.bulk_problem("jacobian_count", params.len(), |problem| {
+            problem.bulk_jacobian(params)
+        })
+    }
+}
+
+/// Wraps a calls to `c`, `b` and `A` defined in the `LinearProgram` trait and as such allows to
+/// call those methods on an instance of `Problem`.
+impl<O: LinearProgram> Problem<O> {
+    /// Calls `c` defined in the `LinearProgram` trait.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, LinearProgram, Error};
+    /// #
+    /// # #[derive(Eq, PartialEq, Debug, Clone)]
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl LinearProgram for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Float = f64;
+    /// #
+    /// #     fn c(&self) -> Result<Vec<Self::Float>, Error> {
+    /// #         Ok(vec![4.0f64, 3.0f64])
+    /// #     }
+    /// #
+    /// #     fn b(&self) -> Result<Vec<Self::Float>, Error> {
+    /// #         Ok(vec![3.0f64, 2.0f64])
+    /// #     }
+    /// #
+    /// #     fn A(&self) -> Result<Vec<Vec<Self::Float>>, Error> {
+    /// #         Ok(vec![vec![1.0f64, 2.0f64], vec![3.0f64, 2.0f64]])
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `LinearProgram`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let c = problem1.c();
+    /// let b = problem1.b();
+    ///
+    /// # assert_eq!(c.unwrap(), vec![4.0f64, 3.0f64]);
+    /// ```
+    pub fn c(&self) -> Result<Vec<O::Float>, Error> {
+        self.problem.as_ref().unwrap().c()
+    }
+
+    /// Calls `b` defined in the `LinearProgram` trait.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, LinearProgram, Error};
+    /// #
+    /// # #[derive(Eq, PartialEq, Debug, Clone)]
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl LinearProgram for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Float = f64;
+    /// #
+    /// #     fn c(&self) -> Result<Vec<Self::Float>, Error> {
+    /// #         Ok(vec![4.0f64, 3.0f64])
+    /// #     }
+    /// #
+    /// #     fn b(&self) -> Result<Vec<Self::Float>, Error> {
+    /// #         Ok(vec![3.0f64, 2.0f64])
+    /// #     }
+    /// #
+    /// #     fn A(&self) -> Result<Vec<Vec<Self::Float>>, Error> {
+    /// #         Ok(vec![vec![1.0f64, 2.0f64], vec![3.0f64, 2.0f64]])
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `LinearProgram`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let b = problem1.b();
+    ///
+    /// # assert_eq!(b.unwrap(), vec![3.0f64, 2.0f64]);
+    /// ```
+    pub fn b(&self) -> Result<Vec<O::Float>, Error> {
+        self.problem.as_ref().unwrap().b()
+    }
+
+    /// Calls `A` defined in the `LinearProgram` trait.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, LinearProgram, Error};
+    /// #
+    /// # #[derive(Eq, PartialEq, Debug, Clone)]
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl LinearProgram for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Float = f64;
+    /// #
+    /// #     fn c(&self) -> Result<Vec<Self::Float>, Error> {
+    /// #         Ok(vec![4.0f64, 3.0f64])
+    /// #     }
+    /// #
+    /// #     fn b(&self) -> Result<Vec<Self::Float>, Error> {
+    /// #         Ok(vec![3.0f64, 2.0f64])
+    /// #     }
+    /// #
+    /// #     fn A(&self) -> Result<Vec<Vec<Self::Float>>, Error> {
+    /// #         Ok(vec![vec![1.0f64, 2.0f64], vec![3.0f64, 2.0f64]])
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `LinearProgram`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let a = problem1.A();
+    ///
+    /// # assert_eq!(a.unwrap(), vec![vec![1.0f64, 2.0f64], vec![3.0f64, 2.0f64]]);
+    /// ```
+    #[allow(non_snake_case)]
+    pub fn A(&self) -> Result<Vec<Vec<O::Float>>, Error> {
+        self.problem.as_ref().unwrap().A()
+    }
+
+    /// Calls `Q` defined in the `LinearProgram` trait.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, LinearProgram, Error};
+    /// #
+    /// # #[derive(Eq, PartialEq, Debug, Clone)]
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl LinearProgram for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Float = f64;
+    /// #
+    /// #     fn Q(&self) -> Result<Option<Vec<Vec<Self::Float>>>, Error> {
+    /// #         Ok(Some(vec![vec![2.0f64, 0.0f64], vec![0.0f64, 2.0f64]]))
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `LinearProgram`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let q = problem1.Q();
+    ///
+    /// # assert_eq!(q.unwrap(), Some(vec![vec![2.0f64, 0.0f64], vec![0.0f64, 2.0f64]]));
+    /// ```
+    #[allow(non_snake_case)]
+    pub fn Q(&self) -> Result<Option<Vec<Vec<O::Float>>>, Error> {
+        self.problem.as_ref().unwrap().Q()
+    }
+}
+
+/// Wraps calls to `H`, `g`, `A_eq`, `b_eq`, `A_ineq` and `b_ineq` defined in the
+/// `QuadraticProgram` trait and as such allows to call those methods on an instance of `Problem`.
+impl<O: QuadraticProgram> Problem<O> {
+    /// Calls `H` defined in the `QuadraticProgram` trait.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, QuadraticProgram, Error};
+    /// #
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl QuadraticProgram for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Matrix = Vec<Vec<f64>>;
+    /// #     type Float = f64;
+    /// #
+    /// #     fn H(&self) -> Result<Self::Matrix, Error> {
+    /// #         Ok(vec![vec![2.0f64, 0.0f64], vec![0.0f64, 2.0f64]])
+    /// #     }
+    /// #
+    /// #     fn g(&self) -> Result<Self::Param, Error> {
+    /// #         Ok(vec![-2.0f64, -5.0f64])
+    /// #     }
+    /// #
+    /// #     fn A_ineq(&self) -> Result<Option<Self::Matrix>, Error> {
+    /// #         Ok(Some(vec![vec![1.0f64, 1.0f64]]))
+    /// #     }
+    /// #
+    /// #     fn b_ineq(&self) -> Result<Option<Self::Param>, Error> {
+    /// #         Ok(Some(vec![1.0f64]))
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `QuadraticProgram`.
+    /// let problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let h = problem1.H();
+    ///
+    /// # assert_eq!(h.unwrap(), vec![vec![2.0f64, 0.0f64], vec![0.0f64, 2.0f64]]);
+    /// ```
+    #[allow(non_snake_case)]
+    pub fn H(&self) -> Result<O::Matrix, Error> {
+        self.problem.as_ref().unwrap().H()
+    }
+
+    /// Calls `g` defined in the `QuadraticProgram` trait.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, QuadraticProgram, Error};
+    /// #
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl QuadraticProgram for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Matrix = Vec<Vec<f64>>;
+    /// #     type Float = f64;
+    /// #
+    /// #     fn H(&self) -> Result<Self::Matrix, Error> {
+    /// #         Ok(vec![vec![2.0f64, 0.0f64], vec![0.0f64, 2.0f64]])
+    /// #     }
+    /// #
+    /// #     fn g(&self) -> Result<Self::Param, Error> {
+    /// #         Ok(vec![-2.0f64, -5.0f64])
+    /// #     }
+    /// #
+    /// #     fn A_ineq(&self) -> Result<Option<Self::Matrix>, Error> {
+    /// #         Ok(Some(vec![vec![1.0f64, 1.0f64]]))
+    /// #     }
+    /// #
+    /// #     fn b_ineq(&self) -> Result<Option<Self::Param>, Error> {
+    /// #         Ok(Some(vec![1.0f64]))
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `QuadraticProgram`.
+    /// let problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let g = problem1.g();
+    ///
+    /// # assert_eq!(g.unwrap(), vec![-2.0f64, -5.0f64]);
+    /// ```
+    pub fn g(&self) -> Result<O::Param, Error> {
+        self.problem.as_ref().unwrap().g()
+    }
+
+    /// Calls `A_eq` defined in the `QuadraticProgram` trait.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, QuadraticProgram, Error};
+    /// #
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl QuadraticProgram for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Matrix = Vec<Vec<f64>>;
+    /// #     type Float = f64;
+    /// #
+    /// #     fn H(&self) -> Result<Self::Matrix, Error> {
+    /// #         Ok(vec![vec![2.0f64, 0.0f64], vec![0.0f64, 2.0f64]])
+    /// #     }
+    /// #
+    /// #     fn g(&self) -> Result<Self::Param, Error> {
+    /// #         Ok(vec![-2.0f64, -5.0f64])
+    /// #     }
+    /// #
+    /// #     fn A_ineq(&self) -> Result<Option<Self::Matrix>, Error> {
+    /// #         Ok(Some(vec![vec![1.0f64, 1.0f64]]))
+    /// #     }
+    /// #
+    /// #     fn b_ineq(&self) -> Result<Option<Self::Param>, Error> {
+    /// #         Ok(Some(vec![1.0f64]))
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `QuadraticProgram`.
+    /// let problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let a_eq = problem1.A_eq();
+    ///
+    /// # assert!(a_eq.unwrap().is_none());
+    /// ```
+    #[allow(non_snake_case)]
+    pub fn A_eq(&self) -> Result<Option<O::Matrix>, Error> {
+        self.problem.as_ref().unwrap().A_eq()
+    }
+
+    /// Calls `b_eq` defined in the `QuadraticProgram` trait.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, QuadraticProgram, Error};
+    /// #
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl QuadraticProgram for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Matrix = Vec<Vec<f64>>;
+    /// #     type Float = f64;
+    /// #
+    /// #     fn H(&self) -> Result<Self::Matrix, Error> {
+    /// #         Ok(vec![vec![2.0f64, 0.0f64], vec![0.0f64, 2.0f64]])
+    /// #     }
+    /// #
+    /// #     fn g(&self) -> Result<Self::Param, Error> {
+    /// #         Ok(vec![-2.0f64, -5.0f64])
+    /// #     }
+    /// #
+    /// #     fn A_ineq(&self) -> Result<Option<Self::Matrix>, Error> {
+    /// #         Ok(Some(vec![vec![1.0f64, 1.0f64]]))
+    /// #     }
+    /// #
+    /// #     fn b_ineq(&self) -> Result<Option<Self::Param>, Error> {
+    /// #         Ok(Some(vec![1.0f64]))
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `QuadraticProgram`.
+    /// let problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let b_eq = problem1.b_eq();
+    ///
+    /// # assert!(b_eq.unwrap().is_none());
+    /// ```
+    pub fn b_eq(&self) -> Result<Option<O::Param>, Error> {
+        self.problem.as_ref().unwrap().b_eq()
+    }
+
+    /// Calls `A_ineq` defined in the `QuadraticProgram` trait.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, QuadraticProgram, Error};
+    /// #
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl QuadraticProgram for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Matrix = Vec<Vec<f64>>;
+    /// #     type Float = f64;
+    /// #
+    /// #     fn H(&self) -> Result<Self::Matrix, Error> {
+    /// #         Ok(vec![vec![2.0f64, 0.0f64], vec![0.0f64, 2.0f64]])
+    /// #     }
+    /// #
+    /// #     fn g(&self) -> Result<Self::Param, Error> {
+    /// #         Ok(vec![-2.0f64, -5.0f64])
+    /// #     }
+    /// #
+    /// #     fn A_ineq(&self) -> Result<Option<Self::Matrix>, Error> {
+    /// #         Ok(Some(vec![vec![1.0f64, 1.0f64]]))
+    /// #     }
+    /// #
+    /// #     fn b_ineq(&self) -> Result<Option<Self::Param>, Error> {
+    /// #         Ok(Some(vec![1.0f64]))
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `QuadraticProgram`.
+    /// let problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let a_ineq = problem1.A_ineq();
+    ///
+    /// # assert_eq!(a_ineq.unwrap(), Some(vec![vec![1.0f64, 1.0f64]]));
+    /// ```
+    #[allow(non_snake_case)]
+    pub fn A_ineq(&self) -> Result<Option<O::Matrix>, Error> {
+        self.problem.as_ref().unwrap().A_ineq()
+    }
+
+    /// Calls `b_ineq` defined in the `QuadraticProgram` trait.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, QuadraticProgram, Error};
+    /// #
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl QuadraticProgram for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Matrix = Vec<Vec<f64>>;
+    /// #     type Float = f64;
+    /// #
+    /// #     fn H(&self) -> Result<Self::Matrix, Error> {
+    /// #         Ok(vec![vec![2.0f64, 0.0f64], vec![0.0f64, 2.0f64]])
+    /// #     }
+    /// #
+    /// #     fn g(&self) -> Result<Self::Param, Error> {
+    /// #         Ok(vec![-2.0f64, -5.0f64])
+    /// #     }
+    /// #
+    /// #     fn A_ineq(&self) -> Result<Option<Self::Matrix>, Error> {
+    /// #         Ok(Some(vec![vec![1.0f64, 1.0f64]]))
+    /// #     }
+    /// #
+    /// #     fn b_ineq(&self) -> Result<Option<Self::Param>, Error> {
+    /// #         Ok(Some(vec![1.0f64]))
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `QuadraticProgram`.
+    /// let problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let b_ineq = problem1.b_ineq();
+    ///
+    /// # assert_eq!(b_ineq.unwrap(), Some(vec![1.0f64]));
+    /// ```
+    pub fn b_ineq(&self) -> Result<Option<O::Param>, Error> {
+        self.problem.as_ref().unwrap().b_ineq()
+    }
+}
+
+/// Wraps calls to `equality_constraints` and `equality_jacobian` defined in the `EqualityConstraints`
+/// trait and as such allows to call them on an instance of `Problem`. Internally, the number of
+/// evaluations is counted.
+impl<O: EqualityConstraints> Problem<O> {
+    /// Calls `equality_constraints` defined in the `EqualityConstraints` trait and keeps track of the
+    /// number of evaluations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, EqualityConstraints, Error};
+    /// #
+    /// # #[derive(Eq, PartialEq, Debug, Clone)]
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl EqualityConstraints for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Float = f64;
+    /// #
+    /// #     fn equality_constraints(&self, param: &Self::Param) -> Result<Vec<f64>, Error> {
+    /// #         Ok(vec![param[0] - 1.0])
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `EqualityConstraints`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let param = vec![2.0f64, 1.0f64];
+    ///
+    /// let res = problem1.equality_constraints(&param);
+    ///
+    /// assert_eq!(problem1.counts["equality_constraints_count"], 1);
+    /// # assert_eq!(res.unwrap(), vec![1.0f64]);
+    /// ```
+    pub fn equality_constraints(&mut self, param: &O::Param) -> Result<Vec<O::Float>, Error> {
+        self.problem("equality_constraints_count", |problem| {
+            problem.equality_constraints(param)
+        })
+    }
+
+    /// Calls `equality_jacobian` defined in the `EqualityConstraints` trait and keeps track of the
+    /// number of evaluations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, EqualityConstraints, Error};
+    /// #
+    /// # #[derive(Eq, PartialEq, Debug, Clone)]
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl EqualityConstraints for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Float = f64;
+    /// #
+    /// #     fn equality_jacobian(&self, param: &Self::Param) -> Result<Vec<Vec<f64>>, Error> {
+    /// #         Ok(vec![vec![1.0f64, 0.0f64]])
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `EqualityConstraints`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let param = vec![2.0f64, 1.0f64];
+    ///
+    /// let res = problem1.equality_jacobian(&param);
+    ///
+    /// assert_eq!(problem1.counts["equality_jacobian_count"], 1);
+    /// # assert_eq!(res.unwrap(), vec![vec![1.0f64, 0.0f64]]);
+    /// ```
+    pub fn equality_jacobian(&mut self, param: &O::Param) -> Result<Vec<O::Param>, Error> {
+        self.problem("equality_jacobian_count", |problem| {
+            problem.equality_jacobian(param)
+        })
+    }
+}
+
+/// Wraps calls to `inequality_constraints` and `inequality_jacobian` defined in the `InequalityConstraints`
+/// trait and as such allows to call them on an instance of `Problem`. Internally, the number of
+/// evaluations is counted.
+impl<O: InequalityConstraints> Problem<O> {
+    /// Calls `inequality_constraints` defined in the `InequalityConstraints` trait and keeps track of the
+    /// number of evaluations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, InequalityConstraints, Error};
+    /// #
+    /// # #[derive(Eq, PartialEq, Debug, Clone)]
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl InequalityConstraints for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Float = f64;
+    /// #
+    /// #     fn inequality_constraints(&self, param: &Self::Param) -> Result<Vec<f64>, Error> {
+    /// #         Ok(vec![param[0] - 1.0])
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `InequalityConstraints`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let param = vec![2.0f64, 1.0f64];
+    ///
+    /// let res = problem1.inequality_constraints(&param);
+    ///
+    /// assert_eq!(problem1.counts["inequality_constraints_count"], 1);
+    /// # assert_eq!(res.unwrap(), vec![1.0f64]);
+    /// ```
+    pub fn inequality_constraints(&mut self, param: &O::Param) -> Result<Vec<O::Float>, Error> {
+        self.problem("inequality_constraints_count", |problem| {
+            problem.inequality_constraints(param)
+        })
+    }
+
+    /// Calls `inequality_jacobian` defined in the `InequalityConstraints` trait and keeps track of the
+    /// number of evaluations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, InequalityConstraints, Error};
+    /// #
+    /// # #[derive(Eq, PartialEq, Debug, Clone)]
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl InequalityConstraints for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Float = f64;
+    /// #
+    /// #     fn inequality_jacobian(&self, param: &Self::Param) -> Result<Vec<Vec<f64>>, Error> {
+    /// #         Ok(vec![vec![1.0f64, 0.0f64]])
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `InequalityConstraints`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let param = vec![2.0f64, 1.0f64];
+    ///
+    /// let res = problem1.inequality_jacobian(&param);
+    ///
+    /// assert_eq!(problem1.counts["inequality_jacobian_count"], 1);
+    /// # assert_eq!(res.unwrap(), vec![vec![1.0f64, 0.0f64]]);
     /// ```
-    pub fn cost(&mut self, param: &O::Param) -> Result<O::Output, Error> {
-        self.problem("cost_count", |problem| problem.cost(param))
+    pub fn inequality_jacobian(&mut self, param: &O::Param) -> Result<Vec<O::Param>, Error> {
+        self.problem("inequality_jacobian_count", |problem| {
+            problem.inequality_jacobian(param)
+        })
     }
+}
 
-    /// Calls `bulk_cost` defined in the `CostFunction` trait and keeps track of the number of
-    /// evaluations.
+/// Wraps calls to `constraint_jacobian` defined in the `ConstraintJacobian` trait and as such allows
+/// to call it on an instance of `Problem`. Internally, the number of evaluations is counted.
+impl<O: ConstraintJacobian> Problem<O> {
+    /// Calls `constraint_jacobian` defined in the `ConstraintJacobian` trait and keeps track of the
+    /// number of evaluations.
     ///
     /// # Example
     ///
     /// ```
-    /// # use argmin::core::{Problem, CostFunction, Error};
+    /// # use argmin::core::{Problem, ConstraintJacobian, Error};
     /// #
     /// # #[derive(Eq, PartialEq, Debug, Clone)]
     /// # struct UserDefinedProblem {};
     /// #
-    /// # impl CostFunction for UserDefinedProblem {
+    /// # impl ConstraintJacobian for UserDefinedProblem {
     /// #     type Param = Vec<f64>;
-    /// #     type Output = f64;
     /// #
-    /// #     fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
-    /// #         Ok(4.0f64)
+    /// #     fn constraint_jacobian(
+    /// #         &self,
+    /// #         param: &Self::Param,
+    /// #     ) -> Result<(Vec<Vec<f64>>, Vec<Vec<f64>>), Error> {
+    /// #         Ok((vec![vec![1.0f64, 0.0f64]], vec![]))
     /// #     }
     /// # }
-    /// // `UserDefinedProblem` implements `CostFunction`.
+    /// // `UserDefinedProblem` implements `ConstraintJacobian`.
     /// let mut problem1 = Problem::new(UserDefinedProblem {});
     ///
-    /// let param1 = vec![2.0f64, 1.0f64];
-    /// let param2 = vec![3.0f64, 5.0f64];
-    /// let params = vec![&param1, &param2];
+    /// let param = vec![2.0f64, 1.0f64];
     ///
-    /// let res = problem1.bulk_cost(&params);
+    /// let res = problem1.constraint_jacobian(&param);
     ///
-    /// assert_eq!(problem1.counts["cost_count"], 2);
-    /// # let res = res.unwrap();
-    /// # assert_eq!(res[0], 4.0f64);
-    /// # assert_eq!(res[1], 4.0f64);
+    /// assert_eq!(problem1.counts["constraint_jacobian_count"], 1);
+    /// # assert_eq!(res.unwrap(), (vec![vec![1.0f64, 0.0f64]], vec![]));
     /// ```
-    pub fn bulk_cost<P>(&mut self, params: &[P]) -> Result<Vec<O::Output>, Error>
-    where
-        P: std::borrow::Borrow<O::Param> + SyncAlias,
-        O::Output: SendAlias,
-        O: SyncAlias,
-    {
-        self.bulk_problem("cost_count", params.len(), |problem| {
-            problem.bulk_cost(params)
+    pub fn constraint_jacobian(
+        &mut self,
+        param: &O::Param,
+    ) -> Result<(Vec<O::Param>, Vec<O::Param>), Error> {
+        self.problem("constraint_jacobian_count", |problem| {
+            problem.constraint_jacobian(param)
         })
     }
 }
 
-/// Wraps a call to `gradient` defined in the `Gradient` trait and as such allows to call `gradient` on
-/// an instance of `Problem`. Internally, the number of evaluations of `gradient` is counted.
-impl<O: Gradient> Problem<O> {
-    /// Calls `gradient` defined in the `Gradient` trait and keeps track of the number of
+/// Wraps calls to `prox` and `nonsmooth_cost` defined in the `ProximalOperator` trait and as such
+/// allows to call them on an instance of `Problem`. Internally, the number of evaluations is
+/// counted.
+impl<O: ProximalOperator> Problem<O> {
+    /// Calls `prox` defined in the `ProximalOperator` trait and keeps track of the number of
     /// evaluations.
     ///
     /// # Example
     ///
     /// ```
-    /// # use argmin::core::{Problem, Gradient, Error};
+    /// # use argmin::core::{Problem, ProximalOperator, Error};
     /// #
     /// # #[derive(Eq, PartialEq, Debug, Clone)]
     /// # struct UserDefinedProblem {};
     /// #
-    /// # impl Gradient for UserDefinedProblem {
+    /// # impl ProximalOperator for UserDefinedProblem {
     /// #     type Param = Vec<f64>;
-    /// #     type Gradient = Vec<f64>;
+    /// #     type Float = f64;
     /// #
-    /// #     fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
-    /// #         Ok(vec![1.0f64, 1.0f64])
+    /// #     fn prox(&self, param: &Self::Param, _step: f64) -> Result<Self::Param, Error> {
+    /// #         Ok(param.iter().map(|x| x.max(0.0)).collect())
+    /// #     }
+    /// #
+    /// #     fn nonsmooth_cost(&self, _param: &Self::Param) -> Result<f64, Error> {
+    /// #         Ok(0.0)
     /// #     }
     /// # }
-    /// // `UserDefinedProblem` implements `Gradient`.
+    /// // `UserDefinedProblem` implements `ProximalOperator`.
     /// let mut problem1 = Problem::new(UserDefinedProblem {});
     ///
-    /// let param = vec![2.0f64, 1.0f64];
+    /// let param = vec![2.0f64, -1.0f64];
     ///
-    /// let res = problem1.gradient(&param);
+    /// let res = problem1.prox(&param, 0.5);
     ///
-    /// assert_eq!(problem1.counts["gradient_count"], 1);
-    /// # assert_eq!(res.unwrap(), vec![1.0f64, 1.0f64]);
+    /// assert_eq!(problem1.counts["prox_count"], 1);
+    /// # assert_eq!(res.unwrap(), vec![2.0f64, 0.0f64]);
     /// ```
-    pub fn gradient(&mut self, param: &O::Param) -> Result<O::Gradient, Error> {
-        self.problem("gradient_count", |problem| problem.gradient(param))
+    pub fn prox(&mut self, param: &O::Param, step: O::Float) -> Result<O::Param, Error> {
+        self.problem("prox_count", |problem| problem.prox(param, step))
     }
 
-    /// Calls `bulk_gradient` defined in the `Gradient` trait and keeps track of the number of
-    /// evaluations.
+    /// Calls `nonsmooth_cost` defined in the `ProximalOperator` trait and keeps track of the
+    /// number of evaluations.
     ///
     /// # Example
     ///
     /// ```
-    /// # use argmin::core::{Problem, Gradient, Error};
+    /// # use argmin::core::{Problem, ProximalOperator, Error};
     /// #
     /// # #[derive(Eq, PartialEq, Debug, Clone)]
     /// # struct UserDefinedProblem {};
     /// #
-    /// # impl Gradient for UserDefinedProblem {
+    /// # impl ProximalOperator for UserDefinedProblem {
     /// #     type Param = Vec<f64>;
-    /// #     type Gradient = Vec<f64>;
+    /// #     type Float = f64;
     /// #
-    /// #     fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
-    /// #         Ok(vec![1.0f64, 1.0f64])
+    /// #     fn prox(&self, param: &Self::Param, _step: f64) -> Result<Self::Param, Error> {
+    /// #         Ok(param.clone())
+    /// #     }
+    /// #
+    /// #     fn nonsmooth_cost(&self, param: &Self::Param) -> Result<f64, Error> {
+    /// #         Ok(param.iter().map(|x| x.abs()).sum())
     /// #     }
     /// # }
-    /// // `UserDefinedProblem` implements `Gradient`.
+    /// // `UserDefinedProblem` implements `ProximalOperator`.
     /// let mut problem1 = Problem::new(UserDefinedProblem {});
     ///
-    /// let param1 = vec![2.0f64, 1.0f64];
-    /// let param2 = vec![3.0f64, 5.0f64];
-    /// let params = vec![&param1, &param2];
+    /// let param = vec![2.0f64, -1.0f64];
     ///
-    /// let res = problem1.bulk_gradient(&params);
+    /// let res = problem1.nonsmooth_cost(&param);
     ///
-    /// assert_eq!(problem1.counts["gradient_count"], 2);
-    /// # let res = res.unwrap();
-    /// # assert_eq!(res[0], vec![1.0f64, 1.0f64]);
-    /// # assert_eq!(res[1], vec![1.0f64, 1.0f64]);
+    /// assert_eq!(problem1.counts["nonsmooth_cost_count"], 1);
+    /// # assert_eq!(res.unwrap(), 3.0f64);
     /// ```
-    pub fn bulk_gradient<P>(&mut self, params: &[P]) -> Result<Vec<O::Gradient>, Error>
-    where
-        P: std::borrow::Borrow<O::Param> + SyncAlias,
-        O::Gradient: SendAlias,
-        O: SyncAlias,
-    {
-        self.bulk_problem("gradient_count", params.len(), |problem| {
-            problem.bulk_gradient(params)
+    pub fn nonsmooth_cost(&mut self, param: &O::Param) -> Result<O::Float, Error> {
+        self.problem("nonsmooth_cost_count", |problem| {
+            problem.nonsmooth_cost(param)
         })
     }
 }
 
-/// Wraps a call to `hessian` defined in the `Hessian` trait and as such allows to call `hessian` on
-/// an instance of `Problem`. Internally, the number of evaluations of `hessian` is counted.
-impl<O: Hessian> Problem<O> {
-    /// Calls `hessian` defined in the `Hessian` trait and keeps track of the number of evaluations.
+/// Wraps a call to `minibatch_gradient` defined in the `MinibatchGradient` trait and as such allows
+/// to call `minibatch_gradient` on an instance of `Problem`. Internally, the number of evaluations
+/// of `minibatch_gradient` is counted.
+impl<O: MinibatchGradient> Problem<O> {
+    /// Returns the total number of samples as defined in the `MinibatchGradient` trait. This is
+    /// not counted as a function evaluation.
     ///
     /// # Example
     ///
     /// ```
-    /// # use argmin::core::{Problem, Hessian, Error};
+    /// # use argmin::core::{Problem, MinibatchGradient, Error};
     /// #
-    /// # #[derive(Eq, PartialEq, Debug, Clone)]
     /// # struct UserDefinedProblem {};
     /// #
-    /// # impl Hessian for UserDefinedProblem {
+    /// # impl MinibatchGradient for UserDefinedProblem {
     /// #     type Param = Vec<f64>;
-    /// #     type Hessian = Vec<Vec<f64>>;
+    /// #     type Gradient = Vec<f64>;
     /// #
-    /// #     fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
-    /// #         Ok(vec![vec![1.0f64, 0.0f64], vec![0.0f64, 1.0f64]])
+    /// #     fn num_samples(&self) -> usize {
+    /// #         10
+    /// #     }
+    /// #
+    /// #     fn minibatch_gradient(&self, param: &Self::Param, _idx: &[usize]) -> Result<Self::Gradient, Error> {
+    /// #         Ok(param.clone())
     /// #     }
     /// # }
-    /// // `UserDefinedProblem` implements `Hessian`.
-    /// let mut problem1 = Problem::new(UserDefinedProblem {});
-    ///
-    /// let param = vec![2.0f64, 1.0f64];
-    ///
-    /// let res = problem1.hessian(&param);
+    /// // `UserDefinedProblem` implements `MinibatchGradient`.
+    /// let problem1 = Problem::new(UserDefinedProblem {});
     ///
-    /// assert_eq!(problem1.counts["hessian_count"], 1);
-    /// # assert_eq!(res.unwrap(), vec![vec![1.0f64, 0.0f64], vec![0.0f64, 1.0f64]]);
+    /// assert_eq!(problem1.num_samples(), 10);
     /// ```
-    pub fn hessian(&mut self, param: &O::Param) -> Result<O::Hessian, Error> {
-        self.problem("hessian_count", |problem| problem.hessian(param))
+    pub fn num_samples(&self) -> usize {
+        self.problem.as_ref().unwrap().num_samples()
     }
 
-    /// Calls `bulk_hessian` defined in the `Hessian` trait and keeps track of the number of
-    /// evaluations.
+    /// Calls `minibatch_gradient` defined in the `MinibatchGradient` trait and keeps track of the
+    /// number of evaluations.
     ///
     /// # Example
     ///
     /// ```
-    /// # use argmin::core::{Problem, Hessian, Error};
+    /// # use argmin::core::{Problem, MinibatchGradient, Error};
     /// #
-    /// # #[derive(Eq, PartialEq, Debug, Clone)]
     /// # struct UserDefinedProblem {};
     /// #
-    /// # impl Hessian for UserDefinedProblem {
+    /// # impl MinibatchGradient for UserDefinedProblem {
     /// #     type Param = Vec<f64>;
-    /// #     type Hessian = Vec<Vec<f64>>;
+    /// #     type Gradient = Vec<f64>;
     /// #
-    /// #     fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
-    /// #         Ok(vec![vec![1.0f64, 0.0f64], vec![0.0f64, 1.0f64]])
+    /// #     fn num_samples(&self) -> usize {
+    /// #         10
+    /// #     }
+    /// #
+    /// #     fn minibatch_gradient(&self, param: &Self::Param, _idx: &[usize]) -> Result<Self::Gradient, Error> {
+    /// #         Ok(param.clone())
     /// #     }
     /// # }
-    /// // `UserDefinedProblem` implements `Hessian`.
+    /// // `UserDefinedProblem` implements `MinibatchGradient`.
     /// let mut problem1 = Problem::new(UserDefinedProblem {});
     ///
-    /// let param1 = vec![2.0f64, 1.0f64];
-    /// let param2 = vec![3.0f64, 5.0f64];
-    /// let params = vec![&param1, &param2];
+    /// let param = vec![2.0f64, 1.0f64];
     ///
-    /// let res = problem1.bulk_hessian(&params);
+    /// let res = problem1.minibatch_gradient(&param, &[0, 3, 7]);
     ///
-    /// assert_eq!(problem1.counts["hessian_count"], 2);
-    /// # let res = res.unwrap();
-    /// # assert_eq!(res[0], vec![vec![1.0f64, 0.0f64], vec![0.0f64, 1.0f64]]);
-    /// # assert_eq!(res[1], vec![vec![1.0f64, 0.0f64], vec![0.0f64, 1.0f64]]);
+    /// assert_eq!(problem1.counts["minibatch_gradient_count"], 1);
+    /// # assert_eq!(res.unwrap(), vec![2.0f64, 1.0f64]);
     /// ```
-    pub fn bulk_hessian<P>(&mut self, params: &[P]) -> Result<Vec<O::Hessian>, Error>
-    where
-        P: std::borrow::Borrow<O::Param> + SyncAlias,
-        O::Hessian: SendAlias,
-        O: SyncAlias,
-    {
-        self.bulk_problem("hessian_count", params.len(), |problem| {
-            problem.bulk_hessian(params)
+    pub fn minibatch_gradient(
+        &mut self,
+        param: &O::Param,
+        indices: &[usize],
+    ) -> Result<O::Gradient, Error> {
+        self.problem("minibatch_gradient_count", |problem| {
+            problem.minibatch_gradient(param, indices)
         })
     }
 }
 
-/// Wraps a call to `jacobian` defined in the `Jacobian` trait and as such allows to call `jacobian`
-/// on an instance of `Problem`. Internally, the number of evaluations of `jacobian` is counted.
-impl<O: Jacobian> Problem<O> {
-    /// Calls `jacobian` defined in the `Jacobian` trait and keeps track of the number of
-    /// evaluations.
+/// Wraps a call to `partial_gradient` defined in the `PartialGradient` trait and as such allows
+/// to call `partial_gradient` on an instance of `Problem`. Internally, the number of evaluations
+/// of `partial_gradient` is counted.
+impl<O: PartialGradient> Problem<O> {
+    /// Calls `partial_gradient` defined in the `PartialGradient` trait and keeps track of the
+    /// number of evaluations.
     ///
     /// # Example
     ///
     /// ```
-    /// # use argmin::core::{Problem, Jacobian, Error};
+    /// # use argmin::core::{Problem, PartialGradient, Error};
     /// #
-    /// # #[derive(Eq, PartialEq, Debug, Clone)]
     /// # struct UserDefinedProblem {};
     /// #
-    /// # impl Jacobian for UserDefinedProblem {
+    /// # impl PartialGradient for UserDefinedProblem {
     /// #     type Param = Vec<f64>;
-    /// #     type Jacobian = Vec<Vec<f64>>;
+    /// #     type Float = f64;
     /// #
-    /// #     fn jacobian(&self, param: &Self::Param) -> Result<Self::Jacobian, Error> {
-    /// #         Ok(vec![vec![1.0f64, 0.0f64], vec![0.0f64, 1.0f64]])
+    /// #     fn partial_gradient(&self, param: &Self::Param, idx: usize) -> Result<f64, Error> {
+    /// #         Ok(2.0 * param[idx])
+    /// #     }
+    /// #
+    /// #     fn coordinate_lipschitz(&self, _idx: usize) -> Result<f64, Error> {
+    /// #         Ok(2.0)
     /// #     }
     /// # }
-    /// // `UserDefinedProblem` implements `Jacobian`.
+    /// // `UserDefinedProblem` implements `PartialGradient`.
     /// let mut problem1 = Problem::new(UserDefinedProblem {});
     ///
     /// let param = vec![2.0f64, 1.0f64];
     ///
-    /// let res = problem1.jacobian(&param);
+    /// let res = problem1.partial_gradient(&param, 1);
     ///
-    /// assert_eq!(problem1.counts["jacobian_count"], 1);
-    /// # assert_eq!(res.unwrap(), vec![vec![1.0f64, 0.0f64], vec![0.0f64, 1.0f64]]);
+    /// assert_eq!(problem1.counts["partial_gradient_count"], 1);
+    /// # assert_eq!(res.unwrap(), 2.0f64);
     /// ```
-    pub fn jacobian(&mut self, param: &O::Param) -> Result<O::Jacobian, Error> {
-        self.problem("jacobian_count", |problem| problem.jacobian(param))
+    pub fn partial_gradient(&mut self, param: &O::Param, idx: usize) -> Result<O::Float, Error> {
+        self.problem("partial_gradient_count", |problem| {
+            problem.partial_gradient(param, idx)
+        })
     }
 
-    /// Calls `bulk_jacobian` defined in the `Jacobian` trait and keeps track of the number of
-    /// evaluations.
+    /// Returns the upper bound on the second partial derivative as defined in the
+    /// `PartialGradient` trait. This is not counted as a function evaluation.
     ///
     /// # Example
     ///
     /// ```
-    /// # use argmin::core::{Problem, Jacobian, Error};
+    /// # use argmin::core::{Problem, PartialGradient, Error};
     /// #
-    /// # #[derive(Eq, PartialEq, Debug, Clone)]
     /// # struct UserDefinedProblem {};
     /// #
-    /// # impl Jacobian for UserDefinedProblem {
+    /// # impl PartialGradient for UserDefinedProblem {
     /// #     type Param = Vec<f64>;
-    /// #     type Jacobian = Vec<Vec<f64>>;
+    /// #     type Float = f64;
     /// #
-    /// #     fn jacobian(&self, param: &Self::Param) -> Result<Self::Jacobian, Error> {
-    /// #         Ok(vec![vec![1.0f64, 0.0f64], vec![0.0f64, 1.0f64]])
+    /// #     fn partial_gradient(&self, param: &Self::Param, idx: usize) -> Result<f64, Error> {
+    /// #         Ok(2.0 * param[idx])
+    /// #     }
+    /// #
+    /// #     fn coordinate_lipschitz(&self, _idx: usize) -> Result<f64, Error> {
+    /// #         Ok(2.0)
     /// #     }
     /// # }
-    /// // `UserDefinedProblem` implements `Jacobian`.
-    /// let mut problem1 = Problem::new(UserDefinedProblem {});
-    ///
-    /// let params = vec![vec![2.0f64, 1.0f64], vec![3.0f64, 5.0f64]];
-    ///
-    /// let res = problem1.bulk_jacobian(&params);
+    /// // `UserDefinedProblem` implements `PartialGradient`.
+    /// let problem1 = Problem::new(UserDefinedProblem {});
     ///
-    /// assert_eq!(problem1.counts["jacobian_count"], 2);
-    /// # let res = res.unwrap();
-    /// # assert_eq!(res[0], vec![vec![1.0f64, 0.0f64], vec![0.0f64, 1.0f64]]);
-    /// # assert_eq!(res[1], vec![vec![1.0f64, 0.0f64], vec![0.0f64, 1.0f64]]);
+    /// assert_eq!(problem1.coordinate_lipschitz(0).unwrap(), 2.0);
     /// ```
-    pub fn bulk_jacobian<P>(&mut self, params: &[P]) -> Result<Vec<O::Jacobian>, Error>
-    where
-        P: std::borrow::Borrow<O::Param> + SyncAlias,
-        O::Jacobian: SendAlias,
-        O: SyncAlias,
-    {
-        self.bulk_problem("jacobian_count", params.len(), |problem| {
-            problem.bulk_jacobian(params)
-        })
+    pub fn coordinate_lipschitz(&self, idx: usize) -> Result<O::Float, Error> {
+        self.problem.as_ref().unwrap().coordinate_lipschitz(idx)
     }
 }
 
-/// Wraps a calls to `c`, `b` and `A` defined in the `LinearProgram` trait and as such allows to
-/// call those methods on an instance of `Problem`.
-impl<O: LinearProgram> Problem<O> {
-    /// Calls `c` defined in the `LinearProgram` trait.
+/// Wraps a call to `lmo` defined in the `LinearMinimizationOracle` trait and as such allows to
+/// call `lmo` on an instance of `Problem`. Internally, the number of evaluations of `lmo` is
+/// counted.
+impl<O: LinearMinimizationOracle> Problem<O> {
+    /// Calls `lmo` defined in the `LinearMinimizationOracle` trait and keeps track of the number
+    /// of evaluations.
     ///
     /// # Example
     ///
     /// ```
-    /// # use argmin::core::{Problem, LinearProgram, Error};
+    /// # use argmin::core::{Problem, LinearMinimizationOracle, Error};
     /// #
-    /// # #[derive(Eq, PartialEq, Debug, Clone)]
     /// # struct UserDefinedProblem {};
     /// #
-    /// # impl LinearProgram for UserDefinedProblem {
+    /// # impl LinearMinimizationOracle for UserDefinedProblem {
     /// #     type Param = Vec<f64>;
-    /// #     type Float = f64;
-    /// #
-    /// #     fn c(&self) -> Result<Vec<Self::Float>, Error> {
-    /// #         Ok(vec![4.0f64, 3.0f64])
-    /// #     }
-    /// #
-    /// #     fn b(&self) -> Result<Vec<Self::Float>, Error> {
-    /// #         Ok(vec![3.0f64, 2.0f64])
-    /// #     }
+    /// #     type Gradient = Vec<f64>;
     /// #
-    /// #     fn A(&self) -> Result<Vec<Vec<Self::Float>>, Error> {
-    /// #         Ok(vec![vec![1.0f64, 2.0f64], vec![3.0f64, 2.0f64]])
+    /// #     fn lmo(&self, gradient: &Self::Gradient) -> Result<Self::Param, Error> {
+    /// #         Ok(gradient.iter().map(|g| -g.signum()).collect())
     /// #     }
     /// # }
-    /// // `UserDefinedProblem` implements `LinearProgram`.
+    /// // `UserDefinedProblem` implements `LinearMinimizationOracle`.
     /// let mut problem1 = Problem::new(UserDefinedProblem {});
     ///
-    /// let c = problem1.c();
-    /// let b = problem1.b();
+    /// let gradient = vec![2.0f64, -1.0f64];
     ///
-    /// # assert_eq!(c.unwrap(), vec![4.0f64, 3.0f64]);
+    /// let res = problem1.lmo(&gradient);
+    ///
+    /// assert_eq!(problem1.counts["lmo_count"], 1);
+    /// # assert_eq!(res.unwrap(), vec![-1.0f64, 1.0f64]);
     /// ```
-    pub fn c(&self) -> Result<Vec<O::Float>, Error> {
-        self.problem.as_ref().unwrap().c()
+    pub fn lmo(&mut self, gradient: &O::Gradient) -> Result<O::Param, Error> {
+        self.problem("lmo_count", |problem| problem.lmo(gradient))
     }
+}
 
-    /// Calls `b` defined in the `LinearProgram` trait.
+/// Wraps a call to `objectives` defined in the `MultiObjective` trait and as such allows to call
+/// `objectives` on an instance of `Problem`. Internally, the number of evaluations of `objectives`
+/// is counted.
+impl<O: MultiObjective> Problem<O> {
+    /// Calls `objectives` defined in the `MultiObjective` trait and keeps track of the number of
+    /// evaluations.
     ///
     /// # Example
     ///
     /// ```
-    /// # use argmin::core::{Problem, LinearProgram, Error};
+    /// # use argmin::core::{Problem, MultiObjective, Error};
     /// #
-    /// # #[derive(Eq, PartialEq, Debug, Clone)]
     /// # struct UserDefinedProblem {};
     /// #
-    /// # impl LinearProgram for UserDefinedProblem {
+    /// # impl MultiObjective for UserDefinedProblem {
     /// #     type Param = Vec<f64>;
     /// #     type Float = f64;
     /// #
-    /// #     fn c(&self) -> Result<Vec<Self::Float>, Error> {
-    /// #         Ok(vec![4.0f64, 3.0f64])
-    /// #     }
-    /// #
-    /// #     fn b(&self) -> Result<Vec<Self::Float>, Error> {
-    /// #         Ok(vec![3.0f64, 2.0f64])
-    /// #     }
-    /// #
-    /// #     fn A(&self) -> Result<Vec<Vec<Self::Float>>, Error> {
-    /// #         Ok(vec![vec![1.0f64, 2.0f64], vec![3.0f64, 2.0f64]])
+    /// #     fn objectives(&self, param: &Self::Param) -> Result<Vec<Self::Float>, Error> {
+    /// #         Ok(vec![param[0], 1.0 - param[0]])
     /// #     }
     /// # }
-    /// // `UserDefinedProblem` implements `LinearProgram`.
+    /// // `UserDefinedProblem` implements `MultiObjective`.
     /// let mut problem1 = Problem::new(UserDefinedProblem {});
     ///
-    /// let b = problem1.b();
+    /// let param = vec![0.25f64];
     ///
-    /// # assert_eq!(b.unwrap(), vec![3.0f64, 2.0f64]);
+    /// let res = problem1.objectives(&param);
+    ///
+    /// assert_eq!(problem1.counts["objectives_count"], 1);
+    /// # assert_eq!(res.unwrap(), vec![0.25f64, 0.75f64]);
     /// ```
-    pub fn b(&self) -> Result<Vec<O::Float>, Error> {
-        self.problem.as_ref().unwrap().b()
+    pub fn objectives(&mut self, param: &O::Param) -> Result<Vec<O::Float>, Error> {
+        self.problem("objectives_count", |problem| problem.objectives(param))
     }
 
-    /// Calls `A` defined in the `LinearProgram` trait.
+    /// Calls `bulk_objectives` defined in the `MultiObjective` trait and keeps track of the number
+    /// of evaluations.
     ///
     /// # Example
     ///
     /// ```
-    /// # use argmin::core::{Problem, LinearProgram, Error};
+    /// # use argmin::core::{Problem, MultiObjective, Error};
     /// #
-    /// # #[derive(Eq, PartialEq, Debug, Clone)]
     /// # struct UserDefinedProblem {};
     /// #
-    /// # impl LinearProgram for UserDefinedProblem {
+    /// # impl MultiObjective for UserDefinedProblem {
     /// #     type Param = Vec<f64>;
     /// #     type Float = f64;
     /// #
-    /// #     fn c(&self) -> Result<Vec<Self::Float>, Error> {
-    /// #         Ok(vec![4.0f64, 3.0f64])
-    /// #     }
-    /// #
-    /// #     fn b(&self) -> Result<Vec<Self::Float>, Error> {
-    /// #         Ok(vec![3.0f64, 2.0f64])
-    /// #     }
-    /// #
-    /// #     fn A(&self) -> Result<Vec<Vec<Self::Float>>, Error> {
-    /// #         Ok(vec![vec![1.0f64, 2.0f64], vec![3.0f64, 2.0f64]])
+    /// #     fn objectives(&self, param: &Self::Param) -> Result<Vec<Self::Float>, Error> {
+    /// #         Ok(vec![param[0], 1.0 - param[0]])
     /// #     }
     /// # }
-    /// // `UserDefinedProblem` implements `LinearProgram`.
+    /// // `UserDefinedProblem` implements `MultiObjective`.
     /// let mut problem1 = Problem::new(UserDefinedProblem {});
     ///
-    /// let a = problem1.A();
+    /// let param1 = vec![0.25f64];
+    /// let param2 = vec![0.5f64];
+    /// let params = vec![&param1, &param2];
     ///
-    /// # assert_eq!(a.unwrap(), vec![vec![1.0f64, 2.0f64], vec![3.0f64, 2.0f64]]);
+    /// let res = problem1.bulk_objectives(&params);
+    ///
+    /// assert_eq!(problem1.counts["objectives_count"], 2);
+    /// # let res = res.unwrap();
+    /// # assert_eq!(res[0], vec![0.25f64, 0.75f64]);
+    /// # assert_eq!(res[1], vec![0.5f64, 0.5f64]);
     /// ```
-    #[allow(non_snake_case)]
-    pub fn A(&self) -> Result<Vec<Vec<O::Float>>, Error> {
-        self.problem.as_ref().unwrap().A()
+    pub fn bulk_objectives<P>(&mut self, params: &[P]) -> Result<Vec<Vec<O::Float>>, Error>
+    where
+        P: std::borrow::Borrow<O::Param> + SyncAlias,
+        O::Float: SendAlias,
+        O: SyncAlias,
+    {
+        self.bulk_problem("objectives_count", params.len(), |problem| {
+            problem.bulk_objectives(params)
+        })
     }
 }
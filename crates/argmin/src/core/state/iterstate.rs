@@ -23,6 +23,8 @@ use std::collections::HashMap;
 /// * Jacobian of current and previous iteration
 /// * Hessian of current and previous iteration
 /// * inverse Hessian of current and previous iteration
+/// * residuals of current and previous iteration
+/// * constraint violation and Lagrange multiplier estimates of constrained problems
 /// * cost function value of current and previous iteration
 /// * current and previous best cost function value
 /// * target cost function value
@@ -74,6 +76,10 @@ pub struct IterState<P, G, J, H, R, F> {
     pub residuals: Option<R>,
     /// Value of residuals from previous call to apply
     pub prev_residuals: Option<R>,
+    /// Constraint violation of the current parameter vector
+    pub constraint_violation: Option<F>,
+    /// Lagrange multiplier estimates of the constraints
+    pub multipliers: Option<Vec<F>>,
     /// Current iteration
     pub iter: u64,
     /// Iteration number of last best cost
@@ -311,6 +317,41 @@ where
         self
     }
 
+    /// Set constraint violation of the current parameter vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, State};
+    /// # let state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+    /// # assert!(state.constraint_violation.is_none());
+    /// let state = state.constraint_violation(1e-3);
+    /// # assert_eq!(state.constraint_violation.unwrap().to_ne_bytes(), 1e-3f64.to_ne_bytes());
+    /// ```
+    #[must_use]
+    pub fn constraint_violation(mut self, constraint_violation: F) -> Self {
+        self.constraint_violation = Some(constraint_violation);
+        self
+    }
+
+    /// Set Lagrange multiplier estimates of the constraints.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, State};
+    /// # let state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+    /// # assert!(state.multipliers.is_none());
+    /// let state = state.multipliers(vec![1.0, 0.0]);
+    /// # assert_eq!(state.multipliers.as_ref().unwrap()[0].to_ne_bytes(), 1.0f64.to_ne_bytes());
+    /// # assert_eq!(state.multipliers.as_ref().unwrap()[1].to_ne_bytes(), 0.0f64.to_ne_bytes());
+    /// ```
+    #[must_use]
+    pub fn multipliers(mut self, multipliers: Vec<F>) -> Self {
+        self.multipliers = Some(multipliers);
+        self
+    }
+
     /// Returns the current cost function value
     ///
     /// # Example
@@ -970,6 +1011,56 @@ where
     pub fn take_prev_residuals(&mut self) -> Option<R> {
         self.prev_residuals.take()
     }
+
+    /// Returns the constraint violation of the current parameter vector
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, State};
+    /// # let mut state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+    /// # assert!(state.get_constraint_violation().is_none());
+    /// # state.constraint_violation = Some(1e-3);
+    /// let constraint_violation = state.get_constraint_violation();  // Option<F>
+    /// # assert_eq!(constraint_violation.unwrap().to_ne_bytes(), 1e-3f64.to_ne_bytes());
+    /// ```
+    pub fn get_constraint_violation(&self) -> Option<F> {
+        self.constraint_violation
+    }
+
+    /// Returns a reference to the Lagrange multiplier estimates
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, State};
+    /// # let mut state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+    /// # assert!(state.get_multipliers().is_none());
+    /// # state.multipliers = Some(vec![1.0, 2.0]);
+    /// let multipliers = state.get_multipliers();  // Option<&Vec<F>>
+    /// # assert_eq!(multipliers.unwrap()[0].to_ne_bytes(), 1.0f64.to_ne_bytes());
+    /// # assert_eq!(multipliers.unwrap()[1].to_ne_bytes(), 2.0f64.to_ne_bytes());
+    /// ```
+    pub fn get_multipliers(&self) -> Option<&Vec<F>> {
+        self.multipliers.as_ref()
+    }
+
+    /// Moves the Lagrange multiplier estimates out and replaces them internally with `None`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, State};
+    /// # let mut state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+    /// # state.multipliers = Some(vec![1.0, 2.0]);
+    /// let multipliers = state.take_multipliers();  // Option<Vec<F>>
+    /// # assert!(state.take_multipliers().is_none());
+    /// # assert_eq!(multipliers.as_ref().unwrap()[0].to_ne_bytes(), 1.0f64.to_ne_bytes());
+    /// # assert_eq!(multipliers.as_ref().unwrap()[1].to_ne_bytes(), 2.0f64.to_ne_bytes());
+    /// ```
+    pub fn take_multipliers(&mut self) -> Option<Vec<F>> {
+        self.multipliers.take()
+    }
 }
 
 impl<P, G, J, H, R, F> State for IterState<P, G, J, H, R, F>
@@ -1008,6 +1099,8 @@ where
     /// # assert!(state.prev_inv_hessian.is_none());
     /// # assert!(state.jacobian.is_none());
     /// # assert!(state.prev_jacobian.is_none());
+    /// # assert!(state.constraint_violation.is_none());
+    /// # assert!(state.multipliers.is_none());
     /// # assert_eq!(state.iter, 0);
     /// # assert_eq!(state.last_best_iter, 0);
     /// # assert_eq!(state.max_iters, std::u64::MAX);
@@ -1036,6 +1129,8 @@ where
             prev_jacobian: None,
             residuals: None,
             prev_residuals: None,
+            constraint_violation: None,
+            multipliers: None,
             iter: 0,
             last_best_iter: 0,
             max_iters: std::u64::MAX,
@@ -1454,6 +1549,8 @@ mod tests {
         assert!(state.get_prev_jacobian().is_none());
         assert!(state.get_residuals().is_none());
         assert!(state.get_prev_residuals().is_none());
+        assert!(state.get_constraint_violation().is_none());
+        assert!(state.get_multipliers().is_none());
         assert_eq!(state.get_iter(), 0);
 
         assert!(state.is_best());
@@ -1493,6 +1590,14 @@ mod tests {
         assert_eq!(*state.get_residuals().unwrap(), new_residuals);
         assert_eq!(*state.get_prev_residuals().unwrap(), residuals);
 
+        state = state.constraint_violation(1e-3).multipliers(vec![1.0, 0.0]);
+
+        assert_eq!(
+            state.get_constraint_violation().unwrap().to_ne_bytes(),
+            1e-3f64.to_ne_bytes()
+        );
+        assert_eq!(*state.get_multipliers().unwrap(), vec![1.0, 0.0]);
+
         let new_cost: f64 = 21.0;
 
         let mut state = state.cost(new_cost);
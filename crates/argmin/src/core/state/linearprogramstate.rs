@@ -25,6 +25,9 @@ use std::collections::HashMap;
 /// * current iteration number
 /// * iteration number where the last best parameter vector was found
 /// * maximum number of iterations that will be executed
+/// * basis (indices of the basic variables)
+/// * active set (indices of the active inequality constraints)
+/// * dual values (Lagrange multipliers of the constraints)
 /// * problem function evaluation counts (cost function, gradient, jacobian, hessian,
 /// * elapsed time
 /// * termination status
@@ -55,6 +58,12 @@ pub struct LinearProgramState<P, F> {
     pub last_best_iter: u64,
     /// Maximum number of iterations
     pub max_iters: u64,
+    /// Indices of basic variables
+    pub basis: Option<Vec<usize>>,
+    /// Indices of active inequality constraints
+    pub active_set: Option<Vec<usize>>,
+    /// Dual values (Lagrange multipliers of the constraints)
+    pub dual: Option<Vec<F>>,
     /// Evaluation counts
     pub counts: HashMap<String, u64>,
     /// Time required so far
@@ -151,6 +160,108 @@ impl<P, F> LinearProgramState<P, F> {
         self.cost = cost;
         self
     }
+
+    /// Set the indices of the basic variables.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{LinearProgramState, State};
+    /// # let state: LinearProgramState<Vec<f64>, f64> = LinearProgramState::new();
+    /// # assert!(state.basis.is_none());
+    /// # assert!(state.active_set.is_none());
+    /// let state = state.basis(vec![0, 2]);
+    /// # assert_eq!(state.basis.unwrap(), vec![0, 2]);
+    /// ```
+    #[must_use]
+    pub fn basis(mut self, basis: Vec<usize>) -> Self {
+        self.basis = Some(basis);
+        self
+    }
+
+    /// Set the indices of the active inequality constraints.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{LinearProgramState, State};
+    /// # let state: LinearProgramState<Vec<f64>, f64> = LinearProgramState::new();
+    /// # assert!(state.active_set.is_none());
+    /// let state = state.active_set(vec![1, 3]);
+    /// # assert_eq!(state.active_set.unwrap(), vec![1, 3]);
+    /// ```
+    #[must_use]
+    pub fn active_set(mut self, active_set: Vec<usize>) -> Self {
+        self.active_set = Some(active_set);
+        self
+    }
+
+    /// Set the dual values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{LinearProgramState, State};
+    /// # let state: LinearProgramState<Vec<f64>, f64> = LinearProgramState::new();
+    /// # assert!(state.dual.is_none());
+    /// let state = state.dual(vec![1.0, 2.0]);
+    /// # assert_eq!(state.dual.as_ref().unwrap()[0].to_ne_bytes(), 1.0f64.to_ne_bytes());
+    /// # assert_eq!(state.dual.as_ref().unwrap()[1].to_ne_bytes(), 2.0f64.to_ne_bytes());
+    /// ```
+    #[must_use]
+    pub fn dual(mut self, dual: Vec<F>) -> Self {
+        self.dual = Some(dual);
+        self
+    }
+
+    /// Returns a reference to the indices of the basic variables
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{LinearProgramState, State};
+    /// # let mut state: LinearProgramState<Vec<f64>, f64> = LinearProgramState::new();
+    /// # assert!(state.get_basis().is_none());
+    /// # state.basis = Some(vec![0, 2]);
+    /// let basis = state.get_basis();  // Option<&Vec<usize>>
+    /// # assert_eq!(*basis.unwrap(), vec![0, 2]);
+    /// ```
+    pub fn get_basis(&self) -> Option<&Vec<usize>> {
+        self.basis.as_ref()
+    }
+
+    /// Returns a reference to the indices of the active inequality constraints
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{LinearProgramState, State};
+    /// # let mut state: LinearProgramState<Vec<f64>, f64> = LinearProgramState::new();
+    /// # assert!(state.get_active_set().is_none());
+    /// # state.active_set = Some(vec![1, 3]);
+    /// let active_set = state.get_active_set();  // Option<&Vec<usize>>
+    /// # assert_eq!(*active_set.unwrap(), vec![1, 3]);
+    /// ```
+    pub fn get_active_set(&self) -> Option<&Vec<usize>> {
+        self.active_set.as_ref()
+    }
+
+    /// Returns a reference to the dual values
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{LinearProgramState, State};
+    /// # let mut state: LinearProgramState<Vec<f64>, f64> = LinearProgramState::new();
+    /// # assert!(state.get_dual().is_none());
+    /// # state.dual = Some(vec![1.0, 2.0]);
+    /// let dual = state.get_dual();  // Option<&Vec<F>>
+    /// # assert_eq!(dual.unwrap()[0].to_ne_bytes(), 1.0f64.to_ne_bytes());
+    /// # assert_eq!(dual.unwrap()[1].to_ne_bytes(), 2.0f64.to_ne_bytes());
+    /// ```
+    pub fn get_dual(&self) -> Option<&Vec<F>> {
+        self.dual.as_ref()
+    }
 }
 
 impl<P, F> State for LinearProgramState<P, F>
@@ -187,6 +298,8 @@ where
     /// # assert_eq!(state.iter, 0);
     /// # assert_eq!(state.last_best_iter, 0);
     /// # assert_eq!(state.max_iters, std::u64::MAX);
+    /// # assert!(state.basis.is_none());
+    /// # assert!(state.dual.is_none());
     /// # assert_eq!(state.counts, HashMap::new());
     /// # assert_eq!(state.time.unwrap(), instant::Duration::new(0, 0));
     /// # assert_eq!(state.termination_status, TerminationStatus::NotTerminated);
@@ -205,6 +318,9 @@ where
             iter: 0,
             last_best_iter: 0,
             max_iters: std::u64::MAX,
+            basis: None,
+            active_set: None,
+            dual: None,
             counts: HashMap::new(),
             time: Some(instant::Duration::new(0, 0)),
             termination_status: TerminationStatus::NotTerminated,
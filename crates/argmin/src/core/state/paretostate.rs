@@ -0,0 +1,615 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, Problem, State, TerminationReason, TerminationStatus};
+use instant;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Maintains the state from iteration to iteration of a multi-objective solver
+///
+/// This struct is passed from one iteration of an algorithm to the next.
+///
+/// Keeps track of
+///
+/// * population and objective values of its members
+/// * Pareto front (non-dominated members of the population) and its objective values
+/// * current iteration number
+/// * maximum number of iterations that will be executed
+/// * problem function evaluation counts
+/// * elapsed time
+/// * termination status
+///
+/// A multi-objective problem does not have a single cost function value. The current and best
+/// parameter vectors are therefore the member of the Pareto front with the lexicographically
+/// smallest objective values, and [`get_cost`](`State::get_cost`) and
+/// [`get_best_cost`](`State::get_best_cost`) return its first objective value, which is the
+/// smallest value of the first objective on the Pareto front. Both are `Inf` as long as there is
+/// no Pareto front. Every new Pareto front is considered to be the best one so far. Observers
+/// interested in the Pareto front as a whole should use
+/// [`get_pareto_front`](`ParetoState::get_pareto_front`) and
+/// [`get_pareto_objectives`](`ParetoState::get_pareto_objectives`), or the `front_size` KV metric
+/// reported by the solvers.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct ParetoState<P, F> {
+    /// All members of the population
+    pub population: Option<Vec<P>>,
+    /// Objective values of all members of the population
+    pub objectives: Option<Vec<Vec<F>>>,
+    /// Non-dominated members of the population
+    pub pareto_front: Option<Vec<P>>,
+    /// Objective values of the members of the Pareto front
+    pub pareto_objectives: Option<Vec<Vec<F>>>,
+    /// Current iteration
+    pub iter: u64,
+    /// Iteration number of last best Pareto front
+    pub last_best_iter: u64,
+    /// Maximum number of iterations
+    pub max_iters: u64,
+    /// Evaluation counts
+    pub counts: HashMap<String, u64>,
+    /// Time required so far
+    pub time: Option<instant::Duration>,
+    /// Status of optimization execution
+    pub termination_status: TerminationStatus,
+}
+
+impl<P, F> ParetoState<P, F> {
+    /// Set population.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # assert!(state.population.is_none());
+    /// let state = state.population(vec![vec![0.0f64, 1.0], vec![2.0, 3.0]]);
+    /// # assert_eq!(state.population.as_ref().unwrap()[1][0].to_ne_bytes(), 2.0f64.to_ne_bytes());
+    /// ```
+    #[must_use]
+    pub fn population(mut self, population: Vec<P>) -> Self {
+        self.population = Some(population);
+        self
+    }
+
+    /// Set objective values of all members of the population.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # assert!(state.objectives.is_none());
+    /// let state = state.objectives(vec![vec![1.0f64, 4.0], vec![2.0, 3.0]]);
+    /// # assert_eq!(state.objectives.as_ref().unwrap()[1][0].to_ne_bytes(), 2.0f64.to_ne_bytes());
+    /// ```
+    #[must_use]
+    pub fn objectives(mut self, objectives: Vec<Vec<F>>) -> Self {
+        self.objectives = Some(objectives);
+        self
+    }
+
+    /// Set Pareto front and the objective values of its members.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # assert!(state.pareto_front.is_none());
+    /// let state = state.pareto_front(vec![vec![0.0f64, 1.0]], vec![vec![1.0f64, 4.0]]);
+    /// # assert_eq!(state.pareto_front.as_ref().unwrap()[0][1].to_ne_bytes(), 1.0f64.to_ne_bytes());
+    /// # assert_eq!(state.pareto_objectives.as_ref().unwrap()[0][1].to_ne_bytes(), 4.0f64.to_ne_bytes());
+    /// ```
+    #[must_use]
+    pub fn pareto_front(mut self, pareto_front: Vec<P>, objectives: Vec<Vec<F>>) -> Self {
+        self.pareto_front = Some(pareto_front);
+        self.pareto_objectives = Some(objectives);
+        self
+    }
+
+    /// Set maximum number of iterations
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # assert_eq!(state.max_iters, u64::MAX);
+    /// let state = state.max_iters(1000);
+    /// # assert_eq!(state.max_iters, 1000);
+    /// ```
+    #[must_use]
+    pub fn max_iters(mut self, iters: u64) -> Self {
+        self.max_iters = iters;
+        self
+    }
+
+    /// Returns a reference to the population
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # assert!(state.get_population().is_none());
+    /// # let state = state.population(vec![vec![0.0f64, 1.0]]);
+    /// let population = state.get_population();  // Option<&Vec<P>>
+    /// # assert_eq!(population.unwrap()[0][1].to_ne_bytes(), 1.0f64.to_ne_bytes());
+    /// ```
+    pub fn get_population(&self) -> Option<&Vec<P>> {
+        self.population.as_ref()
+    }
+
+    /// Takes population and replaces it internally with `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # let mut state = state.population(vec![vec![0.0f64, 1.0]]);
+    /// let population = state.take_population();  // Option<Vec<P>>
+    /// # assert!(state.population.is_none());
+    /// # assert_eq!(population.unwrap()[0][1].to_ne_bytes(), 1.0f64.to_ne_bytes());
+    /// ```
+    pub fn take_population(&mut self) -> Option<Vec<P>> {
+        self.population.take()
+    }
+
+    /// Returns a reference to the objective values of all members of the population
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # assert!(state.get_objectives().is_none());
+    /// # let state = state.objectives(vec![vec![1.0f64, 4.0]]);
+    /// let objectives = state.get_objectives();  // Option<&Vec<Vec<F>>>
+    /// # assert_eq!(objectives.unwrap()[0][1].to_ne_bytes(), 4.0f64.to_ne_bytes());
+    /// ```
+    pub fn get_objectives(&self) -> Option<&Vec<Vec<F>>> {
+        self.objectives.as_ref()
+    }
+
+    /// Takes objective values of the population and replaces them internally with `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # let mut state = state.objectives(vec![vec![1.0f64, 4.0]]);
+    /// let objectives = state.take_objectives();  // Option<Vec<Vec<F>>>
+    /// # assert!(state.objectives.is_none());
+    /// # assert_eq!(objectives.unwrap()[0][1].to_ne_bytes(), 4.0f64.to_ne_bytes());
+    /// ```
+    pub fn take_objectives(&mut self) -> Option<Vec<Vec<F>>> {
+        self.objectives.take()
+    }
+
+    /// Returns a reference to the Pareto front
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # assert!(state.get_pareto_front().is_none());
+    /// # let state = state.pareto_front(vec![vec![0.0f64, 1.0]], vec![vec![1.0f64, 4.0]]);
+    /// let pareto_front = state.get_pareto_front();  // Option<&Vec<P>>
+    /// # assert_eq!(pareto_front.unwrap()[0][1].to_ne_bytes(), 1.0f64.to_ne_bytes());
+    /// ```
+    pub fn get_pareto_front(&self) -> Option<&Vec<P>> {
+        self.pareto_front.as_ref()
+    }
+
+    /// Returns a reference to the objective values of the members of the Pareto front
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # assert!(state.get_pareto_objectives().is_none());
+    /// # let state = state.pareto_front(vec![vec![0.0f64, 1.0]], vec![vec![1.0f64, 4.0]]);
+    /// let pareto_objectives = state.get_pareto_objectives();  // Option<&Vec<Vec<F>>>
+    /// # assert_eq!(pareto_objectives.unwrap()[0][1].to_ne_bytes(), 4.0f64.to_ne_bytes());
+    /// ```
+    pub fn get_pareto_objectives(&self) -> Option<&Vec<Vec<F>>> {
+        self.pareto_objectives.as_ref()
+    }
+
+    /// Returns the index of the member of the Pareto front with the lexicographically smallest
+    /// objective values
+    fn lexicographic_best(&self) -> Option<usize>
+    where
+        F: PartialOrd,
+    {
+        let front = self
+            .pareto_front
+            .as_ref()
+            .filter(|front| !front.is_empty())?;
+        let best = self
+            .pareto_objectives
+            .as_ref()
+            .filter(|objectives| objectives.len() == front.len())
+            .and_then(|objectives| {
+                objectives
+                    .iter()
+                    .enumerate()
+                    .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+                    .map(|(i, _)| i)
+            });
+        Some(best.unwrap_or(0))
+    }
+}
+
+impl<P, F> State for ParetoState<P, F>
+where
+    P: Clone,
+    F: ArgminFloat,
+{
+    /// Type of parameter vector
+    type Param = P;
+    /// Floating point precision
+    type Float = F;
+
+    /// Create new `ParetoState` instance
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate instant;
+    /// # use instant;
+    /// # use std::collections::HashMap;
+    /// # use argmin::core::TerminationStatus;
+    /// use argmin::core::{ParetoState, State};
+    /// let state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    ///
+    /// # assert!(state.population.is_none());
+    /// # assert!(state.objectives.is_none());
+    /// # assert!(state.pareto_front.is_none());
+    /// # assert!(state.pareto_objectives.is_none());
+    /// # assert_eq!(state.iter, 0);
+    /// # assert_eq!(state.last_best_iter, 0);
+    /// # assert_eq!(state.max_iters, u64::MAX);
+    /// # assert_eq!(state.counts, HashMap::new());
+    /// # assert_eq!(state.time.unwrap(), instant::Duration::new(0, 0));
+    /// # assert_eq!(state.termination_status, TerminationStatus::NotTerminated);
+    /// ```
+    fn new() -> Self {
+        ParetoState {
+            population: None,
+            objectives: None,
+            pareto_front: None,
+            pareto_objectives: None,
+            iter: 0,
+            last_best_iter: 0,
+            max_iters: u64::MAX,
+            counts: HashMap::new(),
+            time: Some(instant::Duration::new(0, 0)),
+            termination_status: TerminationStatus::NotTerminated,
+        }
+    }
+
+    /// Marks the current Pareto front as the best one so far.
+    ///
+    /// Pareto fronts cannot be ranked by a single cost function value, therefore every new Pareto
+    /// front is considered to be the best one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// state.iter = 3;
+    /// # assert!(!state.is_best());
+    ///
+    /// state.update();
+    ///
+    /// assert_eq!(state.last_best_iter, 3);
+    /// assert!(state.is_best());
+    /// ```
+    fn update(&mut self) {
+        self.last_best_iter = self.iter;
+    }
+
+    /// Returns a reference to the member of the Pareto front with the lexicographically smallest
+    /// objective values
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # assert!(state.get_param().is_none());
+    /// # let state = state.pareto_front(vec![vec![0.0f64, 1.0], vec![2.0, 3.0]], vec![vec![2.0f64, 3.0], vec![1.0, 4.0]]);
+    /// let param = state.get_param();  // Option<&P>
+    /// # assert_eq!(param.unwrap()[1].to_ne_bytes(), 3.0f64.to_ne_bytes());
+    /// ```
+    fn get_param(&self) -> Option<&P> {
+        let best = self.lexicographic_best()?;
+        self.pareto_front.as_ref().map(|front| &front[best])
+    }
+
+    /// Returns a reference to the member of the Pareto front with the lexicographically smallest
+    /// objective values
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # assert!(state.get_best_param().is_none());
+    /// # let state = state.pareto_front(vec![vec![0.0f64, 1.0], vec![2.0, 3.0]], vec![vec![2.0f64, 3.0], vec![1.0, 4.0]]);
+    /// let best_param = state.get_best_param();  // Option<&P>
+    /// # assert_eq!(best_param.unwrap()[1].to_ne_bytes(), 3.0f64.to_ne_bytes());
+    /// ```
+    fn get_best_param(&self) -> Option<&P> {
+        self.get_param()
+    }
+
+    /// Sets the termination status to [`Terminated`](`TerminationStatus::Terminated`) with the given reason
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State, TerminationReason, TerminationStatus};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # assert_eq!(state.termination_status, TerminationStatus::NotTerminated);
+    /// let state = state.terminate_with(TerminationReason::MaxItersReached);
+    /// # assert_eq!(state.termination_status, TerminationStatus::Terminated(TerminationReason::MaxItersReached));
+    /// ```
+    fn terminate_with(mut self, reason: TerminationReason) -> Self {
+        self.termination_status = TerminationStatus::Terminated(reason);
+        self
+    }
+
+    /// Sets the time required so far.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate instant;
+    /// # use instant;
+    /// # use argmin::core::{ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// let state = state.time(Some(instant::Duration::new(0, 12)));
+    /// # assert_eq!(state.time.unwrap(), instant::Duration::new(0, 12));
+    /// ```
+    fn time(&mut self, time: Option<instant::Duration>) -> &mut Self {
+        self.time = time;
+        self
+    }
+
+    /// Returns the first objective value of the current parameter vector, which is the smallest
+    /// value of the first objective on the Pareto front. Returns `Inf` if there is no Pareto front.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # assert_eq!(state.get_cost().to_ne_bytes(), f64::INFINITY.to_ne_bytes());
+    /// # let state = state.pareto_front(vec![vec![0.0f64, 1.0], vec![2.0, 3.0]], vec![vec![2.0f64, 3.0], vec![1.0, 4.0]]);
+    /// let cost = state.get_cost();
+    /// # assert_eq!(cost.to_ne_bytes(), 1.0f64.to_ne_bytes());
+    /// ```
+    fn get_cost(&self) -> Self::Float {
+        self.lexicographic_best()
+            .and_then(|best| self.pareto_objectives.as_ref()?.get(best)?.first().copied())
+            .unwrap_or_else(Self::Float::infinity)
+    }
+
+    /// Returns the first objective value of the best parameter vector, which is the smallest value
+    /// of the first objective on the Pareto front. Returns `Inf` if there is no Pareto front.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # assert_eq!(state.get_best_cost().to_ne_bytes(), f64::INFINITY.to_ne_bytes());
+    /// # let state = state.pareto_front(vec![vec![0.0f64, 1.0], vec![2.0, 3.0]], vec![vec![2.0f64, 3.0], vec![1.0, 4.0]]);
+    /// let best_cost = state.get_best_cost();
+    /// # assert_eq!(best_cost.to_ne_bytes(), 1.0f64.to_ne_bytes());
+    /// ```
+    fn get_best_cost(&self) -> Self::Float {
+        self.get_cost()
+    }
+
+    /// Returns `-Inf`, since there is no single cost function value to compare to.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// let target_cost = state.get_target_cost();
+    /// # assert_eq!(target_cost.to_ne_bytes(), f64::NEG_INFINITY.to_ne_bytes());
+    /// ```
+    fn get_target_cost(&self) -> Self::Float {
+        Self::Float::neg_infinity()
+    }
+
+    /// Returns current number of iterations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # state.iter = 12;
+    /// let iter = state.get_iter();
+    /// # assert_eq!(iter, 12);
+    /// ```
+    fn get_iter(&self) -> u64 {
+        self.iter
+    }
+
+    /// Returns iteration number of last best Pareto front.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # state.last_best_iter = 12;
+    /// let last_best_iter = state.get_last_best_iter();
+    /// # assert_eq!(last_best_iter, 12);
+    /// ```
+    fn get_last_best_iter(&self) -> u64 {
+        self.last_best_iter
+    }
+
+    /// Returns the maximum number of iterations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # state.max_iters = 12;
+    /// let max_iters = state.get_max_iters();
+    /// # assert_eq!(max_iters, 12);
+    /// ```
+    fn get_max_iters(&self) -> u64 {
+        self.max_iters
+    }
+
+    /// Returns the termination status.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State, TerminationStatus};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// let termination_status = state.get_termination_status();
+    /// # assert_eq!(*termination_status, TerminationStatus::NotTerminated);
+    /// ```
+    fn get_termination_status(&self) -> &TerminationStatus {
+        &self.termination_status
+    }
+
+    /// Returns the termination reason if terminated, otherwise None.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State, TerminationReason};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// let termination_reason = state.get_termination_reason();
+    /// # assert_eq!(termination_reason, None);
+    /// ```
+    fn get_termination_reason(&self) -> Option<&TerminationReason> {
+        match &self.termination_status {
+            TerminationStatus::Terminated(reason) => Some(reason),
+            TerminationStatus::NotTerminated => None,
+        }
+    }
+
+    /// Returns the time elapsed since the start of the optimization.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate instant;
+    /// # use instant;
+    /// # use argmin::core::{ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// let time = state.get_time();
+    /// # assert_eq!(time.unwrap(), instant::Duration::new(0, 0));
+    /// ```
+    fn get_time(&self) -> Option<instant::Duration> {
+        self.time
+    }
+
+    /// Increments the number of iterations by one
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # assert_eq!(state.iter, 0);
+    /// state.increment_iter();
+    /// # assert_eq!(state.iter, 1);
+    /// ```
+    fn increment_iter(&mut self) {
+        self.iter += 1;
+    }
+
+    /// Set all function evaluation counts to the evaluation counts of another `Problem`.
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// # use argmin::core::{Problem, ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # assert_eq!(state.counts, HashMap::new());
+    /// # state.counts.insert("test2".to_string(), 10u64);
+    /// #
+    /// # #[derive(Eq, PartialEq, Debug)]
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # let mut problem = Problem::new(UserDefinedProblem {});
+    /// # problem.counts.insert("test1", 10u64);
+    /// # problem.counts.insert("test2", 2);
+    /// state.func_counts(&problem);
+    /// # let mut hm = HashMap::new();
+    /// # hm.insert("test1".to_string(), 10u64);
+    /// # hm.insert("test2".to_string(), 2u64);
+    /// # assert_eq!(state.counts, hm);
+    /// ```
+    fn func_counts<O>(&mut self, problem: &Problem<O>) {
+        for (k, &v) in problem.counts.iter() {
+            let count = self.counts.entry(k.to_string()).or_insert(0);
+            *count = v
+        }
+    }
+
+    /// Returns function evaluation counts
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// # use argmin::core::{ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # assert_eq!(state.counts, HashMap::new());
+    /// # state.counts.insert("test2".to_string(), 10u64);
+    /// let counts = state.get_func_counts();
+    /// # let mut hm = HashMap::new();
+    /// # hm.insert("test2".to_string(), 10u64);
+    /// # assert_eq!(*counts, hm);
+    /// ```
+    fn get_func_counts(&self) -> &HashMap<String, u64> {
+        &self.counts
+    }
+
+    /// Returns whether the current Pareto front is also the best one found so far.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # state.last_best_iter = 12;
+    /// # state.iter = 12;
+    /// let is_best = state.is_best();
+    /// # assert!(is_best);
+    /// # state.iter = 21;
+    /// # let is_best = state.is_best();
+    /// # assert!(!is_best);
+    /// ```
+    fn is_best(&self) -> bool {
+        self.last_best_iter == self.iter
+    }
+}
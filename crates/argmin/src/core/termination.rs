@@ -32,6 +32,10 @@ impl TerminationStatus {
     /// assert!(TerminationStatus::Terminated(TerminationReason::SolverConverged).terminated());
     /// assert!(TerminationStatus::Terminated(TerminationReason::Interrupt).terminated());
     /// assert!(TerminationStatus::Terminated(TerminationReason::Timeout).terminated());
+    /// assert!(TerminationStatus::Terminated(TerminationReason::Infeasible).terminated());
+    /// assert!(TerminationStatus::Terminated(TerminationReason::Unbounded).terminated());
+    /// assert!(TerminationStatus::Terminated(TerminationReason::BracketLost).terminated());
+    /// assert!(TerminationStatus::Terminated(TerminationReason::DerivativeVanished).terminated());
     /// assert!(TerminationStatus::Terminated(TerminationReason::SolverExit("Exit reason".to_string())).terminated());
     /// ```
     pub fn terminated(&self) -> bool {
@@ -62,6 +66,15 @@ pub enum TerminationReason {
     SolverConverged,
     /// Timeout reached
     Timeout,
+    /// Problem has no feasible solution
+    Infeasible,
+    /// Objective function is unbounded from below on the feasible set
+    Unbounded,
+    /// Iterate left the interval known to contain a root, or the interval no longer brackets a
+    /// root
+    BracketLost,
+    /// Derivative vanished, therefore no further step could be computed
+    DerivativeVanished,
     /// Solver exit with given reason
     SolverExit(String),
 }
@@ -95,6 +108,22 @@ impl TerminationReason {
     ///     "Timeout reached"
     /// );
     /// assert_eq!(
+    ///     TerminationReason::Infeasible.text(),
+    ///     "Problem is infeasible"
+    /// );
+    /// assert_eq!(
+    ///     TerminationReason::Unbounded.text(),
+    ///     "Problem is unbounded"
+    /// );
+    /// assert_eq!(
+    ///     TerminationReason::BracketLost.text(),
+    ///     "Bracket lost"
+    /// );
+    /// assert_eq!(
+    ///     TerminationReason::DerivativeVanished.text(),
+    ///     "Derivative vanished"
+    /// );
+    /// assert_eq!(
     ///     TerminationReason::SolverExit("Aborted".to_string()).text(),
     ///     "Aborted"
     /// );
@@ -106,6 +135,10 @@ impl TerminationReason {
             TerminationReason::Interrupt => "Interrupt",
             TerminationReason::SolverConverged => "Solver converged",
             TerminationReason::Timeout => "Timeout reached",
+            TerminationReason::Infeasible => "Problem is infeasible",
+            TerminationReason::Unbounded => "Problem is unbounded",
+            TerminationReason::BracketLost => "Bracket lost",
+            TerminationReason::DerivativeVanished => "Derivative vanished",
             TerminationReason::SolverExit(reason) => reason.as_ref(),
         }
     }
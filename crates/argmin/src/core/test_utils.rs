@@ -6,7 +6,8 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::core::{
-    CostFunction, Error, Gradient, Hessian, IterState, Jacobian, Operator, Problem, Solver, KV,
+    CostFunction, Error, Gradient, Hessian, HessianVectorProduct, IterState, Jacobian, Operator,
+    Problem, Solver, KV,
 };
 use crate::solver::simulatedannealing::Anneal;
 #[cfg(feature = "serde1")]
@@ -15,8 +16,8 @@ use std::fmt::Debug;
 
 /// Pseudo problem useful for testing
 ///
-/// Implements [`CostFunction`], [`Operator`], [`Gradient`], [`Jacobian`], [`Hessian`], and
-/// [`Anneal`].
+/// Implements [`CostFunction`], [`Operator`], [`Gradient`], [`Jacobian`], [`Hessian`],
+/// [`HessianVectorProduct`] and [`Anneal`].
 #[derive(Clone, Copy, Default, Debug, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
 pub struct TestProblem {}
@@ -150,6 +151,35 @@ impl Hessian for TestProblem {
     }
 }
 
+impl HessianVectorProduct for TestProblem {
+    type Param = Vec<f64>;
+    type Output = Vec<f64>;
+
+    /// Returns a clone of `vec`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin::core::test_utils::TestProblem;
+    /// use argmin::core::HessianVectorProduct;
+    /// # use argmin::core::Error;
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let problem = TestProblem::new();
+    ///
+    /// let param = vec![1.0, 2.0];
+    /// let vec = vec![3.0, 4.0];
+    ///
+    /// let res = problem.hessian_vec(&param, &vec)?;
+    /// # assert_eq!(res, vec);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn hessian_vec(&self, _p: &Self::Param, vec: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(vec.clone())
+    }
+}
+
 impl Jacobian for TestProblem {
     type Param = Vec<f64>;
     type Jacobian = Vec<Vec<f64>>;
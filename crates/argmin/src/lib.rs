@@ -45,9 +45,23 @@
 //!   - [Cauchy point method](`crate::solver::trustregion::CauchyPoint`)
 //!   - [Dogleg method](`crate::solver::trustregion::Dogleg`)
 //!   - [Steihaug method](`crate::solver::trustregion::Steihaug`)
+//!   - [Moré-Sorensen method](`crate::solver::trustregion::MoreSorensen`)
 //!   
 //! - [Steepest descent](`crate::solver::gradientdescent::SteepestDescent`)
 //!
+//! - [Proximal gradient method (ISTA/FISTA)](`crate::solver::proximalgradient::ProximalGradient`)
+//!
+//! - [Coordinate descent](`crate::solver::coordinatedescent::CoordinateDescent`)
+//!
+//! - [Frank-Wolfe methods](`crate::solver::frankwolfe`)
+//!   - [Frank-Wolfe method](`crate::solver::frankwolfe::FrankWolfe`)
+//!   - [Frank-Wolfe method with line search](`crate::solver::frankwolfe::FrankWolfeLS`)
+//!
+//! - [Stochastic gradient methods](`crate::solver::stochastic`)
+//!   - [SGD with (Nesterov) momentum](`crate::solver::stochastic::SGD`)
+//!   - [Adam/AdamW](`crate::solver::stochastic::Adam`)
+//!   - [RMSProp](`crate::solver::stochastic::RMSProp`)
+//!
 //! - [Conjugate gradient methods](`crate::solver::conjugategradient`)
 //!   - [Conjugate gradient method](`crate::solver::conjugategradient::ConjugateGradient`)
 //!   - [Nonlinear conjugate gradient method](`crate::solver::conjugategradient::NonlinearConjugateGradient`)
@@ -59,6 +73,7 @@
 //! - [Quasi-Newton methods](`crate::solver::quasinewton`)
 //!   - [BFGS](`crate::solver::quasinewton::BFGS`)
 //!   - [L-BFGS](`crate::solver::quasinewton::LBFGS`)
+//!   - [L-BFGS-B](`crate::solver::quasinewton::LBFGSB`)
 //!   - [DFP](`crate::solver::quasinewton::DFP`)
 //!   - [SR1](`crate::solver::quasinewton::SR1`)
 //!   - [SR1-TrustRegion](`crate::solver::quasinewton::SR1TrustRegion`)
@@ -67,6 +82,13 @@
 //!   - [Gauss-Newton method](`crate::solver::gaussnewton::GaussNewton`)
 //!   - [Gauss-Newton method with linesearch](`crate::solver::gaussnewton::GaussNewtonLS`)
 //!
+//! - [Levenberg-Marquardt method](`crate::solver::levenbergmarquardt::LevenbergMarquardt`)
+//!
+//! - [Nonlinear systems of equations](`crate::solver::nonlinearsystem`)
+//!   - [Damped Newton method](`crate::solver::nonlinearsystem::DampedNewton`)
+//!   - [Broyden's method](`crate::solver::nonlinearsystem::Broyden`)
+//!   - [Newton-Krylov method](`crate::solver::nonlinearsystem::NewtonKrylov`)
+//!
 //! - [Golden-section search](`crate::solver::goldensectionsearch::GoldenSectionSearch`)
 //!
 //! - [Landweber iteration](`crate::solver::landweber::Landweber`)
@@ -75,12 +97,59 @@
 //!   - [Brent's minimization method](`crate::solver::brent::BrentOpt`)
 //!   - [Brent's root finding method](`crate::solver::brent::BrentRoot`)
 //!
+//! - [Scalar root finding methods](`crate::solver::rootfinding`)
+//!   - [Bisection](`crate::solver::rootfinding::Bisection`)
+//!   - [Regula falsi (with Illinois modification)](`crate::solver::rootfinding::RegulaFalsi`)
+//!   - [ITP method](`crate::solver::rootfinding::ITP`)
+//!   - [Newton-Raphson method](`crate::solver::rootfinding::NewtonRaphson`)
+//!   - [Secant method](`crate::solver::rootfinding::Secant`)
+//!   - [Halley's method](`crate::solver::rootfinding::Halley`)
+//!
 //! - [Nelder-Mead method](`crate::solver::neldermead::NelderMead`)
 //!
+//! - [Direction set methods](`crate::solver::powell`)
+//!   - [Powell's method](`crate::solver::powell::Powell`)
+//!   - [PRAXIS](`crate::solver::powell::Praxis`)
+//!
+//! - [Pattern search methods](`crate::solver::patternsearch`)
+//!   - [Hooke-Jeeves method](`crate::solver::patternsearch::HookeJeeves`)
+//!   - [Generalized pattern search](`crate::solver::patternsearch::PatternSearch`)
+//!   - [Mesh adaptive direct search](`crate::solver::patternsearch::MADS`)
+//!
+//! - [BOBYQA](`crate::solver::bobyqa::BOBYQA`)
+//!
 //! - [Simulated Annealing](`crate::solver::simulatedannealing::SimulatedAnnealing`)
 //!
+//! - [Parallel Tempering](`crate::solver::paralleltempering::ParallelTempering`)
+//!
 //! - [Particle Swarm Optimization](`crate::solver::particleswarm::ParticleSwarm`)
 //!
+//! - [CMA-ES](`crate::solver::cmaes::CMAES`)
+//!
+//! - [Differential Evolution](`crate::solver::differentialevolution::DifferentialEvolution`)
+//!
+//! - [Genetic Algorithm](`crate::solver::geneticalgorithm::GeneticAlgorithm`)
+//!
+//! - [Bayesian optimization](`crate::solver::bayesopt::BayesOpt`)
+//!
+//! - Global optimization meta-solvers
+//!   - [Multi-start](`crate::solver::globalopt::MultiStart`)
+//!   - [Basin-hopping](`crate::solver::globalopt::BasinHopping`)
+//!
+//! - [Multi-objective evolutionary algorithms](`crate::solver::nsga`)
+//!   - [NSGA-II](`crate::solver::nsga::NSGAII`)
+//!   - [NSGA-III](`crate::solver::nsga::NSGAIII`)
+//!
+//! - [Simplex method](`crate::solver::simplex::Simplex`)
+//!
+//! - [Interior point method](`crate::solver::interiorpoint::InteriorPoint`)
+//!
+//! - [Active set method](`crate::solver::activeset::ActiveSet`)
+//!
+//! - [Augmented Lagrangian method](`crate::solver::augmentedlagrangian::AugmentedLagrangian`)
+//!
+//! - [Sequential quadratic programming (SQP)](`crate::solver::sqp::SQP`)
+//!
 //! ## External solvers compatible with argmin
 //!
 //! External solvers which implement the `Solver` trait are compatible with argmins `Executor`,
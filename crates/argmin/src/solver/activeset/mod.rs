@@ -0,0 +1,914 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Active set method
+//!
+//! Dual active set method (Goldfarb-Idnani) for strictly convex quadratic programs with linear
+//! equality and inequality constraints.
+//!
+//! See [`ActiveSet`] for details.
+//!
+//! ## References
+//!
+//! Donald Goldfarb and Ashok Idnani (1983). A numerically stable dual method for solving strictly
+//! convex quadratic programs. Mathematical Programming 27, 1–33.
+//! DOI: <https://doi.org/10.1007/BF02591962>
+//!
+//! Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+//! Springer. ISBN 0-387-30303-0.
+
+use crate::core::{
+    ArgminFloat, Error, LinearProgramState, Problem, QuadraticProgram, Solver, State,
+    TerminationReason, TerminationStatus, KV,
+};
+use argmin_math::{
+    ArgminCholesky, ArgminCholeskySolve, ArgminDot, ArgminEye, ArgminGet, ArgminMul,
+    ArgminScaledAdd, ArgminSet, ArgminSize,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Active set method
+///
+/// Dual active set method of Goldfarb and Idnani for strictly convex quadratic programs
+///
+/// ```text
+/// min_x  1/2 x^T H x + g^T x
+/// s.t.   A_eq x = b_eq
+///        A_ineq x <= b_ineq
+/// ```
+///
+/// (see [`QuadraticProgram`]). The method starts from the minimizer subject to the equality
+/// constraints only and keeps a working set of constraints which are satisfied as equalities and
+/// whose Lagrange multipliers are non-negative. Each iteration adds the most violated inequality
+/// constraint to the working set. On the way, constraints whose multipliers would become negative
+/// are dropped from the working set. The objective function value increases monotonically and no
+/// feasible starting point is required. The solver terminates once no constraint is violated by
+/// more than the tolerance, or with [`TerminationReason::Infeasible`] if the constraints cannot be
+/// satisfied.
+///
+/// The linear systems are solved via Cholesky factorizations of `H` and of the (small) matrix
+/// `N^T H^-1 N`, where the columns of `N` are the normals of the constraints in the working set.
+/// `H` is factorized once during initialization. In contrast to the original method of Goldfarb
+/// and Idnani, the factorization of `N^T H^-1 N` is not updated incrementally but recomputed
+/// whenever the working set changes, which is cheap as long as the working set is small. All
+/// products and factorizations are computed with the vector and matrix types of the problem,
+/// which allows the use of the linear algebra backends of `argmin-math` (for instance
+/// `ndarray::Array2<f64>` or `nalgebra::DMatrix<f64>`).
+///
+/// ## Warm start
+///
+/// The indices of the inequality constraints of an initial working set (for instance the active
+/// set of a previous, similar problem) can be provided via
+/// [`LinearProgramState::active_set`]. These constraints are added to the working set during
+/// initialization (unless they are linearly dependent on the constraints which were already
+/// added) and constraints with negative multipliers are dropped again. If the initial working set
+/// is the optimal active set, the solver terminates without any iteration. The initial parameter
+/// vector is not used.
+///
+/// ## Results
+///
+/// Parameter vector and cost function value are stored in the [`LinearProgramState`]. The cost
+/// function value is infinity as long as the current parameter vector violates any constraint.
+/// The indices of the active inequality constraints are available via
+/// [`LinearProgramState::get_active_set`], the Lagrange multipliers via
+/// [`LinearProgramState::get_dual`]: the multipliers `y` of the equality constraints are followed
+/// by the (non-negative) multipliers `z` of the inequality constraints, such that
+/// `H x + g + A_eq^T y + A_ineq^T z = 0`. The index of the constraint added in an iteration, the
+/// number of dropped constraints and the size of the working set are reported to the observers as
+/// `added`, `dropped` and `working_set`.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`QuadraticProgram`] with vector type `P`
+/// and matrix type `M`. The matrix `H` must be symmetric positive definite. `M` needs to
+/// implement [`ArgminCholesky`] and [`ArgminCholeskySolve`]; for `ndarray` this requires
+/// `ndarray-linalg` support.
+///
+/// ## References
+///
+/// Donald Goldfarb and Ashok Idnani (1983). A numerically stable dual method for solving strictly
+/// convex quadratic programs. Mathematical Programming 27, 1–33.
+/// DOI: <https://doi.org/10.1007/BF02591962>
+///
+/// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+/// Springer. ISBN 0-387-30303-0.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct ActiveSet<P, M, F> {
+    /// Tolerance for constraint violations
+    tol: F,
+    /// Quadratic term of objective function
+    h: Option<M>,
+    /// Cholesky factor of `h`
+    h_chol: Option<M>,
+    /// Linear term of objective function
+    g: Option<P>,
+    /// Normals `n_i` of all constraints in the form `n_i^T x >= c_i`, equality constraints first
+    normals: Vec<P>,
+    /// `H^-1 n_i` for all constraints
+    hinv_normals: Vec<P>,
+    /// Right-hand sides `c_i` of all constraints
+    rhs: Vec<F>,
+    /// Number of equality constraints
+    num_eq: usize,
+    /// Indices of the constraints in the working set
+    working_set: Vec<usize>,
+    /// Lagrange multipliers of the constraints in the working set
+    u: Vec<F>,
+    /// Current parameter vector
+    x: Option<P>,
+}
+
+impl<P, M, F> ActiveSet<P, M, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `ActiveSet`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::activeset::ActiveSet;
+    /// let solver: ActiveSet<Vec<f64>, Vec<Vec<f64>>, f64> = ActiveSet::new();
+    /// ```
+    pub fn new() -> Self {
+        ActiveSet {
+            tol: F::epsilon().sqrt(),
+            h: None,
+            h_chol: None,
+            g: None,
+            normals: vec![],
+            hinv_normals: vec![],
+            rhs: vec![],
+            num_eq: 0,
+            working_set: vec![],
+            u: vec![],
+            x: None,
+        }
+    }
+
+    /// Set tolerance
+    ///
+    /// A constraint is considered to be violated if it is violated by more than
+    /// `tol * (1 + |b_i|)`. Must be larger than 0 and defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::activeset::ActiveSet;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let solver: ActiveSet<Vec<f64>, Vec<Vec<f64>>, f64> = ActiveSet::new().with_tolerance(1e-10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ActiveSet`: tolerance must be > 0."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Removes the constraint at position `k` of the working set
+    fn drop_constraint(&mut self, k: usize) {
+        self.working_set.remove(k);
+        self.u.remove(k);
+    }
+}
+
+impl<P, M, F> ActiveSet<P, M, F>
+where
+    P: Clone + From<Vec<F>> + ArgminGet<usize, F> + ArgminDot<P, F> + ArgminScaledAdd<P, F, P>,
+    M: ArgminEye + ArgminSet<(usize, usize), F> + ArgminCholesky<M> + ArgminCholeskySolve<P, P>,
+    F: ArgminFloat,
+{
+    /// Value `n_j^T x - c_j` of constraint `j`, which is negative if the constraint is violated
+    fn slack(&self, j: usize) -> F {
+        self.normals[j].dot(self.x.as_ref().unwrap()) - self.rhs[j]
+    }
+
+    /// Whether constraint `j` is violated by more than the tolerance
+    fn violated(&self, j: usize) -> bool {
+        let slack = self.slack(j);
+        let threshold = self.tol * (float!(1.0) + self.rhs[j].abs());
+        if j < self.num_eq {
+            slack.abs() > threshold
+        } else {
+            slack < -threshold
+        }
+    }
+
+    /// Most violated inequality constraint
+    fn most_violated(&self) -> Option<usize> {
+        (self.num_eq..self.rhs.len())
+            .filter(|&j| self.violated(j))
+            .map(|j| (j, self.slack(j)))
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(j, _)| j)
+    }
+
+    /// Largest constraint violation
+    fn max_violation(&self) -> F {
+        (0..self.rhs.len()).fold(float!(0.0), |acc, j| {
+            let slack = self.slack(j);
+            if j < self.num_eq {
+                acc.max(slack.abs())
+            } else {
+                acc.max(-slack)
+            }
+        })
+    }
+
+    /// Objective function value `1/2 x^T H x + g^T x`
+    fn objective(&self) -> F
+    where
+        M: ArgminDot<P, P>,
+    {
+        let x = self.x.as_ref().unwrap();
+        float!(0.5) * x.dot(&self.h.as_ref().unwrap().dot(x)) + self.g.as_ref().unwrap().dot(x)
+    }
+
+    /// Solves `N^T H^-1 N v = b` for the constraints `N` of the working set
+    fn solve_normal_system(&self, b: Vec<F>) -> Result<Vec<F>, Error> {
+        let k = self.working_set.len();
+        let mut s = M::eye(k);
+        for (a, &i) in self.working_set.iter().enumerate() {
+            for (c, &j) in self.working_set.iter().enumerate() {
+                s.set((a, c), self.normals[i].dot(&self.hinv_normals[j]));
+            }
+        }
+        let v = s.cholesky()?.cholesky_solve(&P::from(b))?;
+        Ok((0..k).map(|a| v.get(a)).collect())
+    }
+
+    /// Computes the step direction `z = H^-1 (I - N N^*) n_j` in the primal space and
+    /// `r = N^* n_j` in the space of the multipliers for adding constraint `j`, where
+    /// `N^* = (N^T H^-1 N)^-1 N^T H^-1`.
+    fn directions(&self, j: usize) -> Result<(P, Vec<F>), Error> {
+        if self.working_set.is_empty() {
+            return Ok((self.hinv_normals[j].clone(), vec![]));
+        }
+        let r = self.solve_normal_system(
+            self.working_set
+                .iter()
+                .map(|&i| self.hinv_normals[i].dot(&self.normals[j]))
+                .collect(),
+        )?;
+        let z = self
+            .working_set
+            .iter()
+            .zip(r.iter())
+            .fold(self.hinv_normals[j].clone(), |z, (&i, &ri)| {
+                z.scaled_add(&(-ri), &self.hinv_normals[i])
+            });
+        Ok((z, r))
+    }
+
+    /// Whether the normal of constraint `j` is linearly dependent on the normals of the working
+    /// set
+    fn dependent(&self, j: usize) -> Result<bool, Error> {
+        let (z, _) = self.directions(j)?;
+        let n = &self.normals[j];
+        Ok(z.dot(n) <= self.tol * self.hinv_normals[j].dot(n))
+    }
+
+    /// Computes parameter vector and multipliers which minimize the objective function subject to
+    /// the constraints of the working set (treated as equality constraints)
+    fn solve_working_set(&mut self) -> Result<(), Error>
+    where
+        P: ArgminMul<F, P>,
+    {
+        // H x + g = N u  and  N^T x = c  yield  N^T H^-1 N u = c + N^T H^-1 g
+        let hinv_g = self
+            .h_chol
+            .as_ref()
+            .unwrap()
+            .cholesky_solve(self.g.as_ref().unwrap())?;
+        self.u = if self.working_set.is_empty() {
+            vec![]
+        } else {
+            self.solve_normal_system(
+                self.working_set
+                    .iter()
+                    .map(|&i| self.rhs[i] + self.normals[i].dot(&hinv_g))
+                    .collect(),
+            )?
+        };
+        self.x = Some(
+            self.working_set
+                .iter()
+                .zip(self.u.iter())
+                .fold(hinv_g.mul(&float!(-1.0)), |x, (&i, &ui)| {
+                    x.scaled_add(&ui, &self.hinv_normals[i])
+                }),
+        );
+        Ok(())
+    }
+
+    /// Stores param, cost, active set and Lagrange multipliers in the state.
+    fn update_state(&self, state: LinearProgramState<P, F>) -> LinearProgramState<P, F>
+    where
+        M: ArgminDot<P, P>,
+    {
+        let cost = if (0..self.rhs.len()).any(|j| self.violated(j)) {
+            F::infinity()
+        } else {
+            self.objective()
+        };
+        let mut dual = vec![float!(0.0); self.rhs.len()];
+        for (&i, &ui) in self.working_set.iter().zip(self.u.iter()) {
+            // Equality constraints are stored as `A_eq x >= b_eq`, inequality constraints as
+            // `-A_ineq x >= -b_ineq`
+            dual[i] = if i < self.num_eq { -ui } else { ui };
+        }
+        let mut active_set: Vec<usize> = self
+            .working_set
+            .iter()
+            .filter(|&&i| i >= self.num_eq)
+            .map(|&i| i - self.num_eq)
+            .collect();
+        active_set.sort_unstable();
+        state
+            .param(self.x.clone().unwrap())
+            .cost(cost)
+            .active_set(active_set)
+            .dual(dual)
+    }
+}
+
+impl<P, M, F> Default for ActiveSet<P, M, F>
+where
+    F: ArgminFloat,
+{
+    fn default() -> ActiveSet<P, M, F> {
+        ActiveSet::new()
+    }
+}
+
+impl<O, P, M, F> Solver<O, LinearProgramState<P, F>> for ActiveSet<P, M, F>
+where
+    O: QuadraticProgram<Param = P, Matrix = M, Float = F>,
+    P: Clone
+        + From<Vec<F>>
+        + ArgminGet<usize, F>
+        + ArgminSize<usize>
+        + ArgminDot<P, F>
+        + ArgminScaledAdd<P, F, P>
+        + ArgminMul<F, P>,
+    M: ArgminEye
+        + ArgminGet<(usize, usize), F>
+        + ArgminSet<(usize, usize), F>
+        + ArgminSize<(usize, usize)>
+        + ArgminDot<P, P>
+        + ArgminCholesky<M>
+        + ArgminCholeskySolve<P, P>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Active set method";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: LinearProgramState<P, F>,
+    ) -> Result<(LinearProgramState<P, F>, Option<KV>), Error> {
+        let h = problem.H()?;
+        let g = problem.g()?;
+        let n = g.shape();
+        if n == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ActiveSet`: `g` must not be empty."
+            ));
+        }
+        if h.shape() != (n, n) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ActiveSet`: `H` must be a square matrix matching the length of `g`."
+            ));
+        }
+        let (a_eq, b_eq) = constraints("eq", problem.A_eq()?, problem.b_eq()?, n, F::one())?;
+        let (a_ineq, b_ineq) =
+            constraints("ineq", problem.A_ineq()?, problem.b_ineq()?, n, -F::one())?;
+
+        let warm_start = state.get_active_set().cloned().unwrap_or_default();
+        for (k, &j) in warm_start.iter().enumerate() {
+            if j >= b_ineq.len() || warm_start[..k].contains(&j) {
+                return Err(argmin_error!(
+                    InvalidParameter,
+                    "`ActiveSet`: initial active set must consist of distinct indices of inequality constraints."
+                ));
+            }
+        }
+
+        let h_chol = h.cholesky().map_err(|_| -> Error {
+            argmin_error!(
+                InvalidParameter,
+                "`ActiveSet`: `H` must be positive definite."
+            )
+        })?;
+        self.num_eq = b_eq.len();
+        // Inequality constraints are stored as `-A_ineq x >= -b_ineq`
+        self.normals = a_eq.into_iter().chain(a_ineq).collect();
+        self.hinv_normals = self
+            .normals
+            .iter()
+            .map(|n| h_chol.cholesky_solve(n))
+            .collect::<Result<_, _>>()?;
+        self.rhs = b_eq.into_iter().chain(b_ineq).collect();
+        self.h = Some(h);
+        self.h_chol = Some(h_chol);
+        self.g = Some(g);
+
+        // Equality constraints are always in the working set, followed by the constraints of the
+        // initial active set. Linearly dependent constraints are skipped.
+        self.working_set = vec![];
+        let candidates: Vec<usize> = (0..self.num_eq)
+            .chain(warm_start.iter().map(|&j| self.num_eq + j))
+            .collect();
+        for i in candidates {
+            if !self.dependent(i)? {
+                self.working_set.push(i);
+            }
+        }
+        self.solve_working_set()?;
+        while let Some(k) = (0..self.working_set.len())
+            .filter(|&k| self.working_set[k] >= self.num_eq && self.u[k] < float!(0.0))
+            .min_by(|&a, &b| {
+                self.u[a]
+                    .partial_cmp(&self.u[b])
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+        {
+            self.drop_constraint(k);
+            self.solve_working_set()?;
+        }
+
+        let kv = kv!(
+            "working_set" => self.working_set.len() as u64;
+            "max_violation" => self.max_violation();
+        );
+        // Skipped equality constraints which are not satisfied contradict the others
+        let state = self.update_state(state);
+        if (0..self.num_eq).any(|j| self.violated(j)) {
+            return Ok((
+                state.terminate_with(TerminationReason::Infeasible),
+                Some(kv),
+            ));
+        }
+        Ok((state, Some(kv)))
+    }
+
+    fn next_iter(
+        &mut self,
+        _problem: &mut Problem<O>,
+        state: LinearProgramState<P, F>,
+    ) -> Result<(LinearProgramState<P, F>, Option<KV>), Error> {
+        let p = if let Some(p) = self.most_violated() {
+            p
+        } else {
+            return Ok((
+                self.update_state(state)
+                    .terminate_with(TerminationReason::SolverConverged),
+                None,
+            ));
+        };
+        let nhn = self.hinv_normals[p].dot(&self.normals[p]);
+        // Multiplier of the added constraint
+        let mut u_p = float!(0.0);
+        let mut dropped = 0u64;
+        loop {
+            let (z, r) = self.directions(p)?;
+
+            // Largest step which keeps the multipliers of the inequality constraints non-negative
+            let (t1, k) = (0..self.working_set.len())
+                .filter(|&k| self.working_set[k] >= self.num_eq && r[k] > float!(0.0))
+                .map(|k| (self.u[k] / r[k], Some(k)))
+                .fold(
+                    (F::infinity(), None),
+                    |acc, v| if v.0 < acc.0 { v } else { acc },
+                );
+            // Step which satisfies the added constraint
+            let zn = z.dot(&self.normals[p]);
+            let t2 = if zn > self.tol * nhn {
+                -self.slack(p) / zn
+            } else {
+                F::infinity()
+            };
+
+            let t = t1.min(t2);
+            if t.is_infinite() {
+                let kv = kv!("dropped" => dropped; "working_set" => self.working_set.len() as u64;);
+                return Ok((
+                    self.update_state(state)
+                        .terminate_with(TerminationReason::Infeasible),
+                    Some(kv),
+                ));
+            }
+
+            if t2.is_finite() {
+                self.x = self.x.take().map(|x| x.scaled_add(&t, &z));
+            }
+            for (ui, &ri) in self.u.iter_mut().zip(r.iter()) {
+                *ui = *ui - t * ri;
+            }
+            u_p = u_p + t;
+
+            if t2 <= t1 {
+                self.working_set.push(p);
+                self.u.push(u_p);
+                break;
+            }
+            self.drop_constraint(k.unwrap());
+            dropped += 1;
+        }
+
+        let kv = kv!(
+            "added" => (p - self.num_eq) as u64;
+            "dropped" => dropped;
+            "working_set" => self.working_set.len() as u64;
+        );
+        Ok((self.update_state(state), Some(kv)))
+    }
+
+    fn terminate(&mut self, _state: &LinearProgramState<P, F>) -> TerminationStatus {
+        if self.most_violated().is_none() {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+        TerminationStatus::NotTerminated
+    }
+}
+
+/// Checks matrix and right-hand side of the equality (`kind == "eq"`) or inequality
+/// (`kind == "ineq"`) constraints and returns the rows of the matrix and the right-hand side,
+/// both multiplied by `sign`
+#[allow(clippy::type_complexity)]
+fn constraints<P, M, F>(
+    kind: &str,
+    a: Option<M>,
+    b: Option<P>,
+    n: usize,
+    sign: F,
+) -> Result<(Vec<P>, Vec<F>), Error>
+where
+    P: From<Vec<F>> + ArgminGet<usize, F> + ArgminSize<usize>,
+    M: ArgminGet<(usize, usize), F> + ArgminSize<(usize, usize)>,
+    F: ArgminFloat,
+{
+    match (a, b) {
+        (None, None) => Ok((vec![], vec![])),
+        (Some(a), Some(b)) => {
+            let m = b.shape();
+            if a.shape() != (m, n) {
+                return Err(argmin_error!(
+                    InvalidParameter,
+                    format!(
+                        "`ActiveSet`: `A_{kind}` must have as many rows as `b_{kind}` and as many columns as `g`."
+                    )
+                ));
+            }
+            let rows = (0..m)
+                .map(|i| P::from((0..n).map(|j| sign * a.get((i, j))).collect()))
+                .collect();
+            Ok((rows, (0..m).map(|i| sign * b.get(i)).collect()))
+        }
+        _ => Err(argmin_error!(
+            InvalidParameter,
+            format!("`ActiveSet`: either both or none of `A_{kind}` and `b_{kind}` must be given.")
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use crate::core::Executor;
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(activeset, ActiveSet<Vec<f64>, Vec<Vec<f64>>, f64>);
+
+    #[test]
+    fn test_new() {
+        let solver: ActiveSet<Vec<f64>, Vec<Vec<f64>>, f64> = ActiveSet::new();
+        let ActiveSet {
+            tol,
+            h,
+            h_chol,
+            g,
+            normals,
+            hinv_normals,
+            rhs,
+            num_eq,
+            working_set,
+            u,
+            x,
+        } = solver;
+        assert_eq!(tol.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert!(h.is_none());
+        assert!(h_chol.is_none());
+        assert!(g.is_none());
+        assert!(normals.is_empty());
+        assert!(hinv_normals.is_empty());
+        assert!(rhs.is_empty());
+        assert_eq!(num_eq, 0);
+        assert!(working_set.is_empty());
+        assert!(u.is_empty());
+        assert!(x.is_none());
+    }
+
+    #[test]
+    fn test_with_tolerance() {
+        let solver: ActiveSet<Vec<f64>, Vec<Vec<f64>>, f64> =
+            ActiveSet::new().with_tolerance(1e-6).unwrap();
+        assert_eq!(solver.tol.to_ne_bytes(), 1e-6f64.to_ne_bytes());
+
+        for tol in [0.0, -1.0] {
+            let res = ActiveSet::<Vec<f64>, Vec<Vec<f64>>, f64>::new().with_tolerance(tol);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`ActiveSet`: tolerance must be > 0.\""
+            );
+        }
+    }
+
+    /// Quadratic program given by vectors of type `P` and matrices of type `M`
+    struct Qp<P, M> {
+        h: M,
+        g: P,
+        a_eq: Option<M>,
+        b_eq: Option<P>,
+        a_ineq: Option<M>,
+        b_ineq: Option<P>,
+    }
+
+    impl<P: Clone, M: Clone> QuadraticProgram for Qp<P, M> {
+        type Param = P;
+        type Matrix = M;
+        type Float = f64;
+
+        fn H(&self) -> Result<M, Error> {
+            Ok(self.h.clone())
+        }
+
+        fn g(&self) -> Result<P, Error> {
+            Ok(self.g.clone())
+        }
+
+        fn A_eq(&self) -> Result<Option<M>, Error> {
+            Ok(self.a_eq.clone())
+        }
+
+        fn b_eq(&self) -> Result<Option<P>, Error> {
+            Ok(self.b_eq.clone())
+        }
+
+        fn A_ineq(&self) -> Result<Option<M>, Error> {
+            Ok(self.a_ineq.clone())
+        }
+
+        fn b_ineq(&self) -> Result<Option<P>, Error> {
+            Ok(self.b_ineq.clone())
+        }
+    }
+
+    /// Generates the tests for a linear algebra backend, given functions which convert a slice
+    /// into a vector and a slice of rows into a matrix
+    macro_rules! backend_tests {
+        ($backend:ident, $p:ty, $m:ty, $vector:expr, $matrix:expr) => {
+            mod $backend {
+                use super::*;
+
+                /// Example 16.4 of Nocedal and Wright: min (x1 - 1)^2 + (x2 - 2.5)^2 subject to
+                /// five inequality constraints, with solution (1.4, 1.7). The objective function
+                /// omits the constant term 7.25.
+                fn nocedal_wright() -> Qp<$p, $m> {
+                    Qp {
+                        h: $matrix(&[&[2.0, 0.0], &[0.0, 2.0]]),
+                        g: $vector(&[-2.0, -5.0]),
+                        a_eq: None,
+                        b_eq: None,
+                        a_ineq: Some($matrix(&[
+                            &[-1.0, 2.0],
+                            &[1.0, 2.0],
+                            &[1.0, -2.0],
+                            &[-1.0, 0.0],
+                            &[0.0, -1.0],
+                        ])),
+                        b_ineq: Some($vector(&[2.0, 6.0, 2.0, 0.0, 0.0])),
+                    }
+                }
+
+                #[test]
+                fn test_inequality_constraints() {
+                    let res = Executor::new(nocedal_wright(), ActiveSet::new())
+                        .run()
+                        .unwrap();
+                    let state = res.state();
+                    let x = state.get_best_param().unwrap();
+                    assert_relative_eq!(x[0], 1.4, epsilon = 1e-10);
+                    assert_relative_eq!(x[1], 1.7, epsilon = 1e-10);
+                    assert_relative_eq!(state.get_best_cost(), -6.45, epsilon = 1e-10);
+                    assert_eq!(state.get_active_set().unwrap(), &vec![0]);
+                    let dual = state.get_dual().unwrap();
+                    assert_relative_eq!(dual[0], 0.8, epsilon = 1e-10);
+                    for &d in dual[1..].iter() {
+                        assert_relative_eq!(d, 0.0, epsilon = 1e-10);
+                    }
+                    assert_eq!(
+                        state.get_termination_status(),
+                        &TerminationStatus::Terminated(TerminationReason::SolverConverged)
+                    );
+                }
+
+                #[test]
+                fn test_warm_start() {
+                    let cold = Executor::new(nocedal_wright(), ActiveSet::new())
+                        .run()
+                        .unwrap();
+                    assert!(cold.state().get_iter() > 0);
+
+                    // Optimal active set: no iterations required
+                    let active_set = cold.state().get_active_set().unwrap().clone();
+                    let warm = Executor::new(nocedal_wright(), ActiveSet::new())
+                        .configure(|state| state.active_set(active_set))
+                        .run()
+                        .unwrap();
+                    assert_eq!(warm.state().get_iter(), 0);
+                    let x = warm.state().get_best_param().unwrap();
+                    assert_relative_eq!(x[0], 1.4, epsilon = 1e-10);
+                    assert_relative_eq!(x[1], 1.7, epsilon = 1e-10);
+
+                    // Wrong working set: constraints with negative multipliers are dropped
+                    let warm = Executor::new(nocedal_wright(), ActiveSet::new())
+                        .configure(|state| state.active_set(vec![3, 4]))
+                        .run()
+                        .unwrap();
+                    let x = warm.state().get_best_param().unwrap();
+                    assert_relative_eq!(x[0], 1.4, epsilon = 1e-10);
+                    assert_relative_eq!(x[1], 1.7, epsilon = 1e-10);
+                    assert_eq!(warm.state().get_active_set().unwrap(), &vec![0]);
+                }
+
+                #[test]
+                fn test_equality_constraints() {
+                    // min x1^2 + x2^2 + x3^2 s.t. x1 + x2 + x3 = 1, x1 >= 0.5
+                    let qp = Qp {
+                        h: $matrix(&[&[2.0, 0.0, 0.0], &[0.0, 2.0, 0.0], &[0.0, 0.0, 2.0]]),
+                        g: $vector(&[0.0, 0.0, 0.0]),
+                        a_eq: Some($matrix(&[&[1.0, 1.0, 1.0]])),
+                        b_eq: Some($vector(&[1.0])),
+                        a_ineq: Some($matrix(&[&[-1.0, 0.0, 0.0]])),
+                        b_ineq: Some($vector(&[-0.5])),
+                    };
+                    let res = Executor::new(qp, ActiveSet::new()).run().unwrap();
+                    let state = res.state();
+                    let x = state.get_best_param().unwrap();
+                    assert_relative_eq!(x[0], 0.5, epsilon = 1e-10);
+                    assert_relative_eq!(x[1], 0.25, epsilon = 1e-10);
+                    assert_relative_eq!(x[2], 0.25, epsilon = 1e-10);
+                    assert_eq!(state.get_active_set().unwrap(), &vec![0]);
+                    // 2 x + y (1, 1, 1) + z (-1, 0, 0) = 0
+                    let dual = state.get_dual().unwrap();
+                    assert_relative_eq!(dual[0], -0.5, epsilon = 1e-10);
+                    assert_relative_eq!(dual[1], 0.5, epsilon = 1e-10);
+                }
+
+                #[test]
+                fn test_infeasible() {
+                    // x1 <= 0 and x1 >= 1
+                    let qp = Qp {
+                        h: $matrix(&[&[1.0, 0.0], &[0.0, 1.0]]),
+                        g: $vector(&[0.0, 0.0]),
+                        a_eq: None,
+                        b_eq: None,
+                        a_ineq: Some($matrix(&[&[1.0, 0.0], &[-1.0, 0.0]])),
+                        b_ineq: Some($vector(&[0.0, -1.0])),
+                    };
+                    let res = Executor::new(qp, ActiveSet::new()).run().unwrap();
+                    assert_eq!(
+                        res.state().get_termination_status(),
+                        &TerminationStatus::Terminated(TerminationReason::Infeasible)
+                    );
+                    assert!(res.state().get_cost().is_infinite());
+
+                    // x1 + x2 = 1 and x1 + x2 = 2
+                    let qp = Qp {
+                        h: $matrix(&[&[1.0, 0.0], &[0.0, 1.0]]),
+                        g: $vector(&[0.0, 0.0]),
+                        a_eq: Some($matrix(&[&[1.0, 1.0], &[1.0, 1.0]])),
+                        b_eq: Some($vector(&[1.0, 2.0])),
+                        a_ineq: None,
+                        b_ineq: None,
+                    };
+                    let res = Executor::new(qp, ActiveSet::new()).run().unwrap();
+                    assert_eq!(res.state().get_iter(), 0);
+                    assert_eq!(
+                        res.state().get_termination_status(),
+                        &TerminationStatus::Terminated(TerminationReason::Infeasible)
+                    );
+                }
+
+                #[test]
+                fn test_init_errors() {
+                    let mut qp = nocedal_wright();
+                    qp.g = $vector(&[1.0, 2.0, 3.0]);
+                    let res = Executor::new(qp, ActiveSet::new()).run();
+                    assert_error!(
+                        res,
+                        ArgminError,
+                        concat!(
+                            "Invalid parameter: \"`ActiveSet`: `H` must be a square matrix ",
+                            "matching the length of `g`.\""
+                        )
+                    );
+
+                    let mut qp = nocedal_wright();
+                    qp.b_ineq = Some($vector(&[1.0]));
+                    let res = Executor::new(qp, ActiveSet::new()).run();
+                    assert_error!(
+                        res,
+                        ArgminError,
+                        concat!(
+                            "Invalid parameter: \"`ActiveSet`: `A_ineq` must have as many rows as ",
+                            "`b_ineq` and as many columns as `g`.\""
+                        )
+                    );
+
+                    let mut qp = nocedal_wright();
+                    qp.b_eq = Some($vector(&[1.0]));
+                    let res = Executor::new(qp, ActiveSet::new()).run();
+                    assert_error!(
+                        res,
+                        ArgminError,
+                        concat!(
+                            "Invalid parameter: \"`ActiveSet`: either both or none of `A_eq` and ",
+                            "`b_eq` must be given.\""
+                        )
+                    );
+
+                    let mut qp = nocedal_wright();
+                    qp.h = $matrix(&[&[1.0, 1.0], &[1.0, 1.0]]);
+                    let res = Executor::new(qp, ActiveSet::new()).run();
+                    assert_error!(
+                        res,
+                        ArgminError,
+                        "Invalid parameter: \"`ActiveSet`: `H` must be positive definite.\""
+                    );
+
+                    for active_set in [vec![5], vec![1, 1]] {
+                        let res = Executor::new(nocedal_wright(), ActiveSet::new())
+                            .configure(|state| state.active_set(active_set))
+                            .run();
+                        assert_error!(
+                            res,
+                            ArgminError,
+                            concat!(
+                                "Invalid parameter: \"`ActiveSet`: initial active set must ",
+                                "consist of distinct indices of inequality constraints.\""
+                            )
+                        );
+                    }
+                }
+            }
+        };
+    }
+
+    backend_tests!(
+        with_vec,
+        Vec<f64>,
+        Vec<Vec<f64>>,
+        |v: &[f64]| v.to_vec(),
+        |rows: &[&[f64]]| rows.iter().map(|row| row.to_vec()).collect::<Vec<_>>()
+    );
+
+    #[cfg(feature = "_nalgebral")]
+    backend_tests!(
+        with_nalgebra,
+        nalgebra::DVector<f64>,
+        nalgebra::DMatrix<f64>,
+        |v: &[f64]| nalgebra::DVector::from_column_slice(v),
+        |rows: &[&[f64]]| nalgebra::DMatrix::from_fn(rows.len(), rows[0].len(), |i, j| rows[i][j])
+    );
+
+    #[cfg(feature = "_ndarrayl")]
+    backend_tests!(
+        with_ndarray,
+        ndarray::Array1<f64>,
+        ndarray::Array2<f64>,
+        |v: &[f64]| ndarray::Array1::from_vec(v.to_vec()),
+        |rows: &[&[f64]]| ndarray::Array2::from_shape_fn((rows.len(), rows[0].len()), |(i, j)| {
+            rows[i][j]
+        })
+    );
+}
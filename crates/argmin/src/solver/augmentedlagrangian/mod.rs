@@ -0,0 +1,896 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Augmented Lagrangian method
+//!
+//! Solves problems with equality and inequality constraints by repeatedly minimizing an augmented
+//! Lagrangian function with an unconstrained solver.
+//!
+//! See [`AugmentedLagrangian`] for details.
+//!
+//! ## References
+//!
+//! Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+//! Springer. ISBN 0-387-30303-0.
+//!
+//! Ernesto G. Birgin and José Mario Martínez (2014). Practical Augmented Lagrangian Methods for
+//! Constrained Optimization. SIAM. ISBN 978-1-611973-35-8.
+
+use crate::core::{
+    ArgminFloat, CostFunction, EqualityConstraints, Error, Executor, Gradient,
+    InequalityConstraints, IterState, OptimizationResult, Problem, Solver, State,
+    TerminationReason, KV,
+};
+use argmin_math::ArgminScaledAdd;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+/// # Augmented Lagrangian method
+///
+/// Minimizes a cost function `f(x)` subject to equality constraints `c(x) = 0` and inequality
+/// constraints `g(x) <= 0` (see [`EqualityConstraints`] and [`InequalityConstraints`]). In each
+/// iteration, the augmented Lagrangian
+///
+/// ```text
+/// L(x) = f(x) + sum_i (lambda_i c_i(x) + mu/2 c_i(x)^2)
+///             + 1/(2 mu) sum_j (max(0, nu_j + mu g_j(x))^2 - nu_j^2)
+/// ```
+///
+/// is minimized by an inner unconstrained solver (for instance [`LBFGS`] or [`NelderMead`]),
+/// starting from the current parameter vector. Afterwards, the multiplier estimates are updated
+/// via `lambda_i <- lambda_i + mu c_i(x)` and `nu_j <- max(0, nu_j + mu g_j(x))`. If the
+/// violation of the constraints and of the complementarity conditions did not decrease
+/// sufficiently, the penalty parameter `mu` is increased.
+///
+/// The algorithm stops once the violation of the constraints and of the complementarity
+/// conditions is below the tolerance set via
+/// [`with_tolerance`](`AugmentedLagrangian::with_tolerance`).
+///
+/// The inner solver is cloned for every iteration and runs for at most the number of iterations
+/// set via [`with_inner_max_iters`](`AugmentedLagrangian::with_inner_max_iters`). Its function
+/// evaluation counts are merged into the counts of the outer solver, including the evaluations of
+/// the constraints performed while evaluating the augmented Lagrangian.
+///
+/// The constraint violation `max(max_i |c_i(x)|, max_j g_j(x), 0)` and the multiplier estimates
+/// (equality constraints first, followed by inequality constraints) are stored in the state (see
+/// [`IterState::get_constraint_violation`] and [`IterState::get_multipliers`]) and are reported
+/// together with the penalty parameter as KV metrics `constraint_violation`, `penalty` and
+/// `multipliers_norm`. The cost function value of parameter vectors whose constraint violation
+/// exceeds the tolerance is reported as infinity, such that the best parameter vector is the best
+/// feasible one. The value of the cost function is always available as KV metric `objective`.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`], [`EqualityConstraints`]
+/// and [`InequalityConstraints`]. If the inner solver requires gradients, the problem must also
+/// implement [`Gradient`] and provide the Jacobians of the constraints.
+///
+/// ## References
+///
+/// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+/// Springer. ISBN 0-387-30303-0.
+///
+/// Ernesto G. Birgin and José Mario Martínez (2014). Practical Augmented Lagrangian Methods for
+/// Constrained Optimization. SIAM. ISBN 978-1-611973-35-8.
+///
+/// [`LBFGS`]: `crate::solver::quasinewton::LBFGS`
+/// [`NelderMead`]: `crate::solver::neldermead::NelderMead`
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct AugmentedLagrangian<S, I, F> {
+    /// Inner solver
+    solver: S,
+    /// Penalty parameter
+    penalty: F,
+    /// Factor by which the penalty parameter is increased
+    penalty_factor: F,
+    /// Tolerance on constraint violation
+    tol: F,
+    /// Maximum number of iterations of the inner solver
+    inner_max_iters: u64,
+    /// Multiplier estimates of the equality constraints
+    eq_multipliers: Vec<F>,
+    /// Multiplier estimates of the inequality constraints
+    ineq_multipliers: Vec<F>,
+    /// Violation of constraints and complementarity of the previous iteration
+    prev_violation: F,
+    /// Inner state
+    phantom: PhantomData<I>,
+}
+
+impl<S, I, F> AugmentedLagrangian<S, I, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `AugmentedLagrangian`
+    ///
+    /// Requires an inner solver which minimizes the augmented Lagrangian.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::augmentedlagrangian::AugmentedLagrangian;
+    /// # use argmin::solver::neldermead::NelderMead;
+    /// # use argmin::core::{IterState};
+    /// # let inner: NelderMead<Vec<f64>, f64> = NelderMead::new(vec![]);
+    /// let solver: AugmentedLagrangian<_, IterState<Vec<f64>, (), (), (), (), f64>, f64> =
+    ///     AugmentedLagrangian::new(inner);
+    /// ```
+    pub fn new(solver: S) -> Self {
+        AugmentedLagrangian {
+            solver,
+            penalty: float!(10.0),
+            penalty_factor: float!(10.0),
+            tol: float!(1e-6),
+            inner_max_iters: 1000,
+            eq_multipliers: vec![],
+            ineq_multipliers: vec![],
+            prev_violation: F::infinity(),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Set initial penalty parameter
+    ///
+    /// Must be larger than `0`. Defaults to `10`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::augmentedlagrangian::AugmentedLagrangian;
+    /// # use argmin::core::{Error, IterState};
+    /// # fn main() -> Result<(), Error> {
+    /// # let inner = ();
+    /// let solver: AugmentedLagrangian<_, IterState<Vec<f64>, (), (), (), (), f64>, f64> =
+    ///     AugmentedLagrangian::new(inner).with_penalty(100.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_penalty(mut self, penalty: F) -> Result<Self, Error> {
+        if penalty <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`AugmentedLagrangian`: penalty must be > 0."
+            ));
+        }
+        self.penalty = penalty;
+        Ok(self)
+    }
+
+    /// Set factor by which the penalty parameter is increased
+    ///
+    /// Must be larger than `1`. Defaults to `10`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::augmentedlagrangian::AugmentedLagrangian;
+    /// # use argmin::core::{Error, IterState};
+    /// # fn main() -> Result<(), Error> {
+    /// # let inner = ();
+    /// let solver: AugmentedLagrangian<_, IterState<Vec<f64>, (), (), (), (), f64>, f64> =
+    ///     AugmentedLagrangian::new(inner).with_penalty_factor(5.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_penalty_factor(mut self, penalty_factor: F) -> Result<Self, Error> {
+        if penalty_factor <= float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`AugmentedLagrangian`: penalty factor must be > 1."
+            ));
+        }
+        self.penalty_factor = penalty_factor;
+        Ok(self)
+    }
+
+    /// Set tolerance on the violation of the constraints and of the complementarity conditions
+    ///
+    /// Must be larger than `0`. Defaults to `1e-6`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::augmentedlagrangian::AugmentedLagrangian;
+    /// # use argmin::core::{Error, IterState};
+    /// # fn main() -> Result<(), Error> {
+    /// # let inner = ();
+    /// let solver: AugmentedLagrangian<_, IterState<Vec<f64>, (), (), (), (), f64>, f64> =
+    ///     AugmentedLagrangian::new(inner).with_tolerance(1e-8)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`AugmentedLagrangian`: tolerance must be > 0."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Set maximum number of iterations of the inner solver per outer iteration
+    ///
+    /// Must be larger than `0`. Defaults to `1000`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::augmentedlagrangian::AugmentedLagrangian;
+    /// # use argmin::core::{Error, IterState};
+    /// # fn main() -> Result<(), Error> {
+    /// # let inner = ();
+    /// let solver: AugmentedLagrangian<_, IterState<Vec<f64>, (), (), (), (), f64>, f64> =
+    ///     AugmentedLagrangian::new(inner).with_inner_max_iters(100)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_inner_max_iters(mut self, inner_max_iters: u64) -> Result<Self, Error> {
+        if inner_max_iters < 1 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`AugmentedLagrangian`: maximum number of inner iterations must be > 0."
+            ));
+        }
+        self.inner_max_iters = inner_max_iters;
+        Ok(self)
+    }
+
+    /// Set initial multiplier estimates of the equality and inequality constraints
+    ///
+    /// The multipliers of the inequality constraints must be non-negative. Defaults to zeros.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::augmentedlagrangian::AugmentedLagrangian;
+    /// # use argmin::core::{Error, IterState};
+    /// # fn main() -> Result<(), Error> {
+    /// # let inner = ();
+    /// let solver: AugmentedLagrangian<_, IterState<Vec<f64>, (), (), (), (), f64>, f64> =
+    ///     AugmentedLagrangian::new(inner).with_multipliers(vec![1.0], vec![0.5, 0.0])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_multipliers(
+        mut self,
+        eq_multipliers: Vec<F>,
+        ineq_multipliers: Vec<F>,
+    ) -> Result<Self, Error> {
+        if ineq_multipliers.iter().any(|&nu| nu < float!(0.0)) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`AugmentedLagrangian`: multipliers of inequality constraints must be >= 0."
+            ));
+        }
+        self.eq_multipliers = eq_multipliers;
+        self.ineq_multipliers = ineq_multipliers;
+        Ok(self)
+    }
+
+    /// Returns the constraint violation and the violation of constraints and complementarity
+    /// conditions with respect to the current multipliers
+    fn violations(&self, eq: &[F], ineq: &[F]) -> (F, F) {
+        let eq_violation = eq.iter().fold(float!(0.0), |acc: F, c| acc.max(c.abs()));
+        let infeasibility = ineq.iter().fold(eq_violation, |acc, &g| acc.max(g));
+        let violation = ineq
+            .iter()
+            .zip(self.ineq_multipliers.iter())
+            .fold(eq_violation, |acc, (&g, &nu)| {
+                acc.max(g.max(-nu / self.penalty).abs())
+            });
+        (infeasibility, violation)
+    }
+
+    /// All multiplier estimates, equality constraints first
+    fn multipliers(&self) -> Vec<F> {
+        self.eq_multipliers
+            .iter()
+            .chain(self.ineq_multipliers.iter())
+            .cloned()
+            .collect()
+    }
+
+    /// KV metrics of the current iteration
+    fn kv(&self, objective: F, infeasibility: F) -> KV {
+        let multipliers_norm = self
+            .eq_multipliers
+            .iter()
+            .chain(self.ineq_multipliers.iter())
+            .fold(float!(0.0), |acc: F, &m| acc + m * m)
+            .sqrt();
+        kv!(
+            "objective" => objective;
+            "constraint_violation" => infeasibility;
+            "penalty" => self.penalty;
+            "multipliers_norm" => multipliers_norm;
+        )
+    }
+}
+
+impl<O, S, P, G, J, H, R, F> Solver<O, IterState<P, (), (), (), (), F>>
+    for AugmentedLagrangian<S, IterState<P, G, J, H, R, F>, F>
+where
+    O: CostFunction<Param = P, Output = F>
+        + EqualityConstraints<Param = P, Float = F>
+        + InequalityConstraints<Param = P, Float = F>,
+    S: Solver<AugmentedLagrangianProblem<O, F>, IterState<P, G, J, H, R, F>> + Clone,
+    P: Clone,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Augmented Lagrangian";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`AugmentedLagrangian` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+
+        let eq = problem.equality_constraints(&param)?;
+        let ineq = problem.inequality_constraints(&param)?;
+        if self.eq_multipliers.is_empty() && self.ineq_multipliers.is_empty() {
+            self.eq_multipliers = vec![float!(0.0); eq.len()];
+            self.ineq_multipliers = vec![float!(0.0); ineq.len()];
+        }
+        if self.eq_multipliers.len() != eq.len() || self.ineq_multipliers.len() != ineq.len() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`AugmentedLagrangian`: number of multipliers does not match number of constraints."
+            ));
+        }
+
+        let cost = problem.cost(&param)?;
+        let (infeasibility, violation) = self.violations(&eq, &ineq);
+        self.prev_violation = violation;
+        let kv = self.kv(cost, infeasibility);
+
+        Ok((
+            state
+                .param(param)
+                .cost(if infeasibility <= self.tol {
+                    cost
+                } else {
+                    F::infinity()
+                })
+                .constraint_violation(infeasibility)
+                .multipliers(self.multipliers()),
+            Some(kv),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`AugmentedLagrangian`: Parameter vector in state not set."
+        ))?;
+
+        let lagrangian = AugmentedLagrangianProblem {
+            problem: problem.take_problem().unwrap(),
+            eq_multipliers: self.eq_multipliers.clone(),
+            ineq_multipliers: self.ineq_multipliers.clone(),
+            penalty: self.penalty,
+        };
+        let inner_max_iters = self.inner_max_iters;
+        let OptimizationResult {
+            problem: mut inner_problem,
+            state: mut inner_state,
+            ..
+        } = Executor::new(lagrangian, self.solver.clone())
+            .configure(|config| config.param(param).max_iters(inner_max_iters))
+            .ctrlc(false)
+            .run()?;
+
+        let param = inner_state
+            .take_best_param()
+            .or_else(|| inner_state.take_param())
+            .ok_or_else(argmin_error_closure!(
+                PotentialBug,
+                "`AugmentedLagrangian`: No parameters returned by inner solver."
+            ))?;
+
+        // take back problem and take care of function evaluation counts
+        let lagrangian = inner_problem.take_problem().unwrap();
+        let cost_evals = inner_problem.counts.get("cost_count").cloned().unwrap_or(0);
+        let grad_evals = inner_problem
+            .counts
+            .get("gradient_count")
+            .cloned()
+            .unwrap_or(0);
+        problem.problem = Some(lagrangian.problem);
+        problem.consume_func_counts(inner_problem);
+        let mut add_counts = |counts_string: &'static str, evals: u64| {
+            if evals > 0 {
+                *problem.counts.entry(counts_string).or_insert(0) += evals;
+            }
+        };
+        if !self.eq_multipliers.is_empty() {
+            add_counts("equality_constraints_count", cost_evals + grad_evals);
+            add_counts("equality_jacobian_count", grad_evals);
+        }
+        if !self.ineq_multipliers.is_empty() {
+            add_counts("inequality_constraints_count", cost_evals + grad_evals);
+            add_counts("inequality_jacobian_count", grad_evals);
+        }
+
+        let cost = problem.cost(&param)?;
+        let eq = problem.equality_constraints(&param)?;
+        let ineq = problem.inequality_constraints(&param)?;
+        let (infeasibility, violation) = self.violations(&eq, &ineq);
+
+        for (lambda, &c) in self.eq_multipliers.iter_mut().zip(eq.iter()) {
+            *lambda = *lambda + self.penalty * c;
+        }
+        for (nu, &g) in self.ineq_multipliers.iter_mut().zip(ineq.iter()) {
+            *nu = (*nu + self.penalty * g).max(float!(0.0));
+        }
+
+        if violation <= self.tol {
+            state = state.terminate_with(TerminationReason::SolverConverged);
+        } else if violation > float!(0.5) * self.prev_violation {
+            self.penalty = self.penalty * self.penalty_factor;
+        }
+        self.prev_violation = violation;
+
+        let kv = self.kv(cost, infeasibility);
+        Ok((
+            state
+                .param(param)
+                .cost(if infeasibility <= self.tol {
+                    cost
+                } else {
+                    F::infinity()
+                })
+                .constraint_violation(infeasibility)
+                .multipliers(self.multipliers()),
+            Some(kv),
+        ))
+    }
+}
+
+/// Augmented Lagrangian of a constrained optimization problem
+///
+/// Minimized by the inner solver of [`AugmentedLagrangian`]. Implements [`CostFunction`] and, if
+/// the underlying problem implements [`Gradient`], also [`Gradient`].
+#[derive(Clone, Debug)]
+pub struct AugmentedLagrangianProblem<O, F> {
+    /// Constrained problem
+    problem: O,
+    /// Multiplier estimates of the equality constraints
+    eq_multipliers: Vec<F>,
+    /// Multiplier estimates of the inequality constraints
+    ineq_multipliers: Vec<F>,
+    /// Penalty parameter
+    penalty: F,
+}
+
+impl<O, P, F> CostFunction for AugmentedLagrangianProblem<O, F>
+where
+    O: CostFunction<Param = P, Output = F>
+        + EqualityConstraints<Param = P, Float = F>
+        + InequalityConstraints<Param = P, Float = F>,
+    F: ArgminFloat,
+{
+    type Param = P;
+    type Output = F;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        let mu = self.penalty;
+        let eq = self.problem.equality_constraints(param)?;
+        let ineq = self.problem.inequality_constraints(param)?;
+        let cost = eq
+            .iter()
+            .zip(self.eq_multipliers.iter())
+            .fold(self.problem.cost(param)?, |acc, (&c, &lambda)| {
+                acc + lambda * c + float!(0.5) * mu * c * c
+            });
+        Ok(ineq
+            .iter()
+            .zip(self.ineq_multipliers.iter())
+            .fold(cost, |acc, (&g, &nu)| {
+                acc + ((nu + mu * g).max(float!(0.0)).powi(2) - nu * nu) / (float!(2.0) * mu)
+            }))
+    }
+}
+
+impl<O, P, F> Gradient for AugmentedLagrangianProblem<O, F>
+where
+    O: Gradient<Param = P, Gradient = P>
+        + EqualityConstraints<Param = P, Float = F>
+        + InequalityConstraints<Param = P, Float = F>,
+    P: ArgminScaledAdd<P, F, P>,
+    F: ArgminFloat,
+{
+    type Param = P;
+    type Gradient = P;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        let mu = self.penalty;
+        let mut grad = self.problem.gradient(param)?;
+        let eq = self.problem.equality_constraints(param)?;
+        if !eq.is_empty() {
+            let jacobian = self.problem.equality_jacobian(param)?;
+            if jacobian.len() != eq.len() {
+                return Err(argmin_error!(
+                    InvalidParameter,
+                    "`AugmentedLagrangian`: number of rows of the Jacobian of the equality constraints does not match number of constraints."
+                ));
+            }
+            for ((c, lambda), row) in eq.iter().zip(self.eq_multipliers.iter()).zip(jacobian) {
+                grad = grad.scaled_add(&(*lambda + mu * *c), &row);
+            }
+        }
+        let ineq = self.problem.inequality_constraints(param)?;
+        if !ineq.is_empty() {
+            let jacobian = self.problem.inequality_jacobian(param)?;
+            if jacobian.len() != ineq.len() {
+                return Err(argmin_error!(
+                    InvalidParameter,
+                    "`AugmentedLagrangian`: number of rows of the Jacobian of the inequality constraints does not match number of constraints."
+                ));
+            }
+            for ((g, nu), row) in ineq.iter().zip(self.ineq_multipliers.iter()).zip(jacobian) {
+                let factor = (*nu + mu * *g).max(float!(0.0));
+                if factor > float!(0.0) {
+                    grad = grad.scaled_add(&factor, &row);
+                }
+            }
+        }
+        Ok(grad)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use crate::solver::linesearch::MoreThuenteLineSearch;
+    use crate::solver::neldermead::NelderMead;
+    use crate::solver::quasinewton::LBFGS;
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    type Inner = LBFGS<MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64>, Vec<f64>, Vec<f64>, f64>;
+    type InnerState = IterState<Vec<f64>, Vec<f64>, (), (), (), f64>;
+
+    test_trait_impl!(
+        augmented_lagrangian,
+        AugmentedLagrangian<Inner, InnerState, f64>
+    );
+
+    fn lbfgs() -> AugmentedLagrangian<Inner, InnerState, f64> {
+        AugmentedLagrangian::new(LBFGS::new(MoreThuenteLineSearch::new(), 5))
+    }
+
+    /// min (x_0 - 1)^2 + (x_1 - 2)^2  s.t.  x_0 + x_1 = 1
+    ///
+    /// Solution: x = (0, 1) with multiplier 2
+    struct EqProblem {}
+
+    impl CostFunction for EqProblem {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok((p[0] - 1.0).powi(2) + (p[1] - 2.0).powi(2))
+        }
+    }
+
+    impl Gradient for EqProblem {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(vec![2.0 * (p[0] - 1.0), 2.0 * (p[1] - 2.0)])
+        }
+    }
+
+    impl EqualityConstraints for EqProblem {
+        type Param = Vec<f64>;
+        type Float = f64;
+
+        fn equality_constraints(&self, p: &Self::Param) -> Result<Vec<f64>, Error> {
+            Ok(vec![p[0] + p[1] - 1.0])
+        }
+
+        fn equality_jacobian(&self, _p: &Self::Param) -> Result<Vec<Vec<f64>>, Error> {
+            Ok(vec![vec![1.0, 1.0]])
+        }
+    }
+
+    impl InequalityConstraints for EqProblem {
+        type Param = Vec<f64>;
+        type Float = f64;
+    }
+
+    /// min (x_0 - 2)^2 + (x_1 - 1)^2  s.t.  x_0^2 + x_1^2 <= 1,  -x_0 <= 0
+    ///
+    /// Solution: x = (2, 1) / sqrt(5) with multipliers (sqrt(5) - 1, 0)
+    struct IneqProblem {}
+
+    impl CostFunction for IneqProblem {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok((p[0] - 2.0).powi(2) + (p[1] - 1.0).powi(2))
+        }
+    }
+
+    impl Gradient for IneqProblem {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(vec![2.0 * (p[0] - 2.0), 2.0 * (p[1] - 1.0)])
+        }
+    }
+
+    impl EqualityConstraints for IneqProblem {
+        type Param = Vec<f64>;
+        type Float = f64;
+    }
+
+    impl InequalityConstraints for IneqProblem {
+        type Param = Vec<f64>;
+        type Float = f64;
+
+        fn inequality_constraints(&self, p: &Self::Param) -> Result<Vec<f64>, Error> {
+            Ok(vec![p[0].powi(2) + p[1].powi(2) - 1.0, -p[0]])
+        }
+
+        fn inequality_jacobian(&self, p: &Self::Param) -> Result<Vec<Vec<f64>>, Error> {
+            Ok(vec![vec![2.0 * p[0], 2.0 * p[1]], vec![-1.0, 0.0]])
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let solver = lbfgs();
+        let AugmentedLagrangian {
+            penalty,
+            penalty_factor,
+            tol,
+            inner_max_iters,
+            eq_multipliers,
+            ineq_multipliers,
+            ..
+        } = solver;
+        assert_eq!(penalty.to_ne_bytes(), 10.0f64.to_ne_bytes());
+        assert_eq!(penalty_factor.to_ne_bytes(), 10.0f64.to_ne_bytes());
+        assert_eq!(tol.to_ne_bytes(), 1e-6f64.to_ne_bytes());
+        assert_eq!(inner_max_iters, 1000);
+        assert!(eq_multipliers.is_empty());
+        assert!(ineq_multipliers.is_empty());
+    }
+
+    #[test]
+    fn test_builder_errors() {
+        assert_error!(
+            lbfgs().with_penalty(0.0),
+            ArgminError,
+            "Invalid parameter: \"`AugmentedLagrangian`: penalty must be > 0.\""
+        );
+        assert_error!(
+            lbfgs().with_penalty_factor(1.0),
+            ArgminError,
+            "Invalid parameter: \"`AugmentedLagrangian`: penalty factor must be > 1.\""
+        );
+        assert_error!(
+            lbfgs().with_tolerance(0.0),
+            ArgminError,
+            "Invalid parameter: \"`AugmentedLagrangian`: tolerance must be > 0.\""
+        );
+        assert_error!(
+            lbfgs().with_inner_max_iters(0),
+            ArgminError,
+            "Invalid parameter: \"`AugmentedLagrangian`: maximum number of inner iterations must be > 0.\""
+        );
+        assert_error!(
+            lbfgs().with_multipliers(vec![], vec![-1.0]),
+            ArgminError,
+            "Invalid parameter: \"`AugmentedLagrangian`: multipliers of inequality constraints must be >= 0.\""
+        );
+    }
+
+    #[test]
+    fn test_init_errors() {
+        let mut solver = lbfgs();
+        let res = solver.init(&mut Problem::new(EqProblem {}), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`AugmentedLagrangian` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+
+        let mut solver = lbfgs().with_multipliers(vec![1.0, 2.0], vec![]).unwrap();
+        let res = solver.init(
+            &mut Problem::new(EqProblem {}),
+            IterState::new().param(vec![0.0, 0.0]),
+        );
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`AugmentedLagrangian`: number of multipliers does not match number of constraints.\""
+        );
+    }
+
+    #[test]
+    fn test_init() {
+        let mut solver = lbfgs();
+        let mut problem = Problem::new(IneqProblem {});
+        let (state, kv) = solver
+            .init(&mut problem, IterState::new().param(vec![1.0, 1.0]))
+            .unwrap();
+        assert_eq!(
+            state.get_constraint_violation().unwrap().to_ne_bytes(),
+            1.0f64.to_ne_bytes()
+        );
+        assert_eq!(*state.get_multipliers().unwrap(), vec![0.0, 0.0]);
+        assert!(state.get_cost().is_infinite());
+        let kv = kv.unwrap();
+        assert_eq!(
+            kv.get("objective")
+                .unwrap()
+                .get_float()
+                .unwrap()
+                .to_ne_bytes(),
+            1.0f64.to_ne_bytes()
+        );
+        assert_eq!(problem.counts["cost_count"], 1);
+        assert_eq!(problem.counts["equality_constraints_count"], 1);
+        assert_eq!(problem.counts["inequality_constraints_count"], 1);
+    }
+
+    #[test]
+    fn test_augmented_lagrangian_problem() {
+        let lagrangian = AugmentedLagrangianProblem {
+            problem: IneqProblem {},
+            eq_multipliers: vec![],
+            ineq_multipliers: vec![1.0, 2.0],
+            penalty: 10.0,
+        };
+        // g = (0.25, -0.5): first constraint is active, second one is inactive
+        let p = vec![0.5, 1.0];
+        let expected = 2.25 + (3.5f64.powi(2) - 1.0) / 20.0 + (0.0 - 4.0) / 20.0;
+        assert_relative_eq!(lagrangian.cost(&p).unwrap(), expected, epsilon = 1e-12);
+        let grad = lagrangian.gradient(&p).unwrap();
+        assert_relative_eq!(grad[0], -3.0 + 3.5 * 1.0, epsilon = 1e-12);
+        assert_relative_eq!(grad[1], 0.0 + 3.5 * 2.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_equality_constraints() {
+        let res = Executor::new(EqProblem {}, lbfgs())
+            .configure(|state| state.param(vec![0.0, 0.0]).max_iters(50))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        let param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(param[0], 0.0, epsilon = 1e-5);
+        assert_relative_eq!(param[1], 1.0, epsilon = 1e-5);
+        assert_relative_eq!(res.state.get_best_cost(), 2.0, epsilon = 1e-5);
+        assert_relative_eq!(res.state.get_multipliers().unwrap()[0], 2.0, epsilon = 1e-4);
+        assert!(res.state.get_constraint_violation().unwrap() <= 1e-6);
+
+        let counts = res.state.get_func_counts();
+        assert_eq!(
+            counts["equality_constraints_count"],
+            counts["cost_count"] + counts["gradient_count"]
+        );
+        assert_eq!(counts["equality_jacobian_count"], counts["gradient_count"]);
+        assert!(!counts.contains_key("inequality_jacobian_count"));
+    }
+
+    #[test]
+    fn test_inequality_constraints() {
+        let res = Executor::new(IneqProblem {}, lbfgs())
+            .configure(|state| state.param(vec![0.5, -0.5]).max_iters(50))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        let param = res.state.get_best_param().unwrap();
+        let sqrt5 = 5.0f64.sqrt();
+        assert_relative_eq!(param[0], 2.0 / sqrt5, epsilon = 1e-5);
+        assert_relative_eq!(param[1], 1.0 / sqrt5, epsilon = 1e-5);
+        let multipliers = res.state.get_multipliers().unwrap();
+        assert_relative_eq!(multipliers[0], sqrt5 - 1.0, epsilon = 1e-4);
+        assert_relative_eq!(multipliers[1], 0.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_derivative_free_inner_solver() {
+        let inner: NelderMead<Vec<f64>, f64> =
+            NelderMead::new(vec![vec![0.5, -0.5], vec![0.6, -0.5], vec![0.5, -0.4]])
+                .with_sd_tolerance(1e-10)
+                .unwrap();
+        let solver: AugmentedLagrangian<_, IterState<Vec<f64>, (), (), (), (), f64>, f64> =
+            AugmentedLagrangian::new(inner)
+                .with_tolerance(1e-5)
+                .unwrap();
+        let res = Executor::new(IneqProblem {}, solver)
+            .configure(|state| state.param(vec![0.5, -0.5]).max_iters(50))
+            .run()
+            .unwrap();
+        let param = res.state.get_best_param().unwrap();
+        let sqrt5 = 5.0f64.sqrt();
+        assert_relative_eq!(param[0], 2.0 / sqrt5, epsilon = 1e-4);
+        assert_relative_eq!(param[1], 1.0 / sqrt5, epsilon = 1e-4);
+        assert!(!res
+            .state
+            .get_func_counts()
+            .contains_key("inequality_jacobian_count"));
+    }
+
+    #[test]
+    fn test_missing_jacobian() {
+        struct NoJacobian {}
+
+        impl CostFunction for NoJacobian {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(p[0].powi(2))
+            }
+        }
+
+        impl Gradient for NoJacobian {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(vec![2.0 * p[0]])
+            }
+        }
+
+        impl EqualityConstraints for NoJacobian {
+            type Param = Vec<f64>;
+            type Float = f64;
+
+            fn equality_constraints(&self, p: &Self::Param) -> Result<Vec<f64>, Error> {
+                Ok(vec![p[0] - 1.0])
+            }
+        }
+
+        impl InequalityConstraints for NoJacobian {
+            type Param = Vec<f64>;
+            type Float = f64;
+        }
+
+        let res = Executor::new(NoJacobian {}, lbfgs())
+            .configure(|state| state.param(vec![0.0]))
+            .run();
+        assert_error!(
+            res,
+            ArgminError,
+            "Not implemented: \"Method `equality_jacobian` of EqualityConstraints trait not implemented!\""
+        );
+    }
+}
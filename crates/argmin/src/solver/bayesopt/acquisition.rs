@@ -0,0 +1,197 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, Error};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Acquisition function of [`BayesOpt`](`super::BayesOpt`)
+///
+/// The acquisition function rates a candidate point `x` based on the posterior mean `mu(x)` and
+/// standard deviation `sigma(x)` of the surrogate and the best cost function value observed so
+/// far `f*`. The point which maximizes the acquisition function is evaluated next. All quantities,
+/// including the parameters `xi` and `kappa`, are measured in units of the standard deviation of
+/// the observed cost function values.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum AcquisitionFunction<F> {
+    /// Expected improvement `E[max(f* - f(x) - xi, 0)]`
+    ExpectedImprovement {
+        /// Minimum improvement; larger values favor exploration
+        xi: F,
+    },
+    /// Probability of improvement `P(f(x) < f* - xi)`
+    ProbabilityOfImprovement {
+        /// Minimum improvement; larger values favor exploration
+        xi: F,
+    },
+    /// Upper confidence bound `kappa sigma(x) - mu(x)` of the negated cost function
+    UpperConfidenceBound {
+        /// Weight of the standard deviation; larger values favor exploration
+        kappa: F,
+    },
+}
+
+impl<F: ArgminFloat> Default for AcquisitionFunction<F> {
+    /// Expected improvement with `xi = 0.01`
+    fn default() -> Self {
+        AcquisitionFunction::ExpectedImprovement { xi: float!(0.01) }
+    }
+}
+
+impl<F: ArgminFloat> AcquisitionFunction<F> {
+    /// Checks that the parameter of the acquisition function is non-negative
+    pub(super) fn validate(&self) -> Result<(), Error> {
+        let (name, value) = match *self {
+            AcquisitionFunction::ExpectedImprovement { xi }
+            | AcquisitionFunction::ProbabilityOfImprovement { xi } => ("xi", xi),
+            AcquisitionFunction::UpperConfidenceBound { kappa } => ("kappa", kappa),
+        };
+        if value < float!(0.0) || value.is_nan() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!("`BayesOpt`: {name} of the acquisition function must be >= 0.")
+            ));
+        }
+        Ok(())
+    }
+
+    /// Value of the acquisition function given the posterior `mean` and standard deviation `std`
+    /// and the best observed value `best`
+    pub(super) fn value(&self, mean: F, std: F, best: F) -> F {
+        match *self {
+            AcquisitionFunction::ExpectedImprovement { xi } => {
+                let improvement = best - mean - xi;
+                if std <= F::epsilon() {
+                    return improvement.max(float!(0.0));
+                }
+                let z = improvement / std;
+                improvement * norm_cdf(z) + std * norm_pdf(z)
+            }
+            AcquisitionFunction::ProbabilityOfImprovement { xi } => {
+                let improvement = best - mean - xi;
+                if std <= F::epsilon() {
+                    return if improvement > float!(0.0) {
+                        float!(1.0)
+                    } else {
+                        float!(0.0)
+                    };
+                }
+                norm_cdf(improvement / std)
+            }
+            AcquisitionFunction::UpperConfidenceBound { kappa } => kappa * std - mean,
+        }
+    }
+}
+
+/// Probability density function of the standard normal distribution
+fn norm_pdf<F: ArgminFloat>(z: F) -> F {
+    (-z * z / float!(2.0)).exp() / F::TAU().sqrt()
+}
+
+/// Cumulative distribution function of the standard normal distribution
+fn norm_cdf<F: ArgminFloat>(z: F) -> F {
+    erfc(-z / F::SQRT_2()) / float!(2.0)
+}
+
+/// Complementary error function
+///
+/// Chebyshev approximation with a relative error below `1.2e-7` (Numerical Recipes, 2nd edition,
+/// section 6.2).
+fn erfc<F: ArgminFloat>(x: F) -> F {
+    let t = float!(1.0) / (float!(1.0) + x.abs() / float!(2.0));
+    let poly = [
+        -1.26551223,
+        1.00002368,
+        0.37409196,
+        0.09678418,
+        -0.18628806,
+        0.27886807,
+        -1.13520398,
+        1.48851587,
+        -0.82215223,
+        0.17087277,
+    ]
+    .iter()
+    .rev()
+    .fold(float!(0.0), |acc, &c| acc * t + float!(c));
+    let ans = t * (-x * x + poly).exp();
+    if x >= float!(0.0) {
+        ans
+    } else {
+        float!(2.0) - ans
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_norm_cdf() {
+        assert_relative_eq!(norm_cdf(0.0f64), 0.5, epsilon = 1e-7);
+        assert_relative_eq!(norm_cdf(1.0f64), 0.8413447460685429, epsilon = 1e-7);
+        assert_relative_eq!(norm_cdf(-1.96f64), 0.024997895148220435, epsilon = 1e-7);
+        assert_relative_eq!(norm_cdf(3.0f64), 0.9986501019683699, epsilon = 1e-7);
+        assert_relative_eq!(norm_pdf(0.0f64), 0.3989422804014327, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_expected_improvement() {
+        let ei = AcquisitionFunction::ExpectedImprovement { xi: 0.0f64 };
+        // Closed form for improvement 0: sigma / sqrt(2 pi)
+        assert_relative_eq!(
+            ei.value(1.0, 2.0, 1.0),
+            2.0 * 0.3989422804014327,
+            epsilon = 1e-7
+        );
+        // Without uncertainty, the expected improvement is the improvement
+        assert_relative_eq!(ei.value(0.5, 0.0, 1.0), 0.5, epsilon = f64::EPSILON);
+        assert_relative_eq!(ei.value(1.5, 0.0, 1.0), 0.0, epsilon = f64::EPSILON);
+        // More uncertainty and lower means are preferred
+        assert!(ei.value(1.0, 2.0, 1.0) > ei.value(1.0, 1.0, 1.0));
+        assert!(ei.value(0.0, 1.0, 1.0) > ei.value(1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_probability_of_improvement() {
+        let pi = AcquisitionFunction::ProbabilityOfImprovement { xi: 0.0f64 };
+        assert_relative_eq!(pi.value(1.0, 2.0, 1.0), 0.5, epsilon = 1e-7);
+        assert_relative_eq!(pi.value(0.0, 1.0, 1.0), 0.8413447460685429, epsilon = 1e-7);
+        assert_relative_eq!(pi.value(0.5, 0.0, 1.0), 1.0, epsilon = f64::EPSILON);
+        assert_relative_eq!(pi.value(1.5, 0.0, 1.0), 0.0, epsilon = f64::EPSILON);
+    }
+
+    #[test]
+    fn test_upper_confidence_bound() {
+        let ucb = AcquisitionFunction::UpperConfidenceBound { kappa: 2.0f64 };
+        assert_relative_eq!(ucb.value(1.0, 0.5, 0.0), 0.0, epsilon = f64::EPSILON);
+        assert_relative_eq!(ucb.value(-1.0, 1.0, 0.0), 3.0, epsilon = f64::EPSILON);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(AcquisitionFunction::ExpectedImprovement { xi: 0.0f64 }
+            .validate()
+            .is_ok());
+        assert!(AcquisitionFunction::UpperConfidenceBound { kappa: 3.0f64 }
+            .validate()
+            .is_ok());
+        assert_error!(
+            AcquisitionFunction::ProbabilityOfImprovement { xi: -1.0f64 }.validate(),
+            ArgminError,
+            "Invalid parameter: \"`BayesOpt`: xi of the acquisition function must be >= 0.\""
+        );
+        assert_error!(
+            AcquisitionFunction::UpperConfidenceBound { kappa: f64::NAN }.validate(),
+            ArgminError,
+            "Invalid parameter: \"`BayesOpt`: kappa of the acquisition function must be >= 0.\""
+        );
+    }
+}
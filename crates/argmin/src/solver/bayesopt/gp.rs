@@ -0,0 +1,362 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, CostFunction, Error, Executor, State};
+use crate::solver::brent::BrentOpt;
+use crate::solver::dense::{chol_solve, cholesky, dot, forward_substitution};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Smallest nugget (noise variance relative to the signal variance) of the surrogate
+const MIN_NUGGET: f64 = 1e-6;
+
+/// Largest nugget of the surrogate
+const MAX_NUGGET: f64 = 1.0;
+
+/// Smallest length scale of the surrogate in the unit cube
+const MIN_LENGTHSCALE: f64 = 1e-2;
+
+/// Number of coordinate-wise sweeps over the hyperparameters
+const HYPERPARAMETER_SWEEPS: usize = 2;
+
+/// Maximum number of iterations of each one-dimensional hyperparameter search
+const HYPERPARAMETER_ITERS: u64 = 50;
+
+/// Covariance function of the Gaussian process surrogate of [`BayesOpt`](`super::BayesOpt`)
+///
+/// The kernels are stationary and isotropic: the correlation of two points only depends on their
+/// scaled distance `r = ||x - x'|| / l`, where the length scale `l` is fitted to the observations.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum Kernel {
+    /// Squared exponential kernel `exp(-r^2 / 2)`, which leads to very smooth surrogates
+    RBF,
+    /// Matérn kernel with smoothness `3/2`: `(1 + sqrt(3) r) exp(-sqrt(3) r)`
+    Matern32,
+    /// Matérn kernel with smoothness `5/2`: `(1 + sqrt(5) r + 5 r^2 / 3) exp(-sqrt(5) r)`
+    Matern52,
+}
+
+impl Kernel {
+    /// Correlation of two points at scaled distance `r`
+    fn correlation<F: ArgminFloat>(&self, r: F) -> F {
+        match self {
+            Kernel::RBF => (-r * r / float!(2.0)).exp(),
+            Kernel::Matern32 => {
+                let s = float!(3.0f64.sqrt()) * r;
+                (float!(1.0) + s) * (-s).exp()
+            }
+            Kernel::Matern52 => {
+                let s = float!(5.0f64.sqrt()) * r;
+                (float!(1.0) + s + s * s / float!(3.0)) * (-s).exp()
+            }
+        }
+    }
+}
+
+/// Gaussian process regression model with constant mean
+///
+/// The covariance of the observations is `s^2 (R + g I)`, where `R` is the correlation matrix
+/// given by the kernel and the length scale `l`, and `g` is the nugget which models observation
+/// noise. The signal variance `s^2` is estimated in closed form, while `l` and `g` maximize the
+/// concentrated log marginal likelihood. Observations are standardized internally; means and
+/// standard deviations are therefore returned in units of the standard deviation of the
+/// observations.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub(super) struct GaussianProcess<F> {
+    /// Covariance function
+    kernel: Kernel,
+    /// Observed points
+    xs: Vec<Vec<F>>,
+    /// Standardized observations
+    ys: Vec<F>,
+    /// Mean of the observations
+    y_mean: F,
+    /// Standard deviation of the observations
+    y_std: F,
+    /// Length scale
+    lengthscale: F,
+    /// Noise variance relative to the signal variance
+    nugget: F,
+    /// Signal variance
+    variance: F,
+    /// Cholesky factor of `R + g I`
+    chol: Vec<Vec<F>>,
+    /// `(R + g I)^-1 y`
+    alpha: Vec<F>,
+}
+
+impl<F: ArgminFloat> GaussianProcess<F> {
+    /// Construct an empty Gaussian process with the given kernel
+    pub(super) fn new(kernel: Kernel) -> Self {
+        GaussianProcess {
+            kernel,
+            xs: vec![],
+            ys: vec![],
+            y_mean: float!(0.0),
+            y_std: float!(1.0),
+            lengthscale: float!(0.5),
+            nugget: float!(MIN_NUGGET),
+            variance: float!(1.0),
+            chol: vec![],
+            alpha: vec![],
+        }
+    }
+
+    /// Length scale
+    pub(super) fn lengthscale(&self) -> F {
+        self.lengthscale
+    }
+
+    /// Noise variance relative to the signal variance
+    pub(super) fn nugget(&self) -> F {
+        self.nugget
+    }
+
+    /// Fits the Gaussian process including its hyperparameters to the observations `ys` at `xs`
+    ///
+    /// The hyperparameters are optimized on a logarithmic scale, one after another, with
+    /// [`BrentOpt`], starting from the values of the previous fit.
+    pub(super) fn fit(&mut self, xs: &[Vec<F>], ys: &[F]) -> Result<(), Error> {
+        let n = F::from_usize(ys.len()).unwrap();
+        self.y_mean = ys.iter().fold(float!(0.0), |acc, &y| acc + y) / n;
+        let var = ys
+            .iter()
+            .fold(float!(0.0), |acc, &y| acc + (y - self.y_mean).powi(2))
+            / n;
+        self.y_std = if var > float!(0.0) {
+            var.sqrt()
+        } else {
+            float!(1.0)
+        };
+        self.xs = xs.to_vec();
+        self.ys = ys.iter().map(|&y| (y - self.y_mean) / self.y_std).collect();
+
+        let dim = F::from_usize(xs.first().map_or(1, |x| x.len())).unwrap();
+        let lengthscale_bounds = (
+            float!(MIN_LENGTHSCALE.ln()),
+            (float!(10.0) * dim.sqrt()).ln(),
+        );
+        let nugget_bounds = (float!(MIN_NUGGET.ln()), float!(MAX_NUGGET.ln()));
+        for _ in 0..HYPERPARAMETER_SWEEPS {
+            self.lengthscale = self
+                .minimize_likelihood(Hyperparameter::Lengthscale, lengthscale_bounds)?
+                .exp();
+            self.nugget = self
+                .minimize_likelihood(Hyperparameter::Nugget, nugget_bounds)?
+                .exp();
+        }
+        self.factorize()
+    }
+
+    /// Adds an observation without refitting the hyperparameters
+    ///
+    /// `y` is given in units of the standardized observations.
+    pub(super) fn condition(&mut self, x: Vec<F>, y: F) -> Result<(), Error> {
+        self.xs.push(x);
+        self.ys.push(y);
+        self.factorize()
+    }
+
+    /// Converts a cost function value to units of the standardized observations
+    pub(super) fn standardize(&self, y: F) -> F {
+        (y - self.y_mean) / self.y_std
+    }
+
+    /// Posterior mean and standard deviation at `x`
+    pub(super) fn predict(&self, x: &[F]) -> (F, F) {
+        let k: Vec<F> = self
+            .xs
+            .iter()
+            .map(|xi| self.kernel.correlation(distance(xi, x) / self.lengthscale))
+            .collect();
+        let mean = dot(&k, &self.alpha);
+        let v = forward_substitution(&self.chol, &k);
+        let var = self.variance * (float!(1.0) - dot(&v, &v));
+        (mean, var.max(float!(0.0)).sqrt())
+    }
+
+    /// Minimizes the negative log likelihood with respect to the logarithm of one hyperparameter
+    fn minimize_likelihood(&self, which: Hyperparameter, bounds: (F, F)) -> Result<F, Error> {
+        let current = match which {
+            Hyperparameter::Lengthscale => self.lengthscale,
+            Hyperparameter::Nugget => self.nugget,
+        };
+        let likelihood = NegLogLikelihood { gp: self, which };
+        let current_cost = likelihood.cost(&current.ln())?;
+        let res = Executor::new(
+            likelihood,
+            BrentOpt::new(bounds.0, bounds.1).set_tolerance(F::epsilon().sqrt(), float!(1e-3)),
+        )
+        .configure(|state| state.max_iters(HYPERPARAMETER_ITERS))
+        .ctrlc(false)
+        .run()?;
+        let state = res.state();
+        Ok(match state.get_best_param() {
+            Some(&t) if state.get_best_cost() < current_cost => t,
+            _ => current.ln(),
+        })
+    }
+
+    /// Factorizes the covariance matrix for the current hyperparameters
+    fn factorize(&mut self) -> Result<(), Error> {
+        let factorization =
+            self.likelihood(self.lengthscale, self.nugget)
+                .ok_or_else(argmin_error_closure!(
+                    PotentialBug,
+                    "`BayesOpt`: Covariance matrix of the surrogate is not positive definite."
+                ))?;
+        self.chol = factorization.chol;
+        self.alpha = factorization.alpha;
+        self.variance = factorization.variance;
+        Ok(())
+    }
+
+    /// Factorizes `R + g I` for the given hyperparameters, or returns `None` if it is not positive
+    /// definite
+    fn likelihood(&self, lengthscale: F, nugget: F) -> Option<Factorization<F>> {
+        let n = self.xs.len();
+        // Lower triangle of `R + g I`
+        let a: Vec<Vec<F>> = self
+            .xs
+            .iter()
+            .enumerate()
+            .map(|(i, xi)| {
+                self.xs[..i]
+                    .iter()
+                    .map(|xj| self.kernel.correlation(distance(xi, xj) / lengthscale))
+                    .chain(std::iter::once(float!(1.0) + nugget))
+                    .collect()
+            })
+            .collect();
+        let chol = cholesky(&a)?;
+        let alpha = chol_solve(&chol, &self.ys);
+        let nf = F::from_usize(n).unwrap();
+        let variance = (dot(&self.ys, &alpha) / nf).max(F::min_positive_value());
+        let log_det = (0..n).fold(float!(0.0), |acc, i| acc + chol[i][i].ln());
+        let nll = nf / float!(2.0) * variance.ln() + log_det;
+        Some(Factorization {
+            chol,
+            alpha,
+            variance,
+            nll,
+        })
+    }
+}
+
+/// Factorized covariance matrix of the Gaussian process for a set of hyperparameters
+struct Factorization<F> {
+    /// Cholesky factor of `R + g I`
+    chol: Vec<Vec<F>>,
+    /// `(R + g I)^-1 y`
+    alpha: Vec<F>,
+    /// Estimated signal variance
+    variance: F,
+    /// Concentrated negative log likelihood
+    nll: F,
+}
+
+/// Hyperparameter of the Gaussian process
+#[derive(Clone, Copy)]
+enum Hyperparameter {
+    Lengthscale,
+    Nugget,
+}
+
+/// Concentrated negative log likelihood as a function of the logarithm of one hyperparameter
+struct NegLogLikelihood<'a, F> {
+    gp: &'a GaussianProcess<F>,
+    which: Hyperparameter,
+}
+
+impl<F: ArgminFloat> CostFunction for NegLogLikelihood<'_, F> {
+    type Param = F;
+    type Output = F;
+
+    fn cost(&self, log_value: &Self::Param) -> Result<Self::Output, Error> {
+        let (lengthscale, nugget) = match self.which {
+            Hyperparameter::Lengthscale => (log_value.exp(), self.gp.nugget),
+            Hyperparameter::Nugget => (self.gp.lengthscale, log_value.exp()),
+        };
+        Ok(self
+            .gp
+            .likelihood(lengthscale, nugget)
+            .map_or(F::infinity(), |factorization| factorization.nll))
+    }
+}
+
+fn distance<F: ArgminFloat>(a: &[F], b: &[F]) -> F {
+    a.iter()
+        .zip(b.iter())
+        .fold(float!(0.0), |acc, (&ai, &bi)| acc + (ai - bi).powi(2))
+        .sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn data() -> (Vec<Vec<f64>>, Vec<f64>) {
+        let xs: Vec<Vec<f64>> = (0..8).map(|i| vec![i as f64 / 7.0]).collect();
+        let ys = xs.iter().map(|x| (6.0 * x[0]).sin() + x[0]).collect();
+        (xs, ys)
+    }
+
+    #[test]
+    fn test_kernels() {
+        for kernel in [Kernel::RBF, Kernel::Matern32, Kernel::Matern52] {
+            assert_relative_eq!(kernel.correlation(0.0f64), 1.0, epsilon = f64::EPSILON);
+            let mut last = 1.0;
+            for i in 1..20 {
+                let c = kernel.correlation(i as f64 * 0.25);
+                assert!(c > 0.0);
+                assert!(c < last);
+                last = c;
+            }
+        }
+        assert_relative_eq!(
+            Kernel::RBF.correlation(1.0f64),
+            (-0.5f64).exp(),
+            epsilon = f64::EPSILON
+        );
+    }
+
+    #[test]
+    fn test_interpolation() {
+        let (xs, ys) = data();
+        for kernel in [Kernel::RBF, Kernel::Matern32, Kernel::Matern52] {
+            let mut gp = GaussianProcess::new(kernel);
+            gp.fit(&xs, &ys).unwrap();
+            assert!(gp.lengthscale() >= MIN_LENGTHSCALE);
+            assert!(gp.nugget() >= MIN_NUGGET);
+            for (x, &y) in xs.iter().zip(ys.iter()) {
+                let (mean, std) = gp.predict(x);
+                assert_relative_eq!(mean, gp.standardize(y), epsilon = 1e-2);
+                assert!(std < 1e-1);
+            }
+            // Uncertainty grows away from the observations
+            let (_, std_between) = gp.predict(&[1.0 / 14.0]);
+            let (_, std_outside) = gp.predict(&[3.0]);
+            assert!(std_outside > std_between);
+        }
+    }
+
+    #[test]
+    fn test_condition() {
+        let (xs, ys) = data();
+        let mut gp = GaussianProcess::new(Kernel::Matern52);
+        gp.fit(&xs, &ys).unwrap();
+        let lengthscale = gp.lengthscale();
+        gp.condition(vec![1.5], 2.0).unwrap();
+        assert_relative_eq!(gp.lengthscale(), lengthscale, epsilon = f64::EPSILON);
+        let (mean, std) = gp.predict(&[1.5]);
+        assert_relative_eq!(mean, 2.0, epsilon = 1e-2);
+        assert!(std < 1e-1);
+    }
+}
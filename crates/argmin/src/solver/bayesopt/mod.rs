@@ -0,0 +1,803 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Bayesian optimization
+//!
+//! Global, derivative-free optimization of expensive cost functions within box constraints. A
+//! Gaussian process surrogate is fitted to all cost function values observed so far and the next
+//! point is chosen by maximizing an acquisition function, which trades off exploration of
+//! uncertain regions against exploitation of the surrogate.
+//!
+//! See [`BayesOpt`] for details.
+//!
+//! ## References
+//!
+//! \[0\] Donald R. Jones, Matthias Schonlau and William J. Welch (1998). Efficient Global
+//! Optimization of Expensive Black-Box Functions. Journal of Global Optimization 13, 455–492.
+//! DOI: <https://doi.org/10.1023/A:1008306431147>
+//!
+//! \[1\] Carl Edward Rasmussen and Christopher K. I. Williams (2006). Gaussian Processes for
+//! Machine Learning. MIT Press. <http://gaussianprocess.org/gpml/>
+//!
+//! \[2\] Bobak Shahriari, Kevin Swersky, Ziyu Wang, Ryan P. Adams and Nando de Freitas (2016).
+//! Taking the Human Out of the Loop: A Review of Bayesian Optimization. Proceedings of the IEEE
+//! 104 (1), 148–175. DOI: <https://doi.org/10.1109/JPROC.2015.2494218>
+//!
+//! \[3\] David Ginsbourger, Rodolphe Le Riche and Laurent Carraro (2010). Kriging Is Well-Suited
+//! to Parallelize Optimization. Computational Intelligence in Expensive Optimization Problems,
+//! 131–162. DOI: <https://doi.org/10.1007/978-3-642-10701-6_6>
+
+mod acquisition;
+mod gp;
+
+pub use self::acquisition::AcquisitionFunction;
+pub use self::gp::Kernel;
+
+use self::gp::GaussianProcess;
+use crate::core::{
+    ArgminFloat, CostFunction, Error, Executor, IterState, Problem, Solver, State, SyncAlias, KV,
+};
+use crate::solver::particleswarm::ParticleSwarm;
+use argmin_math::{
+    ArgminAdd, ArgminGet, ArgminMinMax, ArgminMul, ArgminRandom, ArgminSet, ArgminSize, ArgminSub,
+    ArgminZeroLike,
+};
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+/// # Bayesian optimization
+///
+/// Minimizes a cost function within the box `lower <= x <= upper` with as few cost function
+/// evaluations as possible. It is therefore suited for expensive cost functions, where a budget
+/// of up to a few hundred evaluations is available.
+///
+/// In [`init`](`Solver::init`), an initial design of `n_init` points is sampled from the box via
+/// Latin hypercube sampling (together with the initial parameter vector, if one is provided) and
+/// evaluated. In every iteration, a Gaussian process with the chosen [`Kernel`] is fitted to all
+/// observations. Its length scale and noise level maximize the marginal likelihood. The next point
+/// to evaluate is the maximizer of the [`AcquisitionFunction`], which is found with
+/// [`ParticleSwarm`] within the bounds.
+///
+/// By default, one point is evaluated per iteration. With
+/// [`with_batch_size`](`BayesOpt::with_batch_size`), a batch of points is proposed per iteration:
+/// after each proposal, the surrogate is conditioned on its own prediction at the proposed point
+/// ("kriging believer", \[3\]) before the next point is chosen. The points of the initial design
+/// and of each batch are evaluated via [`Problem::bulk_cost`], therefore the `rayon` feature
+/// enables parallel computation of the cost function.
+///
+/// Parameter vector and cost function value of the state hold the best point of the most recent
+/// batch, while the best point found so far is available as best parameter vector. The method
+/// does not converge on its own, therefore a maximum number of iterations should be set. The
+/// length scale and noise level of the surrogate, the maximal value of the acquisition function
+/// and the number of observations are reported as KV metrics `lengthscale`, `noise`,
+/// `acquisition` and `observations`. The computational cost of fitting the surrogate grows with
+/// the third power of the number of observations.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`].
+///
+/// ## References
+///
+/// \[0\] Donald R. Jones, Matthias Schonlau and William J. Welch (1998). Efficient Global
+/// Optimization of Expensive Black-Box Functions. Journal of Global Optimization 13, 455–492.
+/// DOI: <https://doi.org/10.1023/A:1008306431147>
+///
+/// \[1\] Carl Edward Rasmussen and Christopher K. I. Williams (2006). Gaussian Processes for
+/// Machine Learning. MIT Press. <http://gaussianprocess.org/gpml/>
+///
+/// \[2\] Bobak Shahriari, Kevin Swersky, Ziyu Wang, Ryan P. Adams and Nando de Freitas (2016).
+/// Taking the Human Out of the Loop: A Review of Bayesian Optimization. Proceedings of the IEEE
+/// 104 (1), 148–175. DOI: <https://doi.org/10.1109/JPROC.2015.2494218>
+///
+/// \[3\] David Ginsbourger, Rodolphe Le Riche and Laurent Carraro (2010). Kriging Is Well-Suited
+/// to Parallelize Optimization. Computational Intelligence in Expensive Optimization Problems,
+/// 131–162. DOI: <https://doi.org/10.1007/978-3-642-10701-6_6>
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct BayesOpt<P, F, R> {
+    /// Bounds on parameter space
+    bounds: (P, P),
+    /// Covariance function of the surrogate
+    kernel: Kernel,
+    /// Acquisition function
+    acquisition: AcquisitionFunction<F>,
+    /// User-provided size of the initial design
+    init_samples: Option<usize>,
+    /// Number of points evaluated per iteration
+    batch_size: usize,
+    /// Number of particles used to maximize the acquisition function
+    num_particles: usize,
+    /// Number of iterations used to maximize the acquisition function
+    acquisition_iters: u64,
+    /// Random number generator
+    rng_generator: R,
+    /// Lower bounds
+    lower: Vec<F>,
+    /// Upper bounds
+    upper: Vec<F>,
+    /// Evaluated points, scaled to the unit cube
+    xs: Vec<Vec<F>>,
+    /// Cost function values at the evaluated points
+    ys: Vec<F>,
+    /// Surrogate
+    gp: GaussianProcess<F>,
+}
+
+impl<P, F> BayesOpt<P, F, rand::rngs::StdRng>
+where
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `BayesOpt`
+    ///
+    /// Takes the bounds on the search space as input. `bounds` is a tuple
+    /// `(lower_bound, upper_bound)`, where `lower_bound` and `upper_bound` are of the same type
+    /// and length as the parameter vector. Both bounds must be finite.
+    ///
+    /// Defaults to the [`Matern52`](`Kernel::Matern52`) kernel and the
+    /// [`ExpectedImprovement`](`AcquisitionFunction::ExpectedImprovement`) acquisition function
+    /// with `xi = 0.01`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::bayesopt::BayesOpt;
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let bo: BayesOpt<_, f64, _> = BayesOpt::new((lower_bound, upper_bound));
+    /// ```
+    pub fn new(bounds: (P, P)) -> Self {
+        BayesOpt {
+            bounds,
+            kernel: Kernel::Matern52,
+            acquisition: AcquisitionFunction::default(),
+            init_samples: None,
+            batch_size: 1,
+            num_particles: 40,
+            acquisition_iters: 50,
+            rng_generator: rand::rngs::StdRng::from_entropy(),
+            lower: vec![],
+            upper: vec![],
+            xs: vec![],
+            ys: vec![],
+            gp: GaussianProcess::new(Kernel::Matern52),
+        }
+    }
+}
+
+impl<P, F, R0> BayesOpt<P, F, R0>
+where
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+    R0: Rng,
+{
+    /// Set the random number generator
+    ///
+    /// Defaults to `rand::rngs::StdRng::from_entropy()`. The random number generator is used for
+    /// the initial design and to seed the [`ParticleSwarm`] which maximizes the acquisition
+    /// function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::bayesopt::BayesOpt;
+    /// # use rand::SeedableRng;
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let bo: BayesOpt<_, f64, _> = BayesOpt::new((lower_bound, upper_bound))
+    ///     .with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(1729));
+    /// ```
+    pub fn with_rng_generator<R1: Rng>(self, generator: R1) -> BayesOpt<P, F, R1> {
+        BayesOpt {
+            bounds: self.bounds,
+            kernel: self.kernel,
+            acquisition: self.acquisition,
+            init_samples: self.init_samples,
+            batch_size: self.batch_size,
+            num_particles: self.num_particles,
+            acquisition_iters: self.acquisition_iters,
+            rng_generator: generator,
+            lower: self.lower,
+            upper: self.upper,
+            xs: self.xs,
+            ys: self.ys,
+            gp: self.gp,
+        }
+    }
+}
+
+impl<P, F, R> BayesOpt<P, F, R>
+where
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+    R: Rng,
+{
+    /// Set the covariance function of the Gaussian process surrogate
+    ///
+    /// Defaults to [`Kernel::Matern52`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::bayesopt::{BayesOpt, Kernel};
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let bo: BayesOpt<_, f64, _> =
+    ///     BayesOpt::new((lower_bound, upper_bound)).with_kernel(Kernel::RBF);
+    /// ```
+    #[must_use]
+    pub fn with_kernel(mut self, kernel: Kernel) -> Self {
+        self.kernel = kernel;
+        self.gp = GaussianProcess::new(kernel);
+        self
+    }
+
+    /// Set the acquisition function
+    ///
+    /// Its parameter (`xi` or `kappa`) must be non-negative. Defaults to
+    /// [`ExpectedImprovement`](`AcquisitionFunction::ExpectedImprovement`) with `xi = 0.01`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::bayesopt::{AcquisitionFunction, BayesOpt};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let bo: BayesOpt<_, f64, _> = BayesOpt::new((lower_bound, upper_bound))
+    ///     .with_acquisition(AcquisitionFunction::UpperConfidenceBound { kappa: 2.0 })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_acquisition(mut self, acquisition: AcquisitionFunction<F>) -> Result<Self, Error> {
+        acquisition.validate()?;
+        self.acquisition = acquisition;
+        Ok(self)
+    }
+
+    /// Set the number of points of the initial design
+    ///
+    /// Must be at least `2`. Defaults to `2 n + 1`, where `n` is the number of parameters. If an
+    /// initial parameter vector is provided, it is evaluated in addition to these points.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::bayesopt::BayesOpt;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let bo: BayesOpt<_, f64, _> =
+    ///     BayesOpt::new((lower_bound, upper_bound)).with_initial_samples(10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_initial_samples(mut self, n_init: usize) -> Result<Self, Error> {
+        if n_init < 2 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BayesOpt`: number of initial samples must be >= 2."
+            ));
+        }
+        self.init_samples = Some(n_init);
+        Ok(self)
+    }
+
+    /// Set the number of points which are proposed and evaluated per iteration
+    ///
+    /// Must be at least `1`. Defaults to `1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::bayesopt::BayesOpt;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let bo: BayesOpt<_, f64, _> = BayesOpt::new((lower_bound, upper_bound)).with_batch_size(4)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_batch_size(mut self, batch_size: usize) -> Result<Self, Error> {
+        if batch_size < 1 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BayesOpt`: batch size must be >= 1."
+            ));
+        }
+        self.batch_size = batch_size;
+        Ok(self)
+    }
+
+    /// Set the number of particles and iterations of the [`ParticleSwarm`] which maximizes the
+    /// acquisition function
+    ///
+    /// Both must be at least `1`. Defaults to `40` particles and `50` iterations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::bayesopt::BayesOpt;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let bo: BayesOpt<_, f64, _> =
+    ///     BayesOpt::new((lower_bound, upper_bound)).with_acquisition_optimizer(100, 100)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_acquisition_optimizer(
+        mut self,
+        num_particles: usize,
+        max_iters: u64,
+    ) -> Result<Self, Error> {
+        if num_particles < 1 || max_iters < 1 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BayesOpt`: number of particles and iterations of the acquisition optimizer must be >= 1."
+            ));
+        }
+        self.num_particles = num_particles;
+        self.acquisition_iters = max_iters;
+        Ok(self)
+    }
+
+    /// Scales a parameter vector to the unit cube
+    fn to_unit(&self, param: &P) -> Vec<F> {
+        to_unit(param, &self.lower, &self.upper)
+    }
+
+    /// Converts a point of the unit cube into a parameter vector
+    fn to_param(&self, x: &[F]) -> P {
+        let mut param = self.bounds.0.clone();
+        for (i, &xi) in x.iter().enumerate() {
+            param.set(i, self.lower[i] + xi * (self.upper[i] - self.lower[i]));
+        }
+        param
+    }
+
+    /// Latin hypercube sample of `m` points in the unit cube
+    fn latin_hypercube(&mut self, m: usize) -> Vec<Vec<F>> {
+        let n = self.lower.len();
+        let mut xs = vec![vec![float!(0.0); n]; m];
+        for j in 0..n {
+            let mut strata: Vec<usize> = (0..m).collect();
+            for i in (1..m).rev() {
+                strata.swap(i, self.rng_generator.gen_range(0..=i));
+            }
+            for (x, stratum) in xs.iter_mut().zip(strata) {
+                let u: f64 = self.rng_generator.gen();
+                x[j] = float!((stratum as f64 + u) / m as f64);
+            }
+        }
+        xs
+    }
+
+    /// Evaluates the points `xs` of the unit cube and records the observations
+    ///
+    /// Returns the best of the evaluated parameter vectors and its cost function value.
+    fn evaluate<O>(&mut self, problem: &mut Problem<O>, xs: Vec<Vec<F>>) -> Result<(P, F), Error>
+    where
+        O: CostFunction<Param = P, Output = F> + SyncAlias,
+        P: SyncAlias,
+    {
+        let params: Vec<P> = xs.iter().map(|x| self.to_param(x)).collect();
+        let costs = problem.bulk_cost(&params)?;
+        let best = (0..costs.len())
+            .min_by(|&a, &b| {
+                costs[a]
+                    .partial_cmp(&costs[b])
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .unwrap();
+        let best_cost = costs[best];
+        let best_param = params.into_iter().nth(best).unwrap();
+        self.xs.extend(xs);
+        self.ys.extend(costs);
+        Ok((best_param, best_cost))
+    }
+}
+
+/// Scales a parameter vector to the unit cube `lower <= x <= upper`
+fn to_unit<P, F>(param: &P, lower: &[F], upper: &[F]) -> Vec<F>
+where
+    P: ArgminGet<usize, F>,
+    F: ArgminFloat,
+{
+    lower
+        .iter()
+        .zip(upper.iter())
+        .enumerate()
+        .map(|(i, (&l, &u))| {
+            ((param.get(i) - l) / (u - l))
+                .max(float!(0.0))
+                .min(float!(1.0))
+        })
+        .collect()
+}
+
+/// Negated acquisition function, minimized by [`ParticleSwarm`]
+struct AcquisitionProblem<'a, P, F> {
+    /// Surrogate
+    gp: &'a GaussianProcess<F>,
+    /// Acquisition function
+    acquisition: AcquisitionFunction<F>,
+    /// Best observation in units of the surrogate
+    best: F,
+    /// Lower bounds
+    lower: &'a [F],
+    /// Upper bounds
+    upper: &'a [F],
+    _param: PhantomData<P>,
+}
+
+impl<P, F> CostFunction for AcquisitionProblem<'_, P, F>
+where
+    P: ArgminGet<usize, F>,
+    F: ArgminFloat,
+{
+    type Param = P;
+    type Output = F;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        let (mean, std) = self.gp.predict(&to_unit(param, self.lower, self.upper));
+        Ok(-self.acquisition.value(mean, std, self.best))
+    }
+}
+
+impl<O, P, F, R> Solver<O, IterState<P, (), (), (), (), F>> for BayesOpt<P, F, R>
+where
+    O: CostFunction<Param = P, Output = F> + SyncAlias,
+    P: Clone
+        + SyncAlias
+        + ArgminAdd<P, P>
+        + ArgminSub<P, P>
+        + ArgminMul<F, P>
+        + ArgminZeroLike
+        + ArgminRandom
+        + ArgminMinMax
+        + ArgminGet<usize, F>
+        + ArgminSet<usize, F>
+        + ArgminSize<usize>,
+    F: ArgminFloat + SyncAlias,
+    R: Rng,
+{
+    const NAME: &'static str = "Bayesian Optimization";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let n = self.bounds.0.shape();
+        if n < 1 || self.bounds.1.shape() != n {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BayesOpt`: lower and upper bound must have the same, non-zero length."
+            ));
+        }
+        self.lower = (0..n).map(|i| self.bounds.0.get(i)).collect();
+        self.upper = (0..n).map(|i| self.bounds.1.get(i)).collect();
+        if self
+            .lower
+            .iter()
+            .zip(self.upper.iter())
+            .any(|(&l, &u)| l >= u || !l.is_finite() || !u.is_finite())
+        {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BayesOpt`: lower bound must be finite and smaller than the finite upper bound."
+            ));
+        }
+
+        self.xs = vec![];
+        self.ys = vec![];
+        let mut xs = self.latin_hypercube(self.init_samples.unwrap_or(2 * n + 1));
+        if let Some(init_param) = state.take_param() {
+            if init_param.shape() != n {
+                return Err(argmin_error!(
+                    InvalidParameter,
+                    "`BayesOpt`: initial parameter vector must have the same length as the bounds."
+                ));
+            }
+            xs.insert(0, self.to_unit(&init_param));
+        }
+        let (param, cost) = self.evaluate(problem, xs)?;
+
+        Ok((
+            state.param(param).cost(cost),
+            Some(kv!("observations" => self.ys.len() as u64;)),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        self.gp.fit(&self.xs, &self.ys)?;
+        let best = self
+            .ys
+            .iter()
+            .fold(F::infinity(), |acc, &y| acc.min(self.gp.standardize(y)));
+
+        let mut batch = Vec::with_capacity(self.batch_size);
+        let mut max_acquisition = F::neg_infinity();
+        for k in 0..self.batch_size {
+            let pso = ParticleSwarm::new(self.bounds.clone(), self.num_particles)
+                .with_rng_generator(rand::rngs::StdRng::seed_from_u64(self.rng_generator.gen()));
+            let acquisition_problem = AcquisitionProblem {
+                gp: &self.gp,
+                acquisition: self.acquisition,
+                best,
+                lower: &self.lower,
+                upper: &self.upper,
+                _param: PhantomData,
+            };
+            let res = Executor::new(acquisition_problem, pso)
+                .configure(|state| state.max_iters(self.acquisition_iters))
+                .ctrlc(false)
+                .run()?;
+            let particle = res
+                .state()
+                .get_best_param()
+                .ok_or_else(argmin_error_closure!(
+                    PotentialBug,
+                    "`BayesOpt`: No best particle in state of acquisition optimizer."
+                ))?;
+            let x = self.to_unit(&particle.position);
+            if k == 0 {
+                max_acquisition = -res.state().get_best_cost();
+            }
+            if k + 1 < self.batch_size {
+                // Kriging believer: pretend that the surrogate predicts the cost function exactly
+                let (mean, _) = self.gp.predict(&x);
+                self.gp.condition(x.clone(), mean)?;
+            }
+            batch.push(x);
+        }
+
+        let (param, cost) = self.evaluate(problem, batch)?;
+
+        Ok((
+            state.param(param).cost(cost),
+            Some(kv!(
+                "lengthscale" => self.gp.lengthscale();
+                "noise" => self.gp.nugget();
+                "acquisition" => max_acquisition;
+                "observations" => self.ys.len() as u64;
+            )),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{test_utils::TestProblem, ArgminError};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(bayesopt, BayesOpt<Vec<f64>, f64, rand::rngs::StdRng>);
+
+    /// Shifted sphere with minimum 1 at (0.3, -0.2)
+    struct ShiftedSphere {}
+
+    impl CostFunction for ShiftedSphere {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok((p[0] - 0.3).powi(2) + (p[1] + 0.2).powi(2) + 1.0)
+        }
+    }
+
+    /// Forrester function, which has a local minimum next to the global one
+    struct Forrester {}
+
+    impl CostFunction for Forrester {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok((6.0 * p[0] - 2.0).powi(2) * (12.0 * p[0] - 4.0).sin())
+        }
+    }
+
+    fn solver() -> BayesOpt<Vec<f64>, f64, rand_xoshiro::Xoroshiro128Plus> {
+        BayesOpt::new((vec![-1.0, -1.0], vec![1.0, 1.0]))
+            .with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(42))
+    }
+
+    #[test]
+    fn test_new() {
+        let bo: BayesOpt<Vec<f64>, f64, _> = BayesOpt::new((vec![-1.0], vec![1.0]));
+        assert_eq!(bo.kernel, Kernel::Matern52);
+        assert_eq!(
+            bo.acquisition,
+            AcquisitionFunction::ExpectedImprovement { xi: 0.01 }
+        );
+        assert!(bo.init_samples.is_none());
+        assert_eq!(bo.batch_size, 1);
+        assert_eq!(bo.num_particles, 40);
+        assert_eq!(bo.acquisition_iters, 50);
+    }
+
+    #[test]
+    fn test_builders() {
+        let bo = solver()
+            .with_kernel(Kernel::RBF)
+            .with_acquisition(AcquisitionFunction::UpperConfidenceBound { kappa: 1.0 })
+            .unwrap()
+            .with_initial_samples(7)
+            .unwrap()
+            .with_batch_size(3)
+            .unwrap()
+            .with_acquisition_optimizer(10, 20)
+            .unwrap();
+        assert_eq!(bo.kernel, Kernel::RBF);
+        assert_eq!(
+            bo.acquisition,
+            AcquisitionFunction::UpperConfidenceBound { kappa: 1.0 }
+        );
+        assert_eq!(bo.init_samples, Some(7));
+        assert_eq!(bo.batch_size, 3);
+        assert_eq!(bo.num_particles, 10);
+        assert_eq!(bo.acquisition_iters, 20);
+    }
+
+    #[test]
+    fn test_builder_errors() {
+        assert_error!(
+            solver().with_acquisition(AcquisitionFunction::ExpectedImprovement { xi: -0.1 }),
+            ArgminError,
+            "Invalid parameter: \"`BayesOpt`: xi of the acquisition function must be >= 0.\""
+        );
+        assert_error!(
+            solver().with_initial_samples(1),
+            ArgminError,
+            "Invalid parameter: \"`BayesOpt`: number of initial samples must be >= 2.\""
+        );
+        assert_error!(
+            solver().with_batch_size(0),
+            ArgminError,
+            "Invalid parameter: \"`BayesOpt`: batch size must be >= 1.\""
+        );
+        assert_error!(
+            solver().with_acquisition_optimizer(0, 10),
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`BayesOpt`: number of particles and iterations of the ",
+                "acquisition optimizer must be >= 1.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_invalid_bounds() {
+        for bounds in [
+            (vec![-1.0, 1.0], vec![1.0, 1.0]),
+            (vec![-1.0, f64::NEG_INFINITY], vec![1.0, 1.0]),
+        ] {
+            let mut bo: BayesOpt<_, f64, _> = BayesOpt::new(bounds);
+            let res = bo.init(&mut Problem::new(TestProblem::new()), IterState::new());
+            assert_error!(
+                res,
+                ArgminError,
+                concat!(
+                    "Invalid parameter: \"`BayesOpt`: lower bound must be finite and smaller ",
+                    "than the finite upper bound.\""
+                )
+            );
+        }
+        let mut bo: BayesOpt<_, f64, _> = BayesOpt::new((vec![-1.0, -1.0], vec![1.0]));
+        let res = bo.init(&mut Problem::new(TestProblem::new()), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`BayesOpt`: lower and upper bound must have the same, ",
+                "non-zero length.\""
+            )
+        );
+        let mut bo: BayesOpt<_, f64, _> = BayesOpt::new((vec![-1.0, -1.0], vec![1.0, 1.0]));
+        let res = bo.init(
+            &mut Problem::new(TestProblem::new()),
+            IterState::new().param(vec![0.0]),
+        );
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`BayesOpt`: initial parameter vector must have the same ",
+                "length as the bounds.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_init() {
+        let mut bo = solver().with_initial_samples(6).unwrap();
+        let mut problem = Problem::new(ShiftedSphere {});
+        let (state, _) = bo
+            .init(&mut problem, IterState::new().param(vec![0.3, -0.2]))
+            .unwrap();
+        // The initial parameter vector is evaluated in addition to the initial design
+        assert_eq!(bo.xs.len(), 7);
+        assert_eq!(problem.counts["cost_count"], 7);
+        assert_relative_eq!(state.get_cost(), 1.0, epsilon = 1e-12);
+        assert_relative_eq!(state.get_param().unwrap()[0], 0.3, epsilon = 1e-12);
+        assert_relative_eq!(state.get_param().unwrap()[1], -0.2, epsilon = 1e-12);
+        // Latin hypercube: every stratum of every coordinate holds one of the sampled points
+        for j in 0..2 {
+            let mut strata: Vec<usize> = bo.xs[1..]
+                .iter()
+                .map(|x| (x[j] * 6.0).floor() as usize)
+                .collect();
+            strata.sort_unstable();
+            assert_eq!(strata, (0..6).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_sphere() {
+        let res = Executor::new(ShiftedSphere {}, solver())
+            .configure(|state| state.max_iters(20))
+            .run()
+            .unwrap();
+        let best = res.state().get_best_param().unwrap();
+        assert_relative_eq!(best[0], 0.3, epsilon = 5e-2);
+        assert_relative_eq!(best[1], -0.2, epsilon = 5e-2);
+        assert!(res.state().get_best_cost() < 1.0 + 5e-3);
+        assert_eq!(res.problem.counts["cost_count"], 5 + 20);
+    }
+
+    #[test]
+    fn test_forrester() {
+        for acquisition in [
+            AcquisitionFunction::ExpectedImprovement { xi: 0.01 },
+            AcquisitionFunction::ProbabilityOfImprovement { xi: 0.01 },
+            AcquisitionFunction::UpperConfidenceBound { kappa: 2.0 },
+        ] {
+            let bo = BayesOpt::new((vec![0.0], vec![1.0]))
+                .with_acquisition(acquisition)
+                .unwrap()
+                .with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(1729));
+            let res = Executor::new(Forrester {}, bo)
+                .configure(|state| state.max_iters(25))
+                .run()
+                .unwrap();
+            // Global minimum -6.0207 at 0.7572
+            assert_relative_eq!(
+                res.state().get_best_param().unwrap()[0],
+                0.7572,
+                epsilon = 1e-2
+            );
+        }
+    }
+
+    #[test]
+    fn test_batch() {
+        let bo = solver()
+            .with_kernel(Kernel::RBF)
+            .with_batch_size(3)
+            .unwrap();
+        let res = Executor::new(ShiftedSphere {}, bo)
+            .configure(|state| state.max_iters(8))
+            .run()
+            .unwrap();
+        assert_eq!(res.problem.counts["cost_count"], 5 + 3 * 8);
+        assert!(res.state().get_best_cost() < 1.0 + 1e-2);
+    }
+}
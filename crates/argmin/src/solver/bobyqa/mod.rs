@@ -0,0 +1,993 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # BOBYQA
+//!
+//! Derivative-free trust region method for bound constrained optimization, which builds quadratic
+//! models of the cost function by interpolation.
+//!
+//! See [`BOBYQA`] for details.
+//!
+//! ## References
+//!
+//! \[0\] M. J. D. Powell (2009). The BOBYQA algorithm for bound constrained optimization without
+//! derivatives. Technical Report DAMTP 2009/NA06, University of Cambridge.
+//!
+//! \[1\] M. J. D. Powell (2004). Least Frobenius norm updating of quadratic models that satisfy
+//! interpolation conditions. Mathematical Programming 100, 183–215.
+//! DOI: <https://doi.org/10.1007/s10107-003-0490-7>
+
+use crate::core::{
+    ArgminFloat, CostFunction, Error, IterState, Problem, Solver, State, SyncAlias,
+    TerminationReason, KV,
+};
+use crate::solver::dense::{add, dot, invert, mat_vec, scale, sub};
+use crate::solver::trustregion::reduction_ratio;
+use argmin_math::{ArgminGet, ArgminSet, ArgminSize};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # BOBYQA
+///
+/// Derivative-free trust region method which approximates the cost function by a quadratic model
+/// that interpolates the cost function at `m` points (by default `m = 2n + 1`, where `n` is the
+/// number of parameters). Since `m` is usually smaller than the `(n + 1)(n + 2) / 2` points which
+/// determine a quadratic function, the freedom in the model is taken up by minimizing the
+/// Frobenius norm of the change of the Hessian of the model \[1\]. Each iteration requires at
+/// most one evaluation of the cost function, which makes the method well suited for expensive
+/// cost functions without derivatives.
+///
+/// The method follows the structure of Powell's BOBYQA \[0\] and works with two radii: the trust
+/// region radius `radius` and a lower bound `rho` on the trust region radius, which decreases from
+/// the initial radius to the final radius. In every iteration, the model is minimized within the
+/// trust region and within the bounds by a truncated conjugate gradient method. The trust region
+/// radius is then updated based on the
+/// [`reduction_ratio`](`crate::solver::trustregion::reduction_ratio`) of the step, and the new
+/// point replaces the interpolation point for which the value of the corresponding Lagrange
+/// function, weighted by its distance to the best point, is largest. If a step is poor and some
+/// interpolation points are far from the best point, the next iteration improves the geometry of
+/// the interpolation set instead by moving the farthest point to a position where its Lagrange
+/// function is large. `rho` is decreased if neither trust region steps nor geometry improvements
+/// make progress. The method terminates once `rho` reaches the final radius.
+///
+/// In contrast to Powell's implementation, the interpolation system is solved from scratch in
+/// every iteration instead of updating its inverse, which costs `O((m + n)^3)` operations per
+/// iteration. This is negligible for the small problems with expensive cost functions that the
+/// method is designed for.
+///
+/// Optional bounds can be set via [`with_bounds`](`BOBYQA::with_bounds`); the cost function is
+/// never evaluated outside of the bounds. All points of the initial interpolation set lie within
+/// the bounds, which requires that the difference between the upper and lower bounds is at least
+/// twice the initial radius.
+///
+/// The trust region radius and `rho` are reported as KV metrics `radius` and `rho`.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`].
+///
+/// ## References
+///
+/// \[0\] M. J. D. Powell (2009). The BOBYQA algorithm for bound constrained optimization without
+/// derivatives. Technical Report DAMTP 2009/NA06, University of Cambridge.
+///
+/// \[1\] M. J. D. Powell (2004). Least Frobenius norm updating of quadratic models that satisfy
+/// interpolation conditions. Mathematical Programming 100, 183–215.
+/// DOI: <https://doi.org/10.1007/s10107-003-0490-7>
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct BOBYQA<P, F> {
+    /// Optional bounds `(lower, upper)`
+    bounds: Option<(P, P)>,
+    /// Initial radius
+    rho_begin: F,
+    /// Final radius
+    rho_end: F,
+    /// Number of interpolation points (defaults to `2n + 1`)
+    num_points: Option<usize>,
+    /// Template used to convert internal vectors into parameter vectors
+    template: Option<P>,
+    /// Lower bounds
+    lower: Vec<F>,
+    /// Upper bounds
+    upper: Vec<F>,
+    /// Interpolation points
+    points: Vec<Vec<F>>,
+    /// Cost function values at the interpolation points
+    costs: Vec<F>,
+    /// Index of the best interpolation point
+    kopt: usize,
+    /// Hessian of the last model
+    hessian: Vec<Vec<F>>,
+    /// Trust region radius
+    radius: F,
+    /// Lower bound on the trust region radius
+    rho: F,
+    /// Whether the next iteration improves the geometry of the interpolation set
+    improve_geometry: bool,
+}
+
+impl<P, F> BOBYQA<P, F>
+where
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `BOBYQA`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::bobyqa::BOBYQA;
+    /// let bobyqa: BOBYQA<Vec<f64>, f64> = BOBYQA::new();
+    /// ```
+    pub fn new() -> Self {
+        BOBYQA {
+            bounds: None,
+            rho_begin: float!(0.5),
+            rho_end: float!(1e-6),
+            num_points: None,
+            template: None,
+            lower: vec![],
+            upper: vec![],
+            points: vec![],
+            costs: vec![],
+            kopt: 0,
+            hessian: vec![],
+            radius: float!(0.5),
+            rho: float!(0.5),
+            improve_geometry: false,
+        }
+    }
+
+    /// Set lower and upper bounds
+    ///
+    /// `lower` and `upper` must have the same number of elements as the parameter vector. Infinite
+    /// bounds are allowed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::bobyqa::BOBYQA;
+    /// let bobyqa: BOBYQA<Vec<f64>, f64> =
+    ///     BOBYQA::new().with_bounds(vec![-1.0, 0.0], vec![1.0, f64::INFINITY]);
+    /// ```
+    #[must_use]
+    pub fn with_bounds(mut self, lower: P, upper: P) -> Self {
+        self.bounds = Some((lower, upper));
+        self
+    }
+
+    /// Set initial radius
+    ///
+    /// Should be about a tenth of the largest expected change of a parameter. Must be larger than
+    /// `0`. Defaults to `0.5`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::bobyqa::BOBYQA;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let bobyqa: BOBYQA<Vec<f64>, f64> = BOBYQA::new().with_initial_radius(0.1)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_initial_radius(mut self, rho_begin: F) -> Result<Self, Error> {
+        if rho_begin <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BOBYQA`: initial radius must be > 0."
+            ));
+        }
+        self.rho_begin = rho_begin;
+        Ok(self)
+    }
+
+    /// Set final radius
+    ///
+    /// Determines the accuracy of the solution. Must be larger than `0` and must not exceed the
+    /// initial radius. Defaults to `1e-6`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::bobyqa::BOBYQA;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let bobyqa: BOBYQA<Vec<f64>, f64> = BOBYQA::new().with_final_radius(1e-8)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_final_radius(mut self, rho_end: F) -> Result<Self, Error> {
+        if rho_end <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BOBYQA`: final radius must be > 0."
+            ));
+        }
+        self.rho_end = rho_end;
+        Ok(self)
+    }
+
+    /// Set number of interpolation points
+    ///
+    /// Must be in `[n + 2, (n + 1)(n + 2) / 2]`, where `n` is the number of parameters. Defaults
+    /// to `2n + 1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::bobyqa::BOBYQA;
+    /// let bobyqa: BOBYQA<Vec<f64>, f64> = BOBYQA::new().with_num_interpolation_points(6);
+    /// ```
+    #[must_use]
+    pub fn with_num_interpolation_points(mut self, num_points: usize) -> Self {
+        self.num_points = Some(num_points);
+        self
+    }
+
+    /// Converts an internal vector into a parameter vector
+    fn to_param(&self, x: &[F]) -> P {
+        let mut param = self.template.as_ref().unwrap().clone();
+        for (i, &xi) in x.iter().enumerate() {
+            param.set(i, xi);
+        }
+        param
+    }
+
+    /// Builds the quadratic model around the best interpolation point
+    ///
+    /// Returns the gradient and Hessian of the model as well as the inverse of the interpolation
+    /// matrix.
+    fn build_model(&self) -> Result<Model<F>, Error> {
+        let n = self.lower.len();
+        let m = self.points.len();
+        let xopt = &self.points[self.kopt];
+        let d: Vec<Vec<F>> = self.points.iter().map(|y| sub(y, xopt)).collect();
+
+        // The interpolation system is set up in coordinates scaled by the distance of the
+        // farthest point, otherwise it becomes badly scaled as the radius decreases. Scaling all
+        // coordinates uniformly does not change the least Frobenius norm solution.
+        let scale = d
+            .iter()
+            .map(|di| dot(di, di).sqrt())
+            .fold(float!(0.0), |acc: F, x| acc.max(x));
+        let d_hat: Vec<Vec<F>> = d.iter().map(|di| self::scale(di, scale.recip())).collect();
+
+        let mut w = vec![vec![float!(0.0); m + n + 1]; m + n + 1];
+        for i in 0..m {
+            for j in 0..m {
+                w[i][j] = float!(0.5) * dot(&d_hat[i], &d_hat[j]).powi(2);
+            }
+            w[i][m] = float!(1.0);
+            w[m][i] = float!(1.0);
+            for k in 0..n {
+                w[i][m + 1 + k] = d_hat[i][k];
+                w[m + 1 + k][i] = d_hat[i][k];
+            }
+        }
+        let w_inv = invert(w).ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`BOBYQA`: interpolation system is singular."
+        ))?;
+
+        // Least Frobenius norm change of the Hessian: interpolate the residuals of the Hessian
+        // part of the previous model.
+        let mut rhs = vec![float!(0.0); m + n + 1];
+        for i in 0..m {
+            rhs[i] = self.costs[i] - float!(0.5) * quad(&self.hessian, &d[i]);
+        }
+        let z = mat_vec(&w_inv, &rhs);
+
+        let gradient = self::scale(&z[(m + 1)..], scale.recip());
+        let mut hessian = self.hessian.clone();
+        for (lambda, di) in z[..m].iter().zip(d_hat.iter()) {
+            let lambda = *lambda / (scale * scale);
+            for (k, row) in hessian.iter_mut().enumerate() {
+                for (l, h) in row.iter_mut().enumerate() {
+                    *h = *h + lambda * di[k] * di[l];
+                }
+            }
+        }
+        Ok(Model {
+            gradient,
+            hessian,
+            w_inv,
+            d,
+            scale,
+        })
+    }
+
+    /// Lower and upper bounds on steps from the best interpolation point
+    fn step_bounds(&self) -> (Vec<F>, Vec<F>) {
+        let xopt = &self.points[self.kopt];
+        (sub(&self.lower, xopt), sub(&self.upper, xopt))
+    }
+
+    /// Index and distance of the interpolation point farthest from the best point
+    fn farthest_point(&self, model: &Model<F>) -> (usize, F) {
+        model.d.iter().map(|d| dot(d, d).sqrt()).enumerate().fold(
+            (0, float!(0.0)),
+            |(k, max), (i, dist)| {
+                if dist > max {
+                    (i, dist)
+                } else {
+                    (k, max)
+                }
+            },
+        )
+    }
+
+    /// Decreases `rho`. Returns `false` if `rho` already reached the final radius.
+    fn reduce_rho(&mut self) -> bool {
+        if self.rho <= self.rho_end {
+            return false;
+        }
+        let ratio = self.rho / self.rho_end;
+        let rho_new = if ratio <= float!(16.0) {
+            self.rho_end
+        } else if ratio <= float!(250.0) {
+            (self.rho * self.rho_end).sqrt()
+        } else {
+            float!(0.1) * self.rho
+        };
+        self.radius = (float!(0.5) * self.rho).max(rho_new);
+        self.rho = rho_new;
+        true
+    }
+
+    /// Replaces interpolation point `t` with the point `xopt + s` with cost `cost`
+    fn replace_point(&mut self, t: usize, s: &[F], cost: F) {
+        let new_point = add(&self.points[self.kopt], s);
+        // never leave the bounds due to rounding errors
+        self.points[t] = new_point
+            .iter()
+            .zip(self.lower.iter().zip(self.upper.iter()))
+            .map(|(&x, (&l, &u))| x.max(l).min(u))
+            .collect();
+        self.costs[t] = cost;
+        if cost < self.costs[self.kopt] {
+            self.kopt = t;
+        }
+    }
+
+    /// Step which improves the geometry of the interpolation set by moving interpolation point `t`
+    fn geometry_step(&self, model: &Model<F>, t: usize, step_length: F) -> Vec<F> {
+        let n = self.lower.len();
+        let (sl, su) = self.step_bounds();
+        let m = self.points.len();
+        let grad_t: Vec<F> = (0..n).map(|k| model.w_inv[m + 1 + k][t]).collect();
+
+        // candidates: along the gradient of the Lagrange function, towards the old point and
+        // along the coordinate directions and their pairwise combinations
+        let mut directions = vec![grad_t, model.d[t].clone()];
+        for i in 0..n {
+            let mut e = vec![float!(0.0); n];
+            e[i] = float!(1.0);
+            directions.push(e.clone());
+            for j in (i + 1)..n {
+                for sign in [float!(1.0), float!(-1.0)] {
+                    let mut e = e.clone();
+                    e[j] = sign;
+                    directions.push(e);
+                }
+            }
+        }
+        let mut best = vec![float!(0.0); n];
+        let mut best_value = float!(-1.0);
+        for direction in directions {
+            for sign in [float!(1.0), float!(-1.0)] {
+                // components which point out of active bounds are dropped
+                let direction: Vec<F> = direction
+                    .iter()
+                    .zip(sl.iter().zip(su.iter()))
+                    .map(|(&di, (&l, &u))| {
+                        let di = sign * di;
+                        if (di > float!(0.0) && u <= float!(0.0))
+                            || (di < float!(0.0) && l >= float!(0.0))
+                        {
+                            float!(0.0)
+                        } else {
+                            di
+                        }
+                    })
+                    .collect();
+                let norm = dot(&direction, &direction).sqrt();
+                if norm <= float!(0.0) {
+                    continue;
+                }
+                let s: Vec<F> = direction
+                    .iter()
+                    .zip(sl.iter().zip(su.iter()))
+                    .map(|(&di, (&l, &u))| (step_length * di / norm).max(l).min(u))
+                    .collect();
+                let value = model.lagrange(t, &s).abs();
+                if value > best_value {
+                    best_value = value;
+                    best = s;
+                }
+            }
+        }
+        best
+    }
+}
+
+impl<P, F> Default for BOBYQA<P, F>
+where
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        BOBYQA::new()
+    }
+}
+
+impl<O, P, F> Solver<O, IterState<P, (), (), (), (), F>> for BOBYQA<P, F>
+where
+    O: CostFunction<Param = P, Output = F> + SyncAlias,
+    P: Clone + SyncAlias + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "BOBYQA";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`BOBYQA` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let n = param.shape();
+        if n < 1 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BOBYQA`: parameter vector must have at least one element."
+            ));
+        }
+        if self.rho_end > self.rho_begin {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BOBYQA`: final radius must not exceed initial radius."
+            ));
+        }
+        let m = self.num_points.unwrap_or(2 * n + 1);
+        if m < n + 2 || m > (n + 1) * (n + 2) / 2 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BOBYQA`: number of interpolation points must be in [n + 2, (n + 1)(n + 2) / 2]."
+            ));
+        }
+        let (lower, upper) = match &self.bounds {
+            Some((lower, upper)) => {
+                if lower.shape() != n || upper.shape() != n {
+                    return Err(argmin_error!(
+                        InvalidParameter,
+                        "`BOBYQA`: bounds must have the same length as the parameter vector."
+                    ));
+                }
+                (
+                    (0..n).map(|i| lower.get(i)).collect(),
+                    (0..n).map(|i| upper.get(i)).collect(),
+                )
+            }
+            None => (vec![F::neg_infinity(); n], vec![F::infinity(); n]),
+        };
+        let rho = self.rho_begin;
+        if lower
+            .iter()
+            .zip(upper.iter())
+            .any(|(&l, &u): (&F, &F)| u - l < float!(2.0) * rho)
+        {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BOBYQA`: difference between upper and lower bound must be at least twice the initial radius."
+            ));
+        }
+
+        // Project the initial guess onto the bounds and choose offsets along the coordinate
+        // directions such that all initial interpolation points are feasible.
+        let mut x0: Vec<F> = (0..n).map(|i| param.get(i)).collect();
+        let mut offsets = Vec::with_capacity(n);
+        for i in 0..n {
+            x0[i] = x0[i].max(lower[i]).min(upper[i]);
+            if x0[i] - lower[i] < rho {
+                x0[i] = lower[i];
+                offsets.push((rho, float!(2.0) * rho));
+            } else if upper[i] - x0[i] < rho {
+                x0[i] = upper[i];
+                offsets.push((-rho, float!(-2.0) * rho));
+            } else {
+                offsets.push((rho, -rho));
+            }
+        }
+        let mut points = vec![x0.clone()];
+        for (i, &(o, _)) in offsets.iter().enumerate() {
+            let mut y = x0.clone();
+            y[i] = y[i] + o;
+            points.push(y);
+        }
+        for (i, &(_, o)) in offsets.iter().enumerate().take(m.min(2 * n + 1) - n - 1) {
+            let mut y = x0.clone();
+            y[i] = y[i] + o;
+            points.push(y);
+        }
+        'outer: for i in 0..n {
+            for j in (i + 1)..n {
+                if points.len() >= m {
+                    break 'outer;
+                }
+                let mut y = x0.clone();
+                y[i] = y[i] + offsets[i].0;
+                y[j] = y[j] + offsets[j].0;
+                points.push(y);
+            }
+        }
+
+        self.template = Some(param);
+        self.lower = lower;
+        self.upper = upper;
+        let params: Vec<P> = points.iter().map(|y| self.to_param(y)).collect();
+        self.costs = problem.bulk_cost(&params)?;
+        self.points = points;
+        self.kopt = (0..m).fold(0, |k, i| if self.costs[i] < self.costs[k] { i } else { k });
+        self.hessian = vec![vec![float!(0.0); n]; n];
+        self.radius = rho;
+        self.rho = rho;
+        self.improve_geometry = false;
+
+        Ok((
+            state
+                .param(self.to_param(&self.points[self.kopt]))
+                .cost(self.costs[self.kopt]),
+            Some(kv!("radius" => self.radius; "rho" => self.rho;)),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let model = self.build_model()?;
+        self.hessian = model.hessian.clone();
+
+        if self.improve_geometry {
+            self.improve_geometry = false;
+            let (t, dist) = self.farthest_point(&model);
+            let step_length = (float!(0.1) * dist).min(self.radius).max(self.rho);
+            let s = self.geometry_step(&model, t, step_length);
+            let cost = problem.cost(&self.to_param(&add(&self.points[self.kopt], &s)))?;
+            self.replace_point(t, &s, cost);
+        } else {
+            let (sl, su) = self.step_bounds();
+            let s = trust_region_step(&model.gradient, &model.hessian, self.radius, &sl, &su);
+            let step_norm = dot(&s, &s).sqrt();
+            if step_norm < float!(0.5) * self.rho {
+                // The step is too short to be worth a function evaluation.
+                self.radius = (float!(0.5) * self.radius).max(self.rho);
+                let (_, dist) = self.farthest_point(&model);
+                if dist > float!(2.0) * self.radius {
+                    self.improve_geometry = true;
+                } else if !self.reduce_rho() {
+                    state = state.terminate_with(TerminationReason::SolverConverged);
+                }
+            } else {
+                let fopt = self.costs[self.kopt];
+                let cost = problem.cost(&self.to_param(&add(&self.points[self.kopt], &s)))?;
+                let model_change =
+                    dot(&model.gradient, &s) + float!(0.5) * quad(&model.hessian, &s);
+                let ratio = reduction_ratio(fopt, cost, float!(0.0), model_change);
+
+                self.radius = if ratio <= float!(0.1) {
+                    (float!(0.5) * self.radius).min(step_norm)
+                } else if ratio <= float!(0.7) {
+                    (float!(0.5) * self.radius).max(step_norm)
+                } else {
+                    (float!(0.5) * self.radius).max(float!(2.0) * step_norm)
+                };
+                if self.radius <= float!(1.5) * self.rho {
+                    self.radius = self.rho;
+                }
+
+                // Choose the interpolation point to be replaced. The best point may only be
+                // replaced if the new point is better.
+                let weight_scale = (float!(0.1) * self.radius).max(self.rho).powi(2);
+                let mut t = self.kopt;
+                let mut best_score = float!(-1.0);
+                for (j, dj) in model.d.iter().enumerate() {
+                    if j == self.kopt && cost >= fopt {
+                        continue;
+                    }
+                    let weight = (dot(dj, dj) / weight_scale).max(float!(1.0)).powi(2);
+                    let score = weight * model.lagrange(j, &s).abs();
+                    if score > best_score {
+                        best_score = score;
+                        t = j;
+                    }
+                }
+                self.replace_point(t, &s, cost);
+
+                if ratio <= float!(0.1) {
+                    let (_, dist) = self.farthest_point(&self.build_model()?);
+                    if dist > float!(2.0) * self.radius {
+                        self.improve_geometry = true;
+                    } else if step_norm.max(self.radius) <= self.rho && !self.reduce_rho() {
+                        state = state.terminate_with(TerminationReason::SolverConverged);
+                    }
+                }
+            }
+        }
+
+        Ok((
+            state
+                .param(self.to_param(&self.points[self.kopt]))
+                .cost(self.costs[self.kopt]),
+            Some(kv!("radius" => self.radius; "rho" => self.rho;)),
+        ))
+    }
+}
+
+/// Quadratic model around the best interpolation point
+struct Model<F> {
+    /// Gradient of the model
+    gradient: Vec<F>,
+    /// Hessian of the model
+    hessian: Vec<Vec<F>>,
+    /// Inverse of the interpolation matrix
+    w_inv: Vec<Vec<F>>,
+    /// Interpolation points relative to the best point
+    d: Vec<Vec<F>>,
+    /// Scaling of the coordinates of the interpolation system
+    scale: F,
+}
+
+impl<F: ArgminFloat> Model<F> {
+    /// Value of the Lagrange function of interpolation point `t` at step `s`
+    fn lagrange(&self, t: usize, s: &[F]) -> F {
+        let m = self.d.len();
+        let s = scale(s, self.scale.recip());
+        let linear = s
+            .iter()
+            .enumerate()
+            .fold(self.w_inv[m][t], |acc, (k, &sk)| {
+                acc + self.w_inv[m + 1 + k][t] * sk
+            });
+        self.d.iter().enumerate().fold(linear, |acc, (j, dj)| {
+            acc + float!(0.5) * self.w_inv[j][t] * (dot(dj, &s) / self.scale).powi(2)
+        })
+    }
+}
+
+/// Approximately minimizes `g^T s + 1/2 s^T H s` subject to `||s|| <= radius` and
+/// `sl <= s <= su` with a truncated conjugate gradient method. Variables which reach a bound are
+/// fixed and the conjugate gradient method is restarted.
+fn trust_region_step<F: ArgminFloat>(
+    g: &[F],
+    h: &[Vec<F>],
+    radius: F,
+    sl: &[F],
+    su: &[F],
+) -> Vec<F> {
+    let n = g.len();
+    let mut s = vec![float!(0.0); n];
+    let mut fixed: Vec<bool> = (0..n)
+        .map(|i| {
+            (sl[i] >= float!(0.0) && g[i] >= float!(0.0))
+                || (su[i] <= float!(0.0) && g[i] <= float!(0.0))
+        })
+        .collect();
+    let residual = |s: &[F], fixed: &[bool]| -> Vec<F> {
+        let hs = mat_vec(h, s);
+        (0..n)
+            .map(|i| {
+                if fixed[i] {
+                    float!(0.0)
+                } else {
+                    -(g[i] + hs[i])
+                }
+            })
+            .collect::<Vec<F>>()
+    };
+    let tol = float!(1e-10) * dot(g, g).sqrt();
+
+    for _ in 0..=n {
+        let mut r = residual(&s, &fixed);
+        let mut rr = dot(&r, &r);
+        if rr.sqrt() <= tol {
+            return s;
+        }
+        let mut d = r.clone();
+        let mut restart = false;
+        for _ in 0..n {
+            let hd: Vec<F> = mat_vec(h, &d)
+                .iter()
+                .zip(fixed.iter())
+                .map(|(&x, &f)| if f { float!(0.0) } else { x })
+                .collect();
+            let dhd = dot(&d, &hd);
+            let (ss, sd, dd) = (dot(&s, &s), dot(&s, &d), dot(&d, &d));
+            let alpha_tr = (-sd
+                + (sd * sd + dd * (radius * radius - ss))
+                    .max(float!(0.0))
+                    .sqrt())
+                / dd;
+            let mut alpha_bound = F::infinity();
+            let mut bound_idx = 0;
+            for i in 0..n {
+                if fixed[i] || d[i] == float!(0.0) {
+                    continue;
+                }
+                let alpha = if d[i] > float!(0.0) {
+                    (su[i] - s[i]) / d[i]
+                } else {
+                    (sl[i] - s[i]) / d[i]
+                };
+                if alpha < alpha_bound {
+                    alpha_bound = alpha;
+                    bound_idx = i;
+                }
+            }
+            let alpha_model = if dhd > float!(0.0) {
+                rr / dhd
+            } else {
+                F::infinity()
+            };
+
+            if alpha_model <= alpha_tr && alpha_model <= alpha_bound {
+                s = add(&s, &scale(&d, alpha_model));
+                let r_new = sub(&r, &scale(&hd, alpha_model));
+                let rr_new = dot(&r_new, &r_new);
+                if rr_new.sqrt() <= tol {
+                    return s;
+                }
+                d = add(&r_new, &scale(&d, rr_new / rr));
+                r = r_new;
+                rr = rr_new;
+            } else if alpha_tr <= alpha_bound {
+                return add(&s, &scale(&d, alpha_tr));
+            } else {
+                s = add(&s, &scale(&d, alpha_bound.max(float!(0.0))));
+                s[bound_idx] = if d[bound_idx] > float!(0.0) {
+                    su[bound_idx]
+                } else {
+                    sl[bound_idx]
+                };
+                fixed[bound_idx] = true;
+                restart = true;
+                break;
+            }
+        }
+        if !restart {
+            return s;
+        }
+    }
+    s
+}
+
+/// Computes `x^T A x`
+fn quad<F: ArgminFloat>(a: &[Vec<F>], x: &[F]) -> F {
+    dot(x, &mat_vec(a, x))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{test_utils::TestProblem, ArgminError, Executor};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(bobyqa, BOBYQA<Vec<f64>, f64>);
+
+    struct Rosenbrock {}
+
+    impl CostFunction for Rosenbrock {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(argmin_testfunctions::rosenbrock(p, 1.0, 100.0))
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let bobyqa: BOBYQA<Vec<f64>, f64> = BOBYQA::new();
+        let BOBYQA {
+            bounds,
+            rho_begin,
+            rho_end,
+            num_points,
+            ..
+        } = bobyqa;
+        assert!(bounds.is_none());
+        assert_eq!(rho_begin.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert_eq!(rho_end.to_ne_bytes(), 1e-6f64.to_ne_bytes());
+        assert!(num_points.is_none());
+    }
+
+    #[test]
+    fn test_builder_errors() {
+        assert_error!(
+            BOBYQA::<Vec<f64>, f64>::new().with_initial_radius(0.0),
+            ArgminError,
+            "Invalid parameter: \"`BOBYQA`: initial radius must be > 0.\""
+        );
+        assert_error!(
+            BOBYQA::<Vec<f64>, f64>::new().with_final_radius(0.0),
+            ArgminError,
+            "Invalid parameter: \"`BOBYQA`: final radius must be > 0.\""
+        );
+    }
+
+    #[test]
+    fn test_init_errors() {
+        let mut bobyqa: BOBYQA<Vec<f64>, f64> = BOBYQA::new();
+        let res = bobyqa.init(&mut Problem::new(TestProblem::new()), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`BOBYQA` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+
+        let cases: Vec<(BOBYQA<Vec<f64>, f64>, &str)> = vec![
+            (
+                BOBYQA::new().with_final_radius(1.0).unwrap(),
+                "Invalid parameter: \"`BOBYQA`: final radius must not exceed initial radius.\"",
+            ),
+            (
+                BOBYQA::new().with_num_interpolation_points(3),
+                "Invalid parameter: \"`BOBYQA`: number of interpolation points must be in [n + 2, (n + 1)(n + 2) / 2].\"",
+            ),
+            (
+                BOBYQA::new().with_num_interpolation_points(7),
+                "Invalid parameter: \"`BOBYQA`: number of interpolation points must be in [n + 2, (n + 1)(n + 2) / 2].\"",
+            ),
+            (
+                BOBYQA::new().with_bounds(vec![0.0], vec![1.0]),
+                "Invalid parameter: \"`BOBYQA`: bounds must have the same length as the parameter vector.\"",
+            ),
+            (
+                BOBYQA::new().with_bounds(vec![0.0, 0.0], vec![1.0, 0.5]),
+                "Invalid parameter: \"`BOBYQA`: difference between upper and lower bound must be at least twice the initial radius.\"",
+            ),
+        ];
+        for (mut bobyqa, msg) in cases {
+            let res = bobyqa.init(
+                &mut Problem::new(TestProblem::new()),
+                IterState::new().param(vec![0.5, 0.5]),
+            );
+            assert_error!(res, ArgminError, msg);
+        }
+    }
+
+    #[test]
+    fn test_init_points() {
+        let mut bobyqa: BOBYQA<Vec<f64>, f64> = BOBYQA::new()
+            .with_bounds(vec![0.0, -10.0, -10.0], vec![10.0, 0.1, 10.0])
+            .with_num_interpolation_points(9);
+        let mut problem = Problem::new(Rosenbrock {});
+        let (state, _) = bobyqa
+            .init(&mut problem, IterState::new().param(vec![0.2, 0.0, -20.0]))
+            .unwrap();
+        assert_eq!(problem.counts["cost_count"], 9);
+        assert_eq!(bobyqa.points.len(), 9);
+        let expected = vec![
+            vec![0.0, 0.1, -10.0],
+            vec![0.5, 0.1, -10.0],
+            vec![0.0, -0.4, -10.0],
+            vec![0.0, 0.1, -9.5],
+            vec![1.0, 0.1, -10.0],
+            vec![0.0, -0.9, -10.0],
+            vec![0.0, 0.1, -9.0],
+            vec![0.5, -0.4, -10.0],
+            vec![0.5, 0.1, -9.5],
+        ];
+        for (point, expected) in bobyqa.points.iter().zip(expected.iter()) {
+            for (x, y) in point.iter().zip(expected.iter()) {
+                assert_relative_eq!(*x, *y, epsilon = 1e-12);
+            }
+        }
+        let best = bobyqa.costs.iter().cloned().fold(f64::INFINITY, f64::min);
+        assert_eq!(state.get_cost().to_ne_bytes(), best.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_model_interpolates() {
+        let mut bobyqa: BOBYQA<Vec<f64>, f64> = BOBYQA::new();
+        let mut problem = Problem::new(Rosenbrock {});
+        bobyqa
+            .init(&mut problem, IterState::new().param(vec![-1.2, 1.0]))
+            .unwrap();
+        let model = bobyqa.build_model().unwrap();
+        let fopt = bobyqa.costs[bobyqa.kopt];
+        for (d, cost) in model.d.iter().zip(bobyqa.costs.iter()) {
+            let value = fopt + dot(&model.gradient, d) + 0.5 * quad(&model.hessian, d);
+            assert_relative_eq!(value, *cost, epsilon = 1e-8);
+        }
+        for t in 0..model.d.len() {
+            for (j, d) in model.d.iter().enumerate() {
+                let expected = if j == t { 1.0 } else { 0.0 };
+                assert_relative_eq!(model.lagrange(t, d), expected, epsilon = 1e-8);
+            }
+        }
+    }
+
+    #[test]
+    fn test_trust_region_step() {
+        let g = vec![-1.0, -2.0];
+        let h = vec![vec![2.0, 0.0], vec![0.0, 4.0]];
+        let inf = f64::INFINITY;
+
+        // unconstrained Newton step
+        let s = trust_region_step(&g, &h, 10.0, &[-inf, -inf], &[inf, inf]);
+        assert_relative_eq!(s[0], 0.5, epsilon = 1e-12);
+        assert_relative_eq!(s[1], 0.5, epsilon = 1e-12);
+
+        // step limited by the trust region
+        let s = trust_region_step(&g, &h, 0.1, &[-inf, -inf], &[inf, inf]);
+        assert_relative_eq!(dot(&s, &s).sqrt(), 0.1, epsilon = 1e-12);
+
+        // step limited by a bound
+        let s = trust_region_step(&g, &h, 10.0, &[-inf, -inf], &[0.2, inf]);
+        assert_relative_eq!(s[0], 0.2, epsilon = 1e-12);
+        assert_relative_eq!(s[1], 0.5, epsilon = 1e-12);
+
+        // variable at its bound with the gradient pointing outwards stays fixed
+        let s = trust_region_step(&g, &h, 10.0, &[-inf, -inf], &[0.0, inf]);
+        assert_relative_eq!(s[0], 0.0, epsilon = 1e-12);
+        assert_relative_eq!(s[1], 0.5, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_rosenbrock() {
+        for num_points in [4, 5, 6] {
+            let solver: BOBYQA<Vec<f64>, f64> = BOBYQA::new()
+                .with_final_radius(1e-8)
+                .unwrap()
+                .with_num_interpolation_points(num_points);
+            let res = Executor::new(Rosenbrock {}, solver)
+                .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(2000))
+                .run()
+                .unwrap();
+            assert_eq!(
+                res.state.get_termination_reason(),
+                Some(&TerminationReason::SolverConverged)
+            );
+            for x in res.state.get_best_param().unwrap() {
+                assert_relative_eq!(*x, 1.0, epsilon = 1e-5);
+            }
+            assert!(res.state.get_func_counts()["cost_count"] < 1000);
+        }
+    }
+
+    #[test]
+    fn test_rosenbrock_bounds() {
+        // The unconstrained minimum (1, 1) violates the upper bound of the first parameter. The
+        // constrained minimum is (0.5, 0.25).
+        let solver: BOBYQA<Vec<f64>, f64> = BOBYQA::new()
+            .with_bounds(vec![-2.0, -2.0], vec![0.5, 2.0])
+            .with_initial_radius(0.2)
+            .unwrap();
+        let res = Executor::new(Rosenbrock {}, solver)
+            .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(2000))
+            .run()
+            .unwrap();
+        let param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(param[0], 0.5, epsilon = 1e-6);
+        assert_relative_eq!(param[1], 0.25, epsilon = 1e-5);
+        assert_relative_eq!(res.state.get_best_cost(), 0.25, epsilon = 1e-8);
+        assert!(res.state.get_func_counts()["cost_count"] < 500);
+    }
+}
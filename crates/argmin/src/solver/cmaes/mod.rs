@@ -0,0 +1,996 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Covariance Matrix Adaptation Evolution Strategy (CMA-ES)
+//!
+//! Derivative-free stochastic optimization method which samples candidates from a multivariate
+//! normal distribution and adapts mean, step size and covariance matrix of this distribution
+//! based on the ranking of the candidates. Optionally, the method can be restarted with an
+//! increasing population size (IPOP) or with alternating large and small populations (BIPOP).
+//!
+//! See [`CMAES`] for details.
+//!
+//! ## References
+//!
+//! \[0\] Nikolaus Hansen (2016). The CMA Evolution Strategy: A Tutorial.
+//! arXiv: <https://arxiv.org/abs/1604.00772>
+//!
+//! \[1\] Anne Auger and Nikolaus Hansen (2005). A Restart CMA Evolution Strategy With Increasing
+//! Population Size. 2005 IEEE Congress on Evolutionary Computation.
+//! DOI: <https://doi.org/10.1109/CEC.2005.1554902>
+//!
+//! \[2\] Nikolaus Hansen (2009). Benchmarking a BI-Population CMA-ES on the BBOB-2009 Function
+//! Testbed. GECCO '09. DOI: <https://doi.org/10.1145/1570256.1570333>
+
+use crate::core::{
+    ArgminFloat, CostFunction, Error, PopulationState, Problem, Solver, State, SyncAlias,
+    TerminationReason, KV,
+};
+use crate::solver::random::standard_normal;
+use argmin_math::{ArgminGet, ArgminSet, ArgminSize};
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Restart strategy of [`CMAES`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum RestartStrategy {
+    /// Every restart doubles the population size (IPOP-CMA-ES)
+    IPOP,
+    /// Restarts alternate between a regime with doubling population size and a regime with small
+    /// populations and small initial step sizes (BIPOP-CMA-ES). The regime which used fewer
+    /// function evaluations so far is chosen.
+    BIPOP,
+}
+
+/// # Covariance Matrix Adaptation Evolution Strategy (CMA-ES)
+///
+/// In every iteration, `lambda` candidates `x_k = m + sigma * y_k` are sampled, where `m` is the
+/// mean, `sigma` the step size and `y_k ~ N(0, C)` a sample of a multivariate normal distribution
+/// with covariance matrix `C`. The candidates are evaluated and ranked by their cost function
+/// values. The mean is moved towards a weighted average of the best `lambda/2` candidates, the
+/// covariance matrix is updated with the rank-one and rank-mu updates and the step size is adapted
+/// via cumulative step size adaptation. All default strategy parameters follow \[0\].
+///
+/// The candidates of the current generation are stored, sorted by cost, as population in
+/// [`PopulationState`]. They are evaluated via [`Problem::bulk_cost`], therefore the `rayon`
+/// feature enables parallel computation of the cost function. This can be beneficial for expensive
+/// cost functions, but may cause a drop in performance for cheap cost functions. Be sure to
+/// benchmark both parallel and sequential computation.
+///
+/// A run is considered converged if either the range of the best cost function values of the
+/// last `10 + ceil(30 n / lambda)` generations (and of all costs of the current generation) falls
+/// below the cost tolerance, if the step size in every coordinate falls below the parameter
+/// tolerance, or if the condition number of the covariance matrix exceeds `1e14`. If restarts are
+/// enabled via [`with_restarts`](`CMAES::with_restarts`), the method is then restarted from the
+/// initial mean with a new population size and step size according to the [`RestartStrategy`],
+/// until the maximum number of restarts is reached.
+///
+/// The step size `sigma`, the condition number of the covariance matrix `cond`, the population
+/// size `lambda` and the number of restarts are reported as KV metrics.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`].
+///
+/// ## References
+///
+/// \[0\] Nikolaus Hansen (2016). The CMA Evolution Strategy: A Tutorial.
+/// arXiv: <https://arxiv.org/abs/1604.00772>
+///
+/// \[1\] Anne Auger and Nikolaus Hansen (2005). A Restart CMA Evolution Strategy With Increasing
+/// Population Size. 2005 IEEE Congress on Evolutionary Computation.
+/// DOI: <https://doi.org/10.1109/CEC.2005.1554902>
+///
+/// \[2\] Nikolaus Hansen (2009). Benchmarking a BI-Population CMA-ES on the BBOB-2009 Function
+/// Testbed. GECCO '09. DOI: <https://doi.org/10.1145/1570256.1570333>
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct CMAES<P, F, R> {
+    /// Initial mean
+    init_mean: P,
+    /// Initial step size
+    init_sigma: F,
+    /// User-provided population size
+    init_lambda: Option<usize>,
+    /// Tolerance on the range of cost function values
+    tol_cost: F,
+    /// Tolerance on the step size
+    tol_param: F,
+    /// Restart strategy and maximum number of restarts
+    restart: Option<(RestartStrategy, u64)>,
+    /// Random number generator
+    rng_generator: R,
+    /// Number of dimensions
+    n: usize,
+    /// Default population size
+    lambda_default: usize,
+    /// Current population size
+    lambda: usize,
+    /// Population size of the large regime
+    lambda_large: usize,
+    /// Recombination weights
+    weights: Vec<F>,
+    /// Variance effective selection mass
+    mu_eff: F,
+    /// Learning rate for cumulation of the step size control
+    c_sigma: F,
+    /// Damping of the step size update
+    d_sigma: F,
+    /// Learning rate for cumulation of the rank-one update
+    c_c: F,
+    /// Learning rate of the rank-one update
+    c_1: F,
+    /// Learning rate of the rank-mu update
+    c_mu: F,
+    /// Expected norm of a `N(0, I)` distributed vector
+    chi_n: F,
+    /// Mean
+    mean: Vec<F>,
+    /// Step size
+    sigma: F,
+    /// Covariance matrix
+    cov: Vec<Vec<F>>,
+    /// Eigenvectors of the covariance matrix (as columns)
+    eigvecs: Vec<Vec<F>>,
+    /// Square roots of the eigenvalues of the covariance matrix
+    eigvals_sqrt: Vec<F>,
+    /// Evolution path of the step size
+    p_sigma: Vec<F>,
+    /// Evolution path of the covariance matrix
+    p_c: Vec<F>,
+    /// Generation counter of the current run
+    generation: u64,
+    /// Best cost function values of the last generations
+    history: VecDeque<F>,
+    /// Number of restarts so far
+    restarts: u64,
+    /// Number of cost function evaluations of the current run
+    evals_run: u64,
+    /// Number of cost function evaluations spent in the large population regime
+    evals_large: u64,
+    /// Number of cost function evaluations spent in the small population regime
+    evals_small: u64,
+    /// Whether the current run belongs to the small population regime
+    small_regime: bool,
+}
+
+impl<P, F> CMAES<P, F, rand::rngs::StdRng>
+where
+    P: Clone + SyncAlias + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `CMAES`
+    ///
+    /// Takes the initial mean `init_mean` and the initial step size `init_sigma` as input. The
+    /// initial step size should be about a third of the width of the region in which the optimum
+    /// is expected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::cmaes::CMAES;
+    /// let cmaes: CMAES<Vec<f64>, f64, _> = CMAES::new(vec![1.0, 2.0], 0.5);
+    /// ```
+    pub fn new(init_mean: P, init_sigma: F) -> Self {
+        CMAES {
+            init_mean,
+            init_sigma,
+            init_lambda: None,
+            tol_cost: float!(1e-12),
+            tol_param: float!(1e-12) * init_sigma,
+            restart: None,
+            rng_generator: rand::rngs::StdRng::from_entropy(),
+            n: 0,
+            lambda_default: 0,
+            lambda: 0,
+            lambda_large: 0,
+            weights: vec![],
+            mu_eff: float!(0.0),
+            c_sigma: float!(0.0),
+            d_sigma: float!(0.0),
+            c_c: float!(0.0),
+            c_1: float!(0.0),
+            c_mu: float!(0.0),
+            chi_n: float!(0.0),
+            mean: vec![],
+            sigma: init_sigma,
+            cov: vec![],
+            eigvecs: vec![],
+            eigvals_sqrt: vec![],
+            p_sigma: vec![],
+            p_c: vec![],
+            generation: 0,
+            history: VecDeque::new(),
+            restarts: 0,
+            evals_run: 0,
+            evals_large: 0,
+            evals_small: 0,
+            small_regime: false,
+        }
+    }
+}
+
+impl<P, F, R0> CMAES<P, F, R0>
+where
+    P: Clone + SyncAlias + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+    R0: Rng,
+{
+    /// Set the random number generator
+    ///
+    /// Defaults to `rand::rngs::StdRng::from_entropy()`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::cmaes::CMAES;
+    /// # use rand::SeedableRng;
+    /// let cmaes: CMAES<Vec<f64>, f64, _> = CMAES::new(vec![1.0, 2.0], 0.5)
+    ///     .with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(1729));
+    /// ```
+    pub fn with_rng_generator<R1: Rng>(self, generator: R1) -> CMAES<P, F, R1> {
+        CMAES {
+            init_mean: self.init_mean,
+            init_sigma: self.init_sigma,
+            init_lambda: self.init_lambda,
+            tol_cost: self.tol_cost,
+            tol_param: self.tol_param,
+            restart: self.restart,
+            rng_generator: generator,
+            n: self.n,
+            lambda_default: self.lambda_default,
+            lambda: self.lambda,
+            lambda_large: self.lambda_large,
+            weights: self.weights,
+            mu_eff: self.mu_eff,
+            c_sigma: self.c_sigma,
+            d_sigma: self.d_sigma,
+            c_c: self.c_c,
+            c_1: self.c_1,
+            c_mu: self.c_mu,
+            chi_n: self.chi_n,
+            mean: self.mean,
+            sigma: self.sigma,
+            cov: self.cov,
+            eigvecs: self.eigvecs,
+            eigvals_sqrt: self.eigvals_sqrt,
+            p_sigma: self.p_sigma,
+            p_c: self.p_c,
+            generation: self.generation,
+            history: self.history,
+            restarts: self.restarts,
+            evals_run: self.evals_run,
+            evals_large: self.evals_large,
+            evals_small: self.evals_small,
+            small_regime: self.small_regime,
+        }
+    }
+}
+
+impl<P, F, R> CMAES<P, F, R>
+where
+    P: Clone + SyncAlias + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+    R: Rng,
+{
+    /// Set the population size `lambda`
+    ///
+    /// Must be at least `2`. Defaults to `4 + floor(3 ln(n))`, where `n` is the number of
+    /// parameters. With restarts enabled, this is the population size of the first run.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::cmaes::CMAES;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let cmaes: CMAES<Vec<f64>, f64, _> =
+    ///     CMAES::new(vec![1.0, 2.0], 0.5).with_population_size(20)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_population_size(mut self, lambda: usize) -> Result<Self, Error> {
+        if lambda < 2 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CMAES`: population size must be >= 2."
+            ));
+        }
+        self.init_lambda = Some(lambda);
+        Ok(self)
+    }
+
+    /// Set tolerance on the range of cost function values
+    ///
+    /// Must be non-negative. Defaults to `1e-12`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::cmaes::CMAES;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let cmaes: CMAES<Vec<f64>, f64, _> =
+    ///     CMAES::new(vec![1.0, 2.0], 0.5).with_tolerance_cost(1e-8)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance_cost(mut self, tol_cost: F) -> Result<Self, Error> {
+        if tol_cost < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CMAES`: cost tolerance must be >= 0."
+            ));
+        }
+        self.tol_cost = tol_cost;
+        Ok(self)
+    }
+
+    /// Set tolerance on the step size
+    ///
+    /// A run is considered converged if the standard deviation of the sampling distribution in
+    /// every coordinate falls below this tolerance. Must be non-negative. Defaults to `1e-12`
+    /// times the initial step size.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::cmaes::CMAES;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let cmaes: CMAES<Vec<f64>, f64, _> =
+    ///     CMAES::new(vec![1.0, 2.0], 0.5).with_tolerance_param(1e-10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance_param(mut self, tol_param: F) -> Result<Self, Error> {
+        if tol_param < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CMAES`: parameter tolerance must be >= 0."
+            ));
+        }
+        self.tol_param = tol_param;
+        Ok(self)
+    }
+
+    /// Enable restarts
+    ///
+    /// After convergence of a run, the method is restarted from the initial mean according to
+    /// `strategy`, at most `max_restarts` times. Restarts are disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::cmaes::{CMAES, RestartStrategy};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let cmaes: CMAES<Vec<f64>, f64, _> =
+    ///     CMAES::new(vec![1.0, 2.0], 0.5).with_restarts(RestartStrategy::BIPOP, 9)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_restarts(
+        mut self,
+        strategy: RestartStrategy,
+        max_restarts: u64,
+    ) -> Result<Self, Error> {
+        if max_restarts < 1 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CMAES`: maximum number of restarts must be >= 1."
+            ));
+        }
+        self.restart = Some((strategy, max_restarts));
+        Ok(self)
+    }
+
+    /// Starts a new run with population size `lambda` and step size `sigma`
+    fn start_run(&mut self, lambda: usize, sigma: F) {
+        let n = self.n;
+        let nf: F = F::from_usize(n).unwrap();
+        let mu = lambda / 2;
+        let mut weights: Vec<F> = (1..=mu)
+            .map(|i| {
+                (F::from_usize(lambda + 1).unwrap() / float!(2.0)).ln()
+                    - F::from_usize(i).unwrap().ln()
+            })
+            .collect();
+        let sum = weights.iter().fold(float!(0.0), |acc, &w| acc + w);
+        weights.iter_mut().for_each(|w| *w = *w / sum);
+        let mu_eff = float!(1.0) / weights.iter().fold(float!(0.0), |acc, &w| acc + w * w);
+
+        self.c_sigma = (mu_eff + float!(2.0)) / (nf + mu_eff + float!(5.0));
+        self.d_sigma = float!(1.0)
+            + float!(2.0)
+                * float!(0.0f64)
+                    .max(((mu_eff - float!(1.0)) / (nf + float!(1.0))).sqrt() - float!(1.0))
+            + self.c_sigma;
+        self.c_c = (float!(4.0) + mu_eff / nf) / (nf + float!(4.0) + float!(2.0) * mu_eff / nf);
+        self.c_1 = float!(2.0) / ((nf + float!(1.3)).powi(2) + mu_eff);
+        self.c_mu = (float!(1.0) - self.c_1).min(
+            float!(2.0) * (mu_eff - float!(2.0) + float!(1.0) / mu_eff)
+                / ((nf + float!(2.0)).powi(2) + mu_eff),
+        );
+        self.chi_n = nf.sqrt()
+            * (float!(1.0) - float!(1.0) / (float!(4.0) * nf)
+                + float!(1.0) / (float!(21.0) * nf * nf));
+        self.weights = weights;
+        self.mu_eff = mu_eff;
+
+        self.lambda = lambda;
+        self.sigma = sigma;
+        self.mean = (0..n).map(|i| self.init_mean.get(i)).collect();
+        self.cov = identity(n);
+        self.eigvecs = identity(n);
+        self.eigvals_sqrt = vec![float!(1.0); n];
+        self.p_sigma = vec![float!(0.0); n];
+        self.p_c = vec![float!(0.0); n];
+        self.generation = 0;
+        self.history.clear();
+        self.evals_run = 0;
+    }
+
+    /// Chooses population size and step size of the next run, returns `false` if no restarts are
+    /// left
+    fn restart(&mut self) -> bool {
+        let strategy = match self.restart {
+            Some((strategy, max_restarts)) if self.restarts < max_restarts => strategy,
+            _ => return false,
+        };
+        if self.small_regime {
+            self.evals_small += self.evals_run;
+        } else {
+            self.evals_large += self.evals_run;
+        }
+        self.restarts += 1;
+
+        let (lambda, sigma) = match strategy {
+            RestartStrategy::IPOP => {
+                self.lambda_large *= 2;
+                (self.lambda_large, self.init_sigma)
+            }
+            RestartStrategy::BIPOP => {
+                // The first restart as well as every restart for which the small regime has used
+                // up at least as many evaluations as the large regime uses a large population.
+                if self.restarts == 1 || self.evals_small >= self.evals_large {
+                    self.small_regime = false;
+                    self.lambda_large *= 2;
+                    (self.lambda_large, self.init_sigma)
+                } else {
+                    self.small_regime = true;
+                    let u: F = F::from_f64(self.rng_generator.gen::<f64>()).unwrap();
+                    let ratio = F::from_usize(self.lambda_large).unwrap()
+                        / F::from_usize(self.lambda_default).unwrap()
+                        / float!(2.0);
+                    let lambda = (F::from_usize(self.lambda_default).unwrap() * ratio.powf(u * u))
+                        .floor()
+                        .to_usize()
+                        .unwrap()
+                        .max(2);
+                    let sigma = self.init_sigma * float!(10.0f64).powf(float!(-2.0) * u);
+                    (lambda, sigma)
+                }
+            }
+        };
+        self.start_run(lambda, sigma);
+        true
+    }
+
+    /// Samples `y ~ N(0, C)`
+    fn sample(&mut self) -> Vec<F> {
+        let n = self.n;
+        let dz: Vec<F> = (0..n)
+            .map(|i| self.eigvals_sqrt[i] * standard_normal(&mut self.rng_generator))
+            .collect();
+        (0..n)
+            .map(|i| (0..n).fold(float!(0.0), |acc, k| acc + self.eigvecs[i][k] * dz[k]))
+            .collect()
+    }
+
+    /// Converts `x` into a parameter vector
+    fn to_param(&self, x: &[F]) -> P {
+        let mut param = self.init_mean.clone();
+        for (i, &xi) in x.iter().enumerate() {
+            param.set(i, xi);
+        }
+        param
+    }
+
+    /// Condition number of the covariance matrix
+    fn condition_number(&self) -> F {
+        let (min, max) = self
+            .eigvals_sqrt
+            .iter()
+            .fold((F::infinity(), float!(0.0)), |(min, max): (F, F), &d| {
+                (min.min(d), max.max(d))
+            });
+        (max / min).powi(2)
+    }
+
+    /// Updates mean, evolution paths, covariance matrix and step size with the sampled steps `ys`,
+    /// sorted by cost
+    fn update(&mut self, ys: &[Vec<F>]) {
+        let n = self.n;
+        let nf: F = F::from_usize(n).unwrap();
+
+        // weighted mean of the selected steps
+        let y_w: Vec<F> = (0..n)
+            .map(|i| {
+                self.weights
+                    .iter()
+                    .zip(ys.iter())
+                    .fold(float!(0.0), |acc, (&w, y)| acc + w * y[i])
+            })
+            .collect();
+        for (m, &y) in self.mean.iter_mut().zip(y_w.iter()) {
+            *m = *m + self.sigma * y;
+        }
+
+        // C^(-1/2) y_w = B D^(-1) B^T y_w
+        let bty: Vec<F> = (0..n)
+            .map(|k| {
+                (0..n).fold(float!(0.0), |acc, i| acc + self.eigvecs[i][k] * y_w[i])
+                    / self.eigvals_sqrt[k]
+            })
+            .collect();
+        let c_sigma = self.c_sigma;
+        let fac_sigma = (c_sigma * (float!(2.0) - c_sigma) * self.mu_eff).sqrt();
+        for i in 0..n {
+            let cy = (0..n).fold(float!(0.0), |acc, k| acc + self.eigvecs[i][k] * bty[k]);
+            self.p_sigma[i] = (float!(1.0) - c_sigma) * self.p_sigma[i] + fac_sigma * cy;
+        }
+        let norm_p_sigma = self
+            .p_sigma
+            .iter()
+            .fold(float!(0.0), |acc, &p| acc + p * p)
+            .sqrt();
+
+        self.generation += 1;
+        let h_sigma = norm_p_sigma
+            / (float!(1.0)
+                - (float!(1.0) - c_sigma)
+                    .powi(2 * self.generation.min(i32::MAX as u64 / 2) as i32))
+            .sqrt()
+            < (float!(1.4) + float!(2.0) / (nf + float!(1.0))) * self.chi_n;
+        let h_sigma: F = if h_sigma { float!(1.0) } else { float!(0.0) };
+
+        let c_c = self.c_c;
+        let fac_c = (c_c * (float!(2.0) - c_c) * self.mu_eff).sqrt();
+        for (p, &y) in self.p_c.iter_mut().zip(y_w.iter()) {
+            *p = (float!(1.0) - c_c) * *p + h_sigma * fac_c * y;
+        }
+
+        // rank-one and rank-mu update
+        let (c_1, c_mu) = (self.c_1, self.c_mu);
+        let delta_h = (float!(1.0) - h_sigma) * c_c * (float!(2.0) - c_c);
+        for i in 0..n {
+            for j in 0..=i {
+                let rank_mu = self
+                    .weights
+                    .iter()
+                    .zip(ys.iter())
+                    .fold(float!(0.0), |acc, (&w, y)| acc + w * y[i] * y[j]);
+                let c = (float!(1.0) - c_1 - c_mu + c_1 * delta_h) * self.cov[i][j]
+                    + c_1 * self.p_c[i] * self.p_c[j]
+                    + c_mu * rank_mu;
+                self.cov[i][j] = c;
+                self.cov[j][i] = c;
+            }
+        }
+
+        // cumulative step size adaptation
+        self.sigma = self.sigma
+            * ((c_sigma / self.d_sigma) * (norm_p_sigma / self.chi_n - float!(1.0))).exp();
+
+        let (eigvals, eigvecs) = symmetric_eigen(&self.cov);
+        self.eigvals_sqrt = eigvals
+            .into_iter()
+            .map(|l| l.max(F::min_positive_value()).sqrt())
+            .collect();
+        self.eigvecs = eigvecs;
+    }
+
+    /// Checks whether the current run is converged
+    fn run_converged(&self, costs: &[F]) -> bool {
+        let n = self.n;
+        // cost tolerance
+        let history_len = 10 + (30.0 * n as f64 / self.lambda as f64).ceil() as usize;
+        if self.history.len() >= history_len {
+            let (min, max) = self.history.iter().chain(costs.iter()).fold(
+                (F::infinity(), F::neg_infinity()),
+                |(min, max): (F, F), &c| (min.min(c), max.max(c)),
+            );
+            if max - min <= self.tol_cost {
+                return true;
+            }
+        }
+        // parameter tolerance
+        if (0..n).all(|i| {
+            self.sigma * self.cov[i][i].sqrt() < self.tol_param
+                && self.sigma * self.p_c[i].abs() < self.tol_param
+        }) {
+            return true;
+        }
+        // condition of the covariance matrix
+        self.condition_number() > float!(1e14)
+    }
+}
+
+impl<O, P, F, R> Solver<O, PopulationState<P, F>> for CMAES<P, F, R>
+where
+    O: CostFunction<Param = P, Output = F> + SyncAlias,
+    P: Clone + SyncAlias + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+    R: Rng,
+{
+    const NAME: &'static str = "CMA-ES";
+
+    fn init(
+        &mut self,
+        _problem: &mut Problem<O>,
+        state: PopulationState<P, F>,
+    ) -> Result<(PopulationState<P, F>, Option<KV>), Error> {
+        if self.init_sigma <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CMAES`: initial step size must be > 0."
+            ));
+        }
+        self.n = self.init_mean.shape();
+        if self.n < 1 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CMAES`: initial mean must have at least one element."
+            ));
+        }
+        self.lambda_default = self
+            .init_lambda
+            .unwrap_or(4 + (3.0 * (self.n as f64).ln()).floor() as usize);
+        self.lambda_large = self.lambda_default;
+        self.restarts = 0;
+        self.evals_large = 0;
+        self.evals_small = 0;
+        self.small_regime = false;
+        self.start_run(self.lambda_default, self.init_sigma);
+
+        Ok((
+            state,
+            Some(kv!(
+                "sigma" => self.sigma;
+                "cond" => self.condition_number();
+                "lambda" => self.lambda as u64;
+                "restarts" => self.restarts;
+            )),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: PopulationState<P, F>,
+    ) -> Result<(PopulationState<P, F>, Option<KV>), Error> {
+        let ys: Vec<Vec<F>> = (0..self.lambda).map(|_| self.sample()).collect();
+        let params: Vec<P> = ys
+            .iter()
+            .map(|y| {
+                let x: Vec<F> = self
+                    .mean
+                    .iter()
+                    .zip(y.iter())
+                    .map(|(&m, &yi)| m + self.sigma * yi)
+                    .collect();
+                self.to_param(&x)
+            })
+            .collect();
+
+        let costs = problem.bulk_cost(&params)?;
+        self.evals_run += self.lambda as u64;
+
+        // sort candidates by cost, such that the first one is the best one
+        let mut idx: Vec<usize> = (0..self.lambda).collect();
+        idx.sort_by(|&a, &b| {
+            costs[a]
+                .partial_cmp(&costs[b])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let ys: Vec<Vec<F>> = idx.iter().map(|&i| ys[i].clone()).collect();
+        let costs: Vec<F> = idx.iter().map(|&i| costs[i]).collect();
+        let mut params: Vec<Option<P>> = params.into_iter().map(Some).collect();
+        let population: Vec<P> = idx.iter().map(|&i| params[i].take().unwrap()).collect();
+
+        self.update(&ys);
+
+        let converged = self.run_converged(&costs);
+        self.history.push_back(costs[0]);
+        let history_len = 10 + (30.0 * self.n as f64 / self.lambda as f64).ceil() as usize;
+        while self.history.len() > history_len {
+            self.history.pop_front();
+        }
+
+        let mut state = state
+            .individual(population[0].clone())
+            .cost(costs[0])
+            .population(population);
+
+        if converged && !self.restart() {
+            state = state.terminate_with(TerminationReason::SolverConverged);
+        }
+
+        Ok((
+            state,
+            Some(kv!(
+                "sigma" => self.sigma;
+                "cond" => self.condition_number();
+                "lambda" => self.lambda as u64;
+                "restarts" => self.restarts;
+            )),
+        ))
+    }
+}
+
+/// Identity matrix of size `n`
+fn identity<F: ArgminFloat>(n: usize) -> Vec<Vec<F>> {
+    (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| if i == j { float!(1.0) } else { float!(0.0) })
+                .collect()
+        })
+        .collect()
+}
+
+/// Eigendecomposition of a symmetric matrix via the cyclic Jacobi method
+///
+/// Returns the eigenvalues and a matrix whose columns are the corresponding eigenvectors.
+fn symmetric_eigen<F: ArgminFloat>(a: &[Vec<F>]) -> (Vec<F>, Vec<Vec<F>>) {
+    let n = a.len();
+    let mut a = a.to_vec();
+    let mut v: Vec<Vec<F>> = identity(n);
+    for _ in 0..100 {
+        let mut off = float!(0.0);
+        let mut total = float!(0.0);
+        for (p, row) in a.iter().enumerate() {
+            for (q, &apq) in row.iter().enumerate() {
+                total = total + apq * apq;
+                if p != q {
+                    off = off + apq * apq;
+                }
+            }
+        }
+        if off <= F::epsilon() * F::epsilon() * total {
+            break;
+        }
+        for p in 0..n {
+            for q in (p + 1)..n {
+                if a[p][q].abs() <= F::min_positive_value() {
+                    continue;
+                }
+                let theta = (a[q][q] - a[p][p]) / (float!(2.0) * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + float!(1.0)).sqrt());
+                let c = float!(1.0) / (t * t + float!(1.0)).sqrt();
+                let s = t * c;
+                for row in a.iter_mut() {
+                    let (akp, akq) = (row[p], row[q]);
+                    row[p] = c * akp - s * akq;
+                    row[q] = s * akp + c * akq;
+                }
+                let (row_p, row_q) = (a[p].clone(), a[q].clone());
+                for (k, (&apk, &aqk)) in row_p.iter().zip(row_q.iter()).enumerate() {
+                    a[p][k] = c * apk - s * aqk;
+                    a[q][k] = s * apk + c * aqk;
+                }
+                for row in v.iter_mut() {
+                    let (vkp, vkq) = (row[p], row[q]);
+                    row[p] = c * vkp - s * vkq;
+                    row[q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+    ((0..n).map(|i| a[i][i]).collect(), v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{test_utils::TestProblem, ArgminError, Executor};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+
+    test_trait_impl!(cmaes, CMAES<Vec<f64>, f64, rand::rngs::StdRng>);
+
+    struct Sphere {}
+
+    impl CostFunction for Sphere {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(p.iter().map(|x| x.powi(2)).sum())
+        }
+    }
+
+    struct Ellipsoid {}
+
+    impl CostFunction for Ellipsoid {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            let n = p.len() as f64;
+            Ok(p.iter()
+                .enumerate()
+                .map(|(i, x)| 1e6f64.powf(i as f64 / (n - 1.0)) * x.powi(2))
+                .sum())
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let cmaes: CMAES<Vec<f64>, f64, _> = CMAES::new(vec![1.0, 2.0], 0.5);
+        let CMAES {
+            init_mean,
+            init_sigma,
+            init_lambda,
+            tol_cost,
+            tol_param,
+            restart,
+            ..
+        } = cmaes;
+        assert_eq!(init_mean, vec![1.0, 2.0]);
+        assert_eq!(init_sigma.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert!(init_lambda.is_none());
+        assert_eq!(tol_cost.to_ne_bytes(), 1e-12f64.to_ne_bytes());
+        assert_eq!(tol_param.to_ne_bytes(), (0.5 * 1e-12f64).to_ne_bytes());
+        assert!(restart.is_none());
+    }
+
+    #[test]
+    fn test_builder_errors() {
+        let cmaes = || -> CMAES<Vec<f64>, f64, _> { CMAES::new(vec![1.0, 2.0], 0.5) };
+        assert_error!(
+            cmaes().with_population_size(1),
+            ArgminError,
+            "Invalid parameter: \"`CMAES`: population size must be >= 2.\""
+        );
+        assert_error!(
+            cmaes().with_tolerance_cost(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`CMAES`: cost tolerance must be >= 0.\""
+        );
+        assert_error!(
+            cmaes().with_tolerance_param(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`CMAES`: parameter tolerance must be >= 0.\""
+        );
+        assert_error!(
+            cmaes().with_restarts(RestartStrategy::IPOP, 0),
+            ArgminError,
+            "Invalid parameter: \"`CMAES`: maximum number of restarts must be >= 1.\""
+        );
+    }
+
+    #[test]
+    fn test_init_errors() {
+        let mut cmaes: CMAES<Vec<f64>, f64, _> = CMAES::new(vec![1.0, 2.0], 0.0);
+        let res = cmaes.init(
+            &mut Problem::new(TestProblem::new()),
+            PopulationState::new(),
+        );
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`CMAES`: initial step size must be > 0.\""
+        );
+
+        let mut cmaes: CMAES<Vec<f64>, f64, _> = CMAES::new(vec![], 1.0);
+        let res = cmaes.init(
+            &mut Problem::new(TestProblem::new()),
+            PopulationState::new(),
+        );
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`CMAES`: initial mean must have at least one element.\""
+        );
+    }
+
+    #[test]
+    fn test_symmetric_eigen() {
+        let a = vec![
+            vec![4.0, 1.0, -2.0],
+            vec![1.0, 2.0, 0.5],
+            vec![-2.0, 0.5, 3.0],
+        ];
+        let (eigvals, eigvecs) = symmetric_eigen(&a);
+        for k in 0..3 {
+            for i in 0..3 {
+                let av: f64 = (0..3).map(|j| a[i][j] * eigvecs[j][k]).sum();
+                assert_relative_eq!(av, eigvals[k] * eigvecs[i][k], epsilon = 1e-12);
+            }
+        }
+        let trace: f64 = eigvals.iter().sum();
+        assert_relative_eq!(trace, 9.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_next_iter() {
+        let mut cmaes: CMAES<Vec<f64>, f64, _> = CMAES::new(vec![1.0, 2.0, 3.0], 0.5)
+            .with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(42));
+        let mut problem = Problem::new(Sphere {});
+        let (state, kv) = cmaes.init(&mut problem, PopulationState::new()).unwrap();
+        let kv = kv.unwrap();
+        assert_eq!(kv.get("lambda").unwrap().get_uint().unwrap(), 7);
+        let (state, _) = cmaes.next_iter(&mut problem, state).unwrap();
+        let population = state.get_population().unwrap();
+        assert_eq!(population.len(), 7);
+        let costs: Vec<f64> = population
+            .iter()
+            .map(|p| Sphere {}.cost(p).unwrap())
+            .collect();
+        for w in costs.windows(2) {
+            assert!(w[0] <= w[1]);
+        }
+        assert_eq!(state.get_cost().to_ne_bytes(), costs[0].to_ne_bytes());
+        assert_eq!(problem.counts["cost_count"], 7);
+    }
+
+    #[test]
+    fn test_ellipsoid() {
+        let cmaes: CMAES<Vec<f64>, f64, _> = CMAES::new(vec![1.0; 5], 0.5)
+            .with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(1729));
+        let res = Executor::new(Ellipsoid {}, cmaes)
+            .configure(|state| state.max_iters(5000))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        assert!(res.state.get_best_cost() < 1e-10);
+        for x in res.state.get_best_param().unwrap() {
+            assert_relative_eq!(*x, 0.0, epsilon = 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_restarts() {
+        for strategy in [RestartStrategy::IPOP, RestartStrategy::BIPOP] {
+            let cmaes: CMAES<Vec<f64>, f64, _> = CMAES::new(vec![1.0, 2.0], 0.5)
+                .with_tolerance_cost(1e-4)
+                .unwrap()
+                .with_restarts(strategy, 3)
+                .unwrap()
+                .with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(1729));
+            let res = Executor::new(Sphere {}, cmaes)
+                .configure(|state| state.max_iters(5000))
+                .run()
+                .unwrap();
+            assert_eq!(
+                res.state.get_termination_reason(),
+                Some(&TerminationReason::SolverConverged)
+            );
+            assert_eq!(res.solver.restarts, 3);
+            assert_eq!(res.solver.lambda_default, 6);
+            match strategy {
+                RestartStrategy::IPOP => {
+                    assert_eq!(res.solver.lambda_large, 48);
+                    assert_eq!(res.solver.lambda, 48);
+                }
+                RestartStrategy::BIPOP => {
+                    assert!(res.solver.lambda_large >= 12);
+                    assert!(res.solver.evals_small > 0 || res.solver.small_regime);
+                }
+            }
+        }
+    }
+}
@@ -5,10 +5,14 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
-use crate::core::{ArgminFloat, Error, IterState, Operator, Problem, Solver, State, KV};
+use crate::core::{
+    ArgminFloat, Error, HessianVectorProduct, IterState, MatrixFree, Operator, Problem, Solver,
+    State, KV,
+};
 use argmin_math::{ArgminConj, ArgminDot, ArgminL2Norm, ArgminMul, ArgminScaledAdd, ArgminSub};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
 
 /// # Conjugate Gradient method
 ///
@@ -23,13 +27,17 @@ use serde::{Deserialize, Serialize};
 ///
 /// The optimization problem is required to implement [`Operator`].
 ///
+/// Alternatively, `A` can be the Hessian of a cost function at a given point. After calling
+/// [`matrix_free`](`ConjugateGradient::matrix_free`), the optimization problem is required to
+/// implement [`HessianVectorProduct`] instead.
+///
 /// ## Reference
 ///
 /// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 /// Springer. ISBN 0-387-30303-0.
 #[derive(Clone)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
-pub struct ConjugateGradient<P, F> {
+pub struct ConjugateGradient<P, F, M = ()> {
     /// b (right hand side of `A * x = b`)
     b: P,
     /// p
@@ -38,6 +46,12 @@ pub struct ConjugateGradient<P, F> {
     p_prev: Option<P>,
     /// r^T * r
     rtr: F,
+    /// p^T * A * p of the previous search direction
+    curvature: F,
+    /// Point at which the Hessian is evaluated (only used with [`MatrixFree`])
+    hessian_param: Option<P>,
+    /// Selects how `A * p` is computed
+    mode: PhantomData<M>,
 }
 
 impl<P, F> ConjugateGradient<P, F>
@@ -61,9 +75,43 @@ where
             p: None,
             p_prev: None,
             rtr: F::nan(),
+            curvature: F::nan(),
+            hessian_param: None,
+            mode: PhantomData,
         }
     }
 
+    /// Solve `H(x) * y = b`, where `H(x)` is the Hessian of a cost function at `hessian_param`,
+    /// using only products of the Hessian with vectors.
+    ///
+    /// The optimization problem is then required to implement [`HessianVectorProduct`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::conjugategradient::ConjugateGradient;
+    /// # use argmin::core::MatrixFree;
+    /// # let b = vec![1.0f64, 1.0];
+    /// # let x = vec![0.5f64, 2.0];
+    /// let cg: ConjugateGradient<_, f64, MatrixFree> = ConjugateGradient::new(b).matrix_free(x);
+    /// ```
+    pub fn matrix_free(self, hessian_param: P) -> ConjugateGradient<P, F, MatrixFree> {
+        ConjugateGradient {
+            b: self.b,
+            p: self.p,
+            p_prev: self.p_prev,
+            rtr: self.rtr,
+            curvature: self.curvature,
+            hessian_param: Some(hessian_param),
+            mode: PhantomData,
+        }
+    }
+}
+
+impl<P, F, M> ConjugateGradient<P, F, M>
+where
+    F: ArgminFloat,
+{
     /// Return the previous search direction (Needed by [`NewtonCG`](`crate::solver::newton::NewtonCG`))
     ///
     /// Returns an error if the field `p_prev` is not initialized.
@@ -82,42 +130,48 @@ where
             "Field `p_prev` of `ConjugateGradient` not initialized."
         ))
     }
-}
 
-impl<P, O, R, F> Solver<O, IterState<P, (), (), (), R, F>> for ConjugateGradient<P, F>
-where
-    O: Operator<Param = P, Output = P>,
-    P: Clone + ArgminDot<P, F> + ArgminSub<P, R> + ArgminScaledAdd<P, F, P> + ArgminConj,
-    R: ArgminMul<F, R> + ArgminMul<F, P> + ArgminConj + ArgminDot<R, F> + ArgminScaledAdd<P, F, R>,
-    F: ArgminFloat + ArgminL2Norm<F>,
-{
-    const NAME: &'static str = "Conjugate Gradient";
+    /// Return the curvature `p^T * A * p` along the previous search direction (Needed by
+    /// [`NewtonCG`](`crate::solver::newton::NewtonCG`))
+    ///
+    /// Returns `NaN` before the first iteration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::conjugategradient::ConjugateGradient;
+    /// # let cg: ConjugateGradient<_, f64> = ConjugateGradient::new(vec![1.0f64, 1.0]);
+    /// let curvature: f64 = cg.get_prev_curvature();
+    /// # assert!(curvature.is_nan());
+    /// ```
+    pub fn get_prev_curvature(&self) -> F {
+        self.curvature
+    }
 
-    fn init(
+    /// Initializes residual and search direction from `A * x_0`
+    fn init_residuals<R>(
         &mut self,
-        problem: &mut Problem<O>,
+        ap: P,
         state: IterState<P, (), (), (), R, F>,
-    ) -> Result<(IterState<P, (), (), (), R, F>, Option<KV>), Error> {
-        let init_param = state.get_param().ok_or_else(argmin_error_closure!(
-            NotInitialized,
-            concat!(
-                "`ConjugateGradient` requires an initial parameter vector. ",
-                "Please provide an initial guess via `Executor`s `configure` method."
-            )
-        ))?;
-        let ap = problem.apply(init_param)?;
+    ) -> IterState<P, (), (), (), R, F>
+    where
+        P: Clone + ArgminSub<P, R>,
+        R: ArgminMul<F, R> + ArgminMul<F, P> + ArgminConj + ArgminDot<R, F>,
+    {
         let r0: R = self.b.sub(&ap).mul(&(float!(-1.0)));
         self.p = Some(r0.mul(&(float!(-1.0))));
         self.rtr = r0.dot(&r0.conj());
-        Ok((state.residuals(r0), None))
+        state.residuals(r0)
     }
 
-    /// Perform one iteration of CG algorithm
-    fn next_iter(
+    /// Takes the current search direction and residuals
+    fn take_direction<R>(
         &mut self,
-        problem: &mut Problem<O>,
-        mut state: IterState<P, (), (), (), R, F>,
-    ) -> Result<(IterState<P, (), (), (), R, F>, Option<KV>), Error> {
+        state: &mut IterState<P, (), (), (), R, F>,
+    ) -> Result<(P, R), Error>
+    where
+        P: Clone,
+    {
         let p = self.p.take().ok_or_else(argmin_error_closure!(
             PotentialBug,
             "`ConjugateGradient`: Field `p` not set"
@@ -126,9 +180,24 @@ where
             PotentialBug,
             "`ConjugateGradient`: Residuals in `state` not set"
         ))?;
+        Ok((p, r))
+    }
 
-        let apk = problem.apply(&p)?;
-        let alpha = self.rtr.div(p.dot(&apk.conj()));
+    /// Performs a CG step along `p`, given `apk = A * p`
+    fn step<R>(
+        &mut self,
+        p: P,
+        r: R,
+        apk: P,
+        state: IterState<P, (), (), (), R, F>,
+    ) -> Result<(IterState<P, (), (), (), R, F>, Option<KV>), Error>
+    where
+        P: Clone + ArgminDot<P, F> + ArgminScaledAdd<P, F, P> + ArgminConj,
+        R: ArgminMul<F, P> + ArgminConj + ArgminDot<R, F> + ArgminScaledAdd<P, F, R>,
+        F: ArgminL2Norm<F>,
+    {
+        self.curvature = p.dot(&apk.conj());
+        let alpha = self.rtr.div(self.curvature);
         let state_param = state.get_param().ok_or_else(argmin_error_closure!(
             PotentialBug,
             "`ConjugateGradient`: Parameter vector in `state` not set"
@@ -151,6 +220,94 @@ where
     }
 }
 
+impl<P, O, R, F> Solver<O, IterState<P, (), (), (), R, F>> for ConjugateGradient<P, F>
+where
+    O: Operator<Param = P, Output = P>,
+    P: Clone + ArgminDot<P, F> + ArgminSub<P, R> + ArgminScaledAdd<P, F, P> + ArgminConj,
+    R: ArgminMul<F, R> + ArgminMul<F, P> + ArgminConj + ArgminDot<R, F> + ArgminScaledAdd<P, F, R>,
+    F: ArgminFloat + ArgminL2Norm<F>,
+{
+    const NAME: &'static str = "Conjugate Gradient";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, (), (), (), R, F>,
+    ) -> Result<(IterState<P, (), (), (), R, F>, Option<KV>), Error> {
+        let init_param = state.get_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`ConjugateGradient` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let ap = problem.apply(init_param)?;
+        Ok((self.init_residuals(ap, state), None))
+    }
+
+    /// Perform one iteration of CG algorithm
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), R, F>,
+    ) -> Result<(IterState<P, (), (), (), R, F>, Option<KV>), Error> {
+        let (p, r) = self.take_direction(&mut state)?;
+        let apk = problem.apply(&p)?;
+        self.step(p, r, apk, state)
+    }
+}
+
+impl<P, O, R, F> Solver<O, IterState<P, (), (), (), R, F>> for ConjugateGradient<P, F, MatrixFree>
+where
+    O: HessianVectorProduct<Param = P, Output = P>,
+    P: Clone + ArgminDot<P, F> + ArgminSub<P, R> + ArgminScaledAdd<P, F, P> + ArgminConj,
+    R: ArgminMul<F, R> + ArgminMul<F, P> + ArgminConj + ArgminDot<R, F> + ArgminScaledAdd<P, F, R>,
+    F: ArgminFloat + ArgminL2Norm<F>,
+{
+    const NAME: &'static str = "Conjugate Gradient (matrix-free)";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, (), (), (), R, F>,
+    ) -> Result<(IterState<P, (), (), (), R, F>, Option<KV>), Error> {
+        let init_param = state.get_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`ConjugateGradient` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let hessian_param = self
+            .hessian_param
+            .as_ref()
+            .ok_or_else(argmin_error_closure!(
+                PotentialBug,
+                "`ConjugateGradient`: Field `hessian_param` not set"
+            ))?;
+        let ap = problem.hessian_vec(hessian_param, init_param)?;
+        Ok((self.init_residuals(ap, state), None))
+    }
+
+    /// Perform one iteration of CG algorithm
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), R, F>,
+    ) -> Result<(IterState<P, (), (), (), R, F>, Option<KV>), Error> {
+        let (p, r) = self.take_direction(&mut state)?;
+        let hessian_param = self
+            .hessian_param
+            .as_ref()
+            .ok_or_else(argmin_error_closure!(
+                PotentialBug,
+                "`ConjugateGradient`: Field `hessian_param` not set"
+            ))?;
+        let apk = problem.hessian_vec(hessian_param, &p)?;
+        self.step(p, r, apk, state)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -163,12 +320,33 @@ mod tests {
     #[test]
     fn test_new() {
         let cg: ConjugateGradient<_, f64> = ConjugateGradient::new(vec![1.0f64, 2.0]);
-        let ConjugateGradient { b, p, p_prev, rtr } = cg;
+        let ConjugateGradient {
+            b,
+            p,
+            p_prev,
+            rtr,
+            curvature,
+            hessian_param,
+            mode: _,
+        } = cg;
         assert_eq!(b[0].to_ne_bytes(), 1.0f64.to_ne_bytes());
         assert_eq!(b[1].to_ne_bytes(), 2.0f64.to_ne_bytes());
         assert!(p.is_none());
         assert!(p_prev.is_none());
         assert!(rtr.is_nan());
+        assert!(curvature.is_nan());
+        assert!(hessian_param.is_none());
+    }
+
+    #[test]
+    fn test_matrix_free() {
+        let cg: ConjugateGradient<_, f64, MatrixFree> =
+            ConjugateGradient::new(vec![1.0f64, 2.0]).matrix_free(vec![3.0, 4.0]);
+        let ConjugateGradient {
+            b, hessian_param, ..
+        } = cg;
+        assert_eq!(b, vec![1.0, 2.0]);
+        assert_eq!(hessian_param.unwrap(), vec![3.0, 4.0]);
     }
 
     #[test]
@@ -217,7 +395,15 @@ mod tests {
             .unwrap();
         assert!(kv.is_none());
 
-        let ConjugateGradient { b, p, p_prev, rtr } = cg;
+        let ConjugateGradient {
+            b,
+            p,
+            p_prev,
+            rtr,
+            curvature,
+            hessian_param,
+            mode: _,
+        } = cg;
 
         assert_relative_eq!(b[0], 1.0, epsilon = f64::EPSILON);
         assert_relative_eq!(b[1], 2.0, epsilon = f64::EPSILON);
@@ -237,6 +423,8 @@ mod tests {
         assert_relative_eq!(pp[1], p.as_ref().unwrap()[1], epsilon = f64::EPSILON);
         assert_relative_eq!(rtr, 8.0, epsilon = f64::EPSILON);
         assert!(p_prev.is_none());
+        assert!(curvature.is_nan());
+        assert!(hessian_param.is_none());
     }
 
     #[test]
@@ -317,8 +505,63 @@ mod tests {
         assert_relative_eq!(p_n, cg.p.as_ref().unwrap()[0]);
         assert_relative_eq!(p, cg.p_prev.as_ref().unwrap()[0]);
         assert_relative_eq!(rtr_n, cg.rtr);
+        assert_relative_eq!(p * apk, cg.get_prev_curvature());
 
         assert_relative_eq!(norm, state.get_cost());
         assert_relative_eq!(new_param, state.get_param().unwrap()[0]);
     }
+
+    #[test]
+    fn test_hessian_vec() {
+        use crate::core::Executor;
+
+        /// f(x) = 1/2 x^T A x with A = [[4, 1], [1, 3]]
+        struct Quadratic {}
+
+        impl HessianVectorProduct for Quadratic {
+            type Param = Vec<f64>;
+            type Output = Vec<f64>;
+
+            fn hessian_vec(&self, _param: &Vec<f64>, v: &Vec<f64>) -> Result<Vec<f64>, Error> {
+                Ok(vec![4.0 * v[0] + v[1], v[0] + 3.0 * v[1]])
+            }
+        }
+
+        let cg: ConjugateGradient<_, f64, MatrixFree> =
+            ConjugateGradient::new(vec![1.0f64, 2.0]).matrix_free(vec![0.0, 0.0]);
+        let res = Executor::new(Quadratic {}, cg)
+            .configure(|state| state.param(vec![0.0, 0.0]).max_iters(2))
+            .run()
+            .unwrap();
+
+        let x = res.state().get_best_param().unwrap();
+        assert_relative_eq!(x[0], 1.0 / 11.0, epsilon = 1e-12);
+        assert_relative_eq!(x[1], 7.0 / 11.0, epsilon = 1e-12);
+        assert_eq!(res.problem().counts["hessian_vec_count"], 3);
+    }
+
+    #[test]
+    fn test_matrix_free_hessian_param_not_set() {
+        let mut cg: ConjugateGradient<Vec<f64>, f64, MatrixFree> = ConjugateGradient {
+            b: vec![1.0f64, 2.0],
+            p: None,
+            p_prev: None,
+            rtr: f64::NAN,
+            curvature: f64::NAN,
+            hessian_param: None,
+            mode: PhantomData,
+        };
+        let state: IterState<Vec<f64>, (), (), (), Vec<f64>, f64> =
+            IterState::new().param(vec![0.0, 0.0]);
+        let res = cg.init(&mut Problem::new(TestProblem::new()), state);
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Potential bug: \"`ConjugateGradient`: ",
+                "Field `hessian_param` not set\". This is potentially a bug. ",
+                "Please file a report on https://github.com/argmin-rs/argmin/issues"
+            )
+        );
+    }
 }
@@ -0,0 +1,826 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Coordinate descent
+//!
+//! Minimizes cost functions with separable L1 (LASSO) and L2 (ridge) penalties one coordinate
+//! at a time.
+//!
+//! See [`CoordinateDescent`] for details.
+//!
+//! ## References
+//!
+//! Jerome Friedman, Trevor Hastie and Rob Tibshirani (2010). Regularization Paths for Generalized
+//! Linear Models via Coordinate Descent. Journal of Statistical Software 33(1), pp. 1-22.
+//!
+//! Stephen J. Wright (2015). Coordinate descent algorithms. Mathematical Programming 151,
+//! pp. 3-34.
+
+use crate::core::{
+    ArgminFloat, CostFunction, Error, IterState, PartialGradient, Problem, Solver,
+    TerminationReason, TerminationStatus, KV,
+};
+use argmin_math::{ArgminGet, ArgminSet, ArgminSize};
+use rand::prelude::*;
+use rand_xoshiro::Xoshiro256PlusPlus;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Order in which the coordinates are updated by [`CoordinateDescent`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum CoordinateSelection {
+    /// Coordinates are updated in ascending order
+    Cyclic,
+    /// Coordinates are updated in a random order which is drawn anew for every sweep
+    Random,
+}
+
+/// # Coordinate descent
+///
+/// Minimizes `f(x) + l1 ||x||_1 + l2/2 ||x||_2^2`, where the smooth part `f` is defined via
+/// [`CostFunction`] and [`PartialGradient`], with an optional elastic net penalty. Each iteration
+/// is a sweep over the coordinates which updates one parameter `x_i` at a time via
+///
+/// ```text
+/// x_i <- S(L_i x_i - df/dx_i(x), l1) / (L_i + l2)
+/// ```
+///
+/// where `L_i` is the upper bound on the second partial derivative provided by
+/// [`PartialGradient::coordinate_lipschitz`] and `S(z, t) = sign(z) max(|z| - t, 0)` is the
+/// soft-thresholding operator. For quadratic `f` (e.g. least squares), this is the exact minimizer
+/// along coordinate `i`. The coefficients of the penalties are set via
+/// [`with_l1_regularization`](`CoordinateDescent::with_l1_regularization`) and
+/// [`with_l2_regularization`](`CoordinateDescent::with_l2_regularization`).
+///
+/// The coordinates are updated in ascending order (the default) or in a random order which is
+/// drawn for every sweep (see [`with_selection`](`CoordinateDescent::with_selection`) and
+/// [`CoordinateSelection`]).
+///
+/// With screening enabled (via [`with_screening`](`CoordinateDescent::with_screening`)), a full
+/// sweep over all coordinates is followed by sweeps over the nonzero coordinates only until these
+/// have converged, after which another full sweep checks whether further coordinates become
+/// nonzero. For sparse solutions, this greatly reduces the number of partial derivatives
+/// computed.
+///
+/// The algorithm stops after a full sweep if the largest scaled update `(L_i + l2) |x_i^new - x_i|`
+/// (the infinity norm of the coordinate-wise gradient mapping) is below the tolerance set via
+/// [`with_tolerance_grad`](`CoordinateDescent::with_tolerance_grad`), or if the cost function
+/// value changed by less than the tolerance set via
+/// [`with_tolerance_cost`](`CoordinateDescent::with_tolerance_cost`).
+///
+/// The cost function value stored in the state includes the penalties. The number of updated
+/// coordinates, the number of nonzero parameters and whether the sweep covered all coordinates
+/// are reported as KV metrics `coordinates`, `nonzero` and `full_sweep`.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`] and [`PartialGradient`].
+/// Since `partial_gradient` is called once per coordinate update, implementations should avoid
+/// evaluating the full gradient.
+///
+/// ## References
+///
+/// Jerome Friedman, Trevor Hastie and Rob Tibshirani (2010). Regularization Paths for Generalized
+/// Linear Models via Coordinate Descent. Journal of Statistical Software 33(1), pp. 1-22.
+///
+/// Stephen J. Wright (2015). Coordinate descent algorithms. Mathematical Programming 151,
+/// pp. 3-34.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct CoordinateDescent<F, R> {
+    /// Coefficient of the L1 penalty
+    l1: F,
+    /// Coefficient of the L2 penalty
+    l2: F,
+    /// Order of the coordinate updates
+    selection: CoordinateSelection,
+    /// Restrict sweeps to nonzero coordinates
+    screening: bool,
+    /// Tolerance on the scaled coordinate updates
+    tol_grad: F,
+    /// Tolerance on the change of the cost function value
+    tol_cost: F,
+    /// Upper bounds on the second partial derivatives
+    lipschitz: Vec<F>,
+    /// Nonzero coordinates after the last full sweep
+    active: Vec<usize>,
+    /// Whether the next sweep covers all coordinates
+    full_sweep: bool,
+    /// Whether the last sweep covered all coordinates
+    last_full_sweep: bool,
+    /// Largest scaled coordinate update of the last sweep
+    max_step: F,
+    /// Random number generator
+    rng: R,
+}
+
+impl<F> CoordinateDescent<F, Xoshiro256PlusPlus>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `CoordinateDescent`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::coordinatedescent::CoordinateDescent;
+    /// let cd: CoordinateDescent<f64, _> = CoordinateDescent::new();
+    /// ```
+    pub fn new() -> Self {
+        CoordinateDescent {
+            l1: float!(0.0),
+            l2: float!(0.0),
+            selection: CoordinateSelection::Cyclic,
+            screening: false,
+            tol_grad: F::epsilon().sqrt(),
+            tol_cost: F::epsilon(),
+            lipschitz: vec![],
+            active: vec![],
+            full_sweep: true,
+            last_full_sweep: false,
+            max_step: F::infinity(),
+            rng: Xoshiro256PlusPlus::from_entropy(),
+        }
+    }
+}
+
+impl<F> Default for CoordinateDescent<F, Xoshiro256PlusPlus>
+where
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        CoordinateDescent::new()
+    }
+}
+
+impl<F, R0> CoordinateDescent<F, R0>
+where
+    F: ArgminFloat,
+{
+    /// Set the random number generator
+    ///
+    /// Only used with [`CoordinateSelection::Random`]. Defaults to
+    /// `rand_xoshiro::Xoshiro256PlusPlus::from_entropy()`. In order to be able to resume from a
+    /// checkpoint, the random number generator needs to be serializable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::coordinatedescent::CoordinateDescent;
+    /// # use rand::SeedableRng;
+    /// let cd: CoordinateDescent<f64, _> = CoordinateDescent::new()
+    ///     .with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(1729));
+    /// ```
+    pub fn with_rng_generator<R1: Rng>(self, generator: R1) -> CoordinateDescent<F, R1> {
+        CoordinateDescent {
+            l1: self.l1,
+            l2: self.l2,
+            selection: self.selection,
+            screening: self.screening,
+            tol_grad: self.tol_grad,
+            tol_cost: self.tol_cost,
+            lipschitz: self.lipschitz,
+            active: self.active,
+            full_sweep: self.full_sweep,
+            last_full_sweep: self.last_full_sweep,
+            max_step: self.max_step,
+            rng: generator,
+        }
+    }
+
+    /// Set coefficient of the L1 penalty `l1 ||x||_1`
+    ///
+    /// Must be non-negative. Defaults to `0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::coordinatedescent::CoordinateDescent;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let cd: CoordinateDescent<f64, _> = CoordinateDescent::new().with_l1_regularization(0.1)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_l1_regularization(mut self, l1: F) -> Result<Self, Error> {
+        if l1 < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CoordinateDescent`: coefficient of L1-regularization must be >= 0."
+            ));
+        }
+        self.l1 = l1;
+        Ok(self)
+    }
+
+    /// Set coefficient of the L2 penalty `l2/2 ||x||_2^2`
+    ///
+    /// Must be non-negative. Defaults to `0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::coordinatedescent::CoordinateDescent;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let cd: CoordinateDescent<f64, _> = CoordinateDescent::new().with_l2_regularization(0.1)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_l2_regularization(mut self, l2: F) -> Result<Self, Error> {
+        if l2 < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CoordinateDescent`: coefficient of L2-regularization must be >= 0."
+            ));
+        }
+        self.l2 = l2;
+        Ok(self)
+    }
+
+    /// Set the order in which coordinates are updated
+    ///
+    /// Defaults to [`CoordinateSelection::Cyclic`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::coordinatedescent::{CoordinateDescent, CoordinateSelection};
+    /// let cd: CoordinateDescent<f64, _> =
+    ///     CoordinateDescent::new().with_selection(CoordinateSelection::Random);
+    /// ```
+    #[must_use]
+    pub fn with_selection(mut self, selection: CoordinateSelection) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// Enable or disable screening
+    ///
+    /// If enabled, full sweeps alternate with sweeps over the nonzero coordinates only. Defaults
+    /// to `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::coordinatedescent::CoordinateDescent;
+    /// let cd: CoordinateDescent<f64, _> = CoordinateDescent::new().with_screening(true);
+    /// ```
+    #[must_use]
+    pub fn with_screening(mut self, screening: bool) -> Self {
+        self.screening = screening;
+        self
+    }
+
+    /// Sets tolerance for the stopping criterion based on the scaled coordinate updates
+    ///
+    /// The provided value must be non-negative. Defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::coordinatedescent::CoordinateDescent;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let cd: CoordinateDescent<f64, _> = CoordinateDescent::new().with_tolerance_grad(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance_grad(mut self, tol_grad: F) -> Result<Self, Error> {
+        if tol_grad < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CoordinateDescent`: gradient tolerance must be >= 0."
+            ));
+        }
+        self.tol_grad = tol_grad;
+        Ok(self)
+    }
+
+    /// Sets tolerance for the stopping criterion based on the change of the cost
+    ///
+    /// The provided value must be non-negative. Defaults to `EPSILON`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::coordinatedescent::CoordinateDescent;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let cd: CoordinateDescent<f64, _> = CoordinateDescent::new().with_tolerance_cost(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance_cost(mut self, tol_cost: F) -> Result<Self, Error> {
+        if tol_cost < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CoordinateDescent`: cost tolerance must be >= 0."
+            ));
+        }
+        self.tol_cost = tol_cost;
+        Ok(self)
+    }
+
+    /// Computes the cost function value including the penalties
+    fn cost<O, P>(&self, problem: &mut Problem<O>, param: &P) -> Result<F, Error>
+    where
+        O: CostFunction<Param = P, Output = F>,
+        P: ArgminGet<usize, F> + ArgminSize<usize>,
+    {
+        let (l1_norm, l2_norm_sq) = (0..param.shape()).fold(
+            (float!(0.0), float!(0.0)),
+            |(l1_norm, l2_norm_sq): (F, F), i| {
+                let x = param.get(i);
+                (l1_norm + x.abs(), l2_norm_sq + x * x)
+            },
+        );
+        Ok(problem.cost(param)? + self.l1 * l1_norm + float!(0.5) * self.l2 * l2_norm_sq)
+    }
+}
+
+impl<O, P, F, R> Solver<O, IterState<P, (), (), (), (), F>> for CoordinateDescent<F, R>
+where
+    O: CostFunction<Param = P, Output = F> + PartialGradient<Param = P, Float = F>,
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+    R: Rng,
+{
+    const NAME: &'static str = "Coordinate descent";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`CoordinateDescent` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+
+        self.lipschitz = (0..param.shape())
+            .map(|i| problem.coordinate_lipschitz(i))
+            .collect::<Result<_, _>>()?;
+        if self
+            .lipschitz
+            .iter()
+            .any(|&l| !(l >= float!(0.0) && l + self.l2 > float!(0.0)))
+        {
+            return Err(argmin_error!(
+                InvalidParameter,
+                concat!(
+                    "`CoordinateDescent`: `coordinate_lipschitz` must be >= 0 ",
+                    "and > 0 without L2-regularization."
+                )
+            ));
+        }
+        self.active = vec![];
+        self.full_sweep = true;
+        self.last_full_sweep = false;
+        self.max_step = F::infinity();
+
+        let cost = self.cost(problem, &param)?;
+        Ok((state.param(param).cost(cost), None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let mut param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`CoordinateDescent`: Parameter vector in state not set."
+        ))?;
+
+        let full_sweep = !self.screening || self.full_sweep;
+        let mut coordinates: Vec<usize> = if full_sweep {
+            (0..param.shape()).collect()
+        } else {
+            self.active.clone()
+        };
+        if self.selection == CoordinateSelection::Random {
+            coordinates.shuffle(&mut self.rng);
+        }
+
+        let mut max_step = float!(0.0);
+        for &i in coordinates.iter() {
+            let x = param.get(i);
+            let grad = problem.partial_gradient(&param, i)?;
+            let curvature = self.lipschitz[i] + self.l2;
+            let x_new = soft_threshold(self.lipschitz[i] * x - grad, self.l1) / curvature;
+            max_step = max_step.max(curvature * (x_new - x).abs());
+            param.set(i, x_new);
+        }
+        self.max_step = max_step;
+        self.last_full_sweep = full_sweep;
+
+        let nonzero: Vec<usize> = (0..param.shape())
+            .filter(|&i| param.get(i) != float!(0.0))
+            .collect();
+        if self.screening {
+            if full_sweep {
+                self.full_sweep = false;
+                self.active = nonzero.clone();
+            } else if max_step <= self.tol_grad {
+                self.full_sweep = true;
+            }
+        }
+
+        let cost = self.cost(problem, &param)?;
+        Ok((
+            state.param(param).cost(cost),
+            Some(kv!(
+                "coordinates" => coordinates.len() as u64;
+                "nonzero" => nonzero.len() as u64;
+                "full_sweep" => full_sweep;
+            )),
+        ))
+    }
+
+    fn terminate(&mut self, state: &IterState<P, (), (), (), (), F>) -> TerminationStatus {
+        if self.last_full_sweep {
+            if self.max_step <= self.tol_grad {
+                return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+            }
+            if (state.get_prev_cost() - state.get_cost()).abs() < self.tol_cost {
+                return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+            }
+        }
+        TerminationStatus::NotTerminated
+    }
+}
+
+/// Soft-thresholding operator `sign(z) max(|z| - t, 0)`
+fn soft_threshold<F: ArgminFloat>(z: F, t: F) -> F {
+    if z > t {
+        z - t
+    } else if z < -t {
+        z + t
+    } else {
+        float!(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor, State};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(
+        coordinate_descent,
+        CoordinateDescent<f64, Xoshiro256PlusPlus>
+    );
+
+    /// Least squares `1/2 ||X x - y||^2` with `X` given by its columns
+    struct LeastSquares {
+        columns: Vec<Vec<f64>>,
+        y: Vec<f64>,
+    }
+
+    impl LeastSquares {
+        fn residual(&self, p: &[f64]) -> Vec<f64> {
+            (0..self.y.len())
+                .map(|k| {
+                    self.columns
+                        .iter()
+                        .zip(p.iter())
+                        .map(|(c, x)| c[k] * x)
+                        .sum::<f64>()
+                        - self.y[k]
+                })
+                .collect()
+        }
+    }
+
+    impl CostFunction for LeastSquares {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(0.5 * self.residual(p).iter().map(|r| r * r).sum::<f64>())
+        }
+    }
+
+    impl PartialGradient for LeastSquares {
+        type Param = Vec<f64>;
+        type Float = f64;
+
+        fn partial_gradient(&self, p: &Self::Param, idx: usize) -> Result<f64, Error> {
+            Ok(self
+                .residual(p)
+                .iter()
+                .zip(self.columns[idx].iter())
+                .map(|(r, c)| r * c)
+                .sum())
+        }
+
+        fn coordinate_lipschitz(&self, idx: usize) -> Result<f64, Error> {
+            Ok(self.columns[idx].iter().map(|c| c * c).sum())
+        }
+    }
+
+    /// Orthonormal design: the LASSO solution is the soft-thresholded least squares solution
+    fn orthonormal() -> LeastSquares {
+        LeastSquares {
+            columns: vec![
+                vec![1.0, 0.0, 0.0, 0.0],
+                vec![0.0, 1.0, 0.0, 0.0],
+                vec![0.0, 0.0, 1.0, 0.0],
+            ],
+            y: vec![3.0, -0.5, -2.0, 1.0],
+        }
+    }
+
+    /// Correlated design with a sparse solution
+    fn correlated() -> LeastSquares {
+        let columns = vec![
+            vec![1.0, 0.5, 0.2, 0.0, 0.3, 1.0],
+            vec![0.8, 0.6, 0.1, 0.2, 0.2, 0.9],
+            vec![0.0, 1.0, 0.0, 1.0, 0.0, 0.5],
+            vec![0.2, 0.1, 1.0, 0.0, 0.4, 0.0],
+            vec![0.1, 0.0, 0.3, 0.6, 1.0, 0.2],
+        ];
+        // y = X (2, 0, 0, -1, 0)
+        let y = (0..6)
+            .map(|k| 2.0 * columns[0][k] - columns[3][k])
+            .collect();
+        LeastSquares { columns, y }
+    }
+
+    #[test]
+    fn test_new() {
+        let CoordinateDescent {
+            l1,
+            l2,
+            selection,
+            screening,
+            tol_grad,
+            tol_cost,
+            lipschitz,
+            active,
+            full_sweep,
+            last_full_sweep,
+            max_step,
+            rng: _rng,
+        } = CoordinateDescent::<f64, _>::new();
+
+        assert_eq!(l1.to_ne_bytes(), 0.0f64.to_ne_bytes());
+        assert_eq!(l2.to_ne_bytes(), 0.0f64.to_ne_bytes());
+        assert_eq!(selection, CoordinateSelection::Cyclic);
+        assert!(!screening);
+        assert_eq!(tol_grad.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert_eq!(tol_cost.to_ne_bytes(), f64::EPSILON.to_ne_bytes());
+        assert!(lipschitz.is_empty());
+        assert!(active.is_empty());
+        assert!(full_sweep);
+        assert!(!last_full_sweep);
+        assert!(max_step.is_infinite());
+    }
+
+    #[test]
+    fn test_setters() {
+        let cd = CoordinateDescent::<f64, _>::new()
+            .with_l1_regularization(0.5)
+            .unwrap()
+            .with_l2_regularization(0.25)
+            .unwrap()
+            .with_selection(CoordinateSelection::Random)
+            .with_screening(true)
+            .with_tolerance_grad(1e-4)
+            .unwrap()
+            .with_tolerance_cost(1e-8)
+            .unwrap();
+        assert_eq!(cd.l1.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert_eq!(cd.l2.to_ne_bytes(), 0.25f64.to_ne_bytes());
+        assert_eq!(cd.selection, CoordinateSelection::Random);
+        assert!(cd.screening);
+        assert_eq!(cd.tol_grad.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+        assert_eq!(cd.tol_cost.to_ne_bytes(), 1e-8f64.to_ne_bytes());
+
+        assert_error!(
+            CoordinateDescent::<f64, _>::new().with_l1_regularization(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`CoordinateDescent`: coefficient of L1-regularization must be >= 0.\""
+        );
+        assert_error!(
+            CoordinateDescent::<f64, _>::new().with_l2_regularization(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`CoordinateDescent`: coefficient of L2-regularization must be >= 0.\""
+        );
+        assert_error!(
+            CoordinateDescent::<f64, _>::new().with_tolerance_grad(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`CoordinateDescent`: gradient tolerance must be >= 0.\""
+        );
+        assert_error!(
+            CoordinateDescent::<f64, _>::new().with_tolerance_cost(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`CoordinateDescent`: cost tolerance must be >= 0.\""
+        );
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut cd = CoordinateDescent::<f64, _>::new();
+        let res = cd.init(&mut Problem::new(orthonormal()), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`CoordinateDescent` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_init_zero_lipschitz() {
+        let mut problem = orthonormal();
+        problem.columns.push(vec![0.0; 4]);
+        let res = Executor::new(problem, CoordinateDescent::new())
+            .configure(|state| state.param(vec![0.0; 4]))
+            .run();
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`CoordinateDescent`: `coordinate_lipschitz` must be >= 0 ",
+                "and > 0 without L2-regularization.\""
+            )
+        );
+
+        // Fine with L2-regularization
+        let mut problem = orthonormal();
+        problem.columns.push(vec![0.0; 4]);
+        let res = Executor::new(
+            problem,
+            CoordinateDescent::new()
+                .with_l2_regularization(1.0)
+                .unwrap(),
+        )
+        .configure(|state| state.param(vec![1.0; 4]))
+        .run()
+        .unwrap();
+        assert_relative_eq!(res.state().get_best_param().unwrap()[3], 0.0);
+    }
+
+    #[test]
+    fn test_soft_threshold() {
+        assert_relative_eq!(soft_threshold(3.0, 1.0), 2.0);
+        assert_relative_eq!(soft_threshold(-3.0, 1.0), -2.0);
+        assert_relative_eq!(soft_threshold(0.5, 1.0), 0.0);
+        assert_relative_eq!(soft_threshold(-0.5, 1.0), 0.0);
+    }
+
+    #[test]
+    fn test_lasso_orthonormal() {
+        let res = Executor::new(
+            orthonormal(),
+            CoordinateDescent::new()
+                .with_l1_regularization(1.0)
+                .unwrap(),
+        )
+        .configure(|state| state.param(vec![0.0; 3]))
+        .run()
+        .unwrap();
+        let x = res.state().get_best_param().unwrap();
+        assert_relative_eq!(x[0], 2.0);
+        assert_relative_eq!(x[1], 0.0);
+        assert_relative_eq!(x[2], -1.0);
+        assert_eq!(
+            res.state().get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        // 1/2 (1 + 0.25 + 1 + 1) + 3
+        assert_relative_eq!(res.state().get_best_cost(), 4.625);
+        assert_eq!(res.problem.counts["partial_gradient_count"], 3 * 2);
+    }
+
+    #[test]
+    fn test_elastic_net_orthonormal() {
+        let res = Executor::new(
+            orthonormal(),
+            CoordinateDescent::new()
+                .with_l1_regularization(1.0)
+                .unwrap()
+                .with_l2_regularization(1.0)
+                .unwrap(),
+        )
+        .configure(|state| state.param(vec![0.0; 3]))
+        .run()
+        .unwrap();
+        let x = res.state().get_best_param().unwrap();
+        assert_relative_eq!(x[0], 1.0);
+        assert_relative_eq!(x[1], 0.0);
+        assert_relative_eq!(x[2], -0.5);
+    }
+
+    #[test]
+    fn test_lasso_correlated() {
+        let solve = |selection, screening| {
+            Executor::new(
+                correlated(),
+                CoordinateDescent::new()
+                    .with_l1_regularization(0.01)
+                    .unwrap()
+                    .with_selection(selection)
+                    .with_screening(screening)
+                    .with_tolerance_grad(1e-12)
+                    .unwrap()
+                    .with_tolerance_cost(0.0)
+                    .unwrap()
+                    .with_rng_generator(Xoshiro256PlusPlus::seed_from_u64(42)),
+            )
+            .configure(|state| state.param(vec![0.0; 5]).max_iters(10000))
+            .run()
+            .unwrap()
+        };
+
+        let reference = solve(CoordinateSelection::Cyclic, false);
+        assert_eq!(
+            reference.state().get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        let x_ref = reference.state().get_best_param().unwrap().clone();
+        // Close to the sparse least squares solution
+        assert!((x_ref[0] - 2.0).abs() < 0.05);
+        assert!((x_ref[3] + 1.0).abs() < 0.05);
+        assert_relative_eq!(x_ref[1], 0.0);
+        assert_relative_eq!(x_ref[4], 0.0);
+
+        for (selection, screening) in [
+            (CoordinateSelection::Random, false),
+            (CoordinateSelection::Cyclic, true),
+            (CoordinateSelection::Random, true),
+        ] {
+            let res = solve(selection, screening);
+            assert_eq!(
+                res.state().get_termination_reason(),
+                Some(&TerminationReason::SolverConverged)
+            );
+            let x = res.state().get_best_param().unwrap();
+            for i in 0..5 {
+                assert_relative_eq!(x[i], x_ref[i], epsilon = 1e-9);
+            }
+        }
+    }
+    #[test]
+    fn test_screening() {
+        // Two relevant features and ten features which are too weakly correlated with the
+        // residual to ever become nonzero
+        let mut problem = correlated();
+        problem.columns = vec![problem.columns[0].clone(), problem.columns[3].clone()];
+        for k in 0..10 {
+            problem.columns.push(
+                (0..6)
+                    .map(|j| if j == k % 6 { 0.01 } else { 0.0 })
+                    .collect(),
+            );
+        }
+        let solve = |screening| {
+            Executor::new(
+                LeastSquares {
+                    columns: problem.columns.clone(),
+                    y: problem.y.clone(),
+                },
+                CoordinateDescent::new()
+                    .with_l1_regularization(0.1)
+                    .unwrap()
+                    .with_screening(screening)
+                    .with_tolerance_grad(1e-12)
+                    .unwrap()
+                    .with_tolerance_cost(0.0)
+                    .unwrap(),
+            )
+            .configure(|state| state.param(vec![0.0; 12]).max_iters(10000))
+            .run()
+            .unwrap()
+        };
+
+        let full = solve(false);
+        let screened = solve(true);
+        let x_full = full.state().get_best_param().unwrap();
+        let x_screened = screened.state().get_best_param().unwrap();
+        for i in 0..12 {
+            assert_relative_eq!(x_screened[i], x_full[i], epsilon = 1e-9);
+        }
+        for &x in x_full[2..].iter() {
+            assert_relative_eq!(x, 0.0);
+        }
+        assert!(
+            3 * screened.problem.counts["partial_gradient_count"]
+                < full.problem.counts["partial_gradient_count"]
+        );
+    }
+}
@@ -0,0 +1,355 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Dense linear algebra on internal vectors (`Vec<F>`) and row-major matrices (`Vec<Vec<F>>`)
+//!
+//! Some solvers keep small internal models (interpolation systems, limited-memory matrices,
+//! Gaussian process kernels, ...) whose size does not depend on the parameter vector type. These
+//! helpers operate on such internal representations, independent of the `argmin-math` backend.
+
+use crate::core::ArgminFloat;
+use argmin_math::{ArgminGet, ArgminSet, ArgminSize};
+
+/// Extracts the elements of a vector
+pub(crate) fn to_vec<T, F>(v: &T) -> Vec<F>
+where
+    T: ArgminGet<usize, F> + ArgminSize<usize>,
+{
+    (0..v.shape()).map(|i| v.get(i)).collect()
+}
+
+/// Extracts the elements of a matrix as a vector of rows
+pub(crate) fn to_rows<M, F>(m: &M) -> Vec<Vec<F>>
+where
+    M: ArgminGet<(usize, usize), F> + ArgminSize<(usize, usize)>,
+{
+    let (rows, cols) = m.shape();
+    (0..rows)
+        .map(|i| (0..cols).map(|j| m.get((i, j))).collect())
+        .collect()
+}
+
+/// Converts an internal vector into a parameter vector of the same type as `template`
+pub(crate) fn to_param<P, F>(template: &P, x: &[F]) -> P
+where
+    P: Clone + ArgminSet<usize, F>,
+    F: Copy,
+{
+    let mut param = template.clone();
+    for (i, &xi) in x.iter().enumerate() {
+        param.set(i, xi);
+    }
+    param
+}
+
+pub(crate) fn dot<F: ArgminFloat>(a: &[F], b: &[F]) -> F {
+    a.iter()
+        .zip(b.iter())
+        .fold(float!(0.0), |acc, (&x, &y)| acc + x * y)
+}
+
+pub(crate) fn norm<F: ArgminFloat>(a: &[F]) -> F {
+    dot(a, a).sqrt()
+}
+
+pub(crate) fn add<F: ArgminFloat>(a: &[F], b: &[F]) -> Vec<F> {
+    a.iter().zip(b.iter()).map(|(&x, &y)| x + y).collect()
+}
+
+pub(crate) fn sub<F: ArgminFloat>(a: &[F], b: &[F]) -> Vec<F> {
+    a.iter().zip(b.iter()).map(|(&x, &y)| x - y).collect()
+}
+
+pub(crate) fn scale<F: ArgminFloat>(a: &[F], factor: F) -> Vec<F> {
+    a.iter().map(|&x| x * factor).collect()
+}
+
+/// Computes `A x`
+pub(crate) fn mat_vec<F: ArgminFloat>(a: &[Vec<F>], x: &[F]) -> Vec<F> {
+    a.iter().map(|row| dot(row, x)).collect()
+}
+
+/// Computes `A^T x` for a matrix `A` with `n` columns
+pub(crate) fn mat_t_vec<F: ArgminFloat>(a: &[Vec<F>], x: &[F], n: usize) -> Vec<F> {
+    a.iter()
+        .zip(x.iter())
+        .fold(vec![float!(0.0); n], |mut acc, (row, &xi)| {
+            for (acc_j, &aij) in acc.iter_mut().zip(row.iter()) {
+                *acc_j = *acc_j + aij * xi;
+            }
+            acc
+        })
+}
+
+/// Largest absolute value of the elements of `a`, used to detect (numerically) singular pivots
+fn max_abs<F: ArgminFloat>(a: &[Vec<F>]) -> F {
+    a.iter()
+        .flat_map(|row| row.iter())
+        .fold(float!(0.0), |acc: F, &x| acc.max(x.abs()))
+}
+
+/// Solves `A x = b` via Gaussian elimination with partial pivoting. Returns `None` if `A` is
+/// (numerically) singular.
+pub(crate) fn lu_solve<F: ArgminFloat>(mut a: Vec<Vec<F>>, mut b: Vec<F>) -> Option<Vec<F>> {
+    let n = b.len();
+    let tol = F::epsilon() * max_abs(&a);
+    for k in 0..n {
+        let p = (k..n).fold(k, |p, i| if a[i][k].abs() > a[p][k].abs() { i } else { p });
+        if a[p][k].abs() <= tol || !a[p][k].is_finite() {
+            return None;
+        }
+        a.swap(k, p);
+        b.swap(k, p);
+        let (upper, lower) = a.split_at_mut(k + 1);
+        let (b_upper, b_lower) = b.split_at_mut(k + 1);
+        let pivot_row = &upper[k];
+        for (row, bi) in lower.iter_mut().zip(b_lower.iter_mut()) {
+            let factor = row[k] / pivot_row[k];
+            for (rj, &pj) in row[k..].iter_mut().zip(pivot_row[k..].iter()) {
+                *rj = *rj - factor * pj;
+            }
+            *bi = *bi - factor * b_upper[k];
+        }
+    }
+    let mut x = vec![float!(0.0); n];
+    for k in (0..n).rev() {
+        let s = a[k][k + 1..]
+            .iter()
+            .zip(x[k + 1..].iter())
+            .fold(b[k], |s, (&akj, &xj)| s - akj * xj);
+        x[k] = s / a[k][k];
+    }
+    Some(x)
+}
+
+/// Inverts a square matrix via Gauss-Jordan elimination with partial pivoting. Returns `None` if
+/// `A` is (numerically) singular.
+pub(crate) fn invert<F: ArgminFloat>(mut a: Vec<Vec<F>>) -> Option<Vec<Vec<F>>> {
+    let n = a.len();
+    let mut inv: Vec<Vec<F>> = (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| if i == j { float!(1.0) } else { float!(0.0) })
+                .collect()
+        })
+        .collect();
+    let tol = F::epsilon() * max_abs(&a);
+    for col in 0..n {
+        let p = (col..n).fold(col, |p, i| {
+            if a[i][col].abs() > a[p][col].abs() {
+                i
+            } else {
+                p
+            }
+        });
+        if a[p][col].abs() <= tol || !a[p][col].is_finite() {
+            return None;
+        }
+        a.swap(col, p);
+        inv.swap(col, p);
+        let pivot = a[col][col];
+        for j in 0..n {
+            a[col][j] = a[col][j] / pivot;
+            inv[col][j] = inv[col][j] / pivot;
+        }
+        for i in 0..n {
+            if i != col {
+                let factor = a[i][col];
+                if factor != float!(0.0) {
+                    for j in 0..n {
+                        a[i][j] = a[i][j] - factor * a[col][j];
+                        inv[i][j] = inv[i][j] - factor * inv[col][j];
+                    }
+                }
+            }
+        }
+    }
+    Some(inv)
+}
+
+/// Computes the Cholesky factor `L` of `A + shift I`, or returns `None` if the shifted matrix is
+/// not positive definite
+///
+/// Only the lower triangle of `A` is accessed.
+pub(crate) fn cholesky_shifted<F: ArgminFloat>(a: &[Vec<F>], shift: F) -> Option<Vec<Vec<F>>> {
+    let n = a.len();
+    let mut l = vec![vec![float!(0.0); n]; n];
+    for i in 0..n {
+        for j in 0..=i {
+            let sum = (0..j).fold(a[i][j], |acc, k| acc - l[i][k] * l[j][k]);
+            if i == j {
+                let d = sum + shift;
+                if d <= float!(0.0) || !d.is_finite() {
+                    return None;
+                }
+                l[i][i] = d.sqrt();
+            } else {
+                l[i][j] = sum / l[j][j];
+            }
+        }
+    }
+    Some(l)
+}
+
+/// Computes the Cholesky factor `L` of `A`, or returns `None` if `A` is not positive definite
+///
+/// Only the lower triangle of `A` is accessed.
+pub(crate) fn cholesky<F: ArgminFloat>(a: &[Vec<F>]) -> Option<Vec<Vec<F>>> {
+    cholesky_shifted(a, float!(0.0))
+}
+
+/// Computes the Cholesky factor `L` of `A + tau I` for the smallest `tau` of the sequence
+/// `0, beta - min_i a_ii, 2 (beta - min_i a_ii), ...` (with `beta = 1e-3`, starting at the second
+/// element if a diagonal element is not positive) for which `A + tau I` is positive definite
+///
+/// Returns `None` if no such `tau` is found within a limited number of attempts, for instance
+/// because `A` contains a NaN or infinite entry.
+pub(crate) fn modified_cholesky<F: ArgminFloat>(a: &[Vec<F>]) -> Option<Vec<Vec<F>>> {
+    const MAX_ATTEMPTS: usize = 100;
+    let n = a.len();
+    let beta = float!(1e-3);
+    let min_diag = (0..n).fold(F::infinity(), |acc, i| acc.min(a[i][i]));
+    let mut tau = if min_diag > float!(0.0) {
+        float!(0.0)
+    } else {
+        beta - min_diag
+    };
+    for _ in 0..MAX_ATTEMPTS {
+        if !tau.is_finite() {
+            return None;
+        }
+        if let Some(l) = cholesky_shifted(a, tau) {
+            return Some(l);
+        }
+        tau = (float!(2.0) * tau).max(beta);
+    }
+    None
+}
+
+/// Solves `L x = b`
+pub(crate) fn forward_substitution<F: ArgminFloat>(l: &[Vec<F>], b: &[F]) -> Vec<F> {
+    let n = b.len();
+    let mut x = vec![float!(0.0); n];
+    for i in 0..n {
+        x[i] = (0..i).fold(b[i], |acc, k| acc - l[i][k] * x[k]) / l[i][i];
+    }
+    x
+}
+
+/// Solves `L L^T x = b` given the Cholesky factor `L`
+pub(crate) fn chol_solve<F: ArgminFloat>(l: &[Vec<F>], b: &[F]) -> Vec<F> {
+    let n = b.len();
+    let z = forward_substitution(l, b);
+    let mut x = vec![float!(0.0); n];
+    for i in (0..n).rev() {
+        x[i] = (i + 1..n).fold(z[i], |acc, k| acc - l[k][i] * x[k]) / l[i][i];
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_conversions() {
+        let x: Vec<f64> = to_vec(&vec![1.0, 2.0]);
+        assert_eq!(x, vec![1.0, 2.0]);
+        let m: Vec<Vec<f64>> = to_rows(&vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+        assert_eq!(m, vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+        assert_eq!(
+            to_param(&vec![0.0, 0.0, 5.0], &[1.0, 2.0]),
+            vec![1.0, 2.0, 5.0]
+        );
+    }
+
+    #[test]
+    fn test_vector_ops() {
+        assert_relative_eq!(dot(&[1.0, 2.0], &[3.0, 4.0]), 11.0);
+        assert_relative_eq!(norm(&[3.0, 4.0]), 5.0);
+        assert_eq!(add(&[1.0, 2.0], &[3.0, 4.0]), vec![4.0, 6.0]);
+        assert_eq!(sub(&[1.0, 2.0], &[3.0, 4.0]), vec![-2.0, -2.0]);
+        assert_eq!(scale(&[1.0, 2.0], 3.0), vec![3.0, 6.0]);
+        let a = vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]];
+        assert_eq!(mat_vec(&a, &[1.0, 0.0, -1.0]), vec![-2.0, -2.0]);
+        assert_eq!(mat_t_vec(&a, &[1.0, -1.0], 3), vec![-3.0, -3.0, -3.0]);
+    }
+
+    #[test]
+    fn test_lu_solve() {
+        let a = vec![
+            vec![0.0, 1.0, 2.0],
+            vec![1.0, 0.0, 1.0],
+            vec![2.0, 1.0, 0.0],
+        ];
+        let x = lu_solve(a, vec![8.0, 4.0, 4.0]).unwrap();
+        for (xi, ei) in x.iter().zip([1.0, 2.0, 3.0].iter()) {
+            assert_relative_eq!(*xi, *ei, epsilon = 1e-14);
+        }
+        let singular = vec![vec![1.0, 2.0], vec![2.0, 4.0]];
+        assert!(lu_solve(singular, vec![1.0, 1.0]).is_none());
+    }
+
+    #[test]
+    fn test_invert() {
+        let a = vec![vec![2.0, 5.0], vec![1.0, 3.0]];
+        let inv = invert(a).unwrap();
+        let target = [[3.0, -5.0], [-1.0, 2.0]];
+        for i in 0..2 {
+            for j in 0..2 {
+                assert_relative_eq!(inv[i][j], target[i][j], epsilon = 1e-14);
+            }
+        }
+        assert!(invert(vec![vec![1.0, 2.0], vec![2.0, 4.0]]).is_none());
+    }
+
+    #[test]
+    fn test_cholesky() {
+        let a = vec![
+            vec![4.0, 2.0, 2.0],
+            vec![2.0, 5.0, 3.0],
+            vec![2.0, 3.0, 6.0],
+        ];
+        let l = cholesky(&a).unwrap();
+        let target = [[2.0, 0.0, 0.0], [1.0, 2.0, 0.0], [1.0, 1.0, 2.0]];
+        for i in 0..3 {
+            for j in 0..3 {
+                assert_relative_eq!(l[i][j], target[i][j], epsilon = 1e-14);
+            }
+        }
+        // L L^T (1, -1, 2) = (6, 3, 11)
+        let x = chol_solve(&l, &[6.0, 3.0, 11.0]);
+        for (xi, ei) in x.iter().zip([1.0, -1.0, 2.0].iter()) {
+            assert_relative_eq!(*xi, *ei, epsilon = 1e-14);
+        }
+        let indefinite = vec![vec![1.0, 2.0], vec![2.0, 1.0]];
+        assert!(cholesky(&indefinite).is_none());
+        // eigenvalues of the indefinite matrix are -1 and 3
+        assert!(cholesky_shifted(&indefinite, 0.5).is_none());
+        assert!(cholesky_shifted(&indefinite, 1.5).is_some());
+    }
+
+    #[test]
+    fn test_modified_cholesky() {
+        let a = vec![vec![1.0, 0.0], vec![0.0, -1.0]];
+        let l = modified_cholesky(&a).unwrap();
+        let x = chol_solve(&l, &[1.0, 1.0]);
+        // a positive multiple of the identity has been added
+        assert!(x[0] > 0.0 && x[0] < 1.0);
+        assert!(x[1] > 0.0);
+
+        // positive definite matrices are not shifted
+        let a = vec![vec![4.0, 2.0], vec![2.0, 5.0]];
+        assert_eq!(modified_cholesky(&a), cholesky(&a));
+
+        for v in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert!(modified_cholesky(&[vec![1.0, 0.0], vec![0.0, v]]).is_none());
+            assert!(modified_cholesky(&[vec![1.0, v], vec![v, 1.0]]).is_none());
+        }
+    }
+}
@@ -0,0 +1,1013 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Differential Evolution (DE)
+//!
+//! Population-based, derivative-free optimization method which creates trial vectors from scaled
+//! differences of population members. Supports the classic mutation strategies as well as the
+//! self-adaptive parameter control of JADE and SHADE.
+//!
+//! See [`DifferentialEvolution`] for details.
+//!
+//! ## References
+//!
+//! \[0\] Rainer Storn and Kenneth Price (1997). Differential Evolution – A Simple and Efficient
+//! Heuristic for global Optimization over Continuous Spaces. Journal of Global Optimization 11,
+//! 341–359. DOI: <https://doi.org/10.1023/A:1008202821328>
+//!
+//! \[1\] Jingqiao Zhang and Arthur C. Sanderson (2009). JADE: Adaptive Differential Evolution
+//! With Optional External Archive. IEEE Transactions on Evolutionary Computation 13(5), 945–958.
+//! DOI: <https://doi.org/10.1109/TEVC.2009.2014613>
+//!
+//! \[2\] Ryoji Tanabe and Alex Fukunaga (2013). Success-History Based Parameter Adaptation for
+//! Differential Evolution. 2013 IEEE Congress on Evolutionary Computation.
+//! DOI: <https://doi.org/10.1109/CEC.2013.6557555>
+
+use crate::core::{
+    ArgminFloat, CostFunction, Error, PopulationState, Problem, Solver, SyncAlias, KV,
+};
+use crate::solver::random::standard_normal;
+use argmin_math::{
+    ArgminAdd, ArgminGet, ArgminMul, ArgminRandom, ArgminSet, ArgminSize, ArgminSub,
+};
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Mutation strategy of [`DifferentialEvolution`]
+///
+/// All strategies are combined with binomial crossover. `x_i` denotes the target vector,
+/// `x_best` the best member of the population and `x_r1`, `x_r2` and `x_r3` are distinct, randomly
+/// chosen members of the population which differ from the target vector.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum Strategy {
+    /// DE/rand/1/bin: `v = x_r1 + F (x_r2 - x_r3)`
+    Rand1Bin,
+    /// DE/best/1/bin: `v = x_best + F (x_r1 - x_r2)`
+    Best1Bin,
+    /// DE/current-to-best/1/bin: `v = x_i + F (x_best - x_i) + F (x_r1 - x_r2)`
+    ///
+    /// If a p-best fraction is set via
+    /// [`with_pbest_fraction`](`DifferentialEvolution::with_pbest_fraction`), `x_best` is chosen
+    /// randomly from the best members of the population (DE/current-to-pbest/1/bin).
+    CurrentToBest1Bin,
+}
+
+/// Control of mutation factor and crossover probability of [`DifferentialEvolution`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum ParameterAdaptation {
+    /// Mutation factor and crossover probability are fixed
+    Fixed,
+    /// Mutation factor and crossover probability of each trial vector are sampled around means
+    /// which are moved towards the values of successful trial vectors (JADE, without archive).
+    JADE,
+    /// Mutation factor and crossover probability of each trial vector are sampled around values
+    /// drawn from a history of successful parameters (SHADE, without archive). The history has as
+    /// many entries as the population has members.
+    SHADE,
+}
+
+/// Handling of trial vectors which violate the bounds
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum BoundsHandling {
+    /// Violating elements are set to the violated bound
+    Clip,
+    /// Violating elements are set to the midpoint between the violated bound and the
+    /// corresponding element of the target vector
+    Midpoint,
+    /// Violating elements are drawn uniformly from within the bounds
+    Reinitialize,
+}
+
+/// # Differential Evolution (DE)
+///
+/// In every iteration, a trial vector is created for each member (target vector) of the
+/// population by mutation according to the chosen [`Strategy`], followed by binomial crossover
+/// with the target vector: each element is taken from the mutant with the crossover probability
+/// `CR`, and at least one randomly chosen element is always taken from the mutant. Elements which
+/// violate the bounds are corrected according to [`BoundsHandling`]. A trial vector replaces its
+/// target vector if its cost function value is lower or equal.
+///
+/// The mutation factor `F` and the crossover probability `CR` are either fixed or adapted during
+/// the optimization as in JADE \[1\] or SHADE \[2\] (see [`ParameterAdaptation`]). In both adaptive
+/// schemes, `CR` is sampled from a normal distribution and `F` from a Cauchy distribution around
+/// the current means. The external archives of JADE and SHADE are not implemented. The (mean)
+/// mutation factor and crossover probability are reported as KV metrics.
+///
+/// The population is stored, sorted by cost, in [`PopulationState`]. All trial vectors of an
+/// iteration are evaluated via [`Problem::bulk_cost`], therefore the `rayon` feature enables
+/// parallel computation of the cost function. This can be beneficial for expensive cost
+/// functions, but may cause a drop in performance for cheap cost functions. Be sure to benchmark
+/// both parallel and sequential computation.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`].
+///
+/// Unlike [`ParticleSwarm`](`crate::solver::particleswarm::ParticleSwarm`), which only needs
+/// whole-vector operations, binomial crossover and bounds handling act on individual elements.
+/// Therefore the parameter vector `P` is additionally required to implement
+/// [`ArgminGet<usize, F>`](`argmin_math::ArgminGet`),
+/// [`ArgminSet<usize, F>`](`argmin_math::ArgminSet`) and
+/// [`ArgminSize<usize>`](`argmin_math::ArgminSize`), which are available for the `Vec`, `ndarray`
+/// and `nalgebra` backends of `argmin-math`.
+///
+/// ## References
+///
+/// \[0\] Rainer Storn and Kenneth Price (1997). Differential Evolution – A Simple and Efficient
+/// Heuristic for global Optimization over Continuous Spaces. Journal of Global Optimization 11,
+/// 341–359. DOI: <https://doi.org/10.1023/A:1008202821328>
+///
+/// \[1\] Jingqiao Zhang and Arthur C. Sanderson (2009). JADE: Adaptive Differential Evolution
+/// With Optional External Archive. IEEE Transactions on Evolutionary Computation 13(5), 945–958.
+/// DOI: <https://doi.org/10.1109/TEVC.2009.2014613>
+///
+/// \[2\] Ryoji Tanabe and Alex Fukunaga (2013). Success-History Based Parameter Adaptation for
+/// Differential Evolution. 2013 IEEE Congress on Evolutionary Computation.
+/// DOI: <https://doi.org/10.1109/CEC.2013.6557555>
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct DifferentialEvolution<P, F, R> {
+    /// Bounds on parameter space
+    bounds: (P, P),
+    /// Number of members of the population
+    population_size: usize,
+    /// Mutation strategy
+    strategy: Strategy,
+    /// Parameter adaptation
+    adaptation: ParameterAdaptation,
+    /// Bounds handling
+    bounds_handling: BoundsHandling,
+    /// Fraction of the population from which `x_best` is chosen in current-to-best
+    pbest_fraction: F,
+    /// Learning rate of JADE
+    learning_rate: F,
+    /// Mutation factors (one for `Fixed` and `JADE`, history for `SHADE`)
+    mutation_factor: Vec<F>,
+    /// Crossover probabilities (one for `Fixed` and `JADE`, history for `SHADE`)
+    crossover_probability: Vec<F>,
+    /// Position in the SHADE history which is updated next
+    history_index: usize,
+    /// Random number generator
+    rng_generator: R,
+}
+
+impl<P, F> DifferentialEvolution<P, F, rand::rngs::StdRng>
+where
+    P: Clone
+        + SyncAlias
+        + ArgminAdd<P, P>
+        + ArgminSub<P, P>
+        + ArgminMul<F, P>
+        + ArgminRandom
+        + ArgminGet<usize, F>
+        + ArgminSet<usize, F>
+        + ArgminSize<usize>,
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `DifferentialEvolution`
+    ///
+    /// Takes the bounds on the search space and the number of members of the population as
+    /// inputs. `bounds` is a tuple `(lower_bound, upper_bound)`, where `lower_bound` and
+    /// `upper_bound` are of the same type as the parameter vector (`P`).
+    ///
+    /// Defaults to the [`Strategy::Rand1Bin`] strategy with fixed mutation factor `0.8` and
+    /// crossover probability `0.9`, and to clipping of trial vectors at the bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::differentialevolution::DifferentialEvolution;
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let de: DifferentialEvolution<_, f64, _> =
+    ///     DifferentialEvolution::new((lower_bound, upper_bound), 40);
+    /// ```
+    pub fn new(bounds: (P, P), population_size: usize) -> Self {
+        DifferentialEvolution {
+            bounds,
+            population_size,
+            strategy: Strategy::Rand1Bin,
+            adaptation: ParameterAdaptation::Fixed,
+            bounds_handling: BoundsHandling::Clip,
+            pbest_fraction: float!(0.0),
+            learning_rate: float!(0.1),
+            mutation_factor: vec![float!(0.8)],
+            crossover_probability: vec![float!(0.9)],
+            history_index: 0,
+            rng_generator: rand::rngs::StdRng::from_entropy(),
+        }
+    }
+}
+
+impl<P, F, R0> DifferentialEvolution<P, F, R0>
+where
+    P: Clone
+        + SyncAlias
+        + ArgminAdd<P, P>
+        + ArgminSub<P, P>
+        + ArgminMul<F, P>
+        + ArgminRandom
+        + ArgminGet<usize, F>
+        + ArgminSet<usize, F>
+        + ArgminSize<usize>,
+    F: ArgminFloat,
+    R0: Rng,
+{
+    /// Set the random number generator
+    ///
+    /// Defaults to `rand::rngs::StdRng::from_entropy()`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::differentialevolution::DifferentialEvolution;
+    /// # use rand::SeedableRng;
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let de: DifferentialEvolution<_, f64, _> =
+    ///     DifferentialEvolution::new((lower_bound, upper_bound), 40)
+    ///     .with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(1729));
+    /// ```
+    pub fn with_rng_generator<R1: Rng>(self, generator: R1) -> DifferentialEvolution<P, F, R1> {
+        DifferentialEvolution {
+            bounds: self.bounds,
+            population_size: self.population_size,
+            strategy: self.strategy,
+            adaptation: self.adaptation,
+            bounds_handling: self.bounds_handling,
+            pbest_fraction: self.pbest_fraction,
+            learning_rate: self.learning_rate,
+            mutation_factor: self.mutation_factor,
+            crossover_probability: self.crossover_probability,
+            history_index: self.history_index,
+            rng_generator: generator,
+        }
+    }
+}
+
+impl<P, F, R> DifferentialEvolution<P, F, R>
+where
+    P: Clone
+        + SyncAlias
+        + ArgminAdd<P, P>
+        + ArgminSub<P, P>
+        + ArgminMul<F, P>
+        + ArgminRandom
+        + ArgminGet<usize, F>
+        + ArgminSet<usize, F>
+        + ArgminSize<usize>,
+    F: ArgminFloat,
+    R: Rng,
+{
+    /// Set the mutation strategy
+    ///
+    /// Defaults to [`Strategy::Rand1Bin`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::differentialevolution::{DifferentialEvolution, Strategy};
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let de: DifferentialEvolution<_, f64, _> =
+    ///     DifferentialEvolution::new((lower_bound, upper_bound), 40)
+    ///     .with_strategy(Strategy::Best1Bin);
+    /// ```
+    #[must_use]
+    pub fn with_strategy(mut self, strategy: Strategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Set the control of mutation factor and crossover probability
+    ///
+    /// Defaults to [`ParameterAdaptation::Fixed`]. For the adaptive schemes, the values set via
+    /// [`with_mutation_factor`](`DifferentialEvolution::with_mutation_factor`) and
+    /// [`with_crossover_probability`](`DifferentialEvolution::with_crossover_probability`) are
+    /// used as initial means. JADE and SHADE are usually combined with
+    /// [`Strategy::CurrentToBest1Bin`] and a p-best fraction of `0.05` to `0.2`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::differentialevolution::{DifferentialEvolution, ParameterAdaptation};
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let de: DifferentialEvolution<_, f64, _> =
+    ///     DifferentialEvolution::new((lower_bound, upper_bound), 40)
+    ///     .with_adaptation(ParameterAdaptation::SHADE);
+    /// ```
+    #[must_use]
+    pub fn with_adaptation(mut self, adaptation: ParameterAdaptation) -> Self {
+        self.adaptation = adaptation;
+        self
+    }
+
+    /// Set the handling of trial vectors which violate the bounds
+    ///
+    /// Defaults to [`BoundsHandling::Clip`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::differentialevolution::{BoundsHandling, DifferentialEvolution};
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let de: DifferentialEvolution<_, f64, _> =
+    ///     DifferentialEvolution::new((lower_bound, upper_bound), 40)
+    ///     .with_bounds_handling(BoundsHandling::Midpoint);
+    /// ```
+    #[must_use]
+    pub fn with_bounds_handling(mut self, bounds_handling: BoundsHandling) -> Self {
+        self.bounds_handling = bounds_handling;
+        self
+    }
+
+    /// Set the mutation factor `F`
+    ///
+    /// Must be in `(0, 2]`. Defaults to `0.8`. With parameter adaptation enabled, this is the
+    /// initial mean of the mutation factor.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::differentialevolution::DifferentialEvolution;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let de: DifferentialEvolution<_, f64, _> =
+    ///     DifferentialEvolution::new((lower_bound, upper_bound), 40).with_mutation_factor(0.5)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_mutation_factor(mut self, factor: F) -> Result<Self, Error> {
+        if factor <= float!(0.0) || factor > float!(2.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`DifferentialEvolution`: mutation factor must be in (0, 2]."
+            ));
+        }
+        self.mutation_factor = vec![factor];
+        Ok(self)
+    }
+
+    /// Set the crossover probability `CR`
+    ///
+    /// Must be in `[0, 1]`. Defaults to `0.9`. With parameter adaptation enabled, this is the
+    /// initial mean of the crossover probability.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::differentialevolution::DifferentialEvolution;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let de: DifferentialEvolution<_, f64, _> =
+    ///     DifferentialEvolution::new((lower_bound, upper_bound), 40)
+    ///     .with_crossover_probability(0.3)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_crossover_probability(mut self, probability: F) -> Result<Self, Error> {
+        if !(float!(0.0)..=float!(1.0)).contains(&probability) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`DifferentialEvolution`: crossover probability must be in [0, 1]."
+            ));
+        }
+        self.crossover_probability = vec![probability];
+        Ok(self)
+    }
+
+    /// Set the fraction of the population from which `x_best` is chosen in
+    /// [`Strategy::CurrentToBest1Bin`]
+    ///
+    /// Must be in `[0, 1]`. Defaults to `0`, which means that always the best member is chosen.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::differentialevolution::DifferentialEvolution;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let de: DifferentialEvolution<_, f64, _> =
+    ///     DifferentialEvolution::new((lower_bound, upper_bound), 40).with_pbest_fraction(0.1)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_pbest_fraction(mut self, fraction: F) -> Result<Self, Error> {
+        if !(float!(0.0)..=float!(1.0)).contains(&fraction) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`DifferentialEvolution`: p-best fraction must be in [0, 1]."
+            ));
+        }
+        self.pbest_fraction = fraction;
+        Ok(self)
+    }
+
+    /// Draws a uniformly distributed sample from `[0, 1)`
+    fn uniform(&mut self) -> F {
+        F::from_f64(self.rng_generator.gen::<f64>()).unwrap()
+    }
+
+    /// Draws a sample from a normal distribution
+    fn normal(&mut self, mean: F, std_dev: F) -> F {
+        mean + std_dev * standard_normal(&mut self.rng_generator)
+    }
+
+    /// Draws a sample from a Cauchy distribution
+    fn cauchy(&mut self, location: F, scale: F) -> F {
+        let u: f64 = self.rng_generator.gen::<f64>();
+        location + scale * F::from_f64((std::f64::consts::PI * (u - 0.5)).tan()).unwrap()
+    }
+
+    /// Returns mutation factor and crossover probability of a single trial vector
+    fn sample_parameters(&mut self) -> (F, F) {
+        let (mean_f, mean_cr) = match self.adaptation {
+            ParameterAdaptation::Fixed => {
+                return (self.mutation_factor[0], self.crossover_probability[0])
+            }
+            ParameterAdaptation::JADE => (self.mutation_factor[0], self.crossover_probability[0]),
+            ParameterAdaptation::SHADE => {
+                let r = self.rng_generator.gen_range(0..self.mutation_factor.len());
+                (self.mutation_factor[r], self.crossover_probability[r])
+            }
+        };
+        let cr = self
+            .normal(mean_cr, float!(0.1))
+            .max(float!(0.0))
+            .min(float!(1.0));
+        let mut f = self.cauchy(mean_f, float!(0.1));
+        while f <= float!(0.0) {
+            f = self.cauchy(mean_f, float!(0.1));
+        }
+        (f.min(float!(1.0)), cr)
+    }
+
+    /// Updates the means (JADE) or the history (SHADE) of mutation factor and crossover
+    /// probability with the parameters of successful trial vectors and the corresponding
+    /// improvements of the cost function
+    fn adapt_parameters(&mut self, successful: &[(F, F, F)]) {
+        if successful.is_empty() {
+            return;
+        }
+        let weights: Vec<F> = match self.adaptation {
+            ParameterAdaptation::Fixed => return,
+            ParameterAdaptation::JADE => vec![float!(1.0); successful.len()],
+            ParameterAdaptation::SHADE => successful.iter().map(|&(_, _, df)| df).collect(),
+        };
+        let weight_sum = weights.iter().fold(float!(0.0), |acc, &w| acc + w);
+        // all improvements may be zero if trial vectors have the same cost as the targets
+        let weights: Vec<F> = if weight_sum > float!(0.0) {
+            weights.into_iter().map(|w| w / weight_sum).collect()
+        } else {
+            vec![float!(1.0) / F::from_usize(successful.len()).unwrap(); successful.len()]
+        };
+        let (mut f_sq, mut f_sum, mut cr_sum) = (float!(0.0), float!(0.0), float!(0.0));
+        for (&w, &(f, cr, _)) in weights.iter().zip(successful.iter()) {
+            f_sq = f_sq + w * f * f;
+            f_sum = f_sum + w * f;
+            cr_sum = cr_sum + w * cr;
+        }
+        // Lehmer mean of the mutation factors, arithmetic mean of the crossover probabilities
+        let f_mean = f_sq / f_sum;
+        match self.adaptation {
+            ParameterAdaptation::JADE => {
+                let c = self.learning_rate;
+                self.mutation_factor[0] = (float!(1.0) - c) * self.mutation_factor[0] + c * f_mean;
+                self.crossover_probability[0] =
+                    (float!(1.0) - c) * self.crossover_probability[0] + c * cr_sum;
+            }
+            ParameterAdaptation::SHADE => {
+                let k = self.history_index;
+                self.mutation_factor[k] = f_mean;
+                self.crossover_probability[k] = cr_sum;
+                self.history_index = (k + 1) % self.mutation_factor.len();
+            }
+            ParameterAdaptation::Fixed => {}
+        }
+    }
+
+    /// Chooses `num` distinct indices of population members which differ from `exclude`
+    fn distinct_indices(&mut self, exclude: usize, num: usize) -> Vec<usize> {
+        let mut indices = Vec::with_capacity(num);
+        while indices.len() < num {
+            let idx = self.rng_generator.gen_range(0..self.population_size);
+            if idx != exclude && !indices.contains(&idx) {
+                indices.push(idx);
+            }
+        }
+        indices
+    }
+
+    /// Creates the trial vector for the target vector at index `i`. The population is sorted by
+    /// cost.
+    fn trial_vector(&mut self, population: &[Individual<P, F>], i: usize, f: F, cr: F) -> P {
+        let target = &population[i].position;
+        let mutant = match self.strategy {
+            Strategy::Rand1Bin => {
+                let r = self.distinct_indices(i, 3);
+                population[r[0]].position.add(
+                    &population[r[1]]
+                        .position
+                        .sub(&population[r[2]].position)
+                        .mul(&f),
+                )
+            }
+            Strategy::Best1Bin => {
+                let r = self.distinct_indices(i, 2);
+                population[0].position.add(
+                    &population[r[0]]
+                        .position
+                        .sub(&population[r[1]].position)
+                        .mul(&f),
+                )
+            }
+            Strategy::CurrentToBest1Bin => {
+                let num_best = (self.pbest_fraction * F::from_usize(self.population_size).unwrap())
+                    .round()
+                    .to_usize()
+                    .unwrap()
+                    .max(1);
+                let best = &population[self.rng_generator.gen_range(0..num_best)].position;
+                let r = self.distinct_indices(i, 2);
+                target.add(
+                    &best
+                        .sub(target)
+                        .add(&population[r[0]].position.sub(&population[r[1]].position))
+                        .mul(&f),
+                )
+            }
+        };
+
+        // binomial crossover
+        let n = target.shape();
+        let j_rand = self.rng_generator.gen_range(0..n);
+        let mut trial = target.clone();
+        for j in 0..n {
+            if j == j_rand || self.uniform() < cr {
+                trial.set(j, mutant.get(j));
+            }
+        }
+
+        // bounds handling
+        for j in 0..n {
+            let (lower, upper) = (self.bounds.0.get(j), self.bounds.1.get(j));
+            let value = trial.get(j);
+            if value >= lower && value <= upper {
+                continue;
+            }
+            let bound = if value < lower { lower } else { upper };
+            let corrected = match self.bounds_handling {
+                BoundsHandling::Clip => bound,
+                BoundsHandling::Midpoint => (bound + target.get(j)) / float!(2.0),
+                BoundsHandling::Reinitialize => lower + self.uniform() * (upper - lower),
+            };
+            trial.set(j, corrected);
+        }
+        trial
+    }
+}
+
+impl<O, P, F, R> Solver<O, PopulationState<Individual<P, F>, F>> for DifferentialEvolution<P, F, R>
+where
+    O: CostFunction<Param = P, Output = F> + SyncAlias,
+    P: Clone
+        + SyncAlias
+        + ArgminAdd<P, P>
+        + ArgminSub<P, P>
+        + ArgminMul<F, P>
+        + ArgminRandom
+        + ArgminGet<usize, F>
+        + ArgminSet<usize, F>
+        + ArgminSize<usize>,
+    F: ArgminFloat,
+    R: Rng,
+{
+    const NAME: &'static str = "Differential Evolution";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: PopulationState<Individual<P, F>, F>,
+    ) -> Result<(PopulationState<Individual<P, F>, F>, Option<KV>), Error> {
+        if self.population_size < 4 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`DifferentialEvolution`: population size must be >= 4."
+            ));
+        }
+        if self.bounds.0.shape() != self.bounds.1.shape() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`DifferentialEvolution`: lower and upper bound must have the same length."
+            ));
+        }
+
+        if self.adaptation == ParameterAdaptation::SHADE {
+            self.mutation_factor = vec![self.mutation_factor[0]; self.population_size];
+            self.crossover_probability = vec![self.crossover_probability[0]; self.population_size];
+            self.history_index = 0;
+        }
+
+        // Users can provide a population or it will be randomly created.
+        let mut population = match state.take_population() {
+            Some(population) if population.len() == self.population_size => population,
+            Some(population) => {
+                return Err(argmin_error!(
+                    InvalidParameter,
+                    format!(
+                        "`DifferentialEvolution`: Provided population is of length {}, expected {}",
+                        population.len(),
+                        self.population_size
+                    )
+                ))
+            }
+            None => {
+                let (lower, upper) = &self.bounds;
+                let positions: Vec<P> = (0..self.population_size)
+                    .map(|_| P::rand_from_range(lower, upper, &mut self.rng_generator))
+                    .collect();
+                let costs = problem.bulk_cost(&positions)?;
+                positions
+                    .into_iter()
+                    .zip(costs)
+                    .map(|(p, c)| Individual::new(p, c))
+                    .collect()
+            }
+        };
+
+        // sort them, such that the first one is the best one
+        population.sort_by(|a, b| {
+            a.cost
+                .partial_cmp(&b.cost)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        Ok((
+            state
+                .individual(population[0].clone())
+                .cost(population[0].cost)
+                .population(population),
+            None,
+        ))
+    }
+
+    /// Perform one iteration of algorithm
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: PopulationState<Individual<P, F>, F>,
+    ) -> Result<(PopulationState<Individual<P, F>, F>, Option<KV>), Error> {
+        let mut population = state.take_population().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`DifferentialEvolution`: No population in state."
+        ))?;
+
+        let (parameters, trials): (Vec<(F, F)>, Vec<P>) = (0..population.len())
+            .map(|i| {
+                let (f, cr) = self.sample_parameters();
+                ((f, cr), self.trial_vector(&population, i, f, cr))
+            })
+            .unzip();
+
+        let costs = problem.bulk_cost(&trials)?;
+
+        let mut successful = vec![];
+        for (((member, trial), cost), (f, cr)) in
+            population.iter_mut().zip(trials).zip(costs).zip(parameters)
+        {
+            if cost <= member.cost {
+                if cost < member.cost {
+                    successful.push((f, cr, member.cost - cost));
+                }
+                member.position = trial;
+                member.cost = cost;
+            }
+        }
+        self.adapt_parameters(&successful);
+
+        // sort them, such that the first one is the best one
+        population.sort_by(|a, b| {
+            a.cost
+                .partial_cmp(&b.cost)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let len = F::from_usize(self.mutation_factor.len()).unwrap();
+        let mutation_factor = self
+            .mutation_factor
+            .iter()
+            .fold(float!(0.0), |acc, &f| acc + f)
+            / len;
+        let crossover_probability = self
+            .crossover_probability
+            .iter()
+            .fold(float!(0.0), |acc, &cr| acc + cr)
+            / len;
+
+        Ok((
+            state
+                .individual(population[0].clone())
+                .cost(population[0].cost)
+                .population(population),
+            Some(kv!(
+                "mutation_factor" => mutation_factor;
+                "crossover_probability" => crossover_probability;
+            )),
+        ))
+    }
+}
+
+/// A single member of the population
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Individual<T, F> {
+    /// Position of the member
+    pub position: T,
+    /// Cost of the member
+    pub cost: F,
+}
+
+impl<T, F> Individual<T, F> {
+    /// Create a new member of the population with a given position and cost.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::differentialevolution::Individual;
+    /// let individual: Individual<Vec<f64>, f64> = Individual::new(vec![0.0, 1.4], 12.0);
+    /// ```
+    pub fn new(position: T, cost: F) -> Individual<T, F> {
+        Individual { position, cost }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{test_utils::TestProblem, ArgminError, Executor, State};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+
+    test_trait_impl!(
+        differentialevolution,
+        DifferentialEvolution<Vec<f64>, f64, rand::rngs::StdRng>
+    );
+
+    struct Rosenbrock {}
+
+    impl CostFunction for Rosenbrock {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(argmin_testfunctions::rosenbrock(p, 1.0, 100.0))
+        }
+    }
+
+    fn solver(
+        strategy: Strategy,
+    ) -> DifferentialEvolution<Vec<f64>, f64, rand_xoshiro::Xoroshiro128Plus> {
+        DifferentialEvolution::new((vec![-5.0, -5.0, -5.0], vec![5.0, 5.0, 5.0]), 30)
+            .with_strategy(strategy)
+            .with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(1729))
+    }
+
+    #[test]
+    fn test_new() {
+        let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+        let upper_bound: Vec<f64> = vec![1.0, 1.0];
+        let de: DifferentialEvolution<_, f64, rand::rngs::StdRng> =
+            DifferentialEvolution::new((lower_bound.clone(), upper_bound.clone()), 40);
+        let DifferentialEvolution {
+            bounds,
+            population_size,
+            strategy,
+            adaptation,
+            bounds_handling,
+            pbest_fraction,
+            learning_rate,
+            mutation_factor,
+            crossover_probability,
+            history_index,
+            ..
+        } = de;
+
+        assert_eq!(bounds, (lower_bound, upper_bound));
+        assert_eq!(population_size, 40);
+        assert_eq!(strategy, Strategy::Rand1Bin);
+        assert_eq!(adaptation, ParameterAdaptation::Fixed);
+        assert_eq!(bounds_handling, BoundsHandling::Clip);
+        assert_eq!(pbest_fraction.to_ne_bytes(), 0.0f64.to_ne_bytes());
+        assert_eq!(learning_rate.to_ne_bytes(), 0.1f64.to_ne_bytes());
+        assert_eq!(mutation_factor.len(), 1);
+        assert_eq!(mutation_factor[0].to_ne_bytes(), 0.8f64.to_ne_bytes());
+        assert_eq!(crossover_probability.len(), 1);
+        assert_eq!(crossover_probability[0].to_ne_bytes(), 0.9f64.to_ne_bytes());
+        assert_eq!(history_index, 0);
+    }
+
+    #[test]
+    fn test_builder() {
+        let de = solver(Strategy::Best1Bin)
+            .with_adaptation(ParameterAdaptation::JADE)
+            .with_bounds_handling(BoundsHandling::Reinitialize)
+            .with_mutation_factor(0.5)
+            .unwrap()
+            .with_crossover_probability(0.3)
+            .unwrap()
+            .with_pbest_fraction(0.1)
+            .unwrap();
+        assert_eq!(de.strategy, Strategy::Best1Bin);
+        assert_eq!(de.adaptation, ParameterAdaptation::JADE);
+        assert_eq!(de.bounds_handling, BoundsHandling::Reinitialize);
+        assert_eq!(de.mutation_factor[0].to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert_eq!(
+            de.crossover_probability[0].to_ne_bytes(),
+            0.3f64.to_ne_bytes()
+        );
+        assert_eq!(de.pbest_fraction.to_ne_bytes(), 0.1f64.to_ne_bytes());
+
+        for factor in [0.0, -1.0, 2.1] {
+            assert_error!(
+                solver(Strategy::Rand1Bin).with_mutation_factor(factor),
+                ArgminError,
+                "Invalid parameter: \"`DifferentialEvolution`: mutation factor must be in (0, 2].\""
+            );
+        }
+        for probability in [-0.1, 1.1] {
+            assert_error!(
+                solver(Strategy::Rand1Bin).with_crossover_probability(probability),
+                ArgminError,
+                "Invalid parameter: \"`DifferentialEvolution`: crossover probability must be in [0, 1].\""
+            );
+            assert_error!(
+                solver(Strategy::Rand1Bin).with_pbest_fraction(probability),
+                ArgminError,
+                "Invalid parameter: \"`DifferentialEvolution`: p-best fraction must be in [0, 1].\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_init_errors() {
+        let mut de: DifferentialEvolution<_, f64, _> =
+            DifferentialEvolution::new((vec![-1.0, -1.0], vec![1.0, 1.0]), 3);
+        let res = de.init(
+            &mut Problem::new(TestProblem::new()),
+            PopulationState::new(),
+        );
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`DifferentialEvolution`: population size must be >= 4.\""
+        );
+
+        let mut de: DifferentialEvolution<_, f64, _> =
+            DifferentialEvolution::new((vec![-1.0, -1.0], vec![1.0]), 10);
+        let res = de.init(
+            &mut Problem::new(TestProblem::new()),
+            PopulationState::new(),
+        );
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`DifferentialEvolution`: lower and upper bound must have the same length.\""
+        );
+
+        let mut de: DifferentialEvolution<_, f64, _> =
+            DifferentialEvolution::new((vec![-1.0, -1.0], vec![1.0, 1.0]), 10);
+        let state =
+            PopulationState::new().population(vec![Individual::new(vec![0.0, 0.0], 1.0); 4]);
+        let res = de.init(&mut Problem::new(TestProblem::new()), state);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`DifferentialEvolution`: Provided population is of length 4, expected 10\""
+        );
+    }
+
+    #[test]
+    fn test_init_provided_population() {
+        let mut de: DifferentialEvolution<_, f64, _> =
+            DifferentialEvolution::new((vec![-1.0, -1.0], vec![1.0, 1.0]), 4);
+        let population = vec![
+            Individual::new(vec![0.0, 0.0], 3.0),
+            Individual::new(vec![0.1, 0.0], 1.0),
+            Individual::new(vec![0.2, 0.0], 4.0),
+            Individual::new(vec![0.3, 0.0], 2.0),
+        ];
+        let state = PopulationState::new().population(population);
+        let mut problem = Problem::new(TestProblem::new());
+        let (mut state, _) = de.init(&mut problem, state).unwrap();
+        assert_eq!(state.get_cost().to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(state.get_param().unwrap().position, vec![0.1, 0.0]);
+        let costs: Vec<f64> = state
+            .take_population()
+            .unwrap()
+            .iter()
+            .map(|i| i.cost)
+            .collect();
+        assert_eq!(costs, vec![1.0, 2.0, 3.0, 4.0]);
+        assert!(!problem.counts.contains_key("cost_count"));
+    }
+
+    #[test]
+    fn test_next_iter_bounds() {
+        for bounds_handling in [
+            BoundsHandling::Clip,
+            BoundsHandling::Midpoint,
+            BoundsHandling::Reinitialize,
+        ] {
+            let mut de = solver(Strategy::Rand1Bin)
+                .with_mutation_factor(2.0)
+                .unwrap()
+                .with_bounds_handling(bounds_handling);
+            let mut problem = Problem::new(Rosenbrock {});
+            let (mut state, _) = de.init(&mut problem, PopulationState::new()).unwrap();
+            for _ in 0..50 {
+                let best_cost = state.get_cost();
+                (state, _) = de.next_iter(&mut problem, state).unwrap();
+                assert!(state.get_cost() <= best_cost);
+                let population = state.get_population().unwrap();
+                assert_eq!(population.len(), 30);
+                for member in population {
+                    for x in member.position.iter() {
+                        assert!(*x <= 5.0);
+                        assert!(*x >= -5.0);
+                    }
+                }
+            }
+            assert_eq!(problem.counts["cost_count"], 30 * 51);
+        }
+    }
+
+    #[test]
+    fn test_strategies() {
+        for strategy in [
+            Strategy::Rand1Bin,
+            Strategy::Best1Bin,
+            Strategy::CurrentToBest1Bin,
+        ] {
+            let res = Executor::new(Rosenbrock {}, solver(strategy))
+                .configure(|state| state.max_iters(2000).target_cost(1e-10))
+                .run()
+                .unwrap();
+            for x in res.state.get_best_param().unwrap().position.iter() {
+                assert_relative_eq!(*x, 1.0, epsilon = 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_adaptation() {
+        for adaptation in [ParameterAdaptation::JADE, ParameterAdaptation::SHADE] {
+            let solver = solver(Strategy::CurrentToBest1Bin)
+                .with_adaptation(adaptation)
+                .with_mutation_factor(0.5)
+                .unwrap()
+                .with_crossover_probability(0.5)
+                .unwrap()
+                .with_pbest_fraction(0.1)
+                .unwrap();
+            let res = Executor::new(Rosenbrock {}, solver)
+                .configure(|state| state.max_iters(2000).target_cost(1e-10))
+                .run()
+                .unwrap();
+            for x in res.state.get_best_param().unwrap().position.iter() {
+                assert_relative_eq!(*x, 1.0, epsilon = 1e-4);
+            }
+            let expected_len = match adaptation {
+                ParameterAdaptation::SHADE => 30,
+                _ => 1,
+            };
+            assert_eq!(res.solver.mutation_factor.len(), expected_len);
+            assert_eq!(res.solver.crossover_probability.len(), expected_len);
+            for (f, cr) in res
+                .solver
+                .mutation_factor
+                .iter()
+                .zip(res.solver.crossover_probability.iter())
+            {
+                assert!(*f > 0.0 && *f <= 1.0);
+                assert!((0.0..=1.0).contains(cr));
+            }
+        }
+    }
+}
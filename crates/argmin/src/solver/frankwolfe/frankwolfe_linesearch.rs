@@ -0,0 +1,417 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::{duality_gap, Core, FrankWolfeVariant, Step};
+use crate::core::{
+    ArgminFloat, CostFunction, Error, Executor, Gradient, IterState, LineSearch,
+    LinearMinimizationOracle, OptimizationResult, Problem, Solver, State, TerminationReason, KV,
+};
+use argmin_math::{ArgminDot, ArgminScaledAdd, ArgminSub};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Frank-Wolfe method with line search
+///
+/// Frank-Wolfe method (see [`FrankWolfe`](`super::FrankWolfe`)) where the step length is obtained
+/// by a line search along the search direction instead of the standard step length schedule. The
+/// line search starts with the largest feasible step length (`1` for steps towards the vertex
+/// returned by the linear minimization oracle). Step lengths beyond it are capped, such that any
+/// of the line searches in [`linesearch`](`crate::solver::linesearch`) can be used.
+///
+/// With the away-step and pairwise variants (see [`with_variant`](`FrankWolfeLS::with_variant`)),
+/// a line search yields linear convergence for strongly convex functions over polytopes.
+///
+/// The algorithm stops once the Frank-Wolfe duality gap is below the tolerance set via
+/// [`with_tolerance`](`FrankWolfeLS::with_tolerance`). The duality gap, the step length, the kind
+/// of step and the number of active vertices are reported as KV metrics `gap`, `step_length`,
+/// `step` and `vertices`. The smallest attainable duality gap is limited by the smallest step
+/// length the line search is able to take.
+///
+/// Requires a feasible initial parameter vector.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`], [`Gradient`] and
+/// [`LinearMinimizationOracle`].
+///
+/// ## References
+///
+/// Martin Jaggi (2013). Revisiting Frank-Wolfe: Projection-Free Sparse Convex Optimization.
+/// International Conference on Machine Learning.
+///
+/// Simon Lacoste-Julien and Martin Jaggi (2015). On the Global Linear Convergence of Frank-Wolfe
+/// Optimization Variants. Advances in Neural Information Processing Systems.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct FrankWolfeLS<L, P, F> {
+    /// line search
+    linesearch: L,
+    /// Direction, duality gap and active vertices
+    core: Core<P, F>,
+}
+
+impl<L, P, F> FrankWolfeLS<L, P, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`FrankWolfeLS`]
+    ///
+    /// Requires a line search.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::frankwolfe::FrankWolfeLS;
+    /// # let linesearch = ();
+    /// let fw: FrankWolfeLS<_, Vec<f64>, f64> = FrankWolfeLS::new(linesearch);
+    /// ```
+    pub fn new(linesearch: L) -> Self {
+        FrankWolfeLS {
+            linesearch,
+            core: Core::new(),
+        }
+    }
+
+    /// Set the variant
+    ///
+    /// Defaults to [`FrankWolfeVariant::Standard`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::frankwolfe::{FrankWolfeLS, FrankWolfeVariant};
+    /// # let linesearch = ();
+    /// let fw: FrankWolfeLS<_, Vec<f64>, f64> =
+    ///     FrankWolfeLS::new(linesearch).with_variant(FrankWolfeVariant::Pairwise);
+    /// ```
+    #[must_use]
+    pub fn with_variant(mut self, variant: FrankWolfeVariant) -> Self {
+        self.core.variant = variant;
+        self
+    }
+
+    /// Set tolerance on the duality gap
+    ///
+    /// Must be non-negative and defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::frankwolfe::FrankWolfeLS;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let fw: FrankWolfeLS<_, Vec<f64>, f64> = FrankWolfeLS::new(linesearch).with_tolerance(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        self.core.set_tolerance("FrankWolfeLS", tol)?;
+        Ok(self)
+    }
+}
+
+impl<O, L, P, G, F> Solver<O, IterState<P, G, (), (), (), F>> for FrankWolfeLS<L, P, F>
+where
+    O: CostFunction<Param = P, Output = F>
+        + Gradient<Param = P, Gradient = G>
+        + LinearMinimizationOracle<Param = P, Gradient = G>,
+    P: Clone + PartialEq + ArgminSub<P, P> + ArgminScaledAdd<P, F, P> + ArgminDot<P, F>,
+    G: ArgminDot<P, F>,
+    L: Clone + LineSearch<P, F> + Solver<O, IterState<P, G, (), (), (), F>>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Frank-Wolfe method with line search";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, G, (), (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), (), F>, Option<KV>), Error> {
+        let param = state.get_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`FrankWolfeLS` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let cost = problem.cost(param)?;
+        if self.core.tracks_vertices() {
+            self.core.init_vertices(param.clone());
+        } else {
+            self.core.vertices = vec![];
+        }
+        Ok((state.cost(cost), None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, (), (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`FrankWolfeLS`: Parameter vector in state not set."
+        ))?;
+        let cost = state.get_cost();
+        let grad = problem.gradient(&param)?;
+        let vertex = problem.lmo(&grad)?;
+
+        let gap = duality_gap(&param, &grad, &vertex);
+        if gap <= self.core.tol {
+            return Ok((
+                state
+                    .param(param)
+                    .terminate_with(TerminationReason::SolverConverged),
+                Some(kv!("gap" => gap;)),
+            ));
+        }
+
+        let Step {
+            direction,
+            max_step_length,
+            kind,
+        } = self.core.step(&param, &grad, vertex);
+
+        self.linesearch.search_direction(direction.clone());
+        self.linesearch.initial_step_length(max_step_length)?;
+
+        // Run line search
+        let OptimizationResult {
+            problem: line_problem,
+            state: mut linesearch_state,
+            ..
+        } = Executor::new(
+            problem.take_problem().ok_or_else(argmin_error_closure!(
+                PotentialBug,
+                "`FrankWolfeLS`: Failed to take `problem` for line search"
+            ))?,
+            self.linesearch.clone(),
+        )
+        .configure(|config| config.param(param.clone()).gradient(grad).cost(cost))
+        .ctrlc(false)
+        .run()?;
+
+        // Get back problem and function evaluation counts
+        problem.consume_problem(line_problem);
+
+        let linesearch_param = linesearch_state
+            .take_param()
+            .ok_or_else(argmin_error_closure!(
+                PotentialBug,
+                "`FrankWolfeLS`: No `param` returned by line search"
+            ))?;
+
+        // The line search moves along `direction`, hence the step length follows from the
+        // projection onto `direction`.
+        let norm_sq = direction.dot(&direction);
+        let step_length = if norm_sq > float!(0.0) {
+            (linesearch_param.sub(&param).dot(&direction) / norm_sq).max(float!(0.0))
+        } else {
+            float!(0.0)
+        };
+        let (new_param, new_cost, step_length) = if step_length > max_step_length {
+            let new_param = param.scaled_add(&max_step_length, &direction);
+            let new_cost = problem.cost(&new_param)?;
+            (new_param, new_cost, max_step_length)
+        } else {
+            (linesearch_param, linesearch_state.get_cost(), step_length)
+        };
+
+        let step = kind.name().to_string();
+        self.core
+            .update_vertices(kind, step_length, max_step_length);
+
+        let kv = if self.core.variant == FrankWolfeVariant::Standard {
+            kv!("gap" => gap; "step_length" => step_length; "step" => step;)
+        } else {
+            kv!(
+                "gap" => gap;
+                "step_length" => step_length;
+                "step" => step;
+                "vertices" => self.core.vertices.len() as u64;
+            )
+        };
+        Ok((state.param(new_param).cost(new_cost), Some(kv)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use crate::solver::frankwolfe::tests::SimplexProjection;
+    use crate::solver::frankwolfe::FrankWolfe;
+    use crate::solver::linesearch::{
+        condition::ArmijoCondition, BacktrackingLineSearch, MoreThuenteLineSearch,
+    };
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(
+        frank_wolfe_ls,
+        FrankWolfeLS<MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64>, Vec<f64>, f64>
+    );
+
+    #[test]
+    fn test_new() {
+        let linesearch: BacktrackingLineSearch<Vec<f64>, Vec<f64>, ArmijoCondition<f64>, f64> =
+            BacktrackingLineSearch::new(ArmijoCondition::new(0.2).unwrap());
+        let FrankWolfeLS {
+            linesearch: ls,
+            core,
+        } = FrankWolfeLS::<_, Vec<f64>, f64>::new(linesearch.clone());
+        assert_eq!(ls, linesearch);
+        assert_eq!(core.variant, FrankWolfeVariant::Standard);
+        assert_eq!(core.tol.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert!(core.vertices.is_empty());
+    }
+
+    #[test]
+    fn test_setters() {
+        let fw = FrankWolfeLS::<_, Vec<f64>, f64>::new(())
+            .with_variant(FrankWolfeVariant::AwayStep)
+            .with_tolerance(1e-3)
+            .unwrap();
+        assert_eq!(fw.core.variant, FrankWolfeVariant::AwayStep);
+        assert_eq!(fw.core.tol.to_ne_bytes(), 1e-3f64.to_ne_bytes());
+
+        assert_error!(
+            FrankWolfeLS::<_, Vec<f64>, f64>::new(()).with_tolerance(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`FrankWolfeLS`: tolerance must be >= 0.\""
+        );
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let linesearch: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> =
+            MoreThuenteLineSearch::new();
+        let mut fw = FrankWolfeLS::new(linesearch);
+        let res = fw.init(
+            &mut Problem::new(SimplexProjection::new()),
+            IterState::new(),
+        );
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`FrankWolfeLS` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    /// The projection of `y` onto the simplex is `(0.4, 0.3, 0.2, 0.1, 0, ..., 0)`, which lies in
+    /// the relative interior of a face. Starting from a vertex outside of this face, the standard
+    /// variant zig-zags towards the solution.
+    fn face_projection() -> (SimplexProjection, Vec<f64>) {
+        let mut y = vec![-0.1; 10];
+        y[..4].copy_from_slice(&[0.5, 0.4, 0.3, 0.2]);
+        let mut x0 = vec![0.0; 10];
+        x0[9] = 1.0;
+        (SimplexProjection { y }, x0)
+    }
+
+    #[test]
+    fn test_variants() {
+        let (problem, x0) = face_projection();
+        let solve = |variant| {
+            let linesearch: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> =
+                MoreThuenteLineSearch::new();
+            Executor::new(
+                problem.clone(),
+                FrankWolfeLS::new(linesearch)
+                    .with_variant(variant)
+                    .with_tolerance(1e-7)
+                    .unwrap(),
+            )
+            .configure(|state| state.param(x0.clone()).max_iters(1000))
+            .run()
+            .unwrap()
+        };
+
+        let mut iters = vec![];
+        for variant in [
+            FrankWolfeVariant::Standard,
+            FrankWolfeVariant::AwayStep,
+            FrankWolfeVariant::Pairwise,
+        ] {
+            let res = solve(variant);
+            let x = res.state().get_best_param().unwrap();
+            assert_relative_eq!(x.iter().sum::<f64>(), 1.0, epsilon = 1e-12);
+            assert!(x.iter().all(|&xi| xi >= 0.0));
+            if variant != FrankWolfeVariant::Standard {
+                // Linear convergence: the duality gap tolerance is reached
+                assert_eq!(
+                    res.state().get_termination_reason(),
+                    Some(&TerminationReason::SolverConverged)
+                );
+                for (xi, ei) in x
+                    .iter()
+                    .zip([0.4, 0.3, 0.2, 0.1].iter().chain([0.0; 6].iter()))
+                {
+                    assert_relative_eq!(xi, ei, epsilon = 1e-6);
+                }
+            }
+            iters.push(res.state().get_iter());
+        }
+        assert!(iters[1] < iters[0]);
+        assert!(iters[2] < iters[0]);
+    }
+
+    #[test]
+    fn test_backtracking() {
+        let linesearch: BacktrackingLineSearch<Vec<f64>, Vec<f64>, ArmijoCondition<f64>, f64> =
+            BacktrackingLineSearch::new(ArmijoCondition::new(1e-4).unwrap());
+        let res = Executor::new(
+            SimplexProjection::new(),
+            FrankWolfeLS::new(linesearch)
+                .with_variant(FrankWolfeVariant::Pairwise)
+                .with_tolerance(1e-8)
+                .unwrap(),
+        )
+        .configure(|state| state.param(vec![0.0, 0.0, 1.0]).max_iters(1000))
+        .run()
+        .unwrap();
+        let x = res.state().get_best_param().unwrap();
+        assert_relative_eq!(x[0], 0.6, epsilon = 1e-4);
+        assert_relative_eq!(x[1], 0.4, epsilon = 1e-4);
+        assert_relative_eq!(x[2], 0.0, epsilon = 1e-4);
+        // The cost function value is tracked
+        assert_relative_eq!(res.state().get_best_cost(), 0.12, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_line_search_faster_than_schedule() {
+        let (problem, x0) = face_projection();
+        let linesearch: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> =
+            MoreThuenteLineSearch::new();
+        let with_linesearch = Executor::new(
+            problem.clone(),
+            FrankWolfeLS::new(linesearch).with_tolerance(1e-7).unwrap(),
+        )
+        .configure(|state| state.param(x0.clone()).max_iters(100))
+        .run()
+        .unwrap();
+        let schedule = Executor::new(
+            problem,
+            FrankWolfe::<_, f64>::new().with_tolerance(1e-7).unwrap(),
+        )
+        .configure(|state| state.param(x0).max_iters(100))
+        .run()
+        .unwrap();
+        assert_eq!(
+            with_linesearch.state().get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        assert_eq!(
+            schedule.state().get_termination_reason(),
+            Some(&TerminationReason::MaxItersReached)
+        );
+    }
+}
@@ -0,0 +1,312 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::{duality_gap, Core, FrankWolfeVariant, Step};
+use crate::core::{
+    ArgminFloat, Error, Gradient, IterState, LinearMinimizationOracle, Problem, Solver, State,
+    TerminationReason, KV,
+};
+use argmin_math::{ArgminDot, ArgminScaledAdd, ArgminSub};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Frank-Wolfe method
+///
+/// Minimizes a smooth convex function over a compact convex set `C` which is only accessed via a
+/// linear minimization oracle (LMO). In each iteration, the LMO returns the vertex
+///
+/// ```text
+/// s_k = argmin_{s in C} <grad f(x_k), s>
+/// ```
+///
+/// and the parameter vector moves towards it with the standard step length schedule:
+///
+/// ```text
+/// x_{k+1} = x_k + gamma_k (s_k - x_k),   gamma_k = 2 / (k + 2)
+/// ```
+///
+/// Since `x_{k+1}` is a convex combination of feasible points, no projection is required.
+///
+/// The away-step and pairwise variants (see [`with_variant`](`FrankWolfe::with_variant`) and
+/// [`FrankWolfeVariant`]) represent the parameter vector as a convex combination of the initial
+/// parameter vector and the vertices returned by the LMO. Away steps move away from the active
+/// vertex `v` with the largest `<grad f(x_k), v>`, whereas pairwise steps move weight from `v` to
+/// `s_k`. This avoids the zig-zagging of the standard variant when the solution lies on a face of
+/// `C`. The step lengths are capped such that the weights stay non-negative. The linear
+/// convergence of these variants requires a line search, see [`FrankWolfeLS`](`super::FrankWolfeLS`).
+///
+/// The algorithm stops once the Frank-Wolfe duality gap `<grad f(x_k), x_k - s_k>`, an upper
+/// bound on `f(x_k) - f(x*)`, is below the tolerance set via
+/// [`with_tolerance`](`FrankWolfe::with_tolerance`). The duality gap, the step length, the kind of
+/// step and the number of active vertices are reported as KV metrics `gap`, `step_length`, `step`
+/// and `vertices`. The cost function is never evaluated.
+///
+/// Requires a feasible initial parameter vector.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`Gradient`] and
+/// [`LinearMinimizationOracle`].
+///
+/// ## References
+///
+/// Martin Jaggi (2013). Revisiting Frank-Wolfe: Projection-Free Sparse Convex Optimization.
+/// International Conference on Machine Learning.
+///
+/// Simon Lacoste-Julien and Martin Jaggi (2015). On the Global Linear Convergence of Frank-Wolfe
+/// Optimization Variants. Advances in Neural Information Processing Systems.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct FrankWolfe<P, F> {
+    /// Direction, duality gap and active vertices
+    core: Core<P, F>,
+}
+
+impl<P, F> FrankWolfe<P, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`FrankWolfe`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::frankwolfe::FrankWolfe;
+    /// let fw: FrankWolfe<Vec<f64>, f64> = FrankWolfe::new();
+    /// ```
+    pub fn new() -> Self {
+        FrankWolfe { core: Core::new() }
+    }
+
+    /// Set the variant
+    ///
+    /// Defaults to [`FrankWolfeVariant::Standard`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::frankwolfe::{FrankWolfe, FrankWolfeVariant};
+    /// let fw: FrankWolfe<Vec<f64>, f64> = FrankWolfe::new().with_variant(FrankWolfeVariant::AwayStep);
+    /// ```
+    #[must_use]
+    pub fn with_variant(mut self, variant: FrankWolfeVariant) -> Self {
+        self.core.variant = variant;
+        self
+    }
+
+    /// Set tolerance on the duality gap
+    ///
+    /// Must be non-negative and defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::frankwolfe::FrankWolfe;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let fw: FrankWolfe<Vec<f64>, f64> = FrankWolfe::new().with_tolerance(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        self.core.set_tolerance("FrankWolfe", tol)?;
+        Ok(self)
+    }
+}
+
+impl<P, F> Default for FrankWolfe<P, F>
+where
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        FrankWolfe::new()
+    }
+}
+
+impl<O, P, G, F> Solver<O, IterState<P, G, (), (), (), F>> for FrankWolfe<P, F>
+where
+    O: Gradient<Param = P, Gradient = G> + LinearMinimizationOracle<Param = P, Gradient = G>,
+    P: Clone + PartialEq + ArgminSub<P, P> + ArgminScaledAdd<P, F, P>,
+    G: ArgminDot<P, F>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Frank-Wolfe method";
+
+    fn init(
+        &mut self,
+        _problem: &mut Problem<O>,
+        state: IterState<P, G, (), (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), (), F>, Option<KV>), Error> {
+        let param = state.get_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`FrankWolfe` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        if self.core.tracks_vertices() {
+            self.core.init_vertices(param.clone());
+        } else {
+            self.core.vertices = vec![];
+        }
+        Ok((state, None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, (), (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`FrankWolfe`: Parameter vector in state not set."
+        ))?;
+        let grad = problem.gradient(&param)?;
+        let vertex = problem.lmo(&grad)?;
+
+        let gap = duality_gap(&param, &grad, &vertex);
+        if gap <= self.core.tol {
+            return Ok((
+                state
+                    .param(param)
+                    .terminate_with(TerminationReason::SolverConverged),
+                Some(kv!("gap" => gap;)),
+            ));
+        }
+
+        let Step {
+            direction,
+            max_step_length,
+            kind,
+        } = self.core.step(&param, &grad, vertex);
+        let k: F = float!(state.get_iter() as f64);
+        let step_length = (float!(2.0) / (k + float!(2.0))).min(max_step_length);
+        let new_param = param.scaled_add(&step_length, &direction);
+
+        let step = kind.name().to_string();
+        self.core
+            .update_vertices(kind, step_length, max_step_length);
+
+        let kv = if self.core.variant == FrankWolfeVariant::Standard {
+            kv!("gap" => gap; "step_length" => step_length; "step" => step;)
+        } else {
+            kv!(
+                "gap" => gap;
+                "step_length" => step_length;
+                "step" => step;
+                "vertices" => self.core.vertices.len() as u64;
+            )
+        };
+        Ok((state.param(new_param), Some(kv)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::frankwolfe::tests::SimplexProjection;
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(frank_wolfe, FrankWolfe<Vec<f64>, f64>);
+
+    #[test]
+    fn test_new() {
+        let FrankWolfe { core } = FrankWolfe::<Vec<f64>, f64>::new();
+        assert_eq!(core.variant, FrankWolfeVariant::Standard);
+        assert_eq!(core.tol.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert!(core.vertices.is_empty());
+    }
+
+    #[test]
+    fn test_setters() {
+        let fw = FrankWolfe::<Vec<f64>, f64>::new()
+            .with_variant(FrankWolfeVariant::Pairwise)
+            .with_tolerance(1e-3)
+            .unwrap();
+        assert_eq!(fw.core.variant, FrankWolfeVariant::Pairwise);
+        assert_eq!(fw.core.tol.to_ne_bytes(), 1e-3f64.to_ne_bytes());
+
+        assert_error!(
+            FrankWolfe::<Vec<f64>, f64>::new().with_tolerance(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`FrankWolfe`: tolerance must be >= 0.\""
+        );
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut fw = FrankWolfe::<Vec<f64>, f64>::new();
+        let res = fw.init(
+            &mut Problem::new(SimplexProjection::new()),
+            IterState::new(),
+        );
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`FrankWolfe` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_first_iteration() {
+        // The first step has length 1 and jumps to the vertex returned by the LMO
+        let mut fw = FrankWolfe::<Vec<f64>, f64>::new();
+        let mut problem = Problem::new(SimplexProjection::new());
+        let (state, _) = fw
+            .init(&mut problem, IterState::new().param(vec![0.0, 0.0, 1.0]))
+            .unwrap();
+        let (state, kv) = fw.next_iter(&mut problem, state).unwrap();
+        assert_eq!(state.get_param().unwrap(), &vec![1.0, 0.0, 0.0]);
+        let kv = kv.unwrap();
+        assert_relative_eq!(kv.get("gap").unwrap().get_float().unwrap(), 4.0);
+        assert_relative_eq!(kv.get("step_length").unwrap().get_float().unwrap(), 1.0);
+        assert_eq!(problem.counts["lmo_count"], 1);
+        assert_eq!(problem.counts["gradient_count"], 1);
+    }
+
+    #[test]
+    fn test_variants() {
+        for variant in [
+            FrankWolfeVariant::Standard,
+            FrankWolfeVariant::AwayStep,
+            FrankWolfeVariant::Pairwise,
+        ] {
+            let res = Executor::new(
+                SimplexProjection::new(),
+                FrankWolfe::<_, f64>::new().with_variant(variant),
+            )
+            .configure(|state| state.param(vec![1.0 / 3.0; 3]).max_iters(2000))
+            .run()
+            .unwrap();
+            let x = res.state().get_param().unwrap();
+            assert_relative_eq!(x[0], 0.6, epsilon = 1e-2);
+            assert_relative_eq!(x[1], 0.4, epsilon = 1e-2);
+            assert_relative_eq!(x[2], 0.0, epsilon = 1e-2);
+            assert_relative_eq!(x.iter().sum::<f64>(), 1.0, epsilon = 1e-12);
+            assert!(x.iter().all(|&xi| xi >= 0.0));
+        }
+    }
+
+    #[test]
+    fn test_gap_termination() {
+        // Initial parameter vector is optimal, which is detected in the first iteration
+        let res = Executor::new(SimplexProjection::new(), FrankWolfe::<_, f64>::new())
+            .configure(|state| state.param(vec![0.6, 0.4, 0.0]).max_iters(10))
+            .run()
+            .unwrap();
+        assert_eq!(res.state().get_iter(), 1);
+        assert_eq!(
+            res.state().get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+    }
+}
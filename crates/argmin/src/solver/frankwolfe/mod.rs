@@ -0,0 +1,385 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Frank-Wolfe (conditional gradient) methods
+//!
+//! Projection-free methods for minimizing smooth convex functions over compact convex sets which
+//! are accessed via a [`LinearMinimizationOracle`](`crate::core::LinearMinimizationOracle`).
+//!
+//! * [Frank-Wolfe method with the standard step schedule](`FrankWolfe`)
+//! * [Frank-Wolfe method with line search](`FrankWolfeLS`)
+//!
+//! Both support the away-step and pairwise variants (see [`FrankWolfeVariant`]).
+//!
+//! ## References
+//!
+//! Martin Jaggi (2013). Revisiting Frank-Wolfe: Projection-Free Sparse Convex Optimization.
+//! International Conference on Machine Learning.
+//!
+//! Simon Lacoste-Julien and Martin Jaggi (2015). On the Global Linear Convergence of Frank-Wolfe
+//! Optimization Variants. Advances in Neural Information Processing Systems.
+
+mod frankwolfe_linesearch;
+mod frankwolfe_method;
+
+pub use frankwolfe_linesearch::FrankWolfeLS;
+pub use frankwolfe_method::FrankWolfe;
+
+use crate::core::{ArgminFloat, Error};
+use argmin_math::{ArgminDot, ArgminSub};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Variant of the Frank-Wolfe method
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum FrankWolfeVariant {
+    /// Steps towards the vertex `s` returned by the linear minimization oracle
+    Standard,
+    /// Steps towards `s` or away from the active vertex `v` which maximizes `<g, v>`, whichever
+    /// promises more decrease
+    AwayStep,
+    /// Moves weight from the active vertex `v` which maximizes `<g, v>` to `s`
+    Pairwise,
+}
+
+/// Kind of a Frank-Wolfe step
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum StepKind<P> {
+    /// Step towards the vertex
+    Toward(P),
+    /// Step away from the active vertex at the given index
+    Away(usize),
+    /// Step from the active vertex at the given index towards the vertex
+    Pairwise(P, usize),
+}
+
+impl<P> StepKind<P> {
+    /// Name reported to the observers
+    pub(super) fn name(&self) -> &'static str {
+        match self {
+            StepKind::Toward(_) => "frank-wolfe",
+            StepKind::Away(_) => "away",
+            StepKind::Pairwise(_, _) => "pairwise",
+        }
+    }
+}
+
+/// Search direction of a Frank-Wolfe step
+pub(super) struct Step<P, F> {
+    /// Search direction
+    pub(super) direction: P,
+    /// Largest step length which keeps the parameter vector feasible
+    pub(super) max_step_length: F,
+    /// Kind of step
+    pub(super) kind: StepKind<P>,
+}
+
+/// Part of the Frank-Wolfe methods which is independent of the step length rule: computation of
+/// the duality gap and of the search direction, and bookkeeping of the active vertices
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub(super) struct Core<P, F> {
+    /// Variant
+    pub(super) variant: FrankWolfeVariant,
+    /// Tolerance on the duality gap
+    pub(super) tol: F,
+    /// Active vertices and their weights in the convex combination which equals the parameter
+    /// vector (not used by the standard variant)
+    pub(super) vertices: Vec<(P, F)>,
+}
+
+impl<P, F> Core<P, F>
+where
+    F: ArgminFloat,
+{
+    pub(super) fn new() -> Self {
+        Core {
+            variant: FrankWolfeVariant::Standard,
+            tol: F::epsilon().sqrt(),
+            vertices: vec![],
+        }
+    }
+
+    pub(super) fn set_tolerance(&mut self, name: &str, tol: F) -> Result<(), Error> {
+        if tol < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!("`{name}`: tolerance must be >= 0.")
+            ));
+        }
+        self.tol = tol;
+        Ok(())
+    }
+
+    /// Whether the variant requires the parameter vector to be a convex combination of known
+    /// vertices
+    pub(super) fn tracks_vertices(&self) -> bool {
+        self.variant != FrankWolfeVariant::Standard
+    }
+
+    /// Starts the convex combination with a single vertex
+    pub(super) fn init_vertices(&mut self, vertex: P) {
+        self.vertices = vec![(vertex, float!(1.0))];
+    }
+
+    /// Computes the search direction at `param` from the gradient and the vertex returned by the
+    /// linear minimization oracle
+    pub(super) fn step<G>(&self, param: &P, gradient: &G, vertex: P) -> Step<P, F>
+    where
+        P: ArgminSub<P, P>,
+        G: ArgminDot<P, F>,
+    {
+        let toward = |vertex: P| Step {
+            direction: vertex.sub(param),
+            max_step_length: float!(1.0),
+            kind: StepKind::Toward(vertex),
+        };
+        if !self.tracks_vertices() || self.vertices.is_empty() {
+            return toward(vertex);
+        }
+        // Active vertex with the largest slope
+        let (away, away_slope) = self
+            .vertices
+            .iter()
+            .map(|(v, _)| gradient.dot(v))
+            .enumerate()
+            .fold((0, F::neg_infinity()), |acc, (i, slope)| {
+                if slope > acc.1 {
+                    (i, slope)
+                } else {
+                    acc
+                }
+            });
+        let (away_vertex, away_weight) = &self.vertices[away];
+        match self.variant {
+            FrankWolfeVariant::Pairwise => Step {
+                direction: vertex.sub(away_vertex),
+                max_step_length: *away_weight,
+                kind: StepKind::Pairwise(vertex, away),
+            },
+            _ => {
+                let slope = gradient.dot(param);
+                let away_gap = away_slope - slope;
+                let gap = slope - gradient.dot(&vertex);
+                if gap >= away_gap || *away_weight >= float!(1.0) {
+                    toward(vertex)
+                } else {
+                    Step {
+                        direction: param.sub(away_vertex),
+                        max_step_length: *away_weight / (float!(1.0) - *away_weight),
+                        kind: StepKind::Away(away),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Updates the weights of the active vertices after a step of length `step_length`
+    pub(super) fn update_vertices(&mut self, kind: StepKind<P>, step_length: F, max_step_length: F)
+    where
+        P: PartialEq,
+    {
+        if !self.tracks_vertices() {
+            return;
+        }
+        match kind {
+            StepKind::Toward(vertex) => {
+                if step_length >= float!(1.0) {
+                    self.init_vertices(vertex);
+                    return;
+                }
+                for (_, w) in self.vertices.iter_mut() {
+                    *w = *w * (float!(1.0) - step_length);
+                }
+                self.add_weight(vertex, step_length);
+            }
+            StepKind::Away(i) => {
+                for (_, w) in self.vertices.iter_mut() {
+                    *w = *w * (float!(1.0) + step_length);
+                }
+                self.vertices[i].1 = self.vertices[i].1 - step_length;
+                if step_length >= max_step_length {
+                    // Drop step
+                    self.vertices[i].1 = float!(0.0);
+                }
+            }
+            StepKind::Pairwise(vertex, i) => {
+                self.vertices[i].1 = self.vertices[i].1 - step_length;
+                if step_length >= max_step_length {
+                    // Drop step
+                    self.vertices[i].1 = float!(0.0);
+                }
+                self.add_weight(vertex, step_length);
+            }
+        }
+        self.vertices.retain(|(_, w)| *w > float!(0.0));
+    }
+
+    /// Adds `weight` to the weight of `vertex`, which is added to the active vertices if needed
+    fn add_weight(&mut self, vertex: P, weight: F)
+    where
+        P: PartialEq,
+    {
+        if let Some((_, w)) = self.vertices.iter_mut().find(|(v, _)| *v == vertex) {
+            *w = *w + weight;
+        } else {
+            self.vertices.push((vertex, weight));
+        }
+    }
+}
+
+/// Frank-Wolfe duality gap `<g, x - s>`, an upper bound on the difference between the cost
+/// function value at `x` and the optimal cost function value
+pub(super) fn duality_gap<P, G, F>(param: &P, gradient: &G, vertex: &P) -> F
+where
+    G: ArgminDot<P, F>,
+    F: ArgminFloat,
+{
+    gradient.dot(param) - gradient.dot(vertex)
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+    use crate::core::{CostFunction, Gradient, LinearMinimizationOracle};
+    use approx::assert_relative_eq;
+
+    /// Squared distance `||x - y||^2` to a point `y`, minimized over the probability simplex
+    #[derive(Clone)]
+    pub(super) struct SimplexProjection {
+        pub(super) y: Vec<f64>,
+    }
+
+    impl SimplexProjection {
+        /// The projection of `y` is `(0.6, 0.4, 0.0)`, which lies on an edge of the simplex
+        pub(super) fn new() -> Self {
+            SimplexProjection {
+                y: vec![0.8, 0.6, -0.2],
+            }
+        }
+    }
+
+    impl CostFunction for SimplexProjection {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<f64, Error> {
+            Ok(p.iter()
+                .zip(self.y.iter())
+                .map(|(x, y)| (x - y).powi(2))
+                .sum())
+        }
+    }
+
+    impl Gradient for SimplexProjection {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Vec<f64>, Error> {
+            Ok(p.iter()
+                .zip(self.y.iter())
+                .map(|(x, y)| 2.0 * (x - y))
+                .collect())
+        }
+    }
+
+    impl LinearMinimizationOracle for SimplexProjection {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn lmo(&self, gradient: &Self::Gradient) -> Result<Vec<f64>, Error> {
+            let i = (0..gradient.len())
+                .min_by(|&a, &b| gradient[a].total_cmp(&gradient[b]))
+                .unwrap();
+            let mut vertex = vec![0.0; gradient.len()];
+            vertex[i] = 1.0;
+            Ok(vertex)
+        }
+    }
+
+    fn e(i: usize) -> Vec<f64> {
+        let mut v = vec![0.0; 3];
+        v[i] = 1.0;
+        v
+    }
+
+    #[test]
+    fn test_step_standard() {
+        let core: Core<Vec<f64>, f64> = Core::new();
+        let step = core.step(&vec![0.5, 0.5, 0.0], &vec![1.0, 2.0, 3.0], e(0));
+        assert_eq!(step.direction, vec![0.5, -0.5, 0.0]);
+        assert_eq!(step.max_step_length.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(step.kind, StepKind::Toward(e(0)));
+    }
+
+    #[test]
+    fn test_step_away() {
+        let mut core: Core<Vec<f64>, f64> = Core::new();
+        core.variant = FrankWolfeVariant::AwayStep;
+        core.vertices = vec![(e(0), 0.75), (e(2), 0.25)];
+        let x = vec![0.75, 0.0, 0.25];
+
+        // The away direction promises more decrease: away from e(2)
+        let step = core.step(&x, &vec![0.0, 0.0, 3.0], e(0));
+        assert_eq!(step.direction, vec![0.75, 0.0, -0.75]);
+        assert_relative_eq!(step.max_step_length, 1.0 / 3.0);
+        assert_eq!(step.kind, StepKind::Away(1));
+
+        // Towards e(1)
+        let step = core.step(&x, &vec![1.0, -3.0, 1.0], e(1));
+        assert_eq!(step.kind, StepKind::Toward(e(1)));
+
+        // Drop step removes e(2)
+        core.update_vertices(StepKind::Away(1), 1.0 / 3.0, 1.0 / 3.0);
+        assert_eq!(core.vertices.len(), 1);
+        assert_eq!(core.vertices[0].0, e(0));
+        assert_relative_eq!(core.vertices[0].1, 1.0);
+    }
+
+    #[test]
+    fn test_step_pairwise() {
+        let mut core: Core<Vec<f64>, f64> = Core::new();
+        core.variant = FrankWolfeVariant::Pairwise;
+        core.vertices = vec![(e(0), 0.25), (e(2), 0.75)];
+        let x = vec![0.25, 0.0, 0.75];
+
+        let step = core.step(&x, &vec![0.0, -1.0, 3.0], e(1));
+        assert_eq!(step.direction, vec![0.0, 1.0, -1.0]);
+        assert_eq!(step.max_step_length.to_ne_bytes(), 0.75f64.to_ne_bytes());
+        assert_eq!(step.kind, StepKind::Pairwise(e(1), 1));
+
+        core.update_vertices(StepKind::Pairwise(e(1), 1), 0.5, 0.75);
+        assert_eq!(core.vertices, vec![(e(0), 0.25), (e(2), 0.25), (e(1), 0.5)]);
+
+        // Moving weight to an active vertex merges the weights
+        core.update_vertices(StepKind::Pairwise(e(0), 1), 0.25, 0.25);
+        assert_eq!(core.vertices, vec![(e(0), 0.5), (e(1), 0.5)]);
+    }
+
+    #[test]
+    fn test_update_vertices_toward() {
+        let mut core: Core<Vec<f64>, f64> = Core::new();
+        core.variant = FrankWolfeVariant::AwayStep;
+        core.init_vertices(e(0));
+        core.update_vertices(StepKind::Toward(e(1)), 0.25, 1.0);
+        assert_eq!(core.vertices, vec![(e(0), 0.75), (e(1), 0.25)]);
+        core.update_vertices(StepKind::Toward(e(2)), 1.0, 1.0);
+        assert_eq!(core.vertices, vec![(e(2), 1.0)]);
+
+        // Standard variant does not keep track of vertices
+        let mut core: Core<Vec<f64>, f64> = Core::new();
+        core.update_vertices(StepKind::Toward(e(1)), 0.25, 1.0);
+        assert!(core.vertices.is_empty());
+    }
+
+    #[test]
+    fn test_duality_gap() {
+        let gap: f64 = duality_gap(&vec![0.5, 0.5, 0.0], &vec![1.0, 2.0, 3.0], &e(0));
+        assert_eq!(gap.to_ne_bytes(), 0.5f64.to_ne_bytes());
+    }
+}
@@ -0,0 +1,654 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Genetic Algorithm (GA)
+//!
+//! Population-based stochastic optimization method which imitates natural evolution. The genome
+//! is user-defined; recombination and mutation of genomes are provided by the optimization
+//! problem via the [`Crossover`] and [`Mutate`] traits. Parents are chosen by a [`Selection`]
+//! strategy.
+//!
+//! For details see [`GeneticAlgorithm`].
+//!
+//! ## References
+//!
+//! David E. Goldberg (1989). Genetic Algorithms in Search, Optimization and Machine Learning.
+//! Addison-Wesley. ISBN 0-201-15767-5.
+//!
+//! [Wikipedia](https://en.wikipedia.org/wiki/Genetic_algorithm)
+
+mod selection;
+
+pub use self::selection::{RankSelection, Selection, TournamentSelection};
+
+use crate::core::{
+    ArgminFloat, CostFunction, Error, PopulationState, Problem, Solver, SyncAlias, KV,
+};
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// This trait handles the recombination of two genomes. Problems which are to be solved using
+/// [`GeneticAlgorithm`] must implement this trait.
+pub trait Crossover {
+    /// Type of the genome
+    type Param;
+
+    /// Recombine two parent genomes into two offspring genomes
+    fn crossover(
+        &self,
+        parent1: &Self::Param,
+        parent2: &Self::Param,
+    ) -> Result<(Self::Param, Self::Param), Error>;
+}
+
+/// Wraps a call to `crossover` defined in the `Crossover` trait and as such allows to call
+/// `crossover` on an instance of `Problem`. Internally, the number of evaluations of `crossover`
+/// is counted.
+impl<O: Crossover> Problem<O> {
+    /// Calls `crossover` defined in the `Crossover` trait and keeps track of the number of
+    /// evaluations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, Error};
+    /// # use argmin::solver::geneticalgorithm::Crossover;
+    /// #
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl Crossover for UserDefinedProblem {
+    /// #     type Param = Vec<bool>;
+    /// #
+    /// #     fn crossover(
+    /// #         &self,
+    /// #         parent1: &Self::Param,
+    /// #         parent2: &Self::Param,
+    /// #     ) -> Result<(Self::Param, Self::Param), Error> {
+    /// #         Ok((parent2.clone(), parent1.clone()))
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `Crossover`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let parent1 = vec![true, true];
+    /// let parent2 = vec![false, false];
+    ///
+    /// let res = problem1.crossover(&parent1, &parent2);
+    ///
+    /// assert_eq!(problem1.counts["crossover_count"], 1);
+    /// # assert_eq!(res.unwrap(), (parent2, parent1));
+    /// ```
+    pub fn crossover(
+        &mut self,
+        parent1: &O::Param,
+        parent2: &O::Param,
+    ) -> Result<(O::Param, O::Param), Error> {
+        self.problem("crossover_count", |problem| {
+            problem.crossover(parent1, parent2)
+        })
+    }
+}
+
+/// This trait handles the mutation of a genome. Problems which are to be solved using
+/// [`GeneticAlgorithm`] must implement this trait.
+pub trait Mutate {
+    /// Type of the genome
+    type Param;
+
+    /// Mutate a genome
+    fn mutate(&self, param: &Self::Param) -> Result<Self::Param, Error>;
+}
+
+/// Wraps a call to `mutate` defined in the `Mutate` trait and as such allows to call `mutate` on
+/// an instance of `Problem`. Internally, the number of evaluations of `mutate` is counted.
+impl<O: Mutate> Problem<O> {
+    /// Calls `mutate` defined in the `Mutate` trait and keeps track of the number of evaluations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, Error};
+    /// # use argmin::solver::geneticalgorithm::Mutate;
+    /// #
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl Mutate for UserDefinedProblem {
+    /// #     type Param = Vec<bool>;
+    /// #
+    /// #     fn mutate(&self, param: &Self::Param) -> Result<Self::Param, Error> {
+    /// #         Ok(param.iter().map(|b| !b).collect())
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `Mutate`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let param = vec![true, false];
+    ///
+    /// let res = problem1.mutate(&param);
+    ///
+    /// assert_eq!(problem1.counts["mutate_count"], 1);
+    /// # assert_eq!(res.unwrap(), vec![false, true]);
+    /// ```
+    pub fn mutate(&mut self, param: &O::Param) -> Result<O::Param, Error> {
+        self.problem("mutate_count", |problem| problem.mutate(param))
+    }
+}
+
+/// # Genetic Algorithm (GA)
+///
+/// Generational genetic algorithm with elitism. In every iteration, the best `elitism` members
+/// of the population are carried over unchanged into the next generation. The remaining members
+/// are created by choosing pairs of parents with the [`Selection`] strategy, recombining them with
+/// probability `crossover_probability` (otherwise the offspring are copies of the parents) and
+/// mutating each offspring with probability `mutation_probability`.
+///
+/// The genome (the parameter vector `P`) is user-defined. The optimization problem must implement
+/// [`CostFunction`], [`Crossover`] and [`Mutate`], where the latter two define how genomes are
+/// recombined and altered. Any randomness required for these operations has to be provided by the
+/// problem itself (see the genetic algorithm example). The number of calls to `crossover` and
+/// `mutate` are counted as `crossover_count` and `mutate_count`, respectively.
+///
+/// Since genomes are user-defined, the initial population must be provided via
+/// [`PopulationState::population`]. The population of each generation is stored, sorted by cost,
+/// in [`PopulationState`]. The offspring are evaluated via [`Problem::bulk_cost`], therefore the
+/// `rayon` feature enables parallel computation of the cost function. This can be beneficial for
+/// expensive cost functions, but may cause a drop in performance for cheap cost functions. Be sure
+/// to benchmark both parallel and sequential computation.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`], [`Crossover`] and
+/// [`Mutate`].
+///
+/// ## References
+///
+/// David E. Goldberg (1989). Genetic Algorithms in Search, Optimization and Machine Learning.
+/// Addison-Wesley. ISBN 0-201-15767-5.
+///
+/// [Wikipedia](https://en.wikipedia.org/wiki/Genetic_algorithm)
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct GeneticAlgorithm<S, F, R> {
+    /// Selection strategy
+    selection: S,
+    /// Probability of recombining a pair of parents
+    crossover_probability: F,
+    /// Probability of mutating an offspring
+    mutation_probability: F,
+    /// Number of best members which are carried over unchanged into the next generation
+    elitism: usize,
+    /// Cost function values of the current population
+    costs: Vec<F>,
+    /// Random number generator
+    rng_generator: R,
+}
+
+impl<S, F> GeneticAlgorithm<S, F, rand::rngs::StdRng>
+where
+    S: Selection<F>,
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `GeneticAlgorithm`
+    ///
+    /// Takes the selection strategy as input. The crossover probability defaults to `0.9`, the
+    /// mutation probability to `0.1` and the number of elite members to `1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::geneticalgorithm::{GeneticAlgorithm, TournamentSelection};
+    /// let ga: GeneticAlgorithm<_, f64, _> = GeneticAlgorithm::new(TournamentSelection::new());
+    /// ```
+    pub fn new(selection: S) -> Self {
+        GeneticAlgorithm {
+            selection,
+            crossover_probability: float!(0.9),
+            mutation_probability: float!(0.1),
+            elitism: 1,
+            costs: vec![],
+            rng_generator: rand::rngs::StdRng::from_entropy(),
+        }
+    }
+}
+
+impl<S, F, R0> GeneticAlgorithm<S, F, R0>
+where
+    S: Selection<F>,
+    F: ArgminFloat,
+    R0: Rng,
+{
+    /// Set the random number generator
+    ///
+    /// Defaults to `rand::rngs::StdRng::from_entropy()`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::geneticalgorithm::{GeneticAlgorithm, TournamentSelection};
+    /// # use rand::SeedableRng;
+    /// let ga: GeneticAlgorithm<_, f64, _> = GeneticAlgorithm::new(TournamentSelection::new())
+    ///     .with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(1729));
+    /// ```
+    pub fn with_rng_generator<R1: Rng>(self, generator: R1) -> GeneticAlgorithm<S, F, R1> {
+        GeneticAlgorithm {
+            selection: self.selection,
+            crossover_probability: self.crossover_probability,
+            mutation_probability: self.mutation_probability,
+            elitism: self.elitism,
+            costs: self.costs,
+            rng_generator: generator,
+        }
+    }
+}
+
+impl<S, F, R> GeneticAlgorithm<S, F, R>
+where
+    S: Selection<F>,
+    F: ArgminFloat,
+    R: Rng,
+{
+    /// Set the probability of recombining a pair of parents
+    ///
+    /// Must be in `[0, 1]`. Defaults to `0.9`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::geneticalgorithm::{GeneticAlgorithm, TournamentSelection};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let ga: GeneticAlgorithm<_, f64, _> =
+    ///     GeneticAlgorithm::new(TournamentSelection::new()).with_crossover_probability(0.7)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_crossover_probability(mut self, probability: F) -> Result<Self, Error> {
+        if !(float!(0.0)..=float!(1.0)).contains(&probability) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`GeneticAlgorithm`: crossover probability must be in [0, 1]."
+            ));
+        }
+        self.crossover_probability = probability;
+        Ok(self)
+    }
+
+    /// Set the probability of mutating an offspring
+    ///
+    /// Must be in `[0, 1]`. Defaults to `0.1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::geneticalgorithm::{GeneticAlgorithm, TournamentSelection};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let ga: GeneticAlgorithm<_, f64, _> =
+    ///     GeneticAlgorithm::new(TournamentSelection::new()).with_mutation_probability(0.3)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_mutation_probability(mut self, probability: F) -> Result<Self, Error> {
+        if !(float!(0.0)..=float!(1.0)).contains(&probability) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`GeneticAlgorithm`: mutation probability must be in [0, 1]."
+            ));
+        }
+        self.mutation_probability = probability;
+        Ok(self)
+    }
+
+    /// Set the number of best members which are carried over unchanged into the next generation
+    ///
+    /// Must be smaller than the population size. Defaults to `1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::geneticalgorithm::{GeneticAlgorithm, TournamentSelection};
+    /// let ga: GeneticAlgorithm<_, f64, _> =
+    ///     GeneticAlgorithm::new(TournamentSelection::new()).with_elitism(2);
+    /// ```
+    #[must_use]
+    pub fn with_elitism(mut self, elitism: usize) -> Self {
+        self.elitism = elitism;
+        self
+    }
+
+    /// Returns `true` with probability `probability`
+    fn chance(&mut self, probability: F) -> bool {
+        F::from_f64(self.rng_generator.gen::<f64>()).unwrap() < probability
+    }
+}
+
+/// Sorts `population` and `costs` by cost, such that the first member is the best one
+fn sort_population<P, F: ArgminFloat>(population: Vec<P>, costs: Vec<F>) -> (Vec<P>, Vec<F>) {
+    let mut members: Vec<(P, F)> = population.into_iter().zip(costs).collect();
+    members.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+    members.into_iter().unzip()
+}
+
+impl<O, S, P, F, R> Solver<O, PopulationState<P, F>> for GeneticAlgorithm<S, F, R>
+where
+    O: CostFunction<Param = P, Output = F> + Crossover<Param = P> + Mutate<Param = P> + SyncAlias,
+    S: Selection<F>,
+    P: Clone + SyncAlias,
+    F: ArgminFloat,
+    R: Rng,
+{
+    const NAME: &'static str = "Genetic Algorithm";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: PopulationState<P, F>,
+    ) -> Result<(PopulationState<P, F>, Option<KV>), Error> {
+        let population = state.take_population().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`GeneticAlgorithm` requires an initial population. ",
+                "Please provide an initial population via `Executor`s `configure` method."
+            )
+        ))?;
+        if population.len() < 2 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`GeneticAlgorithm`: population must have at least two members."
+            ));
+        }
+        if self.elitism >= population.len() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`GeneticAlgorithm`: number of elite members must be smaller than the population size."
+            ));
+        }
+
+        let costs = problem.bulk_cost(&population)?;
+        let (population, costs) = sort_population(population, costs);
+        self.costs = costs;
+
+        Ok((
+            state
+                .individual(population[0].clone())
+                .cost(self.costs[0])
+                .population(population),
+            None,
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: PopulationState<P, F>,
+    ) -> Result<(PopulationState<P, F>, Option<KV>), Error> {
+        let population = state.take_population().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`GeneticAlgorithm`: No population in state."
+        ))?;
+        let num_offspring = population.len() - self.elitism;
+
+        // select parents in pairs
+        let parents = self.selection.select(
+            &self.costs,
+            num_offspring + num_offspring % 2,
+            &mut self.rng_generator,
+        )?;
+
+        let mut offspring: Vec<P> = Vec::with_capacity(num_offspring + 1);
+        for pair in parents.chunks(2) {
+            let (parent1, parent2) = (&population[pair[0]], &population[pair[1]]);
+            let (child1, child2) = if self.chance(self.crossover_probability) {
+                problem.crossover(parent1, parent2)?
+            } else {
+                (parent1.clone(), parent2.clone())
+            };
+            for child in [child1, child2] {
+                let child = if self.chance(self.mutation_probability) {
+                    problem.mutate(&child)?
+                } else {
+                    child
+                };
+                offspring.push(child);
+            }
+        }
+        offspring.truncate(num_offspring);
+
+        let offspring_costs = problem.bulk_cost(&offspring)?;
+
+        let mut new_population: Vec<P> = population.into_iter().take(self.elitism).collect();
+        new_population.extend(offspring);
+        let mut costs: Vec<F> = self.costs[..self.elitism].to_vec();
+        costs.extend(offspring_costs);
+
+        let (population, costs) = sort_population(new_population, costs);
+        self.costs = costs;
+
+        Ok((
+            state
+                .individual(population[0].clone())
+                .cost(self.costs[0])
+                .population(population),
+            None,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor, State};
+    use crate::test_trait_impl;
+    use rand_xoshiro::Xoroshiro128Plus;
+    use std::sync::{Arc, Mutex};
+
+    test_trait_impl!(
+        geneticalgorithm,
+        GeneticAlgorithm<TournamentSelection, f64, rand::rngs::StdRng>
+    );
+
+    /// Minimize the number of `false` entries of a bit string
+    struct OneMax {
+        rng: Arc<Mutex<Xoroshiro128Plus>>,
+    }
+
+    impl OneMax {
+        fn new() -> Self {
+            OneMax {
+                rng: Arc::new(Mutex::new(Xoroshiro128Plus::seed_from_u64(42))),
+            }
+        }
+    }
+
+    impl CostFunction for OneMax {
+        type Param = Vec<bool>;
+        type Output = f64;
+
+        fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(param.iter().filter(|b| !**b).count() as f64)
+        }
+    }
+
+    impl Crossover for OneMax {
+        type Param = Vec<bool>;
+
+        fn crossover(
+            &self,
+            parent1: &Self::Param,
+            parent2: &Self::Param,
+        ) -> Result<(Self::Param, Self::Param), Error> {
+            let cut = self.rng.lock().unwrap().gen_range(0..parent1.len());
+            let mut child1 = parent1[..cut].to_vec();
+            child1.extend_from_slice(&parent2[cut..]);
+            let mut child2 = parent2[..cut].to_vec();
+            child2.extend_from_slice(&parent1[cut..]);
+            Ok((child1, child2))
+        }
+    }
+
+    impl Mutate for OneMax {
+        type Param = Vec<bool>;
+
+        fn mutate(&self, param: &Self::Param) -> Result<Self::Param, Error> {
+            let mut param = param.clone();
+            let idx = self.rng.lock().unwrap().gen_range(0..param.len());
+            param[idx] = !param[idx];
+            Ok(param)
+        }
+    }
+
+    fn initial_population(num: usize, len: usize) -> Vec<Vec<bool>> {
+        let mut rng = Xoroshiro128Plus::seed_from_u64(1729);
+        (0..num)
+            .map(|_| (0..len).map(|_| rng.gen_bool(0.2)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_new() {
+        let ga: GeneticAlgorithm<_, f64, _> = GeneticAlgorithm::new(TournamentSelection::new());
+        let GeneticAlgorithm {
+            selection,
+            crossover_probability,
+            mutation_probability,
+            elitism,
+            costs,
+            ..
+        } = ga;
+        assert_eq!(selection, TournamentSelection::new());
+        assert_eq!(crossover_probability.to_ne_bytes(), 0.9f64.to_ne_bytes());
+        assert_eq!(mutation_probability.to_ne_bytes(), 0.1f64.to_ne_bytes());
+        assert_eq!(elitism, 1);
+        assert!(costs.is_empty());
+    }
+
+    #[test]
+    fn test_builder() {
+        let ga: GeneticAlgorithm<_, f64, _> = GeneticAlgorithm::new(TournamentSelection::new())
+            .with_crossover_probability(0.5)
+            .unwrap()
+            .with_mutation_probability(0.2)
+            .unwrap()
+            .with_elitism(3);
+        assert_eq!(ga.crossover_probability.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert_eq!(ga.mutation_probability.to_ne_bytes(), 0.2f64.to_ne_bytes());
+        assert_eq!(ga.elitism, 3);
+
+        for probability in [-0.1, 1.1] {
+            assert_error!(
+                GeneticAlgorithm::<_, f64, _>::new(TournamentSelection::new())
+                    .with_crossover_probability(probability),
+                ArgminError,
+                "Invalid parameter: \"`GeneticAlgorithm`: crossover probability must be in [0, 1].\""
+            );
+            assert_error!(
+                GeneticAlgorithm::<_, f64, _>::new(TournamentSelection::new())
+                    .with_mutation_probability(probability),
+                ArgminError,
+                "Invalid parameter: \"`GeneticAlgorithm`: mutation probability must be in [0, 1].\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_init_errors() {
+        let mut ga: GeneticAlgorithm<_, f64, _> = GeneticAlgorithm::new(TournamentSelection::new());
+        let res = ga.init(&mut Problem::new(OneMax::new()), PopulationState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`GeneticAlgorithm` requires an initial population. ",
+                "Please provide an initial population via `Executor`s `configure` method.\""
+            )
+        );
+
+        let state = PopulationState::new().population(initial_population(1, 10));
+        let res = ga.init(&mut Problem::new(OneMax::new()), state);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`GeneticAlgorithm`: population must have at least two members.\""
+        );
+
+        let mut ga = ga.with_elitism(4);
+        let state = PopulationState::new().population(initial_population(4, 10));
+        let res = ga.init(&mut Problem::new(OneMax::new()), state);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`GeneticAlgorithm`: number of elite members must be smaller than the population size.\""
+        );
+    }
+
+    #[test]
+    fn test_next_iter() {
+        let mut ga: GeneticAlgorithm<_, f64, _> = GeneticAlgorithm::new(TournamentSelection::new())
+            .with_crossover_probability(1.0)
+            .unwrap()
+            .with_mutation_probability(1.0)
+            .unwrap()
+            .with_elitism(2)
+            .with_rng_generator(Xoroshiro128Plus::seed_from_u64(42));
+        let mut problem = Problem::new(OneMax::new());
+        let state = PopulationState::new().population(initial_population(11, 10));
+        let (state, _) = ga.init(&mut problem, state).unwrap();
+        let elite: Vec<Vec<bool>> = state.get_population().unwrap()[..2].to_vec();
+        let best_cost = state.get_cost();
+
+        let (state, _) = ga.next_iter(&mut problem, state).unwrap();
+        let population = state.get_population().unwrap();
+        assert_eq!(population.len(), 11);
+        for member in elite {
+            assert!(population.contains(&member));
+        }
+        assert!(state.get_cost() <= best_cost);
+        let costs: Vec<f64> = population
+            .iter()
+            .map(|p| OneMax::new().cost(p).unwrap())
+            .collect();
+        assert_eq!(costs, ga.costs);
+        for w in costs.windows(2) {
+            assert!(w[0] <= w[1]);
+        }
+        assert_eq!(problem.counts["cost_count"], 11 + 9);
+        assert_eq!(problem.counts["crossover_count"], 5);
+        assert_eq!(problem.counts["mutate_count"], 10);
+    }
+
+    fn solve_onemax<S: Selection<f64>>(selection: S) {
+        let solver = GeneticAlgorithm::new(selection)
+            .with_mutation_probability(0.5)
+            .unwrap()
+            .with_rng_generator(Xoroshiro128Plus::seed_from_u64(42));
+        let res = Executor::new(OneMax::new(), solver)
+            .configure(|state| {
+                state
+                    .population(initial_population(30, 40))
+                    .max_iters(1000)
+                    .target_cost(0.0)
+            })
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_best_cost().to_ne_bytes(),
+            0.0f64.to_ne_bytes()
+        );
+        assert_eq!(res.state.get_best_param().unwrap(), &vec![true; 40]);
+    }
+
+    #[test]
+    fn test_onemax_tournament() {
+        solve_onemax(TournamentSelection::new());
+    }
+
+    #[test]
+    fn test_onemax_rank() {
+        solve_onemax(RankSelection::new());
+    }
+}
@@ -0,0 +1,272 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, Error};
+use rand::Rng;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Interface for selection strategies of [`GeneticAlgorithm`](`super::GeneticAlgorithm`)
+///
+/// A selection strategy picks parents for the next generation based on the cost function values
+/// of the members of the current population. Lower cost function values are better.
+pub trait Selection<F> {
+    /// Returns the indices of `num` selected members given their cost function values `costs`
+    fn select<R: Rng>(&self, costs: &[F], num: usize, rng: &mut R) -> Result<Vec<usize>, Error>;
+}
+
+/// # Tournament selection
+///
+/// For each selected member, `size` members are drawn uniformly (with replacement) from the
+/// population and the one with the lowest cost function value wins. Larger tournaments increase
+/// the selection pressure.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct TournamentSelection {
+    /// Number of members per tournament
+    size: usize,
+}
+
+impl TournamentSelection {
+    /// Construct a new instance of `TournamentSelection`
+    ///
+    /// The tournament size defaults to `2`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::geneticalgorithm::TournamentSelection;
+    /// let selection = TournamentSelection::new();
+    /// ```
+    pub fn new() -> Self {
+        TournamentSelection { size: 2 }
+    }
+
+    /// Set the number of members per tournament
+    ///
+    /// Must be at least `1`. Defaults to `2`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::geneticalgorithm::TournamentSelection;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let selection = TournamentSelection::new().with_size(4)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_size(mut self, size: usize) -> Result<Self, Error> {
+        if size < 1 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`TournamentSelection`: size must be >= 1."
+            ));
+        }
+        self.size = size;
+        Ok(self)
+    }
+}
+
+impl Default for TournamentSelection {
+    fn default() -> Self {
+        TournamentSelection::new()
+    }
+}
+
+impl<F: ArgminFloat> Selection<F> for TournamentSelection {
+    fn select<R: Rng>(&self, costs: &[F], num: usize, rng: &mut R) -> Result<Vec<usize>, Error> {
+        if costs.is_empty() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`TournamentSelection`: population must not be empty."
+            ));
+        }
+        Ok((0..num)
+            .map(|_| {
+                (0..self.size)
+                    .map(|_| rng.gen_range(0..costs.len()))
+                    .reduce(|best, idx| if costs[idx] < costs[best] { idx } else { best })
+                    .unwrap()
+            })
+            .collect())
+    }
+}
+
+/// # Linear ranking selection
+///
+/// Members are sorted by their cost function values and selected with a probability which
+/// decreases linearly with their rank. With `N` members and selection pressure `s`, the member of
+/// rank `i` (starting at `0` for the best member) is selected with probability
+/// `(s - (2s - 2) i / (N - 1)) / N`. The best member is therefore selected `s` times as often as
+/// the average member, and the worst member `2 - s` times as often.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct RankSelection<F> {
+    /// Selection pressure
+    pressure: F,
+}
+
+impl<F: ArgminFloat> RankSelection<F> {
+    /// Construct a new instance of `RankSelection`
+    ///
+    /// The selection pressure defaults to `1.5`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::geneticalgorithm::RankSelection;
+    /// let selection: RankSelection<f64> = RankSelection::new();
+    /// ```
+    pub fn new() -> Self {
+        RankSelection {
+            pressure: float!(1.5),
+        }
+    }
+
+    /// Set the selection pressure
+    ///
+    /// Must be in `[1, 2]`. A selection pressure of `1` selects all members with equal probability.
+    /// Defaults to `1.5`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::geneticalgorithm::RankSelection;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let selection: RankSelection<f64> = RankSelection::new().with_pressure(1.8)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_pressure(mut self, pressure: F) -> Result<Self, Error> {
+        if !(float!(1.0)..=float!(2.0)).contains(&pressure) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`RankSelection`: selection pressure must be in [1, 2]."
+            ));
+        }
+        self.pressure = pressure;
+        Ok(self)
+    }
+}
+
+impl<F: ArgminFloat> Default for RankSelection<F> {
+    fn default() -> Self {
+        RankSelection::new()
+    }
+}
+
+impl<F: ArgminFloat> Selection<F> for RankSelection<F> {
+    fn select<R: Rng>(&self, costs: &[F], num: usize, rng: &mut R) -> Result<Vec<usize>, Error> {
+        if costs.is_empty() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`RankSelection`: population must not be empty."
+            ));
+        }
+        let mut ranked: Vec<usize> = (0..costs.len()).collect();
+        ranked.sort_by(|&a, &b| {
+            costs[a]
+                .partial_cmp(&costs[b])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let n = F::from_usize(costs.len()).unwrap();
+        let s = self.pressure;
+        let mut cumulative = Vec::with_capacity(costs.len());
+        let mut sum = float!(0.0);
+        for i in 0..costs.len() {
+            let slope = if costs.len() > 1 {
+                (float!(2.0) * s - float!(2.0)) * F::from_usize(i).unwrap() / (n - float!(1.0))
+            } else {
+                float!(0.0)
+            };
+            sum = sum + (s - slope) / n;
+            cumulative.push(sum);
+        }
+
+        Ok((0..num)
+            .map(|_| {
+                let u = F::from_f64(rng.gen::<f64>()).unwrap() * sum;
+                let pos = cumulative
+                    .iter()
+                    .position(|&c| u < c)
+                    .unwrap_or(costs.len() - 1);
+                ranked[pos]
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_tournament() {
+        let mut rng = rand_xoshiro::Xoroshiro128Plus::seed_from_u64(42);
+        let costs = vec![3.0f64, 1.0, 4.0, 2.0];
+
+        let selection = TournamentSelection::new().with_size(1000).unwrap();
+        let selected = selection.select(&costs, 10, &mut rng).unwrap();
+        assert_eq!(selected, vec![1; 10]);
+
+        let selection = TournamentSelection::new().with_size(1).unwrap();
+        let selected = selection.select(&costs, 1000, &mut rng).unwrap();
+        for idx in 0..4 {
+            assert!(selected.contains(&idx));
+        }
+
+        assert_error!(
+            TournamentSelection::new().with_size(0),
+            ArgminError,
+            "Invalid parameter: \"`TournamentSelection`: size must be >= 1.\""
+        );
+        assert_error!(
+            Selection::<f64>::select(&TournamentSelection::new(), &[], 1, &mut rng),
+            ArgminError,
+            "Invalid parameter: \"`TournamentSelection`: population must not be empty.\""
+        );
+    }
+
+    #[test]
+    fn test_rank() {
+        let mut rng = rand_xoshiro::Xoroshiro128Plus::seed_from_u64(42);
+        let costs = vec![3.0f64, 1.0, 4.0, 2.0];
+
+        let selection = RankSelection::new().with_pressure(2.0).unwrap();
+        let selected = selection.select(&costs, 10000, &mut rng).unwrap();
+        let mut counts = [0usize; 4];
+        for idx in selected {
+            counts[idx] += 1;
+        }
+        // worst member has selection probability 0
+        assert_eq!(counts[2], 0);
+        // expected frequencies are 0.5, 0.333 and 0.167
+        assert!(counts[1] > counts[3] && counts[3] > counts[0]);
+        assert!((counts[1] as f64 / 10000.0 - 0.5).abs() < 0.03);
+
+        let selected = RankSelection::new().select(&[1.0f64], 3, &mut rng).unwrap();
+        assert_eq!(selected, vec![0; 3]);
+
+        for pressure in [0.9, 2.1] {
+            assert_error!(
+                RankSelection::new().with_pressure(pressure),
+                ArgminError,
+                "Invalid parameter: \"`RankSelection`: selection pressure must be in [1, 2].\""
+            );
+        }
+        assert_error!(
+            RankSelection::new().select(&[] as &[f64], 1, &mut rng),
+            ArgminError,
+            "Invalid parameter: \"`RankSelection`: population must not be empty.\""
+        );
+    }
+}
@@ -0,0 +1,571 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::{local_run, LocalResult};
+use crate::core::{ArgminFloat, Error, IterState, Problem, Solver, KV};
+use argmin_math::{ArgminGet, ArgminSet, ArgminSize};
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Basin-hopping
+///
+/// Global optimization via a random walk over the local minima of the cost function \[0\].
+///
+/// In [`init`](`Solver::init`), the local solver is run from the initial parameter vector. In
+/// every iteration, each coordinate of the current local minimum is perturbed by a uniformly
+/// distributed random number from `[-step_size, step_size]` (and clamped to the bounds, if any)
+/// and the local solver is run from the perturbed point. The resulting local minimum with cost
+/// function value `f_new` replaces the current one with cost function value `f` with probability
+/// `min(1, exp(-(f_new - f) / temperature))` (Metropolis criterion). With a temperature of `0`,
+/// only improvements are accepted.
+///
+/// Every local run uses a local solver which is constructed for the start point (see
+/// [`new`](`BasinHopping::new`)) and at most `local_max_iters` iterations (default: `1000`). Parameter vector and cost function value of the state hold the current
+/// local minimum, while the best local minimum found so far is available as best parameter
+/// vector. The method does not converge on its own, therefore a maximum number of iterations
+/// should be set. Whether the new local minimum was accepted, its cost function value and the
+/// fraction of accepted local minima so far are reported as KV metrics `accepted`,
+/// `local_cost` and `acceptance_rate`, and all results are available via
+/// [`local_results`](`BasinHopping::local_results`). The function counts of the local runs are
+/// added to those of the problem.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to satisfy the requirements of the local solver.
+///
+/// ## References
+///
+/// \[0\] David J. Wales and Jonathan P. K. Doye (1997). Global Optimization by Basin-Hopping and
+/// the Lowest Energy Structures of Lennard-Jones Clusters Containing up to 110 Atoms. The Journal
+/// of Physical Chemistry A 101 (28), 5111–5116. DOI: <https://doi.org/10.1021/jp970984n>
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct BasinHopping<S, P, F, R> {
+    /// Constructs the local solver for a start point
+    solver: S,
+    /// Maximum perturbation of each coordinate
+    step_size: F,
+    /// Temperature of the Metropolis criterion
+    temperature: F,
+    /// Optional bounds on the perturbed points
+    bounds: Option<(P, P)>,
+    /// Maximum number of iterations of each local run
+    local_max_iters: u64,
+    /// Random number generator
+    rng_generator: R,
+    /// Number of accepted local minima
+    accepted: u64,
+    /// Results of the local runs performed so far
+    results: Vec<LocalResult<P, F>>,
+}
+
+impl<S, P, F> BasinHopping<S, P, F, rand::rngs::StdRng>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `BasinHopping`
+    ///
+    /// Takes a function which constructs the local solver for a given start point. Solvers which
+    /// start from the initial parameter vector of their state can simply be cloned, while others
+    /// need to be set up for the start point.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::globalopt::BasinHopping;
+    /// # use argmin::solver::neldermead::NelderMead;
+    /// # use argmin::solver::patternsearch::HookeJeeves;
+    /// let hj: HookeJeeves<Vec<f64>, f64> = HookeJeeves::new();
+    /// let bh: BasinHopping<_, Vec<f64>, f64, _> = BasinHopping::new(move |_| Ok(hj.clone()));
+    ///
+    /// // Nelder-Mead with an initial simplex around the start point
+    /// let bh: BasinHopping<_, Vec<f64>, f64, _> = BasinHopping::new(|x: &Vec<f64>| {
+    ///     let simplex = vec![x.clone(), vec![x[0] + 0.1, x[1]], vec![x[0], x[1] + 0.1]];
+    ///     NelderMead::<_, f64>::new(simplex).with_sd_tolerance(1e-8)
+    /// });
+    /// ```
+    pub fn new<L>(solver: S) -> Self
+    where
+        S: Fn(&P) -> Result<L, Error>,
+    {
+        BasinHopping {
+            solver,
+            step_size: float!(0.5),
+            temperature: float!(1.0),
+            bounds: None,
+            local_max_iters: 1000,
+            rng_generator: rand::rngs::StdRng::from_entropy(),
+            accepted: 0,
+            results: vec![],
+        }
+    }
+}
+
+impl<S, P, F, R0> BasinHopping<S, P, F, R0> {
+    /// Set random number generator
+    ///
+    /// Defaults to `rand::rngs::StdRng::from_entropy()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::globalopt::BasinHopping;
+    /// # use argmin::solver::patternsearch::HookeJeeves;
+    /// # use rand::SeedableRng;
+    /// # let hj = |_: &Vec<f64>| Ok(HookeJeeves::<Vec<f64>, f64>::new());
+    /// let bh: BasinHopping<_, Vec<f64>, f64, _> = BasinHopping::new(hj)
+    ///     .with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(1729));
+    /// ```
+    pub fn with_rng_generator<R1: Rng>(self, generator: R1) -> BasinHopping<S, P, F, R1> {
+        BasinHopping {
+            solver: self.solver,
+            step_size: self.step_size,
+            temperature: self.temperature,
+            bounds: self.bounds,
+            local_max_iters: self.local_max_iters,
+            rng_generator: generator,
+            accepted: self.accepted,
+            results: self.results,
+        }
+    }
+}
+
+impl<S, P, F, R> BasinHopping<S, P, F, R>
+where
+    F: ArgminFloat,
+{
+    /// Set maximum perturbation of each coordinate
+    ///
+    /// Must be larger than 0 and defaults to `0.5`. It should be of the order of the distance
+    /// between neighboring local minima.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::globalopt::BasinHopping;
+    /// # use argmin::solver::patternsearch::HookeJeeves;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let hj = |_: &Vec<f64>| Ok(HookeJeeves::<Vec<f64>, f64>::new());
+    /// let bh: BasinHopping<_, Vec<f64>, f64, _> = BasinHopping::new(hj).with_step_size(1.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_step_size(mut self, step_size: F) -> Result<Self, Error> {
+        if step_size <= float!(0.0) || !step_size.is_finite() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BasinHopping`: step size must be > 0 and finite."
+            ));
+        }
+        self.step_size = step_size;
+        Ok(self)
+    }
+
+    /// Set temperature of the Metropolis criterion
+    ///
+    /// Must be non-negative and defaults to `1.0`. It should be of the order of the difference of
+    /// the cost function values of neighboring local minima.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::globalopt::BasinHopping;
+    /// # use argmin::solver::patternsearch::HookeJeeves;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let hj = |_: &Vec<f64>| Ok(HookeJeeves::<Vec<f64>, f64>::new());
+    /// let bh: BasinHopping<_, Vec<f64>, f64, _> = BasinHopping::new(hj).with_temperature(0.1)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_temperature(mut self, temperature: F) -> Result<Self, Error> {
+        if temperature < float!(0.0) || !temperature.is_finite() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BasinHopping`: temperature must be >= 0 and finite."
+            ));
+        }
+        self.temperature = temperature;
+        Ok(self)
+    }
+
+    /// Set bounds the perturbed points are clamped to
+    ///
+    /// By default, the perturbed points are not restricted. The local solver itself is not
+    /// restricted to the bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::globalopt::BasinHopping;
+    /// # use argmin::solver::patternsearch::HookeJeeves;
+    /// # let hj = |_: &Vec<f64>| Ok(HookeJeeves::<Vec<f64>, f64>::new());
+    /// let bh: BasinHopping<_, Vec<f64>, f64, _> =
+    ///     BasinHopping::new(hj).with_bounds((vec![-1.0, -1.0], vec![1.0, 1.0]));
+    /// ```
+    #[must_use]
+    pub fn with_bounds(mut self, bounds: (P, P)) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    /// Set maximum number of iterations of each local run
+    ///
+    /// Must be larger than 0 and defaults to `1000`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::globalopt::BasinHopping;
+    /// # use argmin::solver::patternsearch::HookeJeeves;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let hj = |_: &Vec<f64>| Ok(HookeJeeves::<Vec<f64>, f64>::new());
+    /// let bh: BasinHopping<_, Vec<f64>, f64, _> =
+    ///     BasinHopping::new(hj).with_local_max_iters(200)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_local_max_iters(mut self, local_max_iters: u64) -> Result<Self, Error> {
+        if local_max_iters < 1 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BasinHopping`: maximum number of local iterations must be >= 1."
+            ));
+        }
+        self.local_max_iters = local_max_iters;
+        Ok(self)
+    }
+
+    /// Returns the results of all local runs performed so far
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::globalopt::BasinHopping;
+    /// # use argmin::solver::patternsearch::HookeJeeves;
+    /// # let hj = |_: &Vec<f64>| Ok(HookeJeeves::<Vec<f64>, f64>::new());
+    /// # let bh: BasinHopping<_, Vec<f64>, f64, _> = BasinHopping::new(hj);
+    /// let results = bh.local_results();
+    /// # assert!(results.is_empty());
+    /// ```
+    pub fn local_results(&self) -> &[LocalResult<P, F>] {
+        &self.results
+    }
+}
+
+impl<O, S, L, P, G, J, H, R, F, RNG> Solver<O, IterState<P, G, J, H, R, F>>
+    for BasinHopping<S, P, F, RNG>
+where
+    S: Fn(&P) -> Result<L, Error>,
+    L: Solver<O, IterState<P, G, J, H, R, F>>,
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+    RNG: Rng,
+{
+    const NAME: &'static str = "Basin-Hopping";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, J, H, R, F>,
+    ) -> Result<(IterState<P, G, J, H, R, F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`BasinHopping` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        if let Some((lower, upper)) = self.bounds.as_ref() {
+            let n = param.shape();
+            if lower.shape() != n || upper.shape() != n {
+                return Err(argmin_error!(
+                    InvalidParameter,
+                    "`BasinHopping`: bounds must have the same length as the parameter vector."
+                ));
+            }
+            if (0..n).any(|i| lower.get(i) > upper.get(i)) {
+                return Err(argmin_error!(
+                    InvalidParameter,
+                    "`BasinHopping`: lower bound must not be larger than upper bound."
+                ));
+            }
+        }
+
+        self.accepted = 0;
+        let result = local_run(problem, &self.solver, param, self.local_max_iters)?;
+        let kv = kv!("local_cost" => result.cost; "local_iters" => result.iters;);
+        let state = state.param(result.param.clone()).cost(result.cost);
+        self.results = vec![result];
+        Ok((state, Some(kv)))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, J, H, R, F>,
+    ) -> Result<(IterState<P, G, J, H, R, F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`BasinHopping`: Parameter vector in state not set."
+        ))?;
+        let cost = state.get_cost();
+
+        let mut start = param.clone();
+        for i in 0..param.shape() {
+            let delta: f64 = self.rng_generator.gen_range(-1.0..=1.0);
+            let mut xi = param.get(i) + float!(delta) * self.step_size;
+            if let Some((lower, upper)) = self.bounds.as_ref() {
+                xi = xi.max(lower.get(i)).min(upper.get(i));
+            }
+            start.set(i, xi);
+        }
+        let result = local_run(problem, &self.solver, start, self.local_max_iters)?;
+
+        // Metropolis criterion
+        let accepted = result.cost <= cost
+            || (self.temperature > float!(0.0) && {
+                let prob: f64 = self.rng_generator.gen();
+                float!(prob) < (-(result.cost - cost) / self.temperature).exp()
+            });
+        let state = if accepted {
+            self.accepted += 1;
+            state.param(result.param.clone()).cost(result.cost)
+        } else {
+            state.param(param).cost(cost)
+        };
+        let kv = kv!(
+            "accepted" => accepted;
+            "local_cost" => result.cost;
+            "local_iters" => result.iters;
+            "acceptance_rate" => self.accepted as f64 / self.results.len() as f64;
+        );
+        self.results.push(result);
+        Ok((state, Some(kv)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::SixHumpCamel;
+    use super::*;
+    use crate::core::{test_utils::TestProblem, ArgminError, Executor, State};
+    use crate::solver::linesearch::MoreThuenteLineSearch;
+    use crate::solver::patternsearch::HookeJeeves;
+    use crate::solver::quasinewton::LBFGS;
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    type HJ = HookeJeeves<Vec<f64>, f64>;
+    type Factory = fn(&Vec<f64>) -> Result<HJ, Error>;
+
+    test_trait_impl!(
+        basinhopping,
+        BasinHopping<Factory, Vec<f64>, f64, rand::rngs::StdRng>
+    );
+
+    fn hooke_jeeves() -> Factory {
+        |_| HookeJeeves::new().with_min_mesh_size(1e-8)
+    }
+
+    #[test]
+    fn test_new() {
+        let bh: BasinHopping<Factory, Vec<f64>, f64, _> = BasinHopping::new(hooke_jeeves());
+        let BasinHopping {
+            solver: _,
+            step_size,
+            temperature,
+            bounds,
+            local_max_iters,
+            rng_generator: _,
+            accepted,
+            results,
+        } = bh;
+        assert_eq!(step_size.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert_eq!(temperature.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert!(bounds.is_none());
+        assert_eq!(local_max_iters, 1000);
+        assert_eq!(accepted, 0);
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_builders() {
+        let bh: BasinHopping<Factory, Vec<f64>, f64, _> = BasinHopping::new(hooke_jeeves())
+            .with_step_size(2.0)
+            .unwrap()
+            .with_temperature(0.0)
+            .unwrap()
+            .with_bounds((vec![-1.0], vec![1.0]))
+            .with_local_max_iters(20)
+            .unwrap();
+        assert_eq!(bh.step_size.to_ne_bytes(), 2.0f64.to_ne_bytes());
+        assert_eq!(bh.temperature.to_ne_bytes(), 0.0f64.to_ne_bytes());
+        assert_eq!(bh.bounds, Some((vec![-1.0], vec![1.0])));
+        assert_eq!(bh.local_max_iters, 20);
+    }
+
+    #[test]
+    fn test_builder_errors() {
+        for step_size in [0.0, -1.0, f64::INFINITY, f64::NAN] {
+            let res = BasinHopping::<Factory, Vec<f64>, f64, _>::new(hooke_jeeves())
+                .with_step_size(step_size);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`BasinHopping`: step size must be > 0 and finite.\""
+            );
+        }
+        for temperature in [-1.0, f64::INFINITY, f64::NAN] {
+            let res = BasinHopping::<Factory, Vec<f64>, f64, _>::new(hooke_jeeves())
+                .with_temperature(temperature);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`BasinHopping`: temperature must be >= 0 and finite.\""
+            );
+        }
+        let res =
+            BasinHopping::<Factory, Vec<f64>, f64, _>::new(hooke_jeeves()).with_local_max_iters(0);
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`BasinHopping`: maximum number of local iterations must ",
+                "be >= 1.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_init_errors() {
+        let mut bh: BasinHopping<Factory, Vec<f64>, f64, _> = BasinHopping::new(hooke_jeeves());
+        let res = bh.init(&mut Problem::new(TestProblem::new()), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`BasinHopping` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+
+        let mut bh: BasinHopping<Factory, Vec<f64>, f64, _> =
+            BasinHopping::new(hooke_jeeves()).with_bounds((vec![-1.0], vec![1.0]));
+        let res = bh.init(
+            &mut Problem::new(TestProblem::new()),
+            IterState::new().param(vec![0.0, 0.0]),
+        );
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`BasinHopping`: bounds must have the same length as the ",
+                "parameter vector.\""
+            )
+        );
+
+        let mut bh: BasinHopping<Factory, Vec<f64>, f64, _> =
+            BasinHopping::new(hooke_jeeves()).with_bounds((vec![-1.0, 1.0], vec![1.0, -1.0]));
+        let res = bh.init(
+            &mut Problem::new(TestProblem::new()),
+            IterState::new().param(vec![0.0, 0.0]),
+        );
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`BasinHopping`: lower bound must not be larger than upper ",
+                "bound.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_init() {
+        let mut bh: BasinHopping<Factory, Vec<f64>, f64, _> = BasinHopping::new(hooke_jeeves());
+        let mut problem = Problem::new(SixHumpCamel {});
+        let (state, _) = bh
+            .init(&mut problem, IterState::new().param(vec![1.7, -0.8]))
+            .unwrap();
+        // The local minimum of the basin of the initial parameter vector
+        assert_relative_eq!(state.get_cost(), -0.2155, epsilon = 1e-4);
+        assert_relative_eq!(state.get_param().unwrap()[0], 1.7036, epsilon = 1e-3);
+        assert_relative_eq!(state.get_param().unwrap()[1], -0.7961, epsilon = 1e-3);
+        assert_eq!(bh.results.len(), 1);
+        assert_eq!(bh.results[0].start, vec![1.7, -0.8]);
+        assert!(problem.counts["cost_count"] > 0);
+    }
+
+    #[test]
+    fn test_six_hump_camel() {
+        let bh: BasinHopping<Factory, Vec<f64>, f64, _> = BasinHopping::new(hooke_jeeves())
+            .with_bounds((vec![-2.0, -1.0], vec![2.0, 1.0]))
+            .with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(42));
+        let res = Executor::new(SixHumpCamel {}, bh)
+            .configure(|state| state.param(vec![1.7, 0.8]).max_iters(30))
+            .run()
+            .unwrap();
+        assert_relative_eq!(res.state().get_best_cost(), -1.0316, epsilon = 1e-4);
+        let best = res.state().get_best_param().unwrap();
+        assert_relative_eq!(best[0].abs(), 0.0898, epsilon = 1e-3);
+        assert_relative_eq!(best[1].abs(), 0.7126, epsilon = 1e-3);
+        assert_eq!(res.solver().local_results().len(), 31);
+    }
+
+    #[test]
+    fn test_zero_temperature() {
+        let bh: BasinHopping<Factory, Vec<f64>, f64, _> = BasinHopping::new(hooke_jeeves())
+            .with_temperature(0.0)
+            .unwrap()
+            .with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(7));
+        let res = Executor::new(SixHumpCamel {}, bh)
+            .configure(|state| state.param(vec![1.7, 0.8]).max_iters(10))
+            .run()
+            .unwrap();
+        // Only improvements are accepted, therefore the current cost never increases
+        let results = res.solver().local_results();
+        let mut current = results[0].cost;
+        for result in &results[1..] {
+            current = current.min(result.cost);
+        }
+        assert_relative_eq!(res.state().get_cost(), current, epsilon = f64::EPSILON);
+    }
+
+    #[test]
+    fn test_func_counts() {
+        let linesearch = MoreThuenteLineSearch::new();
+        let lbfgs: LBFGS<_, Vec<f64>, Vec<f64>, f64> = LBFGS::new(linesearch, 5);
+        let bh = BasinHopping::new(move |_| Ok(lbfgs.clone()))
+            .with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(1729));
+        let res = Executor::new(SixHumpCamel {}, bh)
+            .configure(|state| state.param(vec![1.0, 0.5]).max_iters(3))
+            .run()
+            .unwrap();
+
+        let mut cost_count = 0;
+        let mut gradient_count = 0;
+        for result in res.solver().local_results() {
+            let local = Executor::new(SixHumpCamel {}, LBFGS::new(MoreThuenteLineSearch::new(), 5))
+                .configure(|state| state.param(result.start.clone()).max_iters(1000))
+                .ctrlc(false)
+                .run()
+                .unwrap();
+            assert_eq!(local.state().get_iter(), result.iters);
+            let counts = local.state().get_func_counts();
+            cost_count += counts["cost_count"];
+            gradient_count += counts["gradient_count"];
+        }
+        assert_eq!(res.solver().local_results().len(), 4);
+        let counts = res.state().get_func_counts();
+        assert_eq!(counts["cost_count"], cost_count);
+        assert_eq!(counts["gradient_count"], gradient_count);
+    }
+}
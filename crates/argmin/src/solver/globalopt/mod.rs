@@ -0,0 +1,147 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Global optimization meta-solvers
+//!
+//! Meta-solvers which wrap a local solver and run it to completion from many start points in
+//! order to find the global minimum of a cost function with several local minima.
+//!
+//! * [Multi-start](`MultiStart`) runs the local solver from a fixed set of start points, which
+//!   are spread over a box via a Sobol sequence or Latin hypercube sampling.
+//! * [Basin-hopping](`BasinHopping`) randomly perturbs the current local minimum, runs the local
+//!   solver from the perturbed point and accepts the new local minimum according to the
+//!   Metropolis criterion.
+//!
+//! The local solver of every run is constructed for its start point by a function passed to the
+//! meta-solver, such that solvers which are initialized via their constructor (for instance the
+//! initial simplex of [`NelderMead`](`crate::solver::neldermead::NelderMead`)) can be used as
+//! well. The start point is also set as initial parameter vector of the local run. Every local
+//! run is executed within a single iteration of the meta-solver via an
+//! [`Executor`](`crate::core::Executor`) of its own. The function
+//! evaluations of all local runs are added to the function counts of the outer problem (like
+//! [`Problem::consume_problem`](`crate::core::Problem::consume_problem`) does) and the result of
+//! every local run is recorded as a [`LocalResult`].
+//!
+//! The meta-solvers use the same state as the local solver, but only set parameter vector and
+//! cost function value.
+//!
+//! ## References
+//!
+//! \[0\] David J. Wales and Jonathan P. K. Doye (1997). Global Optimization by Basin-Hopping and
+//! the Lowest Energy Structures of Lennard-Jones Clusters Containing up to 110 Atoms. The Journal
+//! of Physical Chemistry A 101 (28), 5111–5116. DOI: <https://doi.org/10.1021/jp970984n>
+//!
+//! \[1\] Rafael Martí, Mauricio G. C. Resende and Celso C. Ribeiro (2013). Multi-start methods
+//! for combinatorial optimization. European Journal of Operational Research 226 (1), 1–8.
+//! DOI: <https://doi.org/10.1016/j.ejor.2012.10.012>
+//!
+//! \[2\] Stephen Joe and Frances Y. Kuo (2008). Constructing Sobol Sequences with Better
+//! Two-Dimensional Projections. SIAM Journal on Scientific Computing 30 (5), 2635–2654.
+//! DOI: <https://doi.org/10.1137/070709359>
+
+mod basinhopping;
+mod multistart;
+
+pub use self::basinhopping::*;
+pub use self::multistart::*;
+
+use crate::core::{
+    ArgminFloat, Error, Executor, IterState, OptimizationResult, Problem, Solver, State,
+    TerminationStatus,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Result of a single run of the local solver of a meta-solver
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct LocalResult<P, F> {
+    /// Start point of the local run
+    pub start: P,
+    /// Best parameter vector found by the local run
+    pub param: P,
+    /// Cost function value of `param`
+    pub cost: F,
+    /// Number of iterations of the local run
+    pub iters: u64,
+    /// Termination status of the local run
+    pub termination_status: TerminationStatus,
+}
+
+/// Constructs the local solver for `start` via `solver`, runs it from `start` for at most
+/// `max_iters` iterations and merges its function counts into those of `problem`
+fn local_run<O, S, L, P, G, J, H, R, F>(
+    problem: &mut Problem<O>,
+    solver: &S,
+    start: P,
+    max_iters: u64,
+) -> Result<LocalResult<P, F>, Error>
+where
+    S: Fn(&P) -> Result<L, Error>,
+    L: Solver<O, IterState<P, G, J, H, R, F>>,
+    P: Clone,
+    F: ArgminFloat,
+{
+    let local_solver = solver(&start)?;
+    let local_problem = problem.take_problem().ok_or_else(argmin_error_closure!(
+        PotentialBug,
+        "Problem of meta-solver is not available for a local run."
+    ))?;
+    let OptimizationResult {
+        problem: local_problem,
+        mut state,
+        ..
+    } = Executor::new(local_problem, local_solver)
+        .configure(|state| state.param(start.clone()).max_iters(max_iters))
+        .ctrlc(false)
+        .run()?;
+    problem.consume_problem(local_problem);
+
+    let cost = state.get_best_cost();
+    let param = state.take_best_param().unwrap_or_else(|| start.clone());
+    Ok(LocalResult {
+        start,
+        param,
+        cost,
+        iters: state.get_iter(),
+        termination_status: state.get_termination_status().clone(),
+    })
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use crate::core::{CostFunction, Error, Gradient};
+
+    /// Six-hump camel function with two global minima `-1.0316` at `(0.0898, -0.7126)` and
+    /// `(-0.0898, 0.7126)` and four further local minima
+    pub(super) struct SixHumpCamel {}
+
+    impl CostFunction for SixHumpCamel {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            let (x, y) = (p[0], p[1]);
+            Ok((4.0 - 2.1 * x.powi(2) + x.powi(4) / 3.0) * x.powi(2)
+                + x * y
+                + (-4.0 + 4.0 * y.powi(2)) * y.powi(2))
+        }
+    }
+
+    impl Gradient for SixHumpCamel {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            let (x, y) = (p[0], p[1]);
+            Ok(vec![
+                8.0 * x - 8.4 * x.powi(3) + 2.0 * x.powi(5) + y,
+                x - 8.0 * y + 16.0 * y.powi(3),
+            ])
+        }
+    }
+}
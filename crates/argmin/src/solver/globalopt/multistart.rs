@@ -0,0 +1,708 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::{local_run, LocalResult};
+use crate::core::{
+    ArgminFloat, Error, IterState, Problem, Solver, TerminationReason, TerminationStatus, KV,
+};
+use argmin_math::{ArgminGet, ArgminSet, ArgminSize};
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Distribution of the start points of [`MultiStart`] within the bounds
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum StartPoints {
+    /// Sobol low-discrepancy sequence with the direction numbers of Joe and Kuo \[2\]
+    ///
+    /// Deterministic and supports up to 21 dimensions. The first point of the sequence (the lower
+    /// bound) is skipped.
+    #[default]
+    Sobol,
+    /// Latin hypercube sampling
+    ///
+    /// Random, but every coordinate axis is divided into `num_starts` intervals of equal length
+    /// and every interval holds the corresponding coordinate of exactly one start point.
+    LatinHypercube,
+}
+
+/// # Multi-start
+///
+/// Runs a local solver from `num_starts` start points which are spread over the box
+/// `lower <= x <= upper`, either via a Sobol sequence (default) or via Latin hypercube sampling
+/// (see [`StartPoints`]). If an initial parameter vector is provided, it is used as an additional
+/// first start point. The local solver itself is not restricted to the box.
+///
+/// Each iteration performs one complete local run, using a local solver which is constructed for
+/// the start point (see [`new`](`MultiStart::new`)) and at most `local_max_iters` iterations
+/// (default: `1000`). Parameter vector and cost function value of
+/// the state are the result of the most recent local run, therefore the best local minimum is
+/// available as best parameter vector. The solver terminates once all start points have been
+/// used. Index of the start point, final cost function value and number of iterations of each
+/// local run are reported as KV metrics `start`, `local_cost` and `local_iters`, and all results
+/// are available via [`local_results`](`MultiStart::local_results`). The function counts of the
+/// local runs are added to those of the problem.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to satisfy the requirements of the local solver.
+///
+/// ## References
+///
+/// \[1\] Rafael Martí, Mauricio G. C. Resende and Celso C. Ribeiro (2013). Multi-start methods
+/// for combinatorial optimization. European Journal of Operational Research 226 (1), 1–8.
+/// DOI: <https://doi.org/10.1016/j.ejor.2012.10.012>
+///
+/// \[2\] Stephen Joe and Frances Y. Kuo (2008). Constructing Sobol Sequences with Better
+/// Two-Dimensional Projections. SIAM Journal on Scientific Computing 30 (5), 2635–2654.
+/// DOI: <https://doi.org/10.1137/070709359>
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct MultiStart<S, P, F, R> {
+    /// Constructs the local solver for a start point
+    solver: S,
+    /// Bounds on parameter space
+    bounds: (P, P),
+    /// Number of start points within the bounds
+    num_starts: usize,
+    /// Distribution of the start points
+    start_points: StartPoints,
+    /// Maximum number of iterations of each local run
+    local_max_iters: u64,
+    /// Random number generator
+    rng_generator: R,
+    /// Start points of all local runs
+    starts: Vec<P>,
+    /// Results of the local runs performed so far
+    results: Vec<LocalResult<P, F>>,
+}
+
+impl<S, P, F> MultiStart<S, P, F, rand::rngs::StdRng> {
+    /// Construct a new instance of `MultiStart`
+    ///
+    /// Takes a function which constructs the local solver for a given start point, the bounds of
+    /// the box the start points are taken from and the number of start points. Solvers which
+    /// start from the initial parameter vector of their state can simply be cloned, while others
+    /// need to be set up for the start point.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::globalopt::MultiStart;
+    /// # use argmin::solver::neldermead::NelderMead;
+    /// # use argmin::solver::patternsearch::HookeJeeves;
+    /// let hj: HookeJeeves<Vec<f64>, f64> = HookeJeeves::new();
+    /// let ms: MultiStart<_, Vec<f64>, f64, _> =
+    ///     MultiStart::new(move |_| Ok(hj.clone()), (vec![-1.0, -1.0], vec![1.0, 1.0]), 20);
+    ///
+    /// // Nelder-Mead with an initial simplex around the start point
+    /// let ms: MultiStart<_, Vec<f64>, f64, _> = MultiStart::new(
+    ///     |x: &Vec<f64>| {
+    ///         let simplex = vec![x.clone(), vec![x[0] + 0.1, x[1]], vec![x[0], x[1] + 0.1]];
+    ///         NelderMead::<_, f64>::new(simplex).with_sd_tolerance(1e-8)
+    ///     },
+    ///     (vec![-1.0, -1.0], vec![1.0, 1.0]),
+    ///     20,
+    /// );
+    /// ```
+    pub fn new<L>(solver: S, bounds: (P, P), num_starts: usize) -> Self
+    where
+        S: Fn(&P) -> Result<L, Error>,
+    {
+        MultiStart {
+            solver,
+            bounds,
+            num_starts,
+            start_points: StartPoints::default(),
+            local_max_iters: 1000,
+            rng_generator: rand::rngs::StdRng::from_entropy(),
+            starts: vec![],
+            results: vec![],
+        }
+    }
+}
+
+impl<S, P, F, R0> MultiStart<S, P, F, R0> {
+    /// Set random number generator
+    ///
+    /// Defaults to `rand::rngs::StdRng::from_entropy()`. The random number generator is only used
+    /// for Latin hypercube sampling.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::globalopt::MultiStart;
+    /// # use argmin::solver::patternsearch::HookeJeeves;
+    /// # use rand::SeedableRng;
+    /// # let hj = |_: &Vec<f64>| Ok(HookeJeeves::<Vec<f64>, f64>::new());
+    /// let ms: MultiStart<_, Vec<f64>, f64, _> =
+    ///     MultiStart::new(hj, (vec![-1.0, -1.0], vec![1.0, 1.0]), 20)
+    ///         .with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(1729));
+    /// ```
+    pub fn with_rng_generator<R1: Rng>(self, generator: R1) -> MultiStart<S, P, F, R1> {
+        MultiStart {
+            solver: self.solver,
+            bounds: self.bounds,
+            num_starts: self.num_starts,
+            start_points: self.start_points,
+            local_max_iters: self.local_max_iters,
+            rng_generator: generator,
+            starts: self.starts,
+            results: self.results,
+        }
+    }
+}
+
+impl<S, P, F, R> MultiStart<S, P, F, R> {
+    /// Set distribution of the start points
+    ///
+    /// Defaults to [`StartPoints::Sobol`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::globalopt::{MultiStart, StartPoints};
+    /// # use argmin::solver::patternsearch::HookeJeeves;
+    /// # let hj = |_: &Vec<f64>| Ok(HookeJeeves::<Vec<f64>, f64>::new());
+    /// let ms: MultiStart<_, Vec<f64>, f64, _> =
+    ///     MultiStart::new(hj, (vec![-1.0, -1.0], vec![1.0, 1.0]), 20)
+    ///         .with_start_points(StartPoints::LatinHypercube);
+    /// ```
+    #[must_use]
+    pub fn with_start_points(mut self, start_points: StartPoints) -> Self {
+        self.start_points = start_points;
+        self
+    }
+
+    /// Set maximum number of iterations of each local run
+    ///
+    /// Must be larger than 0 and defaults to `1000`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::globalopt::MultiStart;
+    /// # use argmin::solver::patternsearch::HookeJeeves;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let hj = |_: &Vec<f64>| Ok(HookeJeeves::<Vec<f64>, f64>::new());
+    /// let ms: MultiStart<_, Vec<f64>, f64, _> =
+    ///     MultiStart::new(hj, (vec![-1.0, -1.0], vec![1.0, 1.0]), 20)
+    ///         .with_local_max_iters(200)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_local_max_iters(mut self, local_max_iters: u64) -> Result<Self, Error> {
+        if local_max_iters < 1 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`MultiStart`: maximum number of local iterations must be >= 1."
+            ));
+        }
+        self.local_max_iters = local_max_iters;
+        Ok(self)
+    }
+
+    /// Returns the results of all local runs performed so far
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::globalopt::MultiStart;
+    /// # use argmin::solver::patternsearch::HookeJeeves;
+    /// # let hj = |_: &Vec<f64>| Ok(HookeJeeves::<Vec<f64>, f64>::new());
+    /// # let ms: MultiStart<_, Vec<f64>, f64, _> =
+    /// #     MultiStart::new(hj, (vec![-1.0, -1.0], vec![1.0, 1.0]), 20);
+    /// let results = ms.local_results();
+    /// # assert!(results.is_empty());
+    /// ```
+    pub fn local_results(&self) -> &[LocalResult<P, F>] {
+        &self.results
+    }
+}
+
+impl<O, S, L, P, G, J, H, R, F, RNG> Solver<O, IterState<P, G, J, H, R, F>>
+    for MultiStart<S, P, F, RNG>
+where
+    S: Fn(&P) -> Result<L, Error>,
+    L: Solver<O, IterState<P, G, J, H, R, F>>,
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+    RNG: Rng,
+{
+    const NAME: &'static str = "Multi-Start";
+
+    fn init(
+        &mut self,
+        _problem: &mut Problem<O>,
+        mut state: IterState<P, G, J, H, R, F>,
+    ) -> Result<(IterState<P, G, J, H, R, F>, Option<KV>), Error> {
+        let n = self.bounds.0.shape();
+        if n < 1 || self.bounds.1.shape() != n {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`MultiStart`: lower and upper bound must have the same, non-zero length."
+            ));
+        }
+        let lower: Vec<F> = (0..n).map(|i| self.bounds.0.get(i)).collect();
+        let upper: Vec<F> = (0..n).map(|i| self.bounds.1.get(i)).collect();
+        if lower
+            .iter()
+            .zip(upper.iter())
+            .any(|(&l, &u)| l > u || !l.is_finite() || !u.is_finite())
+        {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`MultiStart`: lower bound must be finite and not larger than the finite upper bound."
+            ));
+        }
+        if self.num_starts < 1 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`MultiStart`: number of start points must be >= 1."
+            ));
+        }
+
+        let xs = match self.start_points {
+            StartPoints::Sobol => sobol(n, self.num_starts)?,
+            StartPoints::LatinHypercube => {
+                latin_hypercube(n, self.num_starts, &mut self.rng_generator)
+            }
+        };
+        self.starts = vec![];
+        if let Some(init_param) = state.take_param() {
+            if init_param.shape() != n {
+                return Err(argmin_error!(
+                    InvalidParameter,
+                    "`MultiStart`: initial parameter vector must have the same length as the bounds."
+                ));
+            }
+            self.starts.push(init_param);
+        }
+        for x in xs {
+            let mut start = self.bounds.0.clone();
+            for (i, xi) in x.into_iter().enumerate() {
+                start.set(i, lower[i] + float!(xi) * (upper[i] - lower[i]));
+            }
+            self.starts.push(start);
+        }
+        self.results = vec![];
+
+        Ok((state, Some(kv!("starts" => self.starts.len() as u64;))))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, G, J, H, R, F>,
+    ) -> Result<(IterState<P, G, J, H, R, F>, Option<KV>), Error> {
+        let k = self.results.len();
+        let start = self.starts[k].clone();
+        let result = local_run(problem, &self.solver, start, self.local_max_iters)?;
+        let kv = kv!(
+            "start" => k as u64;
+            "local_cost" => result.cost;
+            "local_iters" => result.iters;
+        );
+        let state = state.param(result.param.clone()).cost(result.cost);
+        self.results.push(result);
+        Ok((state, Some(kv)))
+    }
+
+    fn terminate(&mut self, _state: &IterState<P, G, J, H, R, F>) -> TerminationStatus {
+        if self.results.len() >= self.starts.len() {
+            return TerminationStatus::Terminated(TerminationReason::SolverExit(
+                "All local runs completed".to_string(),
+            ));
+        }
+        TerminationStatus::NotTerminated
+    }
+}
+
+/// Latin hypercube sample of `m` points in the `n`-dimensional unit cube
+fn latin_hypercube<R: Rng>(n: usize, m: usize, rng: &mut R) -> Vec<Vec<f64>> {
+    let mut xs = vec![vec![0.0; n]; m];
+    for j in 0..n {
+        let mut strata: Vec<usize> = (0..m).collect();
+        for i in (1..m).rev() {
+            strata.swap(i, rng.gen_range(0..=i));
+        }
+        for (x, stratum) in xs.iter_mut().zip(strata) {
+            let u: f64 = rng.gen();
+            x[j] = (stratum as f64 + u) / m as f64;
+        }
+    }
+    xs
+}
+
+/// Degree `s`, coefficients `a` and initial direction numbers `m` of the primitive polynomials of
+/// dimensions 2 to 21 of the Sobol sequence (`new-joe-kuo-6.21201` of \[2\])
+const SOBOL_PARAMETERS: [(usize, u32, &[u32]); 20] = [
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+    (5, 4, &[1, 1, 5, 5, 5]),
+    (5, 7, &[1, 1, 7, 11, 19]),
+    (5, 11, &[1, 1, 5, 1, 1]),
+    (5, 13, &[1, 1, 1, 3, 11]),
+    (5, 14, &[1, 3, 5, 5, 31]),
+    (6, 1, &[1, 3, 3, 9, 7, 49]),
+    (6, 13, &[1, 1, 1, 15, 21, 21]),
+    (6, 16, &[1, 3, 1, 13, 27, 49]),
+    (6, 19, &[1, 1, 1, 15, 7, 5]),
+    (6, 22, &[1, 3, 1, 15, 13, 25]),
+    (6, 25, &[1, 1, 5, 5, 19, 61]),
+    (7, 1, &[1, 3, 7, 11, 23, 15, 103]),
+    (7, 4, &[1, 3, 7, 13, 13, 15, 69]),
+];
+
+/// Number of bits of the Sobol points
+const SOBOL_BITS: usize = 32;
+
+/// Direction numbers `v_k = m_k / 2^(k + 1)` (as fixed-point numbers) of dimension `j + 1`
+fn sobol_directions(j: usize) -> [u32; SOBOL_BITS] {
+    let mut v = [0u32; SOBOL_BITS];
+    if j == 0 {
+        // The first dimension is the van der Corput sequence
+        for (k, vk) in v.iter_mut().enumerate() {
+            *vk = 1 << (SOBOL_BITS - 1 - k);
+        }
+        return v;
+    }
+    let (s, a, m) = SOBOL_PARAMETERS[j - 1];
+    for k in 0..SOBOL_BITS {
+        v[k] = if k < s {
+            m[k] << (SOBOL_BITS - 1 - k)
+        } else {
+            (1..s).fold(v[k - s] ^ (v[k - s] >> s), |acc, i| {
+                if (a >> (s - 1 - i)) & 1 == 1 {
+                    acc ^ v[k - i]
+                } else {
+                    acc
+                }
+            })
+        };
+    }
+    v
+}
+
+/// The first `m` points of the `n`-dimensional Sobol sequence, skipping the origin
+fn sobol(n: usize, m: usize) -> Result<Vec<Vec<f64>>, Error> {
+    if n > SOBOL_PARAMETERS.len() + 1 {
+        return Err(argmin_error!(
+            InvalidParameter,
+            format!(
+                "`MultiStart`: Sobol start points are only available for up to {} dimensions.",
+                SOBOL_PARAMETERS.len() + 1
+            )
+        ));
+    }
+    let directions: Vec<[u32; SOBOL_BITS]> = (0..n).map(sobol_directions).collect();
+    let scale = 2.0f64.powi(SOBOL_BITS as i32);
+    let mut x = vec![0u32; n];
+    Ok((0..m)
+        .map(|i| {
+            // Gray code construction: flip the direction number of the lowest zero bit of `i`
+            let c = i.trailing_ones() as usize;
+            for (xj, vj) in x.iter_mut().zip(directions.iter()) {
+                *xj ^= vj[c];
+            }
+            x.iter().map(|&xj| f64::from(xj) / scale).collect()
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::SixHumpCamel;
+    use super::*;
+    use crate::core::{test_utils::TestProblem, ArgminError, Executor, State};
+    use crate::solver::linesearch::MoreThuenteLineSearch;
+    use crate::solver::neldermead::NelderMead;
+    use crate::solver::patternsearch::HookeJeeves;
+    use crate::solver::quasinewton::LBFGS;
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    type HJ = HookeJeeves<Vec<f64>, f64>;
+    type Factory = fn(&Vec<f64>) -> Result<HJ, Error>;
+
+    test_trait_impl!(
+        multistart,
+        MultiStart<Factory, Vec<f64>, f64, rand::rngs::StdRng>
+    );
+
+    fn hooke_jeeves() -> Factory {
+        |_| HookeJeeves::new().with_min_mesh_size(1e-8)
+    }
+
+    #[test]
+    fn test_new() {
+        let ms: MultiStart<Factory, Vec<f64>, f64, _> =
+            MultiStart::new(hooke_jeeves(), (vec![-1.0, -2.0], vec![1.0, 2.0]), 10);
+        let MultiStart {
+            solver: _,
+            bounds,
+            num_starts,
+            start_points,
+            local_max_iters,
+            rng_generator: _,
+            starts,
+            results,
+        } = ms;
+        assert_eq!(bounds, (vec![-1.0, -2.0], vec![1.0, 2.0]));
+        assert_eq!(num_starts, 10);
+        assert_eq!(start_points, StartPoints::Sobol);
+        assert_eq!(local_max_iters, 1000);
+        assert!(starts.is_empty());
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_builders() {
+        let ms: MultiStart<Factory, Vec<f64>, f64, _> =
+            MultiStart::new(hooke_jeeves(), (vec![-1.0, -2.0], vec![1.0, 2.0]), 10)
+                .with_start_points(StartPoints::LatinHypercube)
+                .with_local_max_iters(50)
+                .unwrap();
+        assert_eq!(ms.start_points, StartPoints::LatinHypercube);
+        assert_eq!(ms.local_max_iters, 50);
+
+        let res = ms.with_local_max_iters(0);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`MultiStart`: maximum number of local iterations must be >= 1.\""
+        );
+    }
+
+    #[test]
+    fn test_sobol() {
+        let xs = sobol(2, 7).unwrap();
+        let expected = [
+            [0.5, 0.5],
+            [0.75, 0.25],
+            [0.25, 0.75],
+            [0.375, 0.375],
+            [0.875, 0.875],
+            [0.625, 0.125],
+            [0.125, 0.625],
+        ];
+        for (x, e) in xs.iter().zip(expected.iter()) {
+            assert_relative_eq!(x[0], e[0], epsilon = f64::EPSILON);
+            assert_relative_eq!(x[1], e[1], epsilon = f64::EPSILON);
+        }
+
+        // Together with the origin, the first 2^k points hold one point in each interval of
+        // length 2^-k of every coordinate axis
+        let n = SOBOL_PARAMETERS.len() + 1;
+        let xs = sobol(n, 255).unwrap();
+        for j in 0..n {
+            let mut strata: Vec<usize> = xs
+                .iter()
+                .map(|x| (x[j] * 256.0).floor() as usize)
+                .chain(std::iter::once(0))
+                .collect();
+            strata.sort_unstable();
+            assert_eq!(strata, (0..256).collect::<Vec<_>>());
+        }
+
+        assert_error!(
+            sobol(22, 10),
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`MultiStart`: Sobol start points are only available for up ",
+                "to 21 dimensions.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_sobol_parameters() {
+        // Initial direction numbers must be odd and smaller than 2^k
+        for (s, a, m) in SOBOL_PARAMETERS {
+            assert_eq!(m.len(), s);
+            assert!(a < 1 << (s - 1).max(1));
+            for (k, &mk) in m.iter().enumerate() {
+                assert_eq!(mk % 2, 1);
+                assert!(mk < 1 << (k + 1));
+            }
+        }
+    }
+
+    #[test]
+    fn test_init() {
+        let mut ms: MultiStart<Factory, Vec<f64>, f64, _> =
+            MultiStart::new(hooke_jeeves(), (vec![-1.0, 2.0], vec![3.0, 4.0]), 4);
+        let (state, kv) = ms
+            .init(
+                &mut Problem::new(TestProblem::new()),
+                IterState::new().param(vec![0.0, 0.0]),
+            )
+            .unwrap();
+        assert!(state.get_param().is_none());
+        assert_eq!(kv.unwrap().get("starts").unwrap().get_uint(), Some(5));
+        // The initial parameter vector is used as first start point
+        assert_eq!(ms.starts[0], vec![0.0, 0.0]);
+        assert_eq!(ms.starts[1], vec![1.0, 3.0]);
+        assert_eq!(ms.starts[2], vec![2.0, 2.5]);
+        assert_eq!(ms.starts[3], vec![0.0, 3.5]);
+        assert_eq!(ms.starts[4], vec![0.5, 2.75]);
+
+        let mut ms: MultiStart<Factory, Vec<f64>, f64, _> =
+            MultiStart::new(hooke_jeeves(), (vec![-1.0, 2.0], vec![3.0, 4.0]), 8)
+                .with_start_points(StartPoints::LatinHypercube)
+                .with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(42));
+        ms.init(&mut Problem::new(TestProblem::new()), IterState::new())
+            .unwrap();
+        assert_eq!(ms.starts.len(), 8);
+        let mut strata: Vec<usize> = ms
+            .starts
+            .iter()
+            .map(|x| ((x[1] - 2.0) * 4.0).floor() as usize)
+            .collect();
+        strata.sort_unstable();
+        assert_eq!(strata, (0..8).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_init_errors() {
+        for bounds in [
+            (vec![-1.0, 1.0], vec![1.0, 0.0]),
+            (vec![-1.0, f64::NAN], vec![1.0, 1.0]),
+        ] {
+            let mut ms: MultiStart<Factory, Vec<f64>, f64, _> =
+                MultiStart::new(hooke_jeeves(), bounds, 4);
+            let res = ms.init(&mut Problem::new(TestProblem::new()), IterState::new());
+            assert_error!(
+                res,
+                ArgminError,
+                concat!(
+                    "Invalid parameter: \"`MultiStart`: lower bound must be finite and not ",
+                    "larger than the finite upper bound.\""
+                )
+            );
+        }
+        let mut ms: MultiStart<Factory, Vec<f64>, f64, _> =
+            MultiStart::new(hooke_jeeves(), (vec![-1.0], vec![1.0, 1.0]), 4);
+        let res = ms.init(&mut Problem::new(TestProblem::new()), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`MultiStart`: lower and upper bound must have the same, ",
+                "non-zero length.\""
+            )
+        );
+        let mut ms: MultiStart<Factory, Vec<f64>, f64, _> =
+            MultiStart::new(hooke_jeeves(), (vec![-1.0, -1.0], vec![1.0, 1.0]), 0);
+        let res = ms.init(&mut Problem::new(TestProblem::new()), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`MultiStart`: number of start points must be >= 1.\""
+        );
+        let mut ms: MultiStart<Factory, Vec<f64>, f64, _> =
+            MultiStart::new(hooke_jeeves(), (vec![-1.0, -1.0], vec![1.0, 1.0]), 4);
+        let res = ms.init(
+            &mut Problem::new(TestProblem::new()),
+            IterState::new().param(vec![0.0]),
+        );
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`MultiStart`: initial parameter vector must have the same ",
+                "length as the bounds.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_six_hump_camel() {
+        let ms: MultiStart<Factory, Vec<f64>, f64, _> =
+            MultiStart::new(hooke_jeeves(), (vec![-2.0, -1.0], vec![2.0, 1.0]), 15);
+        let res = Executor::new(SixHumpCamel {}, ms).run().unwrap();
+        assert_eq!(
+            res.state().get_termination_reason(),
+            Some(&TerminationReason::SolverExit(
+                "All local runs completed".to_string()
+            ))
+        );
+        assert_eq!(res.state().get_iter(), 15);
+        assert_relative_eq!(res.state().get_best_cost(), -1.0316, epsilon = 1e-4);
+        let best = res.state().get_best_param().unwrap();
+        assert_relative_eq!(best[0].abs(), 0.0898, epsilon = 1e-3);
+        assert_relative_eq!(best[1].abs(), 0.7126, epsilon = 1e-3);
+
+        // Every local run is recorded and its cost function evaluations are counted
+        let results = res.solver().local_results();
+        assert_eq!(results.len(), 15);
+        let best_local = results.iter().map(|r| r.cost).fold(f64::INFINITY, f64::min);
+        assert_relative_eq!(best_local, res.state().get_best_cost(), epsilon = 1e-12);
+        assert!(results.iter().all(|r| r.termination_status.terminated()));
+        assert!(res.state().get_func_counts()["cost_count"] >= 15 * 3);
+    }
+
+    #[test]
+    fn test_nelder_mead() {
+        // The initial simplex is constructed around each start point
+        let ms: MultiStart<_, Vec<f64>, f64, _> = MultiStart::new(
+            |x: &Vec<f64>| {
+                let simplex = vec![x.clone(), vec![x[0] + 0.1, x[1]], vec![x[0], x[1] + 0.1]];
+                NelderMead::new(simplex).with_sd_tolerance(1e-10)
+            },
+            (vec![-2.0, -1.0], vec![2.0, 1.0]),
+            7,
+        );
+        let res = Executor::new(SixHumpCamel {}, ms).run().unwrap();
+        assert_relative_eq!(res.state().get_best_cost(), -1.0316, epsilon = 1e-4);
+
+        let results = res.solver().local_results();
+        assert_eq!(results.len(), 7);
+        assert!(results.iter().all(|r| r.termination_status.terminated()));
+        // Runs from different start points end up in different local minima
+        let first = &results[0].param;
+        assert!(results
+            .iter()
+            .any(|r| (r.param[0] - first[0]).hypot(r.param[1] - first[1]) > 0.1));
+    }
+
+    #[test]
+    fn test_func_counts() {
+        let linesearch = MoreThuenteLineSearch::new();
+        let lbfgs: LBFGS<_, Vec<f64>, Vec<f64>, f64> = LBFGS::new(linesearch, 5);
+        let ms = MultiStart::new(
+            move |_| Ok(lbfgs.clone()),
+            (vec![-2.0, -1.0], vec![2.0, 1.0]),
+            3,
+        );
+        let res = Executor::new(SixHumpCamel {}, ms).run().unwrap();
+
+        let mut cost_count = 0;
+        let mut gradient_count = 0;
+        for result in res.solver().local_results() {
+            let local = Executor::new(SixHumpCamel {}, LBFGS::new(MoreThuenteLineSearch::new(), 5))
+                .configure(|state| state.param(result.start.clone()).max_iters(1000))
+                .ctrlc(false)
+                .run()
+                .unwrap();
+            assert_eq!(local.state().get_iter(), result.iters);
+            let counts = local.state().get_func_counts();
+            cost_count += counts["cost_count"];
+            gradient_count += counts["gradient_count"];
+        }
+        let counts = res.state().get_func_counts();
+        assert_eq!(counts["cost_count"], cost_count);
+        assert_eq!(counts["gradient_count"], gradient_count);
+    }
+}
@@ -0,0 +1,932 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Interior point method
+//!
+//! Primal-dual interior point method (Mehrotra predictor-corrector) for linear programs and convex
+//! quadratic programs in standard form.
+//!
+//! See [`InteriorPoint`] for details.
+//!
+//! ## Reference
+//!
+//! Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+//! Springer. ISBN 0-387-30303-0.
+
+use crate::core::{
+    ArgminFloat, Error, LinearProgram, LinearProgramState, Problem, Solver, TerminationReason,
+    TerminationStatus, KV,
+};
+use crate::solver::dense::{dot, mat_t_vec, mat_vec, norm};
+use argmin_math::{ArgminCholesky, ArgminCholeskySolve, ArgminEye, ArgminGet, ArgminSet};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Interior point method
+///
+/// Primal-dual interior point method with Mehrotra's predictor-corrector scheme for linear
+/// programs and convex quadratic programs of the form
+///
+/// ```text
+/// min_x  1/2 x^T Q x + c^T x
+/// s.t.   A x = b
+///        x >= 0
+/// ```
+///
+/// where the quadratic term is optional (see [`LinearProgram::Q`]).
+///
+/// Starting from `x = 1`, `s = 1` and `y = 0` (where `y` are the Lagrange multipliers of the
+/// equality constraints and `s` those of the non-negativity constraints), each iteration computes
+/// an affine scaling (predictor) step, uses it to choose the centering parameter and then computes
+/// the corrector step. Both steps require the solution of the same linear system
+///
+/// ```text
+/// | H   -A^T | |dx|   |r1|
+/// | A    0   | |dy| = |r2|
+/// ```
+///
+/// with `H = Q + X^-1 S`, which is reduced to the normal equations `A H^-1 A^T dy = r2 - A H^-1 r1`.
+/// For linear programs, `H^-1 = X S^-1` is diagonal and the normal matrix is `A X S^-1 A^T`. The
+/// normal matrix (and `H` for quadratic programs) is Cholesky factorized once per iteration and the
+/// factorization is reused for the predictor and the corrector step. The factorizations and solves
+/// are carried out by the linear algebra backends of `argmin-math`: the type parameters `M` and
+/// `V` are the matrix and vector types used for this purpose (for instance `ndarray::Array2<f64>`
+/// and `ndarray::Array1<f64>` or `nalgebra::DMatrix<f64>` and `nalgebra::DVector<f64>`). The step
+/// lengths are chosen as fraction `eta` of the maximum step length which keeps `x` and `s`
+/// positive. For linear programs, the primal and dual step lengths are chosen independently.
+///
+/// The solver terminates when the relative primal residual `||A x - b|| / (1 + ||b||)`, the
+/// relative dual residual `||Q x + c - A^T y - s|| / (1 + ||c||)` and the relative duality gap
+/// `x^T s / (1 + |1/2 x^T Q x + c^T x|)` are below the tolerance. These quantities (in absolute
+/// terms) are reported to the observers as `primal_residual`, `dual_residual` and `duality_gap`.
+///
+/// Since the iterates are in general infeasible, the cost function value is reported as infinity
+/// until these criteria are met. The Lagrange multipliers `y` of the equality constraints are
+/// stored as dual values in the [`LinearProgramState`].
+///
+/// Infeasible and unbounded problems are detected by diverging iterates: If the norm of the dual
+/// variables `(y, s)` exceeds `divergence_threshold * (1 + ||b|| + ||c||)`, the problem is
+/// considered infeasible and the solver terminates with [`TerminationReason::Infeasible`]. If the
+/// norm of the primal variables `x` exceeds this bound, the problem is considered unbounded and the
+/// solver terminates with [`TerminationReason::Unbounded`].
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`LinearProgram`]. The constraint matrix `A`
+/// must have full row rank.
+///
+/// ## Reference
+///
+/// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+/// Springer. ISBN 0-387-30303-0.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct InteriorPoint<V, M, F> {
+    /// Tolerance for residuals and duality gap
+    tol: F,
+    /// Fraction of the maximum step length
+    eta: F,
+    /// Relative norm of the iterates above which the problem is considered infeasible or unbounded
+    divergence_threshold: F,
+    /// Objective function coefficients
+    c: Vec<F>,
+    /// Right-hand side
+    b: Vec<F>,
+    /// Constraint matrix
+    a: Vec<Vec<F>>,
+    /// Quadratic term of objective function
+    q: Option<Vec<Vec<F>>>,
+    /// Current primal variables
+    x: Vec<F>,
+    /// Current Lagrange multipliers of the equality constraints
+    y: Vec<F>,
+    /// Current Lagrange multipliers of the non-negativity constraints
+    s: Vec<F>,
+    /// Norm of primal residual
+    primal_residual: F,
+    /// Norm of dual residual
+    dual_residual: F,
+    /// Duality gap
+    duality_gap: F,
+    /// Objective function value
+    objective: F,
+    phantom: std::marker::PhantomData<(V, M)>,
+}
+
+/// Factorization of the Newton system of one iteration
+struct NewtonSystem<M, F> {
+    /// Cholesky factor of `H = Q + X^-1 S` (quadratic programs only)
+    h: Option<M>,
+    /// `A H^-1`, given as a vector of rows
+    w: Vec<Vec<F>>,
+    /// Cholesky factor of the normal matrix `A H^-1 A^T`
+    n: M,
+}
+
+impl<V, M, F> InteriorPoint<V, M, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `InteriorPoint`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::interiorpoint::InteriorPoint;
+    /// let ip: InteriorPoint<Vec<f64>, Vec<Vec<f64>>, f64> = InteriorPoint::new();
+    /// ```
+    pub fn new() -> Self {
+        InteriorPoint {
+            tol: F::epsilon().sqrt(),
+            eta: float!(0.99),
+            divergence_threshold: float!(1e10),
+            c: vec![],
+            b: vec![],
+            a: vec![],
+            q: None,
+            x: vec![],
+            y: vec![],
+            s: vec![],
+            primal_residual: F::infinity(),
+            dual_residual: F::infinity(),
+            duality_gap: F::infinity(),
+            objective: F::infinity(),
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// Set tolerance
+    ///
+    /// Tolerance for the relative primal and dual residuals and the relative duality gap. Must be
+    /// larger than 0 and defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::interiorpoint::InteriorPoint;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let ip: InteriorPoint<Vec<f64>, Vec<Vec<f64>>, f64> = InteriorPoint::new().with_tolerance(1e-10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`InteriorPoint`: tolerance must be > 0."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Set eta
+    ///
+    /// Fraction of the maximum step length which keeps the iterates strictly positive. Must be in
+    /// `(0, 1)` and defaults to `0.99`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::interiorpoint::InteriorPoint;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let ip: InteriorPoint<Vec<f64>, Vec<Vec<f64>>, f64> = InteriorPoint::new().with_eta(0.95)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_eta(mut self, eta: F) -> Result<Self, Error> {
+        if eta <= float!(0.0) || eta >= float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`InteriorPoint`: eta must be in (0, 1)."
+            ));
+        }
+        self.eta = eta;
+        Ok(self)
+    }
+
+    /// Set divergence threshold
+    ///
+    /// If the norm of the primal or dual variables exceeds `divergence_threshold * (1 + ||b|| +
+    /// ||c||)`, the problem is considered unbounded or infeasible, respectively. Must be larger
+    /// than 0 and defaults to `1e10`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::interiorpoint::InteriorPoint;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let ip: InteriorPoint<Vec<f64>, Vec<Vec<f64>>, f64> =
+    ///     InteriorPoint::new().with_divergence_threshold(1e8)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_divergence_threshold(mut self, divergence_threshold: F) -> Result<Self, Error> {
+        if divergence_threshold <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`InteriorPoint`: divergence threshold must be > 0."
+            ));
+        }
+        self.divergence_threshold = divergence_threshold;
+        Ok(self)
+    }
+
+    /// Computes `Q v` (zero if there is no quadratic term)
+    fn q_mul(&self, v: &[F]) -> Vec<F> {
+        match self.q.as_ref() {
+            Some(q) => mat_vec(q, v),
+            None => vec![float!(0.0); v.len()],
+        }
+    }
+
+    /// Computes primal and dual residuals, duality gap and objective function value of the
+    /// current iterate.
+    fn residuals(&mut self) -> (Vec<F>, Vec<F>) {
+        let rp: Vec<F> = mat_vec(&self.a, &self.x)
+            .iter()
+            .zip(self.b.iter())
+            .map(|(&ax, &b)| ax - b)
+            .collect();
+        let qx = self.q_mul(&self.x);
+        let aty = mat_t_vec(&self.a, &self.y, self.x.len());
+        let rd: Vec<F> = (0..self.x.len())
+            .map(|i| qx[i] + self.c[i] - aty[i] - self.s[i])
+            .collect();
+        self.primal_residual = norm(&rp);
+        self.dual_residual = norm(&rd);
+        self.duality_gap = dot(&self.x, &self.s);
+        self.objective = float!(0.5) * dot(&self.x, &qx) + dot(&self.c, &self.x);
+        (rp, rd)
+    }
+
+    /// Whether the current iterate satisfies the convergence criteria
+    fn converged(&self) -> bool {
+        let one = float!(1.0);
+        self.primal_residual <= self.tol * (one + norm(&self.b))
+            && self.dual_residual <= self.tol * (one + norm(&self.c))
+            && self.duality_gap <= self.tol * (one + self.objective.abs())
+    }
+
+    /// Whether the current iterate indicates an infeasible or unbounded problem
+    fn diverged(&self) -> Option<TerminationReason> {
+        let bound = self.divergence_threshold * (float!(1.0) + norm(&self.b) + norm(&self.c));
+        if (dot(&self.y, &self.y) + dot(&self.s, &self.s)).sqrt() > bound {
+            Some(TerminationReason::Infeasible)
+        } else if norm(&self.x) > bound {
+            Some(TerminationReason::Unbounded)
+        } else {
+            None
+        }
+    }
+
+    /// Maximum step length in `[0, 1]` such that `v + alpha * dv >= 0`
+    fn max_step(v: &[F], dv: &[F]) -> F {
+        v.iter()
+            .zip(dv.iter())
+            .filter(|(_, &dvi)| dvi < float!(0.0))
+            .fold(float!(1.0), |alpha, (&vi, &dvi)| alpha.min(-vi / dvi))
+    }
+
+    /// Primal and dual step lengths. For quadratic programs, both are identical.
+    fn step_lengths(&self, dx: &[F], ds: &[F]) -> (F, F) {
+        let alpha_p = Self::max_step(&self.x, dx);
+        let alpha_d = Self::max_step(&self.s, ds);
+        if self.q.is_some() {
+            let alpha = alpha_p.min(alpha_d);
+            (alpha, alpha)
+        } else {
+            (alpha_p, alpha_d)
+        }
+    }
+
+    /// Stores param, cost and dual values in the state.
+    fn update_state(&self, state: LinearProgramState<Vec<F>, F>) -> LinearProgramState<Vec<F>, F> {
+        let cost = if self.converged() {
+            self.objective
+        } else {
+            F::infinity()
+        };
+        state.param(self.x.clone()).cost(cost).dual(self.y.clone())
+    }
+
+    fn kv(&self) -> KV {
+        kv!(
+            "duality_gap" => self.duality_gap;
+            "primal_residual" => self.primal_residual;
+            "dual_residual" => self.dual_residual;
+        )
+    }
+}
+
+impl<V, M, F> InteriorPoint<V, M, F>
+where
+    V: From<Vec<F>> + ArgminGet<usize, F>,
+    M: ArgminEye + ArgminSet<(usize, usize), F> + ArgminCholesky<M> + ArgminCholeskySolve<V, V>,
+    F: ArgminFloat,
+{
+    /// Copies a square matrix given as a vector of rows into a backend matrix.
+    fn to_matrix(rows: &[Vec<F>]) -> M {
+        let mut mat = M::eye(rows.len());
+        for (i, row) in rows.iter().enumerate() {
+            for (j, &v) in row.iter().enumerate() {
+                mat.set((i, j), v);
+            }
+        }
+        mat
+    }
+
+    /// Solves `L L^T v = rhs` with the Cholesky factor `L` using the backend.
+    fn solve(l: &M, rhs: Vec<F>) -> Result<Vec<F>, Error> {
+        let len = rhs.len();
+        let sol: V = l.cholesky_solve(&V::from(rhs))?;
+        Ok((0..len).map(|i| sol.get(i)).collect())
+    }
+
+    /// Factorizes the Newton system of the current iterate.
+    fn factorize(&self) -> Result<NewtonSystem<M, F>, Error> {
+        let n = self.x.len();
+        let (h, w) = match self.q.as_ref() {
+            Some(q) => {
+                let h: Vec<Vec<F>> = (0..n)
+                    .map(|i| {
+                        let mut row = q[i].clone();
+                        row[i] = row[i] + self.s[i] / self.x[i];
+                        row
+                    })
+                    .collect();
+                let h = Self::to_matrix(&h).cholesky()?;
+                let w = self
+                    .a
+                    .iter()
+                    .map(|row| Self::solve(&h, row.clone()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                (Some(h), w)
+            }
+            None => {
+                let w = self
+                    .a
+                    .iter()
+                    .map(|row| (0..n).map(|j| row[j] * self.x[j] / self.s[j]).collect())
+                    .collect();
+                (None, w)
+            }
+        };
+        let normal: Vec<Vec<F>> = self
+            .a
+            .iter()
+            .map(|ai| w.iter().map(|wj| dot(ai, wj)).collect())
+            .collect();
+        Ok(NewtonSystem {
+            h,
+            w,
+            n: Self::to_matrix(&normal).cholesky()?,
+        })
+    }
+
+    /// Computes the Newton direction for the complementarity residual `rxs`.
+    fn direction(
+        &self,
+        sys: &NewtonSystem<M, F>,
+        rp: &[F],
+        rd: &[F],
+        rxs: &[F],
+    ) -> Result<(Vec<F>, Vec<F>, Vec<F>), Error> {
+        let n = self.x.len();
+        let r1: Vec<F> = (0..n).map(|i| -rd[i] - rxs[i] / self.x[i]).collect();
+        // t = H^-1 r1
+        let t = match sys.h.as_ref() {
+            Some(h) => Self::solve(h, r1)?,
+            None => (0..n).map(|i| r1[i] * self.x[i] / self.s[i]).collect(),
+        };
+        // A H^-1 A^T dy = -rp - A t
+        let rhs: Vec<F> = rp
+            .iter()
+            .zip(mat_vec(&self.a, &t))
+            .map(|(&r, at)| -r - at)
+            .collect();
+        let dy = Self::solve(&sys.n, rhs)?;
+        // dx = t + H^-1 A^T dy
+        let dx: Vec<F> = mat_t_vec(&sys.w, &dy, n)
+            .iter()
+            .zip(t.iter())
+            .map(|(&wdy, &ti)| ti + wdy)
+            .collect();
+        let ds = (0..n)
+            .map(|i| (-rxs[i] - self.s[i] * dx[i]) / self.x[i])
+            .collect();
+        Ok((dx, dy, ds))
+    }
+}
+
+impl<V, M, F> Default for InteriorPoint<V, M, F>
+where
+    F: ArgminFloat,
+{
+    fn default() -> InteriorPoint<V, M, F> {
+        InteriorPoint::new()
+    }
+}
+
+impl<O, V, M, F> Solver<O, LinearProgramState<Vec<F>, F>> for InteriorPoint<V, M, F>
+where
+    O: LinearProgram<Float = F>,
+    V: From<Vec<F>> + ArgminGet<usize, F>,
+    M: ArgminEye + ArgminSet<(usize, usize), F> + ArgminCholesky<M> + ArgminCholeskySolve<V, V>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Interior point method";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: LinearProgramState<Vec<F>, F>,
+    ) -> Result<(LinearProgramState<Vec<F>, F>, Option<KV>), Error> {
+        let c = problem.c()?;
+        let b = problem.b()?;
+        let a = problem.A()?;
+        let q = problem.Q()?;
+        let n = c.len();
+
+        if n == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`InteriorPoint`: `c` must not be empty."
+            ));
+        }
+        if a.len() != b.len() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`InteriorPoint`: number of rows of `A` must match the length of `b`."
+            ));
+        }
+        if a.iter().any(|row| row.len() != n) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`InteriorPoint`: number of columns of `A` must match the length of `c`."
+            ));
+        }
+        if let Some(q) = q.as_ref() {
+            if q.len() != n || q.iter().any(|row| row.len() != n) {
+                return Err(argmin_error!(
+                    InvalidParameter,
+                    "`InteriorPoint`: `Q` must be a square matrix matching the length of `c`."
+                ));
+            }
+        }
+
+        self.x = vec![float!(1.0); n];
+        self.s = vec![float!(1.0); n];
+        self.y = vec![float!(0.0); b.len()];
+        self.c = c;
+        self.b = b;
+        self.a = a;
+        self.q = q;
+        self.residuals();
+
+        Ok((self.update_state(state), Some(self.kv())))
+    }
+
+    fn next_iter(
+        &mut self,
+        _problem: &mut Problem<O>,
+        state: LinearProgramState<Vec<F>, F>,
+    ) -> Result<(LinearProgramState<Vec<F>, F>, Option<KV>), Error> {
+        let n = self.x.len();
+        let nf = F::from_usize(n).unwrap();
+        let (rp, rd) = self.residuals();
+        let sys = self.factorize()?;
+
+        // Predictor (affine scaling) step
+        let rxs: Vec<F> = self
+            .x
+            .iter()
+            .zip(self.s.iter())
+            .map(|(&x, &s)| x * s)
+            .collect();
+        let (dx_aff, _, ds_aff) = self.direction(&sys, &rp, &rd, &rxs)?;
+        let (alpha_p, alpha_d) = self.step_lengths(&dx_aff, &ds_aff);
+
+        let mu = self.duality_gap / nf;
+        let mu_aff = (0..n).fold(float!(0.0), |acc, i| {
+            acc + (self.x[i] + alpha_p * dx_aff[i]) * (self.s[i] + alpha_d * ds_aff[i])
+        }) / nf;
+        let sigma = (mu_aff / mu).powi(3);
+
+        // Corrector step (reuses the factorization of the predictor step)
+        let rxs: Vec<F> = (0..n)
+            .map(|i| rxs[i] + dx_aff[i] * ds_aff[i] - sigma * mu)
+            .collect();
+        let (dx, dy, ds) = self.direction(&sys, &rp, &rd, &rxs)?;
+        let (alpha_p, alpha_d) = self.step_lengths(&dx, &ds);
+        let alpha_p = (self.eta * alpha_p).min(float!(1.0));
+        let alpha_d = (self.eta * alpha_d).min(float!(1.0));
+
+        for i in 0..n {
+            self.x[i] = self.x[i] + alpha_p * dx[i];
+            self.s[i] = self.s[i] + alpha_d * ds[i];
+        }
+        for (yi, &dyi) in self.y.iter_mut().zip(dy.iter()) {
+            *yi = *yi + alpha_d * dyi;
+        }
+        self.residuals();
+
+        Ok((self.update_state(state), Some(self.kv())))
+    }
+
+    fn terminate(&mut self, _state: &LinearProgramState<Vec<F>, F>) -> TerminationStatus {
+        if self.converged() {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+        if let Some(reason) = self.diverged() {
+            return TerminationStatus::Terminated(reason);
+        }
+        TerminationStatus::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor, State};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(interiorpoint, InteriorPoint<Vec<f64>, Vec<Vec<f64>>, f64>);
+
+    struct Lp {
+        c: Vec<f64>,
+        b: Vec<f64>,
+        a: Vec<Vec<f64>>,
+        q: Option<Vec<Vec<f64>>>,
+    }
+
+    impl LinearProgram for Lp {
+        type Param = Vec<f64>;
+        type Float = f64;
+
+        fn c(&self) -> Result<Vec<f64>, Error> {
+            Ok(self.c.clone())
+        }
+
+        fn b(&self) -> Result<Vec<f64>, Error> {
+            Ok(self.b.clone())
+        }
+
+        fn A(&self) -> Result<Vec<Vec<f64>>, Error> {
+            Ok(self.a.clone())
+        }
+
+        fn Q(&self) -> Result<Option<Vec<Vec<f64>>>, Error> {
+            Ok(self.q.clone())
+        }
+    }
+
+    /// max 3 x1 + 5 x2 s.t. x1 <= 4, 2 x2 <= 12, 3 x1 + 2 x2 <= 18 (with slack variables)
+    fn lp() -> Lp {
+        Lp {
+            c: vec![-3.0, -5.0, 0.0, 0.0, 0.0],
+            b: vec![4.0, 12.0, 18.0],
+            a: vec![
+                vec![1.0, 0.0, 1.0, 0.0, 0.0],
+                vec![0.0, 2.0, 0.0, 1.0, 0.0],
+                vec![3.0, 2.0, 0.0, 0.0, 1.0],
+            ],
+            q: None,
+        }
+    }
+
+    /// min (x1 - 1)^2 + (x2 - 2.5)^2 s.t. x1 + x2 + x3 = 2 (x3 is a slack variable)
+    fn qp() -> Lp {
+        Lp {
+            c: vec![-2.0, -5.0, 0.0],
+            b: vec![2.0],
+            a: vec![vec![1.0, 1.0, 1.0]],
+            q: Some(vec![
+                vec![2.0, 0.0, 0.0],
+                vec![0.0, 2.0, 0.0],
+                vec![0.0, 0.0, 0.0],
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let ip: InteriorPoint<Vec<f64>, Vec<Vec<f64>>, f64> = InteriorPoint::new();
+        assert_eq!(ip.tol.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert_eq!(ip.eta.to_ne_bytes(), 0.99f64.to_ne_bytes());
+        assert!(ip.x.is_empty());
+        assert!(ip.y.is_empty());
+        assert!(ip.s.is_empty());
+        assert!(ip.q.is_none());
+    }
+
+    #[test]
+    fn test_with_tolerance() {
+        let ip: InteriorPoint<Vec<f64>, Vec<Vec<f64>>, f64> =
+            InteriorPoint::new().with_tolerance(1e-6).unwrap();
+        assert_eq!(ip.tol.to_ne_bytes(), 1e-6f64.to_ne_bytes());
+
+        for tol in [0.0, -1.0] {
+            assert_error!(
+                InteriorPoint::<Vec<f64>, Vec<Vec<f64>>, f64>::new().with_tolerance(tol),
+                ArgminError,
+                "Invalid parameter: \"`InteriorPoint`: tolerance must be > 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_with_eta() {
+        for eta in [f64::EPSILON, 0.5, 1.0 - f64::EPSILON] {
+            let ip: InteriorPoint<Vec<f64>, Vec<Vec<f64>>, f64> =
+                InteriorPoint::new().with_eta(eta).unwrap();
+            assert_eq!(ip.eta.to_ne_bytes(), eta.to_ne_bytes());
+        }
+
+        for eta in [0.0, 1.0, -1.0, 2.0] {
+            assert_error!(
+                InteriorPoint::<Vec<f64>, Vec<Vec<f64>>, f64>::new().with_eta(eta),
+                ArgminError,
+                "Invalid parameter: \"`InteriorPoint`: eta must be in (0, 1).\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_with_divergence_threshold() {
+        let ip: InteriorPoint<Vec<f64>, Vec<Vec<f64>>, f64> =
+            InteriorPoint::new().with_divergence_threshold(1e6).unwrap();
+        assert_eq!(ip.divergence_threshold.to_ne_bytes(), 1e6f64.to_ne_bytes());
+
+        for threshold in [0.0, -1.0] {
+            assert_error!(
+                InteriorPoint::<Vec<f64>, Vec<Vec<f64>>, f64>::new()
+                    .with_divergence_threshold(threshold),
+                ArgminError,
+                "Invalid parameter: \"`InteriorPoint`: divergence threshold must be > 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_max_step() {
+        let alpha =
+            InteriorPoint::<Vec<f64>, Vec<Vec<f64>>, f64>::max_step(&[1.0, 2.0], &[1.0, -4.0]);
+        assert_eq!(alpha.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        let alpha =
+            InteriorPoint::<Vec<f64>, Vec<Vec<f64>>, f64>::max_step(&[1.0, 2.0], &[1.0, -1.0]);
+        assert_eq!(alpha.to_ne_bytes(), 1.0f64.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_residuals() {
+        let mut ip: InteriorPoint<Vec<f64>, Vec<Vec<f64>>, f64> = InteriorPoint::new();
+        let Lp { c, b, a, q } = qp();
+        ip.c = c;
+        ip.b = b;
+        ip.a = a;
+        ip.q = q;
+        ip.x = vec![1.0, 1.0, 1.0];
+        ip.y = vec![1.0];
+        ip.s = vec![2.0, 1.0, 1.0];
+        let (rp, rd) = ip.residuals();
+        assert_eq!(rp, vec![1.0]);
+        assert_eq!(rd, vec![-3.0, -5.0, -2.0]);
+        assert_eq!(ip.primal_residual.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(ip.dual_residual.to_ne_bytes(), 38.0f64.sqrt().to_ne_bytes());
+        assert_eq!(ip.duality_gap.to_ne_bytes(), 4.0f64.to_ne_bytes());
+        assert_eq!(ip.objective.to_ne_bytes(), (-5.0f64).to_ne_bytes());
+    }
+
+    #[test]
+    fn test_init_dimension_mismatch() {
+        let mut problem = lp();
+        problem.b = vec![1.0];
+        let mut ip: InteriorPoint<Vec<f64>, Vec<Vec<f64>>, f64> = InteriorPoint::new();
+        assert_error!(
+            ip.init(&mut Problem::new(problem), LinearProgramState::new()),
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`InteriorPoint`: number of rows of `A` must match the ",
+                "length of `b`.\""
+            )
+        );
+
+        let mut problem = lp();
+        problem.c = vec![1.0];
+        assert_error!(
+            ip.init(&mut Problem::new(problem), LinearProgramState::new()),
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`InteriorPoint`: number of columns of `A` must match the ",
+                "length of `c`.\""
+            )
+        );
+
+        let mut problem = qp();
+        problem.q = Some(vec![vec![1.0]]);
+        assert_error!(
+            ip.init(&mut Problem::new(problem), LinearProgramState::new()),
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`InteriorPoint`: `Q` must be a square matrix matching the ",
+                "length of `c`.\""
+            )
+        );
+
+        assert_error!(
+            ip.init(
+                &mut Problem::new(Lp {
+                    c: vec![],
+                    b: vec![],
+                    a: vec![],
+                    q: None
+                }),
+                LinearProgramState::new()
+            ),
+            ArgminError,
+            "Invalid parameter: \"`InteriorPoint`: `c` must not be empty.\""
+        );
+    }
+
+    #[test]
+    fn test_init_not_implemented() {
+        let mut ip: InteriorPoint<Vec<f64>, Vec<Vec<f64>>, f64> = InteriorPoint::new();
+        struct NotImplemented {}
+        impl LinearProgram for NotImplemented {
+            type Param = Vec<f64>;
+            type Float = f64;
+        }
+        assert_error!(
+            ip.init(
+                &mut Problem::new(NotImplemented {}),
+                LinearProgramState::new()
+            ),
+            ArgminError,
+            "Not implemented: \"Method `c` of LinearProgram trait not implemented!\""
+        );
+    }
+
+    #[test]
+    fn test_lp() {
+        let res = Executor::new(lp(), InteriorPoint::<Vec<f64>, Vec<Vec<f64>>, f64>::new())
+            .configure(|state| state.max_iters(100))
+            .run()
+            .unwrap();
+        let state = res.state();
+        assert_eq!(
+            state.termination_status,
+            TerminationStatus::Terminated(TerminationReason::SolverConverged)
+        );
+        let x = state.get_param().unwrap();
+        assert_relative_eq!(x[0], 2.0, epsilon = 1e-6);
+        assert_relative_eq!(x[1], 6.0, epsilon = 1e-6);
+        assert_relative_eq!(state.get_cost(), -36.0, epsilon = 1e-6);
+        let y = state.get_dual().unwrap();
+        assert_relative_eq!(y[0], 0.0, epsilon = 1e-6);
+        assert_relative_eq!(y[1], -1.5, epsilon = 1e-6);
+        assert_relative_eq!(y[2], -1.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_qp() {
+        let res = Executor::new(qp(), InteriorPoint::<Vec<f64>, Vec<Vec<f64>>, f64>::new())
+            .configure(|state| state.max_iters(100))
+            .run()
+            .unwrap();
+        let state = res.state();
+        assert_eq!(
+            state.termination_status,
+            TerminationStatus::Terminated(TerminationReason::SolverConverged)
+        );
+        let x = state.get_param().unwrap();
+        assert_relative_eq!(x[0], 0.25, epsilon = 1e-6);
+        assert_relative_eq!(x[1], 1.75, epsilon = 1e-6);
+        assert_relative_eq!(x[2], 0.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_infeasible() {
+        // x1 + x2 = -1 has no non-negative solution
+        let problem = Lp {
+            c: vec![1.0, 1.0],
+            b: vec![-1.0],
+            a: vec![vec![1.0, 1.0]],
+            q: None,
+        };
+        let res = Executor::new(
+            problem,
+            InteriorPoint::<Vec<f64>, Vec<Vec<f64>>, f64>::new(),
+        )
+        .configure(|state| state.max_iters(200))
+        .run()
+        .unwrap();
+        assert_eq!(
+            res.state().termination_status,
+            TerminationStatus::Terminated(TerminationReason::Infeasible)
+        );
+    }
+
+    #[test]
+    fn test_unbounded() {
+        // min -x1 s.t. x1 - x2 = 0
+        let problem = Lp {
+            c: vec![-1.0, 0.0],
+            b: vec![0.0],
+            a: vec![vec![1.0, -1.0]],
+            q: None,
+        };
+        let res = Executor::new(
+            problem,
+            InteriorPoint::<Vec<f64>, Vec<Vec<f64>>, f64>::new(),
+        )
+        .configure(|state| state.max_iters(200))
+        .run()
+        .unwrap();
+        assert_eq!(
+            res.state().termination_status,
+            TerminationStatus::Terminated(TerminationReason::Unbounded)
+        );
+    }
+
+    #[cfg(feature = "_nalgebral")]
+    #[test]
+    fn test_lp_nalgebra() {
+        use nalgebra::{DMatrix, DVector};
+
+        let res = Executor::new(
+            lp(),
+            InteriorPoint::<DVector<f64>, DMatrix<f64>, f64>::new(),
+        )
+        .configure(|state| state.max_iters(100))
+        .run()
+        .unwrap();
+        let state = res.state();
+        assert_eq!(
+            state.termination_status,
+            TerminationStatus::Terminated(TerminationReason::SolverConverged)
+        );
+        let x = state.get_param().unwrap();
+        assert_relative_eq!(x[0], 2.0, epsilon = 1e-6);
+        assert_relative_eq!(x[1], 6.0, epsilon = 1e-6);
+        assert_relative_eq!(state.get_cost(), -36.0, epsilon = 1e-6);
+        let y = state.get_dual().unwrap();
+        assert_relative_eq!(y[0], 0.0, epsilon = 1e-6);
+        assert_relative_eq!(y[1], -1.5, epsilon = 1e-6);
+        assert_relative_eq!(y[2], -1.0, epsilon = 1e-6);
+    }
+
+    #[cfg(feature = "_nalgebral")]
+    #[test]
+    fn test_qp_nalgebra() {
+        use nalgebra::{DMatrix, DVector};
+
+        let res = Executor::new(
+            qp(),
+            InteriorPoint::<DVector<f64>, DMatrix<f64>, f64>::new(),
+        )
+        .configure(|state| state.max_iters(100))
+        .run()
+        .unwrap();
+        let state = res.state();
+        assert_eq!(
+            state.termination_status,
+            TerminationStatus::Terminated(TerminationReason::SolverConverged)
+        );
+        // Projection of (1, 2.5) onto x1 + x2 <= 2
+        let x = state.get_param().unwrap();
+        assert_relative_eq!(x[0], 0.25, epsilon = 1e-6);
+        assert_relative_eq!(x[1], 1.75, epsilon = 1e-6);
+        assert_relative_eq!(x[2], 0.0, epsilon = 1e-6);
+        let y = state.get_dual().unwrap();
+        assert_relative_eq!(y[0], -1.5, epsilon = 1e-6);
+    }
+
+    #[cfg(feature = "_ndarrayl")]
+    #[test]
+    fn test_lp_ndarray() {
+        use ndarray::{Array1, Array2};
+
+        let res = Executor::new(lp(), InteriorPoint::<Array1<f64>, Array2<f64>, f64>::new())
+            .configure(|state| state.max_iters(100))
+            .run()
+            .unwrap();
+        let state = res.state();
+        assert_eq!(
+            state.termination_status,
+            TerminationStatus::Terminated(TerminationReason::SolverConverged)
+        );
+        let x = state.get_param().unwrap();
+        assert_relative_eq!(x[0], 2.0, epsilon = 1e-6);
+        assert_relative_eq!(x[1], 6.0, epsilon = 1e-6);
+        assert_relative_eq!(state.get_cost(), -36.0, epsilon = 1e-6);
+    }
+}
@@ -0,0 +1,675 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Levenberg-Marquardt method
+//!
+//! Damped Gauss-Newton method for nonlinear least squares problems.
+//!
+//! See [`LevenbergMarquardt`] for details.
+//!
+//! ## References
+//!
+//! Jorge J. Moré (1978). The Levenberg-Marquardt algorithm: Implementation and theory.
+//! In: Numerical Analysis, Lecture Notes in Mathematics, vol. 630. Springer.
+//! DOI: <https://doi.org/10.1007/BFb0067700>
+//!
+//! Mark K. Transtrum and James P. Sethna (2012). Improvements to the Levenberg-Marquardt
+//! algorithm for nonlinear least-squares minimization. arXiv: <https://arxiv.org/abs/1201.5885>
+
+use crate::core::{
+    ArgminFloat, Error, IterState, Jacobian, Operator, Problem, Solver, State, TerminationReason,
+    TerminationStatus, KV,
+};
+use argmin_math::{
+    ArgminAdd, ArgminDot, ArgminGet, ArgminInv, ArgminL2Norm, ArgminMul, ArgminSet, ArgminSize,
+    ArgminSub, ArgminTranspose,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Levenberg-Marquardt method
+///
+/// The Levenberg-Marquardt method solves nonlinear least squares problems by computing steps `d`
+/// from the damped Gauss-Newton equations
+///
+/// ```text
+/// (J^T J + lambda D^T D) d = -J^T r
+/// ```
+///
+/// where `J` is the Jacobian, `r` the residuals and `lambda` the damping parameter. In contrast to
+/// the Gauss-Newton method, the matrix on the left-hand side is positive definite for
+/// `lambda > 0`, even if the Jacobian is ill-conditioned or rank-deficient.
+///
+/// A step is accepted if the ratio of actual and predicted reduction of the sum of squared
+/// residuals exceeds `1e-4`. The damping parameter is updated following Moré: if the ratio is
+/// below `0.25`, the damping is increased by a factor of `2`; if it is above `0.75`, the damping is
+/// decreased by a factor of `3`. Rejected steps reuse the Jacobian of the current parameter
+/// vector, therefore every iteration requires one evaluation of the residuals (two with geodesic
+/// acceleration) and at most one evaluation of the Jacobian.
+///
+/// By default, the diagonal scaling matrix `D` is chosen as proposed by Moré: its squared entries
+/// are the running maximum of the diagonal entries of `J^T J`, which makes the method invariant
+/// under scaling of the parameters. Scaling can be disabled via
+/// [`with_scaling`](`LevenbergMarquardt::with_scaling`), in which case `D` is the identity.
+///
+/// Optionally, geodesic acceleration (Transtrum and Sethna) can be enabled via
+/// [`with_geodesic_acceleration`](`LevenbergMarquardt::with_geodesic_acceleration`). In this case,
+/// a second order correction `a` is computed from a finite difference approximation of the second
+/// directional derivative of the residuals along `d`. The step `d + a/2` is only taken if
+/// `2 ||a|| / ||d||` does not exceed a given threshold, otherwise the step is rejected and the
+/// damping is increased.
+///
+/// The cost function value is the L2 norm of the residuals. The solver terminates if the
+/// reduction of the cost function value of an accepted step is below a tolerance (see
+/// [`with_tolerance`](`LevenbergMarquardt::with_tolerance`)), if the cost function value is zero,
+/// or if the damping parameter exceeds `1/EPSILON`, in which case no further progress is possible.
+///
+/// The damping parameter and the gain ratio are reported to the observers as `lambda` and
+/// `gain_ratio`.
+///
+/// Requires an initial parameter vector.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`Operator`] (returning the residuals) and
+/// [`Jacobian`].
+///
+/// ## References
+///
+/// Jorge J. Moré (1978). The Levenberg-Marquardt algorithm: Implementation and theory.
+/// In: Numerical Analysis, Lecture Notes in Mathematics, vol. 630. Springer.
+/// DOI: <https://doi.org/10.1007/BFb0067700>
+///
+/// Mark K. Transtrum and James P. Sethna (2012). Improvements to the Levenberg-Marquardt
+/// algorithm for nonlinear least-squares minimization. arXiv: <https://arxiv.org/abs/1201.5885>
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct LevenbergMarquardt<F> {
+    /// Damping parameter
+    lambda: F,
+    /// Whether Moré's diagonal scaling is used
+    scaling: bool,
+    /// Squared diagonal entries of scaling matrix
+    diag: Vec<F>,
+    /// Geodesic acceleration: finite difference step size and acceptance threshold
+    geodesic: Option<(F, F)>,
+    /// Tolerance for the stopping criterion based on cost difference
+    tol: F,
+    /// Whether the last step was accepted
+    accepted: bool,
+}
+
+impl<F: ArgminFloat> LevenbergMarquardt<F> {
+    /// Construct a new instance of [`LevenbergMarquardt`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::levenbergmarquardt::LevenbergMarquardt;
+    /// let lm: LevenbergMarquardt<f64> = LevenbergMarquardt::new();
+    /// ```
+    pub fn new() -> Self {
+        LevenbergMarquardt {
+            lambda: float!(1e-3),
+            scaling: true,
+            diag: vec![],
+            geodesic: None,
+            tol: F::epsilon().sqrt(),
+            accepted: true,
+        }
+    }
+
+    /// Set the initial damping parameter.
+    ///
+    /// Must be larger than zero and defaults to `1e-3`. With diagonal scaling enabled, the damping
+    /// is relative to the diagonal of `J^T J`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::levenbergmarquardt::LevenbergMarquardt;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let lm = LevenbergMarquardt::new().with_damping(1e-2f64)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_damping(mut self, lambda: F) -> Result<Self, Error> {
+        if lambda <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`LevenbergMarquardt`: damping must be > 0."
+            ));
+        }
+        self.lambda = lambda;
+        Ok(self)
+    }
+
+    /// Enable or disable Moré's diagonal scaling. Enabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::levenbergmarquardt::LevenbergMarquardt;
+    /// let lm: LevenbergMarquardt<f64> = LevenbergMarquardt::new().with_scaling(false);
+    /// ```
+    #[must_use]
+    pub fn with_scaling(mut self, scaling: bool) -> Self {
+        self.scaling = scaling;
+        self
+    }
+
+    /// Enable geodesic acceleration.
+    ///
+    /// `h` is the step size of the finite difference approximation of the second directional
+    /// derivative of the residuals (commonly `0.1`) and must be larger than zero. `alpha` is the
+    /// largest acceptable ratio `2 ||a|| / ||d||` of acceleration and step (commonly `0.75`) and
+    /// must be larger than zero. Disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::levenbergmarquardt::LevenbergMarquardt;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let lm = LevenbergMarquardt::new().with_geodesic_acceleration(0.1f64, 0.75)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_geodesic_acceleration(mut self, h: F, alpha: F) -> Result<Self, Error> {
+        if h <= float!(0.0) || alpha <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`LevenbergMarquardt`: h and alpha of geodesic acceleration must be > 0."
+            ));
+        }
+        self.geodesic = Some((h, alpha));
+        Ok(self)
+    }
+
+    /// Set tolerance for the stopping criterion based on cost difference.
+    ///
+    /// Tolerance must be larger than zero and defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::levenbergmarquardt::LevenbergMarquardt;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let lm = LevenbergMarquardt::new().with_tolerance(1e-4f64)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`LevenbergMarquardt`: tolerance must be > 0."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Computes `J^T J + lambda D^T D` and updates the scaling matrix `D`.
+    fn damped_normal_matrix<J>(&mut self, jtj: &J) -> J
+    where
+        J: Clone
+            + ArgminGet<(usize, usize), F>
+            + ArgminSet<(usize, usize), F>
+            + ArgminSize<(usize, usize)>,
+    {
+        let (n, _) = jtj.shape();
+        if self.diag.len() != n {
+            self.diag = vec![float!(0.0); n];
+        }
+        let mut mat = jtj.clone();
+        for i in 0..n {
+            let jtj_ii = jtj.get((i, i));
+            let d = if self.scaling {
+                self.diag[i] = self.diag[i].max(jtj_ii);
+                // Guard against zero columns of the Jacobian
+                if self.diag[i] > float!(0.0) {
+                    self.diag[i]
+                } else {
+                    float!(1.0)
+                }
+            } else {
+                float!(1.0)
+            };
+            mat.set((i, i), jtj_ii + self.lambda * d);
+        }
+        mat
+    }
+}
+
+impl<F: ArgminFloat> Default for LevenbergMarquardt<F> {
+    fn default() -> LevenbergMarquardt<F> {
+        LevenbergMarquardt::new()
+    }
+}
+
+impl<O, P, J, R, F> Solver<O, IterState<P, (), J, (), R, F>> for LevenbergMarquardt<F>
+where
+    O: Operator<Param = P, Output = R> + Jacobian<Param = P, Jacobian = J>,
+    P: Clone + ArgminAdd<P, P> + ArgminMul<F, P> + ArgminL2Norm<F>,
+    R: Clone + ArgminAdd<R, R> + ArgminSub<R, R> + ArgminMul<F, R> + ArgminL2Norm<F>,
+    J: Clone
+        + ArgminTranspose<J>
+        + ArgminInv<J>
+        + ArgminDot<J, J>
+        + ArgminDot<R, P>
+        + ArgminDot<P, P>
+        + ArgminDot<P, R>
+        + ArgminGet<(usize, usize), F>
+        + ArgminSet<(usize, usize), F>
+        + ArgminSize<(usize, usize)>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Levenberg-Marquardt method";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), J, (), R, F>,
+    ) -> Result<(IterState<P, (), J, (), R, F>, Option<KV>), Error> {
+        let init_param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`LevenbergMarquardt` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let residuals = problem.apply(&init_param)?;
+        let jacobian = problem.jacobian(&init_param)?;
+        let cost = residuals.l2_norm();
+        self.diag = vec![];
+        self.accepted = true;
+        Ok((
+            state
+                .param(init_param)
+                .residuals(residuals)
+                .jacobian(jacobian)
+                .cost(cost),
+            Some(kv!("lambda" => self.lambda;)),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), J, (), R, F>,
+    ) -> Result<(IterState<P, (), J, (), R, F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`LevenbergMarquardt`: `param` not set"
+        ))?;
+        let residuals = state.take_residuals().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`LevenbergMarquardt`: `residuals` not set"
+        ))?;
+        let jacobian = state.take_jacobian().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`LevenbergMarquardt`: `jacobian` not set"
+        ))?;
+
+        let jt = jacobian.clone().t();
+        let neg_grad = jt.dot(&residuals).mul(&float!(-1.0));
+        let mat_inv = self.damped_normal_matrix(&jt.dot(&jacobian)).inv()?;
+
+        // Damped Gauss-Newton step
+        let mut step: P = mat_inv.dot(&neg_grad);
+        let mut geodesic_ok = true;
+
+        if let Some((h, alpha)) = self.geodesic {
+            // Second directional derivative of the residuals along `step`
+            let residuals_h = problem.apply(&param.add(&step.mul(&h)))?;
+            let rvv = residuals_h
+                .sub(&residuals)
+                .mul(&(float!(1.0) / h))
+                .sub(&jacobian.dot(&step))
+                .mul(&(float!(2.0) / h));
+            let jt_rvv: P = jt.dot(&rvv);
+            let accel: P = mat_inv.dot(&jt_rvv);
+            let accel = accel.mul(&float!(-1.0));
+            if float!(2.0) * accel.l2_norm() <= alpha * step.l2_norm() {
+                step = step.add(&accel.mul(&float!(0.5)));
+            } else {
+                geodesic_ok = false;
+            }
+        }
+
+        let cost_sq = residuals.l2_norm().powi(2);
+        let (new_param, new_residuals, rho) = if geodesic_ok {
+            let new_param = param.add(&step);
+            let new_residuals = problem.apply(&new_param)?;
+            let predicted = cost_sq - residuals.add(&jacobian.dot(&step)).l2_norm().powi(2);
+            let actual = cost_sq - new_residuals.l2_norm().powi(2);
+            let rho = if predicted > float!(0.0) {
+                actual / predicted
+            } else {
+                float!(0.0)
+            };
+            (Some(new_param), Some(new_residuals), rho)
+        } else {
+            (None, None, float!(0.0))
+        };
+
+        if rho < float!(0.25) {
+            self.lambda = self.lambda * float!(2.0);
+        } else if rho > float!(0.75) {
+            self.lambda = self.lambda / float!(3.0);
+        }
+
+        self.accepted = rho > float!(1e-4);
+        let kv = kv!("lambda" => self.lambda; "gain_ratio" => rho;);
+
+        if self.accepted {
+            let new_param = new_param.unwrap();
+            let new_residuals = new_residuals.unwrap();
+            let new_jacobian = problem.jacobian(&new_param)?;
+            let cost = new_residuals.l2_norm();
+            Ok((
+                state
+                    .param(new_param)
+                    .residuals(new_residuals)
+                    .jacobian(new_jacobian)
+                    .cost(cost),
+                Some(kv),
+            ))
+        } else {
+            // Rejected step: keep parameter vector, residuals and Jacobian. `cost` and
+            // `prev_cost` are deliberately left untouched.
+            state.param = Some(param);
+            state.residuals = Some(residuals);
+            state.jacobian = Some(jacobian);
+            Ok((state, Some(kv)))
+        }
+    }
+
+    fn terminate(&mut self, state: &IterState<P, (), J, (), R, F>) -> TerminationStatus {
+        if state.get_cost() <= float!(0.0) {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+        if self.accepted
+            && state.get_iter() > 0
+            && (state.get_prev_cost() - state.get_cost()).abs() < self.tol
+        {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+        if self.lambda > float!(1.0) / F::epsilon() {
+            return TerminationStatus::Terminated(TerminationReason::SolverExit(
+                "Damping parameter too large".to_string(),
+            ));
+        }
+        TerminationStatus::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    #[cfg(feature = "_nalgebral")]
+    use crate::core::Executor;
+    use crate::test_trait_impl;
+    #[cfg(feature = "_nalgebral")]
+    use approx::assert_relative_eq;
+    #[cfg(feature = "_nalgebral")]
+    use nalgebra::{DMatrix, DVector};
+
+    test_trait_impl!(levenberg_marquardt, LevenbergMarquardt<f64>);
+
+    /// Rosenbrock function written as least squares problem
+    #[cfg(feature = "_nalgebral")]
+    struct Rosenbrock {}
+
+    #[cfg(feature = "_nalgebral")]
+    impl Operator for Rosenbrock {
+        type Param = DVector<f64>;
+        type Output = DVector<f64>;
+
+        fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(DVector::from_vec(vec![
+                10.0 * (p[1] - p[0].powi(2)),
+                1.0 - p[0],
+            ]))
+        }
+    }
+
+    #[cfg(feature = "_nalgebral")]
+    impl Jacobian for Rosenbrock {
+        type Param = DVector<f64>;
+        type Jacobian = DMatrix<f64>;
+
+        fn jacobian(&self, p: &Self::Param) -> Result<Self::Jacobian, Error> {
+            Ok(DMatrix::from_row_slice(
+                2,
+                2,
+                &[-20.0 * p[0], 10.0, -1.0, 0.0],
+            ))
+        }
+    }
+
+    /// Fit of `y = a * exp(b * t)` to data generated with `a = 2` and `b = -0.5`. The model is
+    /// overparameterized by a redundant third parameter `c` which enters as `a * c`, resulting in
+    /// a rank-deficient Jacobian.
+    #[cfg(feature = "_nalgebral")]
+    struct ExpFit {
+        t: Vec<f64>,
+        y: Vec<f64>,
+    }
+
+    #[cfg(feature = "_nalgebral")]
+    impl ExpFit {
+        fn new() -> Self {
+            let t: Vec<f64> = (0..10).map(|i| i as f64 * 0.5).collect();
+            let y = t.iter().map(|t| 2.0 * (-0.5 * t).exp()).collect();
+            ExpFit { t, y }
+        }
+    }
+
+    #[cfg(feature = "_nalgebral")]
+    impl Operator for ExpFit {
+        type Param = DVector<f64>;
+        type Output = DVector<f64>;
+
+        fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(DVector::from_iterator(
+                self.t.len(),
+                self.t
+                    .iter()
+                    .zip(self.y.iter())
+                    .map(|(t, y)| p[0] * p[2] * (p[1] * t).exp() - y),
+            ))
+        }
+    }
+
+    #[cfg(feature = "_nalgebral")]
+    impl Jacobian for ExpFit {
+        type Param = DVector<f64>;
+        type Jacobian = DMatrix<f64>;
+
+        fn jacobian(&self, p: &Self::Param) -> Result<Self::Jacobian, Error> {
+            Ok(DMatrix::from_fn(self.t.len(), 3, |i, j| {
+                let t = self.t[i];
+                let e = (p[1] * t).exp();
+                match j {
+                    0 => p[2] * e,
+                    1 => p[0] * p[2] * t * e,
+                    _ => p[0] * e,
+                }
+            }))
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let LevenbergMarquardt {
+            lambda,
+            scaling,
+            diag,
+            geodesic,
+            tol,
+            accepted,
+        } = LevenbergMarquardt::<f64>::new();
+
+        assert_eq!(lambda.to_ne_bytes(), 1e-3f64.to_ne_bytes());
+        assert!(scaling);
+        assert!(diag.is_empty());
+        assert!(geodesic.is_none());
+        assert_eq!(tol.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert!(accepted);
+    }
+
+    #[test]
+    fn test_with_damping() {
+        let lm = LevenbergMarquardt::new().with_damping(2.0f64).unwrap();
+        assert_eq!(lm.lambda.to_ne_bytes(), 2.0f64.to_ne_bytes());
+
+        for lambda in [0.0, -1.0] {
+            assert_error!(
+                LevenbergMarquardt::new().with_damping(lambda),
+                ArgminError,
+                "Invalid parameter: \"`LevenbergMarquardt`: damping must be > 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_with_scaling() {
+        let lm: LevenbergMarquardt<f64> = LevenbergMarquardt::new().with_scaling(false);
+        assert!(!lm.scaling);
+    }
+
+    #[test]
+    fn test_with_geodesic_acceleration() {
+        let lm = LevenbergMarquardt::new()
+            .with_geodesic_acceleration(0.1f64, 0.75)
+            .unwrap();
+        let (h, alpha) = lm.geodesic.unwrap();
+        assert_eq!(h.to_ne_bytes(), 0.1f64.to_ne_bytes());
+        assert_eq!(alpha.to_ne_bytes(), 0.75f64.to_ne_bytes());
+
+        for (h, alpha) in [(0.0, 0.75), (0.1, 0.0), (-1.0, 0.75), (0.1, -1.0)] {
+            assert_error!(
+                LevenbergMarquardt::new().with_geodesic_acceleration(h, alpha),
+                ArgminError,
+                concat!(
+                    "Invalid parameter: \"`LevenbergMarquardt`: h and alpha of geodesic ",
+                    "acceleration must be > 0.\""
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_with_tolerance() {
+        let lm = LevenbergMarquardt::new().with_tolerance(1e-4f64).unwrap();
+        assert_eq!(lm.tol.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+
+        for tol in [0.0, -1.0] {
+            assert_error!(
+                LevenbergMarquardt::new().with_tolerance(tol),
+                ArgminError,
+                "Invalid parameter: \"`LevenbergMarquardt`: tolerance must be > 0.\""
+            );
+        }
+    }
+
+    #[cfg(feature = "_nalgebral")]
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut lm = LevenbergMarquardt::<f64>::new();
+        let res = lm.init(&mut Problem::new(Rosenbrock {}), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`LevenbergMarquardt` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[cfg(feature = "_nalgebral")]
+    #[test]
+    fn test_damped_normal_matrix() {
+        let mut lm = LevenbergMarquardt::new().with_damping(0.5f64).unwrap();
+        let jtj = DMatrix::from_row_slice(2, 2, &[4.0, 1.0, 1.0, 0.0]);
+        let mat = lm.damped_normal_matrix(&jtj);
+        assert_relative_eq!(mat[(0, 0)], 6.0, epsilon = f64::EPSILON);
+        assert_relative_eq!(mat[(0, 1)], 1.0, epsilon = f64::EPSILON);
+        assert_relative_eq!(mat[(1, 0)], 1.0, epsilon = f64::EPSILON);
+        // zero diagonal entry falls back to 1
+        assert_relative_eq!(mat[(1, 1)], 0.5, epsilon = f64::EPSILON);
+
+        // Scaling keeps the running maximum
+        let jtj = DMatrix::from_row_slice(2, 2, &[1.0, 0.0, 0.0, 2.0]);
+        let mat = lm.damped_normal_matrix(&jtj);
+        assert_relative_eq!(mat[(0, 0)], 3.0, epsilon = f64::EPSILON);
+        assert_relative_eq!(mat[(1, 1)], 3.0, epsilon = f64::EPSILON);
+
+        let mut lm = LevenbergMarquardt::new()
+            .with_damping(0.5f64)
+            .unwrap()
+            .with_scaling(false);
+        let mat = lm.damped_normal_matrix(&jtj);
+        assert_relative_eq!(mat[(0, 0)], 1.5, epsilon = f64::EPSILON);
+        assert_relative_eq!(mat[(1, 1)], 2.5, epsilon = f64::EPSILON);
+    }
+
+    #[cfg(feature = "_nalgebral")]
+    #[test]
+    fn test_rosenbrock() {
+        for solver in [
+            LevenbergMarquardt::new(),
+            LevenbergMarquardt::new().with_scaling(false),
+            LevenbergMarquardt::new()
+                .with_geodesic_acceleration(0.1, 0.75)
+                .unwrap(),
+        ] {
+            let res = Executor::new(Rosenbrock {}, solver)
+                .configure(|state| {
+                    state
+                        .param(DVector::from_vec(vec![-1.2, 1.0]))
+                        .max_iters(200)
+                })
+                .run()
+                .unwrap();
+            assert_eq!(
+                res.state.termination_status,
+                TerminationStatus::Terminated(TerminationReason::SolverConverged)
+            );
+            let param = res.state.get_best_param().unwrap();
+            assert_relative_eq!(param[0], 1.0, epsilon = 1e-6);
+            assert_relative_eq!(param[1], 1.0, epsilon = 1e-6);
+        }
+    }
+
+    #[cfg(feature = "_nalgebral")]
+    #[test]
+    fn test_rank_deficient() {
+        let res = Executor::new(ExpFit::new(), LevenbergMarquardt::new())
+            .configure(|state| {
+                state
+                    .param(DVector::from_vec(vec![1.0, -1.0, 1.0]))
+                    .max_iters(200)
+            })
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.termination_status,
+            TerminationStatus::Terminated(TerminationReason::SolverConverged)
+        );
+        let param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(param[0] * param[2], 2.0, epsilon = 1e-6);
+        assert_relative_eq!(param[1], -0.5, epsilon = 1e-6);
+        assert!(res.state.get_best_cost() < 1e-6);
+    }
+}
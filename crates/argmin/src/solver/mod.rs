@@ -5,16 +5,40 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
+pub mod activeset;
+pub mod augmentedlagrangian;
+pub mod bayesopt;
+pub mod bobyqa;
 pub mod brent;
+pub mod cmaes;
 pub mod conjugategradient;
+pub mod coordinatedescent;
+pub(crate) mod dense;
+pub mod differentialevolution;
+pub mod frankwolfe;
 pub mod gaussnewton;
+pub mod geneticalgorithm;
+pub mod globalopt;
 pub mod goldensectionsearch;
 pub mod gradientdescent;
+pub mod interiorpoint;
 pub mod landweber;
+pub mod levenbergmarquardt;
 pub mod linesearch;
 pub mod neldermead;
 pub mod newton;
+pub mod nonlinearsystem;
+pub mod nsga;
+pub mod paralleltempering;
 pub mod particleswarm;
+pub mod patternsearch;
+pub mod powell;
+pub mod proximalgradient;
 pub mod quasinewton;
+pub(crate) mod random;
+pub mod rootfinding;
+pub mod simplex;
 pub mod simulatedannealing;
+pub mod sqp;
+pub mod stochastic;
 pub mod trustregion;
@@ -6,8 +6,9 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::core::{
-    ArgminFloat, Error, Executor, Gradient, Hessian, IterState, LineSearch, Operator,
-    OptimizationResult, Problem, Solver, State, TerminationReason, TerminationStatus, KV,
+    ArgminFloat, Error, Executor, Gradient, Hessian, HessianVectorProduct, IterState, LineSearch,
+    MatrixFree, Operator, OptimizationResult, Problem, Solver, State, TerminationReason,
+    TerminationStatus, KV,
 };
 use crate::solver::conjugategradient::ConjugateGradient;
 use argmin_math::{
@@ -15,6 +16,7 @@ use argmin_math::{
 };
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
 
 /// # Newton-Conjugate-Gradient (Newton-CG) method
 ///
@@ -25,19 +27,26 @@ use serde::{Deserialize, Serialize};
 ///
 /// The optimization problem is required to implement [`Gradient`] and [`Hessian`].
 ///
+/// Since CG only needs products of the Hessian with vectors, the full Hessian never has to be
+/// formed. After calling [`matrix_free`](`NewtonCG::matrix_free`), the optimization problem is
+/// required to implement [`Gradient`] and [`HessianVectorProduct`] instead. If only the gradient
+/// is available, [`FiniteDiffHessianVec`](`crate::core::FiniteDiffHessianVec`) can be used.
+///
 /// ## Reference
 ///
 /// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 /// Springer. ISBN 0-387-30303-0.
 #[derive(Clone)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
-pub struct NewtonCG<L, F> {
+pub struct NewtonCG<L, F, M = ()> {
     /// line search
     linesearch: L,
     /// curvature_threshold
     curvature_threshold: F,
     /// Tolerance for the stopping criterion based on cost difference
     tol: F,
+    /// Selects how products with the Hessian are computed
+    mode: PhantomData<M>,
 }
 
 impl<L, F> NewtonCG<L, F>
@@ -58,9 +67,35 @@ where
             linesearch,
             curvature_threshold: float!(0.0),
             tol: F::epsilon(),
+            mode: PhantomData,
+        }
+    }
+
+    /// Compute products with the Hessian via [`HessianVectorProduct`] instead of evaluating the
+    /// full [`Hessian`] in each iteration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::newton::NewtonCG;
+    /// # use argmin::core::MatrixFree;
+    /// # let linesearch = ();
+    /// let ncg: NewtonCG<_, f64, MatrixFree> = NewtonCG::new(linesearch).matrix_free();
+    /// ```
+    pub fn matrix_free(self) -> NewtonCG<L, F, MatrixFree> {
+        NewtonCG {
+            linesearch: self.linesearch,
+            curvature_threshold: self.curvature_threshold,
+            tol: self.tol,
+            mode: PhantomData,
         }
     }
+}
 
+impl<L, F, M> NewtonCG<L, F, M>
+where
+    F: ArgminFloat,
+{
     /// Set curvature threshold
     ///
     /// Defaults to 0.
@@ -103,6 +138,85 @@ where
         self.tol = tol;
         Ok(self)
     }
+
+    /// Approximately solves the Newton equations `H * x = -g` with a truncated CG
+    fn search_direction<O, C, P, G>(
+        &self,
+        cg_problem: &mut Problem<O>,
+        mut cg: ConjugateGradient<P, F, C>,
+        grad: &G,
+        zero: P,
+    ) -> Result<P, Error>
+    where
+        ConjugateGradient<P, F, C>: Solver<O, IterState<P, (), (), (), P, F>>,
+        P: Clone,
+        G: ArgminL2Norm<F> + ArgminMul<F, P>,
+    {
+        let mut x_p = zero;
+
+        let (mut cg_state, _): (IterState<_, _, _, _, _, _>, _) =
+            cg.init(cg_problem, IterState::new().param(x_p.clone()))?;
+
+        let grad_norm_factor = float!(0.5).min(grad.l2_norm().sqrt()) * grad.l2_norm();
+
+        for iter in 0.. {
+            (cg_state, _) = cg.next_iter(cg_problem, cg_state)?;
+
+            let cost = cg_state.get_cost();
+
+            let x = cg_state.take_param().unwrap();
+
+            if cg.get_prev_curvature() <= self.curvature_threshold {
+                if iter == 0 {
+                    return Ok(grad.mul(&(float!(-1.0))));
+                } else {
+                    return Ok(x_p);
+                }
+            }
+
+            if cost <= grad_norm_factor {
+                return Ok(x);
+            }
+
+            cg_state = cg_state.param(x.clone()).cost(cost);
+            x_p = x;
+        }
+        unreachable!()
+    }
+
+    /// Performs a line search along `direction` and returns the new parameter vector and its cost
+    fn line_search<O, P, G>(
+        &mut self,
+        problem: &mut Problem<O>,
+        param: P,
+        grad: G,
+        cost: F,
+        direction: P,
+    ) -> Result<(P, F), Error>
+    where
+        L: Clone + LineSearch<P, F> + Solver<O, IterState<P, G, (), (), (), F>>,
+        P: Clone,
+    {
+        // TODO: Should the algorithm stop when search direction is close to 0?
+        self.linesearch.search_direction(direction);
+
+        // Run solver
+        let OptimizationResult {
+            problem: line_problem,
+            state: mut linesearch_state,
+            ..
+        } = Executor::new(problem.take_problem().unwrap(), self.linesearch.clone())
+            .configure(|state| state.param(param).gradient(grad).cost(cost))
+            .ctrlc(false)
+            .run()?;
+
+        problem.consume_problem(line_problem);
+
+        Ok((
+            linesearch_state.take_param().unwrap(),
+            linesearch_state.get_cost(),
+        ))
+    }
 }
 
 impl<O, L, P, G, H, F> Solver<O, IterState<P, G, (), H, (), F>> for NewtonCG<L, F>
@@ -145,69 +259,71 @@ where
 
         // Solve CG subproblem
         let mut cg_problem = Problem::new(CGSubProblem::new(&hessian));
+        let cg = ConjugateGradient::new(grad.mul(&(float!(-1.0))));
+        let x = self.search_direction(&mut cg_problem, cg, &grad, param.zero_like())?;
 
-        let mut x_p = param.zero_like();
-        let mut x = param.zero_like();
-        let mut cg = ConjugateGradient::new(grad.mul(&(float!(-1.0))));
-
-        let (mut cg_state, _): (IterState<_, _, _, _, _, _>, _) =
-            cg.init(&mut cg_problem, IterState::new().param(x_p.clone()))?;
-
-        let grad_norm_factor = float!(0.5).min(grad.l2_norm().sqrt()) * grad.l2_norm();
-
-        for iter in 0.. {
-            (cg_state, _) = cg.next_iter(&mut cg_problem, cg_state)?;
+        // perform line search
+        let line_cost = state.get_cost();
+        let (new_param, new_cost) = self.line_search(problem, param, grad, line_cost, x)?;
 
-            let cost = cg_state.get_cost();
+        Ok((state.param(new_param).cost(new_cost), None))
+    }
 
-            x = cg_state.take_param().unwrap();
-            let p = cg.get_prev_p()?;
+    fn terminate(&mut self, state: &IterState<P, G, (), H, (), F>) -> TerminationStatus {
+        if (state.get_cost() - state.get_prev_cost()).abs() < self.tol {
+            TerminationStatus::Terminated(TerminationReason::SolverConverged)
+        } else {
+            TerminationStatus::NotTerminated
+        }
+    }
+}
 
-            let curvature = p.dot(&hessian.dot(p));
-            if curvature <= self.curvature_threshold {
-                if iter == 0 {
-                    x = grad.mul(&(float!(-1.0)));
-                } else {
-                    x = x_p;
-                }
-                break;
-            }
+impl<O, L, P, G, F> Solver<O, IterState<P, G, (), (), (), F>> for NewtonCG<L, F, MatrixFree>
+where
+    O: Gradient<Param = P, Gradient = G> + HessianVectorProduct<Param = P, Output = P>,
+    P: Clone
+        + ArgminSub<P, P>
+        + ArgminDot<P, F>
+        + ArgminScaledAdd<P, F, P>
+        + ArgminMul<F, P>
+        + ArgminConj
+        + ArgminZeroLike,
+    G: ArgminL2Norm<F> + ArgminMul<F, P>,
+    L: Clone + LineSearch<P, F> + Solver<O, IterState<P, G, (), (), (), F>>,
+    F: ArgminFloat + ArgminL2Norm<F>,
+{
+    const NAME: &'static str = "Newton-CG (matrix-free)";
 
-            if cost <= grad_norm_factor {
-                break;
-            }
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, (), (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`NewtonCG` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let grad = state
+            .take_gradient()
+            .map(Result::Ok)
+            .unwrap_or_else(|| problem.gradient(&param))?;
 
-            cg_state = cg_state.param(x.clone()).cost(cost);
-            x_p = x.clone();
-        }
+        // Solve CG subproblem. Products with the Hessian are evaluated directly on `problem` and
+        // are therefore counted as `hessian_vec_count`.
+        let cg = ConjugateGradient::new(grad.mul(&(float!(-1.0)))).matrix_free(param.clone());
+        let x = self.search_direction(problem, cg, &grad, param.zero_like())?;
 
         // perform line search
-        // TODO: Should the algorithm stop when search direction is close to 0?
-        self.linesearch.search_direction(x);
-
         let line_cost = state.get_cost();
+        let (new_param, new_cost) = self.line_search(problem, param, grad, line_cost, x)?;
 
-        // Run solver
-        let OptimizationResult {
-            problem: line_problem,
-            state: mut linesearch_state,
-            ..
-        } = Executor::new(problem.take_problem().unwrap(), self.linesearch.clone())
-            .configure(|state| state.param(param).gradient(grad).cost(line_cost))
-            .ctrlc(false)
-            .run()?;
-
-        problem.consume_problem(line_problem);
-
-        Ok((
-            state
-                .param(linesearch_state.take_param().unwrap())
-                .cost(linesearch_state.get_cost()),
-            None,
-        ))
+        Ok((state.param(new_param).cost(new_cost), None))
     }
 
-    fn terminate(&mut self, state: &IterState<P, G, (), H, (), F>) -> TerminationStatus {
+    fn terminate(&mut self, state: &IterState<P, G, (), (), (), F>) -> TerminationStatus {
         if (state.get_cost() - state.get_prev_cost()).abs() < self.tol {
             TerminationStatus::Terminated(TerminationReason::SolverConverged)
         } else {
@@ -249,9 +365,11 @@ mod tests {
     #![allow(clippy::let_unit_value)]
 
     use super::*;
-    use crate::core::{test_utils::TestProblem, ArgminError};
+    use crate::core::{test_utils::TestProblem, ArgminError, CostFunction, FiniteDiffHessianVec};
     use crate::solver::linesearch::MoreThuenteLineSearch;
     use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+    use argmin_testfunctions::{rosenbrock, rosenbrock_derivative, rosenbrock_hessian};
 
     test_trait_impl!(
         newton_cg,
@@ -283,6 +401,7 @@ mod tests {
             linesearch,
             curvature_threshold,
             tol,
+            mode: _,
         } = ncg;
         assert_eq!(linesearch, ls);
         assert_eq!(curvature_threshold.to_ne_bytes(), 0.0f64.to_ne_bytes());
@@ -299,6 +418,7 @@ mod tests {
             linesearch,
             curvature_threshold,
             tol,
+            mode: _,
         } = ncg;
         assert_eq!(linesearch, ls);
         assert_eq!(curvature_threshold.to_ne_bytes(), 1e-6f64.to_ne_bytes());
@@ -339,5 +459,103 @@ mod tests {
         );
     }
 
-    // TODO: Test next_iter.
+    #[test]
+    fn test_matrix_free() {
+        let ncg: NewtonCG<_, f64, MatrixFree> = NewtonCG::new(())
+            .with_curvature_threshold(1e-6)
+            .with_tolerance(1e-4)
+            .unwrap()
+            .matrix_free();
+        let NewtonCG {
+            curvature_threshold,
+            tol,
+            ..
+        } = ncg;
+        assert_eq!(curvature_threshold.to_ne_bytes(), 1e-6f64.to_ne_bytes());
+        assert_eq!(tol.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+    }
+
+    #[derive(Clone)]
+    struct Rosenbrock {}
+
+    impl CostFunction for Rosenbrock {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(rosenbrock(p, 1.0, 100.0))
+        }
+    }
+
+    impl Gradient for Rosenbrock {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(rosenbrock_derivative(p, 1.0, 100.0))
+        }
+    }
+
+    impl Hessian for Rosenbrock {
+        type Param = Vec<f64>;
+        type Hessian = Vec<Vec<f64>>;
+
+        fn hessian(&self, p: &Self::Param) -> Result<Self::Hessian, Error> {
+            Ok(rosenbrock_hessian(p, 1.0, 100.0))
+        }
+    }
+
+    impl HessianVectorProduct for Rosenbrock {
+        type Param = Vec<f64>;
+        type Output = Vec<f64>;
+
+        fn hessian_vec(&self, p: &Self::Param, v: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(rosenbrock_hessian(p, 1.0, 100.0).dot(v))
+        }
+    }
+
+    #[test]
+    fn test_rosenbrock() {
+        let linesearch: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> =
+            MoreThuenteLineSearch::new();
+        let init_param = vec![-1.2, 1.0, 0.5];
+
+        let full = Executor::new(Rosenbrock {}, NewtonCG::new(linesearch.clone()))
+            .configure(|state| state.param(init_param.clone()).max_iters(20))
+            .run()
+            .unwrap();
+
+        let matrix_free = Executor::new(
+            Rosenbrock {},
+            NewtonCG::new(linesearch.clone()).matrix_free(),
+        )
+        .configure(|state| state.param(init_param.clone()).max_iters(20))
+        .run()
+        .unwrap();
+
+        let finite_diff = Executor::new(
+            FiniteDiffHessianVec::<_, f64>::new(Rosenbrock {}),
+            NewtonCG::new(linesearch).matrix_free(),
+        )
+        .configure(|state| state.param(init_param.clone()).max_iters(20))
+        .run()
+        .unwrap();
+
+        // Both variants compute the same products and therefore take identical steps
+        let x_full = full.state().get_best_param().unwrap();
+        let x_matrix_free = matrix_free.state().get_best_param().unwrap();
+        let x_finite_diff = finite_diff.state().get_best_param().unwrap();
+        for i in 0..init_param.len() {
+            assert_relative_eq!(x_full[i], x_matrix_free[i], epsilon = 1e-12);
+            assert_relative_eq!(x_full[i], x_finite_diff[i], epsilon = 1e-5);
+        }
+        assert!(full.state().get_best_cost() < rosenbrock(&init_param, 1.0, 100.0));
+
+        assert!(full.problem().counts["hessian_count"] > 0);
+        assert!(!full.problem().counts.contains_key("hessian_vec_count"));
+        for counts in [&matrix_free.problem().counts, &finite_diff.problem().counts] {
+            assert!(counts["hessian_vec_count"] > 0);
+            assert!(!counts.contains_key("hessian_count"));
+        }
+    }
 }
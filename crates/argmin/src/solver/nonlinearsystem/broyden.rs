@@ -0,0 +1,480 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::Iterate;
+use crate::core::{
+    ArgminFloat, Error, IterState, Operator, Problem, Solver, State, TerminationReason, KV,
+};
+use crate::solver::dense::{dot, invert, to_rows};
+use argmin_math::{ArgminGet, ArgminSet, ArgminSize};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Update of the approximation of the inverse Jacobian in [`Broyden`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum BroydenUpdate {
+    /// "Good" Broyden update, a rank one update of the Jacobian which satisfies the secant
+    /// equation with the least change of the Jacobian
+    Good,
+    /// "Bad" Broyden update, a rank one update of the inverse Jacobian which satisfies the secant
+    /// equation with the least change of the inverse Jacobian
+    Bad,
+}
+
+/// # Broyden's method
+///
+/// Quasi-Newton method for square systems of nonlinear equations `F(x) = 0`. Instead of the
+/// Jacobian, an approximation `H` of the inverse Jacobian is maintained, which is updated after
+/// every step `s` with change of the residuals `y` such that the secant equation `H y = s` holds.
+/// The step direction is `d = -H F(x)` and the step length is chosen by a backtracking line search
+/// on `||F(x)||^2 / 2`. The method converges superlinearly close to a root with nonsingular
+/// Jacobian.
+///
+/// Two updates are available (see [`BroydenUpdate`]), which can be chosen via
+/// [`with_update`](`Broyden::with_update`):
+///
+/// * "good" (default): `H <- H + (s - H y) s^T H / (s^T H y)`
+/// * "bad": `H <- H + (s - H y) y^T / (y^T y)`
+///
+/// The initial approximation is the inverse of the Jacobian at the initial guess. If provided via
+/// [`with_initial_jacobian`](`Broyden::with_initial_jacobian`), this Jacobian is used, otherwise
+/// it is approximated by forward differences, which costs one evaluation of the residuals per
+/// parameter. If the line search fails along the quasi-Newton direction, the approximation is
+/// reset to a finite difference approximation at the current iterate once before the solver gives
+/// up. The number of these resets is reported as KV metric `resets`.
+///
+/// The solver terminates if the residual norm falls below a tolerance (see
+/// [`with_tolerance`](`Broyden::with_tolerance`)), if the length of a step relative to the length
+/// of the parameter vector falls below a tolerance (see
+/// [`with_step_tolerance`](`Broyden::with_step_tolerance`)), if the approximation of the Jacobian
+/// is singular or if the line search fails.
+///
+/// Requires an initial parameter vector.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`Operator`] (returning the residuals).
+///
+/// ## Reference
+///
+/// C. G. Broyden (1965). A class of methods for solving nonlinear simultaneous equations.
+/// Mathematics of Computation 19, 577–593. DOI: <https://doi.org/10.1090/S0025-5718-1965-0198670-6>
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Broyden<F> {
+    /// Update of the inverse Jacobian
+    update: BroydenUpdate,
+    /// Jacobian at the initial guess (row-major)
+    init_jacobian: Option<Vec<Vec<F>>>,
+    /// Approximation of the inverse Jacobian (row-major)
+    inv_jacobian: Vec<Vec<F>>,
+    /// Number of resets of the inverse Jacobian
+    resets: u64,
+    /// Tolerance on the residual norm
+    tol_f: F,
+    /// Tolerance on the relative step length
+    tol_x: F,
+}
+
+impl<F: ArgminFloat> Broyden<F> {
+    /// Construct a new instance of [`Broyden`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nonlinearsystem::Broyden;
+    /// let broyden: Broyden<f64> = Broyden::new();
+    /// ```
+    pub fn new() -> Self {
+        Broyden {
+            update: BroydenUpdate::Good,
+            init_jacobian: None,
+            inv_jacobian: vec![],
+            resets: 0,
+            tol_f: F::epsilon().powf(float!(2.0 / 3.0)),
+            tol_x: F::epsilon(),
+        }
+    }
+
+    /// Set update of the inverse Jacobian.
+    ///
+    /// Defaults to [`BroydenUpdate::Good`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nonlinearsystem::{Broyden, BroydenUpdate};
+    /// let broyden: Broyden<f64> = Broyden::new().with_update(BroydenUpdate::Bad);
+    /// ```
+    #[must_use]
+    pub fn with_update(mut self, update: BroydenUpdate) -> Self {
+        self.update = update;
+        self
+    }
+
+    /// Set Jacobian at the initial guess.
+    ///
+    /// The Jacobian must be square and nonsingular. If not provided, it is approximated by forward
+    /// differences.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nonlinearsystem::Broyden;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let broyden = Broyden::new().with_initial_jacobian(&vec![vec![2.0f64, 0.0], vec![0.0, 1.0]])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_initial_jacobian<J>(mut self, jacobian: &J) -> Result<Self, Error>
+    where
+        J: ArgminGet<(usize, usize), F> + ArgminSize<(usize, usize)>,
+    {
+        let (rows, cols) = jacobian.shape();
+        if rows != cols {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Broyden`: initial Jacobian must be square."
+            ));
+        }
+        self.init_jacobian = Some(to_rows(jacobian));
+        Ok(self)
+    }
+
+    /// Set tolerance on the residual norm.
+    ///
+    /// Must be non-negative and defaults to `EPSILON^(2/3)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nonlinearsystem::Broyden;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let broyden = Broyden::new().with_tolerance(1e-8f64)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Broyden`: tolerance on the residual norm must be >= 0."
+            ));
+        }
+        self.tol_f = tol;
+        Ok(self)
+    }
+
+    /// Set tolerance on the length of a step relative to the length of the parameter vector.
+    ///
+    /// Must be non-negative and defaults to `EPSILON`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nonlinearsystem::Broyden;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let broyden = Broyden::new().with_step_tolerance(1e-10f64)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_step_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Broyden`: tolerance on the step length must be >= 0."
+            ));
+        }
+        self.tol_x = tol;
+        Ok(self)
+    }
+
+    /// Quasi-Newton direction `-H F(x)`
+    fn direction(&self, fx: &[F]) -> Vec<F> {
+        self.inv_jacobian.iter().map(|row| -dot(row, fx)).collect()
+    }
+
+    /// Updates the inverse Jacobian with step `s` and change of residuals `y`
+    fn update_inv_jacobian(&mut self, s: &[F], y: &[F]) {
+        let hy: Vec<F> = self.inv_jacobian.iter().map(|row| dot(row, y)).collect();
+        let u: Vec<F> = s.iter().zip(hy.iter()).map(|(&si, &hi)| si - hi).collect();
+        let (v, denom) = match self.update {
+            BroydenUpdate::Good => {
+                // s^T H
+                let n = s.len();
+                let sh: Vec<F> = (0..n)
+                    .map(|j| {
+                        self.inv_jacobian
+                            .iter()
+                            .zip(s.iter())
+                            .fold(float!(0.0), |acc, (row, &si)| acc + si * row[j])
+                    })
+                    .collect();
+                (sh, dot(s, &hy))
+            }
+            BroydenUpdate::Bad => (y.to_vec(), dot(y, y)),
+        };
+        if denom == float!(0.0) || !denom.is_finite() {
+            return;
+        }
+        for (row, &ui) in self.inv_jacobian.iter_mut().zip(u.iter()) {
+            for (hij, &vj) in row.iter_mut().zip(v.iter()) {
+                *hij = *hij + ui * vj / denom;
+            }
+        }
+    }
+}
+
+impl<F: ArgminFloat> Default for Broyden<F> {
+    fn default() -> Broyden<F> {
+        Broyden::new()
+    }
+}
+
+impl<O, P, R, F> Solver<O, IterState<P, (), (), (), R, F>> for Broyden<F>
+where
+    O: Operator<Param = P, Output = R>,
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    R: Clone + ArgminGet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Broyden";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), R, F>,
+    ) -> Result<(IterState<P, (), (), (), R, F>, Option<KV>), Error> {
+        let iterate = Iterate::init("Broyden", problem, &mut state)?;
+        self.resets = 0;
+        if iterate.norm <= self.tol_f {
+            return Ok((
+                iterate
+                    .into_state(state)
+                    .terminate_with(TerminationReason::SolverConverged),
+                None,
+            ));
+        }
+        let jacobian = match self.init_jacobian.as_ref() {
+            Some(jacobian) => {
+                if jacobian.len() != iterate.x.len() {
+                    return Err(argmin_error!(
+                        InvalidParameter,
+                        "`Broyden`: initial Jacobian must match the number of parameters."
+                    ));
+                }
+                jacobian.clone()
+            }
+            None => iterate.jacobian_fd(problem)?,
+        };
+        self.inv_jacobian = match invert(jacobian) {
+            Some(inv) => inv,
+            None => {
+                return Ok((
+                    iterate
+                        .into_state(state)
+                        .terminate_with(TerminationReason::SolverExit(
+                            "Singular Jacobian".to_string(),
+                        )),
+                    None,
+                ))
+            }
+        };
+        Ok((iterate.into_state(state), None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, (), (), (), R, F>,
+    ) -> Result<(IterState<P, (), (), (), R, F>, Option<KV>), Error> {
+        let iterate = Iterate::from_state("Broyden", &state)?;
+        let mut result = iterate.line_search(problem, &self.direction(&iterate.fx))?;
+        if result.is_none() {
+            // Restart from a finite difference approximation of the Jacobian
+            self.resets += 1;
+            let jacobian = iterate.jacobian_fd(problem)?;
+            self.inv_jacobian = match invert(jacobian) {
+                Some(inv) => inv,
+                None => {
+                    return Ok((
+                        state.terminate_with(TerminationReason::SolverExit(
+                            "Singular Jacobian".to_string(),
+                        )),
+                        Some(kv!("resets" => self.resets;)),
+                    ))
+                }
+            };
+            result = iterate.line_search(problem, &self.direction(&iterate.fx))?;
+        }
+        let (alpha, new_iterate) = match result {
+            Some(res) => res,
+            None => {
+                return Ok((
+                    state.terminate_with(TerminationReason::SolverExit(
+                        "Line search failed".to_string(),
+                    )),
+                    Some(kv!("resets" => self.resets;)),
+                ))
+            }
+        };
+
+        let s: Vec<F> = new_iterate
+            .x
+            .iter()
+            .zip(iterate.x.iter())
+            .map(|(&a, &b)| a - b)
+            .collect();
+        let y: Vec<F> = new_iterate
+            .fx
+            .iter()
+            .zip(iterate.fx.iter())
+            .map(|(&a, &b)| a - b)
+            .collect();
+        self.update_inv_jacobian(&s, &y);
+
+        let converged = new_iterate.converged(&iterate.x, self.tol_f, self.tol_x);
+        let state = new_iterate.into_state(state);
+        let kv = Some(kv!("step_length" => alpha; "resets" => self.resets;));
+        if converged {
+            Ok((state.terminate_with(TerminationReason::SolverConverged), kv))
+        } else {
+            Ok((state, kv))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::nonlinearsystem::tests::{BroydenTridiagonal, RosenbrockSystem};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(broyden, Broyden<f64>);
+
+    #[test]
+    fn test_new() {
+        let Broyden {
+            update,
+            init_jacobian,
+            inv_jacobian,
+            resets,
+            tol_f,
+            tol_x,
+        } = Broyden::<f64>::new();
+        assert_eq!(update, BroydenUpdate::Good);
+        assert!(init_jacobian.is_none());
+        assert!(inv_jacobian.is_empty());
+        assert_eq!(resets, 0);
+        assert_eq!(
+            tol_f.to_ne_bytes(),
+            f64::EPSILON.powf(2.0 / 3.0).to_ne_bytes()
+        );
+        assert_eq!(tol_x.to_ne_bytes(), f64::EPSILON.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_builders() {
+        let broyden: Broyden<f64> = Broyden::new().with_update(BroydenUpdate::Bad);
+        assert_eq!(broyden.update, BroydenUpdate::Bad);
+
+        assert_error!(
+            Broyden::new().with_initial_jacobian(&vec![vec![1.0f64, 0.0]]),
+            ArgminError,
+            "Invalid parameter: \"`Broyden`: initial Jacobian must be square.\""
+        );
+        assert_error!(
+            Broyden::new().with_tolerance(-1.0f64),
+            ArgminError,
+            "Invalid parameter: \"`Broyden`: tolerance on the residual norm must be >= 0.\""
+        );
+        assert_error!(
+            Broyden::new().with_step_tolerance(-1.0f64),
+            ArgminError,
+            "Invalid parameter: \"`Broyden`: tolerance on the step length must be >= 0.\""
+        );
+    }
+
+    #[test]
+    fn test_secant_equation() {
+        for update in [BroydenUpdate::Good, BroydenUpdate::Bad] {
+            let mut broyden: Broyden<f64> = Broyden::new().with_update(update);
+            broyden.inv_jacobian = vec![vec![1.0, 0.5], vec![0.0, 2.0]];
+            let s = vec![0.3, -0.2];
+            let y = vec![1.0, 0.4];
+            broyden.update_inv_jacobian(&s, &y);
+            let hy: Vec<f64> = broyden.inv_jacobian.iter().map(|r| dot(r, &y)).collect();
+            assert_relative_eq!(hy[0], s[0], epsilon = 1e-14);
+            assert_relative_eq!(hy[1], s[1], epsilon = 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_tridiagonal() {
+        for update in [BroydenUpdate::Good, BroydenUpdate::Bad] {
+            let res = Executor::new(BroydenTridiagonal {}, Broyden::new().with_update(update))
+                .configure(|state| state.param(vec![-1.0; 10]).max_iters(100))
+                .run()
+                .unwrap();
+            assert_eq!(
+                res.state().get_termination_reason(),
+                Some(&TerminationReason::SolverConverged)
+            );
+            assert!(res.state().get_cost() < 1e-9);
+            let residuals = res.state().get_residuals().unwrap();
+            assert!(residuals.iter().all(|r| r.abs() < 1e-9));
+        }
+    }
+
+    #[test]
+    fn test_initial_jacobian() {
+        let jacobian = vec![vec![24.0, 10.0], vec![-1.0, 0.0]];
+        let solver = Broyden::new().with_initial_jacobian(&jacobian).unwrap();
+        let res = Executor::new(RosenbrockSystem {}, solver)
+            .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(100))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state().get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        let param = res.state().get_best_param().unwrap();
+        assert_relative_eq!(param[0], 1.0, epsilon = 1e-8);
+        assert_relative_eq!(param[1], 1.0, epsilon = 1e-8);
+
+        let solver = Broyden::new()
+            .with_initial_jacobian(&vec![vec![1.0]])
+            .unwrap();
+        let res = Executor::new(RosenbrockSystem {}, solver)
+            .configure(|state| state.param(vec![-1.2, 1.0]))
+            .run();
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`Broyden`: initial Jacobian must match the number of parameters.\""
+        );
+    }
+
+    #[test]
+    fn test_init_errors() {
+        let res = Executor::new(RosenbrockSystem {}, Broyden::new()).run();
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`Broyden` requires an initial guess. Please provide an ",
+                "initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+}
@@ -0,0 +1,404 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Nonlinear systems of equations
+//!
+//! Solvers for square systems of nonlinear equations `F(x) = 0`, where `F` maps `n` parameters to
+//! `n` residuals and is provided via the [`Operator`](`crate::core::Operator`) trait.
+//!
+//! * [Damped Newton method](`DampedNewton`), which requires the
+//!   [`Jacobian`](`crate::core::Jacobian`) of `F`
+//! * [Broyden's method](`Broyden`) with the "good" and "bad" update, which optionally takes an
+//!   initial Jacobian and otherwise approximates it by finite differences
+//! * [Newton-Krylov method](`NewtonKrylov`), which solves the Newton equations with GMRES and
+//!   only requires products of the Jacobian with vectors, which are approximated by finite
+//!   differences of `F`
+//!
+//! All methods globalize the iteration with a backtracking line search on the merit function
+//! `||F(x)||^2 / 2`. The residuals of the current iterate are stored in the
+//! [`IterState`](`crate::core::IterState`) and can be retrieved via
+//! [`get_residuals`](`crate::core::IterState::get_residuals`); the cost function value is the L2
+//! norm of the residuals. The solvers converge if the residual norm or the relative length of a
+//! step falls below a tolerance. The step length chosen by the line search is reported as KV
+//! metric `step_length`.
+//!
+//! ## References
+//!
+//! \[0\] J. E. Dennis Jr. and Robert B. Schnabel (1996). Numerical Methods for Unconstrained
+//! Optimization and Nonlinear Equations. SIAM. DOI: <https://doi.org/10.1137/1.9781611971200>
+//!
+//! \[1\] C. G. Broyden (1965). A class of methods for solving nonlinear simultaneous equations.
+//! Mathematics of Computation 19, 577–593. DOI: <https://doi.org/10.1090/S0025-5718-1965-0198670-6>
+//!
+//! \[2\] D. A. Knoll and D. E. Keyes (2004). Jacobian-free Newton–Krylov methods: a survey of
+//! approaches and applications. Journal of Computational Physics 193 (2), 357–397.
+//! DOI: <https://doi.org/10.1016/j.jcp.2003.08.010>
+
+mod broyden;
+mod newton;
+mod newtonkrylov;
+
+pub use self::broyden::*;
+pub use self::newton::*;
+pub use self::newtonkrylov::*;
+
+use crate::core::{ArgminFloat, Error, IterState, Operator, Problem, State};
+use crate::solver::dense::{norm, to_vec};
+use argmin_math::{ArgminGet, ArgminSet, ArgminSize};
+
+/// Sufficient decrease parameter of the line search
+const ARMIJO: f64 = 1e-4;
+
+/// Smallest step length tried by the line search
+const MIN_STEP_LENGTH: f64 = 1e-10;
+
+/// Parameter vector and residuals at an iterate, together with their internal representation
+#[derive(Clone)]
+struct Iterate<P, R, F> {
+    /// Parameter vector
+    param: P,
+    /// Residuals
+    residuals: R,
+    /// Parameter vector as internal vector
+    x: Vec<F>,
+    /// Residuals as internal vector
+    fx: Vec<F>,
+    /// L2 norm of the residuals
+    norm: F,
+}
+
+impl<P, R, F> Iterate<P, R, F>
+where
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    R: Clone + ArgminGet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+{
+    /// Takes the initial guess from the state and evaluates the residuals. Fails if there is no
+    /// initial guess or if the system is not square.
+    fn init<O>(
+        name: &str,
+        problem: &mut Problem<O>,
+        state: &mut IterState<P, (), (), (), R, F>,
+    ) -> Result<Self, Error>
+    where
+        O: Operator<Param = P, Output = R>,
+    {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            format!(
+                "`{name}` requires an initial guess. Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let iterate = Iterate::evaluate(problem, param)?;
+        if iterate.x.is_empty() || iterate.x.len() != iterate.fx.len() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!(
+                    "`{name}`: number of residuals ({}) must equal number of parameters ({}).",
+                    iterate.fx.len(),
+                    iterate.x.len()
+                )
+            ));
+        }
+        Ok(iterate)
+    }
+
+    /// Restores the current iterate from the state
+    fn from_state(name: &str, state: &IterState<P, (), (), (), R, F>) -> Result<Self, Error> {
+        let param = state.get_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            format!("`{name}`: Parameter vector in state not set.")
+        ))?;
+        let residuals = state.get_residuals().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            format!("`{name}`: Residuals in state not set.")
+        ))?;
+        let fx = to_vec(residuals);
+        Ok(Iterate {
+            param: param.clone(),
+            residuals: residuals.clone(),
+            x: to_vec(param),
+            norm: norm(&fx),
+            fx,
+        })
+    }
+
+    /// Evaluates the residuals at `param`
+    fn evaluate<O>(problem: &mut Problem<O>, param: P) -> Result<Self, Error>
+    where
+        O: Operator<Param = P, Output = R>,
+    {
+        let residuals = problem.apply(&param)?;
+        let fx = to_vec(&residuals);
+        Ok(Iterate {
+            x: to_vec(&param),
+            norm: norm(&fx),
+            param,
+            residuals,
+            fx,
+        })
+    }
+
+    /// Evaluates the residuals at the internal vector `x`
+    fn evaluate_at<O>(&self, problem: &mut Problem<O>, x: &[F]) -> Result<Self, Error>
+    where
+        O: Operator<Param = P, Output = R>,
+    {
+        Iterate::evaluate(problem, self.to_param(x))
+    }
+
+    /// Stores the iterate in the state
+    fn into_state(self, state: IterState<P, (), (), (), R, F>) -> IterState<P, (), (), (), R, F> {
+        state
+            .param(self.param)
+            .residuals(self.residuals)
+            .cost(self.norm)
+    }
+
+    /// Backtracking line search along `d` on the merit function `||F(x)||^2 / 2`, assuming that
+    /// `d` is (an approximation of) the Newton direction. Trial step lengths are obtained by
+    /// minimizing a quadratic model of the merit function, safeguarded to `[0.1, 0.5]` times the
+    /// previous step length. Returns the accepted step length and the new iterate, or `None` if no
+    /// step length above `1e-10` achieves sufficient decrease.
+    fn line_search<O>(&self, problem: &mut Problem<O>, d: &[F]) -> Result<Option<(F, Self)>, Error>
+    where
+        O: Operator<Param = P, Output = R>,
+    {
+        let phi0 = float!(0.5) * self.norm * self.norm;
+        // Directional derivative of the merit function along the exact Newton direction
+        let dphi0 = -self.norm * self.norm;
+        let mut alpha: F = float!(1.0);
+        while alpha >= float!(MIN_STEP_LENGTH) {
+            let x: Vec<F> = self
+                .x
+                .iter()
+                .zip(d.iter())
+                .map(|(&xi, &di)| xi + alpha * di)
+                .collect();
+            let trial = self.evaluate_at(problem, &x)?;
+            let phi = float!(0.5) * trial.norm * trial.norm;
+            if phi <= phi0 + float!(ARMIJO) * alpha * dphi0 {
+                return Ok(Some((alpha, trial)));
+            }
+            let alpha_q = -dphi0 * alpha * alpha / (float!(2.0) * (phi - phi0 - dphi0 * alpha));
+            alpha = if alpha_q.is_finite() {
+                alpha_q.max(float!(0.1) * alpha).min(float!(0.5) * alpha)
+            } else {
+                float!(0.5) * alpha
+            };
+        }
+        Ok(None)
+    }
+
+    /// Forward difference approximation of the Jacobian (row-major)
+    fn jacobian_fd<O>(&self, problem: &mut Problem<O>) -> Result<Vec<Vec<F>>, Error>
+    where
+        O: Operator<Param = P, Output = R>,
+    {
+        let n = self.x.len();
+        let mut jacobian = vec![vec![float!(0.0); n]; n];
+        let mut x = self.x.clone();
+        for j in 0..n {
+            let h = F::epsilon().sqrt() * self.x[j].abs().max(float!(1.0));
+            x[j] = self.x[j] + h;
+            let h = x[j] - self.x[j];
+            let fx = to_vec(&problem.apply(&self.to_param(&x))?);
+            for (row, (&fi_h, &fi)) in jacobian.iter_mut().zip(fx.iter().zip(self.fx.iter())) {
+                row[j] = (fi_h - fi) / h;
+            }
+            x[j] = self.x[j];
+        }
+        Ok(jacobian)
+    }
+
+    /// Forward difference approximation of the product of the Jacobian with `v`
+    fn jacobian_vec_fd<O>(&self, problem: &mut Problem<O>, v: &[F]) -> Result<Vec<F>, Error>
+    where
+        O: Operator<Param = P, Output = R>,
+    {
+        let v_norm = norm(v);
+        if v_norm == float!(0.0) {
+            return Ok(vec![float!(0.0); v.len()]);
+        }
+        let h = F::epsilon().sqrt() * (float!(1.0) + norm(&self.x)) / v_norm;
+        let x: Vec<F> = self
+            .x
+            .iter()
+            .zip(v.iter())
+            .map(|(&xi, &vi)| xi + h * vi)
+            .collect();
+        let fx = to_vec(&problem.apply(&self.to_param(&x))?);
+        Ok(fx
+            .iter()
+            .zip(self.fx.iter())
+            .map(|(&fi_h, &fi)| (fi_h - fi) / h)
+            .collect())
+    }
+
+    /// Converts an internal vector into a parameter vector
+    fn to_param(&self, x: &[F]) -> P {
+        let mut param = self.param.clone();
+        for (i, &xi) in x.iter().enumerate() {
+            param.set(i, xi);
+        }
+        param
+    }
+
+    /// Returns whether the residual norm is below `tol_f` or the step from `prev` is shorter than
+    /// `tol_x` relative to the length of the parameter vector
+    fn converged(&self, prev: &[F], tol_f: F, tol_x: F) -> bool {
+        let step: Vec<F> = self
+            .x
+            .iter()
+            .zip(prev.iter())
+            .map(|(&a, &b)| a - b)
+            .collect();
+        self.norm <= tol_f || norm(&step) <= tol_x * (float!(1.0) + norm(&self.x))
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+    use crate::core::Jacobian;
+    use approx::assert_relative_eq;
+
+    /// F(x, y) = (10 (y - x^2), 1 - x) with root (1, 1)
+    pub(super) struct RosenbrockSystem {}
+
+    impl Operator for RosenbrockSystem {
+        type Param = Vec<f64>;
+        type Output = Vec<f64>;
+
+        fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(vec![10.0 * (p[1] - p[0].powi(2)), 1.0 - p[0]])
+        }
+    }
+
+    impl Jacobian for RosenbrockSystem {
+        type Param = Vec<f64>;
+        type Jacobian = Vec<Vec<f64>>;
+
+        fn jacobian(&self, p: &Self::Param) -> Result<Self::Jacobian, Error> {
+            Ok(vec![vec![-20.0 * p[0], 10.0], vec![-1.0, 0.0]])
+        }
+    }
+
+    /// F(x) = arctan(x), for which undamped Newton diverges far from the root
+    pub(super) struct Arctan {}
+
+    impl Operator for Arctan {
+        type Param = Vec<f64>;
+        type Output = Vec<f64>;
+
+        fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(vec![p[0].atan()])
+        }
+    }
+
+    impl Jacobian for Arctan {
+        type Param = Vec<f64>;
+        type Jacobian = Vec<Vec<f64>>;
+
+        fn jacobian(&self, p: &Self::Param) -> Result<Self::Jacobian, Error> {
+            Ok(vec![vec![1.0 / (1.0 + p[0].powi(2))]])
+        }
+    }
+
+    /// F(x) = x^2 + 1, which has no root and a singular Jacobian at 0
+    pub(super) struct Singular {}
+
+    impl Operator for Singular {
+        type Param = Vec<f64>;
+        type Output = Vec<f64>;
+
+        fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(vec![p[0].powi(2) + 1.0])
+        }
+    }
+
+    impl Jacobian for Singular {
+        type Param = Vec<f64>;
+        type Jacobian = Vec<Vec<f64>>;
+
+        fn jacobian(&self, p: &Self::Param) -> Result<Self::Jacobian, Error> {
+            Ok(vec![vec![2.0 * p[0]]])
+        }
+    }
+
+    /// Broyden's tridiagonal function
+    /// F_i(x) = (3 - 2 x_i) x_i - x_{i-1} - 2 x_{i+1} + 1 with x_0 = x_{n+1} = 0
+    pub(super) struct BroydenTridiagonal {}
+
+    impl Operator for BroydenTridiagonal {
+        type Param = Vec<f64>;
+        type Output = Vec<f64>;
+
+        fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            let n = p.len();
+            Ok((0..n)
+                .map(|i| {
+                    let prev = if i > 0 { p[i - 1] } else { 0.0 };
+                    let next = if i + 1 < n { p[i + 1] } else { 0.0 };
+                    (3.0 - 2.0 * p[i]) * p[i] - prev - 2.0 * next + 1.0
+                })
+                .collect())
+        }
+    }
+
+    /// F(x) = A x - b
+    pub(super) struct LinearSystem {}
+
+    impl LinearSystem {
+        /// Solution of A x = b
+        pub(super) fn solution() -> Vec<f64> {
+            vec![2.0 / 9.0, 1.0 / 9.0, 13.0 / 9.0]
+        }
+    }
+
+    impl Operator for LinearSystem {
+        type Param = Vec<f64>;
+        type Output = Vec<f64>;
+
+        fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(vec![
+                4.0 * p[0] + p[1] - 1.0,
+                p[0] + 3.0 * p[1] + p[2] - 2.0,
+                p[1] + 2.0 * p[2] - 3.0,
+            ])
+        }
+    }
+
+    #[test]
+    fn test_jacobian_fd() {
+        let mut problem = Problem::new(RosenbrockSystem {});
+        let iterate = Iterate::evaluate(&mut problem, vec![-1.2, 1.0]).unwrap();
+        let fd = iterate.jacobian_fd(&mut problem).unwrap();
+        let exact = RosenbrockSystem {}.jacobian(&vec![-1.2, 1.0]).unwrap();
+        for (row_fd, row) in fd.iter().zip(exact.iter()) {
+            for (a, b) in row_fd.iter().zip(row.iter()) {
+                assert_relative_eq!(*a, *b, epsilon = 1e-6);
+            }
+        }
+        let jv = iterate.jacobian_vec_fd(&mut problem, &[1.0, 2.0]).unwrap();
+        assert_relative_eq!(jv[0], 44.0, epsilon = 1e-5);
+        assert_relative_eq!(jv[1], -1.0, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_line_search() {
+        let mut problem = Problem::new(Arctan {});
+        let iterate = Iterate::evaluate(&mut problem, vec![10.0]).unwrap();
+        // Full Newton step overshoots
+        let d = vec![-10.0f64.atan() * 101.0];
+        let (alpha, new_iterate) = iterate.line_search(&mut problem, &d).unwrap().unwrap();
+        assert!(alpha < 0.5);
+        assert!(new_iterate.norm < iterate.norm);
+        // No decrease along an ascent direction
+        assert!(iterate.line_search(&mut problem, &[1.0]).unwrap().is_none());
+    }
+}
@@ -0,0 +1,357 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::Iterate;
+use crate::core::{
+    ArgminFloat, Error, IterState, Jacobian, Operator, Problem, Solver, State, TerminationReason,
+    KV,
+};
+use crate::solver::dense::{lu_solve, to_rows};
+use argmin_math::{ArgminGet, ArgminSet, ArgminSize};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Damped Newton method
+///
+/// Newton's method for square systems of nonlinear equations `F(x) = 0`. In every iteration the
+/// Newton step `d` is computed from the linear system `J(x) d = -F(x)`, where `J` is the Jacobian
+/// of `F`, and the step length along `d` is chosen by a backtracking line search on
+/// `||F(x)||^2 / 2`. Close to a root with nonsingular Jacobian the full step is accepted and the
+/// method converges quadratically.
+///
+/// The solver terminates if the residual norm falls below a tolerance (see
+/// [`with_tolerance`](`DampedNewton::with_tolerance`)), if the length of a step relative to the
+/// length of the parameter vector falls below a tolerance (see
+/// [`with_step_tolerance`](`DampedNewton::with_step_tolerance`)), if the Jacobian is singular or
+/// if the line search fails.
+///
+/// If the Jacobian is not available, consider [`Broyden`](`crate::solver::nonlinearsystem::Broyden`)
+/// or [`NewtonKrylov`](`crate::solver::nonlinearsystem::NewtonKrylov`) instead.
+///
+/// Requires an initial parameter vector.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`Operator`] (returning the residuals) and
+/// [`Jacobian`].
+///
+/// ## Reference
+///
+/// J. E. Dennis Jr. and Robert B. Schnabel (1996). Numerical Methods for Unconstrained
+/// Optimization and Nonlinear Equations. SIAM. DOI: <https://doi.org/10.1137/1.9781611971200>
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct DampedNewton<F> {
+    /// Tolerance on the residual norm
+    tol_f: F,
+    /// Tolerance on the relative step length
+    tol_x: F,
+}
+
+impl<F: ArgminFloat> DampedNewton<F> {
+    /// Construct a new instance of [`DampedNewton`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nonlinearsystem::DampedNewton;
+    /// let newton: DampedNewton<f64> = DampedNewton::new();
+    /// ```
+    pub fn new() -> Self {
+        DampedNewton {
+            tol_f: F::epsilon().powf(float!(2.0 / 3.0)),
+            tol_x: F::epsilon(),
+        }
+    }
+
+    /// Set tolerance on the residual norm.
+    ///
+    /// Must be non-negative and defaults to `EPSILON^(2/3)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nonlinearsystem::DampedNewton;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let newton = DampedNewton::new().with_tolerance(1e-8f64)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`DampedNewton`: tolerance on the residual norm must be >= 0."
+            ));
+        }
+        self.tol_f = tol;
+        Ok(self)
+    }
+
+    /// Set tolerance on the length of a step relative to the length of the parameter vector.
+    ///
+    /// Must be non-negative and defaults to `EPSILON`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nonlinearsystem::DampedNewton;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let newton = DampedNewton::new().with_step_tolerance(1e-10f64)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_step_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`DampedNewton`: tolerance on the step length must be >= 0."
+            ));
+        }
+        self.tol_x = tol;
+        Ok(self)
+    }
+}
+
+impl<F: ArgminFloat> Default for DampedNewton<F> {
+    fn default() -> DampedNewton<F> {
+        DampedNewton::new()
+    }
+}
+
+impl<O, P, R, J, F> Solver<O, IterState<P, (), (), (), R, F>> for DampedNewton<F>
+where
+    O: Operator<Param = P, Output = R> + Jacobian<Param = P, Jacobian = J>,
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    R: Clone + ArgminGet<usize, F> + ArgminSize<usize>,
+    J: ArgminGet<(usize, usize), F> + ArgminSize<(usize, usize)>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Damped Newton method";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), R, F>,
+    ) -> Result<(IterState<P, (), (), (), R, F>, Option<KV>), Error> {
+        let iterate = Iterate::init("DampedNewton", problem, &mut state)?;
+        let converged = iterate.norm <= self.tol_f;
+        let state = iterate.into_state(state);
+        if converged {
+            Ok((
+                state.terminate_with(TerminationReason::SolverConverged),
+                None,
+            ))
+        } else {
+            Ok((state, None))
+        }
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, (), (), (), R, F>,
+    ) -> Result<(IterState<P, (), (), (), R, F>, Option<KV>), Error> {
+        let iterate = Iterate::from_state("DampedNewton", &state)?;
+        let jacobian = to_rows(&problem.jacobian(&iterate.param)?);
+        let neg_fx = iterate.fx.iter().map(|&fi| -fi).collect();
+        let d = match lu_solve(jacobian, neg_fx) {
+            Some(d) => d,
+            None => {
+                return Ok((
+                    state.terminate_with(TerminationReason::SolverExit(
+                        "Singular Jacobian".to_string(),
+                    )),
+                    None,
+                ))
+            }
+        };
+        let (alpha, new_iterate) = match iterate.line_search(problem, &d)? {
+            Some(res) => res,
+            None => {
+                return Ok((
+                    state.terminate_with(TerminationReason::SolverExit(
+                        "Line search failed".to_string(),
+                    )),
+                    None,
+                ))
+            }
+        };
+        let converged = new_iterate.converged(&iterate.x, self.tol_f, self.tol_x);
+        let state = new_iterate.into_state(state);
+        let kv = Some(kv!("step_length" => alpha;));
+        if converged {
+            Ok((state.terminate_with(TerminationReason::SolverConverged), kv))
+        } else {
+            Ok((state, kv))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::nonlinearsystem::tests::{Arctan, RosenbrockSystem, Singular};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(damped_newton, DampedNewton<f64>);
+
+    #[test]
+    fn test_new() {
+        let DampedNewton { tol_f, tol_x } = DampedNewton::<f64>::new();
+        assert_eq!(
+            tol_f.to_ne_bytes(),
+            f64::EPSILON.powf(2.0 / 3.0).to_ne_bytes()
+        );
+        assert_eq!(tol_x.to_ne_bytes(), f64::EPSILON.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_tolerances() {
+        let newton = DampedNewton::new()
+            .with_tolerance(1e-4f64)
+            .unwrap()
+            .with_step_tolerance(0.0)
+            .unwrap();
+        assert_eq!(newton.tol_f.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+        assert_eq!(newton.tol_x.to_ne_bytes(), 0.0f64.to_ne_bytes());
+
+        assert_error!(
+            DampedNewton::new().with_tolerance(-1.0f64),
+            ArgminError,
+            "Invalid parameter: \"`DampedNewton`: tolerance on the residual norm must be >= 0.\""
+        );
+        assert_error!(
+            DampedNewton::new().with_step_tolerance(-1.0f64),
+            ArgminError,
+            "Invalid parameter: \"`DampedNewton`: tolerance on the step length must be >= 0.\""
+        );
+    }
+
+    #[test]
+    fn test_rosenbrock() {
+        let res = Executor::new(RosenbrockSystem {}, DampedNewton::new())
+            .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(20))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state().get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        let param = res.state().get_best_param().unwrap();
+        assert_relative_eq!(param[0], 1.0, epsilon = 1e-12);
+        assert_relative_eq!(param[1], 1.0, epsilon = 1e-12);
+        let residuals = res.state().get_residuals().unwrap();
+        assert!(residuals.iter().all(|r| r.abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_line_search() {
+        // Undamped Newton diverges for arctan when started this far from the root
+        let res = Executor::new(Arctan {}, DampedNewton::new())
+            .configure(|state| state.param(vec![10.0]).max_iters(50))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state().get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        assert_relative_eq!(
+            res.state().get_best_param().unwrap()[0],
+            0.0,
+            epsilon = 1e-10
+        );
+    }
+
+    #[test]
+    fn test_singular_jacobian() {
+        let res = Executor::new(Singular {}, DampedNewton::new())
+            .configure(|state| state.param(vec![0.0]).max_iters(50))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state().get_termination_reason(),
+            Some(&TerminationReason::SolverExit(
+                "Singular Jacobian".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_init_errors() {
+        let res = Executor::new(RosenbrockSystem {}, DampedNewton::new()).run();
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`DampedNewton` requires an initial guess. Please provide an ",
+                "initial guess via `Executor`s `configure` method.\""
+            )
+        );
+
+        let res = Executor::new(RosenbrockSystem {}, DampedNewton::new())
+            .configure(|state| state.param(vec![1.0, 1.0, 1.0]))
+            .run();
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`DampedNewton`: number of residuals (2) must equal number ",
+                "of parameters (3).\""
+            )
+        );
+    }
+
+    #[cfg(feature = "_nalgebral")]
+    #[test]
+    fn test_rosenbrock_nalgebra() {
+        use nalgebra::{DMatrix, DVector};
+
+        struct Problem {}
+
+        impl Operator for Problem {
+            type Param = DVector<f64>;
+            type Output = DVector<f64>;
+
+            fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(DVector::from_vec(vec![
+                    10.0 * (p[1] - p[0].powi(2)),
+                    1.0 - p[0],
+                ]))
+            }
+        }
+
+        impl Jacobian for Problem {
+            type Param = DVector<f64>;
+            type Jacobian = DMatrix<f64>;
+
+            fn jacobian(&self, p: &Self::Param) -> Result<Self::Jacobian, Error> {
+                Ok(DMatrix::from_row_slice(
+                    2,
+                    2,
+                    &[-20.0 * p[0], 10.0, -1.0, 0.0],
+                ))
+            }
+        }
+
+        let res = Executor::new(Problem {}, DampedNewton::new())
+            .configure(|state| {
+                state
+                    .param(DVector::from_vec(vec![-1.2, 1.0]))
+                    .max_iters(20)
+            })
+            .run()
+            .unwrap();
+        let param = res.state().get_best_param().unwrap();
+        assert_relative_eq!(param[0], 1.0, epsilon = 1e-12);
+        assert_relative_eq!(param[1], 1.0, epsilon = 1e-12);
+    }
+}
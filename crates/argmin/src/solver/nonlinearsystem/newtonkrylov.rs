@@ -0,0 +1,494 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::Iterate;
+use crate::core::{
+    ArgminFloat, Error, IterState, Operator, Problem, Solver, State, TerminationReason, KV,
+};
+use crate::solver::dense::{dot, norm};
+use argmin_math::{ArgminGet, ArgminSet, ArgminSize};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Newton-Krylov method
+///
+/// Jacobian-free inexact Newton method for square systems of nonlinear equations `F(x) = 0`. In
+/// every iteration the Newton equations `J(x) d = -F(x)` are solved approximately with GMRES up
+/// to a relative residual `eta` (the forcing term), where the products of the Jacobian `J` with
+/// vectors are approximated by forward differences of `F`. Each GMRES iteration therefore costs
+/// one evaluation of the residuals and the Jacobian is never formed. The step length along `d` is
+/// chosen by a backtracking line search on `||F(x)||^2 / 2`.
+///
+/// By default, the forcing term is chosen adaptively following Eisenstat and Walker ("choice 2"
+/// with `gamma = 0.9` and `alpha = 2`, safeguarded and bounded by `0.9`), which avoids
+/// oversolving far from the root and results in superlinear convergence close to it. A constant
+/// forcing term can be set via [`with_forcing_term`](`NewtonKrylov::with_forcing_term`). The
+/// dimension of the Krylov subspace, i.e. the maximum number of GMRES iterations per Newton
+/// iteration, can be set via [`with_krylov_dim`](`NewtonKrylov::with_krylov_dim`).
+///
+/// The solver terminates if the residual norm falls below a tolerance (see
+/// [`with_tolerance`](`NewtonKrylov::with_tolerance`)), if the length of a step relative to the
+/// length of the parameter vector falls below a tolerance (see
+/// [`with_step_tolerance`](`NewtonKrylov::with_step_tolerance`)) or if the line search fails.
+///
+/// The forcing term and the number of GMRES iterations are reported as KV metrics
+/// `forcing_term` and `krylov_iters`.
+///
+/// Requires an initial parameter vector.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`Operator`] (returning the residuals).
+///
+/// ## References
+///
+/// Youcef Saad and Martin H. Schultz (1986). GMRES: A generalized minimal residual algorithm for
+/// solving nonsymmetric linear systems. SIAM Journal on Scientific and Statistical Computing 7 (3),
+/// 856–869. DOI: <https://doi.org/10.1137/0907058>
+///
+/// Stanley C. Eisenstat and Homer F. Walker (1996). Choosing the forcing terms in an inexact
+/// Newton method. SIAM Journal on Scientific Computing 17 (1), 16–32.
+/// DOI: <https://doi.org/10.1137/0917003>
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct NewtonKrylov<F> {
+    /// Constant forcing term (adaptive if `None`)
+    forcing: Option<F>,
+    /// Current forcing term
+    eta: F,
+    /// Residual norm of the previous iterate
+    prev_norm: F,
+    /// Maximum dimension of the Krylov subspace
+    krylov_dim: usize,
+    /// Tolerance on the residual norm
+    tol_f: F,
+    /// Tolerance on the relative step length
+    tol_x: F,
+}
+
+impl<F: ArgminFloat> NewtonKrylov<F> {
+    /// Construct a new instance of [`NewtonKrylov`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nonlinearsystem::NewtonKrylov;
+    /// let newton_krylov: NewtonKrylov<f64> = NewtonKrylov::new();
+    /// ```
+    pub fn new() -> Self {
+        NewtonKrylov {
+            forcing: None,
+            eta: float!(0.5),
+            prev_norm: F::nan(),
+            krylov_dim: 30,
+            tol_f: F::epsilon().powf(float!(2.0 / 3.0)),
+            tol_x: F::epsilon(),
+        }
+    }
+
+    /// Set constant forcing term.
+    ///
+    /// Must be in `(0, 1)`. By default, the forcing term is chosen adaptively.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nonlinearsystem::NewtonKrylov;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let newton_krylov = NewtonKrylov::new().with_forcing_term(1e-4f64)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_forcing_term(mut self, eta: F) -> Result<Self, Error> {
+        if eta <= float!(0.0) || eta >= float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`NewtonKrylov`: forcing term must be in (0, 1)."
+            ));
+        }
+        self.forcing = Some(eta);
+        Ok(self)
+    }
+
+    /// Set maximum dimension of the Krylov subspace.
+    ///
+    /// Must be at least `1` and defaults to `30`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nonlinearsystem::NewtonKrylov;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let newton_krylov: NewtonKrylov<f64> = NewtonKrylov::new().with_krylov_dim(10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_krylov_dim(mut self, krylov_dim: usize) -> Result<Self, Error> {
+        if krylov_dim < 1 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`NewtonKrylov`: dimension of the Krylov subspace must be >= 1."
+            ));
+        }
+        self.krylov_dim = krylov_dim;
+        Ok(self)
+    }
+
+    /// Set tolerance on the residual norm.
+    ///
+    /// Must be non-negative and defaults to `EPSILON^(2/3)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nonlinearsystem::NewtonKrylov;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let newton_krylov = NewtonKrylov::new().with_tolerance(1e-8f64)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`NewtonKrylov`: tolerance on the residual norm must be >= 0."
+            ));
+        }
+        self.tol_f = tol;
+        Ok(self)
+    }
+
+    /// Set tolerance on the length of a step relative to the length of the parameter vector.
+    ///
+    /// Must be non-negative and defaults to `EPSILON`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nonlinearsystem::NewtonKrylov;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let newton_krylov = NewtonKrylov::new().with_step_tolerance(1e-10f64)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_step_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`NewtonKrylov`: tolerance on the step length must be >= 0."
+            ));
+        }
+        self.tol_x = tol;
+        Ok(self)
+    }
+
+    /// Forcing term for the current iterate with residual norm `norm`
+    fn forcing_term(&self, norm: F) -> F {
+        if let Some(eta) = self.forcing {
+            return eta;
+        }
+        if !self.prev_norm.is_finite() {
+            return self.eta;
+        }
+        let gamma: F = float!(0.9);
+        let ratio = norm / self.prev_norm;
+        let mut eta = gamma * ratio * ratio;
+        let safeguard = gamma * self.eta * self.eta;
+        if safeguard > float!(0.1) {
+            eta = eta.max(safeguard);
+        }
+        eta.min(float!(0.9))
+    }
+}
+
+impl<F: ArgminFloat> Default for NewtonKrylov<F> {
+    fn default() -> NewtonKrylov<F> {
+        NewtonKrylov::new()
+    }
+}
+
+impl<O, P, R, F> Solver<O, IterState<P, (), (), (), R, F>> for NewtonKrylov<F>
+where
+    O: Operator<Param = P, Output = R>,
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    R: Clone + ArgminGet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Newton-Krylov";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), R, F>,
+    ) -> Result<(IterState<P, (), (), (), R, F>, Option<KV>), Error> {
+        let iterate = Iterate::init("NewtonKrylov", problem, &mut state)?;
+        self.eta = self.forcing.unwrap_or(float!(0.5));
+        self.prev_norm = F::nan();
+        let converged = iterate.norm <= self.tol_f;
+        let state = iterate.into_state(state);
+        if converged {
+            Ok((
+                state.terminate_with(TerminationReason::SolverConverged),
+                None,
+            ))
+        } else {
+            Ok((state, None))
+        }
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, (), (), (), R, F>,
+    ) -> Result<(IterState<P, (), (), (), R, F>, Option<KV>), Error> {
+        let iterate = Iterate::from_state("NewtonKrylov", &state)?;
+        self.eta = self.forcing_term(iterate.norm);
+        self.prev_norm = iterate.norm;
+
+        let (d, krylov_iters) = gmres(problem, &iterate, self.eta, self.krylov_dim)?;
+        let (alpha, new_iterate) = match iterate.line_search(problem, &d)? {
+            Some(res) => res,
+            None => {
+                return Ok((
+                    state.terminate_with(TerminationReason::SolverExit(
+                        "Line search failed".to_string(),
+                    )),
+                    None,
+                ))
+            }
+        };
+        let converged = new_iterate.converged(&iterate.x, self.tol_f, self.tol_x);
+        let state = new_iterate.into_state(state);
+        let kv = Some(kv!(
+            "step_length" => alpha;
+            "forcing_term" => self.eta;
+            "krylov_iters" => krylov_iters as u64;
+        ));
+        if converged {
+            Ok((state.terminate_with(TerminationReason::SolverConverged), kv))
+        } else {
+            Ok((state, kv))
+        }
+    }
+}
+
+/// Solves `J d = -F(x)` with GMRES (without restarts) up to a relative residual `eta` using at
+/// most `krylov_dim` iterations. Returns the solution and the number of iterations.
+fn gmres<O, P, R, F>(
+    problem: &mut Problem<O>,
+    iterate: &Iterate<P, R, F>,
+    eta: F,
+    krylov_dim: usize,
+) -> Result<(Vec<F>, usize), Error>
+where
+    O: Operator<Param = P, Output = R>,
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    R: Clone + ArgminGet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+{
+    let n = iterate.x.len();
+    let m = krylov_dim.min(n);
+    let beta = iterate.norm;
+    // Orthonormal basis of the Krylov subspace
+    let mut basis: Vec<Vec<F>> = vec![iterate.fx.iter().map(|&fi| -fi / beta).collect()];
+    // Columns of the upper Hessenberg matrix, reduced to upper triangular form by Givens rotations
+    let mut h: Vec<Vec<F>> = Vec::with_capacity(m);
+    let mut rotations: Vec<(F, F)> = Vec::with_capacity(m);
+    let mut g: Vec<F> = vec![beta];
+
+    for k in 0..m {
+        let mut w = iterate.jacobian_vec_fd(problem, &basis[k])?;
+        // Modified Gram-Schmidt
+        let mut col: Vec<F> = Vec::with_capacity(k + 2);
+        for v in basis.iter() {
+            let hik = dot(&w, v);
+            for (wj, &vj) in w.iter_mut().zip(v.iter()) {
+                *wj = *wj - hik * vj;
+            }
+            col.push(hik);
+        }
+        let w_norm = norm(&w);
+        col.push(w_norm);
+
+        // Apply previous rotations and compute the new one
+        for (i, &(c, s)) in rotations.iter().enumerate() {
+            let (a, b) = (col[i], col[i + 1]);
+            col[i] = c * a + s * b;
+            col[i + 1] = -s * a + c * b;
+        }
+        let r = col[k].hypot(col[k + 1]);
+        let (c, s) = if r == float!(0.0) {
+            (float!(1.0), float!(0.0))
+        } else {
+            (col[k] / r, col[k + 1] / r)
+        };
+        col[k] = r;
+        col[k + 1] = float!(0.0);
+        rotations.push((c, s));
+        g.push(-s * g[k]);
+        g[k] = c * g[k];
+        h.push(col);
+
+        if g[k + 1].abs() <= eta * beta || w_norm == float!(0.0) {
+            break;
+        }
+        basis.push(w.iter().map(|&wi| wi / w_norm).collect());
+    }
+
+    // Back substitution
+    let iters = h.len();
+    let mut y = vec![float!(0.0); iters];
+    for k in (0..iters).rev() {
+        let s = ((k + 1)..iters).fold(g[k], |s, j| s - h[j][k] * y[j]);
+        y[k] = if h[k][k] == float!(0.0) {
+            float!(0.0)
+        } else {
+            s / h[k][k]
+        };
+    }
+    let mut d = vec![float!(0.0); n];
+    for (v, &yk) in basis.iter().zip(y.iter()) {
+        for (di, &vi) in d.iter_mut().zip(v.iter()) {
+            *di = *di + yk * vi;
+        }
+    }
+    Ok((d, iters))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::nonlinearsystem::tests::{BroydenTridiagonal, LinearSystem};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(newton_krylov, NewtonKrylov<f64>);
+
+    #[test]
+    fn test_new() {
+        let NewtonKrylov {
+            forcing,
+            eta,
+            prev_norm,
+            krylov_dim,
+            tol_f,
+            tol_x,
+        } = NewtonKrylov::<f64>::new();
+        assert!(forcing.is_none());
+        assert_eq!(eta.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert!(prev_norm.is_nan());
+        assert_eq!(krylov_dim, 30);
+        assert_eq!(
+            tol_f.to_ne_bytes(),
+            f64::EPSILON.powf(2.0 / 3.0).to_ne_bytes()
+        );
+        assert_eq!(tol_x.to_ne_bytes(), f64::EPSILON.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_builders() {
+        let nk = NewtonKrylov::new()
+            .with_forcing_term(0.1f64)
+            .unwrap()
+            .with_krylov_dim(5)
+            .unwrap();
+        assert_eq!(nk.forcing.unwrap().to_ne_bytes(), 0.1f64.to_ne_bytes());
+        assert_eq!(nk.krylov_dim, 5);
+
+        for eta in [0.0, 1.0, -0.5] {
+            assert_error!(
+                NewtonKrylov::new().with_forcing_term(eta),
+                ArgminError,
+                "Invalid parameter: \"`NewtonKrylov`: forcing term must be in (0, 1).\""
+            );
+        }
+        assert_error!(
+            NewtonKrylov::<f64>::new().with_krylov_dim(0),
+            ArgminError,
+            "Invalid parameter: \"`NewtonKrylov`: dimension of the Krylov subspace must be >= 1.\""
+        );
+        assert_error!(
+            NewtonKrylov::new().with_tolerance(-1.0f64),
+            ArgminError,
+            "Invalid parameter: \"`NewtonKrylov`: tolerance on the residual norm must be >= 0.\""
+        );
+        assert_error!(
+            NewtonKrylov::new().with_step_tolerance(-1.0f64),
+            ArgminError,
+            "Invalid parameter: \"`NewtonKrylov`: tolerance on the step length must be >= 0.\""
+        );
+    }
+
+    #[test]
+    fn test_forcing_term() {
+        let mut nk: NewtonKrylov<f64> = NewtonKrylov::new();
+        // First iteration
+        assert_eq!(nk.forcing_term(1.0).to_ne_bytes(), 0.5f64.to_ne_bytes());
+        // Safeguard kicks in since 0.9 * 0.5^2 > 0.1
+        nk.prev_norm = 1.0;
+        assert_relative_eq!(nk.forcing_term(0.01), 0.225);
+        // Bounded by 0.9
+        assert_relative_eq!(nk.forcing_term(2.0), 0.9);
+        nk.eta = 0.01;
+        assert_relative_eq!(nk.forcing_term(0.1), 0.009);
+
+        let nk = NewtonKrylov::new().with_forcing_term(0.3f64).unwrap();
+        assert_eq!(nk.forcing_term(0.1).to_ne_bytes(), 0.3f64.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_gmres() {
+        // For a linear system GMRES solves the Newton equations up to finite difference errors
+        let mut problem = Problem::new(LinearSystem {});
+        let iterate = Iterate::evaluate(&mut problem, vec![0.0, 0.0, 0.0]).unwrap();
+        let (d, iters) = gmres(&mut problem, &iterate, 1e-12, 30).unwrap();
+        assert_eq!(iters, 3);
+        let expected = LinearSystem::solution();
+        for (di, ei) in d.iter().zip(expected.iter()) {
+            assert_relative_eq!(*di, *ei, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_tridiagonal() {
+        for forcing in [None, Some(1e-3)] {
+            let mut solver = NewtonKrylov::new();
+            if let Some(eta) = forcing {
+                solver = solver.with_forcing_term(eta).unwrap();
+            }
+            let res = Executor::new(BroydenTridiagonal {}, solver)
+                .configure(|state| state.param(vec![-1.0; 10]).max_iters(100))
+                .run()
+                .unwrap();
+            assert_eq!(
+                res.state().get_termination_reason(),
+                Some(&TerminationReason::SolverConverged)
+            );
+            assert!(res.state().get_cost() < 1e-9);
+            let residuals = res.state().get_residuals().unwrap();
+            assert!(residuals.iter().all(|r| r.abs() < 1e-9));
+        }
+    }
+
+    #[test]
+    fn test_init_errors() {
+        let res = Executor::new(BroydenTridiagonal {}, NewtonKrylov::new()).run();
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`NewtonKrylov` requires an initial guess. Please provide an ",
+                "initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+}
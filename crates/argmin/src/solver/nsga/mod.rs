@@ -0,0 +1,573 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Non-dominated sorting genetic algorithms (NSGA-II and NSGA-III)
+//!
+//! Multi-objective evolutionary algorithms which approximate the Pareto front of a problem
+//! implementing [`MultiObjective`](`crate::core::MultiObjective`) within box constraints.
+//!
+//! * [`NSGAII`] ranks the population by non-dominated sorting and keeps the members of the last
+//!   admitted front which have the largest crowding distance. It works best for two or three
+//!   objectives.
+//! * [`NSGAIII`] replaces the crowding distance by a set of reference points on the unit simplex,
+//!   which keeps the population well spread for many objectives.
+//!
+//! Both solvers create offspring via simulated binary crossover and polynomial mutation and
+//! report the population and its Pareto front in a [`ParetoState`](`crate::core::ParetoState`).
+//!
+//! ## References
+//!
+//! \[0\] Kalyanmoy Deb, Amrit Pratap, Sameer Agarwal and T. Meyarivan (2002). A fast and elitist
+//! multiobjective genetic algorithm: NSGA-II. IEEE Transactions on Evolutionary Computation 6 (2),
+//! 182–197. DOI: <https://doi.org/10.1109/4235.996017>
+//!
+//! \[1\] Kalyanmoy Deb and Himanshu Jain (2014). An Evolutionary Many-Objective Optimization
+//! Algorithm Using Reference-Point-Based Nondominated Sorting Approach, Part I: Solving Problems
+//! With Box Constraints. IEEE Transactions on Evolutionary Computation 18 (4), 577–601.
+//! DOI: <https://doi.org/10.1109/TEVC.2013.2281535>
+//!
+//! \[2\] Kalyanmoy Deb and Ram Bhushan Agrawal (1995). Simulated Binary Crossover for Continuous
+//! Search Space. Complex Systems 9 (2), 115–148.
+
+mod nsga2;
+mod nsga3;
+mod sorting;
+
+pub use self::nsga2::NSGAII;
+pub use self::nsga3::NSGAIII;
+
+use crate::core::{ArgminFloat, Error, MultiObjective, ParetoState, Problem, SyncAlias};
+use argmin_math::{ArgminGet, ArgminSet, ArgminSize};
+use rand::Rng;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Settings and variation operators shared by [`NSGAII`] and [`NSGAIII`]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+struct Evolution<P, F, R> {
+    /// Name of the solver used in error messages
+    name: String,
+    /// Bounds on parameter space
+    bounds: (P, P),
+    /// Number of individuals
+    population_size: usize,
+    /// Probability of crossover of a pair of parents
+    crossover_prob: F,
+    /// Distribution index of simulated binary crossover
+    crossover_eta: F,
+    /// Probability of mutation of each coordinate; defaults to `1/n`
+    mutation_prob: Option<F>,
+    /// Distribution index of polynomial mutation
+    mutation_eta: F,
+    /// Random number generator
+    rng_generator: R,
+    /// Lower bounds
+    lower: Vec<F>,
+    /// Upper bounds
+    upper: Vec<F>,
+}
+
+impl<P, F, R> Evolution<P, F, R>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `Evolution`
+    fn new(name: &str, bounds: (P, P), population_size: usize, rng_generator: R) -> Self {
+        Evolution {
+            name: name.to_string(),
+            bounds,
+            population_size,
+            crossover_prob: float!(0.9),
+            crossover_eta: float!(15.0),
+            mutation_prob: None,
+            mutation_eta: float!(20.0),
+            rng_generator,
+            lower: vec![],
+            upper: vec![],
+        }
+    }
+
+    /// Replaces the random number generator
+    fn with_rng_generator<R1>(self, generator: R1) -> Evolution<P, F, R1> {
+        Evolution {
+            name: self.name,
+            bounds: self.bounds,
+            population_size: self.population_size,
+            crossover_prob: self.crossover_prob,
+            crossover_eta: self.crossover_eta,
+            mutation_prob: self.mutation_prob,
+            mutation_eta: self.mutation_eta,
+            rng_generator: generator,
+            lower: self.lower,
+            upper: self.upper,
+        }
+    }
+
+    /// Sets probability and distribution index of simulated binary crossover
+    fn set_crossover(&mut self, prob: F, eta: F) -> Result<(), Error> {
+        if !(float!(0.0)..=float!(1.0)).contains(&prob) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!("`{}`: crossover probability must be in [0, 1].", self.name)
+            ));
+        }
+        if eta < float!(0.0) || !eta.is_finite() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!(
+                    "`{}`: distribution index of crossover must be >= 0 and finite.",
+                    self.name
+                )
+            ));
+        }
+        self.crossover_prob = prob;
+        self.crossover_eta = eta;
+        Ok(())
+    }
+
+    /// Sets probability and distribution index of polynomial mutation
+    fn set_mutation(&mut self, prob: F, eta: F) -> Result<(), Error> {
+        if !(float!(0.0)..=float!(1.0)).contains(&prob) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!("`{}`: mutation probability must be in [0, 1].", self.name)
+            ));
+        }
+        if eta < float!(0.0) || !eta.is_finite() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!(
+                    "`{}`: distribution index of mutation must be >= 0 and finite.",
+                    self.name
+                )
+            ));
+        }
+        self.mutation_prob = Some(prob);
+        self.mutation_eta = eta;
+        Ok(())
+    }
+}
+
+impl<P, F, R> Evolution<P, F, R>
+where
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+    R: Rng,
+{
+    /// Checks the settings and creates the initial population
+    ///
+    /// The members of `population` (if any) are complemented by individuals which are uniformly
+    /// distributed within the bounds.
+    fn init_population(&mut self, population: Option<Vec<P>>) -> Result<Vec<P>, Error> {
+        if self.population_size < 2 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!("`{}`: population size must be >= 2.", self.name)
+            ));
+        }
+        let n = self.bounds.0.shape();
+        if n < 1 || self.bounds.1.shape() != n {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!(
+                    "`{}`: lower and upper bound must have the same, non-zero length.",
+                    self.name
+                )
+            ));
+        }
+        self.lower = (0..n).map(|i| self.bounds.0.get(i)).collect();
+        self.upper = (0..n).map(|i| self.bounds.1.get(i)).collect();
+        if self
+            .lower
+            .iter()
+            .zip(self.upper.iter())
+            .any(|(&l, &u)| l > u || !l.is_finite() || !u.is_finite())
+        {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!(
+                    "`{}`: lower bound must be finite and not larger than the finite upper bound.",
+                    self.name
+                )
+            ));
+        }
+
+        let mut population = population.unwrap_or_default();
+        if population.len() > self.population_size {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!(
+                    "`{}`: initial population must not be larger than the population size.",
+                    self.name
+                )
+            ));
+        }
+        if population.iter().any(|p| p.shape() != n) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!(
+                    "`{}`: members of the initial population must have the same length as the bounds.",
+                    self.name
+                )
+            ));
+        }
+        while population.len() < self.population_size {
+            let x: Vec<F> = self
+                .lower
+                .iter()
+                .zip(self.upper.iter())
+                .map(|(&l, &u)| {
+                    let r: f64 = self.rng_generator.gen();
+                    l + float!(r) * (u - l)
+                })
+                .collect();
+            population.push(self.to_param(&x));
+        }
+        Ok(population)
+    }
+
+    /// Evaluates the objectives of all `params`
+    fn evaluate<O>(&self, problem: &mut Problem<O>, params: &[P]) -> Result<Vec<Vec<F>>, Error>
+    where
+        O: MultiObjective<Param = P, Float = F> + SyncAlias,
+        P: SyncAlias,
+    {
+        let objectives = problem.bulk_objectives(params)?;
+        let m = objectives.first().map(|o| o.len()).unwrap_or(0);
+        if m < 1 || objectives.iter().any(|o| o.len() != m) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!(
+                    "`{}`: all objective vectors must have the same, non-zero length.",
+                    self.name
+                )
+            ));
+        }
+        Ok(objectives)
+    }
+
+    /// Creates two children from the parents `p1` and `p2` via simulated binary crossover and
+    /// polynomial mutation
+    fn offspring(&mut self, p1: &P, p2: &P) -> (P, P) {
+        let n = self.lower.len();
+        let mut c1: Vec<F> = (0..n).map(|i| p1.get(i)).collect();
+        let mut c2: Vec<F> = (0..n).map(|i| p2.get(i)).collect();
+        if self.rng_generator.gen::<f64>() < self.crossover_prob.to_f64().unwrap() {
+            for i in 0..n {
+                if self.rng_generator.gen::<f64>() < 0.5 {
+                    let (y1, y2) = self.crossover(i, c1[i], c2[i]);
+                    c1[i] = y1;
+                    c2[i] = y2;
+                }
+            }
+        }
+        let mutation_prob = self
+            .mutation_prob
+            .unwrap_or_else(|| float!(1.0 / n as f64))
+            .to_f64()
+            .unwrap();
+        for c in [&mut c1, &mut c2] {
+            for (i, ci) in c.iter_mut().enumerate() {
+                if self.rng_generator.gen::<f64>() < mutation_prob {
+                    *ci = self.mutate(i, *ci);
+                }
+            }
+        }
+        (self.to_param(&c1), self.to_param(&c2))
+    }
+
+    /// Simulated binary crossover of coordinate `i` with values `x1` and `x2` of the parents
+    fn crossover(&mut self, i: usize, x1: F, x2: F) -> (F, F) {
+        let (l, u) = (self.lower[i], self.upper[i]);
+        if (x1 - x2).abs() <= F::epsilon() || u <= l {
+            return (x1, x2);
+        }
+        let (y1, y2) = if x1 < x2 { (x1, x2) } else { (x2, x1) };
+        let exponent = float!(1.0) / (self.crossover_eta + float!(1.0));
+        let r: F = float!(self.rng_generator.gen::<f64>());
+        // Spread factor respecting the distance `dist` of the parents to the bound
+        let betaq = |dist: F| {
+            let beta = float!(1.0) + float!(2.0) * dist / (y2 - y1);
+            let alpha = float!(2.0) - beta.powf(-(self.crossover_eta + float!(1.0)));
+            if r <= float!(1.0) / alpha {
+                (r * alpha).powf(exponent)
+            } else {
+                (float!(1.0) / (float!(2.0) - r * alpha)).powf(exponent)
+            }
+        };
+        let half = float!(0.5);
+        let c1 = (half * ((y1 + y2) - betaq(y1 - l) * (y2 - y1)))
+            .max(l)
+            .min(u);
+        let c2 = (half * ((y1 + y2) + betaq(u - y2) * (y2 - y1)))
+            .max(l)
+            .min(u);
+        if self.rng_generator.gen::<f64>() < 0.5 {
+            (c2, c1)
+        } else {
+            (c1, c2)
+        }
+    }
+
+    /// Polynomial mutation of coordinate `i` with value `x`
+    fn mutate(&mut self, i: usize, x: F) -> F {
+        let (l, u) = (self.lower[i], self.upper[i]);
+        if u <= l {
+            return x;
+        }
+        let delta1 = (x - l) / (u - l);
+        let delta2 = (u - x) / (u - l);
+        let exponent = float!(1.0) / (self.mutation_eta + float!(1.0));
+        let r: F = float!(self.rng_generator.gen::<f64>());
+        let one = float!(1.0);
+        let two = float!(2.0);
+        let deltaq = if r < float!(0.5) {
+            let val = two * r + (one - two * r) * (one - delta1).powf(self.mutation_eta + one);
+            val.powf(exponent) - one
+        } else {
+            let val = two * (one - r)
+                + two * (r - float!(0.5)) * (one - delta2).powf(self.mutation_eta + one);
+            one - val.powf(exponent)
+        };
+        (x + deltaq * (u - l)).max(l).min(u)
+    }
+
+    /// Converts coordinates into a parameter vector
+    fn to_param(&self, x: &[F]) -> P {
+        let mut param = self.bounds.0.clone();
+        for (i, &xi) in x.iter().enumerate() {
+            param.set(i, xi);
+        }
+        param
+    }
+}
+
+/// Takes population and objective values out of `state`
+fn take_population<P, F>(
+    name: &str,
+    state: &mut ParetoState<P, F>,
+) -> Result<(Vec<P>, Vec<Vec<F>>), Error> {
+    let population = state.take_population().ok_or_else(argmin_error_closure!(
+        PotentialBug,
+        format!("`{name}`: No population in state.")
+    ))?;
+    let objectives = state.take_objectives().ok_or_else(argmin_error_closure!(
+        PotentialBug,
+        format!("`{name}`: No objective values in state.")
+    ))?;
+    Ok((population, objectives))
+}
+
+/// Stores population, objective values and the Pareto front of the population in `state`
+///
+/// Returns the updated state and the size of the Pareto front.
+fn store_population<P: Clone, F: ArgminFloat>(
+    state: ParetoState<P, F>,
+    population: Vec<P>,
+    objectives: Vec<Vec<F>>,
+) -> (ParetoState<P, F>, usize) {
+    let fronts = sorting::non_dominated_sort(&objectives);
+    let (front, front_objectives) = select(&population, &objectives, &fronts[0]);
+    let front_size = front.len();
+    let state = state
+        .population(population)
+        .objectives(objectives)
+        .pareto_front(front, front_objectives);
+    (state, front_size)
+}
+
+/// Selects the members `indices` of `population` and `objectives`
+fn select<P: Clone, F: Clone>(
+    population: &[P],
+    objectives: &[Vec<F>],
+    indices: &[usize],
+) -> (Vec<P>, Vec<Vec<F>>) {
+    indices
+        .iter()
+        .map(|&i| (population[i].clone(), objectives[i].clone()))
+        .unzip()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use rand::SeedableRng;
+
+    /// ZDT1 test problem with Pareto front `f2 = 1 - sqrt(f1)` for `x_i = 0, i > 0`
+    pub(super) struct Zdt1 {}
+
+    impl MultiObjective for Zdt1 {
+        type Param = Vec<f64>;
+        type Float = f64;
+
+        fn objectives(&self, p: &Self::Param) -> Result<Vec<Self::Float>, Error> {
+            let g = 1.0 + 9.0 * p[1..].iter().sum::<f64>() / (p.len() - 1) as f64;
+            Ok(vec![p[0], g * (1.0 - (p[0] / g).sqrt())])
+        }
+    }
+
+    fn evolution() -> Evolution<Vec<f64>, f64, rand_xoshiro::Xoroshiro128Plus> {
+        Evolution::new(
+            "NSGAII",
+            (vec![0.0, -1.0, 2.0], vec![1.0, 1.0, 2.0]),
+            10,
+            rand_xoshiro::Xoroshiro128Plus::seed_from_u64(42),
+        )
+    }
+
+    #[test]
+    fn test_settings() {
+        let mut ev = evolution();
+        ev.set_crossover(0.5, 10.0).unwrap();
+        ev.set_mutation(0.2, 5.0).unwrap();
+        assert_eq!(ev.crossover_prob.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert_eq!(ev.crossover_eta.to_ne_bytes(), 10.0f64.to_ne_bytes());
+        assert_eq!(ev.mutation_prob, Some(0.2));
+        assert_eq!(ev.mutation_eta.to_ne_bytes(), 5.0f64.to_ne_bytes());
+
+        assert_error!(
+            ev.set_crossover(1.5, 10.0),
+            ArgminError,
+            "Invalid parameter: \"`NSGAII`: crossover probability must be in [0, 1].\""
+        );
+        assert_error!(
+            ev.set_crossover(0.5, f64::NAN),
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`NSGAII`: distribution index of crossover must be >= 0 ",
+                "and finite.\""
+            )
+        );
+        assert_error!(
+            ev.set_mutation(-0.1, 10.0),
+            ArgminError,
+            "Invalid parameter: \"`NSGAII`: mutation probability must be in [0, 1].\""
+        );
+        assert_error!(
+            ev.set_mutation(0.5, -1.0),
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`NSGAII`: distribution index of mutation must be >= 0 ",
+                "and finite.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_init_population() {
+        let mut ev = evolution();
+        let population = ev.init_population(Some(vec![vec![0.5, 0.0, 2.0]])).unwrap();
+        assert_eq!(population.len(), 10);
+        assert_eq!(population[0], vec![0.5, 0.0, 2.0]);
+        for p in population.iter() {
+            assert!((0.0..=1.0).contains(&p[0]));
+            assert!((-1.0..=1.0).contains(&p[1]));
+            assert_eq!(p[2].to_ne_bytes(), 2.0f64.to_ne_bytes());
+        }
+
+        let mut ev = evolution();
+        assert_error!(
+            ev.init_population(Some(vec![vec![0.5, 0.0]])),
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`NSGAII`: members of the initial population must have the ",
+                "same length as the bounds.\""
+            )
+        );
+        assert_error!(
+            ev.init_population(Some(vec![vec![0.5, 0.0, 2.0]; 11])),
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`NSGAII`: initial population must not be larger than the ",
+                "population size.\""
+            )
+        );
+        ev.population_size = 1;
+        assert_error!(
+            ev.init_population(None),
+            ArgminError,
+            "Invalid parameter: \"`NSGAII`: population size must be >= 2.\""
+        );
+        for bounds in [
+            (vec![0.0, 1.0], vec![1.0, 0.0]),
+            (vec![0.0, f64::NEG_INFINITY], vec![1.0, 0.0]),
+        ] {
+            let mut ev = Evolution::new("NSGAII", bounds, 10, rand::rngs::StdRng::seed_from_u64(1));
+            assert_error!(
+                ev.init_population(None),
+                ArgminError,
+                concat!(
+                    "Invalid parameter: \"`NSGAII`: lower bound must be finite and not larger ",
+                    "than the finite upper bound.\""
+                )
+            );
+        }
+        let mut ev = Evolution::new(
+            "NSGAII",
+            (vec![0.0, 1.0], vec![1.0]),
+            10,
+            rand::rngs::StdRng::seed_from_u64(1),
+        );
+        assert_error!(
+            ev.init_population(None),
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`NSGAII`: lower and upper bound must have the same, ",
+                "non-zero length.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_offspring_within_bounds() {
+        let mut ev = evolution();
+        ev.set_mutation(1.0, 20.0).unwrap();
+        let population = ev.init_population(None).unwrap();
+        for k in 0..200 {
+            let (c1, c2) = ev.offspring(&population[k % 10], &population[(k + 3) % 10]);
+            for c in [c1, c2] {
+                assert!((0.0..=1.0).contains(&c[0]));
+                assert!((-1.0..=1.0).contains(&c[1]));
+                assert_eq!(c[2].to_ne_bytes(), 2.0f64.to_ne_bytes());
+            }
+        }
+    }
+
+    #[test]
+    fn test_evaluate() {
+        struct Ragged {}
+
+        impl MultiObjective for Ragged {
+            type Param = Vec<f64>;
+            type Float = f64;
+
+            fn objectives(&self, p: &Self::Param) -> Result<Vec<Self::Float>, Error> {
+                Ok(vec![0.0; p.len()])
+            }
+        }
+
+        let ev = evolution();
+        let mut problem = Problem::new(Zdt1 {});
+        let objectives = ev
+            .evaluate(&mut problem, &[vec![0.25, 0.0, 0.0], vec![1.0, 0.0, 0.0]])
+            .unwrap();
+        assert_eq!(objectives, vec![vec![0.25, 0.5], vec![1.0, 0.0]]);
+        assert_eq!(problem.counts["objectives_count"], 2);
+
+        let mut problem = Problem::new(Ragged {});
+        assert_error!(
+            ev.evaluate(&mut problem, &[vec![0.0], vec![0.0, 1.0]]),
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`NSGAII`: all objective vectors must have the same, ",
+                "non-zero length.\""
+            )
+        );
+    }
+}
@@ -0,0 +1,540 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::sorting::{crowding_distance, non_dominated_sort};
+use super::{select, store_population, take_population, Evolution};
+use crate::core::{
+    ArgminFloat, Error, MultiObjective, ParetoState, Problem, Solver, SyncAlias, KV,
+};
+use argmin_math::{ArgminGet, ArgminSet, ArgminSize};
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// # NSGA-II
+///
+/// Elitist multi-objective genetic algorithm for problems implementing
+/// [`MultiObjective`](`crate::core::MultiObjective`) within the box `lower <= x <= upper`.
+///
+/// Every iteration creates `population_size` offspring from parents chosen by binary tournaments,
+/// where the parent with the lower non-domination rank wins and ties are broken in favor of the
+/// larger crowding distance. The offspring are created via simulated binary crossover (probability
+/// `0.9`, distribution index `15`) and polynomial mutation (probability `1/n` per coordinate,
+/// distribution index `20`). Parents and offspring are then sorted into non-dominated fronts and
+/// the next population is filled front by front. Members of the last front which only partially
+/// fits are chosen by descending crowding distance.
+///
+/// The population, its objective values and its Pareto front (the non-dominated members) are
+/// available in the [`ParetoState`]. Members of an initial population provided via
+/// [`ParetoState::population`] are complemented by individuals which are uniformly distributed
+/// within the bounds. The size of the Pareto front is reported as KV metric `front_size`.
+///
+/// Since Pareto fronts cannot be ranked by a cost function value, the solver only terminates once
+/// the maximum number of iterations is reached.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`MultiObjective`]. All objectives are
+/// minimized.
+///
+/// ## Reference
+///
+/// Kalyanmoy Deb, Amrit Pratap, Sameer Agarwal and T. Meyarivan (2002). A fast and elitist
+/// multiobjective genetic algorithm: NSGA-II. IEEE Transactions on Evolutionary Computation 6 (2),
+/// 182–197. DOI: <https://doi.org/10.1109/4235.996017>
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct NSGAII<P, F, R> {
+    /// Settings and variation operators
+    evolution: Evolution<P, F, R>,
+}
+
+impl<P, F> NSGAII<P, F, rand::rngs::StdRng>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `NSGAII`
+    ///
+    /// Takes the bounds of the search space and the size of the population.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nsga::NSGAII;
+    /// let nsga2: NSGAII<Vec<f64>, f64, _> = NSGAII::new((vec![0.0, 0.0], vec![1.0, 1.0]), 100);
+    /// ```
+    pub fn new(bounds: (P, P), population_size: usize) -> Self {
+        NSGAII {
+            evolution: Evolution::new(
+                "NSGAII",
+                bounds,
+                population_size,
+                rand::rngs::StdRng::from_entropy(),
+            ),
+        }
+    }
+}
+
+impl<P, F, R0> NSGAII<P, F, R0>
+where
+    F: ArgminFloat,
+{
+    /// Set random number generator
+    ///
+    /// Defaults to `rand::rngs::StdRng::from_entropy()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nsga::NSGAII;
+    /// # use rand::SeedableRng;
+    /// let nsga2: NSGAII<Vec<f64>, f64, _> = NSGAII::new((vec![0.0, 0.0], vec![1.0, 1.0]), 100)
+    ///     .with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(1729));
+    /// ```
+    pub fn with_rng_generator<R1: Rng>(self, generator: R1) -> NSGAII<P, F, R1> {
+        NSGAII {
+            evolution: self.evolution.with_rng_generator(generator),
+        }
+    }
+
+    /// Set probability and distribution index of simulated binary crossover
+    ///
+    /// The probability of crossover of a pair of parents must be in `[0, 1]` and defaults to
+    /// `0.9`. The distribution index must be non-negative and finite and defaults to `15`; larger
+    /// values create children closer to their parents.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nsga::NSGAII;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let nsga2: NSGAII<Vec<f64>, f64, _> = NSGAII::new((vec![0.0, 0.0], vec![1.0, 1.0]), 100)
+    ///     .with_crossover(0.8, 20.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_crossover(mut self, prob: F, eta: F) -> Result<Self, Error> {
+        self.evolution.set_crossover(prob, eta)?;
+        Ok(self)
+    }
+
+    /// Set probability and distribution index of polynomial mutation
+    ///
+    /// The probability of mutation of each coordinate must be in `[0, 1]` and defaults to `1/n`,
+    /// where `n` is the number of parameters. The distribution index must be non-negative and
+    /// finite and defaults to `20`; larger values create smaller mutations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nsga::NSGAII;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let nsga2: NSGAII<Vec<f64>, f64, _> = NSGAII::new((vec![0.0, 0.0], vec![1.0, 1.0]), 100)
+    ///     .with_mutation(0.1, 10.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_mutation(mut self, prob: F, eta: F) -> Result<Self, Error> {
+        self.evolution.set_mutation(prob, eta)?;
+        Ok(self)
+    }
+}
+
+impl<P, F, R> NSGAII<P, F, R>
+where
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+    R: Rng,
+{
+    /// Binary tournament based on non-domination rank and crowding distance
+    fn tournament(&mut self, rank: &[usize], distance: &[F]) -> usize {
+        let rng = &mut self.evolution.rng_generator;
+        let a = rng.gen_range(0..rank.len());
+        let b = rng.gen_range(0..rank.len());
+        match rank[a].cmp(&rank[b]) {
+            Ordering::Less => a,
+            Ordering::Greater => b,
+            Ordering::Equal => {
+                if distance[b] > distance[a] {
+                    b
+                } else {
+                    a
+                }
+            }
+        }
+    }
+}
+
+/// Selects `n` members of the population with objective values `objectives` by non-dominated
+/// sorting and crowding distance
+fn environmental_selection<F: ArgminFloat>(objectives: &[Vec<F>], n: usize) -> Vec<usize> {
+    let mut selected = Vec::with_capacity(n);
+    for front in non_dominated_sort(objectives) {
+        if selected.len() + front.len() <= n {
+            selected.extend(front);
+        } else {
+            let distance = crowding_distance(objectives, &front);
+            let mut order: Vec<usize> = (0..front.len()).collect();
+            order.sort_by(|&a, &b| {
+                distance[b]
+                    .partial_cmp(&distance[a])
+                    .unwrap_or(Ordering::Equal)
+            });
+            let missing = n - selected.len();
+            selected.extend(order.into_iter().take(missing).map(|k| front[k]));
+        }
+        if selected.len() >= n {
+            break;
+        }
+    }
+    selected
+}
+
+impl<O, P, F, R> Solver<O, ParetoState<P, F>> for NSGAII<P, F, R>
+where
+    O: MultiObjective<Param = P, Float = F> + SyncAlias,
+    P: Clone + SyncAlias + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+    R: Rng,
+{
+    const NAME: &'static str = "NSGA-II";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: ParetoState<P, F>,
+    ) -> Result<(ParetoState<P, F>, Option<KV>), Error> {
+        let population = self.evolution.init_population(state.take_population())?;
+        let objectives = self.evolution.evaluate(problem, &population)?;
+        let (state, front_size) = store_population(state, population, objectives);
+        Ok((state, Some(kv!("front_size" => front_size as u64;))))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: ParetoState<P, F>,
+    ) -> Result<(ParetoState<P, F>, Option<KV>), Error> {
+        let (mut population, mut objectives) = take_population("NSGAII", &mut state)?;
+        let n = population.len();
+
+        let mut rank = vec![0; n];
+        let mut distance = vec![float!(0.0); n];
+        for (r, front) in non_dominated_sort(&objectives).into_iter().enumerate() {
+            for (&i, d) in front.iter().zip(crowding_distance(&objectives, &front)) {
+                rank[i] = r;
+                distance[i] = d;
+            }
+        }
+
+        let mut offspring = Vec::with_capacity(n + 1);
+        while offspring.len() < n {
+            let a = self.tournament(&rank, &distance);
+            let b = self.tournament(&rank, &distance);
+            let (c1, c2) = self.evolution.offspring(&population[a], &population[b]);
+            offspring.push(c1);
+            offspring.push(c2);
+        }
+        offspring.truncate(n);
+        objectives.extend(self.evolution.evaluate(problem, &offspring)?);
+        population.extend(offspring);
+
+        let selected = environmental_selection(&objectives, n);
+        let (population, objectives) = select(&population, &objectives, &selected);
+        let (state, front_size) = store_population(state, population, objectives);
+        Ok((state, Some(kv!("front_size" => front_size as u64;))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::Zdt1;
+    use super::*;
+    use crate::core::checkpointing::{Checkpoint, CheckpointingFrequency};
+    use crate::core::observers::{Observe, ObserverMode};
+    use crate::core::{ArgminError, Executor, State};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+    use std::sync::{Arc, Mutex};
+
+    type Nsga2 = NSGAII<Vec<f64>, f64, rand_xoshiro::Xoroshiro128Plus>;
+
+    test_trait_impl!(nsga2, NSGAII<Vec<f64>, f64, rand::rngs::StdRng>);
+
+    fn solver(population_size: usize) -> Nsga2 {
+        NSGAII::new((vec![0.0; 10], vec![1.0; 10]), population_size)
+            .with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(42))
+    }
+
+    #[test]
+    fn test_new() {
+        let nsga2: NSGAII<Vec<f64>, f64, _> = NSGAII::new((vec![0.0], vec![1.0]), 50);
+        let Evolution {
+            name,
+            bounds,
+            population_size,
+            crossover_prob,
+            crossover_eta,
+            mutation_prob,
+            mutation_eta,
+            rng_generator: _,
+            lower,
+            upper,
+        } = nsga2.evolution;
+        assert_eq!(name, "NSGAII");
+        assert_eq!(bounds, (vec![0.0], vec![1.0]));
+        assert_eq!(population_size, 50);
+        assert_eq!(crossover_prob.to_ne_bytes(), 0.9f64.to_ne_bytes());
+        assert_eq!(crossover_eta.to_ne_bytes(), 15.0f64.to_ne_bytes());
+        assert!(mutation_prob.is_none());
+        assert_eq!(mutation_eta.to_ne_bytes(), 20.0f64.to_ne_bytes());
+        assert!(lower.is_empty());
+        assert!(upper.is_empty());
+    }
+
+    #[test]
+    fn test_builders() {
+        let nsga2 = solver(20)
+            .with_crossover(0.5, 5.0)
+            .unwrap()
+            .with_mutation(0.3, 30.0)
+            .unwrap();
+        assert_eq!(
+            nsga2.evolution.crossover_prob.to_ne_bytes(),
+            0.5f64.to_ne_bytes()
+        );
+        assert_eq!(
+            nsga2.evolution.crossover_eta.to_ne_bytes(),
+            5.0f64.to_ne_bytes()
+        );
+        assert_eq!(nsga2.evolution.mutation_prob, Some(0.3));
+        assert_eq!(
+            nsga2.evolution.mutation_eta.to_ne_bytes(),
+            30.0f64.to_ne_bytes()
+        );
+
+        assert_error!(
+            solver(20).with_crossover(-0.5, 5.0),
+            ArgminError,
+            "Invalid parameter: \"`NSGAII`: crossover probability must be in [0, 1].\""
+        );
+        assert_error!(
+            solver(20).with_mutation(0.5, f64::INFINITY),
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`NSGAII`: distribution index of mutation must be >= 0 ",
+                "and finite.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_init() {
+        let mut nsga2 = solver(20);
+        let mut problem = Problem::new(Zdt1 {});
+        let state = ParetoState::new().population(vec![vec![0.0; 10], vec![1.0; 10]]);
+        let (state, kv) = nsga2.init(&mut problem, state).unwrap();
+        let population = state.get_population().unwrap();
+        let objectives = state.get_objectives().unwrap();
+        assert_eq!(population.len(), 20);
+        assert_eq!(objectives.len(), 20);
+        assert_eq!(population[0], vec![0.0; 10]);
+        assert_eq!(objectives[0], vec![0.0, 1.0]);
+        assert_eq!(problem.counts["objectives_count"], 20);
+
+        // The point (0, ..., 0) dominates all others with f1 = 0; the front holds the members
+        // with rank 0 only
+        let front = state.get_pareto_objectives().unwrap();
+        assert_eq!(
+            front.len() as u64,
+            kv.unwrap().get("front_size").unwrap().get_uint().unwrap()
+        );
+        for (i, a) in front.iter().enumerate() {
+            for b in front.iter().skip(i + 1) {
+                assert!(!super::super::sorting::dominates(a, b));
+                assert!(!super::super::sorting::dominates(b, a));
+            }
+        }
+
+        let mut nsga2 = solver(20);
+        let res = nsga2.init(
+            &mut problem,
+            ParetoState::new().population(vec![vec![0.0; 3]]),
+        );
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`NSGAII`: members of the initial population must have the ",
+                "same length as the bounds.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_next_iter_without_population() {
+        let mut nsga2 = solver(20);
+        let mut problem = Problem::new(Zdt1 {});
+        let res = nsga2.next_iter(&mut problem, ParetoState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Potential bug: \"`NSGAII`: No population in state.\". ",
+                "This is potentially a bug. Please file a report on ",
+                "https://github.com/argmin-rs/argmin/issues"
+            )
+        );
+    }
+
+    #[test]
+    fn test_environmental_selection() {
+        let objectives = vec![
+            vec![0.0f64, 4.0],
+            vec![1.0, 2.0],
+            vec![5.0, 5.0],
+            vec![2.0, 1.0],
+            vec![4.0, 0.0],
+            vec![1.5, 1.5],
+        ];
+        // The first front holds 0, 1, 3, 4, 5; of these, 5 is the most crowded one
+        let mut selected = environmental_selection(&objectives, 4);
+        selected.sort();
+        assert_eq!(selected, vec![0, 1, 3, 4]);
+        let mut selected = environmental_selection(&objectives, 6);
+        selected.sort();
+        assert_eq!(selected, vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_zdt1() {
+        let res = Executor::new(Zdt1 {}, solver(40))
+            .configure(|state| state.max_iters(200))
+            .run()
+            .unwrap();
+        let state = res.state();
+        let front = state.get_pareto_front().unwrap();
+        let objectives = state.get_pareto_objectives().unwrap();
+        assert_eq!(state.get_population().unwrap().len(), 40);
+        assert_eq!(state.get_func_counts()["objectives_count"], 40 * 201);
+        assert!(front.len() >= 30);
+        // The Pareto optimal front is `f2 = 1 - sqrt(f1)` with `f1` in [0, 1]
+        for f in objectives.iter() {
+            assert_relative_eq!(f[1], 1.0 - f[0].sqrt(), epsilon = 0.05);
+        }
+        let f1_min = objectives
+            .iter()
+            .map(|f| f[0])
+            .fold(f64::INFINITY, f64::min);
+        let f1_max = objectives.iter().map(|f| f[0]).fold(0.0, f64::max);
+        assert!(f1_min < 0.05);
+        assert!(f1_max > 0.95);
+    }
+
+    #[test]
+    fn test_observer() {
+        #[derive(Clone, Default)]
+        struct FrontSizes(Arc<Mutex<Vec<usize>>>);
+
+        impl Observe<ParetoState<Vec<f64>, f64>> for FrontSizes {
+            fn observe_iter(
+                &mut self,
+                state: &ParetoState<Vec<f64>, f64>,
+                _kv: &KV,
+            ) -> Result<(), Error> {
+                let size = state.get_pareto_front().map(|front| front.len()).unwrap();
+                self.0.lock().unwrap().push(size);
+                Ok(())
+            }
+        }
+
+        let observer = FrontSizes::default();
+        Executor::new(Zdt1 {}, solver(20))
+            .configure(|state| state.max_iters(10))
+            .add_observer(observer.clone(), ObserverMode::Always)
+            .run()
+            .unwrap();
+        let sizes = observer.0.lock().unwrap();
+        assert_eq!(sizes.len(), 10);
+        assert!(sizes.iter().all(|&size| (1..=20).contains(&size)));
+    }
+
+    #[test]
+    fn test_checkpoint() {
+        type Saved = Option<(Nsga2, ParetoState<Vec<f64>, f64>)>;
+
+        #[derive(Clone, Default)]
+        struct InMemory(Arc<Mutex<Saved>>);
+
+        impl Checkpoint<Nsga2, ParetoState<Vec<f64>, f64>> for InMemory {
+            fn save(
+                &self,
+                solver: &Nsga2,
+                state: &ParetoState<Vec<f64>, f64>,
+            ) -> Result<(), Error> {
+                *self.0.lock().unwrap() = Some((solver.clone(), state.clone()));
+                Ok(())
+            }
+
+            fn load(&self) -> Result<Saved, Error> {
+                Ok(self.0.lock().unwrap().clone())
+            }
+
+            fn frequency(&self) -> CheckpointingFrequency {
+                CheckpointingFrequency::Every(5)
+            }
+        }
+
+        /// Aborts the optimization in iteration 7
+        struct Abort {}
+
+        impl Observe<ParetoState<Vec<f64>, f64>> for Abort {
+            fn observe_iter(
+                &mut self,
+                state: &ParetoState<Vec<f64>, f64>,
+                _kv: &KV,
+            ) -> Result<(), Error> {
+                if state.get_iter() == 7 {
+                    return Err(argmin_error!(ImpossibleError, "abort"));
+                }
+                Ok(())
+            }
+        }
+
+        let uninterrupted = Executor::new(Zdt1 {}, solver(20))
+            .configure(|state| state.max_iters(12))
+            .run()
+            .unwrap();
+
+        let checkpoint = InMemory::default();
+        let res = Executor::new(Zdt1 {}, solver(20))
+            .configure(|state| state.max_iters(12))
+            .add_observer(Abort {}, ObserverMode::Always)
+            .checkpointing(checkpoint.clone())
+            .run();
+        assert!(res.is_err());
+        assert_eq!(checkpoint.0.lock().unwrap().as_ref().unwrap().1.iter, 5);
+
+        // Resuming from iteration 5 yields the same result as the uninterrupted run
+        let resumed = Executor::new(Zdt1 {}, solver(20))
+            .checkpointing(checkpoint)
+            .run()
+            .unwrap();
+        assert_eq!(resumed.state().get_iter(), 12);
+        assert_eq!(
+            resumed.state().get_pareto_objectives(),
+            uninterrupted.state().get_pareto_objectives()
+        );
+        assert_eq!(
+            resumed.state().get_population(),
+            uninterrupted.state().get_population()
+        );
+    }
+}
@@ -0,0 +1,697 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::sorting::non_dominated_sort;
+use super::{select, store_population, take_population, Evolution};
+use crate::core::{
+    ArgminFloat, Error, MultiObjective, ParetoState, Problem, Solver, SyncAlias, KV,
+};
+use crate::solver::dense::lu_solve;
+use argmin_math::{ArgminGet, ArgminSet, ArgminSize};
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # NSGA-III
+///
+/// Reference-point-based many-objective genetic algorithm for problems implementing
+/// [`MultiObjective`](`crate::core::MultiObjective`) within the box `lower <= x <= upper`.
+///
+/// Every iteration creates `population_size` offspring from randomly chosen pairs of parents via
+/// simulated binary crossover (probability `0.9`, distribution index `15`) and polynomial mutation
+/// (probability `1/n` per coordinate, distribution index `20`). Parents and offspring are sorted
+/// into non-dominated fronts and the next population is filled front by front. Members of the
+/// last front which only partially fits are chosen such that the population is spread along a set
+/// of reference directions: the objective values are normalized via the ideal point and the
+/// intercepts of the hyperplane through the extreme points, each member is associated with the
+/// closest reference direction, and the reference directions with the fewest associated members
+/// are served first.
+///
+/// By default, the reference points are the `C(p + m - 1, m - 1)` points on the unit simplex
+/// with `p` divisions per objective (Das and Dennis), where `m` is the number of objectives. For
+/// three objectives and `p = 12`, for instance, there are `91` reference points. Custom reference
+/// points can be provided via [`with_reference_points`](`NSGAIII::with_reference_points`). The
+/// population size should be slightly larger than the number of reference points.
+///
+/// The population, its objective values and its Pareto front (the non-dominated members) are
+/// available in the [`ParetoState`]. Members of an initial population provided via
+/// [`ParetoState::population`] are complemented by individuals which are uniformly distributed
+/// within the bounds. The number of reference points is reported as KV metric
+/// `reference_points` after initialization, the size of the Pareto front as `front_size`.
+///
+/// Since Pareto fronts cannot be ranked by a cost function value, the solver only terminates once
+/// the maximum number of iterations is reached.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`MultiObjective`]. All objectives are
+/// minimized.
+///
+/// ## Reference
+///
+/// Kalyanmoy Deb and Himanshu Jain (2014). An Evolutionary Many-Objective Optimization Algorithm
+/// Using Reference-Point-Based Nondominated Sorting Approach, Part I: Solving Problems With Box
+/// Constraints. IEEE Transactions on Evolutionary Computation 18 (4), 577–601.
+/// DOI: <https://doi.org/10.1109/TEVC.2013.2281535>
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct NSGAIII<P, F, R> {
+    /// Settings and variation operators
+    evolution: Evolution<P, F, R>,
+    /// Number of divisions per objective of the default reference points
+    divisions: usize,
+    /// Reference points
+    reference_points: Option<Vec<Vec<F>>>,
+}
+
+impl<P, F> NSGAIII<P, F, rand::rngs::StdRng>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `NSGAIII`
+    ///
+    /// Takes the bounds of the search space, the size of the population and the number of
+    /// divisions per objective of the reference points.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nsga::NSGAIII;
+    /// let nsga3: NSGAIII<Vec<f64>, f64, _> =
+    ///     NSGAIII::new((vec![0.0, 0.0, 0.0], vec![1.0, 1.0, 1.0]), 92, 12);
+    /// ```
+    pub fn new(bounds: (P, P), population_size: usize, divisions: usize) -> Self {
+        NSGAIII {
+            evolution: Evolution::new(
+                "NSGAIII",
+                bounds,
+                population_size,
+                rand::rngs::StdRng::from_entropy(),
+            ),
+            divisions,
+            reference_points: None,
+        }
+    }
+}
+
+impl<P, F, R0> NSGAIII<P, F, R0>
+where
+    F: ArgminFloat,
+{
+    /// Set random number generator
+    ///
+    /// Defaults to `rand::rngs::StdRng::from_entropy()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nsga::NSGAIII;
+    /// # use rand::SeedableRng;
+    /// let nsga3: NSGAIII<Vec<f64>, f64, _> =
+    ///     NSGAIII::new((vec![0.0, 0.0, 0.0], vec![1.0, 1.0, 1.0]), 92, 12)
+    ///         .with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(1729));
+    /// ```
+    pub fn with_rng_generator<R1: Rng>(self, generator: R1) -> NSGAIII<P, F, R1> {
+        NSGAIII {
+            evolution: self.evolution.with_rng_generator(generator),
+            divisions: self.divisions,
+            reference_points: self.reference_points,
+        }
+    }
+
+    /// Set probability and distribution index of simulated binary crossover
+    ///
+    /// The probability of crossover of a pair of parents must be in `[0, 1]` and defaults to
+    /// `0.9`. The distribution index must be non-negative and finite and defaults to `15`; larger
+    /// values create children closer to their parents.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nsga::NSGAIII;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let nsga3: NSGAIII<Vec<f64>, f64, _> =
+    ///     NSGAIII::new((vec![0.0, 0.0, 0.0], vec![1.0, 1.0, 1.0]), 92, 12)
+    ///         .with_crossover(1.0, 30.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_crossover(mut self, prob: F, eta: F) -> Result<Self, Error> {
+        self.evolution.set_crossover(prob, eta)?;
+        Ok(self)
+    }
+
+    /// Set probability and distribution index of polynomial mutation
+    ///
+    /// The probability of mutation of each coordinate must be in `[0, 1]` and defaults to `1/n`,
+    /// where `n` is the number of parameters. The distribution index must be non-negative and
+    /// finite and defaults to `20`; larger values create smaller mutations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nsga::NSGAIII;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let nsga3: NSGAIII<Vec<f64>, f64, _> =
+    ///     NSGAIII::new((vec![0.0, 0.0, 0.0], vec![1.0, 1.0, 1.0]), 92, 12)
+    ///         .with_mutation(0.5, 20.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_mutation(mut self, prob: F, eta: F) -> Result<Self, Error> {
+        self.evolution.set_mutation(prob, eta)?;
+        Ok(self)
+    }
+
+    /// Set custom reference points
+    ///
+    /// Replaces the default reference points on the unit simplex. All reference points must have
+    /// one non-negative, finite entry per objective and at least one positive entry.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::nsga::NSGAIII;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let nsga3: NSGAIII<Vec<f64>, f64, _> = NSGAIII::new((vec![0.0, 0.0], vec![1.0, 1.0]), 12, 0)
+    ///     .with_reference_points(vec![vec![1.0, 0.0], vec![0.8, 0.2], vec![0.6, 0.4]])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_reference_points(mut self, reference_points: Vec<Vec<F>>) -> Result<Self, Error> {
+        let m = reference_points.first().map(|w| w.len()).unwrap_or(0);
+        if m < 1 || reference_points.iter().any(|w| w.len() != m) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`NSGAIII`: reference points must have the same, non-zero length."
+            ));
+        }
+        if reference_points.iter().any(|w| {
+            w.iter().any(|&wi| wi < float!(0.0) || !wi.is_finite())
+                || w.iter().all(|&wi| wi <= float!(0.0))
+        }) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`NSGAIII`: entries of reference points must be >= 0 and finite, with at least one entry > 0."
+            ));
+        }
+        self.reference_points = Some(reference_points);
+        Ok(self)
+    }
+}
+
+/// Points on the unit simplex in `m` dimensions with `p` divisions per dimension (Das and Dennis)
+fn das_dennis<F: ArgminFloat>(m: usize, p: usize) -> Vec<Vec<F>> {
+    fn recurse(m: usize, left: usize, current: &mut Vec<usize>, points: &mut Vec<Vec<usize>>) {
+        if current.len() == m - 1 {
+            current.push(left);
+            points.push(current.clone());
+            current.pop();
+            return;
+        }
+        for k in 0..=left {
+            current.push(k);
+            recurse(m, left - k, current, points);
+            current.pop();
+        }
+    }
+
+    let mut points = vec![];
+    recurse(m, p, &mut vec![], &mut points);
+    points
+        .into_iter()
+        .map(|point| {
+            point
+                .into_iter()
+                .map(|k| float!(k as f64 / p as f64))
+                .collect()
+        })
+        .collect()
+}
+
+/// Normalizes the objective values of the members `members` of `objectives`
+///
+/// The objective values are translated by the ideal point and scaled by the intercepts of the
+/// hyperplane through the extreme points. If the hyperplane is degenerate, the maximum of the
+/// translated objective values is used instead.
+fn normalize<F: ArgminFloat>(objectives: &[Vec<F>], members: &[usize]) -> Vec<Vec<F>> {
+    let m = objectives[members[0]].len();
+    let ideal: Vec<F> = (0..m)
+        .map(|j| {
+            members
+                .iter()
+                .map(|&i| objectives[i][j])
+                .fold(F::infinity(), |a, b| a.min(b))
+        })
+        .collect();
+    let translated: Vec<Vec<F>> = members
+        .iter()
+        .map(|&i| {
+            objectives[i]
+                .iter()
+                .zip(ideal.iter())
+                .map(|(&f, &z)| f - z)
+                .collect()
+        })
+        .collect();
+
+    // Extreme point of axis `j` minimizes the achievement scalarizing function with weight `1` on
+    // objective `j` and `1e-6` on all others
+    let extreme: Vec<Vec<F>> = (0..m)
+        .map(|j| {
+            let asf = |f: &Vec<F>| {
+                f.iter()
+                    .enumerate()
+                    .map(|(i, &fi)| if i == j { fi } else { fi / float!(1e-6) })
+                    .fold(F::neg_infinity(), |a, b| a.max(b))
+            };
+            translated
+                .iter()
+                .min_by(|a, b| {
+                    asf(a)
+                        .partial_cmp(&asf(b))
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                })
+                .unwrap()
+                .clone()
+        })
+        .collect();
+
+    let intercepts = lu_solve(extreme, vec![float!(1.0); m])
+        .map(|x| x.into_iter().map(|xi| float!(1.0) / xi).collect::<Vec<F>>())
+        .filter(|a| a.iter().all(|&ai| ai > float!(1e-10) && ai.is_finite()))
+        .unwrap_or_else(|| {
+            (0..m)
+                .map(|j| {
+                    let max = translated
+                        .iter()
+                        .map(|f| f[j])
+                        .fold(float!(0.0), |a: F, b| a.max(b));
+                    if max > float!(1e-10) {
+                        max
+                    } else {
+                        float!(1.0)
+                    }
+                })
+                .collect()
+        });
+
+    translated
+        .into_iter()
+        .map(|f| {
+            f.iter()
+                .zip(intercepts.iter())
+                .map(|(&fi, &a)| fi / a)
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns the index of the closest reference direction to `f` and the perpendicular distance
+fn associate<F: ArgminFloat>(f: &[F], reference_points: &[Vec<F>]) -> (usize, F) {
+    reference_points
+        .iter()
+        .map(|w| {
+            let ww = w.iter().fold(float!(0.0), |acc: F, &wi| acc + wi * wi);
+            let wf = w
+                .iter()
+                .zip(f.iter())
+                .fold(float!(0.0), |acc: F, (&wi, &fi)| acc + wi * fi);
+            let t = wf / ww;
+            w.iter()
+                .zip(f.iter())
+                .fold(float!(0.0), |acc: F, (&wi, &fi)| {
+                    acc + (fi - t * wi) * (fi - t * wi)
+                })
+                .sqrt()
+        })
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+        .unwrap()
+}
+
+/// Selects `n` members of the population with objective values `objectives` by non-dominated
+/// sorting and niching along the reference directions `reference_points`
+fn environmental_selection<F: ArgminFloat, R: Rng>(
+    objectives: &[Vec<F>],
+    n: usize,
+    reference_points: &[Vec<F>],
+    rng: &mut R,
+) -> Vec<usize> {
+    let mut selected = Vec::with_capacity(n);
+    let mut last_front = vec![];
+    for front in non_dominated_sort(objectives) {
+        if selected.len() + front.len() <= n {
+            selected.extend(front);
+        } else {
+            last_front = front;
+        }
+        if selected.len() >= n || !last_front.is_empty() {
+            break;
+        }
+    }
+    if selected.len() >= n {
+        return selected;
+    }
+
+    let members: Vec<usize> = selected.iter().chain(last_front.iter()).cloned().collect();
+    let normalized = normalize(objectives, &members);
+    let association: Vec<(usize, F)> = normalized
+        .iter()
+        .map(|f| associate(f, reference_points))
+        .collect();
+
+    let mut niche_count = vec![0usize; reference_points.len()];
+    for &(j, _) in association.iter().take(selected.len()) {
+        niche_count[j] += 1;
+    }
+    // Candidates of the last front as indices into `members`
+    let mut candidates: Vec<usize> = (selected.len()..members.len()).collect();
+    let mut active = vec![true; reference_points.len()];
+    while selected.len() < n {
+        let min_count = niche_count
+            .iter()
+            .zip(active.iter())
+            .filter(|(_, &a)| a)
+            .map(|(&c, _)| c)
+            .min()
+            .unwrap();
+        let minimal: Vec<usize> = (0..reference_points.len())
+            .filter(|&j| active[j] && niche_count[j] == min_count)
+            .collect();
+        let j = minimal[rng.gen_range(0..minimal.len())];
+        let associated: Vec<usize> = (0..candidates.len())
+            .filter(|&k| association[candidates[k]].0 == j)
+            .collect();
+        if associated.is_empty() {
+            active[j] = false;
+            continue;
+        }
+        let k = if niche_count[j] == 0 {
+            *associated
+                .iter()
+                .min_by(|&&a, &&b| {
+                    association[candidates[a]]
+                        .1
+                        .partial_cmp(&association[candidates[b]].1)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                })
+                .unwrap()
+        } else {
+            associated[rng.gen_range(0..associated.len())]
+        };
+        selected.push(members[candidates.swap_remove(k)]);
+        niche_count[j] += 1;
+    }
+    selected
+}
+
+impl<O, P, F, R> Solver<O, ParetoState<P, F>> for NSGAIII<P, F, R>
+where
+    O: MultiObjective<Param = P, Float = F> + SyncAlias,
+    P: Clone + SyncAlias + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+    R: Rng,
+{
+    const NAME: &'static str = "NSGA-III";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: ParetoState<P, F>,
+    ) -> Result<(ParetoState<P, F>, Option<KV>), Error> {
+        let population = self.evolution.init_population(state.take_population())?;
+        let objectives = self.evolution.evaluate(problem, &population)?;
+        let m = objectives[0].len();
+        match self.reference_points.as_ref() {
+            Some(reference_points) => {
+                if reference_points[0].len() != m {
+                    return Err(argmin_error!(
+                        InvalidParameter,
+                        "`NSGAIII`: reference points must have one entry per objective."
+                    ));
+                }
+            }
+            None => {
+                if self.divisions < 1 {
+                    return Err(argmin_error!(
+                        InvalidParameter,
+                        "`NSGAIII`: number of divisions must be >= 1."
+                    ));
+                }
+                self.reference_points = Some(das_dennis(m, self.divisions));
+            }
+        }
+        let num_reference_points = self.reference_points.as_ref().unwrap().len();
+        let (state, front_size) = store_population(state, population, objectives);
+        Ok((
+            state,
+            Some(kv!(
+                "reference_points" => num_reference_points as u64;
+                "front_size" => front_size as u64;
+            )),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: ParetoState<P, F>,
+    ) -> Result<(ParetoState<P, F>, Option<KV>), Error> {
+        let (mut population, mut objectives) = take_population("NSGAIII", &mut state)?;
+        let reference_points = self
+            .reference_points
+            .as_ref()
+            .ok_or_else(argmin_error_closure!(
+                PotentialBug,
+                "`NSGAIII`: No reference points."
+            ))?;
+        let n = population.len();
+
+        let mut offspring = Vec::with_capacity(n + 1);
+        while offspring.len() < n {
+            let a = self.evolution.rng_generator.gen_range(0..n);
+            let b = self.evolution.rng_generator.gen_range(0..n);
+            let (c1, c2) = self.evolution.offspring(&population[a], &population[b]);
+            offspring.push(c1);
+            offspring.push(c2);
+        }
+        offspring.truncate(n);
+        objectives.extend(self.evolution.evaluate(problem, &offspring)?);
+        population.extend(offspring);
+
+        let selected = environmental_selection(
+            &objectives,
+            n,
+            reference_points,
+            &mut self.evolution.rng_generator,
+        );
+        let (population, objectives) = select(&population, &objectives, &selected);
+        let (state, front_size) = store_population(state, population, objectives);
+        Ok((state, Some(kv!("front_size" => front_size as u64;))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor, State};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(nsga3, NSGAIII<Vec<f64>, f64, rand::rngs::StdRng>);
+
+    /// DTLZ2 test problem with three objectives and Pareto front on the unit sphere
+    struct Dtlz2 {}
+
+    impl MultiObjective for Dtlz2 {
+        type Param = Vec<f64>;
+        type Float = f64;
+
+        fn objectives(&self, p: &Self::Param) -> Result<Vec<Self::Float>, Error> {
+            let g: f64 = p[2..].iter().map(|x| (x - 0.5).powi(2)).sum();
+            let (a, b) = (
+                p[0] * std::f64::consts::FRAC_PI_2,
+                p[1] * std::f64::consts::FRAC_PI_2,
+            );
+            Ok(vec![
+                (1.0 + g) * a.cos() * b.cos(),
+                (1.0 + g) * a.cos() * b.sin(),
+                (1.0 + g) * a.sin(),
+            ])
+        }
+    }
+
+    fn solver(
+        population_size: usize,
+        divisions: usize,
+    ) -> NSGAIII<Vec<f64>, f64, rand_xoshiro::Xoroshiro128Plus> {
+        NSGAIII::new((vec![0.0; 7], vec![1.0; 7]), population_size, divisions)
+            .with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(42))
+    }
+
+    #[test]
+    fn test_new() {
+        let nsga3: NSGAIII<Vec<f64>, f64, _> = NSGAIII::new((vec![0.0], vec![1.0]), 50, 4);
+        assert_eq!(nsga3.evolution.name, "NSGAIII");
+        assert_eq!(nsga3.evolution.population_size, 50);
+        assert_eq!(nsga3.divisions, 4);
+        assert!(nsga3.reference_points.is_none());
+    }
+
+    #[test]
+    fn test_builders() {
+        let nsga3 = solver(20, 4)
+            .with_crossover(0.5, 5.0)
+            .unwrap()
+            .with_mutation(0.3, 30.0)
+            .unwrap()
+            .with_reference_points(vec![vec![1.0, 0.0], vec![0.5, 0.5]])
+            .unwrap();
+        assert_eq!(
+            nsga3.evolution.crossover_prob.to_ne_bytes(),
+            0.5f64.to_ne_bytes()
+        );
+        assert_eq!(nsga3.evolution.mutation_prob, Some(0.3));
+        assert_eq!(
+            nsga3.reference_points,
+            Some(vec![vec![1.0, 0.0], vec![0.5, 0.5]])
+        );
+
+        assert_error!(
+            solver(20, 4).with_crossover(2.0, 5.0),
+            ArgminError,
+            "Invalid parameter: \"`NSGAIII`: crossover probability must be in [0, 1].\""
+        );
+        for reference_points in [vec![], vec![vec![]], vec![vec![1.0, 0.0], vec![1.0]]] {
+            assert_error!(
+                solver(20, 4).with_reference_points(reference_points),
+                ArgminError,
+                "Invalid parameter: \"`NSGAIII`: reference points must have the same, non-zero length.\""
+            );
+        }
+        for reference_points in [
+            vec![vec![1.0, -0.5]],
+            vec![vec![1.0, f64::NAN]],
+            vec![vec![1.0, 0.0], vec![0.0, 0.0]],
+        ] {
+            assert_error!(
+                solver(20, 4).with_reference_points(reference_points),
+                ArgminError,
+                concat!(
+                    "Invalid parameter: \"`NSGAIII`: entries of reference points must be >= 0 ",
+                    "and finite, with at least one entry > 0.\""
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_das_dennis() {
+        let points: Vec<Vec<f64>> = das_dennis(2, 4);
+        assert_eq!(
+            points,
+            vec![
+                vec![0.0, 1.0],
+                vec![0.25, 0.75],
+                vec![0.5, 0.5],
+                vec![0.75, 0.25],
+                vec![1.0, 0.0]
+            ]
+        );
+        let points: Vec<Vec<f64>> = das_dennis(3, 12);
+        assert_eq!(points.len(), 91);
+        for point in points {
+            assert_relative_eq!(point.iter().sum::<f64>(), 1.0, epsilon = 1e-12);
+        }
+        assert_eq!(das_dennis::<f64>(5, 6).len(), 210);
+    }
+
+    #[test]
+    fn test_normalize() {
+        let objectives = vec![
+            vec![1.0f64, 5.0],
+            vec![3.0, 1.0],
+            vec![2.0, 3.0],
+            vec![10.0, 10.0],
+        ];
+        // Ideal point (1, 1), extreme points (0, 4) and (2, 0), intercepts 2 and 4
+        let normalized = normalize(&objectives, &[0, 1, 2]);
+        assert_eq!(
+            normalized,
+            vec![vec![0.0, 1.0], vec![1.0, 0.0], vec![0.5, 0.5]]
+        );
+    }
+
+    #[test]
+    fn test_environmental_selection() {
+        let objectives = vec![
+            vec![0.0f64, 1.0],
+            vec![0.1, 0.9],
+            vec![0.5, 0.5],
+            vec![1.0, 0.0],
+            vec![1.0, 1.0],
+        ];
+        let reference_points = das_dennis(2, 2);
+        let mut rng = rand_xoshiro::Xoroshiro128Plus::seed_from_u64(1);
+        let mut selected = environmental_selection(&objectives, 3, &reference_points, &mut rng);
+        selected.sort();
+        assert_eq!(selected, vec![0, 2, 3]);
+        let mut selected = environmental_selection(&objectives, 4, &reference_points, &mut rng);
+        selected.sort();
+        assert_eq!(selected, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_init_errors() {
+        let mut problem = Problem::new(Dtlz2 {});
+        let mut nsga3 = solver(20, 0);
+        assert_error!(
+            nsga3.init(&mut problem, ParetoState::new()),
+            ArgminError,
+            "Invalid parameter: \"`NSGAIII`: number of divisions must be >= 1.\""
+        );
+        let mut nsga3 = solver(20, 4)
+            .with_reference_points(vec![vec![1.0, 0.0]])
+            .unwrap();
+        assert_error!(
+            nsga3.init(&mut problem, ParetoState::new()),
+            ArgminError,
+            "Invalid parameter: \"`NSGAIII`: reference points must have one entry per objective.\""
+        );
+    }
+
+    #[test]
+    fn test_dtlz2() {
+        let res = Executor::new(Dtlz2 {}, solver(92, 12))
+            .configure(|state| state.max_iters(200))
+            .run()
+            .unwrap();
+        let state = res.state();
+        assert_eq!(state.get_population().unwrap().len(), 92);
+        let objectives = state.get_pareto_objectives().unwrap();
+        assert!(objectives.len() >= 85);
+        // The Pareto optimal front is the positive octant of the unit sphere
+        for f in objectives.iter() {
+            let norm = f.iter().map(|fi| fi * fi).sum::<f64>().sqrt();
+            assert_relative_eq!(norm, 1.0, epsilon = 0.05);
+        }
+        // Every objective is covered from (almost) zero to (almost) one
+        for j in 0..3 {
+            let min = objectives
+                .iter()
+                .map(|f| f[j])
+                .fold(f64::INFINITY, f64::min);
+            let max = objectives.iter().map(|f| f[j]).fold(0.0, f64::max);
+            assert!(min < 0.05);
+            assert!(max > 0.95);
+        }
+    }
+}
@@ -0,0 +1,150 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::ArgminFloat;
+use std::cmp::Ordering;
+
+/// Returns whether the objective values `a` dominate the objective values `b`
+///
+/// `a` dominates `b` if no objective of `a` is larger than the one of `b` and at least one is
+/// smaller.
+pub(super) fn dominates<F: ArgminFloat>(a: &[F], b: &[F]) -> bool {
+    let mut strictly_better = false;
+    for (&ai, &bi) in a.iter().zip(b.iter()) {
+        if ai > bi {
+            return false;
+        }
+        if ai < bi {
+            strictly_better = true;
+        }
+    }
+    strictly_better
+}
+
+/// Fast non-dominated sorting
+///
+/// Splits the indices of `objectives` into fronts: the first front holds all non-dominated
+/// points, the second front all points which are only dominated by points of the first front,
+/// and so on.
+pub(super) fn non_dominated_sort<F: ArgminFloat>(objectives: &[Vec<F>]) -> Vec<Vec<usize>> {
+    let n = objectives.len();
+    // Points dominated by point `i`
+    let mut dominated: Vec<Vec<usize>> = vec![vec![]; n];
+    // Number of points dominating point `i`
+    let mut domination_count = vec![0usize; n];
+    for i in 0..n {
+        for j in (i + 1)..n {
+            if dominates(&objectives[i], &objectives[j]) {
+                dominated[i].push(j);
+                domination_count[j] += 1;
+            } else if dominates(&objectives[j], &objectives[i]) {
+                dominated[j].push(i);
+                domination_count[i] += 1;
+            }
+        }
+    }
+
+    let mut fronts = vec![];
+    let mut front: Vec<usize> = (0..n).filter(|&i| domination_count[i] == 0).collect();
+    while !front.is_empty() {
+        let mut next = vec![];
+        for &i in front.iter() {
+            for &j in dominated[i].iter() {
+                domination_count[j] -= 1;
+                if domination_count[j] == 0 {
+                    next.push(j);
+                }
+            }
+        }
+        fronts.push(front);
+        front = next;
+    }
+    fronts
+}
+
+/// Crowding distance of the points `front` of `objectives`
+///
+/// The crowding distance of a point is the sum over all objectives of the distance between its
+/// two neighbors along this objective, normalized by the range of the objective within the
+/// front. The points with the smallest and largest value of any objective get an infinite
+/// crowding distance. The returned distances are in the order of `front`.
+pub(super) fn crowding_distance<F: ArgminFloat>(objectives: &[Vec<F>], front: &[usize]) -> Vec<F> {
+    let n = front.len();
+    let mut distance = vec![float!(0.0); n];
+    if n == 0 {
+        return distance;
+    }
+    let num_objectives = objectives[front[0]].len();
+    let mut order: Vec<usize> = (0..n).collect();
+    let columns =
+        (0..num_objectives).map(|m| front.iter().map(|&i| objectives[i][m]).collect::<Vec<F>>());
+    for values in columns {
+        let value = |k: usize| values[k];
+        order.sort_by(|&a, &b| value(a).partial_cmp(&value(b)).unwrap_or(Ordering::Equal));
+        let range = value(order[n - 1]) - value(order[0]);
+        distance[order[0]] = F::infinity();
+        distance[order[n - 1]] = F::infinity();
+        if range <= float!(0.0) {
+            continue;
+        }
+        for k in 1..(n - 1) {
+            distance[order[k]] =
+                distance[order[k]] + (value(order[k + 1]) - value(order[k - 1])) / range;
+        }
+    }
+    distance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_dominates() {
+        assert!(dominates(&[1.0f64, 2.0], &[1.0, 3.0]));
+        assert!(dominates(&[0.0f64, 2.0], &[1.0, 3.0]));
+        assert!(!dominates(&[1.0f64, 2.0], &[1.0, 2.0]));
+        assert!(!dominates(&[0.0f64, 4.0], &[1.0, 3.0]));
+        assert!(!dominates(&[1.0f64, 3.0], &[1.0, 2.0]));
+    }
+
+    #[test]
+    fn test_non_dominated_sort() {
+        let objectives = vec![
+            vec![1.0f64, 5.0],
+            vec![2.0, 2.0],
+            vec![3.0, 3.0],
+            vec![5.0, 1.0],
+            vec![4.0, 4.0],
+            vec![2.0, 6.0],
+            vec![6.0, 6.0],
+        ];
+        let fronts = non_dominated_sort(&objectives);
+        assert_eq!(fronts, vec![vec![0, 1, 3], vec![2, 5], vec![4], vec![6]]);
+        assert!(non_dominated_sort::<f64>(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_crowding_distance() {
+        let objectives = vec![
+            vec![0.0f64, 4.0],
+            vec![1.0, 2.0],
+            vec![10.0, 10.0],
+            vec![2.0, 1.0],
+            vec![4.0, 0.0],
+        ];
+        let distance = crowding_distance(&objectives, &[0, 1, 3, 4]);
+        assert!(distance[0].is_infinite());
+        assert!(distance[3].is_infinite());
+        assert_relative_eq!(distance[1], 2.0 / 4.0 + 3.0 / 4.0, epsilon = f64::EPSILON);
+        assert_relative_eq!(distance[2], 3.0 / 4.0 + 2.0 / 4.0, epsilon = f64::EPSILON);
+
+        let distance = crowding_distance(&objectives, &[2]);
+        assert!(distance[0].is_infinite());
+    }
+}
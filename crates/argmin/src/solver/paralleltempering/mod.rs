@@ -0,0 +1,569 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Parallel Tempering
+//!
+//! Parallel tempering, also known as replica-exchange Monte Carlo, runs several annealing chains
+//! at a ladder of fixed temperatures and occasionally exchanges the states of chains at
+//! neighboring temperatures. Hot chains roam the parameter space and pass good states down the
+//! ladder to cold chains, which refine them. For details see [`ParallelTempering`].
+//!
+//! ## References
+//!
+//! [Wikipedia](https://en.wikipedia.org/wiki/Parallel_tempering)
+//!
+//! Robert H. Swendsen and Jian-Sheng Wang (1986). "Replica Monte Carlo Simulation of Spin-Glasses".
+//! Physical Review Letters 57 (21), pp. 2607-2609. DOI: 10.1103/PhysRevLett.57.2607
+//!
+//! David J. Earl and Michael W. Deem (2005). "Parallel tempering: Theory, applications, and new
+//! perspectives". Physical Chemistry Chemical Physics 7 (23), pp. 3910-3916.
+//! DOI: 10.1039/B509983H
+
+use crate::core::{
+    ArgminFloat, CostFunction, Error, IterState, KvValue, Problem, SendAlias, Solver, State,
+    SyncAlias, KV,
+};
+use crate::solver::simulatedannealing::Anneal;
+use rand::prelude::*;
+use rand_xoshiro::Xoshiro256PlusPlus;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Parallel Tempering
+///
+/// Runs one chain (replica) per temperature of a fixed, increasing temperature ladder
+/// `T_0 < T_1 < ... < T_{n-1}`. In each iteration, the parameter vector of every replica is
+/// modified via [`Anneal`] with its temperature as extent, and the move is accepted according to
+/// the Metropolis criterion: improvements are always accepted and a deterioration by `delta` is
+/// accepted with probability `exp(-delta / T_k)`.
+///
+/// Every `swap_interval` iterations ([`ParallelTempering::with_swap_interval`]), exchanges of the
+/// states of neighboring replicas `k` and `k + 1` with costs `E_k` and `E_{k+1}` are proposed and
+/// accepted with probability `min(1, exp((1/T_k - 1/T_{k+1}) (E_k - E_{k+1})))`. Swap rounds
+/// alternate between the pairs starting at even and at odd `k`.
+///
+/// All replicas start from the initial parameter vector, which has to be provided via
+/// [`configure`](`crate::core::Executor::configure`) of [`Executor`](`crate::core::Executor`).
+/// The moves of all replicas are computed via [`Problem::bulk_anneal`] and evaluated via
+/// [`Problem::bulk_cost`], therefore the `rayon` feature enables running the replicas in
+/// parallel. The parameter vector and cost function value of the state are those of the currently
+/// best replica. The method does not converge on its own, therefore a maximum number of
+/// iterations should be set.
+///
+/// The acceptance rate of the moves of replica `k` and the acceptance rate of swaps between
+/// replicas `k` and `k + 1` since the start of the run are reported as KV metrics
+/// `acceptance_rate_k` and `swap_rate_k`, respectively. Swap rates which are close to zero
+/// indicate that neighboring temperatures are too far apart.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`] and [`Anneal`].
+///
+/// ## References
+///
+/// [Wikipedia](https://en.wikipedia.org/wiki/Parallel_tempering)
+///
+/// Robert H. Swendsen and Jian-Sheng Wang (1986). "Replica Monte Carlo Simulation of Spin-Glasses".
+/// Physical Review Letters 57 (21), pp. 2607-2609. DOI: 10.1103/PhysRevLett.57.2607
+///
+/// David J. Earl and Michael W. Deem (2005). "Parallel tempering: Theory, applications, and new
+/// perspectives". Physical Chemistry Chemical Physics 7 (23), pp. 3910-3916.
+/// DOI: 10.1039/B509983H
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct ParallelTempering<P, F, R> {
+    /// Temperature ladder in increasing order
+    temperatures: Vec<F>,
+    /// Number of iterations between swap rounds
+    swap_interval: u64,
+    /// Parameter vectors of the replicas
+    replicas: Vec<P>,
+    /// Cost function values of the replicas
+    costs: Vec<F>,
+    /// Number of moves performed by each replica
+    moves: u64,
+    /// Number of accepted moves per replica
+    moves_accepted: Vec<u64>,
+    /// Number of swap rounds performed
+    swap_rounds: u64,
+    /// Number of proposed swaps per pair of neighboring replicas
+    swaps_proposed: Vec<u64>,
+    /// Number of accepted swaps per pair of neighboring replicas
+    swaps_accepted: Vec<u64>,
+    /// random number generator
+    rng: R,
+}
+
+impl<P, F> ParallelTempering<P, F, Xoshiro256PlusPlus>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`ParallelTempering`]
+    ///
+    /// Takes the temperature ladder as input, which must consist of at least two positive
+    /// temperatures in strictly increasing order. One replica is run per temperature.
+    ///
+    /// Uses the `Xoshiro256PlusPlus` RNG internally. For use of another RNG, consider using
+    /// [`ParallelTempering::new_with_rng`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::paralleltempering::ParallelTempering;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// // Geometric ladder with 8 temperatures between 0.1 and 12.8
+    /// let temperatures: Vec<f64> = (0..8).map(|k| 0.1 * 2.0f64.powi(k)).collect();
+    /// let pt: ParallelTempering<Vec<f64>, _, _> = ParallelTempering::new(temperatures)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(temperatures: Vec<F>) -> Result<Self, Error> {
+        ParallelTempering::new_with_rng(temperatures, Xoshiro256PlusPlus::from_entropy())
+    }
+}
+
+impl<P, F, R> ParallelTempering<P, F, R>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`ParallelTempering`]
+    ///
+    /// Takes the temperature ladder as input, which must consist of at least two positive
+    /// temperatures in strictly increasing order. One replica is run per temperature.
+    /// Requires a RNG which must implement `rand::Rng` (and `serde::Serialize` if the `serde1`
+    /// feature is enabled).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::paralleltempering::ParallelTempering;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let my_rng = ();
+    /// let pt: ParallelTempering<Vec<f64>, _, _> =
+    ///     ParallelTempering::new_with_rng(vec![0.1f64, 1.0, 10.0], my_rng)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new_with_rng(temperatures: Vec<F>, rng: R) -> Result<Self, Error> {
+        if temperatures.len() < 2 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ParallelTempering`: At least two temperatures are required."
+            ));
+        }
+        if !temperatures[0].is_finite() || temperatures[0] <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ParallelTempering`: Temperatures must be > 0."
+            ));
+        }
+        if temperatures
+            .windows(2)
+            .any(|t| !t[1].is_finite() || t[1] <= t[0])
+        {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ParallelTempering`: Temperatures must be finite and strictly increasing."
+            ));
+        }
+        let n = temperatures.len();
+        Ok(ParallelTempering {
+            temperatures,
+            swap_interval: 1,
+            replicas: vec![],
+            costs: vec![],
+            moves: 0,
+            moves_accepted: vec![0; n],
+            swap_rounds: 0,
+            swaps_proposed: vec![0; n - 1],
+            swaps_accepted: vec![0; n - 1],
+            rng,
+        })
+    }
+
+    /// Set the number of iterations between two rounds of swaps
+    ///
+    /// Must be at least `1`. Defaults to `1`, which means that swaps are proposed in every
+    /// iteration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::paralleltempering::ParallelTempering;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let pt: ParallelTempering<Vec<f64>, _, _> =
+    ///     ParallelTempering::new(vec![0.1f64, 1.0, 10.0])?.with_swap_interval(10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_swap_interval(mut self, iters: u64) -> Result<Self, Error> {
+        if iters < 1 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ParallelTempering`: Swap interval must be >= 1."
+            ));
+        }
+        self.swap_interval = iters;
+        Ok(self)
+    }
+}
+
+impl<P, F, R> ParallelTempering<P, F, R>
+where
+    F: ArgminFloat,
+    R: Rng,
+{
+    /// Draws a uniformly distributed random number from `[0, 1)`
+    fn uniform(&mut self) -> F {
+        let u: f64 = self.rng.gen();
+        float!(u)
+    }
+
+    /// Proposes swaps between neighboring replicas, alternating between even and odd pairs
+    fn swap(&mut self) {
+        let start = (self.swap_rounds % 2) as usize;
+        for k in (start..self.temperatures.len() - 1).step_by(2) {
+            let log_alpha = (float!(1.0) / self.temperatures[k]
+                - float!(1.0) / self.temperatures[k + 1])
+                * (self.costs[k] - self.costs[k + 1]);
+            self.swaps_proposed[k] += 1;
+            if log_alpha >= float!(0.0) || self.uniform() < log_alpha.exp() {
+                self.replicas.swap(k, k + 1);
+                self.costs.swap(k, k + 1);
+                self.swaps_accepted[k] += 1;
+            }
+        }
+        self.swap_rounds += 1;
+    }
+
+    /// Index of the replica with the lowest cost function value
+    fn best_replica(&self) -> usize {
+        (0..self.costs.len())
+            .min_by(|&a, &b| {
+                self.costs[a]
+                    .partial_cmp(&self.costs[b])
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .unwrap()
+    }
+
+    /// Acceptance rates of moves and swaps as KV metrics
+    fn rates(&self) -> KV {
+        let mut kv = KV::new();
+        let rate = |accepted: u64, proposed: u64| {
+            KvValue::Float(if proposed > 0 {
+                accepted as f64 / proposed as f64
+            } else {
+                0.0
+            })
+        };
+        for (k, &accepted) in self.moves_accepted.iter().enumerate() {
+            kv.insert(format!("acceptance_rate_{k}"), rate(accepted, self.moves));
+        }
+        for (k, (&accepted, &proposed)) in self
+            .swaps_accepted
+            .iter()
+            .zip(self.swaps_proposed.iter())
+            .enumerate()
+        {
+            kv.insert(format!("swap_rate_{k}"), rate(accepted, proposed));
+        }
+        kv
+    }
+}
+
+impl<O, P, F, R> Solver<O, IterState<P, (), (), (), (), F>> for ParallelTempering<P, F, R>
+where
+    O: CostFunction<Param = P, Output = F> + Anneal<Param = P, Output = P, Float = F> + SyncAlias,
+    P: Clone + SyncAlias + SendAlias,
+    F: ArgminFloat + SyncAlias,
+    R: Rng,
+{
+    const NAME: &'static str = "Parallel Tempering";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`ParallelTempering` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+
+        let cost = state.get_cost();
+        let cost = if cost.is_infinite() {
+            problem.cost(&param)?
+        } else {
+            cost
+        };
+
+        let n = self.temperatures.len();
+        self.replicas = vec![param.clone(); n];
+        self.costs = vec![cost; n];
+        self.moves = 0;
+        self.moves_accepted = vec![0; n];
+        self.swap_rounds = 0;
+        self.swaps_proposed = vec![0; n - 1];
+        self.swaps_accepted = vec![0; n - 1];
+
+        Ok((
+            state.param(param).cost(cost),
+            Some(kv!(
+                "replicas" => n as u64;
+                "swap_interval" => self.swap_interval;
+            )),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let proposals = problem.bulk_anneal(&self.replicas, &self.temperatures)?;
+        let costs = problem.bulk_cost(&proposals)?;
+
+        // Metropolis criterion for the move of each replica
+        for (k, (proposal, cost)) in proposals.into_iter().zip(costs).enumerate() {
+            let delta = cost - self.costs[k];
+            if delta <= float!(0.0) || self.uniform() < (-delta / self.temperatures[k]).exp() {
+                self.replicas[k] = proposal;
+                self.costs[k] = cost;
+                self.moves_accepted[k] += 1;
+            }
+        }
+        self.moves += 1;
+
+        if (state.get_iter() + 1).is_multiple_of(self.swap_interval) {
+            self.swap();
+        }
+
+        let best = self.best_replica();
+        Ok((
+            state
+                .param(self.replicas[best].clone())
+                .cost(self.costs[best]),
+            Some(self.rates()),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{test_utils::TestProblem, ArgminError, Executor};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+    use std::sync::{Arc, Mutex};
+
+    test_trait_impl!(
+        parallel_tempering,
+        ParallelTempering<Vec<f64>, f64, Xoshiro256PlusPlus>
+    );
+
+    /// Double well with a local minimum near `x = 1` and the global minimum near `x = -1`,
+    /// separated by a barrier of height about `1`
+    struct DoubleWell {
+        rng: Arc<Mutex<Xoshiro256PlusPlus>>,
+    }
+
+    impl DoubleWell {
+        fn new(seed: u64) -> Self {
+            DoubleWell {
+                rng: Arc::new(Mutex::new(Xoshiro256PlusPlus::seed_from_u64(seed))),
+            }
+        }
+    }
+
+    impl CostFunction for DoubleWell {
+        type Param = f64;
+        type Output = f64;
+
+        fn cost(&self, x: &Self::Param) -> Result<Self::Output, Error> {
+            Ok((x.powi(2) - 1.0).powi(2) + 0.3 * x)
+        }
+    }
+
+    impl Anneal for DoubleWell {
+        type Param = f64;
+        type Output = f64;
+        type Float = f64;
+
+        fn anneal(&self, x: &Self::Param, temp: Self::Float) -> Result<Self::Output, Error> {
+            let step = 0.2 * temp.sqrt().min(5.0);
+            Ok(x + self.rng.lock().unwrap().gen_range(-step..=step))
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let pt: ParallelTempering<f64, f64, _> =
+            ParallelTempering::new(vec![0.1, 1.0, 10.0]).unwrap();
+        assert_eq!(pt.temperatures, vec![0.1, 1.0, 10.0]);
+        assert_eq!(pt.swap_interval, 1);
+        assert_eq!(pt.moves_accepted, vec![0, 0, 0]);
+        assert_eq!(pt.swaps_proposed, vec![0, 0]);
+        assert_eq!(pt.swaps_accepted, vec![0, 0]);
+        let pt = pt.with_swap_interval(5).unwrap();
+        assert_eq!(pt.swap_interval, 5);
+    }
+
+    #[test]
+    fn test_new_errors() {
+        assert_error!(
+            ParallelTempering::<f64, f64, _>::new(vec![1.0]),
+            ArgminError,
+            "Invalid parameter: \"`ParallelTempering`: At least two temperatures are required.\""
+        );
+        assert_error!(
+            ParallelTempering::<f64, f64, _>::new(vec![0.0, 1.0]),
+            ArgminError,
+            "Invalid parameter: \"`ParallelTempering`: Temperatures must be > 0.\""
+        );
+        for temperatures in [vec![1.0, 1.0], vec![1.0, 0.5], vec![1.0, f64::INFINITY]] {
+            assert_error!(
+                ParallelTempering::<f64, f64, _>::new(temperatures),
+                ArgminError,
+                concat!(
+                    "Invalid parameter: \"`ParallelTempering`: Temperatures must be finite and ",
+                    "strictly increasing.\""
+                )
+            );
+        }
+        assert_error!(
+            ParallelTempering::<f64, f64, _>::new(vec![1.0, 2.0])
+                .unwrap()
+                .with_swap_interval(0),
+            ArgminError,
+            "Invalid parameter: \"`ParallelTempering`: Swap interval must be >= 1.\""
+        );
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut pt: ParallelTempering<Vec<f64>, f64, _> =
+            ParallelTempering::new(vec![1.0, 2.0]).unwrap();
+        let res = pt.init(&mut Problem::new(TestProblem::new()), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`ParallelTempering` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_init() {
+        let mut pt: ParallelTempering<Vec<f64>, f64, _> =
+            ParallelTempering::new(vec![1.0, 2.0, 4.0]).unwrap();
+        let mut problem = Problem::new(TestProblem::new());
+        let (state, kv) = pt
+            .init(&mut problem, IterState::new().param(vec![1.0, 2.0]))
+            .unwrap();
+        assert_eq!(pt.replicas, vec![vec![1.0, 2.0]; 3]);
+        assert_eq!(pt.costs.len(), 3);
+        assert_eq!(problem.counts["cost_count"], 1);
+        assert_eq!(state.get_param().unwrap(), &vec![1.0, 2.0]);
+        let kv = kv.unwrap();
+        assert_eq!(kv.get("replicas").unwrap().get_uint(), Some(3));
+        assert_eq!(kv.get("swap_interval").unwrap().get_uint(), Some(1));
+    }
+
+    #[test]
+    fn test_swap() {
+        let mut pt: ParallelTempering<f64, f64, _> =
+            ParallelTempering::new_with_rng(vec![1.0, 2.0, 4.0], StdRng::seed_from_u64(0)).unwrap();
+        // The cold replica has a much higher cost than the hot one: swap is always accepted
+        pt.replicas = vec![1.0, 2.0, 3.0];
+        pt.costs = vec![100.0, 100.0, 0.0];
+        pt.swap();
+        assert_eq!(pt.swaps_proposed, vec![1, 0]);
+        assert_eq!(pt.swaps_accepted, vec![1, 0]);
+        pt.swap();
+        assert_eq!(pt.swaps_proposed, vec![1, 1]);
+        assert_eq!(pt.swaps_accepted, vec![1, 1]);
+        assert_relative_eq!(pt.replicas[1], 3.0, epsilon = f64::EPSILON);
+        assert_relative_eq!(pt.costs[1], 0.0, epsilon = f64::EPSILON);
+        // The cold replica has a much lower cost than the hot one: swap is never accepted
+        pt.replicas = vec![1.0, 2.0, 3.0];
+        pt.costs = vec![0.0, 100.0, 100.0];
+        pt.swap();
+        assert_eq!(pt.swaps_accepted, vec![1, 1]);
+        assert_relative_eq!(pt.replicas[0], 1.0, epsilon = f64::EPSILON);
+    }
+
+    #[test]
+    fn test_double_well() {
+        let temperatures = vec![0.01, 0.05, 0.2, 1.0, 5.0];
+        let pt = ParallelTempering::new_with_rng(temperatures, StdRng::seed_from_u64(42)).unwrap();
+        let res = Executor::new(DoubleWell::new(1), pt)
+            .configure(|state| state.param(1.0).max_iters(500))
+            .run()
+            .unwrap();
+        // The cold chain alone would stay in the local minimum near `x = 1`
+        assert_relative_eq!(
+            *res.state().get_best_param().unwrap(),
+            -1.04,
+            epsilon = 3e-2
+        );
+        assert_eq!(res.problem.counts["anneal_count"], 5 * 500);
+        assert_eq!(res.problem.counts["cost_count"], 1 + 5 * 500);
+    }
+
+    #[test]
+    fn test_rates() {
+        let temperatures = vec![0.01, 0.1, 1.0];
+        let mut pt =
+            ParallelTempering::new_with_rng(temperatures, StdRng::seed_from_u64(42)).unwrap();
+        let mut problem = Problem::new(DoubleWell::new(2));
+        let (mut state, _) = pt.init(&mut problem, IterState::new().param(0.0)).unwrap();
+        let mut kv = None;
+        for _ in 0..100 {
+            let (new_state, new_kv) = pt.next_iter(&mut problem, state).unwrap();
+            state = new_state;
+            state.increment_iter();
+            kv = new_kv;
+        }
+        let kv = kv.unwrap();
+        for k in 0..3 {
+            let rate = kv
+                .get(format!("acceptance_rate_{k}"))
+                .unwrap()
+                .get_float()
+                .unwrap();
+            assert_relative_eq!(
+                rate,
+                pt.moves_accepted[k] as f64 / 100.0,
+                epsilon = f64::EPSILON
+            );
+            assert!(rate > 0.0 && rate <= 1.0);
+        }
+        assert_eq!(pt.swaps_proposed, vec![50, 50]);
+        for k in 0..2 {
+            let rate = kv
+                .get(format!("swap_rate_{k}"))
+                .unwrap()
+                .get_float()
+                .unwrap();
+            assert_relative_eq!(
+                rate,
+                pt.swaps_accepted[k] as f64 / 50.0,
+                epsilon = f64::EPSILON
+            );
+        }
+        assert!(kv.get("swap_rate_2").is_none());
+    }
+}
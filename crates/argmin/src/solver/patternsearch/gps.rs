@@ -0,0 +1,488 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::{best_improvement, Domain};
+use crate::core::{
+    ArgminFloat, CostFunction, Error, IterState, Problem, Solver, State, SyncAlias,
+    TerminationReason, KV,
+};
+use argmin_math::{ArgminGet, ArgminSet, ArgminSize};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Generalized pattern search
+///
+/// Gradient-free direct search method which consists of a search step and a poll step in each
+/// iteration. The search step evaluates the point obtained by repeating the last successful step.
+/// If this does not decrease the cost function, the poll step evaluates the `2n` points obtained by
+/// moving from the current point by the mesh size along the positive and negative coordinate
+/// directions, where `n` is the number of parameters. All poll points are evaluated in one call to
+/// [`Problem::bulk_cost`] and the best one is accepted if it decreases the cost function.
+///
+/// After a successful iteration the mesh size is multiplied by `expansion`, otherwise by
+/// `contraction`.
+///
+/// Optional bounds can be set via [`with_bounds`](`PatternSearch::with_bounds`); the cost function
+/// is never evaluated outside of the bounds and the initial guess is projected onto the bounds.
+///
+/// The method terminates once the mesh size drops below `min_mesh_size`. The mesh size is reported
+/// as KV metric `mesh_size`.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`].
+///
+/// ## Reference
+///
+/// Virginia Torczon (1997). On the convergence of pattern search algorithms. SIAM Journal on
+/// Optimization 7 (1), 1–25. DOI: <https://doi.org/10.1137/S1052623493250780>
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct PatternSearch<P, F> {
+    /// Optional bounds `(lower, upper)`
+    bounds: Option<(P, P)>,
+    /// Initial mesh size
+    initial_mesh_size: F,
+    /// Minimum mesh size
+    min_mesh_size: F,
+    /// Factor by which the mesh size is increased after a successful iteration
+    expansion: F,
+    /// Factor by which the mesh size is reduced after an unsuccessful iteration
+    contraction: F,
+    /// Feasible region
+    domain: Option<Domain<P, F>>,
+    /// Current point
+    x: Vec<F>,
+    /// Cost function value at the current point
+    fx: F,
+    /// Current mesh size
+    mesh_size: F,
+    /// Last successful step
+    last_step: Option<Vec<F>>,
+}
+
+impl<P, F> PatternSearch<P, F>
+where
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `PatternSearch`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::patternsearch::PatternSearch;
+    /// let gps: PatternSearch<Vec<f64>, f64> = PatternSearch::new();
+    /// ```
+    pub fn new() -> Self {
+        PatternSearch {
+            bounds: None,
+            initial_mesh_size: float!(1.0),
+            min_mesh_size: F::epsilon().sqrt(),
+            expansion: float!(2.0),
+            contraction: float!(0.5),
+            domain: None,
+            x: vec![],
+            fx: F::nan(),
+            mesh_size: float!(1.0),
+            last_step: None,
+        }
+    }
+
+    /// Set lower and upper bounds
+    ///
+    /// `lower` and `upper` must have the same number of elements as the parameter vector. Infinite
+    /// bounds are allowed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::patternsearch::PatternSearch;
+    /// let gps: PatternSearch<Vec<f64>, f64> =
+    ///     PatternSearch::new().with_bounds(vec![-1.0, 0.0], vec![1.0, f64::INFINITY]);
+    /// ```
+    #[must_use]
+    pub fn with_bounds(mut self, lower: P, upper: P) -> Self {
+        self.bounds = Some((lower, upper));
+        self
+    }
+
+    /// Set initial mesh size
+    ///
+    /// Must be larger than `0`. Defaults to `1.0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::patternsearch::PatternSearch;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let gps: PatternSearch<Vec<f64>, f64> = PatternSearch::new().with_initial_mesh_size(0.1)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_initial_mesh_size(mut self, mesh_size: F) -> Result<Self, Error> {
+        if mesh_size <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`PatternSearch`: initial mesh size must be > 0."
+            ));
+        }
+        self.initial_mesh_size = mesh_size;
+        Ok(self)
+    }
+
+    /// Set minimum mesh size
+    ///
+    /// The method terminates once the mesh size drops below this value. Must be larger than `0`.
+    /// Defaults to the square root of machine epsilon.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::patternsearch::PatternSearch;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let gps: PatternSearch<Vec<f64>, f64> = PatternSearch::new().with_min_mesh_size(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_min_mesh_size(mut self, mesh_size: F) -> Result<Self, Error> {
+        if mesh_size <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`PatternSearch`: minimum mesh size must be > 0."
+            ));
+        }
+        self.min_mesh_size = mesh_size;
+        Ok(self)
+    }
+
+    /// Set expansion factor
+    ///
+    /// Factor by which the mesh size is increased after a successful iteration. Must be at least
+    /// `1`. Defaults to `2.0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::patternsearch::PatternSearch;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let gps: PatternSearch<Vec<f64>, f64> = PatternSearch::new().with_expansion(1.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_expansion(mut self, expansion: F) -> Result<Self, Error> {
+        if expansion < float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`PatternSearch`: expansion factor must be >= 1."
+            ));
+        }
+        self.expansion = expansion;
+        Ok(self)
+    }
+
+    /// Set contraction factor
+    ///
+    /// Factor by which the mesh size is reduced after an unsuccessful iteration. Must be in
+    /// `(0, 1)`. Defaults to `0.5`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::patternsearch::PatternSearch;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let gps: PatternSearch<Vec<f64>, f64> = PatternSearch::new().with_contraction(0.25)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_contraction(mut self, contraction: F) -> Result<Self, Error> {
+        if contraction <= float!(0.0) || contraction >= float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`PatternSearch`: contraction factor must be in (0, 1)."
+            ));
+        }
+        self.contraction = contraction;
+        Ok(self)
+    }
+}
+
+impl<P, F> Default for PatternSearch<P, F>
+where
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        PatternSearch::new()
+    }
+}
+
+impl<O, P, F> Solver<O, IterState<P, (), (), (), (), F>> for PatternSearch<P, F>
+where
+    O: CostFunction<Param = P, Output = F> + SyncAlias,
+    P: Clone + SyncAlias + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Generalized Pattern Search";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`PatternSearch` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let (domain, x) = Domain::new("PatternSearch", &param, self.bounds.as_ref())?;
+        let param = domain.to_param(&x);
+        self.fx = problem.cost(&param)?;
+        self.x = x;
+        self.domain = Some(domain);
+        self.mesh_size = self.initial_mesh_size;
+        self.last_step = None;
+        Ok((
+            state.param(param).cost(self.fx),
+            Some(kv!("mesh_size" => self.mesh_size;)),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let domain = self.domain.as_ref().unwrap();
+        let n = self.x.len();
+
+        // Search step: repeat the last successful step
+        let mut step = None;
+        if let Some(last_step) = self.last_step.take() {
+            let point: Vec<F> = self
+                .x
+                .iter()
+                .zip(last_step.iter())
+                .map(|(&x, &s)| x + s)
+                .collect();
+            let costs = domain.evaluate(problem, std::slice::from_ref(&point))?;
+            if costs[0] < self.fx {
+                step = Some((last_step, costs[0]));
+            }
+        }
+
+        // Poll step along the positive and negative coordinate directions
+        if step.is_none() {
+            let steps: Vec<Vec<F>> = (0..2 * n)
+                .map(|k| {
+                    let mut s = vec![float!(0.0); n];
+                    s[k % n] = if k < n {
+                        self.mesh_size
+                    } else {
+                        -self.mesh_size
+                    };
+                    s
+                })
+                .collect();
+            let points: Vec<Vec<F>> = steps
+                .iter()
+                .map(|s| self.x.iter().zip(s.iter()).map(|(&x, &s)| x + s).collect())
+                .collect();
+            let costs = domain.evaluate(problem, &points)?;
+            step = best_improvement(&costs, self.fx).map(|(k, cost)| (steps[k].clone(), cost));
+        }
+
+        let success = step.is_some();
+        if let Some((s, cost)) = step {
+            for (x, s) in self.x.iter_mut().zip(s.iter()) {
+                *x = *x + *s;
+            }
+            self.fx = cost;
+            self.last_step = Some(s);
+            self.mesh_size = self.mesh_size * self.expansion;
+        } else {
+            self.mesh_size = self.mesh_size * self.contraction;
+        }
+
+        let state = state.param(domain.to_param(&self.x)).cost(self.fx);
+        let kv = kv!("mesh_size" => self.mesh_size; "success" => success;);
+
+        if self.mesh_size < self.min_mesh_size {
+            Ok((
+                state.terminate_with(TerminationReason::SolverConverged),
+                Some(kv),
+            ))
+        } else {
+            Ok((state, Some(kv)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::patternsearch::tests::{Nonsmooth, Rosenbrock};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(patternsearch, PatternSearch<Vec<f64>, f64>);
+
+    #[test]
+    fn test_new() {
+        let gps: PatternSearch<Vec<f64>, f64> = PatternSearch::new();
+        let PatternSearch {
+            bounds,
+            initial_mesh_size,
+            min_mesh_size,
+            expansion,
+            contraction,
+            domain,
+            x,
+            fx,
+            mesh_size,
+            last_step,
+        } = gps;
+        assert!(bounds.is_none());
+        assert_eq!(initial_mesh_size.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(
+            min_mesh_size.to_ne_bytes(),
+            f64::EPSILON.sqrt().to_ne_bytes()
+        );
+        assert_eq!(expansion.to_ne_bytes(), 2.0f64.to_ne_bytes());
+        assert_eq!(contraction.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert!(domain.is_none());
+        assert!(x.is_empty());
+        assert!(fx.is_nan());
+        assert_eq!(mesh_size.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert!(last_step.is_none());
+    }
+
+    #[test]
+    fn test_builders() {
+        let gps: PatternSearch<Vec<f64>, f64> = PatternSearch::new()
+            .with_bounds(vec![0.0], vec![1.0])
+            .with_initial_mesh_size(0.1)
+            .unwrap()
+            .with_min_mesh_size(1e-4)
+            .unwrap()
+            .with_expansion(1.0)
+            .unwrap()
+            .with_contraction(0.25)
+            .unwrap();
+        assert_eq!(gps.bounds, Some((vec![0.0], vec![1.0])));
+        assert_eq!(gps.initial_mesh_size.to_ne_bytes(), 0.1f64.to_ne_bytes());
+        assert_eq!(gps.min_mesh_size.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+        assert_eq!(gps.expansion.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(gps.contraction.to_ne_bytes(), 0.25f64.to_ne_bytes());
+
+        for val in [0.0, -1.0] {
+            assert_error!(
+                PatternSearch::<Vec<f64>, f64>::new().with_initial_mesh_size(val),
+                ArgminError,
+                "Invalid parameter: \"`PatternSearch`: initial mesh size must be > 0.\""
+            );
+            assert_error!(
+                PatternSearch::<Vec<f64>, f64>::new().with_min_mesh_size(val),
+                ArgminError,
+                "Invalid parameter: \"`PatternSearch`: minimum mesh size must be > 0.\""
+            );
+        }
+        for val in [0.5, 0.0, -1.0] {
+            assert_error!(
+                PatternSearch::<Vec<f64>, f64>::new().with_expansion(val),
+                ArgminError,
+                "Invalid parameter: \"`PatternSearch`: expansion factor must be >= 1.\""
+            );
+        }
+        for val in [0.0, 1.0, -0.5, 1.5] {
+            assert_error!(
+                PatternSearch::<Vec<f64>, f64>::new().with_contraction(val),
+                ArgminError,
+                "Invalid parameter: \"`PatternSearch`: contraction factor must be in (0, 1).\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut gps: PatternSearch<Vec<f64>, f64> = PatternSearch::new();
+        let res = gps.init(&mut Problem::new(Rosenbrock {}), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`PatternSearch` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_poll_counts() {
+        // Every poll step evaluates 2n points, the search step one point
+        let mut problem = Problem::new(Nonsmooth {});
+        let mut gps: PatternSearch<Vec<f64>, f64> = PatternSearch::new();
+        let (state, _) = gps
+            .init(&mut problem, IterState::new().param(vec![0.0, 1.0, 1.0]))
+            .unwrap();
+        assert_eq!(problem.counts["cost_count"], 1);
+        let (state, kv) = gps.next_iter(&mut problem, state).unwrap();
+        assert_eq!(problem.counts["cost_count"], 7);
+        assert_eq!(gps.x, vec![1.0, 1.0, 1.0]);
+        assert_eq!(gps.mesh_size.to_ne_bytes(), 2.0f64.to_ne_bytes());
+        assert_eq!(kv.unwrap().get("success").unwrap().get_bool(), Some(true));
+        let (_, kv) = gps.next_iter(&mut problem, state).unwrap();
+        assert_eq!(problem.counts["cost_count"], 14);
+        assert_eq!(gps.mesh_size.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(kv.unwrap().get("success").unwrap().get_bool(), Some(false));
+    }
+
+    #[test]
+    fn test_rosenbrock() {
+        let res = Executor::new(Rosenbrock {}, PatternSearch::new())
+            .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(100_000))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state().get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        for x in res.state().get_best_param().unwrap() {
+            assert_relative_eq!(*x, 1.0, epsilon = 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_nonsmooth() {
+        let res = Executor::new(Nonsmooth {}, PatternSearch::new())
+            .configure(|state| state.param(vec![-2.3, 4.1, 0.7]).max_iters(10_000))
+            .run()
+            .unwrap();
+        for x in res.state().get_best_param().unwrap() {
+            assert_relative_eq!(*x, 1.0, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_bounds() {
+        let solver = PatternSearch::new().with_bounds(vec![-2.0, -2.0], vec![0.5, 2.0]);
+        let res = Executor::new(Rosenbrock {}, solver)
+            .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(100_000))
+            .run()
+            .unwrap();
+        let param = res.state().get_best_param().unwrap();
+        assert_relative_eq!(param[0], 0.5, epsilon = 1e-6);
+        assert_relative_eq!(param[1], 0.25, epsilon = 1e-4);
+    }
+}
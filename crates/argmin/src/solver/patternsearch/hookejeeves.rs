@@ -0,0 +1,440 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::{best_improvement, Domain};
+use crate::core::{
+    ArgminFloat, CostFunction, Error, IterState, Problem, Solver, State, SyncAlias,
+    TerminationReason, KV,
+};
+use argmin_math::{ArgminGet, ArgminSet, ArgminSize};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Hooke-Jeeves method
+///
+/// Gradient-free direct search method which alternates between exploratory moves and pattern
+/// moves. An exploratory move perturbs the current point along each coordinate in turn by the mesh
+/// size in both directions and keeps a perturbation whenever it decreases the cost function. Both
+/// perturbations of a coordinate are evaluated together via [`Problem::bulk_cost`]. After a
+/// successful exploratory move, the pattern move jumps further along the direction of improvement
+/// and performs an exploratory move around the new point, which is accepted if it improves on the
+/// current point. If neither move is successful, the mesh size is multiplied by `contraction`.
+///
+/// Optional bounds can be set via [`with_bounds`](`HookeJeeves::with_bounds`); the cost function
+/// is never evaluated outside of the bounds and the initial guess is projected onto the bounds.
+///
+/// The method terminates once the mesh size drops below `min_mesh_size`. The mesh size is reported
+/// as KV metric `mesh_size`.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`].
+///
+/// ## Reference
+///
+/// Robert Hooke and T. A. Jeeves (1961). "Direct Search" Solution of Numerical and Statistical
+/// Problems. Journal of the ACM 8 (2), 212–229. DOI: <https://doi.org/10.1145/321062.321069>
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct HookeJeeves<P, F> {
+    /// Optional bounds `(lower, upper)`
+    bounds: Option<(P, P)>,
+    /// Initial mesh size
+    initial_mesh_size: F,
+    /// Minimum mesh size
+    min_mesh_size: F,
+    /// Factor by which the mesh size is reduced after an unsuccessful iteration
+    contraction: F,
+    /// Feasible region
+    domain: Option<Domain<P, F>>,
+    /// Current point
+    x: Vec<F>,
+    /// Cost function value at the current point
+    fx: F,
+    /// Current mesh size
+    mesh_size: F,
+    /// Point before the last successful iteration
+    prev: Option<Vec<F>>,
+}
+
+impl<P, F> HookeJeeves<P, F>
+where
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `HookeJeeves`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::patternsearch::HookeJeeves;
+    /// let hj: HookeJeeves<Vec<f64>, f64> = HookeJeeves::new();
+    /// ```
+    pub fn new() -> Self {
+        HookeJeeves {
+            bounds: None,
+            initial_mesh_size: float!(1.0),
+            min_mesh_size: F::epsilon().sqrt(),
+            contraction: float!(0.5),
+            domain: None,
+            x: vec![],
+            fx: F::nan(),
+            mesh_size: float!(1.0),
+            prev: None,
+        }
+    }
+
+    /// Set lower and upper bounds
+    ///
+    /// `lower` and `upper` must have the same number of elements as the parameter vector. Infinite
+    /// bounds are allowed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::patternsearch::HookeJeeves;
+    /// let hj: HookeJeeves<Vec<f64>, f64> =
+    ///     HookeJeeves::new().with_bounds(vec![-1.0, 0.0], vec![1.0, f64::INFINITY]);
+    /// ```
+    #[must_use]
+    pub fn with_bounds(mut self, lower: P, upper: P) -> Self {
+        self.bounds = Some((lower, upper));
+        self
+    }
+
+    /// Set initial mesh size
+    ///
+    /// Initial step along each coordinate. Must be larger than `0`. Defaults to `1.0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::patternsearch::HookeJeeves;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let hj: HookeJeeves<Vec<f64>, f64> = HookeJeeves::new().with_initial_mesh_size(0.1)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_initial_mesh_size(mut self, mesh_size: F) -> Result<Self, Error> {
+        if mesh_size <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`HookeJeeves`: initial mesh size must be > 0."
+            ));
+        }
+        self.initial_mesh_size = mesh_size;
+        Ok(self)
+    }
+
+    /// Set minimum mesh size
+    ///
+    /// The method terminates once the mesh size drops below this value. Must be larger than `0`.
+    /// Defaults to the square root of machine epsilon.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::patternsearch::HookeJeeves;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let hj: HookeJeeves<Vec<f64>, f64> = HookeJeeves::new().with_min_mesh_size(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_min_mesh_size(mut self, mesh_size: F) -> Result<Self, Error> {
+        if mesh_size <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`HookeJeeves`: minimum mesh size must be > 0."
+            ));
+        }
+        self.min_mesh_size = mesh_size;
+        Ok(self)
+    }
+
+    /// Set contraction factor
+    ///
+    /// Factor by which the mesh size is reduced after an unsuccessful iteration. Must be in
+    /// `(0, 1)`. Defaults to `0.5`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::patternsearch::HookeJeeves;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let hj: HookeJeeves<Vec<f64>, f64> = HookeJeeves::new().with_contraction(0.25)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_contraction(mut self, contraction: F) -> Result<Self, Error> {
+        if contraction <= float!(0.0) || contraction >= float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`HookeJeeves`: contraction factor must be in (0, 1)."
+            ));
+        }
+        self.contraction = contraction;
+        Ok(self)
+    }
+
+    /// Exploratory move around `base` with known cost `f_base`
+    fn explore<O>(
+        &self,
+        problem: &mut Problem<O>,
+        base: Vec<F>,
+        f_base: F,
+    ) -> Result<(Vec<F>, F), Error>
+    where
+        O: CostFunction<Param = P, Output = F> + SyncAlias,
+        P: SyncAlias,
+    {
+        let domain = self.domain.as_ref().unwrap();
+        let (mut y, mut fy) = (base, f_base);
+        for i in 0..y.len() {
+            let mut points = vec![y.clone(), y.clone()];
+            points[0][i] = y[i] + self.mesh_size;
+            points[1][i] = y[i] - self.mesh_size;
+            let costs = domain.evaluate(problem, &points)?;
+            if let Some((best, cost)) = best_improvement(&costs, fy) {
+                y = points.swap_remove(best);
+                fy = cost;
+            }
+        }
+        Ok((y, fy))
+    }
+}
+
+impl<P, F> Default for HookeJeeves<P, F>
+where
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        HookeJeeves::new()
+    }
+}
+
+impl<O, P, F> Solver<O, IterState<P, (), (), (), (), F>> for HookeJeeves<P, F>
+where
+    O: CostFunction<Param = P, Output = F> + SyncAlias,
+    P: Clone + SyncAlias + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Hooke-Jeeves";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`HookeJeeves` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let (domain, x) = Domain::new("HookeJeeves", &param, self.bounds.as_ref())?;
+        let param = domain.to_param(&x);
+        self.fx = problem.cost(&param)?;
+        self.x = x;
+        self.domain = Some(domain);
+        self.mesh_size = self.initial_mesh_size;
+        self.prev = None;
+        Ok((
+            state.param(param).cost(self.fx),
+            Some(kv!("mesh_size" => self.mesh_size;)),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let mut success = false;
+
+        // Pattern move followed by an exploratory move around the new point
+        if let Some(prev) = self.prev.take() {
+            let z: Vec<F> = self
+                .x
+                .iter()
+                .zip(prev.iter())
+                .map(|(&x, &p)| x + x - p)
+                .collect();
+            if self.domain.as_ref().unwrap().contains(&z) {
+                let fz = self
+                    .domain
+                    .as_ref()
+                    .unwrap()
+                    .evaluate(problem, std::slice::from_ref(&z))?[0];
+                let (y, fy) = self.explore(problem, z, fz)?;
+                if fy < self.fx {
+                    self.prev = Some(std::mem::replace(&mut self.x, y));
+                    self.fx = fy;
+                    success = true;
+                }
+            }
+        }
+
+        // Exploratory move around the current point
+        if !success {
+            let (y, fy) = self.explore(problem, self.x.clone(), self.fx)?;
+            if fy < self.fx {
+                self.prev = Some(std::mem::replace(&mut self.x, y));
+                self.fx = fy;
+                success = true;
+            } else {
+                self.mesh_size = self.mesh_size * self.contraction;
+            }
+        }
+
+        let state = state
+            .param(self.domain.as_ref().unwrap().to_param(&self.x))
+            .cost(self.fx);
+        let kv = kv!("mesh_size" => self.mesh_size; "success" => success;);
+
+        if self.mesh_size < self.min_mesh_size {
+            Ok((
+                state.terminate_with(TerminationReason::SolverConverged),
+                Some(kv),
+            ))
+        } else {
+            Ok((state, Some(kv)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::patternsearch::tests::{Nonsmooth, Rosenbrock};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(hookejeeves, HookeJeeves<Vec<f64>, f64>);
+
+    #[test]
+    fn test_new() {
+        let hj: HookeJeeves<Vec<f64>, f64> = HookeJeeves::new();
+        let HookeJeeves {
+            bounds,
+            initial_mesh_size,
+            min_mesh_size,
+            contraction,
+            domain,
+            x,
+            fx,
+            mesh_size,
+            prev,
+        } = hj;
+        assert!(bounds.is_none());
+        assert_eq!(initial_mesh_size.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(
+            min_mesh_size.to_ne_bytes(),
+            f64::EPSILON.sqrt().to_ne_bytes()
+        );
+        assert_eq!(contraction.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert!(domain.is_none());
+        assert!(x.is_empty());
+        assert!(fx.is_nan());
+        assert_eq!(mesh_size.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert!(prev.is_none());
+    }
+
+    #[test]
+    fn test_builders() {
+        let hj: HookeJeeves<Vec<f64>, f64> = HookeJeeves::new()
+            .with_bounds(vec![0.0], vec![1.0])
+            .with_initial_mesh_size(0.1)
+            .unwrap()
+            .with_min_mesh_size(1e-4)
+            .unwrap()
+            .with_contraction(0.25)
+            .unwrap();
+        assert_eq!(hj.bounds, Some((vec![0.0], vec![1.0])));
+        assert_eq!(hj.initial_mesh_size.to_ne_bytes(), 0.1f64.to_ne_bytes());
+        assert_eq!(hj.min_mesh_size.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+        assert_eq!(hj.contraction.to_ne_bytes(), 0.25f64.to_ne_bytes());
+
+        for val in [0.0, -1.0] {
+            assert_error!(
+                HookeJeeves::<Vec<f64>, f64>::new().with_initial_mesh_size(val),
+                ArgminError,
+                "Invalid parameter: \"`HookeJeeves`: initial mesh size must be > 0.\""
+            );
+            assert_error!(
+                HookeJeeves::<Vec<f64>, f64>::new().with_min_mesh_size(val),
+                ArgminError,
+                "Invalid parameter: \"`HookeJeeves`: minimum mesh size must be > 0.\""
+            );
+        }
+        for val in [0.0, 1.0, -0.5, 1.5] {
+            assert_error!(
+                HookeJeeves::<Vec<f64>, f64>::new().with_contraction(val),
+                ArgminError,
+                "Invalid parameter: \"`HookeJeeves`: contraction factor must be in (0, 1).\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut hj: HookeJeeves<Vec<f64>, f64> = HookeJeeves::new();
+        let res = hj.init(&mut Problem::new(Rosenbrock {}), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`HookeJeeves` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_rosenbrock() {
+        let res = Executor::new(Rosenbrock {}, HookeJeeves::new())
+            .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(100_000))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state().get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        for x in res.state().get_best_param().unwrap() {
+            assert_relative_eq!(*x, 1.0, epsilon = 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_nonsmooth() {
+        let res = Executor::new(Nonsmooth {}, HookeJeeves::new())
+            .configure(|state| state.param(vec![-2.3, 4.1, 0.7]).max_iters(10_000))
+            .run()
+            .unwrap();
+        for x in res.state().get_best_param().unwrap() {
+            assert_relative_eq!(*x, 1.0, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_bounds() {
+        // Minimum of the Rosenbrock function on [-2, 0.5] x [-2, 2] is at (0.5, 0.25)
+        let solver = HookeJeeves::new().with_bounds(vec![-2.0, -2.0], vec![0.5, 2.0]);
+        let res = Executor::new(Rosenbrock {}, solver)
+            .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(100_000))
+            .run()
+            .unwrap();
+        let param = res.state().get_best_param().unwrap();
+        assert_relative_eq!(param[0], 0.5, epsilon = 1e-6);
+        assert_relative_eq!(param[1], 0.25, epsilon = 1e-4);
+    }
+}
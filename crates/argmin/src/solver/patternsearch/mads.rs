@@ -0,0 +1,583 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::{best_improvement, Domain};
+use crate::core::{
+    ArgminFloat, CostFunction, Error, IterState, Problem, Solver, State, SyncAlias,
+    TerminationReason, KV,
+};
+use crate::solver::random::standard_normal;
+use argmin_math::{ArgminGet, ArgminSet, ArgminSize};
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of attempts to draw a nonsingular set of poll directions
+const MAX_DIRECTION_ATTEMPTS: usize = 10;
+
+/// # Mesh adaptive direct search
+///
+/// Gradient-free direct search method which, like [generalized pattern
+/// search](`super::PatternSearch`), consists of a search step and a poll step in each iteration,
+/// but decouples the mesh size `delta` from the poll size `Delta`. The mesh size decreases faster
+/// than the poll size (`delta = min(Delta, Delta^2)`), such that the poll directions become dense
+/// in the unit sphere, which guarantees convergence to a stationary point also for non-smooth cost
+/// functions.
+///
+/// The search step evaluates the point obtained by repeating the last successful step, rounded to
+/// the current mesh. The poll step uses the OrthoMADS construction: a random unit vector `v`
+/// defines the orthogonal Householder matrix `H = I - 2 v v^T`, whose columns are scaled to a
+/// length of about `Delta / delta` in the infinity norm and rounded to integers. The `2n` poll
+/// points are obtained by moving from the current point by `delta` times the positive and negative
+/// rounded columns and are evaluated in one call to [`Problem::bulk_cost`]. The best poll point is
+/// accepted if it decreases the cost function.
+///
+/// After a successful iteration the poll size is doubled, otherwise it is halved.
+///
+/// Optional bounds can be set via [`with_bounds`](`MADS::with_bounds`); the cost function is never
+/// evaluated outside of the bounds (extreme barrier) and the initial guess is projected onto the
+/// bounds.
+///
+/// The method terminates once the mesh size drops below `min_mesh_size`. The mesh size and the poll
+/// size are reported as KV metrics `mesh_size` and `poll_size`.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`].
+///
+/// ## References
+///
+/// Charles Audet and J. E. Dennis Jr. (2006). Mesh adaptive direct search algorithms for
+/// constrained optimization. SIAM Journal on Optimization 17 (1), 188–217.
+/// DOI: <https://doi.org/10.1137/040603371>
+///
+/// Mark A. Abramson, Charles Audet, J. E. Dennis Jr. and Sébastien Le Digabel (2009). OrthoMADS:
+/// A deterministic MADS instance with orthogonal directions. SIAM Journal on Optimization 20 (2),
+/// 948–966. DOI: <https://doi.org/10.1137/080716980>
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct MADS<P, F, R> {
+    /// Optional bounds `(lower, upper)`
+    bounds: Option<(P, P)>,
+    /// Initial poll size
+    initial_poll_size: F,
+    /// Minimum mesh size
+    min_mesh_size: F,
+    /// Random number generator
+    rng_generator: R,
+    /// Feasible region
+    domain: Option<Domain<P, F>>,
+    /// Current point
+    x: Vec<F>,
+    /// Cost function value at the current point
+    fx: F,
+    /// Current poll size
+    poll_size: F,
+    /// Last successful step
+    last_step: Option<Vec<F>>,
+}
+
+impl<P, F> MADS<P, F, rand::rngs::StdRng>
+where
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `MADS`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::patternsearch::MADS;
+    /// let mads: MADS<Vec<f64>, f64, _> = MADS::new();
+    /// ```
+    pub fn new() -> Self {
+        MADS {
+            bounds: None,
+            initial_poll_size: float!(1.0),
+            min_mesh_size: F::epsilon(),
+            rng_generator: rand::rngs::StdRng::from_entropy(),
+            domain: None,
+            x: vec![],
+            fx: F::nan(),
+            poll_size: float!(1.0),
+            last_step: None,
+        }
+    }
+}
+
+impl<P, F> Default for MADS<P, F, rand::rngs::StdRng>
+where
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        MADS::new()
+    }
+}
+
+impl<P, F, R0> MADS<P, F, R0>
+where
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+    R0: Rng,
+{
+    /// Set random number generator
+    ///
+    /// Defaults to `rand::rngs::StdRng::from_entropy()`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::patternsearch::MADS;
+    /// # use rand::SeedableRng;
+    /// let mads: MADS<Vec<f64>, f64, _> = MADS::new()
+    ///     .with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(1729));
+    /// ```
+    pub fn with_rng_generator<R1: Rng>(self, generator: R1) -> MADS<P, F, R1> {
+        MADS {
+            bounds: self.bounds,
+            initial_poll_size: self.initial_poll_size,
+            min_mesh_size: self.min_mesh_size,
+            rng_generator: generator,
+            domain: self.domain,
+            x: self.x,
+            fx: self.fx,
+            poll_size: self.poll_size,
+            last_step: self.last_step,
+        }
+    }
+}
+
+impl<P, F, R> MADS<P, F, R>
+where
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+    R: Rng,
+{
+    /// Set lower and upper bounds
+    ///
+    /// `lower` and `upper` must have the same number of elements as the parameter vector. Infinite
+    /// bounds are allowed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::patternsearch::MADS;
+    /// let mads: MADS<Vec<f64>, f64, _> =
+    ///     MADS::new().with_bounds(vec![-1.0, 0.0], vec![1.0, f64::INFINITY]);
+    /// ```
+    #[must_use]
+    pub fn with_bounds(mut self, lower: P, upper: P) -> Self {
+        self.bounds = Some((lower, upper));
+        self
+    }
+
+    /// Set initial poll size
+    ///
+    /// Must be larger than `0`. Defaults to `1.0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::patternsearch::MADS;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let mads: MADS<Vec<f64>, f64, _> = MADS::new().with_initial_poll_size(0.1)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_initial_poll_size(mut self, poll_size: F) -> Result<Self, Error> {
+        if poll_size <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`MADS`: initial poll size must be > 0."
+            ));
+        }
+        self.initial_poll_size = poll_size;
+        Ok(self)
+    }
+
+    /// Set minimum mesh size
+    ///
+    /// The method terminates once the mesh size drops below this value. Must be larger than `0`.
+    /// Defaults to machine epsilon, which corresponds to a poll size of about the square root of
+    /// machine epsilon.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::patternsearch::MADS;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let mads: MADS<Vec<f64>, f64, _> = MADS::new().with_min_mesh_size(1e-12)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_min_mesh_size(mut self, mesh_size: F) -> Result<Self, Error> {
+        if mesh_size <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`MADS`: minimum mesh size must be > 0."
+            ));
+        }
+        self.min_mesh_size = mesh_size;
+        Ok(self)
+    }
+
+    /// Mesh size corresponding to the current poll size
+    fn mesh_size(&self) -> F {
+        self.poll_size.min(self.poll_size * self.poll_size)
+    }
+
+    /// Returns `n` linearly independent integer directions with an infinity norm of about
+    /// `poll_size / mesh_size`. The poll directions are these directions and their negatives.
+    fn poll_directions(&mut self, n: usize) -> Vec<Vec<F>> {
+        let ratio = self.poll_size / self.mesh_size();
+        for _ in 0..MAX_DIRECTION_ATTEMPTS {
+            let v: Vec<F> = (0..n)
+                .map(|_| standard_normal(&mut self.rng_generator))
+                .collect();
+            let norm = v.iter().fold(float!(0.0), |acc, &v| acc + v * v).sqrt();
+            if norm <= float!(0.0) {
+                continue;
+            }
+            let v: Vec<F> = v.iter().map(|&v| v / norm).collect();
+            // Columns of the Householder matrix I - 2 v v^T
+            let directions: Vec<Vec<F>> = (0..n)
+                .map(|j| {
+                    let h: Vec<F> = (0..n)
+                        .map(|i| {
+                            let e = if i == j { float!(1.0) } else { float!(0.0) };
+                            e - float!(2.0) * v[i] * v[j]
+                        })
+                        .collect();
+                    let h_max = h.iter().fold(float!(0.0), |acc: F, &h| acc.max(h.abs()));
+                    h.iter().map(|&h| (ratio * h / h_max).round()).collect()
+                })
+                .collect();
+            if is_basis(&directions) {
+                return directions;
+            }
+        }
+        // Fall back to the coordinate directions
+        let length = ratio.round().max(float!(1.0));
+        (0..n)
+            .map(|j| {
+                let mut d = vec![float!(0.0); n];
+                d[j] = length;
+                d
+            })
+            .collect()
+    }
+}
+
+/// Returns `true` if the vectors are linearly independent, which is checked by Gaussian
+/// elimination of the normalized vectors.
+fn is_basis<F: ArgminFloat>(vectors: &[Vec<F>]) -> bool {
+    let n = vectors.len();
+    let mut a: Vec<Vec<F>> = vectors
+        .iter()
+        .map(|v| {
+            let norm = v.iter().fold(float!(0.0), |acc: F, &x| acc + x * x).sqrt();
+            v.iter().map(|&x| x / norm).collect()
+        })
+        .collect();
+    let mut det = float!(1.0);
+    for k in 0..n {
+        let pivot = (k..n)
+            .max_by(|&i, &j| {
+                a[i][k]
+                    .abs()
+                    .partial_cmp(&a[j][k].abs())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .unwrap();
+        a.swap(k, pivot);
+        let p = a[k][k];
+        if p == float!(0.0) || p.is_nan() {
+            return false;
+        }
+        det = det * p;
+        let (upper, lower) = a.split_at_mut(k + 1);
+        for row in lower.iter_mut() {
+            let factor = row[k] / p;
+            for (x, &y) in row.iter_mut().zip(upper[k].iter()).skip(k) {
+                *x = *x - factor * y;
+            }
+        }
+    }
+    det.abs() > F::epsilon().sqrt()
+}
+
+impl<O, P, F, R> Solver<O, IterState<P, (), (), (), (), F>> for MADS<P, F, R>
+where
+    O: CostFunction<Param = P, Output = F> + SyncAlias,
+    P: Clone + SyncAlias + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+    R: Rng,
+{
+    const NAME: &'static str = "MADS";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`MADS` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let (domain, x) = Domain::new("MADS", &param, self.bounds.as_ref())?;
+        let param = domain.to_param(&x);
+        self.fx = problem.cost(&param)?;
+        self.x = x;
+        self.domain = Some(domain);
+        self.poll_size = self.initial_poll_size;
+        self.last_step = None;
+        Ok((
+            state.param(param).cost(self.fx),
+            Some(kv!("mesh_size" => self.mesh_size(); "poll_size" => self.poll_size;)),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let n = self.x.len();
+        let mesh_size = self.mesh_size();
+
+        // Search step: repeat the last successful step, rounded to the current mesh
+        let mut step = None;
+        if let Some(last_step) = self.last_step.take() {
+            let s: Vec<F> = last_step
+                .iter()
+                .map(|&s| (s / mesh_size).round() * mesh_size)
+                .collect();
+            if s.iter().any(|&s| s != float!(0.0)) {
+                let point: Vec<F> = self.x.iter().zip(s.iter()).map(|(&x, &s)| x + s).collect();
+                let costs = self
+                    .domain
+                    .as_ref()
+                    .unwrap()
+                    .evaluate(problem, std::slice::from_ref(&point))?;
+                if costs[0] < self.fx {
+                    step = Some((s, costs[0]));
+                }
+            }
+        }
+
+        // Poll step along the positive and negative OrthoMADS directions
+        if step.is_none() {
+            let directions = self.poll_directions(n);
+            let steps: Vec<Vec<F>> = directions
+                .iter()
+                .map(|d| d.iter().map(|&d| d * mesh_size).collect())
+                .chain(
+                    directions
+                        .iter()
+                        .map(|d| d.iter().map(|&d| -d * mesh_size).collect()),
+                )
+                .collect();
+            let points: Vec<Vec<F>> = steps
+                .iter()
+                .map(|s| self.x.iter().zip(s.iter()).map(|(&x, &s)| x + s).collect())
+                .collect();
+            let costs = self.domain.as_ref().unwrap().evaluate(problem, &points)?;
+            step = best_improvement(&costs, self.fx).map(|(k, cost)| (steps[k].clone(), cost));
+        }
+
+        let success = step.is_some();
+        if let Some((s, cost)) = step {
+            for (x, s) in self.x.iter_mut().zip(s.iter()) {
+                *x = *x + *s;
+            }
+            self.fx = cost;
+            self.last_step = Some(s);
+            self.poll_size = self.poll_size * float!(2.0);
+        } else {
+            self.poll_size = self.poll_size * float!(0.5);
+        }
+
+        let state = state
+            .param(self.domain.as_ref().unwrap().to_param(&self.x))
+            .cost(self.fx);
+        let kv = kv!(
+            "mesh_size" => self.mesh_size();
+            "poll_size" => self.poll_size;
+            "success" => success;
+        );
+
+        if self.mesh_size() < self.min_mesh_size {
+            Ok((
+                state.terminate_with(TerminationReason::SolverConverged),
+                Some(kv),
+            ))
+        } else {
+            Ok((state, Some(kv)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::patternsearch::tests::{Nonsmooth, Rosenbrock};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(mads, MADS<Vec<f64>, f64, rand::rngs::StdRng>);
+
+    fn seeded() -> MADS<Vec<f64>, f64, rand_xoshiro::Xoroshiro128Plus> {
+        MADS::new().with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(42))
+    }
+
+    #[test]
+    fn test_new() {
+        let mads: MADS<Vec<f64>, f64, _> = MADS::new();
+        let MADS {
+            bounds,
+            initial_poll_size,
+            min_mesh_size,
+            rng_generator: _,
+            domain,
+            x,
+            fx,
+            poll_size,
+            last_step,
+        } = mads;
+        assert!(bounds.is_none());
+        assert_eq!(initial_poll_size.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(min_mesh_size.to_ne_bytes(), f64::EPSILON.to_ne_bytes());
+        assert!(domain.is_none());
+        assert!(x.is_empty());
+        assert!(fx.is_nan());
+        assert_eq!(poll_size.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert!(last_step.is_none());
+    }
+
+    #[test]
+    fn test_builders() {
+        let mads = seeded()
+            .with_bounds(vec![0.0], vec![1.0])
+            .with_initial_poll_size(0.1)
+            .unwrap()
+            .with_min_mesh_size(1e-4)
+            .unwrap();
+        assert_eq!(mads.bounds, Some((vec![0.0], vec![1.0])));
+        assert_eq!(mads.initial_poll_size.to_ne_bytes(), 0.1f64.to_ne_bytes());
+        assert_eq!(mads.min_mesh_size.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+
+        for val in [0.0, -1.0] {
+            assert_error!(
+                seeded().with_initial_poll_size(val),
+                ArgminError,
+                "Invalid parameter: \"`MADS`: initial poll size must be > 0.\""
+            );
+            assert_error!(
+                seeded().with_min_mesh_size(val),
+                ArgminError,
+                "Invalid parameter: \"`MADS`: minimum mesh size must be > 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut mads = seeded();
+        let res = mads.init(&mut Problem::new(Rosenbrock {}), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`MADS` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_mesh_size() {
+        let mut mads = seeded();
+        for (poll_size, mesh_size) in [(4.0f64, 4.0f64), (1.0, 1.0), (0.5, 0.25), (0.125, 0.015625)]
+        {
+            mads.poll_size = poll_size;
+            assert_eq!(mads.mesh_size().to_ne_bytes(), mesh_size.to_ne_bytes());
+        }
+    }
+
+    #[test]
+    fn test_poll_directions() {
+        let mut mads = seeded();
+        for poll_size in [2.0, 1.0, 0.5, 0.01] {
+            mads.poll_size = poll_size;
+            let ratio = poll_size / mads.mesh_size();
+            for n in 1..6 {
+                let directions = mads.poll_directions(n);
+                assert_eq!(directions.len(), n);
+                assert!(is_basis(&directions));
+                for d in directions {
+                    // Integer entries with infinity norm equal to the rounded ratio
+                    assert!(d.iter().all(|x| x.fract() == 0.0));
+                    let max = d.iter().fold(0.0f64, |acc, x| acc.max(x.abs()));
+                    assert_relative_eq!(max, ratio.round());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_basis() {
+        assert!(is_basis(&[vec![1.0, 0.0], vec![1.0, 1.0]]));
+        assert!(!is_basis(&[vec![1.0, 1.0], vec![-1.0, -1.0]]));
+        assert!(!is_basis(&[vec![0.0, 0.0], vec![1.0, 0.0]]));
+    }
+
+    #[test]
+    fn test_rosenbrock() {
+        let res = Executor::new(Rosenbrock {}, seeded())
+            .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(100_000))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state().get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        for x in res.state().get_best_param().unwrap() {
+            assert_relative_eq!(*x, 1.0, epsilon = 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_nonsmooth() {
+        let res = Executor::new(Nonsmooth {}, seeded())
+            .configure(|state| state.param(vec![-2.3, 4.1, 0.7]).max_iters(10_000))
+            .run()
+            .unwrap();
+        for x in res.state().get_best_param().unwrap() {
+            assert_relative_eq!(*x, 1.0, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_bounds() {
+        let solver = seeded().with_bounds(vec![-2.0, -2.0], vec![0.5, 2.0]);
+        let res = Executor::new(Rosenbrock {}, solver)
+            .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(100_000))
+            .run()
+            .unwrap();
+        // Poll directions along the active bound are rare, which limits the accuracy along it
+        let param = res.state().get_best_param().unwrap();
+        assert_relative_eq!(param[0], 0.5, epsilon = 1e-6);
+        assert_relative_eq!(param[1], 0.25, epsilon = 1e-3);
+    }
+}
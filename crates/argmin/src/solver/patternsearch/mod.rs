@@ -0,0 +1,264 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Pattern search methods
+//!
+//! Gradient-free direct search methods which evaluate the cost function on a mesh around the
+//! current point and refine the mesh whenever no better point is found. They only compare cost
+//! function values and are therefore suited for noisy or non-smooth cost functions.
+//!
+//! * [Hooke-Jeeves method](`HookeJeeves`)
+//! * [Generalized pattern search](`PatternSearch`) (GPS)
+//! * [Mesh adaptive direct search](`MADS`) (MADS)
+//!
+//! All methods support optional bound constraints, which are handled by an extreme barrier: points
+//! outside of the bounds are rejected without evaluating the cost function. The points of each
+//! step are evaluated via [`Problem::bulk_cost`], therefore the `rayon` feature enables their
+//! parallel evaluation. The mesh size is reported as KV metric `mesh_size`.
+//!
+//! ## References
+//!
+//! \[0\] Robert Hooke and T. A. Jeeves (1961). "Direct Search" Solution of Numerical and
+//! Statistical Problems. Journal of the ACM 8 (2), 212–229.
+//! DOI: <https://doi.org/10.1145/321062.321069>
+//!
+//! \[1\] Virginia Torczon (1997). On the convergence of pattern search algorithms. SIAM Journal on
+//! Optimization 7 (1), 1–25. DOI: <https://doi.org/10.1137/S1052623493250780>
+//!
+//! \[2\] Charles Audet and J. E. Dennis Jr. (2006). Mesh adaptive direct search algorithms for
+//! constrained optimization. SIAM Journal on Optimization 17 (1), 188–217.
+//! DOI: <https://doi.org/10.1137/040603371>
+//!
+//! \[3\] Charles Audet and Warren Hare (2017). Derivative-Free and Blackbox Optimization.
+//! Springer. DOI: <https://doi.org/10.1007/978-3-319-68913-5>
+
+mod gps;
+mod hookejeeves;
+mod mads;
+
+pub use self::gps::*;
+pub use self::hookejeeves::*;
+pub use self::mads::*;
+
+use crate::core::{ArgminFloat, CostFunction, Error, Problem, SyncAlias};
+use argmin_math::{ArgminGet, ArgminSet, ArgminSize};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Feasible region of the optimization problem, which also converts between internal vectors and
+/// parameter vectors
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+struct Domain<P, F> {
+    /// Template used to convert internal vectors into parameter vectors
+    template: P,
+    /// Lower bounds
+    lower: Vec<F>,
+    /// Upper bounds
+    upper: Vec<F>,
+}
+
+impl<P, F> Domain<P, F>
+where
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+{
+    /// Sets up the domain from the initial parameter vector and the optional bounds. Returns the
+    /// domain and the initial point projected onto the bounds.
+    fn new(name: &str, param: &P, bounds: Option<&(P, P)>) -> Result<(Self, Vec<F>), Error> {
+        let n = param.shape();
+        if n < 1 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!("`{name}`: parameter vector must have at least one element.")
+            ));
+        }
+        let (lower, upper): (Vec<F>, Vec<F>) = match bounds {
+            Some((lower, upper)) => {
+                if lower.shape() != n || upper.shape() != n {
+                    return Err(argmin_error!(
+                        InvalidParameter,
+                        format!(
+                            "`{name}`: bounds must have the same length as the parameter vector."
+                        )
+                    ));
+                }
+                (
+                    (0..n).map(|i| lower.get(i)).collect(),
+                    (0..n).map(|i| upper.get(i)).collect(),
+                )
+            }
+            None => (vec![F::neg_infinity(); n], vec![F::infinity(); n]),
+        };
+        if lower.iter().zip(upper.iter()).any(|(l, u)| l > u) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!("`{name}`: lower bounds must not exceed upper bounds.")
+            ));
+        }
+        let x = (0..n)
+            .map(|i| param.get(i).max(lower[i]).min(upper[i]))
+            .collect();
+        let domain = Domain {
+            template: param.clone(),
+            lower,
+            upper,
+        };
+        Ok((domain, x))
+    }
+
+    /// Returns `true` if `x` satisfies the bounds
+    fn contains(&self, x: &[F]) -> bool {
+        x.iter()
+            .zip(self.lower.iter().zip(self.upper.iter()))
+            .all(|(&x, (&l, &u))| x >= l && x <= u)
+    }
+
+    /// Converts an internal vector into a parameter vector
+    fn to_param(&self, x: &[F]) -> P {
+        let mut param = self.template.clone();
+        for (i, &xi) in x.iter().enumerate() {
+            param.set(i, xi);
+        }
+        param
+    }
+
+    /// Evaluates the cost function at all feasible `points` in one call to
+    /// [`Problem::bulk_cost`]. Points outside of the bounds are assigned an infinite cost.
+    fn evaluate<O>(&self, problem: &mut Problem<O>, points: &[Vec<F>]) -> Result<Vec<F>, Error>
+    where
+        O: CostFunction<Param = P, Output = F> + SyncAlias,
+        P: SyncAlias,
+    {
+        let feasible: Vec<bool> = points.iter().map(|x| self.contains(x)).collect();
+        let params: Vec<P> = points
+            .iter()
+            .zip(feasible.iter())
+            .filter(|(_, &f)| f)
+            .map(|(x, _)| self.to_param(x))
+            .collect();
+        let mut costs = if params.is_empty() {
+            vec![]
+        } else {
+            problem.bulk_cost(&params)?
+        }
+        .into_iter();
+        Ok(feasible
+            .into_iter()
+            .map(|f| {
+                if f {
+                    costs.next().unwrap()
+                } else {
+                    F::infinity()
+                }
+            })
+            .collect())
+    }
+}
+
+/// Returns the index and the cost of the best point if it improves on `fx`
+fn best_improvement<F: ArgminFloat>(costs: &[F], fx: F) -> Option<(usize, F)> {
+    costs.iter().enumerate().filter(|(_, &c)| c < fx).fold(
+        None,
+        |best: Option<(usize, F)>, (i, &c)| match best {
+            Some((_, b)) if b <= c => best,
+            _ => Some((i, c)),
+        },
+    )
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+
+    pub(super) struct Rosenbrock {}
+
+    impl CostFunction for Rosenbrock {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(argmin_testfunctions::rosenbrock(p, 1.0, 100.0))
+        }
+    }
+
+    /// f(x) = sum_i |x_i - 1| + (x_0 - 1)^2, which is not differentiable at the minimum
+    pub(super) struct Nonsmooth {}
+
+    impl CostFunction for Nonsmooth {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(p.iter().map(|x| (x - 1.0).abs()).sum::<f64>() + (p[0] - 1.0).powi(2))
+        }
+    }
+
+    #[test]
+    fn test_domain() {
+        let (domain, x) = Domain::<Vec<f64>, f64>::new(
+            "Test",
+            &vec![-3.0, 0.5, 3.0],
+            Some(&(vec![-1.0; 3], vec![1.0; 3])),
+        )
+        .unwrap();
+        assert_eq!(x, vec![-1.0, 0.5, 1.0]);
+        assert!(domain.contains(&x));
+        assert!(!domain.contains(&[0.0, 0.0, 1.5]));
+
+        let mut problem = Problem::new(Rosenbrock {});
+        let costs = domain
+            .evaluate(
+                &mut problem,
+                &[
+                    vec![1.0, 1.0, 1.0],
+                    vec![2.0, 0.0, 0.0],
+                    vec![0.0, 0.0, 0.0],
+                ],
+            )
+            .unwrap();
+        assert_eq!(costs[0].to_ne_bytes(), 0.0f64.to_ne_bytes());
+        assert!(costs[1].is_infinite());
+        assert_eq!(costs[2].to_ne_bytes(), 2.0f64.to_ne_bytes());
+        assert_eq!(problem.counts["cost_count"], 2);
+
+        let (domain, x) = Domain::<Vec<f64>, f64>::new("Test", &vec![5.0], None).unwrap();
+        assert_eq!(x, vec![5.0]);
+        assert!(domain.contains(&[1e300]));
+    }
+
+    #[test]
+    fn test_domain_errors() {
+        assert_error!(
+            Domain::<Vec<f64>, f64>::new("Test", &vec![], None),
+            ArgminError,
+            "Invalid parameter: \"`Test`: parameter vector must have at least one element.\""
+        );
+        assert_error!(
+            Domain::<Vec<f64>, f64>::new("Test", &vec![0.0; 2], Some(&(vec![0.0], vec![1.0; 2]))),
+            ArgminError,
+            "Invalid parameter: \"`Test`: bounds must have the same length as the parameter vector.\""
+        );
+        assert_error!(
+            Domain::<Vec<f64>, f64>::new(
+                "Test",
+                &vec![0.0; 2],
+                Some(&(vec![0.0, 1.0], vec![1.0, 0.0]))
+            ),
+            ArgminError,
+            "Invalid parameter: \"`Test`: lower bounds must not exceed upper bounds.\""
+        );
+    }
+
+    #[test]
+    fn test_best_improvement() {
+        assert_eq!(best_improvement(&[3.0, 1.0, 2.0, 1.0], 2.5), Some((1, 1.0)));
+        assert_eq!(best_improvement(&[3.0, f64::INFINITY], 2.5), None);
+        assert_eq!(best_improvement::<f64>(&[], 2.5), None);
+    }
+}
@@ -0,0 +1,291 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Direction set methods
+//!
+//! Gradient-free local methods which minimize the cost function by successive one-dimensional
+//! minimizations along a set of directions that is adapted during the optimization.
+//!
+//! * [Powell's conjugate direction method](`Powell`)
+//! * [Brent's principal axis method](`Praxis`) (PRAXIS)
+//!
+//! The one-dimensional minimizations are performed by [`BrentOpt`] after bracketing a minimum
+//! along the current direction.
+//!
+//! ## References
+//!
+//! \[0\] M. J. D. Powell (1964). An efficient method for finding the minimum of a function of
+//! several variables without calculating derivatives. The Computer Journal 7 (2), 155–162.
+//! DOI: <https://doi.org/10.1093/comjnl/7.2.155>
+//!
+//! \[1\] Richard P. Brent (1973). Algorithms for minimization without derivatives. Prentice-Hall,
+//! chapter 7.
+
+mod powell_method;
+mod praxis;
+
+pub use self::powell_method::*;
+pub use self::praxis::*;
+
+use crate::core::{ArgminFloat, CostFunction, Error, Executor, OptimizationResult, Problem, State};
+use crate::solver::brent::BrentOpt;
+use crate::solver::dense::to_param;
+use argmin_math::ArgminSet;
+
+/// Maximum number of iterations of each one-dimensional minimization
+const LINE_MAX_ITERS: u64 = 100;
+
+/// Maximum number of expansions of the bracketing interval
+const BRACKET_MAX_EXPANSIONS: usize = 50;
+
+/// Cost function restricted to the line `x + t * direction`
+struct LineProblem<O, P, F> {
+    /// Wrapped problem
+    problem: O,
+    /// Template used to convert internal vectors into parameter vectors
+    template: P,
+    /// Starting point
+    x: Vec<F>,
+    /// Direction
+    direction: Vec<F>,
+}
+
+impl<O, P, F> CostFunction for LineProblem<O, P, F>
+where
+    O: CostFunction<Param = P, Output = F>,
+    P: Clone + ArgminSet<usize, F>,
+    F: ArgminFloat,
+{
+    type Param = F;
+    type Output = F;
+
+    fn cost(&self, t: &F) -> Result<F, Error> {
+        let point: Vec<F> = self
+            .x
+            .iter()
+            .zip(self.direction.iter())
+            .map(|(&x, &d)| x + *t * d)
+            .collect();
+        self.problem.cost(&to_param(&self.template, &point))
+    }
+}
+
+/// Result of a one-dimensional minimization
+#[derive(Clone, Copy, Debug)]
+struct LineMinimum<F> {
+    /// Step length along the direction
+    step: F,
+    /// Cost function value at the new point
+    cost: F,
+    /// Estimate of the second derivative along the direction (not positive if unknown)
+    curvature: F,
+}
+
+/// Minimizes the cost function along `direction` starting from `x`, where the cost function value
+/// `fx` is known.
+///
+/// A minimum is bracketed by expanding the interval `[0, step]` by the golden ratio and then
+/// located by [`BrentOpt`] to an absolute accuracy of about `tol`. All cost function evaluations
+/// are counted in `problem`.
+fn line_minimize<O, P, F>(
+    problem: &mut Problem<O>,
+    template: &P,
+    x: &[F],
+    fx: F,
+    direction: &[F],
+    step: F,
+    tol: F,
+) -> Result<LineMinimum<F>, Error>
+where
+    O: CostFunction<Param = P, Output = F>,
+    P: Clone + ArgminSet<usize, F>,
+    F: ArgminFloat,
+{
+    let mut line_problem = Problem::new(LineProblem {
+        problem: problem.take_problem().unwrap(),
+        template: template.clone(),
+        x: x.to_vec(),
+        direction: direction.to_vec(),
+    });
+
+    let result = bracket_and_minimize(&mut line_problem, fx, step, tol);
+
+    // Hand the problem back and merge the function evaluation counts. A failed run of `BrentOpt`
+    // consumes the problem, in which case only its error is returned.
+    let Some(LineProblem { problem: inner, .. }) = line_problem.take_problem() else {
+        return result;
+    };
+    let mut inner = Problem::new(inner);
+    inner.consume_func_counts(line_problem);
+    problem.consume_problem(inner);
+    result
+}
+
+fn bracket_and_minimize<O, P, F>(
+    line_problem: &mut Problem<LineProblem<O, P, F>>,
+    fx: F,
+    step: F,
+    tol: F,
+) -> Result<LineMinimum<F>, Error>
+where
+    O: CostFunction<Param = P, Output = F>,
+    P: Clone + ArgminSet<usize, F>,
+    F: ArgminFloat,
+{
+    let golden: F = float!((1.0 + 5f64.sqrt()) / 2.0);
+
+    // Bracket a minimum such that `fb <= fa` and `fb <= fc`
+    let (mut a, mut fa) = (float!(0.0), fx);
+    let (mut b, mut fb) = (step, line_problem.cost(&step)?);
+    if fb > fa {
+        std::mem::swap(&mut a, &mut b);
+        std::mem::swap(&mut fa, &mut fb);
+    }
+    let mut c = b + golden * (b - a);
+    let mut fc = line_problem.cost(&c)?;
+    let mut expansions = 0;
+    while fc < fb {
+        if expansions >= BRACKET_MAX_EXPANSIONS || !fc.is_finite() {
+            // No minimum found within a reasonable distance, accept the best point so far
+            return Ok(LineMinimum {
+                step: c,
+                cost: fc,
+                curvature: float!(0.0),
+            });
+        }
+        (a, fa) = (b, fb);
+        (b, fb) = (c, fc);
+        c = b + golden * (b - a);
+        fc = line_problem.cost(&c)?;
+        expansions += 1;
+    }
+
+    // Second derivative of the parabola through the three bracketing points
+    let curvature = float!(2.0) * ((fc - fb) / (c - b) - (fb - fa) / (b - a)) / (c - a);
+
+    let OptimizationResult {
+        problem: brent_problem,
+        state: brent_state,
+        ..
+    } = Executor::new(
+        line_problem.take_problem().unwrap(),
+        BrentOpt::new(a.min(c), a.max(c)).set_tolerance(F::epsilon().sqrt(), tol),
+    )
+    .configure(|state| state.max_iters(LINE_MAX_ITERS))
+    .ctrlc(false)
+    .run()?;
+
+    line_problem.consume_problem(brent_problem);
+
+    let (step, cost) = match brent_state.get_best_param() {
+        Some(&t) if brent_state.get_best_cost() < fb => (t, brent_state.get_best_cost()),
+        _ => (b, fb),
+    };
+
+    Ok(LineMinimum {
+        step,
+        cost,
+        curvature,
+    })
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use approx::assert_relative_eq;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    pub(super) struct Rosenbrock {}
+
+    impl CostFunction for Rosenbrock {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(argmin_testfunctions::rosenbrock(p, 1.0, 100.0))
+        }
+    }
+
+    /// f(x) = sum_i (i + 1) * (x_i - 1)^2
+    pub(super) struct Quadratic {}
+
+    impl CostFunction for Quadratic {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(p.iter()
+                .enumerate()
+                .map(|(i, x)| (i + 1) as f64 * (x - 1.0).powi(2))
+                .sum())
+        }
+    }
+
+    #[test]
+    fn test_line_minimize() {
+        let mut problem = Problem::new(Quadratic {});
+        let x = vec![0.0, 0.0];
+        let fx = problem.cost(&x).unwrap();
+
+        // f(t) = (t - 1)^2 + 2 (t - 1)^2 along (1, 1)
+        for step in [0.1, 1.0, -1.0, 10.0] {
+            let res = line_minimize(&mut problem, &x, &x, fx, &[1.0, 1.0], step, 1e-10).unwrap();
+            assert_relative_eq!(res.step, 1.0, epsilon = 1e-8);
+            assert_relative_eq!(res.cost, 0.0, epsilon = 1e-12);
+            assert_relative_eq!(res.curvature, 6.0, epsilon = 1e-8);
+        }
+
+        // Problem was handed back and all evaluations were counted
+        assert!(problem.problem.is_some());
+        assert!(problem.counts["cost_count"] > 4);
+    }
+
+    /// `Quadratic` which fails after `fail_after` evaluations
+    struct Failing {
+        fail_after: u64,
+        calls: AtomicU64,
+    }
+
+    impl CostFunction for Failing {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) >= self.fail_after {
+                return Err(argmin_error!(PotentialBug, "interrupted"));
+            }
+            Quadratic {}.cost(p)
+        }
+    }
+
+    #[test]
+    fn test_line_minimize_error() {
+        let mut problem = Problem::new(Quadratic {});
+        let x = vec![0.0, 0.0];
+        line_minimize(&mut problem, &x, &x, 3.0, &[1.0, 1.0], 0.1, 1e-10).unwrap();
+        let evaluations = problem.counts["cost_count"];
+
+        // Fail while bracketing as well as during the run of `BrentOpt`
+        for fail_after in 0..evaluations {
+            let mut problem = Problem::new(Failing {
+                fail_after,
+                calls: AtomicU64::new(0),
+            });
+            let x = vec![0.0, 0.0];
+            let res = line_minimize(&mut problem, &x, &x, 3.0, &[1.0, 1.0], 0.1, 1e-10);
+            assert_error!(
+                res,
+                ArgminError,
+                concat!(
+                    "Potential bug: \"interrupted\". This is potentially a bug. ",
+                    "Please file a report on https://github.com/argmin-rs/argmin/issues"
+                )
+            );
+        }
+    }
+}
@@ -0,0 +1,520 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::line_minimize;
+use crate::core::{
+    ArgminFloat, CostFunction, Error, IterState, Problem, Solver, State, TerminationReason, KV,
+};
+use crate::solver::dense::to_param;
+use argmin_math::{ArgminGet, ArgminSet, ArgminSize};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Powell's conjugate direction method
+///
+/// Gradient-free method which performs one-dimensional minimizations along each of `n` directions
+/// (initially the coordinate axes) in turn. After each sweep, the overall displacement of the
+/// sweep becomes a new direction and replaces the direction along which the cost function
+/// decreased most, unless extrapolating along the displacement indicates that this would not pay
+/// off or would make the direction set degenerate. For a quadratic cost function, the directions
+/// become mutually conjugate.
+///
+/// Since the direction set may still become (nearly) linearly dependent, it is reset to the
+/// coordinate axes every `reset_interval` iterations (by default every `n` iterations). Whether
+/// the directions were reset in an iteration is reported as KV metric `reset`.
+///
+/// The one-dimensional minimizations are performed by [`BrentOpt`](`crate::solver::brent::BrentOpt`)
+/// after a minimum has been bracketed starting with a step of length `initial_step`.
+///
+/// The method terminates once the decrease of the cost function over one iteration is smaller than
+/// `tol` relative to its value.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`].
+///
+/// ## Reference
+///
+/// M. J. D. Powell (1964). An efficient method for finding the minimum of a function of several
+/// variables without calculating derivatives. The Computer Journal 7 (2), 155–162.
+/// DOI: <https://doi.org/10.1093/comjnl/7.2.155>
+///
+/// William H. Press, Saul A. Teukolsky, William T. Vetterling and Brian P. Flannery (2007).
+/// Numerical Recipes: The Art of Scientific Computing, 3rd edition. Cambridge University Press,
+/// section 10.7.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Powell<P, F> {
+    /// Relative tolerance on the decrease of the cost function
+    tol: F,
+    /// Absolute tolerance of the one-dimensional minimizations
+    line_tol: F,
+    /// Length of the first step when bracketing a minimum
+    initial_step: F,
+    /// Number of iterations after which the directions are reset (defaults to `n`)
+    reset_interval: Option<u64>,
+    /// Template used to convert internal vectors into parameter vectors
+    template: Option<P>,
+    /// Current point
+    x: Vec<F>,
+    /// Cost function value at the current point
+    fx: F,
+    /// Direction set
+    directions: Vec<Vec<F>>,
+    /// Number of iterations since the last reset of the directions
+    since_reset: u64,
+}
+
+impl<P, F> Powell<P, F>
+where
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `Powell`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::powell::Powell;
+    /// let powell: Powell<Vec<f64>, f64> = Powell::new();
+    /// ```
+    pub fn new() -> Self {
+        Powell {
+            tol: F::epsilon().sqrt(),
+            line_tol: F::epsilon().sqrt(),
+            initial_step: float!(1.0),
+            reset_interval: None,
+            template: None,
+            x: vec![],
+            fx: F::nan(),
+            directions: vec![],
+            since_reset: 0,
+        }
+    }
+
+    /// Set tolerance
+    ///
+    /// The method terminates once the decrease of the cost function over one iteration is smaller
+    /// than `tol` relative to its value. Must be larger than `0` and defaults to the square root
+    /// of machine epsilon.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::powell::Powell;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let powell: Powell<Vec<f64>, f64> = Powell::new().with_tolerance(1e-10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Powell`: tolerance must be > 0."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Set tolerance of the one-dimensional minimizations
+    ///
+    /// Absolute accuracy of the step length along each direction. Must be larger than `0` and
+    /// defaults to the square root of machine epsilon.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::powell::Powell;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let powell: Powell<Vec<f64>, f64> = Powell::new().with_line_tolerance(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_line_tolerance(mut self, line_tol: F) -> Result<Self, Error> {
+        if line_tol <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Powell`: line tolerance must be > 0."
+            ));
+        }
+        self.line_tol = line_tol;
+        Ok(self)
+    }
+
+    /// Set initial step
+    ///
+    /// Length of the first step along a direction when bracketing a minimum. Should be about the
+    /// expected distance to the minimum. Must be larger than `0` and defaults to `1.0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::powell::Powell;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let powell: Powell<Vec<f64>, f64> = Powell::new().with_initial_step(0.1)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_initial_step(mut self, initial_step: F) -> Result<Self, Error> {
+        if initial_step <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Powell`: initial step must be > 0."
+            ));
+        }
+        self.initial_step = initial_step;
+        Ok(self)
+    }
+
+    /// Set reset interval
+    ///
+    /// The direction set is reset to the coordinate axes every `reset_interval` iterations. Must
+    /// be larger than `0` and defaults to the number of parameters.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::powell::Powell;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let powell: Powell<Vec<f64>, f64> = Powell::new().with_reset_interval(10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_reset_interval(mut self, reset_interval: u64) -> Result<Self, Error> {
+        if reset_interval == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Powell`: reset interval must be > 0."
+            ));
+        }
+        self.reset_interval = Some(reset_interval);
+        Ok(self)
+    }
+
+    /// Sets the direction set to the coordinate axes
+    fn reset_directions(&mut self) {
+        let n = self.x.len();
+        self.directions = (0..n)
+            .map(|i| {
+                let mut d = vec![float!(0.0); n];
+                d[i] = float!(1.0);
+                d
+            })
+            .collect();
+        self.since_reset = 0;
+    }
+
+    /// Minimizes along `direction` and moves the current point to the minimum
+    fn minimize_along<O>(&mut self, problem: &mut Problem<O>, direction: &[F]) -> Result<(), Error>
+    where
+        O: CostFunction<Param = P, Output = F>,
+    {
+        let res = line_minimize(
+            problem,
+            self.template.as_ref().unwrap(),
+            &self.x,
+            self.fx,
+            direction,
+            self.initial_step,
+            self.line_tol,
+        )?;
+        if res.cost < self.fx {
+            for (x, &d) in self.x.iter_mut().zip(direction.iter()) {
+                *x = *x + res.step * d;
+            }
+            self.fx = res.cost;
+        }
+        Ok(())
+    }
+}
+
+impl<P, F> Default for Powell<P, F>
+where
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        Powell::new()
+    }
+}
+
+impl<O, P, F> Solver<O, IterState<P, (), (), (), (), F>> for Powell<P, F>
+where
+    O: CostFunction<Param = P, Output = F>,
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Powell";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`Powell` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let n = param.shape();
+        if n < 1 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Powell`: parameter vector must have at least one element."
+            ));
+        }
+        self.x = (0..n).map(|i| param.get(i)).collect();
+        self.fx = problem.cost(&param)?;
+        self.template = Some(param.clone());
+        self.reset_directions();
+        Ok((state.param(param).cost(self.fx), None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let n = self.x.len();
+
+        let reset = self.since_reset >= self.reset_interval.unwrap_or(n as u64);
+        if reset {
+            self.reset_directions();
+        }
+
+        let x0 = self.x.clone();
+        let f0 = self.fx;
+
+        // Minimize along all directions and remember the one with the largest decrease
+        let mut largest_decrease = float!(0.0);
+        let mut largest_idx = 0;
+        for i in 0..n {
+            let f_prev = self.fx;
+            let direction = self.directions[i].clone();
+            self.minimize_along(problem, &direction)?;
+            if f_prev - self.fx > largest_decrease {
+                largest_decrease = f_prev - self.fx;
+                largest_idx = i;
+            }
+        }
+
+        let converged = float!(2.0) * (f0 - self.fx)
+            <= self.tol * (f0.abs() + self.fx.abs()) + F::min_positive_value();
+
+        if !converged {
+            let displacement: Vec<F> = self.x.iter().zip(x0.iter()).map(|(&x, &y)| x - y).collect();
+            let norm = displacement
+                .iter()
+                .map(|&d| d * d)
+                .fold(float!(0.0), |acc, d| acc + d)
+                .sqrt();
+            if norm > float!(0.0) {
+                // Cost function at the point extrapolated along the displacement
+                let extrapolated: Vec<F> = self
+                    .x
+                    .iter()
+                    .zip(displacement.iter())
+                    .map(|(&x, &d)| x + d)
+                    .collect();
+                let fe = problem.cost(&to_param(self.template.as_ref().unwrap(), &extrapolated))?;
+                if fe < f0 {
+                    let t = float!(2.0)
+                        * (f0 - float!(2.0) * self.fx + fe)
+                        * (f0 - self.fx - largest_decrease).powi(2)
+                        - largest_decrease * (f0 - fe).powi(2);
+                    if t < float!(0.0) {
+                        let direction: Vec<F> = displacement.iter().map(|&d| d / norm).collect();
+                        self.minimize_along(problem, &direction)?;
+                        self.directions[largest_idx] = self.directions[n - 1].clone();
+                        self.directions[n - 1] = direction;
+                    }
+                }
+            }
+        }
+
+        self.since_reset += 1;
+
+        let state = state
+            .param(to_param(self.template.as_ref().unwrap(), &self.x))
+            .cost(self.fx);
+
+        Ok((
+            if converged {
+                state.terminate_with(TerminationReason::SolverConverged)
+            } else {
+                state
+            },
+            Some(kv!("reset" => reset;)),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::powell::tests::{Quadratic, Rosenbrock};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(powell, Powell<Vec<f64>, f64>);
+
+    #[test]
+    fn test_new() {
+        let powell: Powell<Vec<f64>, f64> = Powell::new();
+        let Powell {
+            tol,
+            line_tol,
+            initial_step,
+            reset_interval,
+            template,
+            x,
+            fx,
+            directions,
+            since_reset,
+        } = powell;
+        assert_eq!(tol.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert_eq!(line_tol.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert_eq!(initial_step.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert!(reset_interval.is_none());
+        assert!(template.is_none());
+        assert!(x.is_empty());
+        assert!(fx.is_nan());
+        assert!(directions.is_empty());
+        assert_eq!(since_reset, 0);
+    }
+
+    #[test]
+    fn test_builders() {
+        let powell: Powell<Vec<f64>, f64> = Powell::new()
+            .with_tolerance(1e-4)
+            .unwrap()
+            .with_line_tolerance(1e-3)
+            .unwrap()
+            .with_initial_step(0.5)
+            .unwrap()
+            .with_reset_interval(7)
+            .unwrap();
+        assert_eq!(powell.tol.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+        assert_eq!(powell.line_tol.to_ne_bytes(), 1e-3f64.to_ne_bytes());
+        assert_eq!(powell.initial_step.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert_eq!(powell.reset_interval, Some(7));
+
+        for val in [0.0, -1.0] {
+            assert_error!(
+                Powell::<Vec<f64>, f64>::new().with_tolerance(val),
+                ArgminError,
+                "Invalid parameter: \"`Powell`: tolerance must be > 0.\""
+            );
+            assert_error!(
+                Powell::<Vec<f64>, f64>::new().with_line_tolerance(val),
+                ArgminError,
+                "Invalid parameter: \"`Powell`: line tolerance must be > 0.\""
+            );
+            assert_error!(
+                Powell::<Vec<f64>, f64>::new().with_initial_step(val),
+                ArgminError,
+                "Invalid parameter: \"`Powell`: initial step must be > 0.\""
+            );
+        }
+        assert_error!(
+            Powell::<Vec<f64>, f64>::new().with_reset_interval(0),
+            ArgminError,
+            "Invalid parameter: \"`Powell`: reset interval must be > 0.\""
+        );
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut powell: Powell<Vec<f64>, f64> = Powell::new();
+        let res = powell.init(&mut Problem::new(Quadratic {}), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`Powell` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+
+        let res = powell.init(
+            &mut Problem::new(Quadratic {}),
+            IterState::new().param(vec![]),
+        );
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`Powell`: parameter vector must have at least one element.\""
+        );
+    }
+
+    #[test]
+    fn test_quadratic() {
+        let res = Executor::new(Quadratic {}, Powell::new())
+            .configure(|state| state.param(vec![0.0, 3.0, -2.0]).max_iters(100))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state().get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        for x in res.state().get_best_param().unwrap() {
+            assert_relative_eq!(*x, 1.0, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_rosenbrock() {
+        for reset_interval in [1, 2, 100] {
+            let solver = Powell::new().with_reset_interval(reset_interval).unwrap();
+            let res = Executor::new(Rosenbrock {}, solver)
+                .configure(|state| state.param(vec![-1.2, 1.0, 0.5]).max_iters(1000))
+                .run()
+                .unwrap();
+            assert_eq!(
+                res.state().get_termination_reason(),
+                Some(&TerminationReason::SolverConverged)
+            );
+            for x in res.state().get_best_param().unwrap() {
+                assert_relative_eq!(*x, 1.0, epsilon = 1e-4);
+            }
+        }
+    }
+
+    #[cfg(feature = "_nalgebral")]
+    #[test]
+    fn test_rosenbrock_nalgebra() {
+        use nalgebra::DVector;
+
+        struct RosenbrockNalgebra {}
+
+        impl CostFunction for RosenbrockNalgebra {
+            type Param = DVector<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(argmin_testfunctions::rosenbrock(p.as_slice(), 1.0, 100.0))
+            }
+        }
+
+        let res = Executor::new(RosenbrockNalgebra {}, Powell::new())
+            .configure(|state| state.param(DVector::from(vec![-1.2, 1.0])).max_iters(1000))
+            .run()
+            .unwrap();
+        for x in res.state().get_best_param().unwrap().iter() {
+            assert_relative_eq!(*x, 1.0, epsilon = 1e-4);
+        }
+    }
+}
@@ -0,0 +1,620 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::line_minimize;
+use crate::core::{
+    ArgminFloat, CostFunction, Error, IterState, Problem, Solver, State, TerminationReason, KV,
+};
+use crate::solver::dense::to_param;
+use argmin_math::{ArgminGet, ArgminSet, ArgminSize};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of sweeps of the Jacobi singular value decomposition
+const JACOBI_MAX_SWEEPS: usize = 30;
+
+/// # PRAXIS
+///
+/// Brent's principal axis method, a refinement of [Powell's method](`super::Powell`). Like
+/// Powell's method it performs one-dimensional minimizations along a set of directions which
+/// become conjugate for quadratic cost functions, and additionally keeps track of the second
+/// derivative of the cost function along each direction. After every sweep through the
+/// directions, the direction set is reset to the principal axes of the resulting quadratic model,
+/// which are computed via a singular value decomposition. This prevents the directions from
+/// becoming linearly dependent.
+///
+/// The one-dimensional minimizations are performed by [`BrentOpt`](`crate::solver::brent::BrentOpt`)
+/// after a minimum has been bracketed. The length of the first step is an estimate of the step
+/// lengths of the previous minimizations bounded by `max_step`. This estimate is reported as KV
+/// metric `step_estimate`.
+///
+/// The method terminates once the step estimate has been smaller than
+/// `sqrt(epsilon) * ||x|| + tol` for more than `ktm` consecutive sweeps.
+///
+/// In contrast to the original algorithm, the random steps taken for ill-conditioned problems,
+/// the rescaling of the parameters and the quadratic extrapolation along the valley are omitted.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`].
+///
+/// ## Reference
+///
+/// Richard P. Brent (1973). Algorithms for minimization without derivatives. Prentice-Hall,
+/// chapter 7.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Praxis<P, F> {
+    /// Absolute tolerance on the parameter vector
+    tol: F,
+    /// Maximum length of the first step along each direction
+    max_step: F,
+    /// Number of sweeps without substantial improvement before terminating
+    ktm: u64,
+    /// Template used to convert internal vectors into parameter vectors
+    template: Option<P>,
+    /// Current point
+    x: Vec<F>,
+    /// Cost function value at the current point
+    fx: F,
+    /// Direction set (unit vectors)
+    directions: Vec<Vec<F>>,
+    /// Second derivatives of the cost function along the directions
+    curvatures: Vec<F>,
+    /// Estimate of the step length
+    ldt: F,
+    /// Number of consecutive sweeps without substantial improvement
+    kt: u64,
+}
+
+impl<P, F> Praxis<P, F>
+where
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `Praxis`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::powell::Praxis;
+    /// let praxis: Praxis<Vec<f64>, f64> = Praxis::new();
+    /// ```
+    pub fn new() -> Self {
+        Praxis {
+            tol: F::epsilon().sqrt(),
+            max_step: float!(1.0),
+            ktm: 1,
+            template: None,
+            x: vec![],
+            fx: F::nan(),
+            directions: vec![],
+            curvatures: vec![],
+            ldt: float!(1.0),
+            kt: 0,
+        }
+    }
+
+    /// Set tolerance
+    ///
+    /// Absolute tolerance on the parameter vector. Must be larger than `0` and defaults to the
+    /// square root of machine epsilon.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::powell::Praxis;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let praxis: Praxis<Vec<f64>, f64> = Praxis::new().with_tolerance(1e-10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Praxis`: tolerance must be > 0."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Set maximum step
+    ///
+    /// Maximum length of the first step along each direction. Should be about the expected
+    /// distance to the minimum. Must be larger than `0` and defaults to `1.0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::powell::Praxis;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let praxis: Praxis<Vec<f64>, f64> = Praxis::new().with_max_step(10.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_max_step(mut self, max_step: F) -> Result<Self, Error> {
+        if max_step <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Praxis`: maximum step must be > 0."
+            ));
+        }
+        self.max_step = max_step;
+        Ok(self)
+    }
+
+    /// Set number of sweeps without substantial improvement before terminating
+    ///
+    /// Defaults to `1`. Brent suggests `4` for a very cautious termination.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::powell::Praxis;
+    /// let praxis: Praxis<Vec<f64>, f64> = Praxis::new().with_ktm(4);
+    /// ```
+    #[must_use]
+    pub fn with_ktm(mut self, ktm: u64) -> Self {
+        self.ktm = ktm;
+        self
+    }
+
+    /// Tolerance of the one-dimensional minimizations at the current point
+    fn line_tolerance(&self) -> F {
+        let norm = self
+            .x
+            .iter()
+            .map(|&x| x * x)
+            .fold(float!(0.0), |acc, x| acc + x)
+            .sqrt();
+        F::epsilon().sqrt() * norm + self.tol
+    }
+
+    /// Minimizes along direction `i`, moves the current point to the minimum and updates the
+    /// curvature along the direction. Returns the step length.
+    fn minimize_along<O>(&mut self, problem: &mut Problem<O>, i: usize) -> Result<F, Error>
+    where
+        O: CostFunction<Param = P, Output = F>,
+    {
+        let tol = self.line_tolerance();
+        let step = self.ldt.max(tol).min(self.max_step);
+        let res = line_minimize(
+            problem,
+            self.template.as_ref().unwrap(),
+            &self.x,
+            self.fx,
+            &self.directions[i],
+            step,
+            tol,
+        )?;
+        if res.curvature > float!(0.0) {
+            self.curvatures[i] = res.curvature;
+        }
+        if res.cost < self.fx {
+            for (x, &d) in self.x.iter_mut().zip(self.directions[i].iter()) {
+                *x = *x + res.step * d;
+            }
+            self.fx = res.cost;
+            Ok(res.step)
+        } else {
+            Ok(float!(0.0))
+        }
+    }
+
+    /// Updates the step estimate and returns `true` if no substantial improvement was made for
+    /// more than `ktm` consecutive sweeps.
+    fn update_step_estimate(&mut self, lds: F) -> bool {
+        self.ldt = (float!(0.1) * self.ldt).max(lds);
+        if self.ldt > float!(0.5) * self.line_tolerance() {
+            self.kt = 0;
+        } else {
+            self.kt += 1;
+        }
+        self.kt > self.ktm
+    }
+
+    /// Performs the sweeps through the directions. Returns `true` if the method converged.
+    fn sweep<O>(&mut self, problem: &mut Problem<O>) -> Result<bool, Error>
+    where
+        O: CostFunction<Param = P, Output = F>,
+    {
+        let n = self.x.len();
+
+        let step = self.minimize_along(problem, 0)?;
+        if step < float!(0.0) {
+            for d in self.directions[0].iter_mut() {
+                *d = -*d;
+            }
+        }
+        if n == 1 {
+            return Ok(self.update_step_estimate(step.abs()));
+        }
+
+        for k in 1..n {
+            let y = self.x.clone();
+
+            // Minimize along the non-conjugate directions and remember the one with the largest
+            // decrease, then along the conjugate ones
+            let mut largest_decrease = float!(0.0);
+            let mut largest_idx = k;
+            for i in k..n {
+                let f_prev = self.fx;
+                self.minimize_along(problem, i)?;
+                if f_prev - self.fx > largest_decrease {
+                    largest_decrease = f_prev - self.fx;
+                    largest_idx = i;
+                }
+            }
+            for i in 0..k {
+                self.minimize_along(problem, i)?;
+            }
+
+            let z: Vec<F> = self.x.iter().zip(y.iter()).map(|(&x, &y)| x - y).collect();
+            let lds = z
+                .iter()
+                .map(|&z| z * z)
+                .fold(float!(0.0), |acc, z| acc + z)
+                .sqrt();
+            if lds > F::min_positive_value() {
+                // Discard the direction with the largest decrease and add the displacement as a
+                // new conjugate direction
+                for i in (k + 1..=largest_idx).rev() {
+                    self.directions.swap(i, i - 1);
+                    self.curvatures.swap(i, i - 1);
+                }
+                self.directions[k] = z.iter().map(|&z| z / lds).collect();
+                self.curvatures[k] = float!(0.0);
+                self.minimize_along(problem, k)?;
+            }
+
+            if self.update_step_estimate(lds) {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Resets the directions to the principal axes of the quadratic model given by the directions
+    /// and the curvatures along them.
+    fn principal_axes(&mut self) {
+        let n = self.x.len();
+        let small = F::epsilon().powi(2);
+        let large = float!(1.0) / small;
+
+        // Columns of B = V D^(-1/2), scaled by the largest entry of D^(-1/2)
+        let scales: Vec<F> = self
+            .curvatures
+            .iter()
+            .map(|&d| float!(1.0) / d.max(small).min(large).sqrt())
+            .collect();
+        let dn = scales.iter().fold(float!(0.0), |acc, &s| acc.max(s));
+        let mut columns: Vec<Vec<F>> = self
+            .directions
+            .iter()
+            .zip(scales.iter())
+            .map(|(v, &s)| v.iter().map(|&v| v * s / dn).collect())
+            .collect();
+
+        // One-sided Jacobi: orthogonalize the columns of B, which yields U * Sigma of the
+        // singular value decomposition B = U Sigma W^T
+        let dot = |a: &[F], b: &[F]| {
+            a.iter()
+                .zip(b.iter())
+                .fold(float!(0.0), |acc, (&a, &b)| acc + a * b)
+        };
+        for _ in 0..JACOBI_MAX_SWEEPS {
+            let mut rotated = false;
+            for p in 0..n {
+                for q in (p + 1)..n {
+                    let alpha = dot(&columns[p], &columns[p]);
+                    let beta = dot(&columns[q], &columns[q]);
+                    let gamma = dot(&columns[p], &columns[q]);
+                    if gamma.abs() <= F::epsilon() * (alpha * beta).sqrt() {
+                        continue;
+                    }
+                    rotated = true;
+                    let zeta = (beta - alpha) / (float!(2.0) * gamma);
+                    let t = zeta.signum() / (zeta.abs() + (float!(1.0) + zeta * zeta).sqrt());
+                    let c = float!(1.0) / (float!(1.0) + t * t).sqrt();
+                    let s = c * t;
+                    let (left, right) = columns.split_at_mut(q);
+                    for (bp, bq) in left[p].iter_mut().zip(right[0].iter_mut()) {
+                        (*bp, *bq) = (c * *bp - s * *bq, s * *bp + c * *bq);
+                    }
+                }
+            }
+            if !rotated {
+                break;
+            }
+        }
+
+        let sigmas: Vec<F> = columns.iter().map(|c| dot(c, c).sqrt()).collect();
+        let sigma_max = sigmas.iter().fold(float!(0.0), |acc, &s| acc.max(s));
+        if sigmas.iter().any(|&s| s <= F::epsilon() * sigma_max) {
+            // Direction set is degenerate, keep the current one
+            return;
+        }
+
+        // The principal axes are the left singular vectors, the curvature along them follows
+        // from the singular values
+        let mut axes: Vec<(F, Vec<F>)> = columns
+            .into_iter()
+            .zip(sigmas)
+            .map(|(c, s)| {
+                let curvature = (float!(1.0) / (dn * s).powi(2)).max(small).min(large);
+                (curvature, c.into_iter().map(|c| c / s).collect())
+            })
+            .collect();
+        axes.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+        (self.curvatures, self.directions) = axes.into_iter().unzip();
+    }
+}
+
+impl<P, F> Default for Praxis<P, F>
+where
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        Praxis::new()
+    }
+}
+
+impl<O, P, F> Solver<O, IterState<P, (), (), (), (), F>> for Praxis<P, F>
+where
+    O: CostFunction<Param = P, Output = F>,
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "PRAXIS";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`Praxis` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let n = param.shape();
+        if n < 1 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Praxis`: parameter vector must have at least one element."
+            ));
+        }
+        self.x = (0..n).map(|i| param.get(i)).collect();
+        self.fx = problem.cost(&param)?;
+        self.template = Some(param.clone());
+        self.directions = (0..n)
+            .map(|i| {
+                let mut d = vec![float!(0.0); n];
+                d[i] = float!(1.0);
+                d
+            })
+            .collect();
+        self.curvatures = vec![float!(0.0); n];
+        self.ldt = self.max_step;
+        self.kt = 0;
+        Ok((state.param(param).cost(self.fx), None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let converged = self.sweep(problem)?;
+        if !converged && self.x.len() > 1 {
+            self.principal_axes();
+        }
+
+        let state = state
+            .param(to_param(self.template.as_ref().unwrap(), &self.x))
+            .cost(self.fx);
+
+        Ok((
+            if converged {
+                state.terminate_with(TerminationReason::SolverConverged)
+            } else {
+                state
+            },
+            Some(kv!("step_estimate" => self.ldt;)),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::powell::tests::{Quadratic, Rosenbrock};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(praxis, Praxis<Vec<f64>, f64>);
+
+    #[test]
+    fn test_new() {
+        let praxis: Praxis<Vec<f64>, f64> = Praxis::new();
+        let Praxis {
+            tol,
+            max_step,
+            ktm,
+            template,
+            x,
+            fx,
+            directions,
+            curvatures,
+            ldt,
+            kt,
+        } = praxis;
+        assert_eq!(tol.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert_eq!(max_step.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(ktm, 1);
+        assert!(template.is_none());
+        assert!(x.is_empty());
+        assert!(fx.is_nan());
+        assert!(directions.is_empty());
+        assert!(curvatures.is_empty());
+        assert_eq!(ldt.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(kt, 0);
+    }
+
+    #[test]
+    fn test_builders() {
+        let praxis: Praxis<Vec<f64>, f64> = Praxis::new()
+            .with_tolerance(1e-4)
+            .unwrap()
+            .with_max_step(3.0)
+            .unwrap()
+            .with_ktm(4);
+        assert_eq!(praxis.tol.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+        assert_eq!(praxis.max_step.to_ne_bytes(), 3.0f64.to_ne_bytes());
+        assert_eq!(praxis.ktm, 4);
+
+        for val in [0.0, -1.0] {
+            assert_error!(
+                Praxis::<Vec<f64>, f64>::new().with_tolerance(val),
+                ArgminError,
+                "Invalid parameter: \"`Praxis`: tolerance must be > 0.\""
+            );
+            assert_error!(
+                Praxis::<Vec<f64>, f64>::new().with_max_step(val),
+                ArgminError,
+                "Invalid parameter: \"`Praxis`: maximum step must be > 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut praxis: Praxis<Vec<f64>, f64> = Praxis::new();
+        let res = praxis.init(&mut Problem::new(Quadratic {}), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`Praxis` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+
+        let res = praxis.init(
+            &mut Problem::new(Quadratic {}),
+            IterState::new().param(vec![]),
+        );
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`Praxis`: parameter vector must have at least one element.\""
+        );
+    }
+
+    #[test]
+    fn test_principal_axes() {
+        // Directions (1, 0) and (1, 1) / sqrt(2) with curvatures 2 and 4 are conjugate with
+        // respect to the Hessian [[2, -2], [-2, 10]]
+        let mut praxis: Praxis<Vec<f64>, f64> = Praxis::new();
+        let s = 0.5f64.sqrt();
+        praxis.x = vec![0.0, 0.0];
+        praxis.directions = vec![vec![1.0, 0.0], vec![s, s]];
+        praxis.curvatures = vec![2.0, 4.0];
+        praxis.principal_axes();
+
+        // Eigenvalues of [[2, -2], [-2, 10]] are 6 +- sqrt(20)
+        let hessian = [[2.0, -2.0], [-2.0, 10.0]];
+        assert_relative_eq!(praxis.curvatures[0], 6.0 + 20f64.sqrt(), epsilon = 1e-10);
+        assert_relative_eq!(praxis.curvatures[1], 6.0 - 20f64.sqrt(), epsilon = 1e-10);
+        for (d, v) in praxis.curvatures.iter().zip(praxis.directions.iter()) {
+            for (row, vi) in hessian.iter().zip(v.iter()) {
+                let hv = row[0] * v[0] + row[1] * v[1];
+                assert_relative_eq!(hv, d * vi, epsilon = 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_quadratic() {
+        let res = Executor::new(Quadratic {}, Praxis::new())
+            .configure(|state| state.param(vec![0.0, 3.0, -2.0]).max_iters(100))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state().get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        for x in res.state().get_best_param().unwrap() {
+            assert_relative_eq!(*x, 1.0, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_one_dimensional() {
+        let res = Executor::new(Quadratic {}, Praxis::new())
+            .configure(|state| state.param(vec![5.0]).max_iters(100))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state().get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        assert_relative_eq!(
+            res.state().get_best_param().unwrap()[0],
+            1.0,
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn test_rosenbrock() {
+        let res = Executor::new(Rosenbrock {}, Praxis::new())
+            .configure(|state| state.param(vec![-1.2, 1.0, 0.5]).max_iters(1000))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state().get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        for x in res.state().get_best_param().unwrap() {
+            assert_relative_eq!(*x, 1.0, epsilon = 1e-4);
+        }
+    }
+
+    #[cfg(feature = "_nalgebral")]
+    #[test]
+    fn test_rosenbrock_nalgebra() {
+        use nalgebra::DVector;
+
+        struct RosenbrockNalgebra {}
+
+        impl CostFunction for RosenbrockNalgebra {
+            type Param = DVector<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(argmin_testfunctions::rosenbrock(p.as_slice(), 1.0, 100.0))
+            }
+        }
+
+        let res = Executor::new(RosenbrockNalgebra {}, Praxis::new())
+            .configure(|state| state.param(DVector::from(vec![-1.2, 1.0])).max_iters(1000))
+            .run()
+            .unwrap();
+        for x in res.state().get_best_param().unwrap().iter() {
+            assert_relative_eq!(*x, 1.0, epsilon = 1e-4);
+        }
+    }
+}
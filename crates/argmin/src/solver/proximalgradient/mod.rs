@@ -0,0 +1,597 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Proximal gradient method (ISTA/FISTA)
+//!
+//! Minimizes composite cost functions `f(x) + g(x)` where `f` is smooth and `g` is possibly
+//! nonsmooth but has an inexpensive proximal operator.
+//!
+//! See [`ProximalGradient`] for details.
+//!
+//! ## References
+//!
+//! Amir Beck and Marc Teboulle (2009). A Fast Iterative Shrinkage-Thresholding Algorithm for
+//! Linear Inverse Problems. SIAM Journal on Imaging Sciences 2(1), pp. 183-202.
+//!
+//! Brendan O'Donoghue and Emmanuel Candès (2015). Adaptive Restart for Accelerated Gradient
+//! Schemes. Foundations of Computational Mathematics 15, pp. 715-732.
+
+use crate::core::{
+    ArgminFloat, CostFunction, Error, Gradient, IterState, Problem, ProximalOperator, Solver,
+    TerminationReason, TerminationStatus, KV,
+};
+use argmin_math::{ArgminDot, ArgminL2Norm, ArgminScaledAdd, ArgminSub};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Proximal gradient method (ISTA/FISTA)
+///
+/// Minimizes `f(x) + g(x)`, where the smooth part `f` is defined via [`CostFunction`] and
+/// [`Gradient`] and the nonsmooth part `g` via [`ProximalOperator`]. Each iteration takes a
+/// gradient step on `f` followed by the proximal operator of `g`:
+///
+/// ```text
+/// x_{k+1} = prox_{t g}(y_k - t grad f(y_k))
+/// ```
+///
+/// The step length `t` is the inverse of an estimate of the Lipschitz constant of the gradient of
+/// `f`. Starting from the initial step length (set via
+/// [`with_step_length`](`ProximalGradient::with_step_length`)), it is reduced by a constant factor
+/// (set via [`with_backtracking_factor`](`ProximalGradient::with_backtracking_factor`)) until the
+/// quadratic upper bound
+///
+/// ```text
+/// f(x_{k+1}) <= f(y_k) + grad f(y_k)^T (x_{k+1} - y_k) + 1/(2 t) ||x_{k+1} - y_k||^2
+/// ```
+///
+/// holds.
+///
+/// With acceleration enabled (FISTA, the default), `y_k` is extrapolated from the last two
+/// iterates with Nesterov momentum. Without acceleration (ISTA), `y_k = x_k`. The momentum is
+/// reset whenever the step `x_{k+1} - x_k` has a positive inner product with the gradient mapping
+/// `(y_k - x_{k+1}) / t`, i.e. whenever the momentum points uphill (gradient based adaptive
+/// restart). This recovers linear convergence on strongly convex problems. Restarts can be disabled via
+/// [`with_restart`](`ProximalGradient::with_restart`).
+///
+/// The algorithm stops once the norm of the gradient mapping `||x_{k+1} - y_k|| / t` is below
+/// the tolerance set via [`with_tolerance`](`ProximalGradient::with_tolerance`).
+///
+/// The cost function value stored in the state is `f(x) + g(x)`. The current step length and
+/// whether the momentum has been restarted are reported as KV metrics `step_length` and
+/// `restart`.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`] and [`Gradient`] for the
+/// smooth part and [`ProximalOperator`] for the nonsmooth part.
+///
+/// ## References
+///
+/// Amir Beck and Marc Teboulle (2009). A Fast Iterative Shrinkage-Thresholding Algorithm for
+/// Linear Inverse Problems. SIAM Journal on Imaging Sciences 2(1), pp. 183-202.
+///
+/// Brendan O'Donoghue and Emmanuel Candès (2015). Adaptive Restart for Accelerated Gradient
+/// Schemes. Foundations of Computational Mathematics 15, pp. 715-732.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct ProximalGradient<P, F> {
+    /// Step length (inverse of the estimate of the Lipschitz constant)
+    step_length: F,
+    /// Factor by which the step length is reduced during backtracking
+    backtracking_factor: F,
+    /// Nesterov acceleration (FISTA)
+    acceleration: bool,
+    /// Adaptive restart of the momentum
+    restart: bool,
+    /// Tolerance on the norm of the gradient mapping
+    tol: F,
+    /// Extrapolated point
+    y: Option<P>,
+    /// Momentum parameter
+    t: F,
+    /// Norm of the gradient mapping of the last iteration
+    residual: F,
+}
+
+impl<P, F> ProximalGradient<P, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `ProximalGradient`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::proximalgradient::ProximalGradient;
+    /// let solver: ProximalGradient<Vec<f64>, f64> = ProximalGradient::new();
+    /// ```
+    pub fn new() -> Self {
+        ProximalGradient {
+            step_length: float!(1.0),
+            backtracking_factor: float!(0.5),
+            acceleration: true,
+            restart: true,
+            tol: F::epsilon().sqrt(),
+            y: None,
+            t: float!(1.0),
+            residual: F::infinity(),
+        }
+    }
+
+    /// Set initial step length
+    ///
+    /// Must be larger than `0`. If the Lipschitz constant `L` of the gradient of the smooth part
+    /// is known, `1/L` avoids backtracking entirely. Defaults to `1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::proximalgradient::ProximalGradient;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let solver: ProximalGradient<Vec<f64>, f64> = ProximalGradient::new().with_step_length(0.1)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_step_length(mut self, step_length: F) -> Result<Self, Error> {
+        if step_length <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ProximalGradient`: step length must be > 0."
+            ));
+        }
+        self.step_length = step_length;
+        Ok(self)
+    }
+
+    /// Set factor by which the step length is reduced during backtracking
+    ///
+    /// Must be in `(0, 1)`. Defaults to `0.5`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::proximalgradient::ProximalGradient;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let solver: ProximalGradient<Vec<f64>, f64> =
+    ///     ProximalGradient::new().with_backtracking_factor(0.8)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_backtracking_factor(mut self, backtracking_factor: F) -> Result<Self, Error> {
+        if backtracking_factor <= float!(0.0) || backtracking_factor >= float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ProximalGradient`: backtracking factor must be in (0, 1)."
+            ));
+        }
+        self.backtracking_factor = backtracking_factor;
+        Ok(self)
+    }
+
+    /// Enable or disable Nesterov acceleration (FISTA)
+    ///
+    /// Defaults to `true`. Without acceleration, the algorithm is the plain proximal gradient
+    /// method (ISTA).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::proximalgradient::ProximalGradient;
+    /// let solver: ProximalGradient<Vec<f64>, f64> = ProximalGradient::new().with_acceleration(false);
+    /// ```
+    #[must_use]
+    pub fn with_acceleration(mut self, acceleration: bool) -> Self {
+        self.acceleration = acceleration;
+        self
+    }
+
+    /// Enable or disable adaptive restart of the momentum
+    ///
+    /// Only has an effect if acceleration is enabled. Defaults to `true`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::proximalgradient::ProximalGradient;
+    /// let solver: ProximalGradient<Vec<f64>, f64> = ProximalGradient::new().with_restart(false);
+    /// ```
+    #[must_use]
+    pub fn with_restart(mut self, restart: bool) -> Self {
+        self.restart = restart;
+        self
+    }
+
+    /// Set tolerance on the norm of the gradient mapping
+    ///
+    /// Must be larger than `0`. Defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::proximalgradient::ProximalGradient;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let solver: ProximalGradient<Vec<f64>, f64> = ProximalGradient::new().with_tolerance(1e-10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ProximalGradient`: tolerance must be > 0."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+}
+
+impl<P, F> Default for ProximalGradient<P, F>
+where
+    F: ArgminFloat,
+{
+    fn default() -> ProximalGradient<P, F> {
+        ProximalGradient::new()
+    }
+}
+
+impl<O, P, G, F> Solver<O, IterState<P, G, (), (), (), F>> for ProximalGradient<P, F>
+where
+    O: CostFunction<Param = P, Output = F>
+        + Gradient<Param = P, Gradient = G>
+        + ProximalOperator<Param = P, Float = F>,
+    P: Clone + ArgminSub<P, P> + ArgminScaledAdd<P, F, P> + ArgminScaledAdd<G, F, P>,
+    P: ArgminDot<P, F> + ArgminL2Norm<F>,
+    G: ArgminDot<P, F>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Proximal Gradient";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, (), (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`ProximalGradient` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+
+        let cost = problem.cost(&param)? + problem.nonsmooth_cost(&param)?;
+        self.y = Some(param.clone());
+        self.t = float!(1.0);
+        self.residual = F::infinity();
+
+        Ok((state.param(param).cost(cost), None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, (), (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`ProximalGradient`: Parameter vector in state not set."
+        ))?;
+
+        let y = self.y.take().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`ProximalGradient`: Extrapolated point not set."
+        ))?;
+
+        let y_cost = problem.cost(&y)?;
+        let y_grad = problem.gradient(&y)?;
+
+        // backtracking on the estimate of the Lipschitz constant
+        let (xk1, xk1_cost, diff) = loop {
+            let step = self.step_length;
+            let z = problem.prox(&y.scaled_add(&(-step), &y_grad), step)?;
+            let z_cost = problem.cost(&z)?;
+            let diff = z.sub(&y);
+            let bound = y_cost + y_grad.dot(&diff) + diff.dot(&diff) / (float!(2.0) * step);
+            // allow for rounding errors close to convergence
+            let rounding = F::epsilon() * float!(10.0) * (y_cost.abs() + z_cost.abs());
+            if z_cost <= bound + rounding {
+                break (z, z_cost, diff);
+            }
+            self.step_length = step * self.backtracking_factor;
+            if self.step_length < F::min_positive_value() {
+                return Err(argmin_error!(
+                    ConditionViolated,
+                    "`ProximalGradient`: backtracking failed to find a valid step length."
+                ));
+            }
+        };
+
+        self.residual = diff.l2_norm() / self.step_length;
+
+        let step = xk1.sub(&param);
+        let restart = self.acceleration && self.restart && diff.dot(&step) < float!(0.0);
+        self.y = if self.acceleration && !restart {
+            let t =
+                (float!(1.0) + (float!(1.0) + float!(4.0) * self.t * self.t).sqrt()) / float!(2.0);
+            let momentum = (self.t - float!(1.0)) / t;
+            self.t = t;
+            Some(xk1.scaled_add(&momentum, &step))
+        } else {
+            self.t = float!(1.0);
+            Some(xk1.clone())
+        };
+
+        let cost = xk1_cost + problem.nonsmooth_cost(&xk1)?;
+
+        Ok((
+            state.param(xk1).cost(cost),
+            Some(kv!(
+                "step_length" => self.step_length;
+                "restart" => restart;
+            )),
+        ))
+    }
+
+    fn terminate(&mut self, _state: &IterState<P, G, (), (), (), F>) -> TerminationStatus {
+        if self.residual < self.tol {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+        TerminationStatus::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor, State};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(proximal_gradient, ProximalGradient<Vec<f64>, f64>);
+
+    fn dot(a: &[f64], b: &[f64]) -> f64 {
+        a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+    }
+
+    /// f(x) = 1/2 x^T Q x - c^T x and g(x) = lambda ||x||_1
+    struct Lasso {
+        q: Vec<Vec<f64>>,
+        c: Vec<f64>,
+        lambda: f64,
+    }
+
+    impl CostFunction for Lasso {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            let qp: Vec<f64> = self.q.iter().map(|row| dot(row, p)).collect();
+            Ok(0.5 * dot(p, &qp) - dot(&self.c, p))
+        }
+    }
+
+    impl Gradient for Lasso {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(self
+                .q
+                .iter()
+                .zip(self.c.iter())
+                .map(|(row, c)| dot(row, p) - c)
+                .collect())
+        }
+    }
+
+    impl ProximalOperator for Lasso {
+        type Param = Vec<f64>;
+        type Float = f64;
+
+        fn prox(&self, p: &Self::Param, step: f64) -> Result<Self::Param, Error> {
+            let threshold = self.lambda * step;
+            Ok(p.iter()
+                .map(|x| x.signum() * (x.abs() - threshold).max(0.0))
+                .collect())
+        }
+
+        fn nonsmooth_cost(&self, p: &Self::Param) -> Result<f64, Error> {
+            Ok(self.lambda * p.iter().map(|x| x.abs()).sum::<f64>())
+        }
+    }
+
+    /// Solution: x = (1, 0) with cost -1
+    fn lasso() -> Lasso {
+        Lasso {
+            q: vec![vec![2.0, 1.0], vec![1.0, 2.0]],
+            c: vec![3.0, 0.1],
+            lambda: 1.0,
+        }
+    }
+
+    /// f(x) = (x_0 - 2)^2 + (x_1 + 1)^2 restricted to the box [0, 1]^2
+    ///
+    /// Solution: x = (1, 0)
+    struct Box {}
+
+    impl CostFunction for Box {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok((p[0] - 2.0).powi(2) + (p[1] + 1.0).powi(2))
+        }
+    }
+
+    impl Gradient for Box {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(vec![2.0 * (p[0] - 2.0), 2.0 * (p[1] + 1.0)])
+        }
+    }
+
+    impl ProximalOperator for Box {
+        type Param = Vec<f64>;
+        type Float = f64;
+
+        fn prox(&self, p: &Self::Param, _step: f64) -> Result<Self::Param, Error> {
+            Ok(p.iter().map(|x| x.clamp(0.0, 1.0)).collect())
+        }
+
+        fn nonsmooth_cost(&self, _p: &Self::Param) -> Result<f64, Error> {
+            Ok(0.0)
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let ProximalGradient {
+            step_length,
+            backtracking_factor,
+            acceleration,
+            restart,
+            tol,
+            y,
+            t,
+            residual,
+        }: ProximalGradient<Vec<f64>, f64> = ProximalGradient::new();
+        assert_eq!(step_length.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(backtracking_factor.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert!(acceleration);
+        assert!(restart);
+        assert_eq!(tol.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert!(y.is_none());
+        assert_eq!(t.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert!(residual.is_infinite());
+    }
+
+    #[test]
+    fn test_builder_errors() {
+        let solver = || -> ProximalGradient<Vec<f64>, f64> { ProximalGradient::new() };
+        assert_error!(
+            solver().with_step_length(0.0),
+            ArgminError,
+            "Invalid parameter: \"`ProximalGradient`: step length must be > 0.\""
+        );
+        for factor in [0.0, 1.0] {
+            assert_error!(
+                solver().with_backtracking_factor(factor),
+                ArgminError,
+                "Invalid parameter: \"`ProximalGradient`: backtracking factor must be in (0, 1).\""
+            );
+        }
+        assert_error!(
+            solver().with_tolerance(0.0),
+            ArgminError,
+            "Invalid parameter: \"`ProximalGradient`: tolerance must be > 0.\""
+        );
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut solver: ProximalGradient<Vec<f64>, f64> = ProximalGradient::new();
+        let res = solver.init(&mut Problem::new(lasso()), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`ProximalGradient` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_next_iter_backtracking() {
+        let mut solver: ProximalGradient<Vec<f64>, f64> = ProximalGradient::new();
+        let mut problem = Problem::new(lasso());
+        let (state, _) = solver
+            .init(&mut problem, IterState::new().param(vec![0.0, 0.0]))
+            .unwrap();
+        assert_relative_eq!(state.get_cost(), 0.0);
+        let (state, kv) = solver.next_iter(&mut problem, state).unwrap();
+        // The curvature along the step is 2, therefore a step length of 1/2 is accepted
+        assert_eq!(solver.step_length.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        let param = state.get_param().unwrap();
+        assert_relative_eq!(param[0], 1.0, epsilon = 1e-12);
+        assert_relative_eq!(param[1], 0.0, epsilon = 1e-12);
+        assert_eq!(
+            kv.unwrap()
+                .get("step_length")
+                .unwrap()
+                .get_float()
+                .unwrap()
+                .to_ne_bytes(),
+            0.5f64.to_ne_bytes()
+        );
+        assert_eq!(problem.counts["prox_count"], 2);
+        assert_eq!(problem.counts["nonsmooth_cost_count"], 2);
+    }
+
+    #[test]
+    fn test_lasso() {
+        for (acceleration, restart) in [(true, true), (true, false), (false, false)] {
+            let solver = ProximalGradient::new()
+                .with_acceleration(acceleration)
+                .with_restart(restart);
+            let res = Executor::new(lasso(), solver)
+                .configure(|state| state.param(vec![-1.0, 2.0]).max_iters(1000))
+                .run()
+                .unwrap();
+            assert_eq!(
+                res.state.get_termination_reason(),
+                Some(&TerminationReason::SolverConverged)
+            );
+            let param = res.state.get_best_param().unwrap();
+            assert_relative_eq!(param[0], 1.0, epsilon = 1e-7);
+            assert_relative_eq!(param[1], 0.0);
+            assert_relative_eq!(res.state.get_best_cost(), -1.0, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_acceleration() {
+        let problem = || Lasso {
+            q: vec![vec![1.0, 0.0], vec![0.0, 100.0]],
+            c: vec![10.0, 10.0],
+            lambda: 1e-3,
+        };
+        let run = |solver: ProximalGradient<Vec<f64>, f64>| {
+            Executor::new(problem(), solver)
+                .configure(|state| state.param(vec![0.0, 0.0]).max_iters(10000))
+                .run()
+                .unwrap()
+                .state
+        };
+        let ista = run(ProximalGradient::new().with_acceleration(false));
+        let fista = run(ProximalGradient::new());
+        for state in [&ista, &fista] {
+            let param = state.get_best_param().unwrap();
+            assert_relative_eq!(param[0], 9.999, epsilon = 1e-6);
+            assert_relative_eq!(param[1], 0.09999, epsilon = 1e-6);
+        }
+        assert!(fista.get_iter() < ista.get_iter());
+    }
+
+    #[test]
+    fn test_box() {
+        let res = Executor::new(Box {}, ProximalGradient::new())
+            .configure(|state| state.param(vec![0.5, 0.5]).max_iters(100))
+            .run()
+            .unwrap();
+        let param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(param[0], 1.0);
+        assert_relative_eq!(param[1], 0.0);
+        assert_relative_eq!(res.state.get_best_cost(), 2.0);
+    }
+}
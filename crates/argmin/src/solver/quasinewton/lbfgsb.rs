@@ -0,0 +1,934 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminFloat, CostFunction, Error, Executor, Gradient, IterState, LineSearch,
+    OptimizationResult, Problem, Solver, State, TerminationReason, TerminationStatus, KV,
+};
+use crate::solver::dense::{dot, invert, lu_solve, mat_vec, to_vec};
+use argmin_math::{ArgminGet, ArgminMinMax, ArgminSet, ArgminSize, ArgminSub};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// # Limited-memory BFGS method with bound constraints (L-BFGS-B)
+///
+/// L-BFGS-B extends [`LBFGS`](`crate::solver::quasinewton::LBFGS`) to problems with simple
+/// bounds `lower <= x <= upper` on the parameter vector. Bounds are provided as a tuple
+/// `(lower_bound, upper_bound)` of the same type as the parameter vector, just like for
+/// [`ParticleSwarm`](`crate::solver::particleswarm::ParticleSwarm`). Unbounded components can be
+/// expressed with infinite bounds.
+///
+/// In each iteration the compact representation of the limited-memory BFGS matrix is used to
+/// compute the generalized Cauchy point along the projected steepest descent path. The free
+/// variables at the Cauchy point are then improved by (approximately) minimizing the quadratic
+/// model on the subspace of free variables. The resulting point defines a search direction along
+/// which the line search is performed. Trial points of the line search are projected onto the
+/// feasible box.
+///
+/// An initial guess for the parameter vector is required, which is to be provided via the
+/// [`configure`](`crate::core::Executor::configure`) method of the
+/// [`Executor`](`crate::core::Executor`) (See [`IterState`], in particular [`IterState::param`]).
+/// The initial guess is projected onto the feasible box if necessary.
+///
+/// Two tolerances can be configured, which are both needed in the stopping criteria.
+/// One is a tolerance on the projected gradient (set with
+/// [`with_tolerance_grad`](`LBFGSB::with_tolerance_grad`)): If the infinity norm of the projected
+/// gradient `P(x - g) - x` is below said tolerance, the algorithm stops. It defaults to
+/// `sqrt(EPSILON)`.
+/// The other one is a tolerance on the change of the cost function from one iteration to the
+/// other. If the change is below this tolerance (default: `EPSILON`), the algorithm stops. This
+/// parameter can be set via [`with_tolerance_cost`](`LBFGSB::with_tolerance_cost`).
+///
+/// The infinity norm of the projected gradient is reported to observers as `proj_grad_norm`.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`] and [`Gradient`].
+///
+/// ## Reference
+///
+/// Richard H. Byrd, Peihuang Lu, Jorge Nocedal and Ciyou Zhu (1995). A Limited Memory Algorithm
+/// for Bound Constrained Optimization. SIAM Journal on Scientific Computing 16(5), 1190–1208.
+///
+/// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+/// Springer. ISBN 0-387-30303-0.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct LBFGSB<L, P, F> {
+    /// line search
+    linesearch: L,
+    /// Bounds on parameter space
+    bounds: (P, P),
+    /// m
+    m: usize,
+    /// s_{k-1}
+    s: VecDeque<Vec<F>>,
+    /// y_{k-1}
+    y: VecDeque<Vec<F>>,
+    /// Scaling factor of the BFGS matrix
+    theta: F,
+    /// Tolerance for the stopping criterion based on the projected gradient
+    tol_grad: F,
+    /// Tolerance for the stopping criterion based on the change of the cost stopping criterion
+    tol_cost: F,
+}
+
+impl<L, P, F> LBFGSB<L, P, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`LBFGSB`]
+    ///
+    /// Takes a line search, the bounds on the search space and the history size `m` as inputs.
+    /// `bounds` is a tuple `(lower_bound, upper_bound)`, where `lower_bound` and `upper_bound`
+    /// are of the same type as the parameter vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::LBFGSB;
+    /// # let linesearch = ();
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let lbfgsb: LBFGSB<_, Vec<f64>, f64> = LBFGSB::new(linesearch, (lower_bound, upper_bound), 5);
+    /// ```
+    pub fn new(linesearch: L, bounds: (P, P), m: usize) -> Self {
+        LBFGSB {
+            linesearch,
+            bounds,
+            m,
+            s: VecDeque::with_capacity(m),
+            y: VecDeque::with_capacity(m),
+            theta: float!(1.0),
+            tol_grad: F::epsilon().sqrt(),
+            tol_cost: F::epsilon(),
+        }
+    }
+
+    /// The algorithm stops if the infinity norm of the projected gradient is below `tol_grad`.
+    ///
+    /// The provided value must be non-negative. Defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::LBFGSB;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// # let bounds = (vec![-1.0, -1.0], vec![1.0, 1.0]);
+    /// let lbfgsb: LBFGSB<_, Vec<f64>, f64> =
+    ///     LBFGSB::new(linesearch, bounds, 3).with_tolerance_grad(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance_grad(mut self, tol_grad: F) -> Result<Self, Error> {
+        if tol_grad < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`L-BFGS-B`: gradient tolerance must be >= 0."
+            ));
+        }
+        self.tol_grad = tol_grad;
+        Ok(self)
+    }
+
+    /// Sets tolerance for the stopping criterion based on the change of the cost stopping criterion
+    ///
+    /// The provided value must be non-negative. Defaults to `EPSILON`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::LBFGSB;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// # let bounds = (vec![-1.0, -1.0], vec![1.0, 1.0]);
+    /// let lbfgsb: LBFGSB<_, Vec<f64>, f64> =
+    ///     LBFGSB::new(linesearch, bounds, 3).with_tolerance_cost(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance_cost(mut self, tol_cost: F) -> Result<Self, Error> {
+        if tol_cost < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`L-BFGS-B`: cost tolerance must be >= 0."
+            ));
+        }
+        self.tol_cost = tol_cost;
+        Ok(self)
+    }
+}
+
+impl<L, P, F> LBFGSB<L, P, F>
+where
+    P: ArgminGet<usize, F> + ArgminSize<usize>,
+    F: ArgminFloat,
+{
+    /// Checks that the bounds are consistent with each other and with the parameter vector
+    fn check_bounds(&self, param: &P) -> Result<(), Error> {
+        let (lower, upper) = &self.bounds;
+        let n = param.shape();
+        if lower.shape() != n || upper.shape() != n {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`L-BFGS-B`: bounds must be of the same length as the parameter vector."
+            ));
+        }
+        if (0..n).any(|i| lower.get(i) > upper.get(i)) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`L-BFGS-B`: lower bound must not exceed upper bound."
+            ));
+        }
+        Ok(())
+    }
+
+    /// Infinity norm of the projected gradient `P(x - g) - x`
+    fn projected_gradient_norm<G>(&self, param: &P, grad: &G) -> F
+    where
+        G: ArgminGet<usize, F>,
+    {
+        let (lower, upper) = &self.bounds;
+        (0..param.shape())
+            .map(|i| {
+                let x = param.get(i);
+                let step = x - grad.get(i);
+                (step.max(lower.get(i)).min(upper.get(i)) - x).abs()
+            })
+            .fold(float!(0.0), |acc, v| acc.max(v))
+    }
+}
+
+impl<L, P, F> LBFGSB<L, P, F>
+where
+    F: ArgminFloat,
+{
+    /// Row `i` of `W = [Y, theta * S]`
+    fn w_row(&self, i: usize) -> Vec<F> {
+        self.y
+            .iter()
+            .map(|y| y[i])
+            .chain(self.s.iter().map(|s| self.theta * s[i]))
+            .collect()
+    }
+
+    /// Middle matrix `M` of the compact representation `B = theta * I - W M W^T`.
+    fn middle_matrix(&self) -> Option<Vec<Vec<F>>> {
+        let k = self.s.len();
+        let mut k_mat = vec![vec![float!(0.0); 2 * k]; 2 * k];
+        for i in 0..k {
+            for j in 0..k {
+                let sy = dot(&self.s[i], &self.y[j]);
+                if i == j {
+                    k_mat[i][j] = -sy;
+                } else if i > j {
+                    // L (lower triangular part of S^T Y) and its transpose
+                    k_mat[k + i][j] = sy;
+                    k_mat[j][k + i] = sy;
+                }
+                k_mat[k + i][k + j] = self.theta * dot(&self.s[i], &self.s[j]);
+            }
+        }
+        invert(k_mat)
+    }
+
+    /// Computes the generalized Cauchy point and the corresponding vector `c = W^T (x_cp - x)`.
+    fn cauchy_point(
+        &self,
+        x: &[F],
+        g: &[F],
+        lower: &[F],
+        upper: &[F],
+        m_mat: &[Vec<F>],
+    ) -> (Vec<F>, Vec<F>) {
+        let n = x.len();
+        let k2 = 2 * self.s.len();
+        let zero = float!(0.0);
+
+        // breakpoints and initial direction
+        let mut t = vec![F::infinity(); n];
+        let mut d = vec![zero; n];
+        for i in 0..n {
+            if g[i] < zero {
+                t[i] = (x[i] - upper[i]) / g[i];
+            } else if g[i] > zero {
+                t[i] = (x[i] - lower[i]) / g[i];
+            }
+            if t[i] > zero {
+                d[i] = -g[i];
+            }
+        }
+
+        let mut xcp = x.to_vec();
+        let mut c = vec![zero; k2];
+        let mut p = vec![zero; k2];
+        for (i, &di) in d.iter().enumerate() {
+            if t[i] > zero {
+                let w = self.w_row(i);
+                for (pj, wj) in p.iter_mut().zip(w.iter()) {
+                    *pj = *pj + *wj * di;
+                }
+            }
+        }
+
+        let mut f1 = -dot(&d, &d);
+        if f1 >= zero {
+            return (xcp, c);
+        }
+        let mp = mat_vec(m_mat, &p);
+        let f2_org = -self.theta * f1 - dot(&p, &mp);
+        let mut f2 = f2_org;
+        let mut dt_min = -f1 / f2;
+        let mut t_old = zero;
+
+        let mut order: Vec<usize> = (0..n)
+            .filter(|&i| t[i] > zero && t[i].is_finite())
+            .collect();
+        order.sort_by(|&a, &b| t[a].partial_cmp(&t[b]).unwrap_or(std::cmp::Ordering::Equal));
+
+        let mut reached = vec![false; n];
+        for &b in order.iter() {
+            let dt = t[b] - t_old;
+            if dt_min < dt {
+                break;
+            }
+            // variable b hits its bound
+            xcp[b] = if d[b] > zero { upper[b] } else { lower[b] };
+            reached[b] = true;
+            let zb = xcp[b] - x[b];
+            for (cj, pj) in c.iter_mut().zip(p.iter()) {
+                *cj = *cj + dt * *pj;
+            }
+            let gb = g[b];
+            let wb = self.w_row(b);
+            let mc = mat_vec(m_mat, &c);
+            let mp = mat_vec(m_mat, &p);
+            let mw = mat_vec(m_mat, &wb);
+            f1 = f1 + dt * f2 + gb * gb + self.theta * gb * zb - gb * dot(&wb, &mc);
+            f2 = f2
+                - self.theta * gb * gb
+                - float!(2.0) * gb * dot(&wb, &mp)
+                - gb * gb * dot(&wb, &mw);
+            f2 = f2.max(F::epsilon() * f2_org);
+            for (pj, wj) in p.iter_mut().zip(wb.iter()) {
+                *pj = *pj + gb * *wj;
+            }
+            d[b] = zero;
+            dt_min = -f1 / f2;
+            t_old = t[b];
+        }
+
+        let dt_min = dt_min.max(zero);
+        let t_old = t_old + dt_min;
+        for i in 0..n {
+            if !reached[i] && t[i] > zero {
+                xcp[i] = x[i] + t_old * d[i];
+            }
+        }
+        for (cj, pj) in c.iter_mut().zip(p.iter()) {
+            *cj = *cj + dt_min * *pj;
+        }
+        (xcp, c)
+    }
+
+    /// Minimizes the quadratic model over the free variables at the Cauchy point (direct primal
+    /// method) and truncates the step such that it stays within the bounds.
+    #[allow(clippy::too_many_arguments)]
+    fn subspace_minimization(
+        &self,
+        x: &[F],
+        g: &[F],
+        lower: &[F],
+        upper: &[F],
+        xcp: &[F],
+        c: &[F],
+        m_mat: &[Vec<F>],
+    ) -> Vec<F> {
+        let free: Vec<usize> = (0..x.len())
+            .filter(|&i| xcp[i] > lower[i] && xcp[i] < upper[i])
+            .collect();
+        if free.is_empty() {
+            return xcp.to_vec();
+        }
+        let k2 = 2 * self.s.len();
+        let theta = self.theta;
+        let mc = mat_vec(m_mat, c);
+        let w_free: Vec<Vec<F>> = free.iter().map(|&i| self.w_row(i)).collect();
+
+        // reduced gradient of the quadratic model at the Cauchy point
+        let rc: Vec<F> = free
+            .iter()
+            .zip(w_free.iter())
+            .map(|(&i, w)| g[i] + theta * (xcp[i] - x[i]) - dot(w, &mc))
+            .collect();
+
+        let mut du: Vec<F> = rc.iter().map(|&r| -r / theta).collect();
+        if k2 > 0 {
+            let mut v = vec![float!(0.0); k2];
+            for (w, &r) in w_free.iter().zip(rc.iter()) {
+                for (vj, &wj) in v.iter_mut().zip(w.iter()) {
+                    *vj = *vj + wj * r;
+                }
+            }
+            let v = mat_vec(m_mat, &v);
+            let mut wtw = vec![vec![float!(0.0); k2]; k2];
+            for w in w_free.iter() {
+                for a in 0..k2 {
+                    for b in 0..k2 {
+                        wtw[a][b] = wtw[a][b] + w[a] * w[b];
+                    }
+                }
+            }
+            let mwtw = m_mat
+                .iter()
+                .map(|row| {
+                    (0..k2)
+                        .map(|b| (0..k2).fold(float!(0.0), |acc, a| acc + row[a] * wtw[a][b]))
+                        .collect::<Vec<F>>()
+                })
+                .collect::<Vec<_>>();
+            let n_mat = (0..k2)
+                .map(|a| {
+                    (0..k2)
+                        .map(|b| {
+                            let id = if a == b { float!(1.0) } else { float!(0.0) };
+                            id - mwtw[a][b] / theta
+                        })
+                        .collect()
+                })
+                .collect();
+            if let Some(v) = lu_solve(n_mat, v) {
+                for (dui, w) in du.iter_mut().zip(w_free.iter()) {
+                    *dui = *dui - dot(w, &v) / (theta * theta);
+                }
+            }
+        }
+
+        // largest step along du which keeps the free variables within the bounds
+        let mut alpha = float!(1.0);
+        for (&i, &dui) in free.iter().zip(du.iter()) {
+            if dui > float!(0.0) {
+                alpha = alpha.min((upper[i] - xcp[i]) / dui);
+            } else if dui < float!(0.0) {
+                alpha = alpha.min((lower[i] - xcp[i]) / dui);
+            }
+        }
+
+        let mut xbar = xcp.to_vec();
+        for (&i, &dui) in free.iter().zip(du.iter()) {
+            xbar[i] = xcp[i] + alpha * dui;
+        }
+        xbar
+    }
+}
+
+/// Wrapper problem which projects parameter vectors onto the feasible box before evaluating the
+/// cost function and gradient.
+struct BoxProjectionProblem<O, P> {
+    problem: O,
+    lower: P,
+    upper: P,
+}
+
+impl<O, P> BoxProjectionProblem<O, P>
+where
+    P: ArgminMinMax,
+{
+    fn project(&self, param: &P) -> P {
+        P::min(&P::max(param, &self.lower), &self.upper)
+    }
+}
+
+impl<O, P, F> CostFunction for BoxProjectionProblem<O, P>
+where
+    O: CostFunction<Param = P, Output = F>,
+    P: ArgminMinMax,
+{
+    type Param = P;
+    type Output = F;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        self.problem.cost(&self.project(param))
+    }
+}
+
+impl<O, P, G> Gradient for BoxProjectionProblem<O, P>
+where
+    O: Gradient<Param = P, Gradient = G>,
+    P: ArgminMinMax,
+{
+    type Param = P;
+    type Gradient = G;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        self.problem.gradient(&self.project(param))
+    }
+}
+
+impl<O, L, P, G, F> Solver<O, IterState<P, G, (), (), (), F>> for LBFGSB<L, P, F>
+where
+    O: CostFunction<Param = P, Output = F> + Gradient<Param = P, Gradient = G>,
+    P: Clone
+        + ArgminSub<P, P>
+        + ArgminGet<usize, F>
+        + ArgminSet<usize, F>
+        + ArgminSize<usize>
+        + ArgminMinMax,
+    G: Clone + ArgminGet<usize, F>,
+    L: Clone
+        + LineSearch<P, F>
+        + Solver<BoxProjectionProblem<O, P>, IterState<P, G, (), (), (), F>>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "L-BFGS-B";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, (), (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`L-BFGS-B` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        self.check_bounds(&param)?;
+
+        let (lower, upper) = &self.bounds;
+        let feasible = (0..param.shape()).all(|i| {
+            let x = param.get(i);
+            lower.get(i) <= x && x <= upper.get(i)
+        });
+        let projected = P::min(&P::max(&param, lower), upper);
+
+        let cost = state.get_cost();
+        let cost = if cost.is_infinite() || !feasible {
+            problem.cost(&projected)?
+        } else {
+            cost
+        };
+
+        let grad = match state.take_gradient() {
+            Some(grad) if feasible => grad,
+            _ => problem.gradient(&projected)?,
+        };
+
+        let proj_grad_norm = self.projected_gradient_norm(&projected, &grad);
+
+        Ok((
+            state.param(projected).cost(cost).gradient(grad),
+            Some(kv!("proj_grad_norm" => proj_grad_norm;)),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, (), (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`L-BFGS-B`: Parameter vector in state not set."
+        ))?;
+        let cur_cost = state.get_cost();
+        let prev_grad = state.take_gradient().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`L-BFGS-B`: Gradient in state not set."
+        ))?;
+
+        let x = to_vec(&param);
+        let g: Vec<F> = (0..x.len()).map(|i| prev_grad.get(i)).collect();
+        let lower = to_vec(&self.bounds.0);
+        let upper = to_vec(&self.bounds.1);
+
+        // Compute the point which minimizes the quadratic model within the bounds. If the
+        // resulting direction is not a descent direction, the memory is discarded and the
+        // computation is repeated with the projected steepest descent direction.
+        let mut xbar = None;
+        for _ in 0..2 {
+            let m_mat = match self.middle_matrix() {
+                Some(m_mat) => m_mat,
+                None => {
+                    self.s.clear();
+                    self.y.clear();
+                    self.theta = float!(1.0);
+                    continue;
+                }
+            };
+            let (xcp, c) = self.cauchy_point(&x, &g, &lower, &upper, &m_mat);
+            let candidate = self.subspace_minimization(&x, &g, &lower, &upper, &xcp, &c, &m_mat);
+            let dg = candidate
+                .iter()
+                .zip(x.iter())
+                .zip(g.iter())
+                .fold(float!(0.0), |acc, ((&xb, &xi), &gi)| acc + (xb - xi) * gi);
+            if dg < float!(0.0) {
+                xbar = Some(candidate);
+                break;
+            }
+            self.s.clear();
+            self.y.clear();
+            self.theta = float!(1.0);
+        }
+
+        let xbar = match xbar {
+            Some(xbar) => xbar,
+            None => {
+                // No descent direction within the bounds exists: x is (numerically) stationary.
+                return Ok((
+                    state
+                        .param(param)
+                        .cost(cur_cost)
+                        .gradient(prev_grad)
+                        .terminate_with(TerminationReason::SolverConverged),
+                    None,
+                ));
+            }
+        };
+
+        let mut xbar_p = param.clone();
+        for (i, v) in xbar.into_iter().enumerate() {
+            xbar_p.set(i, v);
+        }
+        let d = xbar_p.sub(&param);
+
+        self.linesearch.search_direction(d);
+
+        let line_problem = BoxProjectionProblem {
+            problem: problem.take_problem().unwrap(),
+            lower: self.bounds.0.clone(),
+            upper: self.bounds.1.clone(),
+        };
+
+        // Run line search
+        let linesearch_result = Executor::new(line_problem, self.linesearch.clone())
+            .configure(|config| {
+                config
+                    .param(param.clone())
+                    .gradient(prev_grad.clone())
+                    .cost(cur_cost)
+            })
+            .ctrlc(false)
+            .run();
+
+        let OptimizationResult {
+            problem: mut line_problem,
+            state: mut linesearch_state,
+            ..
+        } = match linesearch_result {
+            Ok(res) => res,
+            Err(e) => {
+                return Ok((
+                    state.terminate_with(TerminationReason::SolverExit(format!(
+                        "Line search terminated with: '{}'",
+                        e,
+                    ))),
+                    Some(kv!("theta" => self.theta;)),
+                ))
+            }
+        };
+
+        // take back problem and take care of function evaluation counts
+        let internal_line_problem = line_problem.take_problem().unwrap();
+        let xk1 = internal_line_problem.project(&linesearch_state.take_param().unwrap());
+        let next_cost = linesearch_state.get_cost();
+        problem.problem = Some(internal_line_problem.problem);
+        problem.consume_func_counts(line_problem);
+
+        let grad = problem.gradient(&xk1)?;
+
+        let s: Vec<F> = to_vec(&xk1)
+            .into_iter()
+            .zip(x.iter())
+            .map(|(a, &b)| a - b)
+            .collect();
+        let y: Vec<F> = (0..s.len()).map(|i| grad.get(i) - g[i]).collect();
+        let sy = dot(&s, &y);
+        let yy = dot(&y, &y);
+        // Only update the memory if the curvature condition holds sufficiently
+        if sy > F::epsilon() * yy {
+            if self.s.len() >= self.m {
+                self.s.pop_front();
+                self.y.pop_front();
+            }
+            self.s.push_back(s);
+            self.y.push_back(y);
+            self.theta = yy / sy;
+        }
+
+        let proj_grad_norm = self.projected_gradient_norm(&xk1, &grad);
+
+        Ok((
+            state.param(xk1).cost(next_cost).gradient(grad),
+            Some(kv!(
+                "theta" => self.theta;
+                "proj_grad_norm" => proj_grad_norm;
+            )),
+        ))
+    }
+
+    fn terminate(&mut self, state: &IterState<P, G, (), (), (), F>) -> TerminationStatus {
+        if let (Some(param), Some(grad)) = (state.get_param(), state.get_gradient()) {
+            if self.projected_gradient_norm(param, grad) <= self.tol_grad {
+                return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+            }
+        }
+        if (state.get_prev_cost() - state.get_cost()).abs() < self.tol_cost {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+        TerminationStatus::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{test_utils::TestProblem, ArgminError, IterState, State};
+    use crate::solver::linesearch::MoreThuenteLineSearch;
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(
+        lbfgsb,
+        LBFGSB<MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64>, Vec<f64>, f64>
+    );
+
+    #[derive(Clone, Default)]
+    struct Rosenbrock {}
+
+    impl CostFunction for Rosenbrock {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(argmin_testfunctions::rosenbrock(p, 1.0, 100.0))
+        }
+    }
+
+    impl Gradient for Rosenbrock {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(argmin_testfunctions::rosenbrock_derivative(p, 1.0, 100.0))
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        #[derive(Eq, PartialEq, Debug)]
+        struct MyFakeLineSearch {}
+
+        let lbfgsb: LBFGSB<_, Vec<f64>, f64> =
+            LBFGSB::new(MyFakeLineSearch {}, (vec![-1.0, -2.0], vec![1.0, 2.0]), 3);
+        let LBFGSB {
+            linesearch,
+            bounds,
+            m,
+            s,
+            y,
+            theta,
+            tol_grad,
+            tol_cost,
+        } = lbfgsb;
+
+        assert_eq!(linesearch, MyFakeLineSearch {});
+        assert_eq!(bounds, (vec![-1.0, -2.0], vec![1.0, 2.0]));
+        assert_eq!(m, 3);
+        assert!(s.capacity() >= 3);
+        assert!(y.capacity() >= 3);
+        assert_eq!(theta.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(tol_grad.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert_eq!(tol_cost.to_ne_bytes(), f64::EPSILON.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_with_tolerance_grad() {
+        #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+        struct MyFakeLineSearch {}
+
+        // correct parameters
+        for tol in [1e-6, 0.0, 1e-2, 1.0, 2.0] {
+            let lbfgsb: LBFGSB<_, Vec<f64>, f64> =
+                LBFGSB::new(MyFakeLineSearch {}, (vec![-1.0], vec![1.0]), 3);
+            let res = lbfgsb.with_tolerance_grad(tol);
+            assert!(res.is_ok());
+
+            let nm = res.unwrap();
+            assert_eq!(nm.tol_grad.to_ne_bytes(), tol.to_ne_bytes());
+        }
+
+        // incorrect parameters
+        for tol in [-f64::EPSILON, -1.0, -100.0, -42.0] {
+            let lbfgsb: LBFGSB<_, Vec<f64>, f64> =
+                LBFGSB::new(MyFakeLineSearch {}, (vec![-1.0], vec![1.0]), 3);
+            let res = lbfgsb.with_tolerance_grad(tol);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`L-BFGS-B`: gradient tolerance must be >= 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_with_tolerance_cost() {
+        #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+        struct MyFakeLineSearch {}
+
+        // correct parameters
+        for tol in [1e-6, 0.0, 1e-2, 1.0, 2.0] {
+            let lbfgsb: LBFGSB<_, Vec<f64>, f64> =
+                LBFGSB::new(MyFakeLineSearch {}, (vec![-1.0], vec![1.0]), 3);
+            let res = lbfgsb.with_tolerance_cost(tol);
+            assert!(res.is_ok());
+
+            let nm = res.unwrap();
+            assert_eq!(nm.tol_cost.to_ne_bytes(), tol.to_ne_bytes());
+        }
+
+        // incorrect parameters
+        for tol in [-f64::EPSILON, -1.0, -100.0, -42.0] {
+            let lbfgsb: LBFGSB<_, Vec<f64>, f64> =
+                LBFGSB::new(MyFakeLineSearch {}, (vec![-1.0], vec![1.0]), 3);
+            let res = lbfgsb.with_tolerance_cost(tol);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`L-BFGS-B`: cost tolerance must be >= 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_init() {
+        let linesearch: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> =
+            MoreThuenteLineSearch::new();
+        let mut lbfgsb: LBFGSB<_, Vec<f64>, f64> =
+            LBFGSB::new(linesearch, (vec![-1.0, 0.0], vec![1.0, 2.0]), 3);
+
+        // Forgot to initialize the parameter vector
+        let state: IterState<Vec<f64>, Vec<f64>, (), (), (), f64> = IterState::new();
+        let res = lbfgsb.init(&mut Problem::new(TestProblem::new()), state);
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`L-BFGS-B` requires an initial parameter vector. Please ",
+                "provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+
+        // Wrong length of parameter vector
+        let state: IterState<Vec<f64>, Vec<f64>, (), (), (), f64> =
+            IterState::new().param(vec![0.0, 0.0, 0.0]);
+        let res = lbfgsb.init(&mut Problem::new(TestProblem::new()), state);
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`L-BFGS-B`: bounds must be of the same length as the ",
+                "parameter vector.\""
+            )
+        );
+
+        // Infeasible initial guess is projected
+        let state: IterState<Vec<f64>, Vec<f64>, (), (), (), f64> =
+            IterState::new().param(vec![-3.0, 1.0]).cost(1234.0);
+        let (mut state_out, kv) = lbfgsb
+            .init(&mut Problem::new(TestProblem::new()), state)
+            .unwrap();
+        assert!(kv.is_some());
+        assert_eq!(state_out.take_param().unwrap(), vec![-1.0, 1.0]);
+        assert_eq!(state_out.take_gradient().unwrap(), vec![-1.0, 1.0]);
+        assert_eq!(state_out.get_cost().to_ne_bytes(), 1.0f64.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_init_inconsistent_bounds() {
+        let linesearch: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> =
+            MoreThuenteLineSearch::new();
+        let mut lbfgsb: LBFGSB<_, Vec<f64>, f64> =
+            LBFGSB::new(linesearch, (vec![-1.0, 3.0], vec![1.0, 2.0]), 3);
+        let state: IterState<Vec<f64>, Vec<f64>, (), (), (), f64> =
+            IterState::new().param(vec![0.0, 0.0]);
+        let res = lbfgsb.init(&mut Problem::new(TestProblem::new()), state);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`L-BFGS-B`: lower bound must not exceed upper bound.\""
+        );
+    }
+
+    #[test]
+    fn test_cauchy_point_steepest_descent() {
+        // Without memory the Cauchy point follows the projected steepest descent path
+        let lbfgsb: LBFGSB<(), Vec<f64>, f64> =
+            LBFGSB::new((), (vec![-1.0, -1.0], vec![1.0, 1.0]), 3);
+        let m_mat = lbfgsb.middle_matrix().unwrap();
+        let (xcp, c) = lbfgsb.cauchy_point(
+            &[0.0, 0.0],
+            &[4.0, -0.5],
+            &[-1.0, -1.0],
+            &[1.0, 1.0],
+            &m_mat,
+        );
+        assert!(c.is_empty());
+        assert_relative_eq!(xcp[0], -1.0, epsilon = f64::EPSILON);
+        assert_relative_eq!(xcp[1], 0.5, epsilon = f64::EPSILON);
+    }
+
+    #[test]
+    fn test_unconstrained_minimum() {
+        let linesearch = MoreThuenteLineSearch::new();
+        let solver = LBFGSB::new(linesearch, (vec![-5.0, -5.0], vec![5.0, 5.0]), 7);
+        let res = Executor::new(Rosenbrock {}, solver)
+            .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(100))
+            .run()
+            .unwrap();
+        let param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(param[0], 1.0, epsilon = 1e-4);
+        assert_relative_eq!(param[1], 1.0, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn test_active_bound() {
+        let linesearch = MoreThuenteLineSearch::new();
+        let solver = LBFGSB::new(linesearch, (vec![-2.0, -2.0], vec![0.8, 2.0]), 7);
+        let res = Executor::new(Rosenbrock {}, solver)
+            .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(100))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        let param = res.state.get_best_param().unwrap();
+        // The first component is stuck at its upper bound while the second one is free.
+        assert_relative_eq!(param[0], 0.8, epsilon = 1e-6);
+        assert_relative_eq!(param[1], 0.64, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_corner_solution() {
+        let linesearch = MoreThuenteLineSearch::new();
+        let solver = LBFGSB::new(linesearch, (vec![2.0, -1.0], vec![3.0, 2.0]), 7);
+        let res = Executor::new(Rosenbrock {}, solver)
+            .configure(|state| state.param(vec![2.5, 0.0]).max_iters(100))
+            .run()
+            .unwrap();
+        let param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(param[0], 2.0, epsilon = f64::EPSILON);
+        assert_relative_eq!(param[1], 2.0, epsilon = f64::EPSILON);
+    }
+}
@@ -0,0 +1,42 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Samples of distributions which are not provided by `rand` itself
+//!
+//! Shared by the stochastic solvers, which draw all random numbers from their own random number
+//! generator such that runs are reproducible for a seeded generator.
+
+use crate::core::ArgminFloat;
+use rand::Rng;
+
+/// Draws a sample of the standard normal distribution (Box-Muller transform)
+pub(crate) fn standard_normal<F: ArgminFloat, R: Rng>(rng: &mut R) -> F {
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen::<f64>();
+    F::from_f64((-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_standard_normal() {
+        let mut rng = rand_xoshiro::Xoroshiro128Plus::seed_from_u64(42);
+        let n = 100_000;
+        let samples: Vec<f64> = (0..n).map(|_| standard_normal(&mut rng)).collect();
+        assert!(samples.iter().all(|x| x.is_finite()));
+        let mean = samples.iter().sum::<f64>() / n as f64;
+        let var = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n as f64;
+        assert!(mean.abs() < 0.01);
+        assert!((var - 1.0).abs() < 0.02);
+        // P(|x| <= 1) is about 0.6827
+        let within = samples.iter().filter(|x| x.abs() <= 1.0).count() as f64 / n as f64;
+        assert!((within - 0.6827).abs() < 0.01);
+    }
+}
@@ -0,0 +1,199 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::{Bracket, BracketState, RootTolerance};
+use crate::core::{
+    ArgminFloat, CostFunction, Error, IterState, Problem, Solver, State, TerminationReason, KV,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Bisection
+///
+/// Bracketing root finding method which halves the bracket in every iteration by evaluating the
+/// function at its midpoint and keeping the half in which the function changes its sign. It
+/// converges linearly, but is guaranteed to find a root of a continuous function.
+///
+/// The initial [`Bracket`] must satisfy that `f(lower)` and `f(upper)` have different signs. The
+/// width of the bracket is reported as KV metric `bracket_width`.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`].
+///
+/// ## Reference
+///
+/// <https://en.wikipedia.org/wiki/Bisection_method>
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Bisection<F> {
+    /// Initial bracket
+    bracket: Bracket<F>,
+    /// Tolerances
+    tol: RootTolerance<F>,
+    /// Current bracket
+    state: Option<BracketState<F>>,
+}
+
+impl<F: ArgminFloat> Bisection<F> {
+    /// Construct a new instance of `Bisection`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::rootfinding::{Bisection, Bracket};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let bisection = Bisection::new(Bracket::new(0.0, 2.0)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(bracket: Bracket<F>) -> Self {
+        Bisection {
+            bracket,
+            tol: RootTolerance::new(),
+            state: None,
+        }
+    }
+
+    /// Set tolerances
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::rootfinding::{Bisection, Bracket, RootTolerance};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let bisection = Bisection::new(Bracket::new(0.0, 2.0)?)
+    ///     .with_tolerance(RootTolerance::new().with_x_abs_tol(1e-6)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_tolerance(mut self, tol: RootTolerance<F>) -> Self {
+        self.tol = tol;
+        self
+    }
+}
+
+impl<O, F> Solver<O, IterState<F, (), (), (), (), F>> for Bisection<F>
+where
+    O: CostFunction<Param = F, Output = F>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Bisection";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<F, (), (), (), (), F>,
+    ) -> Result<(IterState<F, (), (), (), (), F>, Option<KV>), Error> {
+        let bracket_state = BracketState::new("Bisection", problem, &self.bracket)?;
+        self.state = Some(bracket_state);
+        Ok(bracket_state.to_state(state, &self.tol))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<F, (), (), (), (), F>,
+    ) -> Result<(IterState<F, (), (), (), (), F>, Option<KV>), Error> {
+        let bracket_state = self.state.as_mut().unwrap();
+        let x = bracket_state.a + float!(0.5) * (bracket_state.b - bracket_state.a);
+        let fx = problem.cost(&x)?;
+        if bracket_state.update(x, fx).is_none() {
+            return Ok((state.terminate_with(TerminationReason::BracketLost), None));
+        }
+        Ok(bracket_state.to_state(state, &self.tol))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::rootfinding::tests::{Cubic, CUBIC_ROOT};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(bisection, Bisection<f64>);
+
+    #[test]
+    fn test_new() {
+        let bracket = Bracket::new(0.0, 2.0).unwrap();
+        let Bisection {
+            bracket: b,
+            tol,
+            state,
+        } = Bisection::new(bracket);
+        assert_eq!(b, bracket);
+        assert_eq!(tol, RootTolerance::new());
+        assert!(state.is_none());
+    }
+
+    #[test]
+    fn test_cubic() {
+        let solver = Bisection::new(Bracket::new(0.0, 3.0).unwrap());
+        let res = Executor::new(Cubic {}, solver)
+            .configure(|state| state.max_iters(100))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state().get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        assert_relative_eq!(
+            *res.state().get_best_param().unwrap(),
+            CUBIC_ROOT,
+            epsilon = 1e-14
+        );
+        // Width of the bracket is halved in every iteration
+        assert!(res.state().get_iter() <= 55);
+    }
+
+    #[test]
+    fn test_tolerance() {
+        let tol = RootTolerance::new().with_x_abs_tol(1e-3).unwrap();
+        let solver = Bisection::new(Bracket::new(0.0, 3.0).unwrap()).with_tolerance(tol);
+        let res = Executor::new(Cubic {}, solver)
+            .configure(|state| state.max_iters(100))
+            .run()
+            .unwrap();
+        assert_eq!(res.state().get_iter(), 11);
+        assert_relative_eq!(
+            *res.state().get_best_param().unwrap(),
+            CUBIC_ROOT,
+            epsilon = 2e-3
+        );
+    }
+
+    #[test]
+    fn test_root_at_bound() {
+        let tol = RootTolerance::new().with_f_abs_tol(1e-12).unwrap();
+        let solver = Bisection::new(Bracket::new(CUBIC_ROOT, 3.0).unwrap()).with_tolerance(tol);
+        let res = Executor::new(Cubic {}, solver)
+            .configure(|state| state.max_iters(100))
+            .run()
+            .unwrap();
+        assert_eq!(res.state().get_iter(), 0);
+        assert_eq!(
+            res.state().get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+    }
+
+    #[test]
+    fn test_wrong_sign() {
+        let solver = Bisection::new(Bracket::new(3.0, 4.0).unwrap());
+        let res = Executor::new(Cubic {}, solver).run();
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`Bisection`: f(lower) and f(upper) must have different signs.\""
+        );
+    }
+}
@@ -0,0 +1,263 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::{initial_guess, open_step, Bracket, RootTolerance};
+use crate::core::{
+    ArgminFloat, CostFunction, Error, Gradient, Hessian, IterState, Problem, Solver, State,
+    TerminationReason, KV,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Halley's method
+///
+/// Open root finding method which additionally uses the second derivative,
+/// `x_{k+1} = x_k - 2 f f' / (2 f'^2 - f f'')`. It converges cubically close to a simple root.
+///
+/// The first derivative `f'` is provided via the [`Gradient`] trait and the second derivative
+/// `f''` via the [`Hessian`] trait. If `f'` vanishes (or is not finite), the solver terminates with
+/// [`TerminationReason::DerivativeVanished`]. If the denominator of the step vanishes, a
+/// Newton-Raphson step is taken instead. Optionally, a [`Bracket`] can be provided via
+/// [`with_bracket`](`Halley::with_bracket`); if a step leaves it, the solver terminates with
+/// [`TerminationReason::BracketLost`]. The length of the last step is reported as KV metric
+/// `step`.
+///
+/// An initial guess must be provided via `Executor`s `configure` method.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`], [`Gradient`] and
+/// [`Hessian`].
+///
+/// ## Reference
+///
+/// <https://en.wikipedia.org/wiki/Halley%27s_method>
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Halley<F> {
+    /// Tolerances
+    tol: RootTolerance<F>,
+    /// Optional bracket
+    bracket: Option<Bracket<F>>,
+    /// Current estimate of the root
+    x: F,
+    /// Function value at current estimate
+    fx: F,
+}
+
+impl<F: ArgminFloat> Halley<F> {
+    /// Construct a new instance of `Halley`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::rootfinding::Halley;
+    /// let halley: Halley<f64> = Halley::new();
+    /// ```
+    pub fn new() -> Self {
+        Halley {
+            tol: RootTolerance::new(),
+            bracket: None,
+            x: F::nan(),
+            fx: F::nan(),
+        }
+    }
+
+    /// Set tolerances
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::rootfinding::{Halley, RootTolerance};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let halley: Halley<f64> =
+    ///     Halley::new().with_tolerance(RootTolerance::new().with_f_abs_tol(1e-10)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_tolerance(mut self, tol: RootTolerance<F>) -> Self {
+        self.tol = tol;
+        self
+    }
+
+    /// Restrict the iterates to a bracket
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::rootfinding::{Bracket, Halley};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let halley = Halley::new().with_bracket(Bracket::new(0.0, 2.0)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_bracket(mut self, bracket: Bracket<F>) -> Self {
+        self.bracket = Some(bracket);
+        self
+    }
+}
+
+impl<F: ArgminFloat> Default for Halley<F> {
+    fn default() -> Self {
+        Halley::new()
+    }
+}
+
+impl<O, F> Solver<O, IterState<F, (), (), (), (), F>> for Halley<F>
+where
+    O: CostFunction<Param = F, Output = F>
+        + Gradient<Param = F, Gradient = F>
+        + Hessian<Param = F, Hessian = F>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Halley";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<F, (), (), (), (), F>,
+    ) -> Result<(IterState<F, (), (), (), (), F>, Option<KV>), Error> {
+        self.x = initial_guess("Halley", &mut state, self.bracket.as_ref())?;
+        self.fx = problem.cost(&self.x)?;
+        let state = state.param(self.x).cost(self.fx.abs());
+        if self.tol.f_converged(self.fx) {
+            Ok((
+                state.terminate_with(TerminationReason::SolverConverged),
+                None,
+            ))
+        } else {
+            Ok((state, None))
+        }
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<F, (), (), (), (), F>,
+    ) -> Result<(IterState<F, (), (), (), (), F>, Option<KV>), Error> {
+        let d1 = problem.gradient(&self.x)?;
+        if d1 == float!(0.0) || !d1.is_finite() {
+            return Ok((
+                state.terminate_with(TerminationReason::DerivativeVanished),
+                None,
+            ));
+        }
+        let d2 = problem.hessian(&self.x)?;
+        let two: F = float!(2.0);
+        let denom = two * d1 * d1 - self.fx * d2;
+        let step = if denom == float!(0.0) || !denom.is_finite() {
+            self.fx / d1
+        } else {
+            two * self.fx * d1 / denom
+        };
+        open_step(
+            problem,
+            state,
+            &self.tol,
+            self.bracket.as_ref(),
+            &mut self.x,
+            &mut self.fx,
+            step,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::rootfinding::tests::{Cubic, Parabola, CUBIC_ROOT};
+    use crate::solver::rootfinding::NewtonRaphson;
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(halley, Halley<f64>);
+
+    #[test]
+    fn test_new() {
+        let Halley {
+            tol,
+            bracket,
+            x,
+            fx,
+        } = Halley::<f64>::new();
+        assert_eq!(tol, RootTolerance::new());
+        assert!(bracket.is_none());
+        assert!(x.is_nan());
+        assert!(fx.is_nan());
+
+        let bracket = Bracket::new(0.0, 2.0).unwrap();
+        let halley = Halley::new().with_bracket(bracket);
+        assert_eq!(halley.bracket, Some(bracket));
+    }
+
+    #[test]
+    fn test_cubic() {
+        let res = Executor::new(Cubic {}, Halley::new())
+            .configure(|state| state.param(4.0).max_iters(100))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state().get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        assert_relative_eq!(
+            *res.state().get_best_param().unwrap(),
+            CUBIC_ROOT,
+            epsilon = 1e-14
+        );
+
+        // Cubic convergence needs fewer iterations than Newton-Raphson
+        let newton = Executor::new(Cubic {}, NewtonRaphson::new())
+            .configure(|state| state.param(4.0).max_iters(100))
+            .run()
+            .unwrap();
+        assert!(res.state().get_iter() < newton.state().get_iter());
+    }
+
+    #[test]
+    fn test_derivative_vanished() {
+        let res = Executor::new(Parabola {}, Halley::new())
+            .configure(|state| state.param(0.0).max_iters(100))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state().get_termination_reason(),
+            Some(&TerminationReason::DerivativeVanished)
+        );
+    }
+
+    #[test]
+    fn test_bracket_lost() {
+        let solver = Halley::new().with_bracket(Bracket::new(0.0, 0.5).unwrap());
+        let res = Executor::new(Parabola {}, solver)
+            .configure(|state| state.param(0.1).max_iters(100))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state().get_termination_reason(),
+            Some(&TerminationReason::BracketLost)
+        );
+    }
+
+    #[test]
+    fn test_initial_guess() {
+        let res = Executor::new(Cubic {}, Halley::new()).run();
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`Halley` requires an initial guess. Please provide an ",
+                "initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+}
@@ -0,0 +1,397 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::{Bracket, BracketState, RootTolerance};
+use crate::core::{
+    ArgminFloat, CostFunction, Error, IterState, Problem, Solver, State, TerminationReason, KV,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # ITP method
+///
+/// Bracketing root finding method (Interpolate, Truncate, Project) which combines the regula falsi
+/// estimate of the root with bisection. The regula falsi estimate is first truncated towards the
+/// midpoint of the bracket by `k1 * width^k2` and then projected onto an interval around the
+/// midpoint whose radius shrinks such that the method never needs more than `n0` iterations more
+/// than bisection. For smooth functions it converges superlinearly.
+///
+/// The accuracy `epsilon` targeted by the method is the tolerance on `x` at the end of the initial
+/// bracket with the largest magnitude and must be positive. Since in floating point arithmetic the
+/// bracket may stop shrinking once the estimate hits the root, the method also converges once the
+/// change of the estimate of the root is below `epsilon`. The width of the bracket is reported as
+/// KV metric `bracket_width`.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`].
+///
+/// ## Reference
+///
+/// I. F. D. Oliveira and R. H. C. Takahashi (2020). An Enhancement of the Bisection Method Average
+/// Performance Preserving Minmax Optimality. ACM Transactions on Mathematical Software 47 (1),
+/// 1–24. DOI: <https://doi.org/10.1145/3423597>
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct ITP<F> {
+    /// Initial bracket
+    bracket: Bracket<F>,
+    /// Tolerances
+    tol: RootTolerance<F>,
+    /// Truncation factor (defaults to `0.2 / width` of the initial bracket)
+    k1: Option<F>,
+    /// Truncation exponent
+    k2: F,
+    /// Slack in the number of iterations compared to bisection
+    n0: u64,
+    /// Current bracket
+    state: Option<BracketState<F>>,
+    /// Targeted accuracy
+    epsilon: F,
+    /// Maximum number of iterations
+    n_max: F,
+    /// Current iteration
+    j: u64,
+    /// Last estimate of the root
+    last_x: F,
+}
+
+impl<F: ArgminFloat> ITP<F> {
+    /// Construct a new instance of `ITP`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::rootfinding::{Bracket, ITP};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let itp = ITP::new(Bracket::new(0.0, 2.0)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(bracket: Bracket<F>) -> Self {
+        ITP {
+            bracket,
+            tol: RootTolerance::new(),
+            k1: None,
+            k2: float!(2.0),
+            n0: 1,
+            state: None,
+            epsilon: F::nan(),
+            n_max: F::nan(),
+            j: 0,
+            last_x: F::nan(),
+        }
+    }
+
+    /// Set tolerances
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::rootfinding::{Bracket, ITP, RootTolerance};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let itp = ITP::new(Bracket::new(0.0, 2.0)?)
+    ///     .with_tolerance(RootTolerance::new().with_x_abs_tol(1e-6)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_tolerance(mut self, tol: RootTolerance<F>) -> Self {
+        self.tol = tol;
+        self
+    }
+
+    /// Set truncation factor `k1`
+    ///
+    /// Must be larger than `0`. Defaults to `0.2 / width` of the initial bracket.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::rootfinding::{Bracket, ITP};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let itp = ITP::new(Bracket::new(0.0, 2.0)?).with_k1(0.1)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_k1(mut self, k1: F) -> Result<Self, Error> {
+        if k1 <= float!(0.0) {
+            return Err(argmin_error!(InvalidParameter, "`ITP`: k1 must be > 0."));
+        }
+        self.k1 = Some(k1);
+        Ok(self)
+    }
+
+    /// Set truncation exponent `k2`
+    ///
+    /// Must be in `[1, 1 + phi)`, where `phi` is the golden ratio. Defaults to `2`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::rootfinding::{Bracket, ITP};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let itp = ITP::new(Bracket::new(0.0, 2.0)?).with_k2(1.5)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_k2(mut self, k2: F) -> Result<Self, Error> {
+        let golden: F = float!((1.0 + 5f64.sqrt()) / 2.0);
+        if k2 < float!(1.0) || k2 >= float!(1.0) + golden {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ITP`: k2 must be in [1, 1 + golden ratio)."
+            ));
+        }
+        self.k2 = k2;
+        Ok(self)
+    }
+
+    /// Set slack `n0` in the number of iterations compared to bisection
+    ///
+    /// Defaults to `1`. With `n0 = 0` the method requires at most as many iterations as bisection.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::rootfinding::{Bracket, ITP};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let itp = ITP::new(Bracket::new(0.0, 2.0)?).with_n0(0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_n0(mut self, n0: u64) -> Self {
+        self.n0 = n0;
+        self
+    }
+}
+
+impl<O, F> Solver<O, IterState<F, (), (), (), (), F>> for ITP<F>
+where
+    O: CostFunction<Param = F, Output = F>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "ITP";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<F, (), (), (), (), F>,
+    ) -> Result<(IterState<F, (), (), (), (), F>, Option<KV>), Error> {
+        let (lower, upper) = (self.bracket.lower(), self.bracket.upper());
+        self.epsilon = self.tol.x_tol(lower.abs().max(upper.abs()));
+        if self.epsilon <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ITP`: tolerance on x must be > 0."
+            ));
+        }
+        let bracket_state = BracketState::new("ITP", problem, &self.bracket)?;
+        self.state = Some(bracket_state);
+        let n_half = ((upper - lower) / (float!(2.0) * self.epsilon))
+            .log2()
+            .ceil()
+            .max(float!(0.0));
+        self.n_max = n_half + F::from_u64(self.n0).unwrap();
+        self.j = 0;
+        self.last_x = F::nan();
+        Ok(bracket_state.to_state(state, &self.tol))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<F, (), (), (), (), F>,
+    ) -> Result<(IterState<F, (), (), (), (), F>, Option<KV>), Error> {
+        let bracket_state = self.state.as_mut().unwrap();
+        let BracketState { a, fa, b, fb } = *bracket_state;
+        let width = bracket_state.width();
+        let k1 = self
+            .k1
+            .unwrap_or(float!(0.2) / (self.bracket.upper() - self.bracket.lower()));
+
+        // Interpolation
+        let x_half = float!(0.5) * (a + b);
+        let x_f = (b * fa - a * fb) / (fa - fb);
+
+        // Truncation
+        let sigma = (x_half - x_f).signum();
+        let delta = k1 * width.powf(self.k2);
+        let x_t = if delta <= (x_half - x_f).abs() {
+            x_f + sigma * delta
+        } else {
+            x_half
+        };
+
+        // Projection
+        let j: F = F::from_u64(self.j).unwrap();
+        let r = (self.epsilon * float!(2.0).powf(self.n_max - j) - float!(0.5) * width)
+            .max(float!(0.0));
+        let x = if (x_t - x_half).abs() <= r {
+            x_t
+        } else {
+            x_half - sigma * r
+        };
+        self.j += 1;
+
+        let fx = problem.cost(&x)?;
+        if bracket_state.update(x, fx).is_none() {
+            return Ok((state.terminate_with(TerminationReason::BracketLost), None));
+        }
+        let step = (x - self.last_x).abs();
+        self.last_x = x;
+        let (state, kv) = bracket_state.to_state(state, &self.tol);
+        if bracket_state.width() <= float!(2.0) * self.epsilon || step <= self.epsilon {
+            Ok((state.terminate_with(TerminationReason::SolverConverged), kv))
+        } else {
+            Ok((state, kv))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::rootfinding::tests::{Cubic, CUBIC_ROOT};
+    use crate::solver::rootfinding::Bisection;
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(itp, ITP<f64>);
+
+    #[test]
+    fn test_new() {
+        let bracket = Bracket::new(0.0f64, 2.0).unwrap();
+        let ITP {
+            bracket: b,
+            tol,
+            k1,
+            k2,
+            n0,
+            state,
+            epsilon,
+            n_max,
+            j,
+            last_x,
+        } = ITP::new(bracket);
+        assert_eq!(b, bracket);
+        assert_eq!(tol, RootTolerance::new());
+        assert!(k1.is_none());
+        assert_eq!(k2.to_ne_bytes(), 2.0f64.to_ne_bytes());
+        assert_eq!(n0, 1);
+        assert!(state.is_none());
+        assert!(epsilon.is_nan());
+        assert!(n_max.is_nan());
+        assert_eq!(j, 0);
+        assert!(last_x.is_nan());
+    }
+
+    #[test]
+    fn test_builders() {
+        let bracket = Bracket::new(0.0f64, 2.0).unwrap();
+        let itp = ITP::new(bracket)
+            .with_k1(0.1)
+            .unwrap()
+            .with_k2(1.5)
+            .unwrap()
+            .with_n0(0);
+        assert_eq!(itp.k1.unwrap().to_ne_bytes(), 0.1f64.to_ne_bytes());
+        assert_eq!(itp.k2.to_ne_bytes(), 1.5f64.to_ne_bytes());
+        assert_eq!(itp.n0, 0);
+
+        for k1 in [0.0, -1.0] {
+            assert_error!(
+                ITP::new(bracket).with_k1(k1),
+                ArgminError,
+                "Invalid parameter: \"`ITP`: k1 must be > 0.\""
+            );
+        }
+        for k2 in [0.9, 2.62, 3.0] {
+            assert_error!(
+                ITP::new(bracket).with_k2(k2),
+                ArgminError,
+                "Invalid parameter: \"`ITP`: k2 must be in [1, 1 + golden ratio).\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_zero_tolerance() {
+        let tol = RootTolerance::new()
+            .with_x_abs_tol(0.0)
+            .unwrap()
+            .with_x_rel_tol(0.0)
+            .unwrap();
+        let solver = ITP::new(Bracket::new(0.0, 3.0).unwrap()).with_tolerance(tol);
+        let res = Executor::new(Cubic {}, solver).run();
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`ITP`: tolerance on x must be > 0.\""
+        );
+    }
+
+    #[test]
+    fn test_cubic() {
+        let bracket = Bracket::new(0.0, 3.0).unwrap();
+        let res = Executor::new(Cubic {}, ITP::new(bracket))
+            .configure(|state| state.max_iters(100))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state().get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        assert_relative_eq!(
+            *res.state().get_best_param().unwrap(),
+            CUBIC_ROOT,
+            epsilon = 1e-14
+        );
+
+        // At most n0 iterations more than bisection, usually far fewer
+        let bisection = Executor::new(Cubic {}, Bisection::new(bracket))
+            .configure(|state| state.max_iters(100))
+            .run()
+            .unwrap();
+        assert!(res.state().get_iter() * 4 < bisection.state().get_iter());
+    }
+
+    #[test]
+    fn test_worst_case() {
+        // Step function, for which interpolation does not help
+        struct Step {}
+
+        impl CostFunction for Step {
+            type Param = f64;
+            type Output = f64;
+
+            fn cost(&self, x: &f64) -> Result<f64, Error> {
+                Ok(if *x < 0.3 { -1.0 } else { 1.0 })
+            }
+        }
+
+        let tol = RootTolerance::new().with_x_abs_tol(1e-10).unwrap();
+        let bracket = Bracket::new(0.0, 1.0).unwrap();
+        let itp = Executor::new(Step {}, ITP::new(bracket).with_tolerance(tol))
+            .configure(|state| state.max_iters(100))
+            .run()
+            .unwrap();
+        let bisection = Executor::new(Step {}, Bisection::new(bracket).with_tolerance(tol))
+            .configure(|state| state.max_iters(100))
+            .run()
+            .unwrap();
+        assert!(itp.state().get_iter() <= bisection.state().get_iter() + 1);
+        assert_relative_eq!(*itp.state().get_param().unwrap(), 0.3, epsilon = 1e-9);
+    }
+}
@@ -0,0 +1,525 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Scalar root finding
+//!
+//! Methods for finding a root `x` of a scalar function `f(x) = 0`, where `f` is given by
+//! [`CostFunction`](`crate::core::CostFunction`).
+//!
+//! Bracketing methods require a [`Bracket`] `[lower, upper]` with `f(lower)` and `f(upper)` of
+//! different signs and shrink it while maintaining this property:
+//!
+//! * [Bisection](`Bisection`)
+//! * [Regula falsi](`RegulaFalsi`), optionally with the Illinois modification
+//! * [ITP method](`ITP`)
+//!
+//! Open methods start from the initial guess provided via `Executor`s `configure` method and
+//! converge faster, but without guarantees:
+//!
+//! * [Newton-Raphson method](`NewtonRaphson`), which uses [`Gradient`](`crate::core::Gradient`)
+//!   as derivative
+//! * [Secant method](`Secant`)
+//! * [Halley's method](`Halley`), which additionally uses [`Hessian`](`crate::core::Hessian`) as
+//!   second derivative
+//!
+//! An optional [`Bracket`] can be provided to open methods as well; they terminate with
+//! [`TerminationReason::BracketLost`] if an iterate would leave it. If the derivative vanishes,
+//! they terminate with [`TerminationReason::DerivativeVanished`].
+//!
+//! All methods share the tolerances in [`RootTolerance`] and report `|f(x)|` as cost function
+//! value, such that the best parameter of the state is the best approximation of the root found.
+//! See also [`BrentRoot`](`crate::solver::brent::BrentRoot`).
+//!
+//! ## References
+//!
+//! \[0\] Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+//! Springer. ISBN 0-387-30303-0.
+//!
+//! \[1\] M. Dowell and P. Jarratt (1971). A modified regula falsi method for computing the root of
+//! an equation. BIT Numerical Mathematics 11, 168–174. DOI: <https://doi.org/10.1007/BF01934364>
+//!
+//! \[2\] I. F. D. Oliveira and R. H. C. Takahashi (2020). An Enhancement of the Bisection Method
+//! Average Performance Preserving Minmax Optimality. ACM Transactions on Mathematical Software
+//! 47 (1), 1–24. DOI: <https://doi.org/10.1145/3423597>
+
+mod bisection;
+mod halley;
+mod itp;
+mod newtonraphson;
+mod regulafalsi;
+mod secant;
+
+pub use self::bisection::*;
+pub use self::halley::*;
+pub use self::itp::*;
+pub use self::newtonraphson::*;
+pub use self::regulafalsi::*;
+pub use self::secant::*;
+
+use crate::core::{
+    ArgminFloat, CostFunction, Error, IterState, Problem, State, TerminationReason, KV,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Interval `[lower, upper]` known to contain a root
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::rootfinding::Bracket;
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let bracket = Bracket::new(0.0, 2.0)?;
+/// assert!(bracket.contains(1.0));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Bracket<F> {
+    /// Lower end of the interval
+    lower: F,
+    /// Upper end of the interval
+    upper: F,
+}
+
+impl<F: ArgminFloat> Bracket<F> {
+    /// Construct a new instance of `Bracket`
+    ///
+    /// `lower` and `upper` must be finite and `lower` must be smaller than `upper`.
+    pub fn new(lower: F, upper: F) -> Result<Self, Error> {
+        if !lower.is_finite() || !upper.is_finite() || lower >= upper {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Bracket`: bounds must be finite and lower bound must be smaller than upper bound."
+            ));
+        }
+        Ok(Bracket { lower, upper })
+    }
+
+    /// Returns the lower end of the interval
+    pub fn lower(&self) -> F {
+        self.lower
+    }
+
+    /// Returns the upper end of the interval
+    pub fn upper(&self) -> F {
+        self.upper
+    }
+
+    /// Returns `true` if `x` lies within the interval
+    pub fn contains(&self, x: F) -> bool {
+        x >= self.lower && x <= self.upper
+    }
+}
+
+/// Tolerances shared by all root finding methods
+///
+/// A method converges once `|f(x)| <= f_abs_tol` or once the root is known up to an accuracy of
+/// `x_abs_tol + x_rel_tol * |x|`. For bracketing methods this is half of the width of the bracket,
+/// for open methods the length of the last step.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::rootfinding::RootTolerance;
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let tol: RootTolerance<f64> = RootTolerance::new()
+///     .with_x_abs_tol(1e-10)?
+///     .with_x_rel_tol(0.0)?
+///     .with_f_abs_tol(1e-12)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct RootTolerance<F> {
+    /// Absolute tolerance on `x`
+    x_abs: F,
+    /// Relative tolerance on `x`
+    x_rel: F,
+    /// Absolute tolerance on `f(x)`
+    f_abs: F,
+}
+
+impl<F: ArgminFloat> RootTolerance<F> {
+    /// Construct a new instance of `RootTolerance`
+    ///
+    /// Defaults to an absolute tolerance on `x` of machine epsilon, a relative tolerance on `x` of
+    /// four times machine epsilon and an absolute tolerance on `f(x)` of `0`.
+    pub fn new() -> Self {
+        RootTolerance {
+            x_abs: F::epsilon(),
+            x_rel: float!(4.0) * F::epsilon(),
+            f_abs: float!(0.0),
+        }
+    }
+
+    /// Set absolute tolerance on `x`
+    ///
+    /// Must not be negative.
+    pub fn with_x_abs_tol(mut self, tol: F) -> Result<Self, Error> {
+        if tol < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`RootTolerance`: absolute tolerance on x must be >= 0."
+            ));
+        }
+        self.x_abs = tol;
+        Ok(self)
+    }
+
+    /// Set relative tolerance on `x`
+    ///
+    /// Must not be negative.
+    pub fn with_x_rel_tol(mut self, tol: F) -> Result<Self, Error> {
+        if tol < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`RootTolerance`: relative tolerance on x must be >= 0."
+            ));
+        }
+        self.x_rel = tol;
+        Ok(self)
+    }
+
+    /// Set absolute tolerance on `f(x)`
+    ///
+    /// Must not be negative.
+    pub fn with_f_abs_tol(mut self, tol: F) -> Result<Self, Error> {
+        if tol < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`RootTolerance`: absolute tolerance on f(x) must be >= 0."
+            ));
+        }
+        self.f_abs = tol;
+        Ok(self)
+    }
+
+    /// Returns the tolerance on `x` at `x`
+    pub fn x_tol(&self, x: F) -> F {
+        self.x_abs + self.x_rel * x.abs()
+    }
+
+    /// Returns `true` if `fx` is small enough to consider `x` a root
+    pub fn f_converged(&self, fx: F) -> bool {
+        fx.abs() <= self.f_abs
+    }
+}
+
+impl<F: ArgminFloat> Default for RootTolerance<F> {
+    fn default() -> Self {
+        RootTolerance::new()
+    }
+}
+
+/// Current interval of a bracketing method, where `f(a)` and `f(b)` have different signs.
+///
+/// `a` and `b` are not ordered; `a` always denotes the end with the same sign as `f(lower)` of the
+/// initial bracket.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+struct BracketState<F> {
+    /// First end of the interval
+    a: F,
+    /// Function value at `a`
+    fa: F,
+    /// Second end of the interval
+    b: F,
+    /// Function value at `b`
+    fb: F,
+}
+
+/// End of the interval replaced by [`BracketState::update`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+enum Side {
+    A,
+    B,
+}
+
+impl<F: ArgminFloat> BracketState<F> {
+    /// Evaluates the function at both ends of `bracket` and checks that their signs differ
+    fn new<O>(name: &str, problem: &mut Problem<O>, bracket: &Bracket<F>) -> Result<Self, Error>
+    where
+        O: CostFunction<Param = F, Output = F>,
+    {
+        let (a, b) = (bracket.lower(), bracket.upper());
+        let fa = problem.cost(&a)?;
+        let fb = problem.cost(&b)?;
+        if (fa * fb).is_nan() || fa * fb > float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!("`{name}`: f(lower) and f(upper) must have different signs.")
+            ));
+        }
+        Ok(BracketState { a, fa, b, fb })
+    }
+
+    /// Width of the interval
+    fn width(&self) -> F {
+        (self.b - self.a).abs()
+    }
+
+    /// Returns the end of the interval with the smaller absolute function value
+    fn best(&self) -> (F, F) {
+        if self.fa.abs() <= self.fb.abs() {
+            (self.a, self.fa)
+        } else {
+            (self.b, self.fb)
+        }
+    }
+
+    /// Replaces the end of the interval with the same sign as `fx` by `x`. Returns `None` if the
+    /// sign of `fx` cannot be determined.
+    fn update(&mut self, x: F, fx: F) -> Option<Side> {
+        if fx.is_nan() {
+            None
+        } else if fx * self.fa > float!(0.0) {
+            self.a = x;
+            self.fa = fx;
+            Some(Side::A)
+        } else {
+            self.b = x;
+            self.fb = fx;
+            Some(Side::B)
+        }
+    }
+
+    /// Returns `true` if the interval is small enough or a root was found
+    fn converged(&self, tol: &RootTolerance<F>) -> bool {
+        let (x, fx) = self.best();
+        tol.f_converged(fx) || self.width() <= float!(2.0) * tol.x_tol(x)
+    }
+
+    /// Writes the best end of the interval into `state` and terminates it if converged
+    fn to_state(
+        self,
+        state: IterState<F, (), (), (), (), F>,
+        tol: &RootTolerance<F>,
+    ) -> (IterState<F, (), (), (), (), F>, Option<KV>) {
+        let (x, fx) = self.best();
+        let state = state.param(x).cost(fx.abs());
+        let kv = Some(kv!("bracket_width" => self.width();));
+        if self.converged(tol) {
+            (state.terminate_with(TerminationReason::SolverConverged), kv)
+        } else {
+            (state, kv)
+        }
+    }
+}
+
+/// Takes the initial guess of an open method from `state`
+fn initial_guess<F: ArgminFloat>(
+    name: &str,
+    state: &mut IterState<F, (), (), (), (), F>,
+    bracket: Option<&Bracket<F>>,
+) -> Result<F, Error> {
+    let x = state.take_param().ok_or_else(argmin_error_closure!(
+        NotInitialized,
+        format!(
+            "`{name}` requires an initial guess. Please provide an initial guess via `Executor`s `configure` method."
+        )
+    ))?;
+    if let Some(bracket) = bracket {
+        if !bracket.contains(x) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!("`{name}`: initial guess must lie within the bracket.")
+            ));
+        }
+    }
+    Ok(x)
+}
+
+/// Performs the step from `x` to `x - step` of an open method.
+///
+/// Terminates with [`TerminationReason::BracketLost`] without evaluating the function if the new
+/// point lies outside of `bracket`, otherwise `x` and `fx` are updated and the state is terminated
+/// if the method converged.
+fn open_step<O, F>(
+    problem: &mut Problem<O>,
+    state: IterState<F, (), (), (), (), F>,
+    tol: &RootTolerance<F>,
+    bracket: Option<&Bracket<F>>,
+    x: &mut F,
+    fx: &mut F,
+    step: F,
+) -> Result<(IterState<F, (), (), (), (), F>, Option<KV>), Error>
+where
+    O: CostFunction<Param = F, Output = F>,
+    F: ArgminFloat,
+{
+    let kv = Some(kv!("step" => step;));
+    let x_new = *x - step;
+    if let Some(bracket) = bracket {
+        if !bracket.contains(x_new) {
+            return Ok((state.terminate_with(TerminationReason::BracketLost), kv));
+        }
+    }
+    *fx = problem.cost(&x_new)?;
+    *x = x_new;
+    let state = state.param(*x).cost(fx.abs());
+    if tol.f_converged(*fx) || step.abs() <= tol.x_tol(*x) {
+        Ok((state.terminate_with(TerminationReason::SolverConverged), kv))
+    } else {
+        Ok((state, kv))
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Gradient, Hessian};
+    use approx::assert_relative_eq;
+
+    /// f(x) = x^3 - 2 x - 5
+    pub(super) struct Cubic {}
+
+    /// Root of [`Cubic`]
+    pub(super) const CUBIC_ROOT: f64 = 2.0945514815423265;
+
+    impl CostFunction for Cubic {
+        type Param = f64;
+        type Output = f64;
+
+        fn cost(&self, x: &f64) -> Result<f64, Error> {
+            Ok(x.powi(3) - 2.0 * x - 5.0)
+        }
+    }
+
+    impl Gradient for Cubic {
+        type Param = f64;
+        type Gradient = f64;
+
+        fn gradient(&self, x: &f64) -> Result<f64, Error> {
+            Ok(3.0 * x.powi(2) - 2.0)
+        }
+    }
+
+    impl Hessian for Cubic {
+        type Param = f64;
+        type Hessian = f64;
+
+        fn hessian(&self, x: &f64) -> Result<f64, Error> {
+            Ok(6.0 * x)
+        }
+    }
+
+    /// f(x) = x^2 - 1, which has a vanishing derivative at 0
+    pub(super) struct Parabola {}
+
+    impl CostFunction for Parabola {
+        type Param = f64;
+        type Output = f64;
+
+        fn cost(&self, x: &f64) -> Result<f64, Error> {
+            Ok(x.powi(2) - 1.0)
+        }
+    }
+
+    impl Gradient for Parabola {
+        type Param = f64;
+        type Gradient = f64;
+
+        fn gradient(&self, x: &f64) -> Result<f64, Error> {
+            Ok(2.0 * x)
+        }
+    }
+
+    impl Hessian for Parabola {
+        type Param = f64;
+        type Hessian = f64;
+
+        fn hessian(&self, _x: &f64) -> Result<f64, Error> {
+            Ok(2.0)
+        }
+    }
+
+    #[test]
+    fn test_bracket() {
+        let bracket = Bracket::new(-1.0f64, 2.0).unwrap();
+        assert_eq!(bracket.lower().to_ne_bytes(), (-1.0f64).to_ne_bytes());
+        assert_eq!(bracket.upper().to_ne_bytes(), 2.0f64.to_ne_bytes());
+        assert!(bracket.contains(-1.0));
+        assert!(bracket.contains(2.0));
+        assert!(!bracket.contains(2.5));
+
+        for (lower, upper) in [
+            (1.0, 1.0),
+            (2.0, 1.0),
+            (f64::NEG_INFINITY, 0.0),
+            (0.0, f64::NAN),
+        ] {
+            assert_error!(
+                Bracket::new(lower, upper),
+                ArgminError,
+                "Invalid parameter: \"`Bracket`: bounds must be finite and lower bound must be smaller than upper bound.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_tolerance() {
+        let tol: RootTolerance<f64> = RootTolerance::new();
+        assert_eq!(tol.x_abs.to_ne_bytes(), f64::EPSILON.to_ne_bytes());
+        assert_eq!(tol.x_rel.to_ne_bytes(), (4.0 * f64::EPSILON).to_ne_bytes());
+        assert_eq!(tol.f_abs.to_ne_bytes(), 0.0f64.to_ne_bytes());
+
+        let tol = tol
+            .with_x_abs_tol(1e-3)
+            .unwrap()
+            .with_x_rel_tol(1e-2)
+            .unwrap()
+            .with_f_abs_tol(1e-4)
+            .unwrap();
+        assert_relative_eq!(tol.x_tol(-2.0), 0.021);
+        assert!(tol.f_converged(-1e-4));
+        assert!(!tol.f_converged(2e-4));
+
+        assert_error!(
+            RootTolerance::new().with_x_abs_tol(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`RootTolerance`: absolute tolerance on x must be >= 0.\""
+        );
+        assert_error!(
+            RootTolerance::new().with_x_rel_tol(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`RootTolerance`: relative tolerance on x must be >= 0.\""
+        );
+        assert_error!(
+            RootTolerance::new().with_f_abs_tol(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`RootTolerance`: absolute tolerance on f(x) must be >= 0.\""
+        );
+    }
+
+    #[test]
+    fn test_bracket_state() {
+        let mut problem = Problem::new(Cubic {});
+        let mut bs =
+            BracketState::new("Test", &mut problem, &Bracket::new(2.0, 3.0).unwrap()).unwrap();
+        assert_eq!(bs.width().to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(bs.best().0.to_ne_bytes(), 2.0f64.to_ne_bytes());
+
+        assert_eq!(bs.update(2.5, 5.625), Some(Side::B));
+        assert_eq!(bs.update(2.1, 0.061), Some(Side::B));
+        assert_eq!(bs.update(2.05, -0.285875), Some(Side::A));
+        assert_eq!(bs.update(2.07, f64::NAN), None);
+        assert_relative_eq!(bs.width(), 0.05, epsilon = 1e-12);
+        assert_eq!(bs.best().0.to_ne_bytes(), 2.1f64.to_ne_bytes());
+
+        assert_error!(
+            BracketState::new("Test", &mut problem, &Bracket::new(3.0, 4.0).unwrap()),
+            ArgminError,
+            "Invalid parameter: \"`Test`: f(lower) and f(upper) must have different signs.\""
+        );
+    }
+}
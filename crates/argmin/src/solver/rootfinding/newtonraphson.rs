@@ -0,0 +1,257 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::{initial_guess, open_step, Bracket, RootTolerance};
+use crate::core::{
+    ArgminFloat, CostFunction, Error, Gradient, IterState, Problem, Solver, State,
+    TerminationReason, KV,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Newton-Raphson method
+///
+/// Open root finding method which moves to the root of the tangent at the current estimate,
+/// `x_{k+1} = x_k - f(x_k) / f'(x_k)`. It converges quadratically close to a simple root, but may
+/// diverge when started far away from it.
+///
+/// The derivative `f'` is provided via the [`Gradient`] trait. If it vanishes (or is not finite),
+/// the solver terminates with [`TerminationReason::DerivativeVanished`]. Optionally, a [`Bracket`]
+/// can be provided via [`with_bracket`](`NewtonRaphson::with_bracket`); if a step leaves it, the
+/// solver terminates with [`TerminationReason::BracketLost`]. The length of the last step is
+/// reported as KV metric `step`.
+///
+/// An initial guess must be provided via `Executor`s `configure` method.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`] and [`Gradient`].
+///
+/// ## Reference
+///
+/// <https://en.wikipedia.org/wiki/Newton%27s_method>
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct NewtonRaphson<F> {
+    /// Tolerances
+    tol: RootTolerance<F>,
+    /// Optional bracket
+    bracket: Option<Bracket<F>>,
+    /// Current estimate of the root
+    x: F,
+    /// Function value at current estimate
+    fx: F,
+}
+
+impl<F: ArgminFloat> NewtonRaphson<F> {
+    /// Construct a new instance of `NewtonRaphson`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::rootfinding::NewtonRaphson;
+    /// let newton: NewtonRaphson<f64> = NewtonRaphson::new();
+    /// ```
+    pub fn new() -> Self {
+        NewtonRaphson {
+            tol: RootTolerance::new(),
+            bracket: None,
+            x: F::nan(),
+            fx: F::nan(),
+        }
+    }
+
+    /// Set tolerances
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::rootfinding::{NewtonRaphson, RootTolerance};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let newton: NewtonRaphson<f64> =
+    ///     NewtonRaphson::new().with_tolerance(RootTolerance::new().with_f_abs_tol(1e-10)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_tolerance(mut self, tol: RootTolerance<F>) -> Self {
+        self.tol = tol;
+        self
+    }
+
+    /// Restrict the iterates to a bracket
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::rootfinding::{Bracket, NewtonRaphson};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let newton = NewtonRaphson::new().with_bracket(Bracket::new(0.0, 2.0)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_bracket(mut self, bracket: Bracket<F>) -> Self {
+        self.bracket = Some(bracket);
+        self
+    }
+}
+
+impl<F: ArgminFloat> Default for NewtonRaphson<F> {
+    fn default() -> Self {
+        NewtonRaphson::new()
+    }
+}
+
+impl<O, F> Solver<O, IterState<F, (), (), (), (), F>> for NewtonRaphson<F>
+where
+    O: CostFunction<Param = F, Output = F> + Gradient<Param = F, Gradient = F>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Newton-Raphson";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<F, (), (), (), (), F>,
+    ) -> Result<(IterState<F, (), (), (), (), F>, Option<KV>), Error> {
+        self.x = initial_guess("NewtonRaphson", &mut state, self.bracket.as_ref())?;
+        self.fx = problem.cost(&self.x)?;
+        let state = state.param(self.x).cost(self.fx.abs());
+        if self.tol.f_converged(self.fx) {
+            Ok((
+                state.terminate_with(TerminationReason::SolverConverged),
+                None,
+            ))
+        } else {
+            Ok((state, None))
+        }
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<F, (), (), (), (), F>,
+    ) -> Result<(IterState<F, (), (), (), (), F>, Option<KV>), Error> {
+        let d = problem.gradient(&self.x)?;
+        if d == float!(0.0) || !d.is_finite() {
+            return Ok((
+                state.terminate_with(TerminationReason::DerivativeVanished),
+                None,
+            ));
+        }
+        let step = self.fx / d;
+        open_step(
+            problem,
+            state,
+            &self.tol,
+            self.bracket.as_ref(),
+            &mut self.x,
+            &mut self.fx,
+            step,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::rootfinding::tests::{Cubic, Parabola, CUBIC_ROOT};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(newtonraphson, NewtonRaphson<f64>);
+
+    #[test]
+    fn test_new() {
+        let NewtonRaphson {
+            tol,
+            bracket,
+            x,
+            fx,
+        } = NewtonRaphson::<f64>::new();
+        assert_eq!(tol, RootTolerance::new());
+        assert!(bracket.is_none());
+        assert!(x.is_nan());
+        assert!(fx.is_nan());
+
+        let bracket = Bracket::new(0.0, 2.0).unwrap();
+        let newton = NewtonRaphson::new().with_bracket(bracket);
+        assert_eq!(newton.bracket, Some(bracket));
+    }
+
+    #[test]
+    fn test_cubic() {
+        let res = Executor::new(Cubic {}, NewtonRaphson::new())
+            .configure(|state| state.param(2.0).max_iters(100))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state().get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        assert_relative_eq!(
+            *res.state().get_best_param().unwrap(),
+            CUBIC_ROOT,
+            epsilon = 1e-14
+        );
+        // Quadratic convergence
+        assert!(res.state().get_iter() <= 6);
+    }
+
+    #[test]
+    fn test_derivative_vanished() {
+        let res = Executor::new(Parabola {}, NewtonRaphson::new())
+            .configure(|state| state.param(0.0).max_iters(100))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state().get_termination_reason(),
+            Some(&TerminationReason::DerivativeVanished)
+        );
+    }
+
+    #[test]
+    fn test_bracket_lost() {
+        // The tangent at 0.1 hits zero far outside the bracket
+        let solver = NewtonRaphson::new().with_bracket(Bracket::new(0.0, 2.0).unwrap());
+        let res = Executor::new(Parabola {}, solver)
+            .configure(|state| state.param(0.1).max_iters(100))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state().get_termination_reason(),
+            Some(&TerminationReason::BracketLost)
+        );
+    }
+
+    #[test]
+    fn test_initial_guess() {
+        let res = Executor::new(Cubic {}, NewtonRaphson::new()).run();
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`NewtonRaphson` requires an initial guess. Please provide an ",
+                "initial guess via `Executor`s `configure` method.\""
+            )
+        );
+
+        let solver = NewtonRaphson::new().with_bracket(Bracket::new(0.0, 2.0).unwrap());
+        let res = Executor::new(Cubic {}, solver)
+            .configure(|state| state.param(3.0))
+            .run();
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`NewtonRaphson`: initial guess must lie within the bracket.\""
+        );
+    }
+}
@@ -0,0 +1,250 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::{Bracket, BracketState, RootTolerance, Side};
+use crate::core::{
+    ArgminFloat, CostFunction, Error, IterState, Problem, Solver, State, TerminationReason, KV,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Regula falsi
+///
+/// Bracketing root finding method which replaces one end of the bracket in every iteration by the
+/// root of the secant through both ends, keeping the end at which the function has the opposite
+/// sign.
+///
+/// Plain regula falsi often keeps one end of the bracket fixed, which makes it converge slowly.
+/// The Illinois modification, enabled via [`with_illinois`](`RegulaFalsi::with_illinois`), halves
+/// the function value used for the secant at an end which was kept twice in a row, which restores
+/// superlinear convergence.
+///
+/// Since the bracket may not shrink to zero width, the method also converges once the change of
+/// the estimate of the root is below the tolerance on `x`. The width of the bracket is reported as
+/// KV metric `bracket_width`.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`].
+///
+/// ## Reference
+///
+/// M. Dowell and P. Jarratt (1971). A modified regula falsi method for computing the root of an
+/// equation. BIT Numerical Mathematics 11, 168–174. DOI: <https://doi.org/10.1007/BF01934364>
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct RegulaFalsi<F> {
+    /// Initial bracket
+    bracket: Bracket<F>,
+    /// Tolerances
+    tol: RootTolerance<F>,
+    /// Whether to use the Illinois modification
+    illinois: bool,
+    /// Current bracket
+    state: Option<BracketState<F>>,
+    /// Scaling of the function values at both ends of the bracket (Illinois modification)
+    scale: (F, F),
+    /// End of the bracket which was replaced in the last iteration
+    last_side: Option<Side>,
+    /// Last estimate of the root
+    last_x: F,
+}
+
+impl<F: ArgminFloat> RegulaFalsi<F> {
+    /// Construct a new instance of `RegulaFalsi`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::rootfinding::{Bracket, RegulaFalsi};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let regula_falsi = RegulaFalsi::new(Bracket::new(0.0, 2.0)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(bracket: Bracket<F>) -> Self {
+        RegulaFalsi {
+            bracket,
+            tol: RootTolerance::new(),
+            illinois: false,
+            state: None,
+            scale: (float!(1.0), float!(1.0)),
+            last_side: None,
+            last_x: F::nan(),
+        }
+    }
+
+    /// Set tolerances
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::rootfinding::{Bracket, RegulaFalsi, RootTolerance};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let regula_falsi = RegulaFalsi::new(Bracket::new(0.0, 2.0)?)
+    ///     .with_tolerance(RootTolerance::new().with_x_abs_tol(1e-6)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_tolerance(mut self, tol: RootTolerance<F>) -> Self {
+        self.tol = tol;
+        self
+    }
+
+    /// Enable or disable the Illinois modification
+    ///
+    /// Defaults to `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::rootfinding::{Bracket, RegulaFalsi};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let illinois = RegulaFalsi::new(Bracket::new(0.0, 2.0)?).with_illinois(true);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_illinois(mut self, illinois: bool) -> Self {
+        self.illinois = illinois;
+        self
+    }
+}
+
+impl<O, F> Solver<O, IterState<F, (), (), (), (), F>> for RegulaFalsi<F>
+where
+    O: CostFunction<Param = F, Output = F>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Regula falsi";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<F, (), (), (), (), F>,
+    ) -> Result<(IterState<F, (), (), (), (), F>, Option<KV>), Error> {
+        let bracket_state = BracketState::new("RegulaFalsi", problem, &self.bracket)?;
+        self.state = Some(bracket_state);
+        self.scale = (float!(1.0), float!(1.0));
+        self.last_side = None;
+        self.last_x = F::nan();
+        Ok(bracket_state.to_state(state, &self.tol))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<F, (), (), (), (), F>,
+    ) -> Result<(IterState<F, (), (), (), (), F>, Option<KV>), Error> {
+        let bracket_state = self.state.as_mut().unwrap();
+        let BracketState { a, fa, b, fb } = *bracket_state;
+        let (fa, fb) = (fa * self.scale.0, fb * self.scale.1);
+        let x = (a * fb - b * fa) / (fb - fa);
+        let fx = problem.cost(&x)?;
+
+        let side = match bracket_state.update(x, fx) {
+            Some(side) => side,
+            None => return Ok((state.terminate_with(TerminationReason::BracketLost), None)),
+        };
+        match side {
+            Side::A => self.scale.0 = float!(1.0),
+            Side::B => self.scale.1 = float!(1.0),
+        }
+        if self.illinois && self.last_side == Some(side) {
+            // The other end was kept twice in a row
+            match side {
+                Side::A => self.scale.1 = self.scale.1 * float!(0.5),
+                Side::B => self.scale.0 = self.scale.0 * float!(0.5),
+            }
+        }
+        self.last_side = Some(side);
+
+        let step = (x - self.last_x).abs();
+        self.last_x = x;
+        let (state, kv) = bracket_state.to_state(state, &self.tol);
+        if step <= self.tol.x_tol(x) {
+            Ok((state.terminate_with(TerminationReason::SolverConverged), kv))
+        } else {
+            Ok((state, kv))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::rootfinding::tests::{Cubic, CUBIC_ROOT};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(regulafalsi, RegulaFalsi<f64>);
+
+    #[test]
+    fn test_new() {
+        let bracket = Bracket::new(0.0f64, 2.0).unwrap();
+        let RegulaFalsi {
+            bracket: b,
+            tol,
+            illinois,
+            state,
+            scale,
+            last_side,
+            last_x,
+        } = RegulaFalsi::new(bracket);
+        assert_eq!(b, bracket);
+        assert_eq!(tol, RootTolerance::new());
+        assert!(!illinois);
+        assert!(state.is_none());
+        assert_eq!(scale.0.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(scale.1.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert!(last_side.is_none());
+        assert!(last_x.is_nan());
+
+        let regula_falsi = RegulaFalsi::new(bracket).with_illinois(true);
+        assert!(regula_falsi.illinois);
+    }
+
+    #[test]
+    fn test_cubic() {
+        let mut iters = vec![];
+        for illinois in [false, true] {
+            let solver = RegulaFalsi::new(Bracket::new(0.0, 3.0).unwrap()).with_illinois(illinois);
+            let res = Executor::new(Cubic {}, solver)
+                .configure(|state| state.max_iters(1000))
+                .run()
+                .unwrap();
+            assert_eq!(
+                res.state().get_termination_reason(),
+                Some(&TerminationReason::SolverConverged)
+            );
+            assert_relative_eq!(
+                *res.state().get_best_param().unwrap(),
+                CUBIC_ROOT,
+                epsilon = 1e-12
+            );
+            iters.push(res.state().get_iter());
+        }
+        // Illinois modification converges considerably faster
+        assert!(iters[1] * 2 < iters[0]);
+    }
+
+    #[test]
+    fn test_wrong_sign() {
+        let solver = RegulaFalsi::new(Bracket::new(3.0, 4.0).unwrap());
+        let res = Executor::new(Cubic {}, solver).run();
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`RegulaFalsi`: f(lower) and f(upper) must have different signs.\""
+        );
+    }
+}
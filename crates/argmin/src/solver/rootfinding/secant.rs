@@ -0,0 +1,322 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::{initial_guess, open_step, Bracket, RootTolerance};
+use crate::core::{
+    ArgminFloat, CostFunction, Error, IterState, Problem, Solver, State, TerminationReason, KV,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Secant method
+///
+/// Open root finding method which moves to the root of the secant through the last two
+/// estimates. It does not require derivatives and converges superlinearly (with order of the
+/// golden ratio) close to a simple root.
+///
+/// The initial guess must be provided via `Executor`s `configure` method. The second estimate
+/// defaults to a small perturbation of the initial guess and can be set via
+/// [`with_second_guess`](`Secant::with_second_guess`). If the function values at the last two
+/// estimates coincide, the solver terminates with [`TerminationReason::DerivativeVanished`].
+/// Optionally, a [`Bracket`] can be provided; if a step leaves it, the solver terminates with
+/// [`TerminationReason::BracketLost`]. The length of the last step is reported as KV metric
+/// `step`.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`].
+///
+/// ## Reference
+///
+/// <https://en.wikipedia.org/wiki/Secant_method>
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Secant<F> {
+    /// Tolerances
+    tol: RootTolerance<F>,
+    /// Optional bracket
+    bracket: Option<Bracket<F>>,
+    /// Second estimate of the root
+    second_guess: Option<F>,
+    /// Current estimate of the root
+    x: F,
+    /// Function value at current estimate
+    fx: F,
+    /// Previous estimate of the root
+    x_prev: F,
+    /// Function value at previous estimate
+    fx_prev: F,
+}
+
+impl<F: ArgminFloat> Secant<F> {
+    /// Construct a new instance of `Secant`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::rootfinding::Secant;
+    /// let secant: Secant<f64> = Secant::new();
+    /// ```
+    pub fn new() -> Self {
+        Secant {
+            tol: RootTolerance::new(),
+            bracket: None,
+            second_guess: None,
+            x: F::nan(),
+            fx: F::nan(),
+            x_prev: F::nan(),
+            fx_prev: F::nan(),
+        }
+    }
+
+    /// Set tolerances
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::rootfinding::{RootTolerance, Secant};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let secant: Secant<f64> =
+    ///     Secant::new().with_tolerance(RootTolerance::new().with_f_abs_tol(1e-10)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_tolerance(mut self, tol: RootTolerance<F>) -> Self {
+        self.tol = tol;
+        self
+    }
+
+    /// Restrict the iterates to a bracket
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::rootfinding::{Bracket, Secant};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let secant = Secant::new().with_bracket(Bracket::new(0.0, 2.0)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_bracket(mut self, bracket: Bracket<F>) -> Self {
+        self.bracket = Some(bracket);
+        self
+    }
+
+    /// Set second estimate of the root
+    ///
+    /// Must differ from the initial guess. Defaults to `x0 * (1 + 1e-4) + 1e-4` (with the sign of
+    /// the second term matching the sign of `x0`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::rootfinding::Secant;
+    /// let secant = Secant::new().with_second_guess(1.5);
+    /// ```
+    #[must_use]
+    pub fn with_second_guess(mut self, x1: F) -> Self {
+        self.second_guess = Some(x1);
+        self
+    }
+}
+
+impl<F: ArgminFloat> Default for Secant<F> {
+    fn default() -> Self {
+        Secant::new()
+    }
+}
+
+impl<O, F> Solver<O, IterState<F, (), (), (), (), F>> for Secant<F>
+where
+    O: CostFunction<Param = F, Output = F>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Secant";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<F, (), (), (), (), F>,
+    ) -> Result<(IterState<F, (), (), (), (), F>, Option<KV>), Error> {
+        let x0 = initial_guess("Secant", &mut state, self.bracket.as_ref())?;
+        let x1 = self.second_guess.unwrap_or_else(|| {
+            let delta: F = float!(1e-4);
+            let x1 = x0 * (float!(1.0) + delta);
+            if x0 >= float!(0.0) {
+                x1 + delta
+            } else {
+                x1 - delta
+            }
+        });
+        if x1 == x0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Secant`: second guess must differ from initial guess."
+            ));
+        }
+        self.x_prev = x0;
+        self.fx_prev = problem.cost(&x0)?;
+        self.x = x1;
+        self.fx = problem.cost(&x1)?;
+        if self.fx_prev.abs() < self.fx.abs() {
+            std::mem::swap(&mut self.x, &mut self.x_prev);
+            std::mem::swap(&mut self.fx, &mut self.fx_prev);
+        }
+        let state = state.param(self.x).cost(self.fx.abs());
+        if self.tol.f_converged(self.fx) {
+            Ok((
+                state.terminate_with(TerminationReason::SolverConverged),
+                None,
+            ))
+        } else {
+            Ok((state, None))
+        }
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<F, (), (), (), (), F>,
+    ) -> Result<(IterState<F, (), (), (), (), F>, Option<KV>), Error> {
+        let df = self.fx - self.fx_prev;
+        let step = self.fx * (self.x - self.x_prev) / df;
+        if df == float!(0.0) || !step.is_finite() {
+            return Ok((
+                state.terminate_with(TerminationReason::DerivativeVanished),
+                None,
+            ));
+        }
+        let (x, fx) = (self.x, self.fx);
+        let res = open_step(
+            problem,
+            state,
+            &self.tol,
+            self.bracket.as_ref(),
+            &mut self.x,
+            &mut self.fx,
+            step,
+        )?;
+        self.x_prev = x;
+        self.fx_prev = fx;
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::rootfinding::tests::{Cubic, Parabola, CUBIC_ROOT};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(secant, Secant<f64>);
+
+    #[test]
+    fn test_new() {
+        let Secant {
+            tol,
+            bracket,
+            second_guess,
+            x,
+            fx,
+            x_prev,
+            fx_prev,
+        } = Secant::<f64>::new();
+        assert_eq!(tol, RootTolerance::new());
+        assert!(bracket.is_none());
+        assert!(second_guess.is_none());
+        assert!(x.is_nan());
+        assert!(fx.is_nan());
+        assert!(x_prev.is_nan());
+        assert!(fx_prev.is_nan());
+
+        let secant = Secant::new().with_second_guess(1.5f64);
+        assert_eq!(
+            secant.second_guess.unwrap().to_ne_bytes(),
+            1.5f64.to_ne_bytes()
+        );
+    }
+
+    #[test]
+    fn test_cubic() {
+        for second_guess in [None, Some(3.0)] {
+            let mut solver = Secant::new();
+            if let Some(x1) = second_guess {
+                solver = solver.with_second_guess(x1);
+            }
+            let res = Executor::new(Cubic {}, solver)
+                .configure(|state| state.param(2.0).max_iters(100))
+                .run()
+                .unwrap();
+            assert_eq!(
+                res.state().get_termination_reason(),
+                Some(&TerminationReason::SolverConverged)
+            );
+            assert_relative_eq!(
+                *res.state().get_best_param().unwrap(),
+                CUBIC_ROOT,
+                epsilon = 1e-14
+            );
+            assert!(res.state().get_iter() <= 10);
+        }
+    }
+
+    #[test]
+    fn test_derivative_vanished() {
+        // Symmetric estimates have the same function value
+        let res = Executor::new(Parabola {}, Secant::new().with_second_guess(0.5))
+            .configure(|state| state.param(-0.5).max_iters(100))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state().get_termination_reason(),
+            Some(&TerminationReason::DerivativeVanished)
+        );
+    }
+
+    #[test]
+    fn test_bracket_lost() {
+        let solver = Secant::new()
+            .with_bracket(Bracket::new(0.0, 2.0).unwrap())
+            .with_second_guess(0.2);
+        let res = Executor::new(Parabola {}, solver)
+            .configure(|state| state.param(0.1).max_iters(100))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state().get_termination_reason(),
+            Some(&TerminationReason::BracketLost)
+        );
+    }
+
+    #[test]
+    fn test_invalid_guesses() {
+        let res = Executor::new(Cubic {}, Secant::new()).run();
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`Secant` requires an initial guess. Please provide an ",
+                "initial guess via `Executor`s `configure` method.\""
+            )
+        );
+
+        let res = Executor::new(Cubic {}, Secant::new().with_second_guess(2.0))
+            .configure(|state| state.param(2.0))
+            .run();
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`Secant`: second guess must differ from initial guess.\""
+        );
+    }
+}
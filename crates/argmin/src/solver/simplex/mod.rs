@@ -0,0 +1,776 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Simplex method
+//!
+//! Two-phase revised simplex method for linear programs in standard form.
+//!
+//! See [`Simplex`] for details.
+//!
+//! ## Reference
+//!
+//! Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+//! Springer. ISBN 0-387-30303-0.
+
+use crate::core::{
+    ArgminFloat, Error, LinearProgram, LinearProgramState, Problem, Solver, State,
+    TerminationReason, KV,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Phase of the two-phase simplex method
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+enum Phase {
+    /// Search for a feasible basis by minimizing the sum of the artificial variables
+    One,
+    /// Minimization of the actual objective function
+    Two,
+}
+
+/// # Simplex method
+///
+/// Two-phase revised simplex method for linear programs of the form
+///
+/// ```text
+/// min_x  c^T x
+/// s.t.   A x = b
+///        x >= 0
+/// ```
+///
+/// In the first phase, artificial variables are added to the constraints and their sum is
+/// minimized in order to find a feasible basis. If the sum cannot be reduced to zero, the problem
+/// is infeasible and the solver terminates with [`TerminationReason::Infeasible`]. In the second
+/// phase, starting from the feasible basis, the actual objective function is minimized. If the
+/// objective function is unbounded from below on the feasible set, the solver terminates with
+/// [`TerminationReason::Unbounded`].
+///
+/// Each iteration performs a single pivot. The entering variable is chosen according to Dantzig's
+/// rule (most negative reduced cost). After a degenerate pivot, Bland's rule is used instead,
+/// which prevents cycling.
+///
+/// The inverse of the basis matrix is stored explicitly and updated in every pivot. After
+/// termination, the indices of the basic variables and the dual values (Lagrange multipliers of
+/// the equality constraints) are stored in the [`LinearProgramState`]. An index `>= n` in the basis
+/// refers to the artificial variable of a redundant constraint.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`LinearProgram`].
+///
+/// ## Reference
+///
+/// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+/// Springer. ISBN 0-387-30303-0.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Simplex<F> {
+    /// Tolerance for reduced costs, pivot elements and feasibility
+    tol: F,
+    /// Current phase
+    phase: Phase,
+    /// Constraint matrix (rows with negative right-hand side are negated)
+    a: Vec<Vec<F>>,
+    /// Right-hand side (non-negative)
+    b: Vec<F>,
+    /// Objective function coefficients
+    c: Vec<F>,
+    /// Sign by which each constraint was multiplied
+    row_sign: Vec<F>,
+    /// Inverse of the basis matrix
+    binv: Vec<Vec<F>>,
+    /// Indices of basic variables
+    basis: Vec<usize>,
+    /// Values of basic variables
+    xb: Vec<F>,
+    /// Whether the last pivot was degenerate
+    degenerate: bool,
+}
+
+impl<F> Simplex<F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `Simplex`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::simplex::Simplex;
+    /// let simplex: Simplex<f64> = Simplex::new();
+    /// ```
+    pub fn new() -> Self {
+        Simplex {
+            tol: F::epsilon().sqrt(),
+            phase: Phase::One,
+            a: vec![],
+            b: vec![],
+            c: vec![],
+            row_sign: vec![],
+            binv: vec![],
+            basis: vec![],
+            xb: vec![],
+            degenerate: false,
+        }
+    }
+
+    /// Set tolerance
+    ///
+    /// The tolerance is used for deciding optimality (reduced costs), for selecting pivot
+    /// elements and for deciding feasibility at the end of phase one. Must be larger than 0 and
+    /// defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::simplex::Simplex;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let simplex: Simplex<f64> = Simplex::new().with_tolerance(1e-10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Simplex`: tolerance must be > 0."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Number of structural variables
+    fn n(&self) -> usize {
+        self.c.len()
+    }
+
+    /// Column `j` of the constraint matrix extended by the identity matrix of the artificial
+    /// variables
+    fn column(&self, j: usize) -> Vec<F> {
+        let n = self.n();
+        if j < n {
+            self.a.iter().map(|row| row[j]).collect()
+        } else {
+            (0..self.b.len())
+                .map(|i| if i == j - n { float!(1.0) } else { float!(0.0) })
+                .collect()
+        }
+    }
+
+    /// Objective function coefficient of variable `j` in the current phase
+    fn cost_coefficient(&self, j: usize) -> F {
+        match (self.phase, j < self.n()) {
+            (Phase::One, true) => float!(0.0),
+            (Phase::One, false) => float!(1.0),
+            (Phase::Two, true) => self.c[j],
+            (Phase::Two, false) => float!(0.0),
+        }
+    }
+
+    /// Dual values `y = B^-T c_B` of the current phase
+    fn dual(&self) -> Vec<F> {
+        let m = self.b.len();
+        (0..m)
+            .map(|i| {
+                self.basis
+                    .iter()
+                    .zip(self.binv.iter())
+                    .fold(float!(0.0), |acc, (&k, row)| {
+                        acc + self.cost_coefficient(k) * row[i]
+                    })
+            })
+            .collect()
+    }
+
+    /// Chooses the entering variable based on the reduced costs `c_j - y^T A_j`. Returns `None`
+    /// if the current basis is optimal for the current phase.
+    fn entering(&self, y: &[F]) -> Option<usize> {
+        let n = self.n();
+        let mut entering: Option<(usize, F)> = None;
+        // Artificial variables never re-enter the basis.
+        for j in (0..n).filter(|j| !self.basis.contains(j)) {
+            let d = self.c_reduced(j, y);
+            if d < -self.tol {
+                if self.degenerate {
+                    // Bland's rule: first variable with negative reduced cost
+                    return Some(j);
+                }
+                if entering.map(|(_, dmin)| d < dmin).unwrap_or(true) {
+                    entering = Some((j, d));
+                }
+            }
+        }
+        entering.map(|(j, _)| j)
+    }
+
+    /// Reduced cost of structural variable `j`
+    fn c_reduced(&self, j: usize, y: &[F]) -> F {
+        self.a
+            .iter()
+            .zip(y.iter())
+            .fold(self.cost_coefficient(j), |acc, (row, &yi)| {
+                acc - yi * row[j]
+            })
+    }
+
+    /// Computes `B^-1 v`
+    fn binv_mul(&self, v: &[F]) -> Vec<F> {
+        self.binv
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .zip(v.iter())
+                    .fold(float!(0.0), |acc, (&r, &vi)| acc + r * vi)
+            })
+            .collect()
+    }
+
+    /// Ratio test: Returns the row of the leaving variable and the step length, or `None` if the
+    /// step length is unbounded.
+    fn leaving(&self, u: &[F]) -> Option<(usize, F)> {
+        let mut leaving: Option<(usize, F)> = None;
+        for (i, (&ui, &xi)) in u.iter().zip(self.xb.iter()).enumerate() {
+            if ui > self.tol {
+                let theta = xi / ui;
+                let better = match leaving {
+                    None => true,
+                    Some((r, theta_min)) => {
+                        theta < theta_min || (theta == theta_min && self.basis[i] < self.basis[r])
+                    }
+                };
+                if better {
+                    leaving = Some((i, theta));
+                }
+            }
+        }
+        leaving
+    }
+
+    /// Exchanges the basic variable in row `r` with variable `q`, where `u = B^-1 A_q`.
+    fn pivot(&mut self, r: usize, q: usize, u: &[F], theta: F) {
+        for (i, xi) in self.xb.iter_mut().enumerate() {
+            if i != r {
+                *xi = *xi - theta * u[i];
+            }
+        }
+        self.xb[r] = theta;
+
+        let ur = u[r];
+        for v in self.binv[r].iter_mut() {
+            *v = *v / ur;
+        }
+        let pivot_row = self.binv[r].clone();
+        for (i, row) in self.binv.iter_mut().enumerate() {
+            if i != r && u[i] != float!(0.0) {
+                for (v, &p) in row.iter_mut().zip(pivot_row.iter()) {
+                    *v = *v - u[i] * p;
+                }
+            }
+        }
+        self.basis[r] = q;
+        self.degenerate = theta <= self.tol;
+    }
+
+    /// Pivots artificial variables out of the basis after phase one. Artificial variables which
+    /// cannot be removed belong to redundant constraints and remain in the basis at level zero.
+    fn remove_artificial_variables(&mut self) {
+        let n = self.n();
+        for r in 0..self.basis.len() {
+            if self.basis[r] < n {
+                continue;
+            }
+            let q = (0..n).find(|j| {
+                !self.basis.contains(j)
+                    && self.binv[r]
+                        .iter()
+                        .zip(self.a.iter())
+                        .fold(float!(0.0), |acc, (&bi, row)| acc + bi * row[*j])
+                        .abs()
+                        > self.tol
+            });
+            if let Some(q) = q {
+                let u = self.binv_mul(&self.column(q));
+                self.xb[r] = float!(0.0);
+                self.pivot(r, q, &u, float!(0.0));
+            }
+        }
+    }
+
+    /// Current values of the structural variables
+    fn param(&self) -> Vec<F> {
+        let mut x = vec![float!(0.0); self.n()];
+        for (&k, &xk) in self.basis.iter().zip(self.xb.iter()) {
+            if k < self.n() {
+                x[k] = xk;
+            }
+        }
+        x
+    }
+
+    /// Sum of the artificial variables
+    fn infeasibility(&self) -> F {
+        let n = self.n();
+        self.basis
+            .iter()
+            .zip(self.xb.iter())
+            .filter(|(&k, _)| k >= n)
+            .fold(float!(0.0), |acc, (_, &xk)| acc + xk)
+    }
+
+    /// Stores param, cost, basis and dual values in the state.
+    fn update_state(
+        &self,
+        state: LinearProgramState<Vec<F>, F>,
+        y: Vec<F>,
+    ) -> LinearProgramState<Vec<F>, F> {
+        let x = self.param();
+        // Cost function values of phase one would not be comparable to those of phase two.
+        let cost = match self.phase {
+            Phase::One => F::infinity(),
+            Phase::Two => x
+                .iter()
+                .zip(self.c.iter())
+                .fold(float!(0.0), |acc, (&xi, &ci)| acc + xi * ci),
+        };
+        let dual = y
+            .iter()
+            .zip(self.row_sign.iter())
+            .map(|(&yi, &s)| yi * s)
+            .collect();
+        state
+            .param(x)
+            .cost(cost)
+            .basis(self.basis.clone())
+            .dual(dual)
+    }
+}
+
+impl<F> Default for Simplex<F>
+where
+    F: ArgminFloat,
+{
+    fn default() -> Simplex<F> {
+        Simplex::new()
+    }
+}
+
+impl<O, F> Solver<O, LinearProgramState<Vec<F>, F>> for Simplex<F>
+where
+    O: LinearProgram<Float = F>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Simplex";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: LinearProgramState<Vec<F>, F>,
+    ) -> Result<(LinearProgramState<Vec<F>, F>, Option<KV>), Error> {
+        let c = problem.c()?;
+        let b = problem.b()?;
+        let a = problem.A()?;
+
+        if problem.Q()?.is_some() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Simplex`: quadratic objective functions are not supported."
+            ));
+        }
+
+        if a.len() != b.len() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Simplex`: number of rows of `A` must match the length of `b`."
+            ));
+        }
+        if a.iter().any(|row| row.len() != c.len()) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Simplex`: number of columns of `A` must match the length of `c`."
+            ));
+        }
+
+        let m = b.len();
+        // Ensure b >= 0 such that the artificial variables form a feasible initial basis.
+        self.row_sign = b
+            .iter()
+            .map(|&bi| {
+                if bi < float!(0.0) {
+                    float!(-1.0)
+                } else {
+                    float!(1.0)
+                }
+            })
+            .collect();
+        self.a = a
+            .into_iter()
+            .zip(self.row_sign.iter())
+            .map(|(row, &s)| row.into_iter().map(|aij| aij * s).collect())
+            .collect();
+        self.b = b
+            .iter()
+            .zip(self.row_sign.iter())
+            .map(|(&bi, &s)| bi * s)
+            .collect();
+        self.c = c;
+        self.phase = Phase::One;
+        self.basis = (self.c.len()..self.c.len() + m).collect();
+        self.xb = self.b.clone();
+        self.binv = (0..m)
+            .map(|i| {
+                (0..m)
+                    .map(|j| if i == j { float!(1.0) } else { float!(0.0) })
+                    .collect()
+            })
+            .collect();
+        self.degenerate = false;
+
+        let y = self.dual();
+        let infeasibility = self.infeasibility();
+        Ok((
+            self.update_state(state, y),
+            Some(kv!("phase" => 1; "infeasibility" => infeasibility;)),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        _problem: &mut Problem<O>,
+        state: LinearProgramState<Vec<F>, F>,
+    ) -> Result<(LinearProgramState<Vec<F>, F>, Option<KV>), Error> {
+        let y = self.dual();
+
+        let q = if let Some(q) = self.entering(&y) {
+            q
+        } else {
+            // Current basis is optimal for the current phase
+            return Ok(match self.phase {
+                Phase::One => {
+                    let infeasibility = self.infeasibility();
+                    let scale = self
+                        .b
+                        .iter()
+                        .fold(float!(1.0), |acc: F, &bi| acc.max(bi.abs()));
+                    if infeasibility > self.tol * scale {
+                        (
+                            self.update_state(state, y)
+                                .terminate_with(TerminationReason::Infeasible),
+                            Some(kv!("phase" => 1; "infeasibility" => infeasibility;)),
+                        )
+                    } else {
+                        self.remove_artificial_variables();
+                        self.phase = Phase::Two;
+                        self.degenerate = false;
+                        let y = self.dual();
+                        (self.update_state(state, y), Some(kv!("phase" => 2;)))
+                    }
+                }
+                Phase::Two => (
+                    self.update_state(state, y)
+                        .terminate_with(TerminationReason::SolverConverged),
+                    Some(kv!("phase" => 2;)),
+                ),
+            });
+        };
+
+        let u = self.binv_mul(&self.column(q));
+
+        let (r, theta) = if let Some(leaving) = self.leaving(&u) {
+            leaving
+        } else {
+            // Phase one is bounded from below by zero, therefore this can only happen in phase two.
+            return Ok((
+                self.update_state(state, y)
+                    .terminate_with(TerminationReason::Unbounded),
+                Some(kv!("phase" => 2;)),
+            ));
+        };
+
+        self.pivot(r, q, &u, theta);
+
+        let y = self.dual();
+        let kv = match self.phase {
+            Phase::One => kv!(
+                "phase" => 1;
+                "infeasibility" => self.infeasibility();
+                "entering" => q as u64;
+                "leaving" => r as u64;
+            ),
+            Phase::Two => kv!(
+                "phase" => 2;
+                "entering" => q as u64;
+                "leaving" => r as u64;
+            ),
+        };
+        Ok((self.update_state(state, y), Some(kv)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor, TerminationStatus};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(simplex, Simplex<f64>);
+
+    struct Lp {
+        c: Vec<f64>,
+        b: Vec<f64>,
+        a: Vec<Vec<f64>>,
+    }
+
+    impl LinearProgram for Lp {
+        type Param = Vec<f64>;
+        type Float = f64;
+
+        fn c(&self) -> Result<Vec<f64>, Error> {
+            Ok(self.c.clone())
+        }
+
+        fn b(&self) -> Result<Vec<f64>, Error> {
+            Ok(self.b.clone())
+        }
+
+        fn A(&self) -> Result<Vec<Vec<f64>>, Error> {
+            Ok(self.a.clone())
+        }
+    }
+
+    fn run(lp: Lp) -> LinearProgramState<Vec<f64>, f64> {
+        Executor::new(lp, Simplex::new())
+            .configure(|state| state.max_iters(100))
+            .run()
+            .unwrap()
+            .state
+    }
+
+    #[test]
+    fn test_new() {
+        let simplex: Simplex<f64> = Simplex::new();
+        let Simplex {
+            tol,
+            phase,
+            a,
+            b,
+            c,
+            row_sign,
+            binv,
+            basis,
+            xb,
+            degenerate,
+        } = simplex;
+        assert_eq!(tol.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert_eq!(phase, Phase::One);
+        assert!(a.is_empty());
+        assert!(b.is_empty());
+        assert!(c.is_empty());
+        assert!(row_sign.is_empty());
+        assert!(binv.is_empty());
+        assert!(basis.is_empty());
+        assert!(xb.is_empty());
+        assert!(!degenerate);
+    }
+
+    #[test]
+    fn test_with_tolerance() {
+        let simplex: Simplex<f64> = Simplex::new().with_tolerance(1e-6).unwrap();
+        assert_eq!(simplex.tol.to_ne_bytes(), 1e-6f64.to_ne_bytes());
+
+        for tol in [0.0, -1.0] {
+            assert_error!(
+                Simplex::<f64>::new().with_tolerance(tol),
+                ArgminError,
+                "Invalid parameter: \"`Simplex`: tolerance must be > 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_init_dimension_mismatch() {
+        let lp = Lp {
+            c: vec![1.0, 1.0],
+            b: vec![1.0, 1.0],
+            a: vec![vec![1.0, 1.0]],
+        };
+        assert_error!(
+            Executor::new(lp, Simplex::<f64>::new()).run(),
+            ArgminError,
+            "Invalid parameter: \"`Simplex`: number of rows of `A` must match the length of `b`.\""
+        );
+
+        let lp = Lp {
+            c: vec![1.0, 1.0],
+            b: vec![1.0],
+            a: vec![vec![1.0, 1.0, 1.0]],
+        };
+        assert_error!(
+            Executor::new(lp, Simplex::<f64>::new()).run(),
+            ArgminError,
+            "Invalid parameter: \"`Simplex`: number of columns of `A` must match the length of `c`.\""
+        );
+    }
+
+    #[test]
+    fn test_init_quadratic_objective() {
+        struct Qp {}
+
+        impl LinearProgram for Qp {
+            type Param = Vec<f64>;
+            type Float = f64;
+
+            fn c(&self) -> Result<Vec<f64>, Error> {
+                Ok(vec![1.0])
+            }
+
+            fn b(&self) -> Result<Vec<f64>, Error> {
+                Ok(vec![1.0])
+            }
+
+            fn A(&self) -> Result<Vec<Vec<f64>>, Error> {
+                Ok(vec![vec![1.0]])
+            }
+
+            fn Q(&self) -> Result<Option<Vec<Vec<f64>>>, Error> {
+                Ok(Some(vec![vec![1.0]]))
+            }
+        }
+
+        assert_error!(
+            Executor::new(Qp {}, Simplex::<f64>::new()).run(),
+            ArgminError,
+            "Invalid parameter: \"`Simplex`: quadratic objective functions are not supported.\""
+        );
+    }
+
+    #[test]
+    fn test_optimal() {
+        // max 3 x1 + 5 x2
+        // s.t. x1 <= 4, 2 x2 <= 12, 3 x1 + 2 x2 <= 18
+        // with slack variables x3, x4, x5
+        let lp = Lp {
+            c: vec![-3.0, -5.0, 0.0, 0.0, 0.0],
+            b: vec![4.0, 12.0, 18.0],
+            a: vec![
+                vec![1.0, 0.0, 1.0, 0.0, 0.0],
+                vec![0.0, 2.0, 0.0, 1.0, 0.0],
+                vec![3.0, 2.0, 0.0, 0.0, 1.0],
+            ],
+        };
+        let state = run(lp);
+        assert_eq!(
+            state.termination_status,
+            TerminationStatus::Terminated(TerminationReason::SolverConverged)
+        );
+        let x = state.best_param.as_ref().unwrap();
+        assert_relative_eq!(x[0], 2.0, epsilon = 1e-10);
+        assert_relative_eq!(x[1], 6.0, epsilon = 1e-10);
+        assert_relative_eq!(x[2], 2.0, epsilon = 1e-10);
+        assert_relative_eq!(state.best_cost, -36.0, epsilon = 1e-10);
+
+        let mut basis = state.get_basis().unwrap().clone();
+        basis.sort();
+        assert_eq!(basis, vec![0, 1, 2]);
+
+        let y = state.get_dual().unwrap();
+        assert_relative_eq!(y[0], 0.0, epsilon = 1e-10);
+        assert_relative_eq!(y[1], -1.5, epsilon = 1e-10);
+        assert_relative_eq!(y[2], -1.0, epsilon = 1e-10);
+    }
+
+    #[test]
+    fn test_negative_rhs_and_redundant_constraint() {
+        // min x1 + 3 x2 + 2 x3
+        // s.t. -x1 - x2 - x3 = -3, 2 x1 + 2 x2 + 2 x3 = 6, x1 - x3 = 1
+        let lp = Lp {
+            c: vec![1.0, 3.0, 2.0],
+            b: vec![-3.0, 6.0, 1.0],
+            a: vec![
+                vec![-1.0, -1.0, -1.0],
+                vec![2.0, 2.0, 2.0],
+                vec![1.0, 0.0, -1.0],
+            ],
+        };
+        let state = run(lp);
+        assert_eq!(
+            state.termination_status,
+            TerminationStatus::Terminated(TerminationReason::SolverConverged)
+        );
+        let x = state.best_param.as_ref().unwrap();
+        assert_relative_eq!(x[0], 2.0, epsilon = 1e-10);
+        assert_relative_eq!(x[1], 0.0, epsilon = 1e-10);
+        assert_relative_eq!(x[2], 1.0, epsilon = 1e-10);
+        assert_relative_eq!(state.best_cost, 4.0, epsilon = 1e-10);
+
+        // Dual feasibility: c - A^T y >= 0
+        let y = state.get_dual().unwrap();
+        let a = [[-1.0, -1.0, -1.0], [2.0, 2.0, 2.0], [1.0, 0.0, -1.0]];
+        let c = [1.0, 3.0, 2.0];
+        for j in 0..3 {
+            let d = c[j] - (0..3).map(|i| a[i][j] * y[i]).sum::<f64>();
+            assert!(d >= -1e-10);
+        }
+        // Strong duality: b^T y = c^T x
+        let by = -3.0 * y[0] + 6.0 * y[1] + y[2];
+        assert_relative_eq!(by, 4.0, epsilon = 1e-10);
+    }
+
+    #[test]
+    fn test_degenerate() {
+        // Degenerate problem on which Dantzig's rule without anti-cycling safeguard cycles
+        // (Beale's example)
+        let lp = Lp {
+            c: vec![-0.75, 20.0, -0.5, 6.0, 0.0, 0.0, 0.0],
+            b: vec![0.0, 0.0, 1.0],
+            a: vec![
+                vec![0.25, -8.0, -1.0, 9.0, 1.0, 0.0, 0.0],
+                vec![0.5, -12.0, -0.5, 3.0, 0.0, 1.0, 0.0],
+                vec![0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0],
+            ],
+        };
+        let state = run(lp);
+        assert_eq!(
+            state.termination_status,
+            TerminationStatus::Terminated(TerminationReason::SolverConverged)
+        );
+        assert_relative_eq!(state.best_cost, -1.25, epsilon = 1e-10);
+    }
+
+    #[test]
+    fn test_infeasible() {
+        // x1 + x2 = 1, x1 + x2 = 2
+        let lp = Lp {
+            c: vec![1.0, 1.0],
+            b: vec![1.0, 2.0],
+            a: vec![vec![1.0, 1.0], vec![1.0, 1.0]],
+        };
+        let state = run(lp);
+        assert_eq!(
+            state.termination_status,
+            TerminationStatus::Terminated(TerminationReason::Infeasible)
+        );
+    }
+
+    #[test]
+    fn test_unbounded() {
+        // min -x1 s.t. x1 - x2 = 1
+        let lp = Lp {
+            c: vec![-1.0, 0.0],
+            b: vec![1.0],
+            a: vec![vec![1.0, -1.0]],
+        };
+        let state = run(lp);
+        assert_eq!(
+            state.termination_status,
+            TerminationStatus::Terminated(TerminationReason::Unbounded)
+        );
+    }
+}
@@ -19,11 +19,13 @@
 //! DOI: 10.1126/science.220.4598.671
 
 use crate::core::{
-    ArgminFloat, CostFunction, Error, IterState, Problem, Solver, TerminationReason,
-    TerminationStatus, KV,
+    ArgminFloat, CostFunction, Error, IterState, Problem, SendAlias, Solver, SyncAlias,
+    TerminationReason, TerminationStatus, KV,
 };
 use rand::prelude::*;
 use rand_xoshiro::Xoshiro256PlusPlus;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
@@ -39,6 +41,62 @@ pub trait Anneal {
 
     /// Anneal a parameter vector
     fn anneal(&self, param: &Self::Param, extent: Self::Float) -> Result<Self::Output, Error>;
+
+    /// Anneal multiple parameter vectors in bulk, where `params[i]` is annealed with extent
+    /// `extents[i]`. If the `rayon` feature is enabled, multiple calls to `anneal` will be run in
+    /// parallel using `rayon`, otherwise they will execute sequentially. If the `rayon` feature is
+    /// enabled, parallelization can still be turned off by overwriting `parallelize` to return
+    /// `false`. Note that even if `parallelize` is set to false, the parameter vectors and the
+    /// problem are still required to be `Send` and `Sync`. Those bounds are linked to the `rayon`
+    /// feature. This method can be overwritten.
+    ///
+    /// Returns an `InvalidParameter` error if `params` and `extents` differ in length.
+    fn bulk_anneal<P>(
+        &self,
+        params: &[P],
+        extents: &[Self::Float],
+    ) -> Result<Vec<Self::Output>, Error>
+    where
+        P: std::borrow::Borrow<Self::Param> + SyncAlias,
+        Self::Output: SendAlias,
+        Self::Float: Copy + SyncAlias,
+        Self: SyncAlias,
+    {
+        check_extents(params.len(), extents.len())?;
+        #[cfg(feature = "rayon")]
+        {
+            if self.parallelize() {
+                params
+                    .par_iter()
+                    .zip(extents.par_iter())
+                    .map(|(p, &e)| self.anneal(p.borrow(), e))
+                    .collect()
+            } else {
+                params
+                    .iter()
+                    .zip(extents.iter())
+                    .map(|(p, &e)| self.anneal(p.borrow(), e))
+                    .collect()
+            }
+        }
+        #[cfg(not(feature = "rayon"))]
+        {
+            params
+                .iter()
+                .zip(extents.iter())
+                .map(|(p, &e)| self.anneal(p.borrow(), e))
+                .collect()
+        }
+    }
+
+    /// Indicates whether to parallelize calls to `anneal` when using `bulk_anneal`. By default
+    /// returns true, but can be set manually to `false` if needed. This allows users to turn off
+    /// parallelization for certain traits implemented on their problem. Note that parallelization
+    /// requires the `rayon` feature to be enabled, otherwise calls to `anneal` will be executed
+    /// sequentially independent of how `parallelize` is set.
+    fn parallelize(&self) -> bool {
+        true
+    }
 }
 
 /// Wraps a call to `anneal` defined in the `Anneal` trait and as such allows to call `anneal` on
@@ -77,6 +135,70 @@ impl<O: Anneal> Problem<O> {
     pub fn anneal(&mut self, param: &O::Param, extent: O::Float) -> Result<O::Output, Error> {
         self.problem("anneal_count", |problem| problem.anneal(param, extent))
     }
+
+    /// Calls `bulk_anneal` defined in the `Anneal` trait and keeps track of the number of
+    /// evaluations. Returns an `InvalidParameter` error if `params` and `extents` differ in
+    /// length.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, Error};
+    /// # use argmin::solver::simulatedannealing::Anneal;
+    /// #
+    /// # #[derive(Eq, PartialEq, Debug, Clone)]
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl Anneal for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Output = Vec<f64>;
+    /// #     type Float = f64;
+    /// #
+    /// #     fn anneal(&self, param: &Self::Param, extent: Self::Float) -> Result<Self::Output, Error> {
+    /// #         Ok(param.iter().map(|x| x + extent).collect())
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `Anneal`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let param1 = vec![2.0f64, 1.0f64];
+    /// let param2 = vec![3.0f64, 5.0f64];
+    /// let params = vec![&param1, &param2];
+    ///
+    /// let res = problem1.bulk_anneal(&params, &[1.0, 2.0]);
+    ///
+    /// assert_eq!(problem1.counts["anneal_count"], 2);
+    /// # let res = res.unwrap();
+    /// # assert_eq!(res[0], vec![3.0f64, 2.0f64]);
+    /// # assert_eq!(res[1], vec![5.0f64, 7.0f64]);
+    /// ```
+    pub fn bulk_anneal<P>(
+        &mut self,
+        params: &[P],
+        extents: &[O::Float],
+    ) -> Result<Vec<O::Output>, Error>
+    where
+        P: std::borrow::Borrow<O::Param> + SyncAlias,
+        O::Output: SendAlias,
+        O::Float: Copy + SyncAlias,
+        O: SyncAlias,
+    {
+        check_extents(params.len(), extents.len())?;
+        self.bulk_problem("anneal_count", params.len(), |problem| {
+            problem.bulk_anneal(params, extents)
+        })
+    }
+}
+
+/// Ensures that every parameter vector passed to `bulk_anneal` comes with an extent.
+fn check_extents(num_params: usize, num_extents: usize) -> Result<(), Error> {
+    if num_params != num_extents {
+        return Err(argmin_error!(
+            InvalidParameter,
+            format!("`bulk_anneal`: got {num_params} parameter vectors but {num_extents} extents.")
+        ));
+    }
+    Ok(())
 }
 
 /// Temperature functions for Simulated Annealing.
@@ -879,4 +1001,29 @@ mod tests {
 
         assert_eq!(state_out.get_cost().to_ne_bytes(), 1.0f64.to_ne_bytes())
     }
+
+    #[test]
+    fn test_bulk_anneal_length_mismatch() {
+        let params = vec![vec![1.0f64, 2.0], vec![3.0, 4.0]];
+
+        let res = TestProblem::new().bulk_anneal(&params, &[1.0]);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`bulk_anneal`: got 2 parameter vectors but 1 extents.\""
+        );
+
+        let mut problem = Problem::new(TestProblem::new());
+        let res = problem.bulk_anneal(&params, &[1.0, 2.0, 3.0]);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`bulk_anneal`: got 2 parameter vectors but 3 extents.\""
+        );
+        assert!(!problem.counts.contains_key("anneal_count"));
+
+        let res = problem.bulk_anneal(&params, &[1.0, 2.0]).unwrap();
+        assert_eq!(res.len(), 2);
+        assert_eq!(problem.counts["anneal_count"], 2);
+    }
 }
@@ -0,0 +1,1215 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Sequential quadratic programming (SQP)
+//!
+//! Solves smooth problems with equality and inequality constraints by solving a sequence of
+//! quadratic programs built from a quasi-Newton approximation of the Hessian of the Lagrangian
+//! and linearizations of the constraints.
+//!
+//! See [`SQP`] for details.
+//!
+//! ## References
+//!
+//! Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+//! Springer. ISBN 0-387-30303-0.
+//!
+//! M. J. D. Powell (1978). A fast algorithm for nonlinearly constrained optimization
+//! calculations. In: Numerical Analysis, Lecture Notes in Mathematics 630, pp. 144-157.
+
+use crate::core::{
+    ArgminFloat, ConstraintJacobian, CostFunction, EqualityConstraints, Error, Executor, Gradient,
+    Hessian, InequalityConstraints, IterState, LineSearch, OptimizationResult, Problem, Solver,
+    TerminationReason, TerminationStatus, KV,
+};
+use crate::solver::dense::{
+    add, chol_solve, dot, lu_solve, mat_vec, modified_cholesky, scale, sub, to_param, to_vec,
+};
+use argmin_math::{ArgminGet, ArgminScaledAdd, ArgminSet, ArgminSize};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of sweeps of the coordinate ascent which solves the quadratic subproblem
+const QP_MAX_SWEEPS: usize = 10000;
+
+/// # Sequential quadratic programming (SQP)
+///
+/// Minimizes a cost function `f(x)` subject to equality constraints `c(x) = 0` and inequality
+/// constraints `g(x) <= 0` (see [`EqualityConstraints`] and [`InequalityConstraints`]). In each
+/// iteration, the search direction `p` is the solution of the quadratic program
+///
+/// ```text
+/// min_p  1/2 p^T B p + grad f(x)^T p
+/// s.t.   c(x) + J_c(x) p = 0
+///        g(x) + J_g(x) p <= 0
+/// ```
+///
+/// where `J_c` and `J_g` are the Jacobians of the constraints and `B` is an approximation of the
+/// Hessian of the Lagrangian. The multipliers of the quadratic program are the new multiplier
+/// estimates. The quadratic program is solved via its dual, which is a bound constrained problem
+/// in the multipliers, and therefore requires linearizations of the constraints which are
+/// consistent.
+///
+/// The step length along `p` is determined by the line search provided via the constructor (for
+/// instance [`BacktrackingLineSearch`] with [`ArmijoCondition`]), applied to the l1 merit
+/// function
+///
+/// ```text
+/// phi(x) = f(x) + mu (sum_i |c_i(x)| + sum_j max(0, g_j(x)))
+/// ```
+///
+/// (see [`MeritFunction`]). Following Powell, the penalty parameter `mu` is set to
+/// `max(1.1 |y|, (mu + 1.1 |y|) / 2)` in every iteration, where `|y|` is the largest absolute
+/// value of the multiplier estimates. This keeps `mu` larger than the multipliers, which
+/// guarantees that `p` is a descent direction of the merit function, while allowing `mu` to
+/// decrease again after overly large multiplier estimates in early iterations.
+///
+/// The initial approximation `B` is the Hessian of the cost function at the initial parameter
+/// vector unless a Hessian is provided via the [`configure`](`crate::core::Executor::configure`)
+/// method of the [`Executor`](`crate::core::Executor`) (see [`IterState::hessian`]). Since the
+/// initial multiplier estimates are zero, this is the Hessian of the Lagrangian at the initial
+/// parameter vector. If it is not positive definite, a multiple of the identity is added.
+/// Afterwards, `B` is updated with the damped BFGS update of Powell, which keeps `B` positive
+/// definite even if the curvature of the Lagrangian along the step is negative.
+///
+/// The algorithm stops once the KKT residual, the maximum of the norm of the gradient of the
+/// Lagrangian, the constraint violation and the violation of complementarity, is below the
+/// tolerance set via [`with_tolerance`](`SQP::with_tolerance`).
+///
+/// The KKT residual is reported as KV metric `kkt_residual`, together with the value of the cost
+/// function (`objective`), the constraint violation `max(max_i |c_i(x)|, max_j g_j(x), 0)`
+/// (`constraint_violation`) and the penalty parameter (`penalty`). The constraint violation and
+/// the multiplier estimates (equality constraints first, followed by inequality constraints) are
+/// also stored in the state (see [`IterState::get_constraint_violation`] and
+/// [`IterState::get_multipliers`]). The cost function value of parameter vectors whose constraint
+/// violation exceeds the tolerance is reported as infinity, such that the best parameter vector
+/// is the best feasible one.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`], [`Gradient`],
+/// [`Hessian`], [`EqualityConstraints`], [`InequalityConstraints`] and [`ConstraintJacobian`].
+/// Problems without equality or inequality constraints can rely on the default implementations of
+/// the respective constraint trait. The optional Jacobian methods of the constraint traits are not
+/// used; the Jacobians are only taken from [`ConstraintJacobian`], which is evaluated whenever
+/// constraints are present. The Hessian is only evaluated if no initial Hessian is provided.
+///
+/// ## References
+///
+/// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+/// Springer. ISBN 0-387-30303-0.
+///
+/// M. J. D. Powell (1978). A fast algorithm for nonlinearly constrained optimization
+/// calculations. In: Numerical Analysis, Lecture Notes in Mathematics 630, pp. 144-157.
+///
+/// [`BacktrackingLineSearch`]: `crate::solver::linesearch::BacktrackingLineSearch`
+/// [`ArmijoCondition`]: `crate::solver::linesearch::condition::ArmijoCondition`
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct SQP<L, F> {
+    /// line search
+    linesearch: L,
+    /// Tolerance on the KKT residual
+    tol: F,
+    /// Penalty parameter of the merit function
+    penalty: F,
+    /// Linearization of the constraints at the current parameter vector
+    linearization: Linearization<F>,
+    /// Cost function value at the current parameter vector
+    objective: F,
+    /// KKT residual at the current parameter vector
+    kkt_residual: F,
+}
+
+impl<L, F> SQP<L, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `SQP`
+    ///
+    /// Requires a line search which is applied to the merit function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::sqp::SQP;
+    /// # let linesearch = ();
+    /// let sqp: SQP<_, f64> = SQP::new(linesearch);
+    /// ```
+    pub fn new(linesearch: L) -> Self {
+        SQP {
+            linesearch,
+            tol: float!(1e-6),
+            penalty: float!(1.0),
+            linearization: Linearization::default(),
+            objective: F::infinity(),
+            kkt_residual: F::infinity(),
+        }
+    }
+
+    /// Set tolerance on the KKT residual
+    ///
+    /// Must be larger than `0`. Defaults to `1e-6`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::sqp::SQP;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let sqp: SQP<_, f64> = SQP::new(linesearch).with_tolerance(1e-8)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SQP`: tolerance must be > 0."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Set initial penalty parameter of the merit function
+    ///
+    /// Must be larger than `0`. Defaults to `1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::sqp::SQP;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let sqp: SQP<_, f64> = SQP::new(linesearch).with_penalty(10.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_penalty(mut self, penalty: F) -> Result<Self, Error> {
+        if penalty <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SQP`: penalty must be > 0."
+            ));
+        }
+        self.penalty = penalty;
+        Ok(self)
+    }
+
+    /// Evaluates the constraints and their Jacobians at `param`
+    fn linearize<O, P>(problem: &mut Problem<O>, param: &P) -> Result<Linearization<F>, Error>
+    where
+        O: EqualityConstraints<Param = P, Float = F>
+            + InequalityConstraints<Param = P, Float = F>
+            + ConstraintJacobian<Param = P>,
+        P: ArgminGet<usize, F> + ArgminSize<usize>,
+    {
+        let eq = problem.equality_constraints(param)?;
+        let ineq = problem.inequality_constraints(param)?;
+        if eq.is_empty() && ineq.is_empty() {
+            return Ok(Linearization::default());
+        }
+        let (eq_jacobian, ineq_jacobian) = problem.constraint_jacobian(param)?;
+        if eq_jacobian.len() != eq.len() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SQP`: number of rows of the Jacobian of the equality constraints does not match number of constraints."
+            ));
+        }
+        if ineq_jacobian.len() != ineq.len() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SQP`: number of rows of the Jacobian of the inequality constraints does not match number of constraints."
+            ));
+        }
+        Ok(Linearization {
+            eq,
+            ineq,
+            eq_jacobian: eq_jacobian.iter().map(to_vec).collect(),
+            ineq_jacobian: ineq_jacobian.iter().map(to_vec).collect(),
+        })
+    }
+
+    /// Returns the KKT residual for the given gradient and multipliers
+    fn kkt_residual(&self, grad: &[F], multipliers: &[F]) -> F {
+        let lin = &self.linearization;
+        let stationarity = lin
+            .gradient_of_lagrangian(grad, multipliers)
+            .iter()
+            .fold(float!(0.0), |acc: F, &x| acc.max(x.abs()));
+        let complementarity = lin
+            .ineq
+            .iter()
+            .zip(multipliers[lin.eq.len()..].iter())
+            .fold(float!(0.0), |acc: F, (&g, &nu)| acc.max((g * nu).abs()));
+        stationarity.max(lin.infeasibility()).max(complementarity)
+    }
+
+    /// KV metrics of the current iteration
+    fn kv(&self) -> KV {
+        kv!(
+            "objective" => self.objective;
+            "kkt_residual" => self.kkt_residual;
+            "constraint_violation" => self.linearization.infeasibility();
+            "penalty" => self.penalty;
+        )
+    }
+}
+
+impl<O, L, P, H, F> Solver<O, IterState<P, P, (), H, (), F>> for SQP<L, F>
+where
+    O: CostFunction<Param = P, Output = F>
+        + Gradient<Param = P, Gradient = P>
+        + Hessian<Param = P, Hessian = H>
+        + EqualityConstraints<Param = P, Float = F>
+        + InequalityConstraints<Param = P, Float = F>
+        + ConstraintJacobian<Param = P>,
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    H: ArgminGet<(usize, usize), F> + ArgminSet<(usize, usize), F>,
+    L: Clone + LineSearch<P, F> + Solver<MeritFunction<O, F>, IterState<P, P, (), (), (), F>>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "SQP";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, P, (), H, (), F>,
+    ) -> Result<(IterState<P, P, (), H, (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`SQP` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+
+        let cost = state.get_cost();
+        let cost = if cost.is_infinite() {
+            problem.cost(&param)?
+        } else {
+            cost
+        };
+
+        let grad = state
+            .take_gradient()
+            .map(Result::Ok)
+            .unwrap_or_else(|| problem.gradient(&param))?;
+
+        let hessian = state
+            .take_hessian()
+            .map(Result::Ok)
+            .unwrap_or_else(|| problem.hessian(&param))?;
+
+        self.linearization = Self::linearize(problem, &param)?;
+        let multipliers = vec![float!(0.0); self.linearization.num_constraints()];
+        self.kkt_residual = self.kkt_residual(&to_vec(&grad), &multipliers);
+        let infeasibility = self.linearization.infeasibility();
+        self.objective = cost;
+        let kv = self.kv();
+
+        Ok((
+            state
+                .param(param)
+                .cost(if infeasibility <= self.tol {
+                    cost
+                } else {
+                    F::infinity()
+                })
+                .gradient(grad)
+                .hessian(hessian)
+                .constraint_violation(infeasibility)
+                .multipliers(multipliers),
+            Some(kv),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, P, (), H, (), F>,
+    ) -> Result<(IterState<P, P, (), H, (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`SQP`: Parameter vector in state not set."
+        ))?;
+
+        let grad = state.take_gradient().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`SQP`: Gradient in state not set."
+        ))?;
+
+        let mut hessian = state.take_hessian().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`SQP`: Hessian in state not set."
+        ))?;
+
+        let multipliers = state.take_multipliers().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`SQP`: Multipliers in state not set."
+        ))?;
+
+        let n = param.shape();
+        let x = to_vec(&param);
+        let g = to_vec(&grad);
+        let b: Vec<Vec<F>> = (0..n)
+            .map(|i| (0..n).map(|j| hessian.get((i, j))).collect())
+            .collect();
+
+        let (p, multipliers) = self.linearization.solve_qp(&b, &g, multipliers)?;
+
+        let max_multiplier = multipliers
+            .iter()
+            .fold(float!(0.0), |acc: F, &m| acc.max(m.abs()));
+        // Powell's update, which allows the penalty parameter to decrease again
+        self.penalty = (float!(1.1) * max_multiplier)
+            .max(float!(0.5) * (self.penalty + float!(1.1) * max_multiplier));
+
+        // The gradient of the merit function where it is differentiable. Its dot product with
+        // the search direction is the directional derivative of the merit function.
+        let lin = &self.linearization;
+        let mut merit_grad = g.clone();
+        for (&c, row) in lin.eq.iter().zip(lin.eq_jacobian.iter()) {
+            if c != float!(0.0) {
+                merit_grad = add(&merit_grad, &scale(row, self.penalty * c.signum()));
+            }
+        }
+        for (&c, row) in lin.ineq.iter().zip(lin.ineq_jacobian.iter()) {
+            if c > float!(0.0) {
+                merit_grad = add(&merit_grad, &scale(row, self.penalty));
+            }
+        }
+        let merit_cost = self.objective + self.penalty * lin.violation_l1();
+
+        self.linesearch.search_direction(to_param(&param, &p));
+
+        let merit = MeritFunction {
+            problem: problem.take_problem().unwrap(),
+            penalty: self.penalty,
+        };
+        let OptimizationResult {
+            problem: mut line_problem,
+            state: mut sub_state,
+            ..
+        } = Executor::new(merit, self.linesearch.clone())
+            .configure(|config| {
+                config
+                    .param(param.clone())
+                    .gradient(to_param(&param, &merit_grad))
+                    .cost(merit_cost)
+            })
+            .ctrlc(false)
+            .run()?;
+
+        let xk1 = sub_state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`SQP`: No parameters returned by line search."
+        ))?;
+
+        // take back problem and take care of function evaluation counts
+        let merit = line_problem.take_problem().unwrap();
+        let cost_evals = line_problem.counts.get("cost_count").cloned().unwrap_or(0);
+        let grad_evals = line_problem
+            .counts
+            .get("gradient_count")
+            .cloned()
+            .unwrap_or(0);
+        problem.problem = Some(merit.problem);
+        problem.consume_func_counts(line_problem);
+        let mut add_counts = |counts_string: &'static str, evals: u64| {
+            if evals > 0 {
+                *problem.counts.entry(counts_string).or_insert(0) += evals;
+            }
+        };
+        if !self.linearization.eq.is_empty() {
+            add_counts("equality_constraints_count", cost_evals + grad_evals);
+        }
+        if !self.linearization.ineq.is_empty() {
+            add_counts("inequality_constraints_count", cost_evals + grad_evals);
+        }
+        if !(self.linearization.eq.is_empty() && self.linearization.ineq.is_empty()) {
+            add_counts("constraint_jacobian_count", grad_evals);
+        }
+
+        let cost = problem.cost(&xk1)?;
+        let grad_k1 = problem.gradient(&xk1)?;
+        let g_k1 = to_vec(&grad_k1);
+        let grad_lagrangian = self.linearization.gradient_of_lagrangian(&g, &multipliers);
+        self.linearization = Self::linearize(problem, &xk1)?;
+        let grad_lagrangian_k1 = self
+            .linearization
+            .gradient_of_lagrangian(&g_k1, &multipliers);
+
+        // damped BFGS update
+        let s = sub(&to_vec(&xk1), &x);
+        let y = sub(&grad_lagrangian_k1, &grad_lagrangian);
+        let bs = mat_vec(&b, &s);
+        let sbs = dot(&s, &bs);
+        if sbs > float!(0.0) {
+            let sy = dot(&s, &y);
+            let theta = if sy >= float!(0.2) * sbs {
+                float!(1.0)
+            } else {
+                float!(0.8) * sbs / (sbs - sy)
+            };
+            let r = add(&scale(&y, theta), &scale(&bs, float!(1.0) - theta));
+            let sr = dot(&s, &r);
+            for i in 0..n {
+                for j in 0..n {
+                    hessian.set((i, j), b[i][j] - bs[i] * bs[j] / sbs + r[i] * r[j] / sr);
+                }
+            }
+        }
+
+        self.kkt_residual = self.kkt_residual(&g_k1, &multipliers);
+        let infeasibility = self.linearization.infeasibility();
+        self.objective = cost;
+        let kv = self.kv();
+
+        Ok((
+            state
+                .param(xk1)
+                .cost(if infeasibility <= self.tol {
+                    cost
+                } else {
+                    F::infinity()
+                })
+                .gradient(grad_k1)
+                .hessian(hessian)
+                .constraint_violation(infeasibility)
+                .multipliers(multipliers),
+            Some(kv),
+        ))
+    }
+
+    fn terminate(&mut self, _state: &IterState<P, P, (), H, (), F>) -> TerminationStatus {
+        if self.kkt_residual <= self.tol {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+        TerminationStatus::NotTerminated
+    }
+}
+
+/// l1 merit function of a constrained optimization problem
+///
+/// Minimized by the line search of [`SQP`]. Implements [`CostFunction`] and [`Gradient`], where
+/// the gradient is the gradient of the merit function wherever it is differentiable.
+#[derive(Clone, Debug)]
+pub struct MeritFunction<O, F> {
+    /// Constrained problem
+    problem: O,
+    /// Penalty parameter
+    penalty: F,
+}
+
+impl<O, P, F> CostFunction for MeritFunction<O, F>
+where
+    O: CostFunction<Param = P, Output = F>
+        + EqualityConstraints<Param = P, Float = F>
+        + InequalityConstraints<Param = P, Float = F>,
+    F: ArgminFloat,
+{
+    type Param = P;
+    type Output = F;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        let eq = self.problem.equality_constraints(param)?;
+        let ineq = self.problem.inequality_constraints(param)?;
+        let violation = eq.iter().fold(float!(0.0), |acc: F, &c| acc + c.abs())
+            + ineq
+                .iter()
+                .fold(float!(0.0), |acc: F, &g| acc + g.max(float!(0.0)));
+        Ok(self.problem.cost(param)? + self.penalty * violation)
+    }
+}
+
+impl<O, P, F> Gradient for MeritFunction<O, F>
+where
+    O: Gradient<Param = P, Gradient = P>
+        + EqualityConstraints<Param = P, Float = F>
+        + InequalityConstraints<Param = P, Float = F>
+        + ConstraintJacobian<Param = P>,
+    P: ArgminScaledAdd<P, F, P>,
+    F: ArgminFloat,
+{
+    type Param = P;
+    type Gradient = P;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        let mut grad = self.problem.gradient(param)?;
+        let eq = self.problem.equality_constraints(param)?;
+        let ineq = self.problem.inequality_constraints(param)?;
+        if eq.iter().any(|&c| c != float!(0.0)) || ineq.iter().any(|&g| g > float!(0.0)) {
+            let (eq_jacobian, ineq_jacobian) = self.problem.constraint_jacobian(param)?;
+            for (c, row) in eq.iter().zip(eq_jacobian) {
+                if *c != float!(0.0) {
+                    grad = grad.scaled_add(&(self.penalty * c.signum()), &row);
+                }
+            }
+            for (g, row) in ineq.iter().zip(ineq_jacobian) {
+                if *g > float!(0.0) {
+                    grad = grad.scaled_add(&self.penalty, &row);
+                }
+            }
+        }
+        Ok(grad)
+    }
+}
+
+/// Values and Jacobians of the constraints at a parameter vector
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+struct Linearization<F> {
+    /// Values of the equality constraints
+    eq: Vec<F>,
+    /// Values of the inequality constraints
+    ineq: Vec<F>,
+    /// Jacobian of the equality constraints
+    eq_jacobian: Vec<Vec<F>>,
+    /// Jacobian of the inequality constraints
+    ineq_jacobian: Vec<Vec<F>>,
+}
+
+impl<F> Default for Linearization<F> {
+    fn default() -> Self {
+        Linearization {
+            eq: vec![],
+            ineq: vec![],
+            eq_jacobian: vec![],
+            ineq_jacobian: vec![],
+        }
+    }
+}
+
+impl<F: ArgminFloat> Linearization<F> {
+    fn num_constraints(&self) -> usize {
+        self.eq.len() + self.ineq.len()
+    }
+
+    /// Returns `max(max_i |c_i(x)|, max_j g_j(x), 0)`
+    fn infeasibility(&self) -> F {
+        let eq_violation = self
+            .eq
+            .iter()
+            .fold(float!(0.0), |acc: F, c| acc.max(c.abs()));
+        self.ineq.iter().fold(eq_violation, |acc, &g| acc.max(g))
+    }
+
+    /// Returns `sum_i |c_i(x)| + sum_j max(0, g_j(x))`
+    fn violation_l1(&self) -> F {
+        self.eq.iter().fold(float!(0.0), |acc: F, &c| acc + c.abs())
+            + self
+                .ineq
+                .iter()
+                .fold(float!(0.0), |acc: F, &g| acc + g.max(float!(0.0)))
+    }
+
+    fn rows(&self) -> impl Iterator<Item = &Vec<F>> {
+        self.eq_jacobian.iter().chain(self.ineq_jacobian.iter())
+    }
+
+    /// Returns the gradient of the Lagrangian given the gradient of the cost function
+    fn gradient_of_lagrangian(&self, grad: &[F], multipliers: &[F]) -> Vec<F> {
+        self.rows()
+            .zip(multipliers.iter())
+            .fold(grad.to_vec(), |acc, (row, &m)| add(&acc, &scale(row, m)))
+    }
+
+    /// Solves the quadratic program for the search direction
+    ///
+    /// The dual of the quadratic program is maximized by coordinate ascent (Hildreth's method),
+    /// starting from the given multipliers. After each sweep, the dual is solved directly on the
+    /// active set estimated from the current multipliers, which terminates the coordinate ascent
+    /// early if the constraint gradients are nearly linearly dependent. Returns the search direction and the multipliers, or
+    /// an error if `b` cannot be made positive definite or if the coordinate ascent does not
+    /// converge within `QP_MAX_SWEEPS` sweeps (which happens if the linearized constraints are
+    /// inconsistent).
+    fn solve_qp(&self, b: &[Vec<F>], grad: &[F], mut y: Vec<F>) -> Result<(Vec<F>, Vec<F>), Error> {
+        let chol = modified_cholesky(b).ok_or_else(argmin_error_closure!(
+            ConditionViolated,
+            "`SQP`: Hessian approximation cannot be made positive definite."
+        ))?;
+        let u = chol_solve(&chol, grad);
+        let v: Vec<Vec<F>> = self.rows().map(|row| chol_solve(&chol, row)).collect();
+        let rows: Vec<&Vec<F>> = self.rows().collect();
+        let m = rows.len();
+        let num_eq = self.eq.len();
+        let m_mat: Vec<Vec<F>> = rows
+            .iter()
+            .map(|row| v.iter().map(|vl| dot(row, vl)).collect())
+            .collect();
+        // residuals of the linearized constraints for zero multipliers
+        let e: Vec<F> = rows
+            .iter()
+            .zip(self.eq.iter().chain(self.ineq.iter()))
+            .map(|(row, &c)| c - dot(row, &u))
+            .collect();
+        let tol = F::epsilon()
+            * float!(100.0)
+            * e.iter().fold(float!(1.0), |acc: F, &x| acc.max(x.abs()));
+
+        let mut converged = false;
+        for _ in 0..QP_MAX_SWEEPS {
+            let mut change = float!(0.0);
+            for k in 0..m {
+                if m_mat[k][k] <= F::epsilon() {
+                    continue;
+                }
+                let r = e[k] - dot(&m_mat[k], &y);
+                let mut yk = y[k] + r / m_mat[k][k];
+                if k >= num_eq {
+                    yk = yk.max(float!(0.0));
+                }
+                change = change.max(((yk - y[k]) * m_mat[k][k]).abs());
+                y[k] = yk;
+            }
+            if change <= tol {
+                converged = true;
+                break;
+            }
+            if let Some(y_active) = active_set_multipliers(&m_mat, &e, &y, num_eq, tol) {
+                y = y_active;
+                converged = true;
+                break;
+            }
+        }
+        if !converged {
+            return Err(argmin_error!(
+                ConditionViolated,
+                format!(
+                    "`SQP`: quadratic subproblem did not converge within {QP_MAX_SWEEPS} sweeps. The linearized constraints may be inconsistent."
+                )
+            ));
+        }
+
+        let p = v
+            .iter()
+            .zip(y.iter())
+            .fold(scale(&u, float!(-1.0)), |acc, (vl, &yl)| {
+                sub(&acc, &scale(vl, yl))
+            });
+        Ok((p, y))
+    }
+}
+
+/// Solves the dual of the quadratic program with all equality constraints and the inequality
+/// constraints with positive multipliers `y` treated as active
+///
+/// Returns the multipliers if they are optimal, that is if the multipliers of the inequality
+/// constraints are nonnegative and the inactive inequality constraints are satisfied.
+fn active_set_multipliers<F: ArgminFloat>(
+    m_mat: &[Vec<F>],
+    e: &[F],
+    y: &[F],
+    num_eq: usize,
+    tol: F,
+) -> Option<Vec<F>> {
+    let active: Vec<usize> = (0..y.len())
+        .filter(|&k| k < num_eq || y[k] > float!(0.0))
+        .collect();
+    let a = active
+        .iter()
+        .map(|&k| active.iter().map(|&l| m_mat[k][l]).collect())
+        .collect();
+    let rhs = active.iter().map(|&k| e[k]).collect();
+    let y_active = lu_solve(a, rhs)?;
+    let mut y_new = vec![float!(0.0); y.len()];
+    for (&k, &yk) in active.iter().zip(y_active.iter()) {
+        if k >= num_eq && yk < float!(0.0) {
+            return None;
+        }
+        y_new[k] = yk;
+    }
+    (num_eq..y.len())
+        .filter(|k| !active.contains(k))
+        .all(|k| e[k] - dot(&m_mat[k], &y_new) <= tol)
+        .then_some(y_new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, State};
+    use crate::solver::linesearch::{condition::ArmijoCondition, BacktrackingLineSearch};
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    type Linesearch = BacktrackingLineSearch<Vec<f64>, Vec<f64>, ArmijoCondition<f64>, f64>;
+
+    test_trait_impl!(sqp, SQP<Linesearch, f64>);
+
+    fn sqp() -> SQP<Linesearch, f64> {
+        SQP::new(BacktrackingLineSearch::new(
+            ArmijoCondition::new(1e-4).unwrap(),
+        ))
+    }
+
+    /// min (x_0 - 1)^2 + (x_1 - 2)^2  s.t.  x_0 + x_1 = 1
+    ///
+    /// Solution: x = (0, 1) with multiplier 2
+    struct EqProblem {}
+
+    impl CostFunction for EqProblem {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok((p[0] - 1.0).powi(2) + (p[1] - 2.0).powi(2))
+        }
+    }
+
+    impl Gradient for EqProblem {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(vec![2.0 * (p[0] - 1.0), 2.0 * (p[1] - 2.0)])
+        }
+    }
+
+    impl Hessian for EqProblem {
+        type Param = Vec<f64>;
+        type Hessian = Vec<Vec<f64>>;
+
+        fn hessian(&self, _p: &Self::Param) -> Result<Self::Hessian, Error> {
+            Ok(vec![vec![2.0, 0.0], vec![0.0, 2.0]])
+        }
+    }
+
+    impl EqualityConstraints for EqProblem {
+        type Param = Vec<f64>;
+        type Float = f64;
+
+        fn equality_constraints(&self, p: &Self::Param) -> Result<Vec<f64>, Error> {
+            Ok(vec![p[0] + p[1] - 1.0])
+        }
+    }
+
+    impl InequalityConstraints for EqProblem {
+        type Param = Vec<f64>;
+        type Float = f64;
+    }
+
+    impl ConstraintJacobian for EqProblem {
+        type Param = Vec<f64>;
+
+        fn constraint_jacobian(
+            &self,
+            _p: &Self::Param,
+        ) -> Result<(Vec<Vec<f64>>, Vec<Vec<f64>>), Error> {
+            Ok((vec![vec![1.0, 1.0]], vec![]))
+        }
+    }
+
+    /// min (x_0 - 2)^2 + (x_1 - 1)^2  s.t.  x_0^2 + x_1^2 <= 1,  -x_0 <= 0
+    ///
+    /// Solution: x = (2, 1) / sqrt(5) with multipliers (sqrt(5) - 1, 0)
+    struct IneqProblem {}
+
+    impl CostFunction for IneqProblem {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok((p[0] - 2.0).powi(2) + (p[1] - 1.0).powi(2))
+        }
+    }
+
+    impl Gradient for IneqProblem {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(vec![2.0 * (p[0] - 2.0), 2.0 * (p[1] - 1.0)])
+        }
+    }
+
+    impl Hessian for IneqProblem {
+        type Param = Vec<f64>;
+        type Hessian = Vec<Vec<f64>>;
+
+        fn hessian(&self, _p: &Self::Param) -> Result<Self::Hessian, Error> {
+            Ok(vec![vec![2.0, 0.0], vec![0.0, 2.0]])
+        }
+    }
+
+    impl EqualityConstraints for IneqProblem {
+        type Param = Vec<f64>;
+        type Float = f64;
+    }
+
+    impl InequalityConstraints for IneqProblem {
+        type Param = Vec<f64>;
+        type Float = f64;
+
+        fn inequality_constraints(&self, p: &Self::Param) -> Result<Vec<f64>, Error> {
+            Ok(vec![p[0].powi(2) + p[1].powi(2) - 1.0, -p[0]])
+        }
+    }
+
+    impl ConstraintJacobian for IneqProblem {
+        type Param = Vec<f64>;
+
+        fn constraint_jacobian(
+            &self,
+            p: &Self::Param,
+        ) -> Result<(Vec<Vec<f64>>, Vec<Vec<f64>>), Error> {
+            Ok((vec![], vec![vec![2.0 * p[0], 2.0 * p[1]], vec![-1.0, 0.0]]))
+        }
+    }
+
+    /// Rosenbrock function on the unit disk
+    ///
+    /// Solution: approximately x = (0.7864, 0.6177)
+    struct RosenbrockDisk {}
+
+    impl CostFunction for RosenbrockDisk {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok((1.0 - p[0]).powi(2) + 100.0 * (p[1] - p[0].powi(2)).powi(2))
+        }
+    }
+
+    impl Gradient for RosenbrockDisk {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(vec![
+                -2.0 * (1.0 - p[0]) - 400.0 * p[0] * (p[1] - p[0].powi(2)),
+                200.0 * (p[1] - p[0].powi(2)),
+            ])
+        }
+    }
+
+    impl Hessian for RosenbrockDisk {
+        type Param = Vec<f64>;
+        type Hessian = Vec<Vec<f64>>;
+
+        fn hessian(&self, p: &Self::Param) -> Result<Self::Hessian, Error> {
+            Ok(vec![
+                vec![2.0 - 400.0 * (p[1] - 3.0 * p[0].powi(2)), -400.0 * p[0]],
+                vec![-400.0 * p[0], 200.0],
+            ])
+        }
+    }
+
+    impl EqualityConstraints for RosenbrockDisk {
+        type Param = Vec<f64>;
+        type Float = f64;
+    }
+
+    impl InequalityConstraints for RosenbrockDisk {
+        type Param = Vec<f64>;
+        type Float = f64;
+
+        fn inequality_constraints(&self, p: &Self::Param) -> Result<Vec<f64>, Error> {
+            Ok(vec![p[0].powi(2) + p[1].powi(2) - 1.0])
+        }
+    }
+
+    impl ConstraintJacobian for RosenbrockDisk {
+        type Param = Vec<f64>;
+
+        fn constraint_jacobian(
+            &self,
+            p: &Self::Param,
+        ) -> Result<(Vec<Vec<f64>>, Vec<Vec<f64>>), Error> {
+            Ok((vec![], vec![vec![2.0 * p[0], 2.0 * p[1]]]))
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let SQP { tol, penalty, .. } = sqp();
+        assert_eq!(tol.to_ne_bytes(), 1e-6f64.to_ne_bytes());
+        assert_eq!(penalty.to_ne_bytes(), 1.0f64.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_builder_errors() {
+        assert_error!(
+            sqp().with_tolerance(0.0),
+            ArgminError,
+            "Invalid parameter: \"`SQP`: tolerance must be > 0.\""
+        );
+        assert_error!(
+            sqp().with_penalty(0.0),
+            ArgminError,
+            "Invalid parameter: \"`SQP`: penalty must be > 0.\""
+        );
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut solver = sqp();
+        let res = solver.init(&mut Problem::new(EqProblem {}), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`SQP` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_init() {
+        let mut solver = sqp();
+        let mut problem = Problem::new(IneqProblem {});
+        let (state, kv) = solver
+            .init(&mut problem, IterState::new().param(vec![1.0, 1.0]))
+            .unwrap();
+        assert_eq!(
+            state.get_constraint_violation().unwrap().to_ne_bytes(),
+            1.0f64.to_ne_bytes()
+        );
+        assert_eq!(*state.get_multipliers().unwrap(), vec![0.0, 0.0]);
+        assert_eq!(
+            *state.get_hessian().unwrap(),
+            vec![vec![2.0, 0.0], vec![0.0, 2.0]]
+        );
+        assert!(state.get_cost().is_infinite());
+        let kv = kv.unwrap();
+        // gradient of the cost function is (-2, 0)
+        assert_eq!(
+            kv.get("kkt_residual")
+                .unwrap()
+                .get_float()
+                .unwrap()
+                .to_ne_bytes(),
+            2.0f64.to_ne_bytes()
+        );
+        assert_eq!(problem.counts["cost_count"], 1);
+        assert_eq!(problem.counts["gradient_count"], 1);
+        assert_eq!(problem.counts["hessian_count"], 1);
+        assert_eq!(problem.counts["constraint_jacobian_count"], 1);
+    }
+
+    #[test]
+    fn test_init_provided_hessian() {
+        let mut solver = sqp();
+        let mut problem = Problem::new(IneqProblem {});
+        let hessian = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+        let (state, _) = solver
+            .init(
+                &mut problem,
+                IterState::new()
+                    .param(vec![1.0, 1.0])
+                    .hessian(hessian.clone()),
+            )
+            .unwrap();
+        assert_eq!(*state.get_hessian().unwrap(), hessian);
+        assert!(!problem.counts.contains_key("hessian_count"));
+    }
+
+    #[test]
+    fn test_solve_qp() {
+        // min 1/2 |p|^2 + (-1, -1)^T p  s.t.  p_0 - 0.5 = 0,  p_1 - 0.25 <= 0
+        let lin = Linearization {
+            eq: vec![-0.5],
+            ineq: vec![-0.25],
+            eq_jacobian: vec![vec![1.0, 0.0]],
+            ineq_jacobian: vec![vec![0.0, 1.0]],
+        };
+        let b = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+        let (p, y) = lin.solve_qp(&b, &[-1.0, -1.0], vec![0.0, 0.0]).unwrap();
+        assert_relative_eq!(p[0], 0.5, epsilon = 1e-12);
+        assert_relative_eq!(p[1], 0.25, epsilon = 1e-12);
+        assert_relative_eq!(y[0], 0.5, epsilon = 1e-12);
+        assert_relative_eq!(y[1], 0.75, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_solve_qp_nearly_dependent() {
+        // p_0 = 1 and p_0 + 1e-3 p_1 = 1 + 1e-3, for which coordinate ascent converges slowly
+        let lin = Linearization {
+            eq: vec![-1.0, -1.001],
+            ineq: vec![],
+            eq_jacobian: vec![vec![1.0, 0.0], vec![1.0, 1e-3]],
+            ineq_jacobian: vec![],
+        };
+        let b = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+        let (p, _) = lin.solve_qp(&b, &[0.0, 0.0], vec![0.0, 0.0]).unwrap();
+        assert_relative_eq!(p[0], 1.0, epsilon = 1e-6);
+        assert_relative_eq!(p[1], 1.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_solve_qp_errors() {
+        // p_0 = 0.5 and p_0 = 1 are inconsistent
+        let lin = Linearization {
+            eq: vec![-0.5, -1.0],
+            ineq: vec![],
+            eq_jacobian: vec![vec![1.0, 0.0], vec![1.0, 0.0]],
+            ineq_jacobian: vec![],
+        };
+        let b = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+        assert_error!(
+            lin.solve_qp(&b, &[-1.0, -1.0], vec![0.0, 0.0]),
+            ArgminError,
+            concat!(
+                "Condition violated: \"`SQP`: quadratic subproblem did not converge within 10000 ",
+                "sweeps. The linearized constraints may be inconsistent.\""
+            )
+        );
+
+        let lin = Linearization::default();
+        let b = vec![vec![1.0, 0.0], vec![0.0, f64::NAN]];
+        assert_error!(
+            lin.solve_qp(&b, &[-1.0, -1.0], vec![]),
+            ArgminError,
+            "Condition violated: \"`SQP`: Hessian approximation cannot be made positive definite.\""
+        );
+    }
+
+    #[test]
+    fn test_merit_function() {
+        let merit = MeritFunction {
+            problem: IneqProblem {},
+            penalty: 10.0,
+        };
+        // g = (0.25, -0.5): first constraint is violated, second one is satisfied
+        let p = vec![0.5, 1.0];
+        assert_relative_eq!(merit.cost(&p).unwrap(), 2.25 + 2.5, epsilon = 1e-12);
+        let grad = merit.gradient(&p).unwrap();
+        assert_relative_eq!(grad[0], -3.0 + 10.0, epsilon = 1e-12);
+        assert_relative_eq!(grad[1], 0.0 + 20.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_equality_constraints() {
+        let res = Executor::new(EqProblem {}, sqp())
+            .configure(|state| state.param(vec![0.0, 0.0]).max_iters(50))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        let param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(param[0], 0.0, epsilon = 1e-6);
+        assert_relative_eq!(param[1], 1.0, epsilon = 1e-6);
+        assert_relative_eq!(res.state.get_best_cost(), 2.0, epsilon = 1e-6);
+        assert_relative_eq!(res.state.get_multipliers().unwrap()[0], 2.0, epsilon = 1e-6);
+
+        let counts = res.state.get_func_counts();
+        assert_eq!(
+            counts["equality_constraints_count"],
+            counts["constraint_jacobian_count"] + counts["cost_count"] - counts["gradient_count"]
+        );
+    }
+
+    #[test]
+    fn test_inequality_constraints() {
+        let res = Executor::new(IneqProblem {}, sqp())
+            .configure(|state| state.param(vec![0.5, -0.5]).max_iters(50))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        let param = res.state.get_best_param().unwrap();
+        let sqrt5 = 5.0f64.sqrt();
+        assert_relative_eq!(param[0], 2.0 / sqrt5, epsilon = 1e-6);
+        assert_relative_eq!(param[1], 1.0 / sqrt5, epsilon = 1e-6);
+        let multipliers = res.state.get_multipliers().unwrap();
+        assert_relative_eq!(multipliers[0], sqrt5 - 1.0, epsilon = 1e-5);
+        assert_relative_eq!(multipliers[1], 0.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_rosenbrock_disk() {
+        let res = Executor::new(RosenbrockDisk {}, sqp())
+            .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(200))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        let param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(param[0], 0.7864, epsilon = 1e-4);
+        assert_relative_eq!(param[1], 0.6177, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn test_jacobian_dimension_mismatch() {
+        struct WrongJacobian {}
+
+        impl CostFunction for WrongJacobian {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(p[0].powi(2))
+            }
+        }
+
+        impl Gradient for WrongJacobian {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(vec![2.0 * p[0]])
+            }
+        }
+
+        impl Hessian for WrongJacobian {
+            type Param = Vec<f64>;
+            type Hessian = Vec<Vec<f64>>;
+
+            fn hessian(&self, _p: &Self::Param) -> Result<Self::Hessian, Error> {
+                Ok(vec![vec![2.0]])
+            }
+        }
+
+        impl EqualityConstraints for WrongJacobian {
+            type Param = Vec<f64>;
+            type Float = f64;
+
+            fn equality_constraints(&self, p: &Self::Param) -> Result<Vec<f64>, Error> {
+                Ok(vec![p[0] - 1.0])
+            }
+        }
+
+        impl InequalityConstraints for WrongJacobian {
+            type Param = Vec<f64>;
+            type Float = f64;
+        }
+
+        impl ConstraintJacobian for WrongJacobian {
+            type Param = Vec<f64>;
+
+            fn constraint_jacobian(
+                &self,
+                _p: &Self::Param,
+            ) -> Result<(Vec<Vec<f64>>, Vec<Vec<f64>>), Error> {
+                Ok((vec![], vec![]))
+            }
+        }
+
+        let res = Executor::new(WrongJacobian {}, sqp())
+            .configure(|state| state.param(vec![0.0]))
+            .run();
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`SQP`: number of rows of the Jacobian of the equality ",
+                "constraints does not match number of constraints.\""
+            )
+        );
+    }
+}
@@ -0,0 +1,504 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::{LearningRateSchedule, MinibatchSampler};
+use crate::core::{ArgminFloat, Error, IterState, MinibatchGradient, Problem, Solver, State, KV};
+use argmin_math::{ArgminAdd, ArgminDiv, ArgminMul, ArgminScaledAdd, ArgminSqrt, ArgminZeroLike};
+use rand::prelude::*;
+use rand_xoshiro::Xoshiro256PlusPlus;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Adam
+///
+/// Adaptive moment estimation. In each iteration, the gradient `g_k` is evaluated on a minibatch
+/// of samples and exponential moving averages of the gradient and of the elementwise squared
+/// gradient are updated:
+///
+/// ```text
+/// m_{k+1} = beta1 * m_k + (1 - beta1) * g_k
+/// v_{k+1} = beta2 * v_k + (1 - beta2) * g_k^2
+/// ```
+///
+/// After correcting the bias of these estimates towards zero, each parameter is updated with its
+/// own step length:
+///
+/// ```text
+/// x_{k+1} = x_k - lr_k * (m_{k+1} / (1 - beta1^(k+1))) / (sqrt(v_{k+1} / (1 - beta2^(k+1))) + eps)
+///               - lr_k * weight_decay * x_k
+/// ```
+///
+/// The learning rate `lr_k` is given by the [`LearningRateSchedule`]. The decay rates `beta1` and
+/// `beta2` (set via [`with_beta1`](`Adam::with_beta1`) and [`with_beta2`](`Adam::with_beta2`))
+/// default to `0.9` and `0.999`, and `eps` (set via [`with_epsilon`](`Adam::with_epsilon`))
+/// defaults to `1e-8`. The weight decay (set via [`with_weight_decay`](`Adam::with_weight_decay`))
+/// defaults to `0`. A positive weight decay is decoupled from the gradient based update, which is
+/// known as AdamW.
+///
+/// The batch size is set via [`with_batch_size`](`Adam::with_batch_size`) and defaults to `1`.
+/// The cost function is never evaluated. The solver runs until the maximum number of iterations
+/// is reached; the current learning rate and epoch are reported as KV metrics `learning_rate` and
+/// `epoch`.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`MinibatchGradient`].
+///
+/// ## References
+///
+/// Diederik P. Kingma and Jimmy Ba (2015). Adam: A Method for Stochastic Optimization.
+/// International Conference on Learning Representations.
+///
+/// Ilya Loshchilov and Frank Hutter (2019). Decoupled Weight Decay Regularization.
+/// International Conference on Learning Representations.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Adam<S, P, F, R> {
+    /// Learning rate schedule
+    schedule: S,
+    /// Decay rate of the first moment estimate
+    beta1: F,
+    /// Decay rate of the second moment estimate
+    beta2: F,
+    /// Regularization of the denominator
+    epsilon: F,
+    /// Decoupled weight decay
+    weight_decay: F,
+    /// Minibatch sampler
+    sampler: MinibatchSampler,
+    /// First moment estimate
+    m: Option<P>,
+    /// Second moment estimate
+    v: Option<P>,
+    /// Random number generator
+    rng: R,
+}
+
+impl<S, P, F> Adam<S, P, F, Xoshiro256PlusPlus>
+where
+    S: LearningRateSchedule<F>,
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `Adam`
+    ///
+    /// Takes the learning rate schedule as input. A constant learning rate can be given as a float.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::stochastic::Adam;
+    /// let adam: Adam<_, Vec<f64>, _, _> = Adam::new(0.001f64);
+    /// ```
+    pub fn new(schedule: S) -> Self {
+        Adam {
+            schedule,
+            beta1: float!(0.9),
+            beta2: float!(0.999),
+            epsilon: float!(1e-8),
+            weight_decay: float!(0.0),
+            sampler: MinibatchSampler::new(1),
+            m: None,
+            v: None,
+            rng: Xoshiro256PlusPlus::from_entropy(),
+        }
+    }
+}
+
+impl<S, P, F, R0> Adam<S, P, F, R0>
+where
+    S: LearningRateSchedule<F>,
+    F: ArgminFloat,
+    R0: Rng,
+{
+    /// Set the random number generator
+    ///
+    /// Defaults to `rand_xoshiro::Xoshiro256PlusPlus::from_entropy()`. In order to be able to
+    /// resume from a checkpoint, the random number generator needs to be serializable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::stochastic::Adam;
+    /// # use rand::SeedableRng;
+    /// let adam: Adam<_, Vec<f64>, _, _> = Adam::new(0.001f64)
+    ///     .with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(1729));
+    /// ```
+    pub fn with_rng_generator<R1: Rng>(self, generator: R1) -> Adam<S, P, F, R1> {
+        Adam {
+            schedule: self.schedule,
+            beta1: self.beta1,
+            beta2: self.beta2,
+            epsilon: self.epsilon,
+            weight_decay: self.weight_decay,
+            sampler: self.sampler,
+            m: self.m,
+            v: self.v,
+            rng: generator,
+        }
+    }
+
+    /// Set the number of samples per minibatch
+    ///
+    /// Must be larger than `0`. Defaults to `1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::stochastic::Adam;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let adam: Adam<_, Vec<f64>, _, _> = Adam::new(0.001f64).with_batch_size(32)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_batch_size(mut self, batch_size: usize) -> Result<Self, Error> {
+        if batch_size == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Adam`: batch size must be > 0."
+            ));
+        }
+        self.sampler = MinibatchSampler::new(batch_size);
+        Ok(self)
+    }
+
+    /// Set decay rate of the first moment estimate
+    ///
+    /// Must be in `[0, 1)`. Defaults to `0.9`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::stochastic::Adam;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let adam: Adam<_, Vec<f64>, _, _> = Adam::new(0.001f64).with_beta1(0.8)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_beta1(mut self, beta1: F) -> Result<Self, Error> {
+        if beta1 < float!(0.0) || beta1 >= float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Adam`: beta1 must be in [0, 1)."
+            ));
+        }
+        self.beta1 = beta1;
+        Ok(self)
+    }
+
+    /// Set decay rate of the second moment estimate
+    ///
+    /// Must be in `[0, 1)`. Defaults to `0.999`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::stochastic::Adam;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let adam: Adam<_, Vec<f64>, _, _> = Adam::new(0.001f64).with_beta2(0.99)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_beta2(mut self, beta2: F) -> Result<Self, Error> {
+        if beta2 < float!(0.0) || beta2 >= float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Adam`: beta2 must be in [0, 1)."
+            ));
+        }
+        self.beta2 = beta2;
+        Ok(self)
+    }
+
+    /// Set regularization of the denominator
+    ///
+    /// Must be larger than `0`. Defaults to `1e-8`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::stochastic::Adam;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let adam: Adam<_, Vec<f64>, _, _> = Adam::new(0.001f64).with_epsilon(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_epsilon(mut self, epsilon: F) -> Result<Self, Error> {
+        if epsilon <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Adam`: epsilon must be > 0."
+            ));
+        }
+        self.epsilon = epsilon;
+        Ok(self)
+    }
+
+    /// Set decoupled weight decay (AdamW)
+    ///
+    /// Must be non-negative. Defaults to `0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::stochastic::Adam;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let adamw: Adam<_, Vec<f64>, _, _> = Adam::new(0.001f64).with_weight_decay(0.01)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_weight_decay(mut self, weight_decay: F) -> Result<Self, Error> {
+        if weight_decay < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Adam`: weight decay must be >= 0."
+            ));
+        }
+        self.weight_decay = weight_decay;
+        Ok(self)
+    }
+}
+
+impl<O, S, P, G, F, R> Solver<O, IterState<P, G, (), (), (), F>> for Adam<S, P, F, R>
+where
+    O: MinibatchGradient<Param = P, Gradient = G>,
+    S: LearningRateSchedule<F>,
+    P: Clone
+        + ArgminZeroLike
+        + ArgminMul<F, P>
+        + ArgminDiv<F, P>
+        + ArgminDiv<P, P>
+        + ArgminAdd<F, P>
+        + ArgminScaledAdd<G, F, P>
+        + ArgminScaledAdd<P, F, P>
+        + ArgminSqrt,
+    G: ArgminMul<G, G>,
+    F: ArgminFloat,
+    R: Rng,
+{
+    const NAME: &'static str = "Adam";
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, (), (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`Adam` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+
+        let indices = self
+            .sampler
+            .next_batch(problem.num_samples(), &mut self.rng);
+        let grad = problem.minibatch_gradient(&param, &indices)?;
+        let learning_rate = self.schedule.learning_rate(state.get_iter());
+
+        let k = F::from_u64(state.get_iter() + 1).unwrap();
+        let bias1 = float!(1.0) - self.beta1.powf(k);
+        let bias2 = float!(1.0) - self.beta2.powf(k);
+
+        let m = self
+            .m
+            .take()
+            .unwrap_or_else(|| param.zero_like())
+            .mul(&self.beta1)
+            .scaled_add(&(float!(1.0) - self.beta1), &grad);
+        let v = self
+            .v
+            .take()
+            .unwrap_or_else(|| param.zero_like())
+            .mul(&self.beta2)
+            .scaled_add(&(float!(1.0) - self.beta2), &grad.mul(&grad));
+        let step = m
+            .div(&bias1)
+            .div(&v.div(&bias2).sqrt().add(&self.epsilon))
+            .scaled_add(&self.weight_decay, &param);
+        let param = param.scaled_add(&(-learning_rate), &step);
+        self.m = Some(m);
+        self.v = Some(v);
+
+        Ok((
+            state.param(param).gradient(grad),
+            Some(kv!(
+                "learning_rate" => learning_rate;
+                "epoch" => self.sampler.epoch;
+            )),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::stochastic::tests::LeastSquares;
+    use crate::solver::stochastic::CosineAnnealing;
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(adam, Adam<f64, Vec<f64>, f64, Xoshiro256PlusPlus>);
+
+    #[test]
+    fn test_new() {
+        let Adam {
+            schedule,
+            beta1,
+            beta2,
+            epsilon,
+            weight_decay,
+            sampler,
+            m,
+            v,
+            rng: _rng,
+        }: Adam<_, Vec<f64>, _, _> = Adam::new(0.1f64);
+        assert_eq!(schedule.to_ne_bytes(), 0.1f64.to_ne_bytes());
+        assert_eq!(beta1.to_ne_bytes(), 0.9f64.to_ne_bytes());
+        assert_eq!(beta2.to_ne_bytes(), 0.999f64.to_ne_bytes());
+        assert_eq!(epsilon.to_ne_bytes(), 1e-8f64.to_ne_bytes());
+        assert_eq!(weight_decay.to_ne_bytes(), 0.0f64.to_ne_bytes());
+        assert_eq!(sampler.batch_size, 1);
+        assert!(m.is_none());
+        assert!(v.is_none());
+    }
+
+    #[test]
+    fn test_builders() {
+        let adam: Adam<_, Vec<f64>, _, _> = Adam::new(0.1f64)
+            .with_batch_size(8)
+            .unwrap()
+            .with_beta1(0.5)
+            .unwrap()
+            .with_beta2(0.9)
+            .unwrap()
+            .with_epsilon(1e-6)
+            .unwrap()
+            .with_weight_decay(0.01)
+            .unwrap();
+        assert_eq!(adam.sampler.batch_size, 8);
+        assert_eq!(adam.beta1.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert_eq!(adam.beta2.to_ne_bytes(), 0.9f64.to_ne_bytes());
+        assert_eq!(adam.epsilon.to_ne_bytes(), 1e-6f64.to_ne_bytes());
+        assert_eq!(adam.weight_decay.to_ne_bytes(), 0.01f64.to_ne_bytes());
+
+        assert_error!(
+            Adam::<_, Vec<f64>, _, _>::new(0.1f64).with_batch_size(0),
+            ArgminError,
+            "Invalid parameter: \"`Adam`: batch size must be > 0.\""
+        );
+        for beta in [-0.1, 1.0] {
+            assert_error!(
+                Adam::<_, Vec<f64>, _, _>::new(0.1f64).with_beta1(beta),
+                ArgminError,
+                "Invalid parameter: \"`Adam`: beta1 must be in [0, 1).\""
+            );
+            assert_error!(
+                Adam::<_, Vec<f64>, _, _>::new(0.1f64).with_beta2(beta),
+                ArgminError,
+                "Invalid parameter: \"`Adam`: beta2 must be in [0, 1).\""
+            );
+        }
+        assert_error!(
+            Adam::<_, Vec<f64>, _, _>::new(0.1f64).with_epsilon(0.0),
+            ArgminError,
+            "Invalid parameter: \"`Adam`: epsilon must be > 0.\""
+        );
+        assert_error!(
+            Adam::<_, Vec<f64>, _, _>::new(0.1f64).with_weight_decay(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`Adam`: weight decay must be >= 0.\""
+        );
+    }
+
+    #[test]
+    fn test_param_not_initialized() {
+        let mut adam = Adam::new(0.1f64);
+        let res = adam.next_iter(&mut Problem::new(LeastSquares::new()), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`Adam` requires an initial parameter vector. Please ",
+                "provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_first_step() {
+        // Due to the bias correction, the first step has length `lr` in every coordinate
+        let mut problem = Problem::new(LeastSquares::new());
+        let mut adam = Adam::new(0.1f64).with_batch_size(100).unwrap();
+        let state = IterState::new().param(vec![0.0f64, 0.0]);
+        let (state, _) = adam.next_iter(&mut problem, state).unwrap();
+        let g = state.get_gradient().unwrap();
+        let p = state.get_param().unwrap();
+        for i in 0..2 {
+            assert_relative_eq!(p[i], -0.1 * g[i].signum(), epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_weight_decay() {
+        let mut problem = Problem::new(LeastSquares::new());
+        let mut adam = Adam::new(0.1f64)
+            .with_batch_size(100)
+            .unwrap()
+            .with_weight_decay(0.5)
+            .unwrap();
+        let state = IterState::new().param(vec![2.0f64, 2.0]);
+        let (state, _) = adam.next_iter(&mut problem, state).unwrap();
+        let g = state.get_gradient().unwrap();
+        let p = state.get_param().unwrap();
+        for i in 0..2 {
+            assert_relative_eq!(
+                p[i],
+                2.0 - 0.1 * (g[i].signum() + 0.5 * 2.0),
+                epsilon = 1e-6
+            );
+        }
+    }
+
+    #[test]
+    fn test_convergence() {
+        let solver = Adam::new(CosineAnnealing::new(0.05f64, 0.0, 3000).unwrap())
+            .with_rng_generator(Xoshiro256PlusPlus::seed_from_u64(42))
+            .with_batch_size(4)
+            .unwrap();
+        let res = Executor::new(LeastSquares::new(), solver)
+            .configure(|state| state.param(vec![0.0, 0.0]).max_iters(2999))
+            .ctrlc(false)
+            .run()
+            .unwrap();
+        let param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(param[0], 1.0, epsilon = 1e-4);
+        assert_relative_eq!(param[1], -2.0, epsilon = 1e-4);
+    }
+
+    #[cfg(feature = "serde1")]
+    #[test]
+    fn test_checkpoint_resume() {
+        let solver = || {
+            Adam::new(0.05f64)
+                .with_batch_size(3)
+                .unwrap()
+                .with_weight_decay(0.01)
+                .unwrap()
+        };
+        crate::solver::stochastic::tests::checkpoint_resume(
+            solver().with_rng_generator(Xoshiro256PlusPlus::seed_from_u64(1)),
+            solver().with_rng_generator(Xoshiro256PlusPlus::seed_from_u64(2)),
+        );
+    }
+}
@@ -0,0 +1,262 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Stochastic gradient methods
+//!
+//! Solvers for cost functions which are sums over many samples, as is common in statistics and
+//! machine learning. Instead of the full gradient, each iteration only evaluates the gradient over
+//! a randomly drawn minibatch of samples (see [`MinibatchGradient`](`crate::core::MinibatchGradient`)).
+//!
+//! * Stochastic gradient descent with optional (Nesterov) momentum ([`SGD`])
+//! * Adam, and AdamW with decoupled weight decay ([`Adam`])
+//! * RMSProp ([`RMSProp`])
+//!
+//! The learning rate of all solvers is given by a [`LearningRateSchedule`]. Besides a constant
+//! learning rate (`f32` or `f64`), [`StepDecay`], [`ExponentialDecay`], [`InverseTimeDecay`] and
+//! [`CosineAnnealing`] are available.
+//!
+//! Minibatches are drawn without replacement: in every epoch, the samples are shuffled and then
+//! split into consecutive batches. The solvers store their random number generator, moment
+//! estimates and sampling state internally, so with the `serde1` feature enabled, an interrupted
+//! run can be resumed from a checkpoint and continues exactly as the uninterrupted run would
+//! have.
+//!
+//! ## References
+//!
+//! Sebastian Ruder (2016). An overview of gradient descent optimization algorithms.
+//! arXiv:1609.04747
+
+/// Adam
+mod adam;
+/// RMSProp
+mod rmsprop;
+/// Learning rate schedules
+mod schedule;
+/// Stochastic gradient descent
+mod sgd;
+
+pub use self::adam::*;
+pub use self::rmsprop::*;
+pub use self::schedule::*;
+pub use self::sgd::*;
+
+use rand::prelude::*;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Draws minibatches without replacement, reshuffling the samples after every epoch.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+struct MinibatchSampler {
+    /// Number of samples per minibatch
+    batch_size: usize,
+    /// Permutation of the sample indices of the current epoch
+    permutation: Vec<usize>,
+    /// Position of the next minibatch in `permutation`
+    position: usize,
+    /// Number of completed epochs
+    epoch: u64,
+}
+
+impl MinibatchSampler {
+    fn new(batch_size: usize) -> Self {
+        MinibatchSampler {
+            batch_size,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the indices of the next minibatch out of `num_samples` samples. The last minibatch
+    /// of an epoch may be smaller than `batch_size`.
+    fn next_batch<R: Rng>(&mut self, num_samples: usize, rng: &mut R) -> Vec<usize> {
+        if self.permutation.len() != num_samples {
+            self.permutation = (0..num_samples).collect();
+            self.permutation.shuffle(rng);
+            self.position = 0;
+        } else if self.position >= num_samples {
+            self.permutation.shuffle(rng);
+            self.position = 0;
+            self.epoch += 1;
+        }
+        let end = (self.position + self.batch_size).min(num_samples);
+        let batch = self.permutation[self.position..end].to_vec();
+        self.position = end;
+        batch
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+    #[cfg(feature = "serde1")]
+    use crate::core::{
+        checkpointing::{Checkpoint, CheckpointingFrequency},
+        Executor, IterState, Solver, State,
+    };
+    use crate::core::{Error, MinibatchGradient};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    #[cfg(feature = "serde1")]
+    use std::{cell::RefCell, rc::Rc};
+
+    /// Linear regression `b_i = x_0 + x_1 t_i` on 20 noise-free samples. The solution is
+    /// `x = (1, -2)`.
+    pub(crate) struct LeastSquares {
+        t: Vec<f64>,
+        b: Vec<f64>,
+        /// Number of calls to `minibatch_gradient` after which an error is returned
+        fail_after: u64,
+        calls: AtomicU64,
+    }
+
+    impl LeastSquares {
+        pub(crate) fn new() -> Self {
+            let t: Vec<f64> = (0..20).map(|i| f64::from(i) / 10.0 - 1.0).collect();
+            let b = t.iter().map(|t| 1.0 - 2.0 * t).collect();
+            LeastSquares {
+                t,
+                b,
+                fail_after: u64::MAX,
+                calls: AtomicU64::new(0),
+            }
+        }
+    }
+
+    impl MinibatchGradient for LeastSquares {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn num_samples(&self) -> usize {
+            self.t.len()
+        }
+
+        fn minibatch_gradient(
+            &self,
+            p: &Self::Param,
+            indices: &[usize],
+        ) -> Result<Self::Gradient, Error> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) >= self.fail_after {
+                return Err(argmin_error!(PotentialBug, "interrupted"));
+            }
+            let mut grad = vec![0.0, 0.0];
+            for &i in indices {
+                let r = p[0] + p[1] * self.t[i] - self.b[i];
+                grad[0] += r / indices.len() as f64;
+                grad[1] += r * self.t[i] / indices.len() as f64;
+            }
+            Ok(grad)
+        }
+    }
+
+    /// Keeps the last checkpoint in memory
+    #[cfg(feature = "serde1")]
+    struct MemoryCheckpoint<S> {
+        #[allow(clippy::type_complexity)]
+        saved: Rc<RefCell<Option<(S, IterState<Vec<f64>, Vec<f64>, (), (), (), f64>)>>>,
+    }
+
+    #[cfg(feature = "serde1")]
+    impl<S: Clone> Checkpoint<S, IterState<Vec<f64>, Vec<f64>, (), (), (), f64>>
+        for MemoryCheckpoint<S>
+    {
+        fn save(
+            &self,
+            solver: &S,
+            state: &IterState<Vec<f64>, Vec<f64>, (), (), (), f64>,
+        ) -> Result<(), Error> {
+            *self.saved.borrow_mut() = Some((solver.clone(), state.clone()));
+            Ok(())
+        }
+
+        #[allow(clippy::type_complexity)]
+        fn load(
+            &self,
+        ) -> Result<Option<(S, IterState<Vec<f64>, Vec<f64>, (), (), (), f64>)>, Error> {
+            Ok(self.saved.borrow().clone())
+        }
+
+        fn frequency(&self) -> CheckpointingFrequency {
+            CheckpointingFrequency::Every(20)
+        }
+    }
+
+    /// Runs `solver` for 30 iterations, once uninterrupted and once interrupted by an error after
+    /// 25 iterations with a checkpoint saved after 20 iterations. The interrupted run is then
+    /// resumed from the checkpoint with `other`, which must lead to the same result as the
+    /// uninterrupted run.
+    #[cfg(feature = "serde1")]
+    pub(super) fn checkpoint_resume<S>(solver: S, other: S)
+    where
+        S: Clone
+            + Solver<LeastSquares, IterState<Vec<f64>, Vec<f64>, (), (), (), f64>>
+            + Serialize
+            + serde::de::DeserializeOwned
+            + 'static,
+    {
+        let saved = Rc::new(RefCell::new(None));
+        let checkpoint = || MemoryCheckpoint {
+            saved: saved.clone(),
+        };
+
+        let expected = Executor::new(LeastSquares::new(), solver.clone())
+            .configure(|state| state.param(vec![0.0f64, 0.0]).max_iters(30))
+            .ctrlc(false)
+            .run()
+            .unwrap();
+
+        let mut problem = LeastSquares::new();
+        problem.fail_after = 25;
+        let res = Executor::new(problem, solver)
+            .configure(|state| state.param(vec![0.0f64, 0.0]).max_iters(30))
+            .checkpointing(checkpoint())
+            .ctrlc(false)
+            .run();
+        assert!(res.is_err());
+        assert_eq!(saved.borrow().as_ref().unwrap().1.get_iter(), 20);
+
+        let res = Executor::new(LeastSquares::new(), other)
+            .configure(|state| state.param(vec![5.0f64, 5.0]).max_iters(30))
+            .checkpointing(checkpoint())
+            .ctrlc(false)
+            .run()
+            .unwrap();
+
+        assert_eq!(res.state.get_iter(), 30);
+        assert_eq!(
+            res.state.get_param().unwrap(),
+            expected.state.get_param().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_minibatch_sampler() {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(42);
+        let mut sampler = MinibatchSampler::new(4);
+        for epoch in 0..3 {
+            let mut seen = vec![];
+            for len in [4, 4, 2] {
+                let batch = sampler.next_batch(10, &mut rng);
+                assert_eq!(batch.len(), len);
+                assert_eq!(sampler.epoch, epoch);
+                seen.extend(batch);
+            }
+            seen.sort_unstable();
+            assert_eq!(seen, (0..10).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_minibatch_sampler_full_batch() {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(42);
+        let mut sampler = MinibatchSampler::new(20);
+        let mut batch = sampler.next_batch(10, &mut rng);
+        batch.sort_unstable();
+        assert_eq!(batch, (0..10).collect::<Vec<_>>());
+        sampler.next_batch(10, &mut rng);
+        assert_eq!(sampler.epoch, 1);
+    }
+}
@@ -0,0 +1,363 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::{LearningRateSchedule, MinibatchSampler};
+use crate::core::{ArgminFloat, Error, IterState, MinibatchGradient, Problem, Solver, State, KV};
+use argmin_math::{ArgminAdd, ArgminDiv, ArgminMul, ArgminScaledAdd, ArgminSqrt, ArgminZeroLike};
+use rand::prelude::*;
+use rand_xoshiro::Xoshiro256PlusPlus;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # RMSProp
+///
+/// In each iteration, the gradient `g_k` is evaluated on a minibatch of samples and an
+/// exponential moving average of the elementwise squared gradient is updated. Each parameter is
+/// then updated with a step length which is normalized by the root of this average:
+///
+/// ```text
+/// v_{k+1} = rho * v_k + (1 - rho) * g_k^2
+/// x_{k+1} = x_k - lr_k * g_k / (sqrt(v_{k+1}) + eps)
+/// ```
+///
+/// The learning rate `lr_k` is given by the [`LearningRateSchedule`]. The decay rate `rho` (set
+/// via [`with_rho`](`RMSProp::with_rho`)) defaults to `0.9` and `eps` (set via
+/// [`with_epsilon`](`RMSProp::with_epsilon`)) defaults to `1e-8`.
+///
+/// The batch size is set via [`with_batch_size`](`RMSProp::with_batch_size`) and defaults to `1`.
+/// The cost function is never evaluated. The solver runs until the maximum number of iterations
+/// is reached; the current learning rate and epoch are reported as KV metrics `learning_rate` and
+/// `epoch`.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`MinibatchGradient`].
+///
+/// ## References
+///
+/// Tijmen Tieleman and Geoffrey Hinton (2012). Lecture 6.5 - RMSProp: Divide the gradient by a
+/// running average of its recent magnitude. COURSERA: Neural Networks for Machine Learning.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct RMSProp<S, P, F, R> {
+    /// Learning rate schedule
+    schedule: S,
+    /// Decay rate of the squared gradient average
+    rho: F,
+    /// Regularization of the denominator
+    epsilon: F,
+    /// Minibatch sampler
+    sampler: MinibatchSampler,
+    /// Average of the squared gradient
+    v: Option<P>,
+    /// Random number generator
+    rng: R,
+}
+
+impl<S, P, F> RMSProp<S, P, F, Xoshiro256PlusPlus>
+where
+    S: LearningRateSchedule<F>,
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `RMSProp`
+    ///
+    /// Takes the learning rate schedule as input. A constant learning rate can be given as a float.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::stochastic::RMSProp;
+    /// let rmsprop: RMSProp<_, Vec<f64>, _, _> = RMSProp::new(0.001f64);
+    /// ```
+    pub fn new(schedule: S) -> Self {
+        RMSProp {
+            schedule,
+            rho: float!(0.9),
+            epsilon: float!(1e-8),
+            sampler: MinibatchSampler::new(1),
+            v: None,
+            rng: Xoshiro256PlusPlus::from_entropy(),
+        }
+    }
+}
+
+impl<S, P, F, R0> RMSProp<S, P, F, R0>
+where
+    S: LearningRateSchedule<F>,
+    F: ArgminFloat,
+    R0: Rng,
+{
+    /// Set the random number generator
+    ///
+    /// Defaults to `rand_xoshiro::Xoshiro256PlusPlus::from_entropy()`. In order to be able to
+    /// resume from a checkpoint, the random number generator needs to be serializable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::stochastic::RMSProp;
+    /// # use rand::SeedableRng;
+    /// let rmsprop: RMSProp<_, Vec<f64>, _, _> = RMSProp::new(0.001f64)
+    ///     .with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(1729));
+    /// ```
+    pub fn with_rng_generator<R1: Rng>(self, generator: R1) -> RMSProp<S, P, F, R1> {
+        RMSProp {
+            schedule: self.schedule,
+            rho: self.rho,
+            epsilon: self.epsilon,
+            sampler: self.sampler,
+            v: self.v,
+            rng: generator,
+        }
+    }
+
+    /// Set the number of samples per minibatch
+    ///
+    /// Must be larger than `0`. Defaults to `1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::stochastic::RMSProp;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let rmsprop: RMSProp<_, Vec<f64>, _, _> = RMSProp::new(0.001f64).with_batch_size(32)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_batch_size(mut self, batch_size: usize) -> Result<Self, Error> {
+        if batch_size == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`RMSProp`: batch size must be > 0."
+            ));
+        }
+        self.sampler = MinibatchSampler::new(batch_size);
+        Ok(self)
+    }
+
+    /// Set decay rate of the squared gradient average
+    ///
+    /// Must be in `[0, 1)`. Defaults to `0.9`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::stochastic::RMSProp;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let rmsprop: RMSProp<_, Vec<f64>, _, _> = RMSProp::new(0.001f64).with_rho(0.99)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_rho(mut self, rho: F) -> Result<Self, Error> {
+        if rho < float!(0.0) || rho >= float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`RMSProp`: rho must be in [0, 1)."
+            ));
+        }
+        self.rho = rho;
+        Ok(self)
+    }
+
+    /// Set regularization of the denominator
+    ///
+    /// Must be larger than `0`. Defaults to `1e-8`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::stochastic::RMSProp;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let rmsprop: RMSProp<_, Vec<f64>, _, _> = RMSProp::new(0.001f64).with_epsilon(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_epsilon(mut self, epsilon: F) -> Result<Self, Error> {
+        if epsilon <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`RMSProp`: epsilon must be > 0."
+            ));
+        }
+        self.epsilon = epsilon;
+        Ok(self)
+    }
+}
+
+impl<O, S, P, G, F, R> Solver<O, IterState<P, G, (), (), (), F>> for RMSProp<S, P, F, R>
+where
+    O: MinibatchGradient<Param = P, Gradient = G>,
+    S: LearningRateSchedule<F>,
+    P: Clone
+        + ArgminZeroLike
+        + ArgminMul<F, P>
+        + ArgminAdd<F, P>
+        + ArgminScaledAdd<G, F, P>
+        + ArgminScaledAdd<P, F, P>
+        + ArgminSqrt,
+    G: ArgminMul<G, G> + ArgminDiv<P, P>,
+    F: ArgminFloat,
+    R: Rng,
+{
+    const NAME: &'static str = "RMSProp";
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, (), (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`RMSProp` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+
+        let indices = self
+            .sampler
+            .next_batch(problem.num_samples(), &mut self.rng);
+        let grad = problem.minibatch_gradient(&param, &indices)?;
+        let learning_rate = self.schedule.learning_rate(state.get_iter());
+
+        let v = self
+            .v
+            .take()
+            .unwrap_or_else(|| param.zero_like())
+            .mul(&self.rho)
+            .scaled_add(&(float!(1.0) - self.rho), &grad.mul(&grad));
+        let step = grad.div(&v.clone().sqrt().add(&self.epsilon));
+        let param = param.scaled_add(&(-learning_rate), &step);
+        self.v = Some(v);
+
+        Ok((
+            state.param(param).gradient(grad),
+            Some(kv!(
+                "learning_rate" => learning_rate;
+                "epoch" => self.sampler.epoch;
+            )),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::stochastic::tests::LeastSquares;
+    use crate::solver::stochastic::StepDecay;
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(rmsprop, RMSProp<f64, Vec<f64>, f64, Xoshiro256PlusPlus>);
+
+    #[test]
+    fn test_new() {
+        let RMSProp {
+            schedule,
+            rho,
+            epsilon,
+            sampler,
+            v,
+            rng: _rng,
+        }: RMSProp<_, Vec<f64>, _, _> = RMSProp::new(0.1f64);
+        assert_eq!(schedule.to_ne_bytes(), 0.1f64.to_ne_bytes());
+        assert_eq!(rho.to_ne_bytes(), 0.9f64.to_ne_bytes());
+        assert_eq!(epsilon.to_ne_bytes(), 1e-8f64.to_ne_bytes());
+        assert_eq!(sampler.batch_size, 1);
+        assert!(v.is_none());
+    }
+
+    #[test]
+    fn test_builders() {
+        let rmsprop: RMSProp<_, Vec<f64>, _, _> = RMSProp::new(0.1f64)
+            .with_batch_size(8)
+            .unwrap()
+            .with_rho(0.5)
+            .unwrap()
+            .with_epsilon(1e-6)
+            .unwrap();
+        assert_eq!(rmsprop.sampler.batch_size, 8);
+        assert_eq!(rmsprop.rho.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert_eq!(rmsprop.epsilon.to_ne_bytes(), 1e-6f64.to_ne_bytes());
+
+        assert_error!(
+            RMSProp::<_, Vec<f64>, _, _>::new(0.1f64).with_batch_size(0),
+            ArgminError,
+            "Invalid parameter: \"`RMSProp`: batch size must be > 0.\""
+        );
+        for rho in [-0.1, 1.0] {
+            assert_error!(
+                RMSProp::<_, Vec<f64>, _, _>::new(0.1f64).with_rho(rho),
+                ArgminError,
+                "Invalid parameter: \"`RMSProp`: rho must be in [0, 1).\""
+            );
+        }
+        assert_error!(
+            RMSProp::<_, Vec<f64>, _, _>::new(0.1f64).with_epsilon(0.0),
+            ArgminError,
+            "Invalid parameter: \"`RMSProp`: epsilon must be > 0.\""
+        );
+    }
+
+    #[test]
+    fn test_param_not_initialized() {
+        let mut rmsprop = RMSProp::new(0.1f64);
+        let res = rmsprop.next_iter(&mut Problem::new(LeastSquares::new()), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`RMSProp` requires an initial parameter vector. Please ",
+                "provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_first_step() {
+        // v = 0.1 g^2, therefore the first step is lr * g / (sqrt(0.1) |g|) in every coordinate
+        let mut problem = Problem::new(LeastSquares::new());
+        let mut rmsprop = RMSProp::new(0.1f64).with_batch_size(100).unwrap();
+        let state = IterState::new().param(vec![0.0f64, 0.0]);
+        let (state, _) = rmsprop.next_iter(&mut problem, state).unwrap();
+        let g = state.get_gradient().unwrap();
+        let p = state.get_param().unwrap();
+        for i in 0..2 {
+            assert_relative_eq!(p[i], -0.1 * g[i].signum() / 0.1f64.sqrt(), epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_convergence() {
+        let solver = RMSProp::new(StepDecay::new(0.01f64, 0.5, 500).unwrap())
+            .with_rng_generator(Xoshiro256PlusPlus::seed_from_u64(42))
+            .with_batch_size(4)
+            .unwrap();
+        let res = Executor::new(LeastSquares::new(), solver)
+            .configure(|state| state.param(vec![0.0, 0.0]).max_iters(3000))
+            .ctrlc(false)
+            .run()
+            .unwrap();
+        let param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(param[0], 1.0, epsilon = 1e-4);
+        assert_relative_eq!(param[1], -2.0, epsilon = 1e-4);
+    }
+
+    #[cfg(feature = "serde1")]
+    #[test]
+    fn test_checkpoint_resume() {
+        let solver = || RMSProp::new(0.01f64).with_batch_size(3).unwrap();
+        crate::solver::stochastic::tests::checkpoint_resume(
+            solver().with_rng_generator(Xoshiro256PlusPlus::seed_from_u64(1)),
+            solver().with_rng_generator(Xoshiro256PlusPlus::seed_from_u64(2)),
+        );
+    }
+}
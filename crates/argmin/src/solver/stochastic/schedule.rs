@@ -0,0 +1,406 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, Error};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Learning rate (step length) of a stochastic solver as a function of the iteration number.
+///
+/// This trait is implemented for `f32` and `f64`, which represent a constant learning rate.
+///
+/// # Example
+///
+/// ```
+/// use argmin::solver::stochastic::LearningRateSchedule;
+///
+/// /// Learning rate which halves every 1000 iterations
+/// struct Halving {
+///     initial: f64,
+/// }
+///
+/// impl LearningRateSchedule<f64> for Halving {
+///     fn learning_rate(&self, iter: u64) -> f64 {
+///         self.initial * 0.5f64.powi((iter / 1000) as i32)
+///     }
+/// }
+/// ```
+pub trait LearningRateSchedule<F> {
+    /// Returns the learning rate in iteration `iter`
+    fn learning_rate(&self, iter: u64) -> F;
+}
+
+macro_rules! constant_schedule {
+    ($t:ty) => {
+        impl LearningRateSchedule<$t> for $t {
+            fn learning_rate(&self, _iter: u64) -> $t {
+                *self
+            }
+        }
+    };
+}
+
+constant_schedule!(f32);
+constant_schedule!(f64);
+
+/// Multiplies the learning rate by `factor` every `step_size` iterations:
+///
+/// ```text
+/// lr_k = initial * factor^floor(k / step_size)
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct StepDecay<F> {
+    /// Initial learning rate
+    initial: F,
+    /// Decay factor
+    factor: F,
+    /// Number of iterations between two decays
+    step_size: u64,
+}
+
+impl<F: ArgminFloat> StepDecay<F> {
+    /// Construct a new instance of `StepDecay`
+    ///
+    /// `initial` must be larger than `0`, `factor` must be in `(0, 1]` and `step_size` must be
+    /// larger than `0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::stochastic::StepDecay;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let schedule = StepDecay::new(0.1f64, 0.5, 1000)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(initial: F, factor: F, step_size: u64) -> Result<Self, Error> {
+        if initial <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`StepDecay`: initial learning rate must be > 0."
+            ));
+        }
+        if factor <= float!(0.0) || factor > float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`StepDecay`: factor must be in (0, 1]."
+            ));
+        }
+        if step_size == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`StepDecay`: step size must be > 0."
+            ));
+        }
+        Ok(StepDecay {
+            initial,
+            factor,
+            step_size,
+        })
+    }
+}
+
+impl<F: ArgminFloat> LearningRateSchedule<F> for StepDecay<F> {
+    fn learning_rate(&self, iter: u64) -> F {
+        self.initial
+            * self
+                .factor
+                .powf(F::from_u64(iter / self.step_size).unwrap())
+    }
+}
+
+/// Multiplies the learning rate by `decay_rate` in every iteration:
+///
+/// ```text
+/// lr_k = initial * decay_rate^k
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct ExponentialDecay<F> {
+    /// Initial learning rate
+    initial: F,
+    /// Decay rate
+    decay_rate: F,
+}
+
+impl<F: ArgminFloat> ExponentialDecay<F> {
+    /// Construct a new instance of `ExponentialDecay`
+    ///
+    /// `initial` must be larger than `0` and `decay_rate` must be in `(0, 1]`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::stochastic::ExponentialDecay;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let schedule = ExponentialDecay::new(0.1f64, 0.999)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(initial: F, decay_rate: F) -> Result<Self, Error> {
+        if initial <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ExponentialDecay`: initial learning rate must be > 0."
+            ));
+        }
+        if decay_rate <= float!(0.0) || decay_rate > float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ExponentialDecay`: decay rate must be in (0, 1]."
+            ));
+        }
+        Ok(ExponentialDecay {
+            initial,
+            decay_rate,
+        })
+    }
+}
+
+impl<F: ArgminFloat> LearningRateSchedule<F> for ExponentialDecay<F> {
+    fn learning_rate(&self, iter: u64) -> F {
+        self.initial * self.decay_rate.powf(F::from_u64(iter).unwrap())
+    }
+}
+
+/// Decays the learning rate inversely proportional to the iteration number:
+///
+/// ```text
+/// lr_k = initial / (1 + decay * k)
+/// ```
+///
+/// For `decay > 0` this satisfies the Robbins-Monro conditions which guarantee convergence of
+/// plain stochastic gradient descent on convex problems.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct InverseTimeDecay<F> {
+    /// Initial learning rate
+    initial: F,
+    /// Decay
+    decay: F,
+}
+
+impl<F: ArgminFloat> InverseTimeDecay<F> {
+    /// Construct a new instance of `InverseTimeDecay`
+    ///
+    /// `initial` must be larger than `0` and `decay` must be non-negative.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::stochastic::InverseTimeDecay;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let schedule = InverseTimeDecay::new(0.1f64, 0.01)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(initial: F, decay: F) -> Result<Self, Error> {
+        if initial <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`InverseTimeDecay`: initial learning rate must be > 0."
+            ));
+        }
+        if decay < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`InverseTimeDecay`: decay must be >= 0."
+            ));
+        }
+        Ok(InverseTimeDecay { initial, decay })
+    }
+}
+
+impl<F: ArgminFloat> LearningRateSchedule<F> for InverseTimeDecay<F> {
+    fn learning_rate(&self, iter: u64) -> F {
+        self.initial / (float!(1.0) + self.decay * F::from_u64(iter).unwrap())
+    }
+}
+
+/// Anneals the learning rate from `initial` to `minimum` along a half cosine wave over `period`
+/// iterations and restarts afterwards (warm restarts):
+///
+/// ```text
+/// lr_k = minimum + 1/2 (initial - minimum) (1 + cos(pi * (k mod period) / period))
+/// ```
+///
+/// ## Reference
+///
+/// Ilya Loshchilov and Frank Hutter (2017). SGDR: Stochastic Gradient Descent with Warm Restarts.
+/// International Conference on Learning Representations.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct CosineAnnealing<F> {
+    /// Initial (maximum) learning rate
+    initial: F,
+    /// Minimum learning rate
+    minimum: F,
+    /// Number of iterations between two restarts
+    period: u64,
+}
+
+impl<F: ArgminFloat> CosineAnnealing<F> {
+    /// Construct a new instance of `CosineAnnealing`
+    ///
+    /// `initial` must be larger than `0`, `minimum` must be in `[0, initial]` and `period` must be
+    /// larger than `0`. To anneal without restarts, set `period` to the maximum number of
+    /// iterations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::stochastic::CosineAnnealing;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let schedule = CosineAnnealing::new(0.1f64, 0.001, 1000)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(initial: F, minimum: F, period: u64) -> Result<Self, Error> {
+        if initial <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CosineAnnealing`: initial learning rate must be > 0."
+            ));
+        }
+        if minimum < float!(0.0) || minimum > initial {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CosineAnnealing`: minimum learning rate must be in [0, initial]."
+            ));
+        }
+        if period == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`CosineAnnealing`: period must be > 0."
+            ));
+        }
+        Ok(CosineAnnealing {
+            initial,
+            minimum,
+            period,
+        })
+    }
+}
+
+impl<F: ArgminFloat> LearningRateSchedule<F> for CosineAnnealing<F> {
+    fn learning_rate(&self, iter: u64) -> F {
+        let phase = F::from_u64(iter % self.period).unwrap() / F::from_u64(self.period).unwrap();
+        self.minimum
+            + float!(0.5) * (self.initial - self.minimum) * (float!(1.0) + (F::PI() * phase).cos())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_constant() {
+        assert_relative_eq!(0.1f64.learning_rate(0), 0.1);
+        assert_relative_eq!(0.1f64.learning_rate(1000), 0.1);
+        assert_relative_eq!(0.1f32.learning_rate(1000), 0.1);
+    }
+
+    #[test]
+    fn test_step_decay() {
+        let schedule = StepDecay::new(1.0f64, 0.5, 10).unwrap();
+        assert_relative_eq!(schedule.learning_rate(0), 1.0);
+        assert_relative_eq!(schedule.learning_rate(9), 1.0);
+        assert_relative_eq!(schedule.learning_rate(10), 0.5);
+        assert_relative_eq!(schedule.learning_rate(35), 0.125);
+
+        for (initial, factor, step_size, msg) in [
+            (0.0, 0.5, 10, "initial learning rate must be > 0."),
+            (1.0, 0.0, 10, "factor must be in (0, 1]."),
+            (1.0, 1.5, 10, "factor must be in (0, 1]."),
+            (1.0, 0.5, 0, "step size must be > 0."),
+        ] {
+            assert_error!(
+                StepDecay::new(initial, factor, step_size),
+                ArgminError,
+                format!("Invalid parameter: \"`StepDecay`: {msg}\"")
+            );
+        }
+    }
+
+    #[test]
+    fn test_exponential_decay() {
+        let schedule = ExponentialDecay::new(2.0f64, 0.5).unwrap();
+        assert_relative_eq!(schedule.learning_rate(0), 2.0);
+        assert_relative_eq!(schedule.learning_rate(3), 0.25);
+
+        for (initial, decay_rate, msg) in [
+            (-1.0, 0.5, "initial learning rate must be > 0."),
+            (1.0, 0.0, "decay rate must be in (0, 1]."),
+            (1.0, 1.1, "decay rate must be in (0, 1]."),
+        ] {
+            assert_error!(
+                ExponentialDecay::new(initial, decay_rate),
+                ArgminError,
+                format!("Invalid parameter: \"`ExponentialDecay`: {msg}\"")
+            );
+        }
+    }
+
+    #[test]
+    fn test_inverse_time_decay() {
+        let schedule = InverseTimeDecay::new(1.0f64, 0.5).unwrap();
+        assert_relative_eq!(schedule.learning_rate(0), 1.0);
+        assert_relative_eq!(schedule.learning_rate(2), 0.5);
+        assert_relative_eq!(schedule.learning_rate(6), 0.25);
+
+        for (initial, decay, msg) in [
+            (0.0, 0.5, "initial learning rate must be > 0."),
+            (1.0, -0.5, "decay must be >= 0."),
+        ] {
+            assert_error!(
+                InverseTimeDecay::new(initial, decay),
+                ArgminError,
+                format!("Invalid parameter: \"`InverseTimeDecay`: {msg}\"")
+            );
+        }
+    }
+
+    #[test]
+    fn test_cosine_annealing() {
+        let schedule = CosineAnnealing::new(1.0f64, 0.2, 10).unwrap();
+        assert_relative_eq!(schedule.learning_rate(0), 1.0);
+        assert_relative_eq!(schedule.learning_rate(5), 0.6);
+        assert_relative_eq!(schedule.learning_rate(10), 1.0);
+        assert_relative_eq!(schedule.learning_rate(15), 0.6);
+
+        for (initial, minimum, period, msg) in [
+            (0.0, 0.0, 10, "initial learning rate must be > 0."),
+            (
+                1.0,
+                -0.1,
+                10,
+                "minimum learning rate must be in [0, initial].",
+            ),
+            (
+                1.0,
+                1.1,
+                10,
+                "minimum learning rate must be in [0, initial].",
+            ),
+            (1.0, 0.1, 0, "period must be > 0."),
+        ] {
+            assert_error!(
+                CosineAnnealing::new(initial, minimum, period),
+                ArgminError,
+                format!("Invalid parameter: \"`CosineAnnealing`: {msg}\"")
+            );
+        }
+    }
+}
@@ -0,0 +1,396 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::{LearningRateSchedule, MinibatchSampler};
+use crate::core::{ArgminFloat, Error, IterState, MinibatchGradient, Problem, Solver, State, KV};
+use argmin_math::{ArgminAdd, ArgminMul, ArgminScaledAdd, ArgminZeroLike};
+use rand::prelude::*;
+use rand_xoshiro::Xoshiro256PlusPlus;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Stochastic gradient descent
+///
+/// In each iteration, the gradient `g_k` is evaluated on a minibatch of samples and the parameter
+/// vector is updated with the learning rate `lr_k` given by the [`LearningRateSchedule`]:
+///
+/// ```text
+/// v_{k+1} = mu * v_k + g_k
+/// x_{k+1} = x_k - lr_k * v_{k+1}
+/// ```
+///
+/// The momentum `mu` (set via [`with_momentum`](`SGD::with_momentum`)) defaults to `0`, which
+/// is plain stochastic gradient descent. With Nesterov momentum enabled (via
+/// [`with_nesterov`](`SGD::with_nesterov`)), the update direction is `g_k + mu * v_{k+1}` instead
+/// of `v_{k+1}`.
+///
+/// The batch size is set via [`with_batch_size`](`SGD::with_batch_size`) and defaults to `1`.
+/// The cost function is never evaluated. The solver runs until the maximum number of iterations
+/// is reached; the current learning rate and epoch are reported as KV metrics `learning_rate` and
+/// `epoch`.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`MinibatchGradient`].
+///
+/// ## References
+///
+/// Ilya Sutskever, James Martens, George Dahl and Geoffrey Hinton (2013). On the importance of
+/// initialization and momentum in deep learning. International Conference on Machine Learning.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct SGD<S, P, F, R> {
+    /// Learning rate schedule
+    schedule: S,
+    /// Momentum
+    momentum: F,
+    /// Nesterov momentum
+    nesterov: bool,
+    /// Minibatch sampler
+    sampler: MinibatchSampler,
+    /// Velocity
+    velocity: Option<P>,
+    /// Random number generator
+    rng: R,
+}
+
+impl<S, P, F> SGD<S, P, F, Xoshiro256PlusPlus>
+where
+    S: LearningRateSchedule<F>,
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `SGD`
+    ///
+    /// Takes the learning rate schedule as input. A constant learning rate can be given as a float.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::stochastic::SGD;
+    /// let sgd: SGD<_, Vec<f64>, _, _> = SGD::new(0.01f64);
+    /// ```
+    pub fn new(schedule: S) -> Self {
+        SGD {
+            schedule,
+            momentum: float!(0.0),
+            nesterov: false,
+            sampler: MinibatchSampler::new(1),
+            velocity: None,
+            rng: Xoshiro256PlusPlus::from_entropy(),
+        }
+    }
+}
+
+impl<S, P, F, R0> SGD<S, P, F, R0>
+where
+    S: LearningRateSchedule<F>,
+    F: ArgminFloat,
+    R0: Rng,
+{
+    /// Set the random number generator
+    ///
+    /// Defaults to `rand_xoshiro::Xoshiro256PlusPlus::from_entropy()`. In order to be able to
+    /// resume from a checkpoint, the random number generator needs to be serializable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::stochastic::SGD;
+    /// # use rand::SeedableRng;
+    /// let sgd: SGD<_, Vec<f64>, _, _> = SGD::new(0.01f64).with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(1729));
+    /// ```
+    pub fn with_rng_generator<R1: Rng>(self, generator: R1) -> SGD<S, P, F, R1> {
+        SGD {
+            schedule: self.schedule,
+            momentum: self.momentum,
+            nesterov: self.nesterov,
+            sampler: self.sampler,
+            velocity: self.velocity,
+            rng: generator,
+        }
+    }
+
+    /// Set the number of samples per minibatch
+    ///
+    /// Must be larger than `0`. Defaults to `1`. A batch size larger than or equal to the number
+    /// of samples results in full gradient descent.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::stochastic::SGD;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let sgd: SGD<_, Vec<f64>, _, _> = SGD::new(0.01f64).with_batch_size(32)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_batch_size(mut self, batch_size: usize) -> Result<Self, Error> {
+        if batch_size == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SGD`: batch size must be > 0."
+            ));
+        }
+        self.sampler = MinibatchSampler::new(batch_size);
+        Ok(self)
+    }
+
+    /// Set momentum
+    ///
+    /// Must be in `[0, 1)`. Defaults to `0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::stochastic::SGD;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let sgd: SGD<_, Vec<f64>, _, _> = SGD::new(0.01f64).with_momentum(0.9)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_momentum(mut self, momentum: F) -> Result<Self, Error> {
+        if momentum < float!(0.0) || momentum >= float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SGD`: momentum must be in [0, 1)."
+            ));
+        }
+        self.momentum = momentum;
+        Ok(self)
+    }
+
+    /// Enable or disable Nesterov momentum
+    ///
+    /// Defaults to `false`. Only has an effect if the momentum is larger than `0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::stochastic::SGD;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let sgd: SGD<_, Vec<f64>, _, _> = SGD::new(0.01f64).with_momentum(0.9)?.with_nesterov(true);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_nesterov(mut self, nesterov: bool) -> Self {
+        self.nesterov = nesterov;
+        self
+    }
+}
+
+impl<O, S, P, G, F, R> Solver<O, IterState<P, G, (), (), (), F>> for SGD<S, P, F, R>
+where
+    O: MinibatchGradient<Param = P, Gradient = G>,
+    S: LearningRateSchedule<F>,
+    P: Clone + ArgminZeroLike + ArgminMul<F, P> + ArgminAdd<G, P> + ArgminScaledAdd<P, F, P>,
+    F: ArgminFloat,
+    R: Rng,
+{
+    const NAME: &'static str = "Stochastic Gradient Descent";
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, (), (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`SGD` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+
+        let indices = self
+            .sampler
+            .next_batch(problem.num_samples(), &mut self.rng);
+        let grad = problem.minibatch_gradient(&param, &indices)?;
+        let learning_rate = self.schedule.learning_rate(state.get_iter());
+
+        let velocity = self
+            .velocity
+            .take()
+            .unwrap_or_else(|| param.zero_like())
+            .mul(&self.momentum)
+            .add(&grad);
+        let param = if self.nesterov {
+            param.scaled_add(&(-learning_rate), &velocity.mul(&self.momentum).add(&grad))
+        } else {
+            param.scaled_add(&(-learning_rate), &velocity)
+        };
+        self.velocity = Some(velocity);
+
+        Ok((
+            state.param(param).gradient(grad),
+            Some(kv!(
+                "learning_rate" => learning_rate;
+                "epoch" => self.sampler.epoch;
+            )),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use crate::solver::stochastic::tests::LeastSquares;
+    use crate::solver::stochastic::InverseTimeDecay;
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(sgd, SGD<f64, Vec<f64>, f64, Xoshiro256PlusPlus>);
+
+    #[test]
+    fn test_new() {
+        let SGD {
+            schedule,
+            momentum,
+            nesterov,
+            sampler,
+            velocity,
+            rng: _rng,
+        }: SGD<_, Vec<f64>, _, _> = SGD::new(0.1f64);
+        assert_eq!(schedule.to_ne_bytes(), 0.1f64.to_ne_bytes());
+        assert_eq!(momentum.to_ne_bytes(), 0.0f64.to_ne_bytes());
+        assert!(!nesterov);
+        assert_eq!(sampler.batch_size, 1);
+        assert!(velocity.is_none());
+    }
+
+    #[test]
+    fn test_builders() {
+        let sgd: SGD<_, Vec<f64>, _, _> = SGD::new(0.1f64)
+            .with_batch_size(8)
+            .unwrap()
+            .with_momentum(0.9)
+            .unwrap()
+            .with_nesterov(true);
+        assert_eq!(sgd.sampler.batch_size, 8);
+        assert_eq!(sgd.momentum.to_ne_bytes(), 0.9f64.to_ne_bytes());
+        assert!(sgd.nesterov);
+
+        assert_error!(
+            SGD::<_, Vec<f64>, _, _>::new(0.1f64).with_batch_size(0),
+            ArgminError,
+            "Invalid parameter: \"`SGD`: batch size must be > 0.\""
+        );
+        for momentum in [-0.1, 1.0] {
+            assert_error!(
+                SGD::<_, Vec<f64>, _, _>::new(0.1f64).with_momentum(momentum),
+                ArgminError,
+                "Invalid parameter: \"`SGD`: momentum must be in [0, 1).\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_param_not_initialized() {
+        let mut sgd = SGD::new(0.1f64);
+        let res = sgd.next_iter(&mut Problem::new(LeastSquares::new()), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`SGD` requires an initial parameter vector. Please ",
+                "provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_momentum_update() {
+        // Full batch on a problem with constant gradient (1, 1) in the first iterations
+        let mut problem = Problem::new(LeastSquares::new());
+        let mut sgd = SGD::new(0.1f64)
+            .with_batch_size(100)
+            .unwrap()
+            .with_momentum(0.5)
+            .unwrap();
+        let state = IterState::new().param(vec![0.0f64, 0.0]);
+        let (state, kv) = sgd.next_iter(&mut problem, state).unwrap();
+        let g0 = state.get_gradient().unwrap().clone();
+        let p1 = state.get_param().unwrap().clone();
+        assert_relative_eq!(p1[0], -0.1 * g0[0]);
+        assert_relative_eq!(p1[1], -0.1 * g0[1]);
+        assert_eq!(
+            kv.unwrap().get("learning_rate").unwrap().get_float(),
+            Some(0.1)
+        );
+
+        let (state, _) = sgd.next_iter(&mut problem, state).unwrap();
+        let g1 = state.get_gradient().unwrap();
+        let p2 = state.get_param().unwrap();
+        for i in 0..2 {
+            assert_relative_eq!(p2[i], p1[i] - 0.1 * (0.5 * g0[i] + g1[i]));
+        }
+        assert_eq!(problem.counts["minibatch_gradient_count"], 2);
+    }
+
+    #[test]
+    fn test_nesterov_update() {
+        let mut problem = Problem::new(LeastSquares::new());
+        let mut sgd = SGD::new(0.1f64)
+            .with_batch_size(100)
+            .unwrap()
+            .with_momentum(0.5)
+            .unwrap()
+            .with_nesterov(true);
+        let state = IterState::new().param(vec![0.0f64, 0.0]);
+        let (state, _) = sgd.next_iter(&mut problem, state).unwrap();
+        let g0 = state.get_gradient().unwrap();
+        let p1 = state.get_param().unwrap();
+        for i in 0..2 {
+            assert_relative_eq!(p1[i], -0.1 * 1.5 * g0[i]);
+        }
+    }
+
+    #[test]
+    fn test_convergence() {
+        for (momentum, nesterov) in [(0.0, false), (0.9, false), (0.9, true)] {
+            let solver = SGD::new(InverseTimeDecay::new(0.1f64, 0.001).unwrap())
+                .with_rng_generator(Xoshiro256PlusPlus::seed_from_u64(42))
+                .with_batch_size(4)
+                .unwrap()
+                .with_momentum(momentum)
+                .unwrap()
+                .with_nesterov(nesterov);
+            let res = Executor::new(LeastSquares::new(), solver)
+                .configure(|state| state.param(vec![0.0, 0.0]).max_iters(2000))
+                .ctrlc(false)
+                .run()
+                .unwrap();
+            let param = res.state.get_best_param().unwrap();
+            assert_relative_eq!(param[0], 1.0, epsilon = 1e-4);
+            assert_relative_eq!(param[1], -2.0, epsilon = 1e-4);
+            assert_eq!(
+                res.problem.counts["minibatch_gradient_count"],
+                res.state.get_iter()
+            );
+        }
+    }
+
+    #[cfg(feature = "serde1")]
+    #[test]
+    fn test_checkpoint_resume() {
+        let solver = || {
+            SGD::new(0.05f64)
+                .with_batch_size(3)
+                .unwrap()
+                .with_momentum(0.9)
+                .unwrap()
+        };
+        crate::solver::stochastic::tests::checkpoint_resume(
+            solver().with_rng_generator(Xoshiro256PlusPlus::seed_from_u64(1)),
+            solver().with_rng_generator(Xoshiro256PlusPlus::seed_from_u64(2)),
+        );
+    }
+}
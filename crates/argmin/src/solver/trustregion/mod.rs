@@ -22,6 +22,8 @@
 mod cauchypoint;
 /// Dogleg method
 mod dogleg;
+/// Moré-Sorensen method
+mod moresorensen;
 /// Steihaug method
 mod steihaug;
 /// Trust region solver
@@ -29,6 +31,7 @@ mod trustregion_method;
 
 pub use self::cauchypoint::*;
 pub use self::dogleg::*;
+pub use self::moresorensen::*;
 pub use self::steihaug::*;
 pub use self::trustregion_method::*;
 
@@ -0,0 +1,524 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminFloat, Error, Gradient, Hessian, IterState, Problem, Solver, State, TerminationReason,
+    TerminationStatus, TrustRegionRadius, KV,
+};
+use crate::solver::dense::{chol_solve, cholesky_shifted, dot, forward_substitution, norm};
+use argmin_math::{ArgminGet, ArgminSet, ArgminSize};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Moré-Sorensen method
+///
+/// Computes a nearly exact solution of the trust region subproblem
+///
+/// ```text
+/// min_p  g^T p + 1/2 p^T H p   subject to ||p|| <= radius
+/// ```
+///
+/// by searching for the Lagrange multiplier `lambda >= 0` such that `H + lambda I` is positive
+/// semidefinite and `(H + lambda I) p = -g` with `lambda (radius - ||p||) = 0`. In each iteration,
+/// a Cholesky factorization of the shifted Hessian `H + lambda I` is computed and `lambda` is
+/// updated with a safeguarded Newton step on the secular equation `1/radius - 1/||p(lambda)|| = 0`.
+///
+/// If `g` is (nearly) orthogonal to the eigenspace of the smallest eigenvalue of an indefinite
+/// Hessian (the *hard case*), `||p(lambda)||` stays below the radius for all admissible `lambda`.
+/// In this case, an approximate eigenvector `z` of `H + lambda I` for its smallest eigenvalue is
+/// computed by inverse iteration and the step is extended by `tau z` to the boundary of the trust
+/// region.
+///
+/// The search stops once `| ||p|| - radius | <= tol * radius` (set via
+/// [`with_tolerance`](`MoreSorensen::with_tolerance`)) or after the maximum number of
+/// factorizations (set via [`with_max_iters`](`MoreSorensen::with_max_iters`)).
+///
+/// Since the Hessian is factorized as a dense matrix, this method is suited for small to medium
+/// sized problems.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`Gradient`] and [`Hessian`].
+///
+/// ## References
+///
+/// Jorge J. Moré and Danny C. Sorensen (1983). Computing a Trust Region Step.
+/// SIAM Journal on Scientific and Statistical Computing 4(3), pp. 553-572.
+///
+/// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+/// Springer. ISBN 0-387-30303-0.
+#[derive(Clone, Debug, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct MoreSorensen<F> {
+    /// Radius
+    radius: F,
+    /// Relative tolerance on the distance of the step to the trust region boundary
+    tol: F,
+    /// Maximum number of Cholesky factorizations
+    max_iters: u64,
+}
+
+impl<F> MoreSorensen<F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`MoreSorensen`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::trustregion::MoreSorensen;
+    /// let ms: MoreSorensen<f64> = MoreSorensen::new();
+    /// ```
+    pub fn new() -> Self {
+        MoreSorensen {
+            radius: F::nan(),
+            tol: float!(1e-3),
+            max_iters: 100,
+        }
+    }
+
+    /// Set tolerance
+    ///
+    /// The search for the Lagrange multiplier stops once the length of the step is within
+    /// `tol * radius` of the radius. Must be in `(0, 1)` and defaults to `1e-3`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::trustregion::MoreSorensen;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let ms: MoreSorensen<f64> = MoreSorensen::new().with_tolerance(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= float!(0.0) || tol >= float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`MoreSorensen`: tolerance must be in (0, 1)."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Set maximum number of Cholesky factorizations
+    ///
+    /// If the tolerance is not met within this number of factorizations, the last step is scaled
+    /// back into the trust region. Defaults to `100`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::trustregion::MoreSorensen;
+    /// let ms: MoreSorensen<f64> = MoreSorensen::new().with_max_iters(20);
+    /// ```
+    #[must_use]
+    pub fn with_max_iters(mut self, iters: u64) -> Self {
+        self.max_iters = iters;
+        self
+    }
+
+    /// Solves the trust region subproblem for the dense Hessian `h` and gradient `g`
+    fn solve(&self, h: &[Vec<F>], g: &[F]) -> Vec<F> {
+        let n = g.len();
+        let radius = self.radius;
+        let g_norm = norm(g);
+
+        // Initial safeguards (Moré and Sorensen, 1983, section 3)
+        let h_norm = (0..n)
+            .map(|j| (0..n).fold(float!(0.0), |acc, i| acc + h[i][j].abs()))
+            .fold(float!(0.0), F::max);
+        let min_diag = (0..n).fold(F::infinity(), |acc, i| acc.min(h[i][i]));
+        let mut lambda_s = float!(0.0).max(-min_diag);
+        let mut lambda_l = lambda_s.max(g_norm / radius - h_norm);
+        let mut lambda_u = float!(0.0).max(g_norm / radius + h_norm);
+        let mut lambda = lambda_l;
+
+        let mut step = vec![float!(0.0); n];
+
+        for _ in 0..self.max_iters {
+            let l = match cholesky_shifted(h, lambda) {
+                Some(l) => l,
+                None => {
+                    // H + lambda I is not positive definite
+                    lambda_s = lambda_s.max(lambda);
+                    lambda_l = lambda_l.max(lambda_s);
+                    lambda = safeguard(lambda_l, lambda_u);
+                    continue;
+                }
+            };
+
+            let p = chol_solve(&l, &g.iter().map(|&gi| -gi).collect::<Vec<_>>());
+            let p_norm = norm(&p);
+            step = p.clone();
+
+            if lambda <= float!(0.0) && p_norm <= radius {
+                // Unconstrained minimizer lies within the trust region
+                return p;
+            }
+            if (p_norm - radius).abs() <= self.tol * radius {
+                return p;
+            }
+
+            if p_norm < radius {
+                lambda_u = lambda_u.min(lambda);
+
+                // Potential hard case: move to the boundary along an approximate eigenvector
+                // for the smallest eigenvalue of H + lambda I
+                let (z, rq) = min_eigenvector(&l);
+                lambda_s = lambda_s.max(lambda - rq);
+                lambda_l = lambda_l.max(lambda_s);
+
+                let pz = dot(&p, &z);
+                let disc = (pz * pz + radius * radius - p_norm * p_norm).sqrt();
+                // root of ||p + tau z|| = radius with the smallest magnitude
+                let tau = if pz >= float!(0.0) {
+                    disc - pz
+                } else {
+                    -disc - pz
+                };
+                let lp = lt_mul(&l, &p);
+                let bound = self.tol
+                    * (float!(2.0) - self.tol)
+                    * (dot(&lp, &lp) + lambda * radius * radius);
+                if tau * tau * rq <= bound {
+                    return p.iter().zip(z.iter()).map(|(&p, &z)| p + tau * z).collect();
+                }
+            } else {
+                lambda_l = lambda_l.max(lambda);
+            }
+
+            // Newton step on the secular equation
+            let q = forward_substitution(&l, &p);
+            let q_norm = norm(&q);
+            let lambda_new = lambda + (p_norm / q_norm).powi(2) * (p_norm - radius) / radius;
+
+            lambda = if lambda_new > lambda_l && lambda_new.is_finite() {
+                lambda_new
+            } else {
+                safeguard(lambda_l, lambda_u)
+            };
+        }
+
+        // Tolerance not met: scale the last step back into the trust region
+        let step_norm = norm(&step);
+        if step_norm > radius {
+            step.iter().map(|&s| s * radius / step_norm).collect()
+        } else {
+            step
+        }
+    }
+}
+
+impl<F> Default for MoreSorensen<F>
+where
+    F: ArgminFloat,
+{
+    fn default() -> MoreSorensen<F> {
+        MoreSorensen::new()
+    }
+}
+
+impl<O, F, P, H> Solver<O, IterState<P, P, (), H, (), F>> for MoreSorensen<F>
+where
+    O: Gradient<Param = P, Gradient = P> + Hessian<Param = P, Hessian = H>,
+    P: Clone + ArgminGet<usize, F> + ArgminSet<usize, F> + ArgminSize<usize>,
+    H: ArgminGet<(usize, usize), F>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Moré-Sorensen";
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, P, (), H, (), F>,
+    ) -> Result<(IterState<P, P, (), H, (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`MoreSorensen` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+
+        let g = state
+            .take_gradient()
+            .map(Result::Ok)
+            .unwrap_or_else(|| problem.gradient(&param))?;
+
+        let h = state
+            .take_hessian()
+            .map(Result::Ok)
+            .unwrap_or_else(|| problem.hessian(&param))?;
+
+        let n = g.shape();
+        let g_vec: Vec<F> = (0..n).map(|i| g.get(i)).collect();
+        let h_vec: Vec<Vec<F>> = (0..n)
+            .map(|i| (0..n).map(|j| h.get((i, j))).collect())
+            .collect();
+
+        let step = self.solve(&h_vec, &g_vec);
+
+        let mut pstar = g.clone();
+        for (i, &s) in step.iter().enumerate() {
+            pstar.set(i, s);
+        }
+
+        Ok((state.param(pstar).gradient(g).hessian(h), None))
+    }
+
+    fn terminate(&mut self, state: &IterState<P, P, (), H, (), F>) -> TerminationStatus {
+        if state.get_iter() >= 1 {
+            TerminationStatus::Terminated(TerminationReason::MaxItersReached)
+        } else {
+            TerminationStatus::NotTerminated
+        }
+    }
+}
+
+impl<F: ArgminFloat> TrustRegionRadius<F> for MoreSorensen<F> {
+    /// Set current radius.
+    ///
+    /// Needed by [`TrustRegion`](`crate::solver::trustregion::TrustRegion`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin::solver::trustregion::{MoreSorensen, TrustRegionRadius};
+    /// let mut ms: MoreSorensen<f64> = MoreSorensen::new();
+    /// ms.set_radius(0.8);
+    /// ```
+    fn set_radius(&mut self, radius: F) {
+        self.radius = radius;
+    }
+}
+
+/// Chooses a new `lambda` within the interval of uncertainty `[lambda_l, lambda_u]`
+fn safeguard<F: ArgminFloat>(lambda_l: F, lambda_u: F) -> F {
+    (lambda_l * lambda_u)
+        .sqrt()
+        .max(lambda_l + float!(0.01) * (lambda_u - lambda_l))
+}
+
+/// Computes `L^T x`
+fn lt_mul<F: ArgminFloat>(l: &[Vec<F>], x: &[F]) -> Vec<F> {
+    let n = x.len();
+    (0..n)
+        .map(|i| (i..n).fold(float!(0.0), |acc, k| acc + l[k][i] * x[k]))
+        .collect()
+}
+
+/// Approximates a unit eigenvector `z` for the smallest eigenvalue of `L L^T` by inverse iteration
+/// and returns it together with the Rayleigh quotient `z^T L L^T z`
+fn min_eigenvector<F: ArgminFloat>(l: &[Vec<F>]) -> (Vec<F>, F) {
+    let n = l.len();
+    let mut z: Vec<F> = (0..n)
+        .map(|i| float!(1.0) + F::from_usize(i).unwrap() / F::from_usize(n).unwrap())
+        .collect();
+    for _ in 0..3 {
+        z = chol_solve(l, &z);
+        let z_norm = norm(&z);
+        z.iter_mut().for_each(|zi| *zi = *zi / z_norm);
+    }
+    let lz = lt_mul(l, &z);
+    let rq = dot(&lz, &lz);
+    (z, rq)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, CostFunction, Executor};
+    use crate::solver::trustregion::TrustRegion;
+    use crate::test_trait_impl;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(more_sorensen, MoreSorensen<f64>);
+
+    struct Quadratic {
+        g: Vec<f64>,
+        h: Vec<Vec<f64>>,
+    }
+
+    impl Gradient for Quadratic {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, _p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(self.g.clone())
+        }
+    }
+
+    impl Hessian for Quadratic {
+        type Param = Vec<f64>;
+        type Hessian = Vec<Vec<f64>>;
+
+        fn hessian(&self, _p: &Self::Param) -> Result<Self::Hessian, Error> {
+            Ok(self.h.clone())
+        }
+    }
+
+    fn step(g: Vec<f64>, h: Vec<Vec<f64>>, radius: f64) -> Vec<f64> {
+        let mut ms: MoreSorensen<f64> = MoreSorensen::new().with_tolerance(1e-8).unwrap();
+        ms.set_radius(radius);
+        let state = IterState::new().param(vec![0.0; g.len()]);
+        let (mut state, kv) = ms
+            .next_iter(&mut Problem::new(Quadratic { g, h }), state)
+            .unwrap();
+        assert!(kv.is_none());
+        state.take_param().unwrap()
+    }
+
+    fn norm(p: &[f64]) -> f64 {
+        p.iter().map(|x| x * x).sum::<f64>().sqrt()
+    }
+
+    #[test]
+    fn test_new() {
+        let ms: MoreSorensen<f64> = MoreSorensen::new();
+
+        let MoreSorensen {
+            radius,
+            tol,
+            max_iters,
+        } = ms;
+
+        assert_eq!(radius.to_ne_bytes(), f64::NAN.to_ne_bytes());
+        assert_eq!(tol.to_ne_bytes(), 1e-3f64.to_ne_bytes());
+        assert_eq!(max_iters, 100);
+    }
+
+    #[test]
+    fn test_builders() {
+        let ms: MoreSorensen<f64> = MoreSorensen::new()
+            .with_tolerance(1e-6)
+            .unwrap()
+            .with_max_iters(5);
+        assert_eq!(ms.tol.to_ne_bytes(), 1e-6f64.to_ne_bytes());
+        assert_eq!(ms.max_iters, 5);
+
+        for tol in [0.0, -1.0, 1.0] {
+            assert_error!(
+                MoreSorensen::<f64>::new().with_tolerance(tol),
+                ArgminError,
+                "Invalid parameter: \"`MoreSorensen`: tolerance must be in (0, 1).\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_param_not_initialized() {
+        let mut ms: MoreSorensen<f64> = MoreSorensen::new();
+        ms.set_radius(1.0);
+        let problem = Quadratic {
+            g: vec![1.0],
+            h: vec![vec![1.0]],
+        };
+        let res = ms.next_iter(&mut Problem::new(problem), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`MoreSorensen` requires an initial parameter vector. Please ",
+                "provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_interior() {
+        // Newton step -H^-1 g = (-1, -0.5) lies within the trust region
+        let p = step(vec![1.0, 1.0], vec![vec![1.0, 0.0], vec![0.0, 2.0]], 10.0);
+        assert_relative_eq!(p[0], -1.0, epsilon = 1e-12);
+        assert_relative_eq!(p[1], -0.5, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_boundary_positive_definite() {
+        let p = step(vec![1.0, 1.0], vec![vec![1.0, 0.0], vec![0.0, 2.0]], 0.5);
+        assert_relative_eq!(norm(&p), 0.5, epsilon = 1e-8);
+        // p_i = -g_i / (h_ii + lambda) with the same lambda for both components
+        let lambda0 = -1.0 / p[0] - 1.0;
+        let lambda1 = -1.0 / p[1] - 2.0;
+        assert!(lambda0 > 0.0);
+        assert_relative_eq!(lambda0, lambda1, epsilon = 1e-8);
+    }
+
+    #[test]
+    fn test_boundary_indefinite() {
+        let p = step(vec![1.0, 1.0], vec![vec![-1.0, 0.0], vec![0.0, 2.0]], 1.0);
+        assert_relative_eq!(norm(&p), 1.0, epsilon = 1e-8);
+        let lambda0 = -1.0 / p[0] + 1.0;
+        let lambda1 = -1.0 / p[1] - 2.0;
+        assert!(lambda0 > 1.0);
+        assert_relative_eq!(lambda0, lambda1, epsilon = 1e-8);
+    }
+
+    #[test]
+    fn test_hard_case() {
+        // g is orthogonal to the eigenvector (1, 0) of the negative eigenvalue. lambda = 1 and the
+        // step is (+-sqrt(4 - 1/9), -1/3).
+        let p = step(vec![0.0, 1.0], vec![vec![-1.0, 0.0], vec![0.0, 2.0]], 2.0);
+        assert_relative_eq!(norm(&p), 2.0, epsilon = 1e-6);
+        assert_relative_eq!(p[0].abs(), (4.0f64 - 1.0 / 9.0).sqrt(), epsilon = 1e-4);
+        assert_relative_eq!(p[1], -1.0 / 3.0, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn test_zero_gradient_indefinite() {
+        let p = step(vec![0.0, 0.0], vec![vec![2.0, 0.0], vec![0.0, -1.0]], 1.5);
+        assert_relative_eq!(p[0], 0.0, epsilon = 1e-6);
+        assert_relative_eq!(p[1].abs(), 1.5, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_trust_region_rosenbrock() {
+        use argmin_testfunctions::{rosenbrock, rosenbrock_derivative, rosenbrock_hessian};
+
+        struct Rosenbrock {}
+
+        impl CostFunction for Rosenbrock {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(rosenbrock(p, 1.0, 100.0))
+            }
+        }
+
+        impl Gradient for Rosenbrock {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(rosenbrock_derivative(p, 1.0, 100.0))
+            }
+        }
+
+        impl Hessian for Rosenbrock {
+            type Param = Vec<f64>;
+            type Hessian = Vec<Vec<f64>>;
+
+            fn hessian(&self, p: &Self::Param) -> Result<Self::Hessian, Error> {
+                Ok(rosenbrock_hessian(p, 1.0, 100.0))
+            }
+        }
+
+        let solver = TrustRegion::new(MoreSorensen::new());
+        let res = Executor::new(Rosenbrock {}, solver)
+            .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(100))
+            .ctrlc(false)
+            .run()
+            .unwrap();
+        let param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(param[0], 1.0, epsilon = 1e-6);
+        assert_relative_eq!(param[1], 1.0, epsilon = 1e-6);
+    }
+}
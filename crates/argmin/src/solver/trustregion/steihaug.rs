@@ -6,27 +6,33 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::core::{
-    ArgminFloat, Error, IterState, Problem, Solver, State, TerminationReason, TerminationStatus,
-    TrustRegionRadius, KV,
-};
-use argmin_math::{
-    ArgminAdd, ArgminDot, ArgminL2Norm, ArgminMul, ArgminWeightedDot, ArgminZeroLike,
+    ArgminFloat, Error, HessianVectorProduct, IterState, MatrixFree, Problem, Solver, State,
+    TerminationReason, TerminationStatus, TrustRegionRadius, KV,
 };
+use argmin_math::{ArgminAdd, ArgminDot, ArgminL2Norm, ArgminMul, ArgminSub, ArgminZeroLike};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
 
 /// # Steihaug method
 ///
 /// The Steihaug method is a conjugate gradients based approach for finding an approximate solution
 /// to the second order approximation of the cost function within the trust region.
 ///
+/// Since only products of the Hessian with vectors are needed, the method can work without ever
+/// forming the Hessian. After calling [`matrix_free`](`Steihaug::matrix_free`), the optimization
+/// problem is required to implement
+/// [`HessianVectorProduct`](`crate::core::HessianVectorProduct`) and the Hessian is not passed via
+/// the state. This requires [`TrustRegion`](`crate::solver::trustregion::TrustRegion`) to be
+/// matrix-free as well.
+///
 /// ## Reference
 ///
 /// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 /// Springer. ISBN 0-387-30303-0.
 #[derive(Clone, Default)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
-pub struct Steihaug<P, F> {
+pub struct Steihaug<P, F, M = ()> {
     /// Radius
     radius: F,
     /// epsilon
@@ -43,6 +49,10 @@ pub struct Steihaug<P, F> {
     d: Option<P>,
     /// max iters
     max_iters: u64,
+    /// Point at which the Hessian is evaluated (only used with [`MatrixFree`])
+    hessian_param: Option<P>,
+    /// Selects how products with the Hessian are computed
+    mode: PhantomData<M>,
 }
 
 impl<P, F> Steihaug<P, F>
@@ -68,9 +78,43 @@ where
             r_0_norm: F::nan(),
             d: None,
             max_iters: std::u64::MAX,
+            hessian_param: None,
+            mode: PhantomData,
         }
     }
 
+    /// Compute products with the Hessian via
+    /// [`HessianVectorProduct`](`crate::core::HessianVectorProduct`) instead of taking the Hessian
+    /// from the state.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::trustregion::Steihaug;
+    /// # use argmin::core::MatrixFree;
+    /// let sh: Steihaug<Vec<f64>, f64, MatrixFree> = Steihaug::new().matrix_free();
+    /// ```
+    pub fn matrix_free(self) -> Steihaug<P, F, MatrixFree> {
+        Steihaug {
+            radius: self.radius,
+            epsilon: self.epsilon,
+            p: self.p,
+            r: self.r,
+            rtr: self.rtr,
+            r_0_norm: self.r_0_norm,
+            d: self.d,
+            max_iters: self.max_iters,
+            hessian_param: self.hessian_param,
+            mode: PhantomData,
+        }
+    }
+}
+
+impl<P, F, M> Steihaug<P, F, M>
+where
+    P: ArgminMul<F, P> + ArgminDot<P, F> + ArgminAdd<P, P>,
+    F: ArgminFloat,
+{
     /// Set epsilon
     ///
     /// The algorithm stops when the residual is smaller than `epsilon`.
@@ -117,20 +161,27 @@ where
         self
     }
 
-    /// evaluate m(p) (without considering f_init because it is not available)
-    fn eval_m<H>(&self, p: &P, g: &P, h: &H) -> F
+    /// evaluate m(p + tau * d) (without considering f_init because it is not available)
+    ///
+    /// Since the residual satisfies `r = g + H * p`, only `H * d` is needed.
+    fn eval_m(&self, tau: F, g: &P, hd: &P) -> F
     where
-        P: ArgminWeightedDot<P, F, H>,
+        P: ArgminSub<P, P>,
     {
-        g.dot(p) + float!(0.5) * p.weighted_dot(h, p)
+        let p = self.p.as_ref().unwrap();
+        let d = self.d.as_ref().unwrap();
+        let hp = self.r.as_ref().unwrap().sub(g);
+        g.dot(p)
+            + tau * g.dot(d)
+            + float!(0.5) * (p.dot(&hp) + float!(2.0) * tau * d.dot(&hp) + tau * tau * d.dot(hd))
     }
 
     /// calculate all possible step lengths
     #[allow(clippy::many_single_char_names)]
-    fn tau<G, H>(&self, filter_func: G, eval: bool, g: &P, h: &H) -> F
+    fn tau<G>(&self, filter_func: G, eval: bool, g: &P, hd: &P) -> F
     where
         G: Fn(F) -> bool,
-        P: ArgminWeightedDot<P, F, H>,
+        P: ArgminSub<P, P>,
     {
         let p = self.p.as_ref().unwrap();
         let d = self.d.as_ref().unwrap();
@@ -155,10 +206,7 @@ where
                 .cloned()
                 .enumerate()
                 .filter(|(_, tau)| (!tau.is_nan() || !tau.is_infinite()) && filter_func(*tau))
-                .map(|(i, tau)| {
-                    let p_local = p.add(&d.mul(&tau));
-                    (i, self.eval_m(&p_local, g, h))
-                })
+                .map(|(i, tau)| (i, self.eval_m(tau, g, hd)))
                 .filter(|(_, m)| !m.is_nan() || !m.is_infinite())
                 .collect::<Vec<(usize, F)>>();
             v.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
@@ -176,6 +224,73 @@ where
 
         t[v[0].0]
     }
+
+    /// Initializes the CG iteration from the gradient and returns the initial step `p = 0`
+    fn init_residuals(&mut self, r: P) -> P
+    where
+        P: Clone + ArgminL2Norm<F> + ArgminZeroLike,
+    {
+        self.r_0_norm = r.l2_norm();
+        self.rtr = r.dot(&r);
+        self.d = Some(r.mul(&float!(-1.0)));
+        let p = r.zero_like();
+        self.p = Some(p.clone());
+        self.r = Some(r);
+        p
+    }
+
+    /// Performs one CG step given the product `hd` of the Hessian with the current search
+    /// direction. Returns the new step and whether the iteration has terminated.
+    fn step(&mut self, grad: &P, hd: &P) -> (P, bool)
+    where
+        P: Clone + ArgminL2Norm<F> + ArgminSub<P, P>,
+    {
+        let d = self.d.as_ref().unwrap();
+        let dhd = d.dot(hd);
+
+        // Current search direction d is a direction of zero curvature or negative curvature
+        let p = self.p.as_ref().unwrap();
+        if dhd <= float!(0.0) {
+            let tau = self.tau(|_| true, true, grad, hd);
+            return (p.add(&d.mul(&tau)), true);
+        }
+
+        let alpha = self.rtr / dhd;
+        let p_n = p.add(&d.mul(&alpha));
+
+        // new p violates trust region bound
+        if p_n.l2_norm() >= self.radius {
+            let tau = self.tau(|x| x >= float!(0.0), false, grad, hd);
+            return (p.add(&d.mul(&tau)), true);
+        }
+
+        let r = self.r.as_ref().unwrap();
+        let r_n = r.add(&hd.mul(&alpha));
+
+        if r_n.l2_norm() < self.epsilon * self.r_0_norm {
+            return (p_n, true);
+        }
+
+        let rjtrj = r_n.dot(&r_n);
+        let beta = rjtrj / self.rtr;
+        self.d = Some(r_n.mul(&float!(-1.0)).add(&d.mul(&beta)));
+        self.r = Some(r_n);
+        self.p = Some(p_n.clone());
+        self.rtr = rjtrj;
+
+        (p_n, false)
+    }
+
+    /// Termination criteria shared by all variants
+    fn check_termination<I: State>(&self, state: &I) -> TerminationStatus {
+        if self.r_0_norm < self.epsilon {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+        if state.get_iter() >= self.max_iters {
+            return TerminationStatus::Terminated(TerminationReason::MaxItersReached);
+        }
+        TerminationStatus::NotTerminated
+    }
 }
 
 impl<P, O, F, H> Solver<O, IterState<P, P, (), H, (), F>> for Steihaug<P, F>
@@ -185,6 +300,7 @@ where
         + ArgminL2Norm<F>
         + ArgminDot<P, F>
         + ArgminAdd<P, P>
+        + ArgminSub<P, P>
         + ArgminZeroLike,
     H: ArgminDot<P, P>,
     F: ArgminFloat,
@@ -217,13 +333,7 @@ where
             ));
         }
 
-        self.r_0_norm = r.l2_norm();
-        self.rtr = r.dot(&r);
-        self.d = Some(r.mul(&float!(-1.0)));
-        let p = r.zero_like();
-        self.p = Some(p.clone());
-
-        self.r = Some(r);
+        let p = self.init_residuals(r);
 
         Ok((state.param(p), None))
     }
@@ -243,72 +353,109 @@ where
             "`Steihaug`: Hessian in state not set."
         ))?;
 
-        let d = self.d.as_ref().unwrap();
-        let dhd = d.weighted_dot(&h, d);
+        let hd = h.dot(self.d.as_ref().unwrap());
 
-        // Current search direction d is a direction of zero curvature or negative curvature
-        let p = self.p.as_ref().unwrap();
-        if dhd <= float!(0.0) {
-            let tau = self.tau(|_| true, true, &grad, &h);
-            return Ok((
+        Ok(match self.step(&grad, &hd) {
+            (p, true) => (
                 state
-                    .param(p.add(&d.mul(&tau)))
+                    .param(p)
                     .terminate_with(TerminationReason::SolverConverged),
                 None,
-            ));
-        }
+            ),
+            (p_n, false) => (
+                state.param(p_n).cost(self.rtr).gradient(grad).hessian(h),
+                None,
+            ),
+        })
+    }
 
-        let alpha = self.rtr / dhd;
-        let p_n = p.add(&d.mul(&alpha));
+    fn terminate(&mut self, state: &IterState<P, P, (), H, (), F>) -> TerminationStatus {
+        self.check_termination(state)
+    }
+}
 
-        // new p violates trust region bound
-        if p_n.l2_norm() >= self.radius {
-            let tau = self.tau(|x| x >= float!(0.0), false, &grad, &h);
-            return Ok((
-                state
-                    .param(p.add(&d.mul(&tau)))
-                    .terminate_with(TerminationReason::SolverConverged),
-                None,
-            ));
-        }
+impl<P, O, F> Solver<O, IterState<P, P, (), (), (), F>> for Steihaug<P, F, MatrixFree>
+where
+    O: HessianVectorProduct<Param = P, Output = P>,
+    P: Clone
+        + ArgminMul<F, P>
+        + ArgminL2Norm<F>
+        + ArgminDot<P, F>
+        + ArgminAdd<P, P>
+        + ArgminSub<P, P>
+        + ArgminZeroLike,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Steihaug (matrix-free)";
 
-        let r = self.r.as_ref().unwrap();
-        let r_n = r.add(&h.dot(d).mul(&alpha));
+    fn init(
+        &mut self,
+        _problem: &mut Problem<O>,
+        mut state: IterState<P, P, (), (), (), F>,
+    ) -> Result<(IterState<P, P, (), (), (), F>, Option<KV>), Error> {
+        let r = state
+            .get_gradient()
+            .ok_or_else(argmin_error_closure!(
+                NotInitialized,
+                concat!(
+                    "`Steihaug` requires an initial gradient. ",
+                    "Please provide an initial gradient via `Executor`s `configure` method."
+                )
+            ))?
+            .clone();
 
-        if r_n.l2_norm() < self.epsilon * self.r_0_norm {
-            return Ok((
+        // The parameter vector of the state is replaced by the step, therefore the point at which
+        // the Hessian is evaluated needs to be kept.
+        self.hessian_param = Some(state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`Steihaug` requires the current parameter vector when used matrix-free. ",
+                "Please provide it via `Executor`s `configure` method."
+            )
+        ))?);
+
+        let p = self.init_residuals(r);
+
+        Ok((state.param(p), None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, P, (), (), (), F>,
+    ) -> Result<(IterState<P, P, (), (), (), F>, Option<KV>), Error> {
+        let grad = state.take_gradient().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`Steihaug`: Gradient in state not set."
+        ))?;
+
+        let hessian_param = self
+            .hessian_param
+            .as_ref()
+            .ok_or_else(argmin_error_closure!(
+                PotentialBug,
+                "`Steihaug`: Field `hessian_param` not set."
+            ))?;
+
+        let hd = problem.hessian_vec(hessian_param, self.d.as_ref().unwrap())?;
+
+        Ok(match self.step(&grad, &hd) {
+            (p, true) => (
                 state
-                    .param(p_n)
+                    .param(p)
                     .terminate_with(TerminationReason::SolverConverged),
                 None,
-            ));
-        }
-
-        let rjtrj = r_n.dot(&r_n);
-        let beta = rjtrj / self.rtr;
-        self.d = Some(r_n.mul(&float!(-1.0)).add(&d.mul(&beta)));
-        self.r = Some(r_n);
-        self.p = Some(p_n.clone());
-        self.rtr = rjtrj;
-
-        Ok((
-            state.param(p_n).cost(self.rtr).gradient(grad).hessian(h),
-            None,
-        ))
+            ),
+            (p_n, false) => (state.param(p_n).cost(self.rtr).gradient(grad), None),
+        })
     }
 
-    fn terminate(&mut self, state: &IterState<P, P, (), H, (), F>) -> TerminationStatus {
-        if self.r_0_norm < self.epsilon {
-            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
-        }
-        if state.get_iter() >= self.max_iters {
-            return TerminationStatus::Terminated(TerminationReason::MaxItersReached);
-        }
-        TerminationStatus::NotTerminated
+    fn terminate(&mut self, state: &IterState<P, P, (), (), (), F>) -> TerminationStatus {
+        self.check_termination(state)
     }
 }
 
-impl<P, F: ArgminFloat> TrustRegionRadius<F> for Steihaug<P, F> {
+impl<P, F: ArgminFloat, M> TrustRegionRadius<F> for Steihaug<P, F, M> {
     /// Set current radius.
     ///
     /// Needed by [`TrustRegion`](`crate::solver::trustregion::TrustRegion`).
@@ -348,6 +495,8 @@ mod tests {
             r_0_norm,
             d,
             max_iters,
+            hessian_param,
+            mode: _,
         } = sh;
 
         assert_eq!(radius.to_ne_bytes(), f64::NAN.to_ne_bytes());
@@ -358,6 +507,7 @@ mod tests {
         assert_eq!(r_0_norm.to_ne_bytes(), f64::NAN.to_ne_bytes());
         assert!(d.is_none());
         assert_eq!(max_iters, u64::MAX);
+        assert!(hessian_param.is_none());
     }
 
     #[test]
@@ -451,6 +601,8 @@ mod tests {
             r_0_norm,
             d,
             max_iters,
+            hessian_param,
+            mode: _,
         } = sh;
 
         assert_eq!(radius.to_ne_bytes(), 1.0f64.to_ne_bytes());
@@ -464,5 +616,103 @@ mod tests {
         assert_relative_eq!(d.as_ref().unwrap()[0], -grad[0], epsilon = f64::EPSILON);
         assert_relative_eq!(d.as_ref().unwrap()[1], -grad[1], epsilon = f64::EPSILON);
         assert_eq!(max_iters, u64::MAX);
+        assert!(hessian_param.is_none());
+    }
+
+    #[test]
+    fn test_matrix_free_init() {
+        let grad: Vec<f64> = vec![1.0, 2.0];
+
+        let mut sh: Steihaug<Vec<f64>, f64, MatrixFree> = Steihaug::new().matrix_free();
+        sh.set_radius(1.0);
+
+        // Forgot to provide the parameter vector
+        let state: IterState<Vec<f64>, Vec<f64>, (), (), (), f64> =
+            IterState::new().gradient(grad.clone());
+        let res = sh.init(&mut Problem::new(TestProblem::new()), state);
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`Steihaug` requires the current parameter vector when used ",
+                "matrix-free. Please provide it via `Executor`s `configure` method.\""
+            )
+        );
+
+        // All good.
+        let state: IterState<Vec<f64>, Vec<f64>, (), (), (), f64> =
+            IterState::new().param(vec![3.0, 4.0]).gradient(grad);
+        let (mut state_out, kv) = sh
+            .init(&mut Problem::new(TestProblem::new()), state)
+            .unwrap();
+
+        assert!(kv.is_none());
+        assert_eq!(state_out.take_param().unwrap(), vec![0.0, 0.0]);
+        assert_eq!(sh.hessian_param.unwrap(), vec![3.0, 4.0]);
+        assert_eq!(sh.rtr.to_ne_bytes(), 5.0f64.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_matrix_free() {
+        use crate::core::{Executor, HessianVectorProduct};
+
+        #[derive(Clone)]
+        struct Quadratic {
+            h: Vec<Vec<f64>>,
+        }
+
+        impl HessianVectorProduct for Quadratic {
+            type Param = Vec<f64>;
+            type Output = Vec<f64>;
+
+            fn hessian_vec(&self, _p: &Self::Param, v: &Self::Param) -> Result<Vec<f64>, Error> {
+                Ok(self.h.dot(v))
+            }
+        }
+
+        let grad = vec![1.0, -2.0, 0.5];
+        for h in [
+            // positive definite, interior solution
+            vec![
+                vec![4.0, 1.0, 0.0],
+                vec![1.0, 3.0, 0.5],
+                vec![0.0, 0.5, 2.0],
+            ],
+            // indefinite
+            vec![
+                vec![1.0, 0.0, 0.0],
+                vec![0.0, -2.0, 0.0],
+                vec![0.0, 0.0, 3.0],
+            ],
+        ] {
+            for radius in [0.1, 10.0] {
+                let mut full: Steihaug<Vec<f64>, f64> = Steihaug::new();
+                full.set_radius(radius);
+                let mut matrix_free = full.clone().matrix_free();
+                matrix_free.set_radius(radius);
+
+                let problem = Quadratic { h: h.clone() };
+                let res_full = Executor::new(problem.clone(), full)
+                    .configure(|state| {
+                        state
+                            .param(vec![0.0; 3])
+                            .gradient(grad.clone())
+                            .hessian(h.clone())
+                    })
+                    .run()
+                    .unwrap();
+                let res_matrix_free = Executor::new(problem, matrix_free)
+                    .configure(|state| state.param(vec![0.0; 3]).gradient(grad.clone()))
+                    .run()
+                    .unwrap();
+
+                let p_full = res_full.state().get_param().unwrap();
+                let p_matrix_free = res_matrix_free.state().get_param().unwrap();
+                for i in 0..3 {
+                    assert_relative_eq!(p_full[i], p_matrix_free[i], epsilon = 1e-12);
+                }
+                assert!(res_matrix_free.problem().counts["hessian_vec_count"] > 0);
+            }
+        }
     }
 }
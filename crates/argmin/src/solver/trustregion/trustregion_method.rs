@@ -6,13 +6,14 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::core::{
-    ArgminFloat, CostFunction, Error, Executor, Gradient, Hessian, IterState, OptimizationResult,
-    Problem, Solver, TerminationStatus, TrustRegionRadius, KV,
+    ArgminFloat, CostFunction, Error, Executor, Gradient, Hessian, HessianVectorProduct, IterState,
+    MatrixFree, OptimizationResult, Problem, Solver, TerminationStatus, TrustRegionRadius, KV,
 };
 use crate::solver::trustregion::reduction_ratio;
 use argmin_math::{ArgminAdd, ArgminDot, ArgminL2Norm, ArgminWeightedDot};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
 
 /// # Trust region method
 ///
@@ -32,13 +33,18 @@ use serde::{Deserialize, Serialize};
 /// The optimization problem is required to implement [`CostFunction`], [`Gradient`] and
 /// [`Hessian`].
 ///
+/// Together with a matrix-free [`Steihaug`](`crate::solver::trustregion::Steihaug::matrix_free`)
+/// subproblem, the Hessian never needs to be formed. After calling
+/// [`matrix_free`](`TrustRegion::matrix_free`), the optimization problem is required to implement
+/// [`CostFunction`], [`Gradient`] and [`HessianVectorProduct`] instead.
+///
 /// ## Reference
 ///
 /// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 /// Springer. ISBN 0-387-30303-0.
 #[derive(Clone)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
-pub struct TrustRegion<R, F> {
+pub struct TrustRegion<R, F, M = ()> {
     /// Radius
     radius: F,
     /// Maximum radius
@@ -51,6 +57,8 @@ pub struct TrustRegion<R, F> {
     fxk: F,
     /// mk(0)
     mk0: F,
+    /// Selects how products with the Hessian are computed
+    mode: PhantomData<M>,
 }
 
 impl<R, F> TrustRegion<R, F>
@@ -74,9 +82,38 @@ where
             subproblem,
             fxk: F::nan(),
             mk0: F::nan(),
+            mode: PhantomData,
         }
     }
 
+    /// Compute products with the Hessian via [`HessianVectorProduct`] instead of evaluating the
+    /// full [`Hessian`]. The subproblem solver needs to be matrix-free as well.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::trustregion::{Steihaug, TrustRegion};
+    /// # use argmin::core::MatrixFree;
+    /// let sh: Steihaug<Vec<f64>, f64, MatrixFree> = Steihaug::new().matrix_free();
+    /// let tr: TrustRegion<_, f64, MatrixFree> = TrustRegion::new(sh).matrix_free();
+    /// ```
+    pub fn matrix_free(self) -> TrustRegion<R, F, MatrixFree> {
+        TrustRegion {
+            radius: self.radius,
+            max_radius: self.max_radius,
+            eta: self.eta,
+            subproblem: self.subproblem,
+            fxk: self.fxk,
+            mk0: self.mk0,
+            mode: PhantomData,
+        }
+    }
+}
+
+impl<R, F, M> TrustRegion<R, F, M>
+where
+    F: ArgminFloat,
+{
     /// Set radius
     ///
     /// Defaults to `1.0`.
@@ -154,6 +191,43 @@ where
         self.eta = eta;
         Ok(self)
     }
+
+    /// Returns the cost at the initial parameter vector, either from the state or by evaluating
+    /// the cost function
+    fn initial_cost<O, P>(
+        &mut self,
+        problem: &mut Problem<O>,
+        param: &P,
+        cost: F,
+    ) -> Result<F, Error>
+    where
+        O: CostFunction<Param = P, Output = F>,
+    {
+        self.fxk = if cost.is_infinite() && cost.is_sign_positive() {
+            problem.cost(param)?
+        } else {
+            cost
+        };
+        self.mk0 = self.fxk;
+        Ok(self.fxk)
+    }
+
+    /// Updates the radius based on the ratio of actual to predicted reduction and returns this
+    /// ratio
+    fn update_radius(&mut self, fxkpk: F, mkpk: F, pk_norm: F) -> F {
+        let rho = reduction_ratio(self.fxk, fxkpk, self.mk0, mkpk);
+
+        self.radius = if rho < float!(0.25) {
+            float!(0.25) * pk_norm
+        } else if rho > float!(0.75) && (pk_norm - self.radius).abs() <= float!(10.0) * F::epsilon()
+        {
+            self.max_radius.min(float!(2.0) * self.radius)
+        } else {
+            self.radius
+        };
+
+        rho
+    }
 }
 
 impl<O, R, F, P, G, H> Solver<O, IterState<P, G, (), H, (), F>> for TrustRegion<R, F>
@@ -192,18 +266,12 @@ where
             .map(Result::Ok)
             .unwrap_or_else(|| problem.hessian(&param))?;
 
-        let cost = state.get_cost();
-        self.fxk = if cost.is_infinite() && cost.is_sign_positive() {
-            problem.cost(&param)?
-        } else {
-            cost
-        };
+        let cost = self.initial_cost(problem, &param, state.get_cost())?;
 
-        self.mk0 = self.fxk;
         Ok((
             state
                 .param(param)
-                .cost(self.fxk)
+                .cost(cost)
                 .gradient(grad)
                 .hessian(hessian),
             None,
@@ -255,20 +323,8 @@ where
         let fxkpk = problem.cost(&new_param)?;
         let mkpk = self.fxk + pk.dot(&grad) + float!(0.5) * pk.weighted_dot(&hessian, &pk);
 
-        let rho = reduction_ratio(self.fxk, fxkpk, self.mk0, mkpk);
-
-        let pk_norm = pk.l2_norm();
-
         let cur_radius = self.radius;
-
-        self.radius = if rho < float!(0.25) {
-            float!(0.25) * pk_norm
-        } else if rho > float!(0.75) && (pk_norm - self.radius).abs() <= float!(10.0) * F::epsilon()
-        {
-            self.max_radius.min(float!(2.0) * self.radius)
-        } else {
-            self.radius
-        };
+        let rho = self.update_radius(fxkpk, mkpk, pk.l2_norm());
 
         Ok((
             if rho > self.eta {
@@ -297,6 +353,98 @@ where
     }
 }
 
+impl<O, R, F, P, G> Solver<O, IterState<P, G, (), (), (), F>> for TrustRegion<R, F, MatrixFree>
+where
+    O: CostFunction<Param = P, Output = F>
+        + Gradient<Param = P, Gradient = G>
+        + HessianVectorProduct<Param = P, Output = P>,
+    P: Clone + ArgminL2Norm<F> + ArgminDot<P, F> + ArgminDot<G, F> + ArgminAdd<P, P>,
+    G: Clone,
+    R: Clone + TrustRegionRadius<F> + Solver<O, IterState<P, G, (), (), (), F>>,
+    F: ArgminFloat,
+{
+    const NAME: &'static str = "Trust region (matrix-free)";
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, (), (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`TrustRegion` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+
+        let grad = state
+            .take_gradient()
+            .map(Result::Ok)
+            .unwrap_or_else(|| problem.gradient(&param))?;
+
+        let cost = self.initial_cost(problem, &param, state.get_cost())?;
+
+        Ok((state.param(param).cost(cost).gradient(grad), None))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, (), (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`TrustRegion`: Parameter vector in state not set."
+        ))?;
+
+        let grad = state.take_gradient().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`TrustRegion`: Gradient in state not set."
+        ))?;
+
+        self.subproblem.set_radius(self.radius);
+
+        let OptimizationResult {
+            problem: sub_problem,
+            state: mut sub_state,
+            ..
+        } = Executor::new(problem.take_problem().unwrap(), self.subproblem.clone())
+            .configure(|config| config.param(param.clone()).gradient(grad.clone()))
+            .ctrlc(false)
+            .run()?;
+
+        let pk = sub_state.take_param().unwrap();
+
+        // Consume intermediate problem again. This takes care of the function evaluation counts.
+        problem.consume_problem(sub_problem);
+
+        let new_param = pk.add(&param);
+        let fxkpk = problem.cost(&new_param)?;
+        let hpk = problem.hessian_vec(&param, &pk)?;
+        let mkpk = self.fxk + pk.dot(&grad) + float!(0.5) * pk.dot(&hpk);
+
+        let cur_radius = self.radius;
+        let rho = self.update_radius(fxkpk, mkpk, pk.l2_norm());
+
+        Ok((
+            if rho > self.eta {
+                self.fxk = fxkpk;
+                self.mk0 = fxkpk;
+                let grad = problem.gradient(&new_param)?;
+                state.param(new_param).cost(fxkpk).gradient(grad)
+            } else {
+                state.param(param).cost(self.fxk).gradient(grad)
+            },
+            Some(kv!("radius" => cur_radius;)),
+        ))
+    }
+
+    fn terminate(&mut self, _state: &IterState<P, G, (), (), (), F>) -> TerminationStatus {
+        TerminationStatus::NotTerminated
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -319,6 +467,7 @@ mod tests {
             subproblem: _,
             fxk,
             mk0,
+            mode: _,
         } = tr;
 
         assert_eq!(radius.to_ne_bytes(), 1.0f64.to_ne_bytes());
@@ -426,6 +575,7 @@ mod tests {
             subproblem: _,
             fxk,
             mk0,
+            mode: _,
         } = tr;
 
         assert_eq!(radius.to_ne_bytes(), 1.0f64.to_ne_bytes());
@@ -434,4 +584,76 @@ mod tests {
         assert_eq!(fxk.to_ne_bytes(), 1.0f64.sqrt().to_ne_bytes());
         assert_eq!(mk0.to_ne_bytes(), 1.0f64.to_ne_bytes());
     }
+
+    #[test]
+    fn test_matrix_free() {
+        use crate::core::{Error, MatrixFree};
+        use approx::assert_relative_eq;
+        use argmin_math::ArgminDot;
+        use argmin_testfunctions::{rosenbrock, rosenbrock_derivative, rosenbrock_hessian};
+
+        struct Rosenbrock {}
+
+        impl CostFunction for Rosenbrock {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(rosenbrock(p, 1.0, 100.0))
+            }
+        }
+
+        impl Gradient for Rosenbrock {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(rosenbrock_derivative(p, 1.0, 100.0))
+            }
+        }
+
+        impl Hessian for Rosenbrock {
+            type Param = Vec<f64>;
+            type Hessian = Vec<Vec<f64>>;
+
+            fn hessian(&self, p: &Self::Param) -> Result<Self::Hessian, Error> {
+                Ok(rosenbrock_hessian(p, 1.0, 100.0))
+            }
+        }
+
+        impl HessianVectorProduct for Rosenbrock {
+            type Param = Vec<f64>;
+            type Output = Vec<f64>;
+
+            fn hessian_vec(&self, p: &Self::Param, v: &Self::Param) -> Result<Vec<f64>, Error> {
+                Ok(rosenbrock_hessian(p, 1.0, 100.0).dot(v))
+            }
+        }
+
+        let init_param = vec![-1.2, 1.0, 0.5];
+
+        let full: TrustRegion<_, f64> = TrustRegion::new(Steihaug::new());
+        let full = Executor::new(Rosenbrock {}, full)
+            .configure(|state| state.param(init_param.clone()).max_iters(200))
+            .run()
+            .unwrap();
+
+        let matrix_free: TrustRegion<_, f64, MatrixFree> =
+            TrustRegion::new(Steihaug::new().matrix_free()).matrix_free();
+        let matrix_free = Executor::new(Rosenbrock {}, matrix_free)
+            .configure(|state| state.param(init_param).max_iters(200))
+            .run()
+            .unwrap();
+
+        let x_full = full.state().get_best_param().unwrap();
+        let x_matrix_free = matrix_free.state().get_best_param().unwrap();
+        for i in 0..3 {
+            assert_relative_eq!(x_full[i], 1.0, epsilon = 1e-6);
+            assert_relative_eq!(x_matrix_free[i], 1.0, epsilon = 1e-6);
+        }
+
+        let counts = &matrix_free.problem().counts;
+        assert!(counts["hessian_vec_count"] > 0);
+        assert!(!counts.contains_key("hessian_count"));
+    }
 }
@@ -0,0 +1,126 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::{
+    core::{observers::ObserverMode, Error, Executor, QuadraticProgram, State},
+    solver::activeset::ActiveSet,
+};
+use argmin_observer_slog::SlogLogger;
+use nalgebra::{DMatrix, DVector};
+
+/// Mean-variance portfolio optimization: minimize `1/2 w^T Sigma w - mu^T w` subject to
+/// `sum(w) = 1` and `0 <= w_i <= 0.4`.
+struct Portfolio {
+    /// Covariance matrix of the returns
+    sigma: DMatrix<f64>,
+    /// Expected returns
+    mu: DVector<f64>,
+}
+
+impl QuadraticProgram for Portfolio {
+    type Param = DVector<f64>;
+    type Matrix = DMatrix<f64>;
+    type Float = f64;
+
+    fn H(&self) -> Result<DMatrix<f64>, Error> {
+        Ok(self.sigma.clone())
+    }
+
+    fn g(&self) -> Result<DVector<f64>, Error> {
+        Ok(-&self.mu)
+    }
+
+    fn A_eq(&self) -> Result<Option<DMatrix<f64>>, Error> {
+        Ok(Some(DMatrix::from_element(1, self.mu.len(), 1.0)))
+    }
+
+    fn b_eq(&self) -> Result<Option<DVector<f64>>, Error> {
+        Ok(Some(DVector::from_element(1, 1.0)))
+    }
+
+    fn A_ineq(&self) -> Result<Option<DMatrix<f64>>, Error> {
+        // w_i <= 0.4 followed by -w_i <= 0
+        let n = self.mu.len();
+        let eye = DMatrix::<f64>::identity(n, n);
+        Ok(Some(DMatrix::from_fn(2 * n, n, |i, j| {
+            if i < n {
+                eye[(i, j)]
+            } else {
+                -eye[(i - n, j)]
+            }
+        })))
+    }
+
+    fn b_ineq(&self) -> Result<Option<DVector<f64>>, Error> {
+        let n = self.mu.len();
+        Ok(Some(DVector::from_fn(
+            2 * n,
+            |i, _| {
+                if i < n {
+                    0.4
+                } else {
+                    0.0
+                }
+            },
+        )))
+    }
+}
+
+fn run() -> Result<(), Error> {
+    let sigma = DMatrix::from_row_slice(
+        4,
+        4,
+        &[
+            0.04, 0.006, 0.002, 0.0, //
+            0.006, 0.09, 0.01, 0.004, //
+            0.002, 0.01, 0.16, 0.02, //
+            0.0, 0.004, 0.02, 0.25,
+        ],
+    );
+    let problem = Portfolio {
+        sigma: sigma.clone(),
+        mu: DVector::from_column_slice(&[0.05, 0.08, 0.12, 0.02]),
+    };
+
+    // Run solver
+    let res = Executor::new(problem, ActiveSet::new())
+        .add_observer(SlogLogger::term(), ObserverMode::Always)
+        .run()?;
+
+    println!("{res}");
+    let active_set = res.state().get_active_set().unwrap().clone();
+    println!("Active inequality constraints: {active_set:?}");
+    println!(
+        "Lagrange multipliers: {:?}",
+        res.state().get_dual().unwrap()
+    );
+
+    // Slightly changed expected returns: start from the previous active set
+    let problem = Portfolio {
+        sigma,
+        mu: DVector::from_column_slice(&[0.05, 0.085, 0.11, 0.02]),
+    };
+    let res = Executor::new(problem, ActiveSet::new())
+        .configure(|state| state.active_set(active_set))
+        .add_observer(SlogLogger::term(), ObserverMode::Always)
+        .run()?;
+
+    println!("{res}");
+    println!(
+        "Warm start: {} iterations, active inequality constraints: {:?}",
+        res.state().get_iter(),
+        res.state().get_active_set().unwrap()
+    );
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{e}");
+        std::process::exit(1);
+    }
+}
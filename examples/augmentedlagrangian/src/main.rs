@@ -0,0 +1,94 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::{
+    core::{
+        observers::ObserverMode, CostFunction, EqualityConstraints, Error, Executor, Gradient,
+        InequalityConstraints, IterState,
+    },
+    solver::{
+        augmentedlagrangian::AugmentedLagrangian, linesearch::MoreThuenteLineSearch,
+        quasinewton::LBFGS,
+    },
+};
+use argmin_observer_slog::SlogLogger;
+use argmin_testfunctions::{rosenbrock, rosenbrock_derivative};
+
+/// Rosenbrock function restricted to the unit disk
+struct ConstrainedRosenbrock {}
+
+impl CostFunction for ConstrainedRosenbrock {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(rosenbrock(p, 1.0, 100.0))
+    }
+}
+
+impl Gradient for ConstrainedRosenbrock {
+    type Param = Vec<f64>;
+    type Gradient = Vec<f64>;
+
+    fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+        Ok(rosenbrock_derivative(p, 1.0, 100.0))
+    }
+}
+
+// The problem has no equality constraints, therefore the default implementation is used.
+impl EqualityConstraints for ConstrainedRosenbrock {
+    type Param = Vec<f64>;
+    type Float = f64;
+}
+
+impl InequalityConstraints for ConstrainedRosenbrock {
+    type Param = Vec<f64>;
+    type Float = f64;
+
+    /// x_0^2 + x_1^2 <= 1
+    fn inequality_constraints(&self, p: &Self::Param) -> Result<Vec<f64>, Error> {
+        Ok(vec![p[0].powi(2) + p[1].powi(2) - 1.0])
+    }
+
+    fn inequality_jacobian(&self, p: &Self::Param) -> Result<Vec<Self::Param>, Error> {
+        Ok(vec![vec![2.0 * p[0], 2.0 * p[1]]])
+    }
+}
+
+fn run() -> Result<(), Error> {
+    // Define problem
+    let problem = ConstrainedRosenbrock {};
+
+    // Define initial parameter vector
+    let init_param: Vec<f64> = vec![-1.2, 1.0];
+
+    // Set up inner solver which minimizes the augmented Lagrangian
+    let linesearch = MoreThuenteLineSearch::new();
+    let inner = LBFGS::new(linesearch, 7);
+
+    // Set up solver
+    let solver: AugmentedLagrangian<_, IterState<_, _, _, _, _, f64>, f64> =
+        AugmentedLagrangian::new(inner).with_tolerance(1e-8)?;
+
+    // Run solver
+    let res = Executor::new(problem, solver)
+        .configure(|state| state.param(init_param).max_iters(50))
+        .add_observer(SlogLogger::term(), ObserverMode::Always)
+        .run()?;
+
+    // Print result
+    println!("{res}");
+    println!("Multipliers: {:?}", res.state.get_multipliers().unwrap());
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{e}");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,53 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::{
+    core::{observers::ObserverMode, CostFunction, Error, Executor},
+    solver::bayesopt::{AcquisitionFunction, BayesOpt, Kernel},
+};
+use argmin_observer_slog::SlogLogger;
+use argmin_testfunctions::goldsteinprice;
+
+struct GoldsteinPrice {}
+
+impl CostFunction for GoldsteinPrice {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        // Pretend that this is expensive to evaluate
+        Ok(goldsteinprice(&[param[0], param[1]]).ln())
+    }
+}
+
+fn run() -> Result<(), Error> {
+    let cost_function = GoldsteinPrice {};
+
+    let solver = BayesOpt::new((vec![-2.0, -2.0], vec![2.0, 2.0]))
+        .with_kernel(Kernel::Matern52)
+        .with_acquisition(AcquisitionFunction::ExpectedImprovement { xi: 0.01 })?
+        .with_initial_samples(10)?
+        .with_batch_size(2)?;
+
+    // 10 initial samples plus 2 evaluations in each of the 40 iterations
+    let res = Executor::new(cost_function, solver)
+        .configure(|state| state.max_iters(40))
+        .add_observer(SlogLogger::term(), ObserverMode::Always)
+        .run()?;
+
+    // Print Result
+    println!("{res}");
+
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{e}");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,53 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::{
+    core::{observers::ObserverMode, CostFunction, Error, Executor},
+    solver::cmaes::{RestartStrategy, CMAES},
+};
+use argmin_observer_slog::SlogLogger;
+use argmin_testfunctions::rastrigin;
+
+struct Rastrigin {}
+
+impl CostFunction for Rastrigin {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(rastrigin(p))
+    }
+}
+
+fn run() -> Result<(), Error> {
+    // Define cost function
+    let cost = Rastrigin {};
+
+    // Define initial mean and step size
+    let init_mean: Vec<f64> = vec![3.0, -2.0, 1.5, 4.0, -3.5];
+    let init_sigma = 2.0;
+
+    // Set up solver. The Rastrigin function has many local minima, therefore the solver is
+    // restarted with increasing population sizes.
+    let solver = CMAES::new(init_mean, init_sigma).with_restarts(RestartStrategy::IPOP, 8)?;
+
+    // Run solver
+    let res = Executor::new(cost, solver)
+        .configure(|state| state.max_iters(5000).target_cost(1e-10))
+        .add_observer(SlogLogger::term(), ObserverMode::Every(50))
+        .run()?;
+
+    // Print result
+    println!("{res}");
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{e}");
+    }
+}
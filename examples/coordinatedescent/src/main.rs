@@ -0,0 +1,123 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::{
+    core::{observers::ObserverMode, CostFunction, Error, Executor, PartialGradient, State},
+    solver::coordinatedescent::{CoordinateDescent, CoordinateSelection},
+};
+use argmin_observer_slog::SlogLogger;
+
+/// Least squares regression 1/2 ||A x - b||^2 (the penalties are added by the solver)
+struct LeastSquares {
+    /// Columns of `A`
+    columns: Vec<Vec<f64>>,
+    b: Vec<f64>,
+}
+
+impl LeastSquares {
+    fn residual(&self, p: &[f64]) -> Vec<f64> {
+        let mut r: Vec<f64> = self.b.iter().map(|b| -b).collect();
+        for (column, &x) in self.columns.iter().zip(p.iter()) {
+            if x != 0.0 {
+                for (ri, a) in r.iter_mut().zip(column.iter()) {
+                    *ri += a * x;
+                }
+            }
+        }
+        r
+    }
+}
+
+impl CostFunction for LeastSquares {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(0.5 * self.residual(p).iter().map(|r| r * r).sum::<f64>())
+    }
+}
+
+impl PartialGradient for LeastSquares {
+    type Param = Vec<f64>;
+    type Float = f64;
+
+    /// a_j^T (A x - b)
+    fn partial_gradient(&self, p: &Self::Param, idx: usize) -> Result<f64, Error> {
+        Ok(self.columns[idx]
+            .iter()
+            .zip(self.residual(p).iter())
+            .map(|(a, r)| a * r)
+            .sum())
+    }
+
+    /// a_j^T a_j
+    fn coordinate_lipschitz(&self, idx: usize) -> Result<f64, Error> {
+        Ok(self.columns[idx].iter().map(|a| a * a).sum())
+    }
+}
+
+/// Pseudo random number in [-1, 1) (xorshift)
+fn uniform(state: &mut u64) -> f64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    (*state >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+}
+
+fn run() -> Result<(), Error> {
+    // Sparse vector with 200 entries which is to be recovered from 50 measurements
+    let mut x_true = vec![0.0; 200];
+    x_true[3] = 3.0;
+    x_true[42] = -2.0;
+    x_true[117] = 1.5;
+    x_true[180] = -1.0;
+    let mut seed = 42u64;
+    let columns: Vec<Vec<f64>> = (0..x_true.len())
+        .map(|_| (0..50).map(|_| uniform(&mut seed)).collect())
+        .collect();
+    let b = (0..50)
+        .map(|i| {
+            columns
+                .iter()
+                .zip(x_true.iter())
+                .map(|(c, x)| c[i] * x)
+                .sum()
+        })
+        .collect();
+
+    // Define problem
+    let problem = LeastSquares { columns, b };
+
+    // Set up solver: elastic net penalty, random order of coordinates and screening
+    let solver = CoordinateDescent::new()
+        .with_l1_regularization(0.5)?
+        .with_l2_regularization(0.01)?
+        .with_selection(CoordinateSelection::Random)
+        .with_screening(true)
+        .with_tolerance_grad(1e-8)?;
+
+    // Run solver
+    let res = Executor::new(problem, solver)
+        .configure(|state| state.param(vec![0.0; x_true.len()]).max_iters(1000))
+        .add_observer(SlogLogger::term(), ObserverMode::Every(10))
+        .run()?;
+
+    // Print result
+    println!("{res}");
+    let x = res.state().get_best_param().unwrap();
+    for (j, xj) in x.iter().enumerate().filter(|(_, xj)| **xj != 0.0) {
+        println!("x[{j}] = {xj:.4} (true value: {})", x_true[j]);
+    }
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{e}");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::{
+    core::{observers::ObserverMode, CostFunction, Error, Executor},
+    solver::differentialevolution::{DifferentialEvolution, ParameterAdaptation, Strategy},
+};
+use argmin_observer_slog::SlogLogger;
+use argmin_testfunctions::rastrigin;
+
+struct Rastrigin {}
+
+impl CostFunction for Rastrigin {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(rastrigin(p))
+    }
+}
+
+fn run() -> Result<(), Error> {
+    // Define cost function
+    let cost = Rastrigin {};
+
+    // Define bounds
+    let lower_bound: Vec<f64> = vec![-5.12; 5];
+    let upper_bound: Vec<f64> = vec![5.12; 5];
+
+    // Set up solver: current-to-pbest/1/bin with SHADE parameter adaptation
+    let solver = DifferentialEvolution::new((lower_bound, upper_bound), 50)
+        .with_strategy(Strategy::CurrentToBest1Bin)
+        .with_adaptation(ParameterAdaptation::SHADE)
+        .with_pbest_fraction(0.1)?;
+
+    // Run solver
+    let res = Executor::new(cost, solver)
+        .configure(|state| state.max_iters(1000).target_cost(1e-10))
+        .add_observer(SlogLogger::term(), ObserverMode::Every(50))
+        .run()?;
+
+    // Print result
+    println!("{res}");
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{e}");
+    }
+}
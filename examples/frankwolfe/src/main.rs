@@ -0,0 +1,146 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::{
+    core::{
+        observers::ObserverMode, CostFunction, Error, Executor, Gradient, LinearMinimizationOracle,
+        State,
+    },
+    solver::{
+        frankwolfe::{FrankWolfe, FrankWolfeLS, FrankWolfeVariant},
+        linesearch::MoreThuenteLineSearch,
+    },
+};
+use argmin_observer_slog::SlogLogger;
+
+/// Least squares regression 1/2 ||A x - b||^2 constrained to the L1 ball ||x||_1 <= radius
+struct Lasso {
+    /// Rows of `A`
+    rows: Vec<Vec<f64>>,
+    b: Vec<f64>,
+    radius: f64,
+}
+
+impl Lasso {
+    fn residual(&self, p: &[f64]) -> Vec<f64> {
+        self.rows
+            .iter()
+            .zip(self.b.iter())
+            .map(|(row, b)| row.iter().zip(p.iter()).map(|(a, x)| a * x).sum::<f64>() - b)
+            .collect()
+    }
+}
+
+impl CostFunction for Lasso {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(0.5 * self.residual(p).iter().map(|r| r * r).sum::<f64>())
+    }
+}
+
+impl Gradient for Lasso {
+    type Param = Vec<f64>;
+    type Gradient = Vec<f64>;
+
+    /// A^T (A x - b)
+    fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+        let mut g = vec![0.0; p.len()];
+        for (row, r) in self.rows.iter().zip(self.residual(p).iter()) {
+            for (gi, a) in g.iter_mut().zip(row.iter()) {
+                *gi += a * r;
+            }
+        }
+        Ok(g)
+    }
+}
+
+impl LinearMinimizationOracle for Lasso {
+    type Param = Vec<f64>;
+    type Gradient = Vec<f64>;
+
+    /// The minimizer of a linear function over the L1 ball is the vertex `-radius * sign(g_i) e_i`
+    /// where `|g_i|` is largest.
+    fn lmo(&self, gradient: &Self::Gradient) -> Result<Self::Param, Error> {
+        let i = (0..gradient.len())
+            .max_by(|&a, &b| gradient[a].abs().total_cmp(&gradient[b].abs()))
+            .unwrap();
+        let mut vertex = vec![0.0; gradient.len()];
+        vertex[i] = -self.radius * gradient[i].signum();
+        Ok(vertex)
+    }
+}
+
+/// Pseudo random number in [-1, 1) (xorshift)
+fn uniform(state: &mut u64) -> f64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    (*state >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+}
+
+fn problem() -> (Lasso, Vec<f64>) {
+    // Sparse vector with 100 entries which is to be recovered from 40 measurements
+    let mut x_true = vec![0.0; 100];
+    x_true[7] = 2.0;
+    x_true[31] = -1.0;
+    x_true[64] = 1.5;
+    let mut seed = 42u64;
+    let rows: Vec<Vec<f64>> = (0..40)
+        .map(|_| (0..x_true.len()).map(|_| uniform(&mut seed)).collect())
+        .collect();
+    let b = rows
+        .iter()
+        .map(|row| row.iter().zip(x_true.iter()).map(|(a, x)| a * x).sum())
+        .collect();
+    let radius = x_true.iter().map(|x: &f64| x.abs()).sum();
+    (Lasso { rows, b, radius }, x_true)
+}
+
+fn run() -> Result<(), Error> {
+    let (lasso, x_true) = problem();
+    let x0 = vec![0.0; x_true.len()];
+
+    // Frank-Wolfe method with the standard step length schedule
+    let res = Executor::new(lasso, FrankWolfe::new().with_tolerance(1e-4)?)
+        .configure(|state| state.param(x0.clone()).max_iters(500))
+        .add_observer(SlogLogger::term(), ObserverMode::Every(100))
+        .run()?;
+    // The cost function is not evaluated by `FrankWolfe`
+    let (lasso, _) = problem();
+    println!(
+        "Frank-Wolfe method: cost {} after {} iterations\n",
+        lasso.cost(res.state().get_param().unwrap())?,
+        res.state().get_iter()
+    );
+
+    // Pairwise Frank-Wolfe method with line search
+    let (lasso, _) = problem();
+    let linesearch = MoreThuenteLineSearch::new();
+    let solver = FrankWolfeLS::new(linesearch)
+        .with_variant(FrankWolfeVariant::Pairwise)
+        .with_tolerance(1e-4)?;
+    let res = Executor::new(lasso, solver)
+        .configure(|state| state.param(x0).max_iters(500))
+        .add_observer(SlogLogger::term(), ObserverMode::Every(10))
+        .run()?;
+    println!("{res}");
+
+    let x = res.state().get_best_param().unwrap();
+    for (j, xj) in x.iter().enumerate().filter(|(_, xj)| xj.abs() > 1e-6) {
+        println!("x[{j}] = {xj:.4} (true value: {})", x_true[j]);
+    }
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{e}");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,159 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::{
+    core::{observers::ObserverMode, CostFunction, Error, Executor},
+    solver::geneticalgorithm::{Crossover, GeneticAlgorithm, Mutate, TournamentSelection},
+};
+use argmin_observer_slog::SlogLogger;
+use rand::prelude::*;
+use rand_xoshiro::Xoshiro256PlusPlus;
+use std::sync::{Arc, Mutex};
+
+/// Traveling salesman problem. A tour is a permutation of the cities.
+struct TravelingSalesman {
+    /// Coordinates of the cities
+    cities: Vec<(f64, f64)>,
+    /// Random number generator. We use a `Arc<Mutex<_>>` here because `Crossover` and `Mutate`
+    /// require `self` to be passed as an immutable reference. This gives us thread safe interior
+    /// mutability.
+    rng: Arc<Mutex<Xoshiro256PlusPlus>>,
+}
+
+impl TravelingSalesman {
+    /// Cities evenly spaced on the unit circle in random order. The shortest tour visits them
+    /// along the circle.
+    pub fn new(num_cities: usize) -> Self {
+        let mut rng = Xoshiro256PlusPlus::from_entropy();
+        let mut cities: Vec<(f64, f64)> = (0..num_cities)
+            .map(|i| {
+                let phi = 2.0 * std::f64::consts::PI * i as f64 / num_cities as f64;
+                (phi.cos(), phi.sin())
+            })
+            .collect();
+        cities.shuffle(&mut rng);
+        TravelingSalesman {
+            cities,
+            rng: Arc::new(Mutex::new(rng)),
+        }
+    }
+
+    /// Random tours
+    pub fn random_tours(&self, num: usize) -> Vec<Vec<usize>> {
+        let mut rng = self.rng.lock().unwrap();
+        (0..num)
+            .map(|_| {
+                let mut tour: Vec<usize> = (0..self.cities.len()).collect();
+                tour.shuffle(&mut *rng);
+                tour
+            })
+            .collect()
+    }
+}
+
+impl CostFunction for TravelingSalesman {
+    type Param = Vec<usize>;
+    type Output = f64;
+
+    /// Length of the round trip
+    fn cost(&self, tour: &Self::Param) -> Result<Self::Output, Error> {
+        Ok((0..tour.len())
+            .map(|i| {
+                let (x1, y1) = self.cities[tour[i]];
+                let (x2, y2) = self.cities[tour[(i + 1) % tour.len()]];
+                ((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt()
+            })
+            .sum())
+    }
+}
+
+/// Order crossover (OX): a random segment is copied from one parent, the remaining cities are
+/// filled in the order in which they appear in the other parent.
+fn order_crossover(parent1: &[usize], parent2: &[usize], start: usize, end: usize) -> Vec<usize> {
+    let segment = &parent1[start..end];
+    let mut rest = parent2.iter().filter(|city| !segment.contains(city));
+    (0..parent1.len())
+        .map(|i| {
+            if (start..end).contains(&i) {
+                parent1[i]
+            } else {
+                *rest.next().unwrap()
+            }
+        })
+        .collect()
+}
+
+impl Crossover for TravelingSalesman {
+    type Param = Vec<usize>;
+
+    fn crossover(
+        &self,
+        parent1: &Self::Param,
+        parent2: &Self::Param,
+    ) -> Result<(Self::Param, Self::Param), Error> {
+        let mut rng = self.rng.lock().unwrap();
+        let a = rng.gen_range(0..parent1.len());
+        let b = rng.gen_range(0..parent1.len());
+        let (start, end) = (a.min(b), a.max(b) + 1);
+        Ok((
+            order_crossover(parent1, parent2, start, end),
+            order_crossover(parent2, parent1, start, end),
+        ))
+    }
+}
+
+impl Mutate for TravelingSalesman {
+    type Param = Vec<usize>;
+
+    /// Reverse a random part of the tour (2-opt move)
+    fn mutate(&self, tour: &Self::Param) -> Result<Self::Param, Error> {
+        let mut rng = self.rng.lock().unwrap();
+        let a = rng.gen_range(0..tour.len());
+        let b = rng.gen_range(0..tour.len());
+        let mut tour = tour.clone();
+        tour[a.min(b)..=a.max(b)].reverse();
+        Ok(tour)
+    }
+}
+
+fn run() -> Result<(), Error> {
+    // Define problem with 30 cities
+    let problem = TravelingSalesman::new(30);
+
+    // Create initial population
+    let init_population = problem.random_tours(100);
+
+    // Length of the shortest tour
+    let optimum = 60.0 * (std::f64::consts::PI / 30.0).sin();
+
+    // Set up solver
+    let solver = GeneticAlgorithm::new(TournamentSelection::new().with_size(3)?)
+        .with_mutation_probability(0.3)?
+        .with_elitism(2);
+
+    // Run solver
+    let res = Executor::new(problem, solver)
+        .configure(|state| {
+            state
+                .population(init_population)
+                .max_iters(2000)
+                .target_cost(optimum + 1e-10)
+        })
+        .add_observer(SlogLogger::term(), ObserverMode::Every(100))
+        .run()?;
+
+    // Print result
+    println!("{res}");
+    println!("Length of the shortest tour: {optimum}");
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{e}");
+    }
+}
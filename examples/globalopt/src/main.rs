@@ -0,0 +1,82 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::{
+    core::{observers::ObserverMode, CostFunction, Error, Executor, Gradient, State},
+    solver::{
+        globalopt::{BasinHopping, MultiStart, StartPoints},
+        linesearch::MoreThuenteLineSearch,
+        quasinewton::LBFGS,
+    },
+};
+use argmin_observer_slog::SlogLogger;
+use argmin_testfunctions::{rastrigin, rastrigin_derivative};
+
+struct Rastrigin {}
+
+impl CostFunction for Rastrigin {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(rastrigin(p))
+    }
+}
+
+impl Gradient for Rastrigin {
+    type Param = Vec<f64>;
+    type Gradient = Vec<f64>;
+
+    fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+        Ok(rastrigin_derivative(p))
+    }
+}
+
+fn run() -> Result<(), Error> {
+    // Constructs the local solver for a start point. L-BFGS starts from the initial parameter
+    // vector of its state, which the meta-solvers set to the start point.
+    let lbfgs = |_: &Vec<f64>| Ok(LBFGS::new(MoreThuenteLineSearch::new(), 5));
+
+    // Multi-start: run L-BFGS from 512 points within [-5.12, 5.12]^2
+    let multistart = MultiStart::new(lbfgs, (vec![-5.12; 2], vec![5.12; 2]), 512)
+        // Optional: use Latin hypercube sampling instead of the default Sobol sequence (whose
+        // first point is the center of the box, the global minimum of the Rastrigin function)
+        .with_start_points(StartPoints::LatinHypercube)
+        .with_local_max_iters(100)?;
+
+    let res = Executor::new(Rastrigin {}, multistart)
+        .add_observer(SlogLogger::term(), ObserverMode::Every(64))
+        .run()?;
+    println!("{res}");
+    println!(
+        "Local runs: {}, cost function evaluations: {}",
+        res.solver().local_results().len(),
+        res.state().get_func_counts()["cost_count"]
+    );
+
+    // Basin-hopping: perturb the current local minimum by up to 1.0 per coordinate, which is the
+    // distance between neighboring local minima of the Rastrigin function
+    let basinhopping = BasinHopping::new(lbfgs)
+        .with_step_size(1.0)?
+        .with_temperature(1.0)?
+        .with_bounds((vec![-5.12; 2], vec![5.12; 2]))
+        .with_local_max_iters(100)?;
+
+    let res = Executor::new(Rastrigin {}, basinhopping)
+        .configure(|state| state.param(vec![2.6, -3.7]).max_iters(200))
+        .add_observer(SlogLogger::term(), ObserverMode::Every(20))
+        .run()?;
+    println!("{res}");
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{e}");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::{
+    core::{observers::ObserverMode, Error, Executor, LinearProgram},
+    solver::interiorpoint::InteriorPoint,
+};
+use argmin_observer_slog::SlogLogger;
+use nalgebra::{DMatrix, DVector};
+
+/// Minimize `(x1 - 1)^2 + (x2 - 2.5)^2` subject to `x1 + x2 <= 2` and `x1, x2 >= 0`.
+///
+/// In standard form, the inequality is turned into an equality by adding the slack variable `x3`.
+struct Projection {}
+
+impl LinearProgram for Projection {
+    type Param = Vec<f64>;
+    type Float = f64;
+
+    fn c(&self) -> Result<Vec<f64>, Error> {
+        Ok(vec![-2.0, -5.0, 0.0])
+    }
+
+    fn b(&self) -> Result<Vec<f64>, Error> {
+        Ok(vec![2.0])
+    }
+
+    fn A(&self) -> Result<Vec<Vec<f64>>, Error> {
+        Ok(vec![vec![1.0, 1.0, 1.0]])
+    }
+
+    fn Q(&self) -> Result<Option<Vec<Vec<f64>>>, Error> {
+        Ok(Some(vec![
+            vec![2.0, 0.0, 0.0],
+            vec![0.0, 2.0, 0.0],
+            vec![0.0, 0.0, 0.0],
+        ]))
+    }
+}
+
+fn run() -> Result<(), Error> {
+    // Set up solver. The linear systems are solved using `nalgebra`.
+    let solver: InteriorPoint<DVector<f64>, DMatrix<f64>, f64> = InteriorPoint::new();
+
+    // Run solver
+    let res = Executor::new(Projection {}, solver)
+        .configure(|state| state.max_iters(100))
+        .add_observer(SlogLogger::term(), ObserverMode::Always)
+        .run()?;
+
+    // Print result
+    println!("{res}");
+    println!("Dual values: {:?}", res.state().get_dual().unwrap());
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{e}");
+        std::process::exit(1);
+    }
+}
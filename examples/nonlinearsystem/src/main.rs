@@ -0,0 +1,89 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::{
+    core::{observers::ObserverMode, Error, Executor, Jacobian, Operator},
+    solver::nonlinearsystem::{Broyden, BroydenUpdate, DampedNewton, NewtonKrylov},
+};
+use argmin_observer_slog::SlogLogger;
+
+/// Broyden's tridiagonal function
+///
+/// F_i(x) = (3 - 2 x_i) x_i - x_{i-1} - 2 x_{i+1} + 1 with x_0 = x_{n+1} = 0
+struct BroydenTridiagonal {}
+
+impl Operator for BroydenTridiagonal {
+    type Param = Vec<f64>;
+    type Output = Vec<f64>;
+
+    fn apply(&self, x: &Self::Param) -> Result<Self::Output, Error> {
+        let n = x.len();
+        Ok((0..n)
+            .map(|i| {
+                let prev = if i > 0 { x[i - 1] } else { 0.0 };
+                let next = if i + 1 < n { x[i + 1] } else { 0.0 };
+                (3.0 - 2.0 * x[i]) * x[i] - prev - 2.0 * next + 1.0
+            })
+            .collect())
+    }
+}
+
+impl Jacobian for BroydenTridiagonal {
+    type Param = Vec<f64>;
+    type Jacobian = Vec<Vec<f64>>;
+
+    fn jacobian(&self, x: &Self::Param) -> Result<Self::Jacobian, Error> {
+        let n = x.len();
+        let mut jacobian = vec![vec![0.0; n]; n];
+        for (i, row) in jacobian.iter_mut().enumerate() {
+            row[i] = 3.0 - 4.0 * x[i];
+            if i > 0 {
+                row[i - 1] = -1.0;
+            }
+            if i + 1 < n {
+                row[i + 1] = -2.0;
+            }
+        }
+        Ok(jacobian)
+    }
+}
+
+fn run() -> Result<(), Error> {
+    let init_param = vec![-1.0; 20];
+
+    // Newton's method with line search requires the Jacobian
+    let res = Executor::new(BroydenTridiagonal {}, DampedNewton::new())
+        .configure(|state| state.param(init_param.clone()).max_iters(50))
+        .add_observer(SlogLogger::term(), ObserverMode::Always)
+        .run()?;
+    println!("Damped Newton:\n{res}");
+
+    // Broyden's method only evaluates the residuals
+    let solver = Broyden::new().with_update(BroydenUpdate::Good);
+    let res = Executor::new(BroydenTridiagonal {}, solver)
+        .configure(|state| state.param(init_param.clone()).max_iters(100))
+        .add_observer(SlogLogger::term(), ObserverMode::Always)
+        .run()?;
+    println!("Broyden:\n{res}");
+
+    // Jacobian-free Newton-GMRES
+    let res = Executor::new(BroydenTridiagonal {}, NewtonKrylov::new())
+        .configure(|state| state.param(init_param).max_iters(50))
+        .add_observer(SlogLogger::term(), ObserverMode::Always)
+        .run()?;
+    println!("Newton-Krylov:\n{res}");
+    println!("Residuals: {:?}", res.state().get_residuals().unwrap());
+
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{e}");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,89 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::{
+    core::{observers::ObserverMode, Error, Executor, MultiObjective, State},
+    solver::nsga::{NSGAII, NSGAIII},
+};
+use argmin_observer_slog::SlogLogger;
+use std::f64::consts::FRAC_PI_2;
+
+/// ZDT1: two objectives, Pareto front `f2 = 1 - sqrt(f1)` for `x_i = 0, i > 0`
+struct Zdt1 {}
+
+impl MultiObjective for Zdt1 {
+    type Param = Vec<f64>;
+    type Float = f64;
+
+    fn objectives(&self, p: &Self::Param) -> Result<Vec<Self::Float>, Error> {
+        let g = 1.0 + 9.0 * p[1..].iter().sum::<f64>() / (p.len() - 1) as f64;
+        Ok(vec![p[0], g * (1.0 - (p[0] / g).sqrt())])
+    }
+}
+
+/// DTLZ2: three objectives, Pareto front on the unit sphere for `x_i = 0.5, i > 1`
+struct Dtlz2 {}
+
+impl MultiObjective for Dtlz2 {
+    type Param = Vec<f64>;
+    type Float = f64;
+
+    fn objectives(&self, p: &Self::Param) -> Result<Vec<Self::Float>, Error> {
+        let g: f64 = p[2..].iter().map(|x| (x - 0.5).powi(2)).sum();
+        let (a, b) = (p[0] * FRAC_PI_2, p[1] * FRAC_PI_2);
+        Ok(vec![
+            (1.0 + g) * a.cos() * b.cos(),
+            (1.0 + g) * a.cos() * b.sin(),
+            (1.0 + g) * a.sin(),
+        ])
+    }
+}
+
+fn run() -> Result<(), Error> {
+    // NSGA-II with 100 individuals on ZDT1 with 30 parameters in [0, 1]
+    let solver = NSGAII::new((vec![0.0; 30], vec![1.0; 30]), 100);
+
+    let res = Executor::new(Zdt1 {}, solver)
+        .configure(|state| state.max_iters(250))
+        .add_observer(SlogLogger::term(), ObserverMode::Every(50))
+        .run()?;
+
+    let mut front = res.state().get_pareto_objectives().unwrap().clone();
+    front.sort_by(|a, b| a[0].total_cmp(&b[0]));
+    println!("NSGA-II: {} points on the Pareto front", front.len());
+    for f in front.iter().step_by(10) {
+        println!("    f1 = {:.4}, f2 = {:.4}", f[0], f[1]);
+    }
+
+    // NSGA-III with 91 reference points (12 divisions per objective) on DTLZ2 with 12 parameters
+    let solver = NSGAIII::new((vec![0.0; 12], vec![1.0; 12]), 92, 12);
+
+    let res = Executor::new(Dtlz2 {}, solver)
+        .configure(|state| state.max_iters(300))
+        .add_observer(SlogLogger::term(), ObserverMode::Every(50))
+        .run()?;
+
+    let front = res.state().get_pareto_objectives().unwrap();
+    let max_deviation = front
+        .iter()
+        .map(|f| (f.iter().map(|fi| fi * fi).sum::<f64>().sqrt() - 1.0).abs())
+        .fold(0.0, f64::max);
+    println!(
+        "NSGA-III: {} points on the Pareto front after {} iterations, \
+         maximum distance to the unit sphere: {max_deviation:.4}",
+        front.len(),
+        res.state().get_iter()
+    );
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{e}");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,102 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::{
+    core::{observers::ObserverMode, CostFunction, Error, Executor},
+    solver::{paralleltempering::ParallelTempering, simulatedannealing::Anneal},
+};
+use argmin_observer_slog::SlogLogger;
+use argmin_testfunctions::rastrigin;
+use rand::{distributions::Uniform, prelude::*};
+use rand_xoshiro::Xoshiro256PlusPlus;
+use std::sync::{Arc, Mutex};
+
+struct Rastrigin {
+    /// lower bound
+    lower_bound: Vec<f64>,
+    /// upper bound
+    upper_bound: Vec<f64>,
+    /// Random number generator. We use a `Arc<Mutex<_>>` here because `Anneal` requires
+    /// `self` to be passed as an immutable reference. This gives us thread safe interior
+    /// mutability.
+    rng: Arc<Mutex<Xoshiro256PlusPlus>>,
+}
+
+impl Rastrigin {
+    /// Constructor
+    pub fn new(lower_bound: Vec<f64>, upper_bound: Vec<f64>) -> Self {
+        Rastrigin {
+            lower_bound,
+            upper_bound,
+            rng: Arc::new(Mutex::new(Xoshiro256PlusPlus::from_entropy())),
+        }
+    }
+}
+
+impl CostFunction for Rastrigin {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(rastrigin(param))
+    }
+}
+
+impl Anneal for Rastrigin {
+    type Param = Vec<f64>;
+    type Output = Vec<f64>;
+    type Float = f64;
+
+    /// Anneal a parameter vector
+    fn anneal(&self, param: &Vec<f64>, temp: f64) -> Result<Vec<f64>, Error> {
+        let mut param_n = param.clone();
+        let mut rng = self.rng.lock().unwrap();
+        // Hot replicas take larger steps than cold ones.
+        let step = 0.5 * temp.sqrt();
+        let idx = rng.sample(Uniform::from(0..param.len()));
+        param_n[idx] += rng.sample(Uniform::new_inclusive(-step, step));
+        param_n[idx] = param_n[idx].clamp(self.lower_bound[idx], self.upper_bound[idx]);
+        Ok(param_n)
+    }
+}
+
+fn run() -> Result<(), Error> {
+    // Define cost function
+    let operator = Rastrigin::new(vec![-5.12; 4], vec![5.12; 4]);
+
+    // Define initial parameter vector
+    let init_param: Vec<f64> = vec![3.0, -4.0, 2.0, 1.0];
+
+    // Geometric temperature ladder with 10 replicas between 0.05 and 25.6
+    let temperatures: Vec<f64> = (0..10).map(|k| 0.05 * 2.0f64.powi(k)).collect();
+
+    // Set up parallel tempering solver, which proposes swaps of neighboring replicas every
+    // 5 iterations.
+    // An alternative random number generator (RNG) can be provided to `new_with_rng`:
+    // ParallelTempering::new_with_rng(temperatures, Xoshiro256PlusPlus::from_entropy())?
+    let solver = ParallelTempering::new(temperatures)?.with_swap_interval(5)?;
+
+    /////////////////////////
+    // Run solver          //
+    /////////////////////////
+    let res = Executor::new(operator, solver)
+        .configure(|state| state.param(init_param).max_iters(10_000))
+        // Optional: Attach a observer which reports acceptance and swap rates
+        .add_observer(SlogLogger::term(), ObserverMode::Every(1000))
+        .run()?;
+
+    // Print result
+    println!("{res}");
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{e}");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::{
+    core::{observers::ObserverMode, CostFunction, Error, Executor},
+    solver::patternsearch::{HookeJeeves, MADS},
+};
+use argmin_observer_slog::SlogLogger;
+use argmin_testfunctions::rosenbrock;
+
+/// Rosenbrock function with a non-smooth penalty
+struct NonsmoothRosenbrock {}
+
+impl CostFunction for NonsmoothRosenbrock {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(rosenbrock(p, 1.0, 100.0) + p.iter().map(|x| (x - 1.0).abs()).sum::<f64>())
+    }
+}
+
+fn run() -> Result<(), Error> {
+    // Define initial parameter vector
+    let init_param: Vec<f64> = vec![-1.2, 1.0, -1.2];
+
+    // Set up Hooke-Jeeves method with bounds
+    let solver = HookeJeeves::new()
+        .with_bounds(vec![-2.0; 3], vec![2.0; 3])
+        .with_initial_mesh_size(0.5)?
+        .with_min_mesh_size(1e-8)?;
+
+    // Run solver
+    let res = Executor::new(NonsmoothRosenbrock {}, solver)
+        .configure(|state| state.param(init_param.clone()).max_iters(10_000))
+        .add_observer(SlogLogger::term(), ObserverMode::Every(100))
+        .run()?;
+
+    // Print result
+    println!("{res}");
+
+    // Set up MADS with bounds
+    let solver = MADS::new()
+        .with_bounds(vec![-2.0; 3], vec![2.0; 3])
+        .with_initial_poll_size(0.5)?;
+
+    // Run solver
+    let res = Executor::new(NonsmoothRosenbrock {}, solver)
+        .configure(|state| state.param(init_param).max_iters(10_000))
+        .add_observer(SlogLogger::term(), ObserverMode::Every(100))
+        .run()?;
+
+    // Print result
+    println!("{res}");
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{e}");
+    }
+}
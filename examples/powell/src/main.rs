@@ -0,0 +1,65 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::{
+    core::{observers::ObserverMode, CostFunction, Error, Executor},
+    solver::powell::{Powell, Praxis},
+};
+use argmin_observer_slog::SlogLogger;
+use argmin_testfunctions::rosenbrock;
+
+struct Rosenbrock {
+    a: f64,
+    b: f64,
+}
+
+impl CostFunction for Rosenbrock {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(rosenbrock(p, self.a, self.b))
+    }
+}
+
+fn run() -> Result<(), Error> {
+    // Define initial parameter vector
+    let init_param: Vec<f64> = vec![-1.2, 1.0, -1.2, 1.0];
+
+    // Set up Powell's method, resetting the directions every 8 iterations
+    let solver = Powell::new()
+        .with_tolerance(1e-12)?
+        .with_reset_interval(8)?;
+
+    // Run solver
+    let res = Executor::new(Rosenbrock { a: 1.0, b: 100.0 }, solver)
+        .configure(|state| state.param(init_param.clone()).max_iters(1000))
+        .add_observer(SlogLogger::term(), ObserverMode::Always)
+        .run()?;
+
+    // Print result
+    println!("{res}");
+
+    // Set up PRAXIS
+    let solver = Praxis::new().with_tolerance(1e-8)?.with_ktm(2);
+
+    // Run solver
+    let res = Executor::new(Rosenbrock { a: 1.0, b: 100.0 }, solver)
+        .configure(|state| state.param(init_param).max_iters(1000))
+        .add_observer(SlogLogger::term(), ObserverMode::Always)
+        .run()?;
+
+    // Print result
+    println!("{res}");
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{e}");
+    }
+}
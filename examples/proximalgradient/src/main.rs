@@ -0,0 +1,110 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::{
+    core::{observers::ObserverMode, CostFunction, Error, Executor, Gradient, ProximalOperator},
+    solver::proximalgradient::ProximalGradient,
+};
+use argmin_observer_slog::SlogLogger;
+
+/// Lasso regression: 1/2 ||A x - b||^2 + lambda ||x||_1
+struct Lasso {
+    a: Vec<Vec<f64>>,
+    b: Vec<f64>,
+    lambda: f64,
+}
+
+impl Lasso {
+    fn residual(&self, p: &[f64]) -> Vec<f64> {
+        self.a
+            .iter()
+            .zip(self.b.iter())
+            .map(|(row, b)| row.iter().zip(p.iter()).map(|(a, x)| a * x).sum::<f64>() - b)
+            .collect()
+    }
+}
+
+impl CostFunction for Lasso {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(0.5 * self.residual(p).iter().map(|r| r * r).sum::<f64>())
+    }
+}
+
+impl Gradient for Lasso {
+    type Param = Vec<f64>;
+    type Gradient = Vec<f64>;
+
+    /// A^T (A x - b)
+    fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+        let r = self.residual(p);
+        Ok((0..p.len())
+            .map(|j| self.a.iter().zip(r.iter()).map(|(row, r)| row[j] * r).sum())
+            .collect())
+    }
+}
+
+impl ProximalOperator for Lasso {
+    type Param = Vec<f64>;
+    type Float = f64;
+
+    /// Soft thresholding
+    fn prox(&self, p: &Self::Param, step: Self::Float) -> Result<Self::Param, Error> {
+        let threshold = self.lambda * step;
+        Ok(p.iter()
+            .map(|x| x.signum() * (x.abs() - threshold).max(0.0))
+            .collect())
+    }
+
+    fn nonsmooth_cost(&self, p: &Self::Param) -> Result<Self::Float, Error> {
+        Ok(self.lambda * p.iter().map(|x| x.abs()).sum::<f64>())
+    }
+}
+
+fn run() -> Result<(), Error> {
+    // Sparse vector which is to be recovered from 20 measurements
+    let x_true = [0.0, 3.0, 0.0, 0.0, -2.0, 0.0, 0.0, 0.0, 1.5, 0.0];
+    let a: Vec<Vec<f64>> = (0..20)
+        .map(|i| {
+            (0..x_true.len())
+                .map(|j| ((i * 7919 + j * 104729) % 1000) as f64 / 500.0 - 1.0)
+                .collect()
+        })
+        .collect();
+    let b = a
+        .iter()
+        .map(|row| row.iter().zip(x_true.iter()).map(|(a, x)| a * x).sum())
+        .collect();
+
+    // Define problem
+    let problem = Lasso { a, b, lambda: 0.1 };
+
+    // Define initial parameter vector
+    let init_param: Vec<f64> = vec![0.0; x_true.len()];
+
+    // Set up solver
+    let solver = ProximalGradient::new().with_tolerance(1e-10)?;
+
+    // Run solver
+    let res = Executor::new(problem, solver)
+        .configure(|state| state.param(init_param).max_iters(1000))
+        .add_observer(SlogLogger::term(), ObserverMode::Every(50))
+        .run()?;
+
+    // Print result
+    println!("{res}");
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{e}");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,95 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::{
+    core::{observers::ObserverMode, CostFunction, Error, Executor, Gradient, Hessian},
+    solver::rootfinding::{
+        Bisection, Bracket, Halley, NewtonRaphson, RegulaFalsi, RootTolerance, Secant, ITP,
+    },
+};
+use argmin_observer_slog::SlogLogger;
+
+/// f(x) = x^3 - 2x - 5
+struct Cubic {}
+
+impl CostFunction for Cubic {
+    // one dimensional problem, no vector needed
+    type Param = f64;
+    type Output = f64;
+
+    fn cost(&self, x: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(x.powi(3) - 2.0 * x - 5.0)
+    }
+}
+
+impl Gradient for Cubic {
+    type Param = f64;
+    type Gradient = f64;
+
+    fn gradient(&self, x: &Self::Param) -> Result<Self::Gradient, Error> {
+        Ok(3.0 * x.powi(2) - 2.0)
+    }
+}
+
+impl Hessian for Cubic {
+    type Param = f64;
+    type Hessian = f64;
+
+    fn hessian(&self, x: &Self::Param) -> Result<Self::Hessian, Error> {
+        Ok(6.0 * x)
+    }
+}
+
+fn run() -> Result<(), Error> {
+    let bracket = Bracket::new(0.0, 3.0)?;
+    let tol = RootTolerance::new().with_x_abs_tol(1e-12)?;
+
+    // Bracketing methods
+    let res = Executor::new(Cubic {}, Bisection::new(bracket).with_tolerance(tol))
+        .configure(|state| state.max_iters(100))
+        .add_observer(SlogLogger::term(), ObserverMode::Always)
+        .run()?;
+    println!("Result of bisection:\n{res}");
+
+    let solver = RegulaFalsi::new(bracket)
+        .with_tolerance(tol)
+        .with_illinois(true);
+    let res = Executor::new(Cubic {}, solver)
+        .configure(|state| state.max_iters(100))
+        .run()?;
+    println!("Result of Illinois:\n{res}");
+
+    let res = Executor::new(Cubic {}, ITP::new(bracket).with_tolerance(tol))
+        .configure(|state| state.max_iters(100))
+        .run()?;
+    println!("Result of ITP:\n{res}");
+
+    // Open methods require an initial guess
+    let res = Executor::new(Cubic {}, NewtonRaphson::new().with_tolerance(tol))
+        .configure(|state| state.param(2.0).max_iters(100))
+        .run()?;
+    println!("Result of Newton-Raphson:\n{res}");
+
+    let res = Executor::new(Cubic {}, Secant::new().with_tolerance(tol))
+        .configure(|state| state.param(2.0).max_iters(100))
+        .run()?;
+    println!("Result of secant method:\n{res}");
+
+    let res = Executor::new(Cubic {}, Halley::new().with_tolerance(tol))
+        .configure(|state| state.param(2.0).max_iters(100))
+        .run()?;
+    println!("Result of Halley's method:\n{res}");
+
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{e}");
+        std::process::exit(1);
+    }
+}
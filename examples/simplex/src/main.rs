@@ -0,0 +1,63 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::{
+    core::{observers::ObserverMode, Error, Executor, LinearProgram},
+    solver::simplex::Simplex,
+};
+use argmin_observer_slog::SlogLogger;
+
+/// Maximize `3 x1 + 5 x2` subject to `x1 <= 4`, `2 x2 <= 12` and `3 x1 + 2 x2 <= 18`.
+///
+/// In standard form, the objective is negated and the inequalities are turned into equalities by
+/// adding the slack variables `x3`, `x4` and `x5`.
+struct Production {}
+
+impl LinearProgram for Production {
+    type Param = Vec<f64>;
+    type Float = f64;
+
+    fn c(&self) -> Result<Vec<f64>, Error> {
+        Ok(vec![-3.0, -5.0, 0.0, 0.0, 0.0])
+    }
+
+    fn b(&self) -> Result<Vec<f64>, Error> {
+        Ok(vec![4.0, 12.0, 18.0])
+    }
+
+    fn A(&self) -> Result<Vec<Vec<f64>>, Error> {
+        Ok(vec![
+            vec![1.0, 0.0, 1.0, 0.0, 0.0],
+            vec![0.0, 2.0, 0.0, 1.0, 0.0],
+            vec![3.0, 2.0, 0.0, 0.0, 1.0],
+        ])
+    }
+}
+
+fn run() -> Result<(), Error> {
+    // Set up solver
+    let solver = Simplex::new();
+
+    // Run solver
+    let res = Executor::new(Production {}, solver)
+        .configure(|state| state.max_iters(100))
+        .add_observer(SlogLogger::term(), ObserverMode::Always)
+        .run()?;
+
+    // Print result
+    println!("{res}");
+    println!("Basis: {:?}", res.state().get_basis().unwrap());
+    println!("Dual values: {:?}", res.state().get_dual().unwrap());
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{e}");
+        std::process::exit(1);
+    }
+}
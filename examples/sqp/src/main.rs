@@ -0,0 +1,121 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::{
+    core::{
+        observers::ObserverMode, ConstraintJacobian, CostFunction, EqualityConstraints, Error,
+        Executor, Gradient, Hessian, InequalityConstraints,
+    },
+    solver::{
+        linesearch::{condition::ArmijoCondition, BacktrackingLineSearch},
+        sqp::SQP,
+    },
+};
+use argmin_observer_slog::SlogLogger;
+use std::f64::consts::PI;
+
+/// Design of a closed cylindrical tank with radius `r = x_0` and height `h = x_1`
+///
+/// The surface area is minimized for a given volume, while the height must be at least 2.5 times
+/// the radius.
+struct Tank {
+    volume: f64,
+}
+
+impl CostFunction for Tank {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    /// Surface area 2 pi r^2 + 2 pi r h
+    fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(2.0 * PI * p[0].powi(2) + 2.0 * PI * p[0] * p[1])
+    }
+}
+
+impl Gradient for Tank {
+    type Param = Vec<f64>;
+    type Gradient = Vec<f64>;
+
+    fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+        Ok(vec![4.0 * PI * p[0] + 2.0 * PI * p[1], 2.0 * PI * p[0]])
+    }
+}
+
+impl Hessian for Tank {
+    type Param = Vec<f64>;
+    type Hessian = Vec<Vec<f64>>;
+
+    fn hessian(&self, _p: &Self::Param) -> Result<Self::Hessian, Error> {
+        Ok(vec![vec![4.0 * PI, 2.0 * PI], vec![2.0 * PI, 0.0]])
+    }
+}
+
+impl EqualityConstraints for Tank {
+    type Param = Vec<f64>;
+    type Float = f64;
+
+    /// pi r^2 h = volume
+    fn equality_constraints(&self, p: &Self::Param) -> Result<Vec<f64>, Error> {
+        Ok(vec![PI * p[0].powi(2) * p[1] - self.volume])
+    }
+}
+
+impl InequalityConstraints for Tank {
+    type Param = Vec<f64>;
+    type Float = f64;
+
+    /// 2.5 r <= h
+    fn inequality_constraints(&self, p: &Self::Param) -> Result<Vec<f64>, Error> {
+        Ok(vec![2.5 * p[0] - p[1]])
+    }
+}
+
+impl ConstraintJacobian for Tank {
+    type Param = Vec<f64>;
+
+    fn constraint_jacobian(
+        &self,
+        p: &Self::Param,
+    ) -> Result<(Vec<Self::Param>, Vec<Self::Param>), Error> {
+        Ok((
+            vec![vec![2.0 * PI * p[0] * p[1], PI * p[0].powi(2)]],
+            vec![vec![2.5, -1.0]],
+        ))
+    }
+}
+
+fn run() -> Result<(), Error> {
+    // Define problem
+    let problem = Tank { volume: 1.0 };
+
+    // Define initial parameter vector
+    let init_param: Vec<f64> = vec![1.0, 1.0];
+
+    // Set up line search for the merit function
+    let linesearch = BacktrackingLineSearch::new(ArmijoCondition::new(1e-4)?);
+
+    // Set up solver
+    let solver = SQP::new(linesearch).with_tolerance(1e-8)?;
+
+    // Run solver
+    let res = Executor::new(problem, solver)
+        .configure(|state| state.param(init_param).max_iters(100))
+        .add_observer(SlogLogger::term(), ObserverMode::Always)
+        .run()?;
+
+    // Print result
+    println!("{res}");
+    println!("Multipliers: {:?}", res.state.get_multipliers().unwrap());
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{e}");
+        std::process::exit(1);
+    }
+}
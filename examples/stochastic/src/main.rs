@@ -0,0 +1,117 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::{
+    core::{
+        checkpointing::CheckpointingFrequency, observers::ObserverMode, Error, Executor,
+        MinibatchGradient,
+    },
+    solver::stochastic::{Adam, CosineAnnealing},
+};
+use argmin_checkpointing_file::FileCheckpoint;
+use argmin_observer_slog::SlogLogger;
+
+/// Logistic regression: mean of log(1 + exp(-y_i a_i^T x)) over all samples
+struct LogisticRegression {
+    a: Vec<[f64; 3]>,
+    y: Vec<f64>,
+}
+
+impl MinibatchGradient for LogisticRegression {
+    type Param = Vec<f64>;
+    type Gradient = Vec<f64>;
+
+    fn num_samples(&self) -> usize {
+        self.y.len()
+    }
+
+    fn minibatch_gradient(
+        &self,
+        p: &Self::Param,
+        indices: &[usize],
+    ) -> Result<Self::Gradient, Error> {
+        let mut grad = vec![0.0; p.len()];
+        for &i in indices {
+            let margin = self.y[i]
+                * self.a[i]
+                    .iter()
+                    .zip(p.iter())
+                    .map(|(a, x)| a * x)
+                    .sum::<f64>();
+            let factor = -self.y[i] / (1.0 + margin.exp()) / indices.len() as f64;
+            for (g, a) in grad.iter_mut().zip(self.a[i].iter()) {
+                *g += factor * a;
+            }
+        }
+        Ok(grad)
+    }
+}
+
+fn run() -> Result<(), Error> {
+    // Two overlapping point clouds, labeled by the sign of 2 u - v + 0.5 with some label noise
+    let n = 500;
+    let a: Vec<[f64; 3]> = (0..n)
+        .map(|i| {
+            let u = ((i * 7919) % 1000) as f64 / 500.0 - 1.0;
+            let v = ((i * 104729) % 1000) as f64 / 500.0 - 1.0;
+            [1.0, u, v]
+        })
+        .collect();
+    let y = a
+        .iter()
+        .enumerate()
+        .map(|(i, a)| {
+            let label = if 0.5 + 2.0 * a[1] - a[2] > 0.0 {
+                1.0
+            } else {
+                -1.0
+            };
+            if i % 17 == 0 {
+                -label
+            } else {
+                label
+            }
+        })
+        .collect();
+
+    // Define problem
+    let problem = LogisticRegression { a, y };
+
+    // Define initial parameter vector
+    let init_param: Vec<f64> = vec![0.0; 3];
+
+    // Set up solver: AdamW with a cosine annealed learning rate
+    let solver = Adam::new(CosineAnnealing::new(0.1, 0.001, 3000)?)
+        .with_batch_size(32)?
+        .with_weight_decay(1e-4)?;
+
+    // Save a checkpoint every 500 iterations. If the run is interrupted, starting the program again
+    // resumes from the last checkpoint.
+    let checkpoint = FileCheckpoint::new(
+        ".checkpoints",
+        "logistic_regression",
+        CheckpointingFrequency::Every(500),
+    );
+
+    // Run solver
+    let res = Executor::new(problem, solver)
+        .configure(|state| state.param(init_param).max_iters(3000))
+        .checkpointing(checkpoint)
+        .add_observer(SlogLogger::term(), ObserverMode::Every(250))
+        .run()?;
+
+    // Print result
+    println!("{res}");
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{e}");
+        std::process::exit(1);
+    }
+}
@@ -8,7 +8,7 @@
 #[allow(unused_imports)]
 use argmin::{
     core::{observers::ObserverMode, CostFunction, Error, Executor, Gradient, Hessian},
-    solver::trustregion::{CauchyPoint, Dogleg, Steihaug, TrustRegion},
+    solver::trustregion::{CauchyPoint, Dogleg, MoreSorensen, Steihaug, TrustRegion},
 };
 use argmin_observer_slog::SlogLogger;
 use argmin_testfunctions::{rosenbrock, rosenbrock_derivative, rosenbrock_hessian};
@@ -68,6 +68,7 @@ fn run() -> Result<(), Error> {
     let subproblem = Steihaug::new().with_max_iters(2);
     // let subproblem = CauchyPoint::new();
     // let subproblem = Dogleg::new();
+    // let subproblem = MoreSorensen::new();
 
     // Set up solver
     let solver = TrustRegion::new(subproblem);